@@ -97,6 +97,8 @@
 //! - `tess_ctrl`
 //! - `tess_eval`
 //! - `compute`
+//! - `task`
+//! - `mesh`
 //! - `raygen`
 //! - `anyhit`
 //! - `closesthit`
@@ -426,6 +428,8 @@ impl Parse for MacroInput {
                         "tess_ctrl" => ShaderKind::TessControl,
                         "tess_eval" => ShaderKind::TessEvaluation,
                         "compute" => ShaderKind::Compute,
+                        "task" => ShaderKind::Task,
+                        "mesh" => ShaderKind::Mesh,
                         "raygen" => ShaderKind::RayGeneration,
                         "anyhit" => ShaderKind::AnyHit,
                         "closesthit" => ShaderKind::ClosestHit,
@@ -435,8 +439,8 @@ impl Parse for MacroInput {
                         ty => bail!(
                             lit,
                             "expected `vertex`, `fragment`, `geometry`, `tess_ctrl`, `tess_eval`, \
-                            `compute`, `raygen`, `anyhit`, `closesthit`, `miss`, `intersection` or \
-                            `callable`, found `{ty}`",
+                            `compute`, `task`, `mesh`, `raygen`, `anyhit`, `closesthit`, `miss`, \
+                            `intersection` or `callable`, found `{ty}`",
                         ),
                     });
                 }
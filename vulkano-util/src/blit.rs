@@ -0,0 +1,749 @@
+// Copyright (c) 2024 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Displaying the contents of an image on another image, typically a swapchain image.
+//!
+//! Applications that only use compute shaders still need to get their result on the screen. This
+//! module provides two ways to scale an image onto a target image, without writing any shaders or
+//! pipelines:
+//!
+//! - [`blit_fullscreen`] records a blit, and needs no graphics pipeline at all.
+//! - [`FullscreenPass`] draws a fullscreen triangle that samples the image. It works with targets
+//!   that don't support the [`TRANSFER_DST`] usage, and can sRGB-encode the color values in the
+//!   shader, for targets with a UNORM format.
+//!
+//! With [`blit_fullscreen`], the copy is done with a blit, which converts between the formats of
+//! the two images. In
+//! particular, if the target has an sRGB format and the source has a linear format, the values are
+//! sRGB-encoded as they are written, so a compute shader can write linear color values and let
+//! the swapchain format decide whether sRGB conversion happens. If the source already contains
+//! sRGB-encoded values, give it a format with the same encoding as the target, so that they are
+//! copied unchanged.
+//!
+//! The target image must have been created with the [`TRANSFER_DST`] usage, and the source image
+//! with the [`TRANSFER_SRC`] usage. [`VulkanoWindowRenderer`] enables `TRANSFER_DST` on its
+//! swapchain images when the surface supports it.
+//!
+//! [`FullscreenPass`] instead needs the [`SAMPLED`] usage on the source image and the
+//! [`COLOR_ATTACHMENT`] usage on the target image, which swapchain images always have. Sampling
+//! and writing the target convert between the formats in the same way as a blit, unless
+//! `encode_srgb` is given to [`FullscreenPass::new`].
+//!
+//! [`TRANSFER_DST`]: vulkano::image::ImageUsage::TRANSFER_DST
+//! [`TRANSFER_SRC`]: vulkano::image::ImageUsage::TRANSFER_SRC
+//! [`SAMPLED`]: vulkano::image::ImageUsage::SAMPLED
+//! [`COLOR_ATTACHMENT`]: vulkano::image::ImageUsage::COLOR_ATTACHMENT
+//! [`VulkanoWindowRenderer`]: crate::renderer::VulkanoWindowRenderer
+
+use std::sync::Arc;
+use vulkano::{
+    command_buffer::{
+        allocator::CommandBufferAllocator, AutoCommandBufferBuilder, BlitImageInfo,
+        ClearColorImageInfo, RenderPassBeginInfo, SubpassBeginInfo, SubpassContents,
+        SubpassEndInfo,
+    },
+    descriptor_set::{
+        allocator::StandardDescriptorSetAllocator, PersistentDescriptorSet, WriteDescriptorSet,
+    },
+    device::Device,
+    format::{ClearColorValue, ClearValue, Format, FormatFeatures},
+    image::{
+        sampler::{Filter, Sampler, SamplerAddressMode, SamplerCreateInfo},
+        view::ImageView,
+        Image,
+    },
+    pipeline::{
+        graphics::{
+            color_blend::{ColorBlendAttachmentState, ColorBlendState},
+            input_assembly::InputAssemblyState,
+            multisample::MultisampleState,
+            rasterization::RasterizationState,
+            vertex_input::VertexInputState,
+            viewport::{Viewport, ViewportState},
+            GraphicsPipelineCreateInfo,
+        },
+        layout::PipelineDescriptorSetLayoutCreateInfo,
+        DynamicState, GraphicsPipeline, Pipeline, PipelineBindPoint, PipelineLayout,
+        PipelineShaderStageCreateInfo,
+    },
+    render_pass::{Framebuffer, FramebufferCreateInfo, RenderPass, Subpass},
+    shader::{ShaderModule, ShaderModuleCreateInfo},
+    Validated, ValidationError, VulkanError,
+};
+
+/// Parameters for [`blit_fullscreen`] and [`FullscreenPass::draw`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FullscreenBlitInfo {
+    /// How the source image is fitted into the target image.
+    ///
+    /// The default value is [`BlitScaling::Letterbox`].
+    pub scaling: BlitScaling,
+
+    /// The filter to use when scaling the source image.
+    ///
+    /// If the format of the source image doesn't support linear filtering, [`Filter::Nearest`]
+    /// is used instead of [`Filter::Linear`].
+    ///
+    /// The default value is [`Filter::Linear`].
+    pub filter: Filter,
+
+    /// The color that the parts of the target image not covered by the source image are cleared
+    /// to. Only used with [`BlitScaling::Letterbox`].
+    ///
+    /// The default value is opaque black.
+    pub clear_color: [f32; 4],
+}
+
+impl Default for FullscreenBlitInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            scaling: BlitScaling::Letterbox,
+            filter: Filter::Linear,
+            clear_color: [0.0, 0.0, 0.0, 1.0],
+        }
+    }
+}
+
+/// How the source image of [`blit_fullscreen`] and [`FullscreenPass::draw`] is fitted into the
+/// target image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum BlitScaling {
+    /// The source image is stretched to cover the whole target image, ignoring its aspect ratio.
+    Stretch,
+
+    /// The source image is scaled as large as possible while keeping its aspect ratio, and is
+    /// centered in the target image. The remaining space is cleared to
+    /// [`FullscreenBlitInfo::clear_color`].
+    Letterbox,
+}
+
+/// Records the commands to display the first mip level and array layer of `source` on `target`.
+///
+/// `target` is usually a swapchain image, in which case this should be the last use of `target`
+/// before it is presented.
+pub fn blit_fullscreen<L, A>(
+    builder: &mut AutoCommandBufferBuilder<L, A>,
+    source: Arc<Image>,
+    target: Arc<Image>,
+    blit_info: &FullscreenBlitInfo,
+) -> Result<(), Box<ValidationError>>
+where
+    A: CommandBufferAllocator,
+{
+    let &FullscreenBlitInfo {
+        scaling,
+        filter,
+        clear_color,
+    } = blit_info;
+
+    let filter = supported_filter(filter, source.format_features());
+
+    let [src_width, src_height, _] = source.extent();
+    let [dst_width, dst_height, _] = target.extent();
+
+    let dst_offsets = match scaling {
+        BlitScaling::Stretch => [[0, 0, 0], [dst_width, dst_height, 1]],
+        BlitScaling::Letterbox => {
+            let [width, height] =
+                letterbox_extent([src_width, src_height], [dst_width, dst_height]);
+            let x = (dst_width - width) / 2;
+            let y = (dst_height - height) / 2;
+
+            if [width, height] != [dst_width, dst_height] {
+                builder.clear_color_image(ClearColorImageInfo {
+                    clear_value: ClearColorValue::Float(clear_color),
+                    ..ClearColorImageInfo::image(target.clone())
+                })?;
+            }
+
+            [[x, y, 0], [x + width, y + height, 1]]
+        }
+    };
+
+    let mut blit_image_info = BlitImageInfo {
+        filter,
+        ..BlitImageInfo::images(source, target)
+    };
+    let region = &mut blit_image_info.regions[0];
+    region.src_subresource.array_layers = 0..1;
+    region.dst_subresource.array_layers = 0..1;
+    region.src_offsets = [[0, 0, 0], [src_width, src_height, 1]];
+    region.dst_offsets = dst_offsets;
+
+    builder.blit_image(blit_image_info)?;
+
+    Ok(())
+}
+
+/// A graphics pass that displays an image on a target image by drawing a fullscreen triangle.
+///
+/// Unlike [`blit_fullscreen`], the source image is sampled in a fragment shader, and the target
+/// image is written as a color attachment. The pass is created for one target format, and can
+/// then be used with any target image view of that format.
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use vulkano::{
+/// #     command_buffer::{allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder,
+/// #         CommandBufferUsage},
+/// #     device::Queue,
+/// #     format::Format,
+/// #     image::view::ImageView,
+/// # };
+/// # use vulkano_util::blit::{FullscreenBlitInfo, FullscreenPass};
+/// # let command_buffer_allocator: StandardCommandBufferAllocator = todo!();
+/// # let queue: Arc<Queue> = todo!();
+/// # let source: Arc<ImageView> = todo!();
+/// # let swapchain_image_view: Arc<ImageView> = todo!();
+/// // The swapchain has a UNORM format, so let the shader encode the linear values of the source.
+/// let pass = FullscreenPass::new(queue.device().clone(), Format::B8G8R8A8_UNORM, true).unwrap();
+///
+/// let mut builder = AutoCommandBufferBuilder::primary(
+///     &command_buffer_allocator,
+///     queue.queue_family_index(),
+///     CommandBufferUsage::OneTimeSubmit,
+/// )
+/// .unwrap();
+/// pass.draw(
+///     &mut builder,
+///     source,
+///     swapchain_image_view,
+///     &FullscreenBlitInfo::default(),
+/// )
+/// .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct FullscreenPass {
+    render_pass: Arc<RenderPass>,
+    pipeline: Arc<GraphicsPipeline>,
+    linear_sampler: Arc<Sampler>,
+    nearest_sampler: Arc<Sampler>,
+    descriptor_set_allocator: StandardDescriptorSetAllocator,
+}
+
+impl FullscreenPass {
+    /// Creates a pass that draws to target images of `target_format`.
+    ///
+    /// If `encode_srgb` is true, the fragment shader sRGB-encodes the color values sampled from
+    /// the source image. Use this to display linear color values on a target with a UNORM format.
+    /// Targets with an SRGB format are encoded when they are written, so `encode_srgb` should be
+    /// false for them.
+    pub fn new(
+        device: Arc<Device>,
+        target_format: Format,
+        encode_srgb: bool,
+    ) -> Result<Self, Validated<VulkanError>> {
+        let render_pass = vulkano::single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                color: {
+                    format: target_format,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: Store,
+                },
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {},
+            },
+        )?;
+
+        let vertex_shader = unsafe {
+            ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&VERTEX_SHADER))
+        }?
+        .entry_point("main")
+        .unwrap();
+        let fragment_shader = unsafe {
+            ShaderModule::new(
+                device.clone(),
+                ShaderModuleCreateInfo::new(&FRAGMENT_SHADER),
+            )
+        }?
+        .specialize([(0, encode_srgb.into())].into_iter().collect())?
+        .entry_point("main")
+        .unwrap();
+
+        let stages = [
+            PipelineShaderStageCreateInfo::new(vertex_shader),
+            PipelineShaderStageCreateInfo::new(fragment_shader),
+        ];
+        let layout = PipelineLayout::new(
+            device.clone(),
+            PipelineDescriptorSetLayoutCreateInfo::from_stages(&stages)
+                .into_pipeline_layout_create_info(device.clone())
+                .map_err(|err| err.error)?,
+        )?;
+        let pipeline = GraphicsPipeline::new(
+            device.clone(),
+            None,
+            GraphicsPipelineCreateInfo {
+                stages: stages.into_iter().collect(),
+                vertex_input_state: Some(VertexInputState::new()),
+                input_assembly_state: Some(InputAssemblyState::default()),
+                viewport_state: Some(ViewportState::default()),
+                rasterization_state: Some(RasterizationState::default()),
+                multisample_state: Some(MultisampleState::default()),
+                color_blend_state: Some(ColorBlendState::with_attachment_states(
+                    1,
+                    ColorBlendAttachmentState::default(),
+                )),
+                dynamic_state: [DynamicState::Viewport].into_iter().collect(),
+                subpass: Some(Subpass::from(render_pass.clone(), 0).unwrap().into()),
+                ..GraphicsPipelineCreateInfo::layout(layout)
+            },
+        )?;
+
+        let sampler = |filter| {
+            Sampler::new(
+                device.clone(),
+                SamplerCreateInfo {
+                    mag_filter: filter,
+                    min_filter: filter,
+                    address_mode: [SamplerAddressMode::ClampToEdge; 3],
+                    ..Default::default()
+                },
+            )
+        };
+        let linear_sampler = sampler(Filter::Linear)?;
+        let nearest_sampler = sampler(Filter::Nearest)?;
+
+        Ok(FullscreenPass {
+            render_pass,
+            pipeline,
+            linear_sampler,
+            nearest_sampler,
+            descriptor_set_allocator: StandardDescriptorSetAllocator::new(
+                device,
+                Default::default(),
+            ),
+        })
+    }
+
+    /// Returns the render pass that the pass draws in.
+    #[inline]
+    pub fn render_pass(&self) -> &Arc<RenderPass> {
+        &self.render_pass
+    }
+
+    /// Records the commands to draw the first mip level and array layer of `source` on
+    /// `target`.
+    ///
+    /// `target` must have the format that the pass was created with. It is usually a view of a
+    /// swapchain image, in which case this should be the last use of the image before it is
+    /// presented.
+    pub fn draw<L, A>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L, A>,
+        source: Arc<ImageView>,
+        target: Arc<ImageView>,
+        blit_info: &FullscreenBlitInfo,
+    ) -> Result<(), Validated<VulkanError>>
+    where
+        A: CommandBufferAllocator,
+    {
+        let &FullscreenBlitInfo {
+            scaling,
+            filter,
+            clear_color,
+        } = blit_info;
+
+        let sampler = match supported_filter(filter, source.format_features()) {
+            Filter::Linear => &self.linear_sampler,
+            _ => &self.nearest_sampler,
+        };
+
+        let [src_width, src_height, _] = source.image().extent();
+        let [dst_width, dst_height, _] = target.image().extent();
+
+        let viewport = match scaling {
+            BlitScaling::Stretch => Viewport {
+                offset: [0.0, 0.0],
+                extent: [dst_width as f32, dst_height as f32],
+                depth_range: 0.0..=1.0,
+            },
+            BlitScaling::Letterbox => {
+                let [width, height] =
+                    letterbox_extent([src_width, src_height], [dst_width, dst_height]);
+
+                Viewport {
+                    offset: [
+                        ((dst_width - width) / 2) as f32,
+                        ((dst_height - height) / 2) as f32,
+                    ],
+                    extent: [width as f32, height as f32],
+                    depth_range: 0.0..=1.0,
+                }
+            }
+        };
+
+        let framebuffer = Framebuffer::new(
+            self.render_pass.clone(),
+            FramebufferCreateInfo {
+                attachments: vec![target],
+                ..Default::default()
+            },
+        )?;
+        let descriptor_set = PersistentDescriptorSet::new(
+            &self.descriptor_set_allocator,
+            self.pipeline.layout().set_layouts()[0].clone(),
+            [WriteDescriptorSet::image_view_sampler(
+                0,
+                source,
+                sampler.clone(),
+            )],
+            [],
+        )?;
+
+        builder
+            .begin_render_pass(
+                RenderPassBeginInfo {
+                    clear_values: vec![Some(ClearValue::Float(clear_color))],
+                    ..RenderPassBeginInfo::framebuffer(framebuffer)
+                },
+                SubpassBeginInfo {
+                    contents: SubpassContents::Inline,
+                    ..Default::default()
+                },
+            )?
+            .set_viewport(0, [viewport].into_iter().collect())?
+            .bind_pipeline_graphics(self.pipeline.clone())?
+            .bind_descriptor_sets(
+                PipelineBindPoint::Graphics,
+                self.pipeline.layout().clone(),
+                0,
+                descriptor_set,
+            )?
+            .draw(3, 1, 0, 0)?
+            .end_render_pass(SubpassEndInfo::default())?;
+
+        Ok(())
+    }
+}
+
+/// Returns `filter`, or [`Filter::Nearest`] if it is [`Filter::Linear`] and linear filtering isn't
+/// supported by `format_features`.
+fn supported_filter(filter: Filter, format_features: FormatFeatures) -> Filter {
+    if filter == Filter::Linear
+        && !format_features.intersects(FormatFeatures::SAMPLED_IMAGE_FILTER_LINEAR)
+    {
+        Filter::Nearest
+    } else {
+        filter
+    }
+}
+
+/// Returns the largest extent with the aspect ratio of `src` that fits in `dst`.
+fn letterbox_extent(src: [u32; 2], dst: [u32; 2]) -> [u32; 2] {
+    let [src_width, src_height] = src.map(u64::from);
+    let [dst_width, dst_height] = dst.map(u64::from);
+
+    if src_width == 0 || src_height == 0 {
+        return dst;
+    }
+
+    if src_width * dst_height > dst_width * src_height {
+        // Source is wider than the target; fill the width.
+        let height = (dst_width * src_height / src_width).max(1);
+        [dst[0], height as u32]
+    } else {
+        let width = (dst_height * src_width / src_height).max(1);
+        [width as u32, dst[1]]
+    }
+}
+
+/*
+    #version 450
+
+    layout(location = 0) out vec2 tex_coords;
+
+    void main() {
+        tex_coords = vec2((gl_VertexIndex << 1) & 2, gl_VertexIndex & 2);
+        gl_Position = vec4(tex_coords * 2.0 - 1.0, 0.0, 1.0);
+    }
+*/
+const VERTEX_SHADER: [u32; 173] = [
+    119734787, 65536, 0, 33, 0, 131089, 1, 196622, 0, 1, 524303, 0, 1, 1852399981, 0, 9, 11, 13,
+    262215, 9, 11, 42, 262215, 11, 30, 0, 262215, 13, 11, 0, 131091, 2, 196641, 3, 2, 262165, 4,
+    32, 1, 196630, 5, 32, 262167, 6, 5, 2, 262167, 7, 5, 4, 262176, 8, 1, 4, 262203, 8, 9, 1,
+    262176, 10, 3, 6, 262203, 10, 11, 3, 262176, 12, 3, 7, 262203, 12, 13, 3, 262187, 4, 14, 1,
+    262187, 4, 15, 2, 262187, 5, 16, 0, 262187, 5, 17, 1065353216, 262187, 5, 18, 1073741824,
+    327724, 6, 19, 17, 17, 327734, 2, 1, 0, 3, 131320, 20, 262205, 4, 21, 9, 327876, 4, 22, 21, 14,
+    327879, 4, 23, 22, 15, 327879, 4, 24, 21, 15, 262255, 5, 25, 23, 262255, 5, 26, 24, 327760, 6,
+    27, 25, 26, 196670, 11, 27, 327822, 6, 28, 27, 18, 327811, 6, 29, 28, 19, 327761, 5, 30, 29, 0,
+    327761, 5, 31, 29, 1, 458832, 7, 32, 30, 31, 16, 17, 196670, 13, 32, 65789, 65592,
+];
+
+/*
+    #version 450
+
+    layout(constant_id = 0) const bool ENCODE_SRGB = false;
+
+    layout(location = 0) in vec2 tex_coords;
+
+    layout(location = 0) out vec4 f_color;
+
+    layout(set = 0, binding = 0) uniform sampler2D tex;
+
+    void main() {
+        vec4 color = texture(tex, tex_coords);
+
+        if (ENCODE_SRGB) {
+            vec3 linear = clamp(color.rgb, 0.0, 1.0);
+            color.rgb = mix(
+                1.055 * pow(linear, vec3(1.0 / 2.4)) - 0.055,
+                linear * 12.92,
+                lessThanEqual(linear, vec3(0.0031308))
+            );
+        }
+
+        f_color = color;
+    }
+*/
+const FRAGMENT_SHADER: [u32; 278] = [
+    119734787, 65536, 0, 49, 0, 131089, 1, 393227, 1, 1280527431, 1685353262, 808793134, 0, 196622,
+    0, 1, 458767, 4, 2, 1852399981, 0, 16, 18, 196624, 2, 7, 262215, 14, 34, 0, 262215, 14, 33, 0,
+    262215, 16, 30, 0, 262215, 18, 30, 0, 262215, 19, 1, 0, 131091, 3, 196641, 4, 3, 196630, 5, 32,
+    262167, 6, 5, 2, 262167, 7, 5, 3, 262167, 8, 5, 4, 131092, 9, 262167, 10, 9, 3, 589849, 11, 5,
+    1, 0, 0, 0, 1, 0, 196635, 12, 11, 262176, 13, 0, 12, 262203, 13, 14, 0, 262176, 15, 1, 6,
+    262203, 15, 16, 1, 262176, 17, 3, 8, 262203, 17, 18, 3, 196657, 9, 19, 262187, 5, 20, 0,
+    262187, 5, 21, 1065353216, 262187, 5, 22, 1054168405, 262187, 5, 23, 1065814589, 262187, 5, 24,
+    1029785518, 262187, 5, 25, 1095678034, 262187, 5, 26, 994913820, 393260, 7, 27, 20, 20, 20,
+    393260, 7, 28, 21, 21, 21, 393260, 7, 29, 22, 22, 22, 393260, 7, 30, 23, 23, 23, 393260, 7, 31,
+    24, 24, 24, 393260, 7, 32, 26, 26, 26, 327734, 3, 2, 0, 4, 131320, 33, 262205, 12, 34, 14,
+    262205, 6, 35, 16, 327767, 8, 36, 34, 35, 524367, 7, 37, 36, 36, 0, 1, 2, 196855, 39, 0,
+    262394, 19, 38, 39, 131320, 38, 524300, 7, 40, 1, 43, 37, 27, 28, 458764, 7, 41, 1, 26, 40, 29,
+    327813, 7, 42, 41, 30, 327811, 7, 43, 42, 31, 327822, 7, 44, 40, 25, 327868, 10, 45, 40, 32,
+    393385, 7, 46, 45, 44, 43, 131321, 39, 131320, 39, 458997, 7, 47, 46, 38, 37, 33, 589903, 8,
+    48, 47, 36, 0, 1, 2, 6, 196670, 18, 48, 65789, 65592,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        blit_fullscreen, letterbox_extent, BlitScaling, FullscreenBlitInfo, FullscreenPass,
+    };
+    use std::sync::Arc;
+    use vulkano::{
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, trace::TracedCommand,
+            AutoCommandBufferBuilder, CommandBufferUsage,
+        },
+        device::{Device, DeviceCreateInfo, QueueCreateInfo},
+        format::Format,
+        image::{view::ImageView, Image, ImageCreateInfo, ImageUsage},
+        instance::Instance,
+        library::{
+            null::{recorded_commands, NullLoader},
+            VulkanLibrary,
+        },
+        memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+    };
+
+    #[test]
+    fn letterbox() {
+        assert_eq!(letterbox_extent([200, 100], [400, 400]), [400, 200]);
+        assert_eq!(letterbox_extent([100, 200], [400, 400]), [200, 400]);
+        assert_eq!(letterbox_extent([100, 100], [400, 300]), [300, 300]);
+        assert_eq!(letterbox_extent([0, 100], [400, 300]), [400, 300]);
+    }
+
+    #[test]
+    fn blit_letterbox() {
+        let library = VulkanLibrary::with_loader(NullLoader::new()).unwrap();
+        let instance = Instance::new(library, Default::default()).unwrap();
+        let physical_device = instance
+            .enumerate_physical_devices()
+            .unwrap()
+            .next()
+            .unwrap();
+        let (device, mut queues) = Device::new(
+            physical_device,
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo::default()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let queue = queues.next().unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let image = |extent, usage| {
+            Image::new(
+                memory_allocator.clone(),
+                ImageCreateInfo {
+                    format: Format::B8G8R8A8_UNORM,
+                    extent,
+                    usage,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap()
+        };
+        let source = image([640, 480, 1], ImageUsage::TRANSFER_SRC);
+        let target = image([1920, 1080, 1], ImageUsage::TRANSFER_DST);
+
+        let command_buffer_allocator =
+            StandardCommandBufferAllocator::new(device, Default::default());
+
+        for (scaling, expected_commands, expected_dst_offsets) in [
+            (
+                BlitScaling::Letterbox,
+                &["vkCmdClearColorImage", "vkCmdBlitImage"][..],
+                [[240, 0, 0], [1680, 1080, 1]],
+            ),
+            (
+                BlitScaling::Stretch,
+                &["vkCmdBlitImage"][..],
+                [[0, 0, 0], [1920, 1080, 1]],
+            ),
+        ] {
+            let mut builder = AutoCommandBufferBuilder::primary(
+                &command_buffer_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap();
+            builder.start_trace();
+            blit_fullscreen(
+                &mut builder,
+                source.clone(),
+                target.clone(),
+                &FullscreenBlitInfo {
+                    scaling,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let trace = builder.take_trace().unwrap();
+            let command_buffer = builder.build().unwrap();
+
+            let commands: Vec<_> = recorded_commands(&*command_buffer)
+                .into_iter()
+                .filter(|&command| command != "vkCmdPipelineBarrier")
+                .collect();
+            assert_eq!(commands, expected_commands);
+
+            match trace.commands.last() {
+                Some(TracedCommand::BlitImage { regions, .. }) => {
+                    assert_eq!(regions.len(), 1);
+                    assert_eq!(regions[0].src_offsets, [[0, 0, 0], [640, 480, 1]]);
+                    assert_eq!(regions[0].dst_offsets, expected_dst_offsets);
+                }
+                _ => panic!(),
+            }
+        }
+    }
+
+    #[test]
+    fn fullscreen_pass() {
+        let library = VulkanLibrary::with_loader(NullLoader::new()).unwrap();
+        let instance = Instance::new(library, Default::default()).unwrap();
+        let physical_device = instance
+            .enumerate_physical_devices()
+            .unwrap()
+            .next()
+            .unwrap();
+        let (device, mut queues) = Device::new(
+            physical_device,
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo::default()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let queue = queues.next().unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let image_view = |format, extent, usage| {
+            let image = Image::new(
+                memory_allocator.clone(),
+                ImageCreateInfo {
+                    format,
+                    extent,
+                    usage,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap();
+
+            ImageView::new_default(image).unwrap()
+        };
+        let source = image_view(
+            Format::R16G16B16A16_SFLOAT,
+            [640, 480, 1],
+            ImageUsage::SAMPLED,
+        );
+        let target = image_view(
+            Format::B8G8R8A8_UNORM,
+            [1920, 1080, 1],
+            ImageUsage::COLOR_ATTACHMENT,
+        );
+
+        let command_buffer_allocator =
+            StandardCommandBufferAllocator::new(device.clone(), Default::default());
+
+        for encode_srgb in [false, true] {
+            let pass =
+                FullscreenPass::new(device.clone(), Format::B8G8R8A8_UNORM, encode_srgb).unwrap();
+
+            for scaling in [BlitScaling::Stretch, BlitScaling::Letterbox] {
+                let mut builder = AutoCommandBufferBuilder::primary(
+                    &command_buffer_allocator,
+                    queue.queue_family_index(),
+                    CommandBufferUsage::OneTimeSubmit,
+                )
+                .unwrap();
+                pass.draw(
+                    &mut builder,
+                    source.clone(),
+                    target.clone(),
+                    &FullscreenBlitInfo {
+                        scaling,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+                let command_buffer = builder.build().unwrap();
+
+                let commands: Vec<_> = recorded_commands(&*command_buffer)
+                    .into_iter()
+                    .filter(|&command| command != "vkCmdPipelineBarrier")
+                    .collect();
+                assert_eq!(
+                    commands,
+                    [
+                        "vkCmdBeginRenderPass",
+                        "vkCmdSetViewport",
+                        "vkCmdBindPipeline",
+                        "vkCmdBindDescriptorSets",
+                        "vkCmdDraw",
+                        "vkCmdEndRenderPass",
+                    ],
+                );
+            }
+        }
+
+        // The target must have the format of the pass.
+        let pass = FullscreenPass::new(device, Format::R8G8B8A8_UNORM, false).unwrap();
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &command_buffer_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        assert!(pass
+            .draw(&mut builder, source, target, &Default::default())
+            .is_err());
+    }
+}
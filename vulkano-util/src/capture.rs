@@ -0,0 +1,470 @@
+// Copyright (c) 2024 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Reading back the contents of an image, for screenshots and image comparison tests.
+//!
+//! [`capture_image`] copies the first mip level and array layer of an image to a host-visible
+//! buffer, and returns a [`PendingCapture`] which can be waited on to get the pixels as tightly
+//! packed RGBA8 data. The image can be any image that was created with the [`TRANSFER_SRC`]
+//! usage, including swapchain images and attachments. [`VulkanoWindowRenderer`] enables
+//! `TRANSFER_SRC` on its swapchain images when the surface supports it.
+//!
+//! Layout transitions and synchronization with other uses of the image are handled by the
+//! automatic synchronization of the command buffer. To capture a swapchain image, the image must
+//! have been acquired, and the acquire future should be passed as the `after` future.
+//!
+//! ```no_run
+//! # use std::sync::Arc;
+//! # use vulkano::{
+//! #     command_buffer::allocator::StandardCommandBufferAllocator,
+//! #     device::Queue,
+//! #     image::Image,
+//! #     memory::allocator::StandardMemoryAllocator,
+//! #     sync,
+//! # };
+//! # use vulkano_util::capture::capture_image;
+//! # let memory_allocator: Arc<StandardMemoryAllocator> = todo!();
+//! # let command_buffer_allocator: StandardCommandBufferAllocator = todo!();
+//! # let queue: Arc<Queue> = todo!();
+//! # let image: Arc<Image> = todo!();
+//! let capture = capture_image(
+//!     memory_allocator,
+//!     &command_buffer_allocator,
+//!     queue.clone(),
+//!     image,
+//!     sync::now(queue.device().clone()),
+//! )
+//! .unwrap();
+//!
+//! let captured = capture.wait(None).unwrap();
+//! assert_eq!(captured.data.len(), (captured.extent[0] * captured.extent[1] * 4) as usize);
+//! ```
+//!
+//! [`TRANSFER_SRC`]: vulkano::image::ImageUsage::TRANSFER_SRC
+//! [`VulkanoWindowRenderer`]: crate::renderer::VulkanoWindowRenderer
+
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+    time::Duration,
+};
+use vulkano::{
+    buffer::{AllocateBufferError, Buffer, BufferCreateInfo, BufferUsage, Subbuffer},
+    command_buffer::{
+        allocator::CommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferExecError,
+        CommandBufferExecFuture, CommandBufferUsage, CopyImageToBufferInfo,
+        PrimaryAutoCommandBuffer,
+    },
+    device::Queue,
+    format::Format,
+    half::f16,
+    image::Image,
+    memory::allocator::{AllocationCreateInfo, MemoryAllocator, MemoryTypeFilter},
+    sync::{
+        future::{FenceSignalFuture, GpuFuture},
+        HostAccessError,
+    },
+    DeviceSize, Validated, ValidationError, VulkanError,
+};
+
+/// Records and submits the commands to copy `image` to host-visible memory.
+///
+/// The copy is executed on `queue` after `after` has completed. Only the first mip level and
+/// array layer of `image` is captured, and for 3D images only the first depth slice.
+///
+/// The following formats are supported:
+/// - `R8G8B8A8_UNORM`, `R8G8B8A8_SRGB`, `B8G8R8A8_UNORM` and `B8G8R8A8_SRGB`, which are copied
+///   unchanged apart from reordering the components.
+/// - `R8_UNORM`, which is expanded to opaque grayscale.
+/// - `R16G16B16A16_SFLOAT` and `R32G32B32A32_SFLOAT`, which are clamped to the `[0, 1]` range and
+///   quantized. No color space conversion is done.
+pub fn capture_image<A, F>(
+    memory_allocator: Arc<dyn MemoryAllocator>,
+    command_buffer_allocator: &A,
+    queue: Arc<Queue>,
+    image: Arc<Image>,
+    after: F,
+) -> Result<PendingCapture<F>, CaptureError>
+where
+    A: CommandBufferAllocator + 'static,
+    F: GpuFuture,
+{
+    let format = image.format();
+
+    if !is_supported_format(format) {
+        return Err(CaptureError::UnsupportedFormat(format));
+    }
+
+    let [width, height, _] = image.extent();
+    let buffer = Buffer::new_slice::<u8>(
+        memory_allocator,
+        BufferCreateInfo {
+            usage: BufferUsage::TRANSFER_DST,
+            ..Default::default()
+        },
+        AllocationCreateInfo {
+            memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+            ..Default::default()
+        },
+        width as DeviceSize * height as DeviceSize * format.block_size(),
+    )
+    .map_err(CaptureError::AllocateBuffer)?;
+
+    let command_buffer = build_capture_command_buffer(
+        command_buffer_allocator,
+        queue.queue_family_index(),
+        image,
+        buffer.clone(),
+    )?;
+    let future = after
+        .then_execute(queue, command_buffer)
+        .map_err(CaptureError::Execute)?
+        .then_signal_fence_and_flush()
+        .map_err(CaptureError::Submit)?;
+
+    Ok(PendingCapture {
+        future,
+        buffer,
+        format,
+        extent: [width, height],
+    })
+}
+
+fn build_capture_command_buffer<A>(
+    command_buffer_allocator: &A,
+    queue_family_index: u32,
+    image: Arc<Image>,
+    buffer: Subbuffer<[u8]>,
+) -> Result<Arc<PrimaryAutoCommandBuffer<A>>, CaptureError>
+where
+    A: CommandBufferAllocator,
+{
+    let [width, height, _] = image.extent();
+    let mut builder = AutoCommandBufferBuilder::primary(
+        command_buffer_allocator,
+        queue_family_index,
+        CommandBufferUsage::OneTimeSubmit,
+    )
+    .map_err(CaptureError::CreateCommandBuffer)?;
+
+    let mut copy_image_to_buffer_info = CopyImageToBufferInfo::image_buffer(image, buffer);
+    let region = &mut copy_image_to_buffer_info.regions[0];
+    region.image_subresource.array_layers = 0..1;
+    region.image_extent = [width, height, 1];
+
+    builder
+        .copy_image_to_buffer(copy_image_to_buffer_info)
+        .map_err(CaptureError::RecordCommands)?;
+
+    builder.build().map_err(CaptureError::CreateCommandBuffer)
+}
+
+/// A capture that has been submitted to the GPU, returned by [`capture_image`].
+#[must_use = "the captured data can only be retrieved through the `PendingCapture`"]
+pub struct PendingCapture<F>
+where
+    F: GpuFuture,
+{
+    future: FenceSignalFuture<CommandBufferExecFuture<F>>,
+    buffer: Subbuffer<[u8]>,
+    format: Format,
+    extent: [u32; 2],
+}
+
+impl<F> PendingCapture<F>
+where
+    F: GpuFuture,
+{
+    /// Returns whether the GPU has finished copying the image.
+    #[inline]
+    pub fn is_ready(&self) -> Result<bool, VulkanError> {
+        self.future.is_signaled()
+    }
+
+    /// Waits until the GPU has finished copying the image, and returns the captured pixels.
+    ///
+    /// If `timeout` is `Some` and the copy doesn't finish within it, [`VulkanError::Timeout`] is
+    /// returned, and `wait` can be called again later.
+    pub fn wait(&self, timeout: Option<Duration>) -> Result<CapturedImage, CaptureError> {
+        self.future.wait(timeout).map_err(CaptureError::Submit)?;
+
+        let data = self.buffer.read().map_err(CaptureError::ReadBuffer)?;
+
+        Ok(CapturedImage {
+            extent: self.extent,
+            data: convert_to_rgba8(self.format, &data),
+        })
+    }
+}
+
+/// The pixels of a captured image.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapturedImage {
+    /// The width and height of the image.
+    pub extent: [u32; 2],
+
+    /// The pixels in row-major order, with four bytes per pixel in RGBA order and no padding
+    /// between rows.
+    pub data: Vec<u8>,
+}
+
+/// Error that can happen when capturing an image.
+#[derive(Clone, Debug)]
+pub enum CaptureError {
+    /// The format of the image can't be converted to RGBA8.
+    UnsupportedFormat(Format),
+
+    /// Allocating the buffer to copy the image to failed.
+    AllocateBuffer(Validated<AllocateBufferError>),
+
+    /// Allocating or building the command buffer failed.
+    CreateCommandBuffer(Validated<VulkanError>),
+
+    /// Recording the copy command failed.
+    RecordCommands(Box<ValidationError>),
+
+    /// Executing the command buffer failed.
+    Execute(CommandBufferExecError),
+
+    /// Submitting the command buffer or waiting for it to complete failed.
+    Submit(Validated<VulkanError>),
+
+    /// Reading the captured data from the buffer failed.
+    ReadBuffer(HostAccessError),
+}
+
+impl Error for CaptureError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::UnsupportedFormat(_) => None,
+            Self::AllocateBuffer(err) => Some(err),
+            Self::CreateCommandBuffer(err) => Some(err),
+            Self::RecordCommands(err) => Some(err),
+            Self::Execute(err) => Some(err),
+            Self::Submit(err) => Some(err),
+            Self::ReadBuffer(err) => Some(err),
+        }
+    }
+}
+
+impl Display for CaptureError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::UnsupportedFormat(format) => {
+                write!(
+                    f,
+                    "capturing images of format {:?} is not supported",
+                    format
+                )
+            }
+            Self::AllocateBuffer(_) => write!(f, "allocating the capture buffer failed"),
+            Self::CreateCommandBuffer(_) => write!(f, "creating the command buffer failed"),
+            Self::RecordCommands(_) => write!(f, "recording the copy command failed"),
+            Self::Execute(_) => write!(f, "executing the command buffer failed"),
+            Self::Submit(_) => write!(f, "submitting the command buffer failed"),
+            Self::ReadBuffer(_) => write!(f, "reading the capture buffer failed"),
+        }
+    }
+}
+
+fn is_supported_format(format: Format) -> bool {
+    matches!(
+        format,
+        Format::R8G8B8A8_UNORM
+            | Format::R8G8B8A8_SRGB
+            | Format::B8G8R8A8_UNORM
+            | Format::B8G8R8A8_SRGB
+            | Format::R8_UNORM
+            | Format::R16G16B16A16_SFLOAT
+            | Format::R32G32B32A32_SFLOAT
+    )
+}
+
+fn convert_to_rgba8(format: Format, data: &[u8]) -> Vec<u8> {
+    fn quantize(value: f32) -> u8 {
+        (value.clamp(0.0, 1.0) * 255.0).round() as u8
+    }
+
+    match format {
+        Format::R8G8B8A8_UNORM | Format::R8G8B8A8_SRGB => data.to_vec(),
+        Format::B8G8R8A8_UNORM | Format::B8G8R8A8_SRGB => data
+            .chunks_exact(4)
+            .flat_map(|bgra| [bgra[2], bgra[1], bgra[0], bgra[3]])
+            .collect(),
+        Format::R8_UNORM => data.iter().flat_map(|&r| [r, r, r, u8::MAX]).collect(),
+        Format::R16G16B16A16_SFLOAT => data
+            .chunks_exact(2)
+            .map(|bytes| quantize(f16::from_ne_bytes([bytes[0], bytes[1]]).to_f32()))
+            .collect(),
+        Format::R32G32B32A32_SFLOAT => data
+            .chunks_exact(4)
+            .map(|bytes| quantize(f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])))
+            .collect(),
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_capture_command_buffer, capture_image, convert_to_rgba8, CaptureError};
+    use std::sync::Arc;
+    use vulkano::{
+        buffer::{Buffer, BufferCreateInfo, BufferUsage},
+        command_buffer::allocator::StandardCommandBufferAllocator,
+        device::{Device, DeviceCreateInfo, QueueCreateInfo},
+        format::Format,
+        half::f16,
+        image::{Image, ImageCreateInfo, ImageUsage},
+        instance::Instance,
+        library::{
+            null::{recorded_commands, NullLoader},
+            VulkanLibrary,
+        },
+        memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+        sync,
+    };
+
+    #[test]
+    fn convert_rgba8() {
+        let data = [1, 2, 3, 4, 5, 6, 7, 8];
+
+        assert_eq!(convert_to_rgba8(Format::R8G8B8A8_UNORM, &data), data);
+        assert_eq!(convert_to_rgba8(Format::R8G8B8A8_SRGB, &data), data);
+    }
+
+    #[test]
+    fn convert_bgra8() {
+        let data = [1, 2, 3, 4, 5, 6, 7, 8];
+        let expected = [3, 2, 1, 4, 7, 6, 5, 8];
+
+        assert_eq!(convert_to_rgba8(Format::B8G8R8A8_UNORM, &data), expected);
+        assert_eq!(convert_to_rgba8(Format::B8G8R8A8_SRGB, &data), expected);
+    }
+
+    #[test]
+    fn convert_r8() {
+        assert_eq!(
+            convert_to_rgba8(Format::R8_UNORM, &[0, 128]),
+            [0, 0, 0, 255, 128, 128, 128, 255],
+        );
+    }
+
+    #[test]
+    fn convert_rgba16_sfloat() {
+        let data: Vec<u8> = [0.0, 0.5, 1.0, 2.0, -1.0, 0.25, 0.75, 1.0]
+            .into_iter()
+            .flat_map(|value| f16::from_f32(value).to_ne_bytes())
+            .collect();
+
+        // Values are clamped to [0, 1] before quantizing.
+        assert_eq!(
+            convert_to_rgba8(Format::R16G16B16A16_SFLOAT, &data),
+            [0, 128, 255, 255, 0, 64, 191, 255],
+        );
+    }
+
+    #[test]
+    fn convert_rgba32_sfloat() {
+        let data: Vec<u8> = [0.0f32, 0.5, 1.0, 2.0, -1.0, 0.25, 0.75, 1.0]
+            .into_iter()
+            .flat_map(f32::to_ne_bytes)
+            .collect();
+
+        assert_eq!(
+            convert_to_rgba8(Format::R32G32B32A32_SFLOAT, &data),
+            [0, 128, 255, 255, 0, 64, 191, 255],
+        );
+    }
+
+    #[test]
+    fn capture() {
+        let library = VulkanLibrary::with_loader(NullLoader::new()).unwrap();
+        let instance = Instance::new(library, Default::default()).unwrap();
+        let physical_device = instance
+            .enumerate_physical_devices()
+            .unwrap()
+            .next()
+            .unwrap();
+        let (device, mut queues) = Device::new(
+            physical_device,
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo::default()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let queue = queues.next().unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let image = |format| {
+            Image::new(
+                memory_allocator.clone(),
+                ImageCreateInfo {
+                    format,
+                    extent: [4, 2, 1],
+                    usage: ImageUsage::TRANSFER_SRC,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap()
+        };
+        let command_buffer_allocator =
+            StandardCommandBufferAllocator::new(device.clone(), Default::default());
+
+        let buffer = Buffer::new_slice::<u8>(
+            memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+            4 * 2 * 4,
+        )
+        .unwrap();
+        let command_buffer = build_capture_command_buffer(
+            &command_buffer_allocator,
+            queue.queue_family_index(),
+            image(Format::B8G8R8A8_UNORM),
+            buffer,
+        )
+        .unwrap();
+        let commands: Vec<_> = recorded_commands(&*command_buffer)
+            .into_iter()
+            .filter(|&command| command != "vkCmdPipelineBarrier")
+            .collect();
+        assert_eq!(commands, ["vkCmdCopyImageToBuffer"]);
+
+        let captured = capture_image(
+            memory_allocator.clone(),
+            &command_buffer_allocator,
+            queue.clone(),
+            image(Format::R16G16B16A16_SFLOAT),
+            sync::now(device.clone()),
+        )
+        .unwrap()
+        .wait(None)
+        .unwrap();
+        assert_eq!(captured.extent, [4, 2]);
+        assert_eq!(captured.data.len(), 4 * 2 * 4);
+
+        assert!(matches!(
+            capture_image(
+                memory_allocator.clone(),
+                &command_buffer_allocator,
+                queue,
+                image(Format::R8G8_UNORM),
+                sync::now(device),
+            ),
+            Err(CaptureError::UnsupportedFormat(Format::R8G8_UNORM)),
+        ));
+    }
+}
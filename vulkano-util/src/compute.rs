@@ -0,0 +1,309 @@
+// Copyright (c) 2024 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Splitting long-running compute dispatches across several submissions.
+//!
+//! Operating systems reset the GPU if a single submission runs for too long, for example after
+//! two seconds with the default TDR settings on Windows. [`dispatch_split`] partitions a large
+//! dispatch into chunks of a bounded number of workgroups, and submits each chunk in its own
+//! command buffer, waiting on a fence for each one so that progress can be reported.
+//!
+//! Each chunk is a box of workgroups, given as a base workgroup and a number of workgroups. The
+//! chunks are recorded by a closure, which typically binds the pipeline and descriptor sets and
+//! then calls [`dispatch_base`], or [`dispatch`] with the base passed to the shader in a push
+//! constant if the pipeline wasn't created with [`PipelineCreateFlags::DISPATCH_BASE`].
+//!
+//! ```no_run
+//! # use std::sync::Arc;
+//! # use vulkano::{
+//! #     command_buffer::allocator::StandardCommandBufferAllocator,
+//! #     device::Queue,
+//! #     pipeline::{
+//! #         compute::ComputePipelineCreateInfo, ComputePipeline, PipelineCreateFlags,
+//! #         PipelineLayout, PipelineShaderStageCreateInfo,
+//! #     },
+//! # };
+//! # use vulkano_util::compute::dispatch_split;
+//! # let command_buffer_allocator: StandardCommandBufferAllocator = todo!();
+//! # let queue: Arc<Queue> = todo!();
+//! # let stage: PipelineShaderStageCreateInfo = todo!();
+//! # let layout: Arc<PipelineLayout> = todo!();
+//! // `dispatch_base` requires a pipeline that was created with this flag.
+//! let pipeline = ComputePipeline::new(
+//!     queue.device().clone(),
+//!     None,
+//!     ComputePipelineCreateInfo {
+//!         flags: PipelineCreateFlags::DISPATCH_BASE,
+//!         ..ComputePipelineCreateInfo::stage_layout(stage, layout)
+//!     },
+//! )
+//! .unwrap();
+//!
+//! dispatch_split(
+//!     &command_buffer_allocator,
+//!     queue,
+//!     [4096, 4096, 1],
+//!     1024 * 1024,
+//!     |builder, base_group, group_counts| {
+//!         builder
+//!             .bind_pipeline_compute(pipeline.clone())?
+//!             .dispatch_base(base_group, group_counts)?;
+//!
+//!         Ok(())
+//!     },
+//!     |groups_done, groups_total| println!("{}/{} workgroups", groups_done, groups_total),
+//! )
+//! .unwrap();
+//! ```
+//!
+//! [`dispatch_base`]: vulkano::command_buffer::AutoCommandBufferBuilder::dispatch_base
+//! [`dispatch`]: vulkano::command_buffer::AutoCommandBufferBuilder::dispatch
+//! [`PipelineCreateFlags::DISPATCH_BASE`]: vulkano::pipeline::PipelineCreateFlags::DISPATCH_BASE
+
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+};
+use vulkano::{
+    command_buffer::{
+        allocator::CommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferExecError,
+        CommandBufferUsage, PrimaryAutoCommandBuffer,
+    },
+    device::Queue,
+    sync::{self, future::GpuFuture},
+    Validated, ValidationError, VulkanError,
+};
+
+/// Records and submits a dispatch of `group_counts` workgroups, split into chunks of at most
+/// `max_groups_per_submission` workgroups each.
+///
+/// For every chunk, `record` is called with a new command buffer builder, the base workgroup of
+/// the chunk and the number of workgroups in the chunk. The command buffer is then submitted to
+/// `queue`. At most two chunks are in flight at the same time, and after each chunk completes,
+/// `progress` is called with the number of workgroups that have completed so far and the total
+/// number of workgroups.
+///
+/// The function returns once all chunks have completed.
+///
+/// # Panics
+///
+/// - Panics if `max_groups_per_submission` is zero.
+pub fn dispatch_split<A, R, P>(
+    command_buffer_allocator: &A,
+    queue: Arc<Queue>,
+    group_counts: [u32; 3],
+    max_groups_per_submission: u32,
+    mut record: R,
+    mut progress: P,
+) -> Result<(), SplitDispatchError>
+where
+    A: CommandBufferAllocator + 'static,
+    R: FnMut(
+        &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer<A>, A>,
+        [u32; 3],
+        [u32; 3],
+    ) -> Result<(), Box<ValidationError>>,
+    P: FnMut(u64, u64),
+{
+    let groups_total = group_counts.iter().map(|&count| count as u64).product();
+    let mut groups_done = 0;
+    let mut in_flight = None;
+
+    for (base_group, chunk_group_counts) in split_dispatch(group_counts, max_groups_per_submission)
+    {
+        let mut builder = AutoCommandBufferBuilder::primary(
+            command_buffer_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .map_err(SplitDispatchError::CreateCommandBuffer)?;
+        record(&mut builder, base_group, chunk_group_counts)
+            .map_err(SplitDispatchError::RecordCommands)?;
+        let command_buffer = builder
+            .build()
+            .map_err(SplitDispatchError::CreateCommandBuffer)?;
+
+        let future = sync::now(queue.device().clone())
+            .then_execute(queue.clone(), command_buffer)
+            .map_err(SplitDispatchError::Execute)?
+            .then_signal_fence_and_flush()
+            .map_err(SplitDispatchError::Submit)?;
+        let chunk_groups: u64 = chunk_group_counts
+            .iter()
+            .map(|&count| count as u64)
+            .product();
+
+        if let Some((previous_future, previous_groups)) = in_flight.replace((future, chunk_groups))
+        {
+            previous_future
+                .wait(None)
+                .map_err(SplitDispatchError::Submit)?;
+            groups_done += previous_groups;
+            progress(groups_done, groups_total);
+        }
+    }
+
+    if let Some((future, chunk_groups)) = in_flight {
+        future.wait(None).map_err(SplitDispatchError::Submit)?;
+        groups_done += chunk_groups;
+        progress(groups_done, groups_total);
+    }
+
+    Ok(())
+}
+
+/// Partitions a dispatch of `group_counts` workgroups into boxes of at most
+/// `max_groups_per_chunk` workgroups each, returning the base workgroup and the number of
+/// workgroups of each box.
+///
+/// The dispatch is split along the Z axis first, so that each chunk covers whole XY planes when
+/// possible, then along the Y axis, and only then along the X axis.
+///
+/// # Panics
+///
+/// - Panics if `max_groups_per_chunk` is zero.
+pub fn split_dispatch(
+    group_counts: [u32; 3],
+    max_groups_per_chunk: u32,
+) -> impl Iterator<Item = ([u32; 3], [u32; 3])> {
+    assert!(max_groups_per_chunk != 0);
+
+    let [x, y, z] = group_counts;
+    let max = max_groups_per_chunk as u64;
+    let plane = x as u64 * y as u64;
+    let mut chunks = Vec::new();
+
+    if x == 0 || y == 0 || z == 0 {
+        // Nothing to dispatch.
+    } else if plane <= max {
+        let slices_per_chunk = (max / plane).min(z as u64) as u32;
+
+        for base_z in (0..z).step_by(slices_per_chunk as usize) {
+            chunks.push(([0, 0, base_z], [x, y, slices_per_chunk.min(z - base_z)]));
+        }
+    } else if x as u64 <= max {
+        let rows_per_chunk = (max / x as u64) as u32;
+
+        for base_z in 0..z {
+            for base_y in (0..y).step_by(rows_per_chunk as usize) {
+                chunks.push(([0, base_y, base_z], [x, rows_per_chunk.min(y - base_y), 1]));
+            }
+        }
+    } else {
+        for base_z in 0..z {
+            for base_y in 0..y {
+                for base_x in (0..x).step_by(max_groups_per_chunk as usize) {
+                    chunks.push((
+                        [base_x, base_y, base_z],
+                        [max_groups_per_chunk.min(x - base_x), 1, 1],
+                    ));
+                }
+            }
+        }
+    }
+
+    chunks.into_iter()
+}
+
+/// Error that can happen when executing a split dispatch.
+#[derive(Clone, Debug)]
+pub enum SplitDispatchError {
+    /// Allocating or building a command buffer failed.
+    CreateCommandBuffer(Validated<VulkanError>),
+
+    /// Recording the commands of a chunk failed.
+    RecordCommands(Box<ValidationError>),
+
+    /// Executing a command buffer failed.
+    Execute(CommandBufferExecError),
+
+    /// Submitting a command buffer or waiting for it to complete failed.
+    Submit(Validated<VulkanError>),
+}
+
+impl Error for SplitDispatchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::CreateCommandBuffer(err) => Some(err),
+            Self::RecordCommands(err) => Some(err),
+            Self::Execute(err) => Some(err),
+            Self::Submit(err) => Some(err),
+        }
+    }
+}
+
+impl Display for SplitDispatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::CreateCommandBuffer(_) => write!(f, "creating a command buffer failed"),
+            Self::RecordCommands(_) => write!(f, "recording the commands of a chunk failed"),
+            Self::Execute(_) => write!(f, "executing a command buffer failed"),
+            Self::Submit(_) => write!(f, "submitting a command buffer failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_dispatch;
+
+    #[test]
+    fn split_exact() {
+        // Two XY planes of 4 workgroups fit exactly in each chunk.
+        let chunks: Vec<_> = split_dispatch([2, 2, 4], 8).collect();
+        assert_eq!(chunks, [([0, 0, 0], [2, 2, 2]), ([0, 0, 2], [2, 2, 2])]);
+    }
+
+    #[test]
+    fn split_remainder() {
+        // Along Z, the last chunk gets the remaining plane.
+        let chunks: Vec<_> = split_dispatch([2, 2, 3], 8).collect();
+        assert_eq!(chunks, [([0, 0, 0], [2, 2, 2]), ([0, 0, 2], [2, 2, 1])]);
+
+        // A plane doesn't fit, so rows are split, with the last chunk of each plane shorter.
+        let chunks: Vec<_> = split_dispatch([4, 3, 2], 8).collect();
+        assert_eq!(
+            chunks,
+            [
+                ([0, 0, 0], [4, 2, 1]),
+                ([0, 2, 0], [4, 1, 1]),
+                ([0, 0, 1], [4, 2, 1]),
+                ([0, 2, 1], [4, 1, 1]),
+            ],
+        );
+
+        // A row doesn't fit, so rows are split along X.
+        let chunks: Vec<_> = split_dispatch([5, 1, 1], 2).collect();
+        assert_eq!(
+            chunks,
+            [
+                ([0, 0, 0], [2, 1, 1]),
+                ([2, 0, 0], [2, 1, 1]),
+                ([4, 0, 0], [1, 1, 1]),
+            ],
+        );
+    }
+
+    #[test]
+    fn split_oversize_limit() {
+        // A limit above the total number of workgroups gives a single chunk.
+        let chunks: Vec<_> = split_dispatch([3, 5, 7], u32::MAX).collect();
+        assert_eq!(chunks, [([0, 0, 0], [3, 5, 7])]);
+
+        // An empty dispatch gives no chunks.
+        assert_eq!(split_dispatch([0, 5, 7], 8).count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_zero_limit() {
+        let _ = split_dispatch([1, 1, 1], 0);
+    }
+}
@@ -7,13 +7,21 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use std::sync::Arc;
+use crate::queues::{QueueAssignment, QueueAssignmentError, QueueRequest};
+use std::{
+    env,
+    error::Error,
+    ffi::OsString,
+    fmt::{Display, Error as FmtError, Formatter},
+    mem,
+    sync::Arc,
+};
 #[cfg(target_os = "macos")]
 use vulkano::instance::InstanceCreateFlags;
 use vulkano::{
     device::{
         physical::{PhysicalDevice, PhysicalDeviceType},
-        Device, DeviceCreateInfo, DeviceExtensions, Features, Queue, QueueCreateInfo, QueueFlags,
+        Device, DeviceCreateInfo, DeviceExtensions, Features, Queue, QueueFlags,
     },
     instance::{
         debug::{DebugUtilsMessenger, DebugUtilsMessengerCreateInfo},
@@ -40,10 +48,36 @@ pub struct VulkanoConfig {
     /// Pass priority order function for your physical device selection. See default for example.
     pub device_priority_fn: Arc<dyn Fn(&PhysicalDevice) -> u32>,
 
+    /// The extensions to enable on the device. Physical devices that don't support all of them
+    /// are skipped during device selection, in addition to `device_filter_fn`.
     pub device_extensions: DeviceExtensions,
 
+    /// The features to enable on the device. Physical devices that don't support all of them are
+    /// skipped during device selection, in addition to `device_filter_fn`.
+    ///
+    /// For example, a compute application that relies on double-precision floats in its shaders
+    /// should enable [`shader_float64`](Features::shader_float64), so that only devices that
+    /// support it are considered, and shaders using the `Float64` SPIR-V capability can be loaded.
     pub device_features: Features,
 
+    /// Pins the physical device to use, instead of picking one with `device_priority_fn`. The
+    /// selected device must still support `device_extensions` and `device_features`, and be
+    /// accepted by `device_filter_fn`.
+    ///
+    /// The default value is `None`. Use [`VulkanoConfig::from_env`] to read the value from the
+    /// environment instead, so that the device can be chosen on multi-GPU machines without
+    /// changing the code.
+    pub device_selector: Option<DeviceSelector>,
+
+    /// Whether the compute and transfer queues can be additional queues of the same queue family
+    /// as the graphics queue.
+    ///
+    /// If `false`, the compute and transfer queues are only separate from the graphics queue if
+    /// they come from a different queue family. Otherwise, they are the graphics queue itself.
+    ///
+    /// The default value is `false`.
+    pub separate_queues: bool,
+
     /// Print your selected device name at start.
     pub print_device_name: bool,
 }
@@ -80,13 +114,167 @@ impl Default for VulkanoConfig {
                 PhysicalDeviceType::Other => 5,
                 _ => 6,
             }),
+            separate_queues: false,
             print_device_name: false,
             device_extensions,
             device_features: Features::empty(),
+            device_selector: None,
+        }
+    }
+}
+
+impl VulkanoConfig {
+    /// Returns the default configuration, with
+    /// [`device_selector`](VulkanoConfig::device_selector) read from the environment with
+    /// [`DeviceSelector::from_env`].
+    ///
+    /// Returns an error if the environment contains a value that can't be parsed.
+    pub fn from_env() -> Result<Self, DeviceSelectorEnvError> {
+        Ok(VulkanoConfig {
+            device_selector: DeviceSelector::from_env()?,
+            ..Default::default()
+        })
+    }
+
+    /// Returns whether `physical_device` supports `device_extensions` and `device_features`, and
+    /// is accepted by `device_filter_fn`.
+    fn accepts_device(&self, physical_device: &PhysicalDevice) -> bool {
+        physical_device
+            .supported_extensions()
+            .contains(&self.device_extensions)
+            && physical_device
+                .supported_features()
+                .contains(&self.device_features)
+            && (self.device_filter_fn)(physical_device)
+    }
+}
+
+/// Selects a specific physical device, out of the devices enumerated by the instance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DeviceSelector {
+    /// The physical device at this index, in the order returned by
+    /// [`Instance::enumerate_physical_devices`].
+    Index(usize),
+
+    /// The physical device whose [`device_uuid`] property is equal to this value.
+    ///
+    /// [`device_uuid`]: vulkano::device::Properties::device_uuid
+    Uuid([u8; 16]),
+
+    /// The physical device whose [`device_luid`] property is equal to this value.
+    ///
+    /// [`device_luid`]: vulkano::device::Properties::device_luid
+    Luid([u8; 8]),
+}
+
+impl DeviceSelector {
+    /// The environment variable read by [`from_env`](Self::from_env) for a device UUID.
+    pub const UUID_ENV_VAR: &'static str = "VULKANO_DEVICE_UUID";
+
+    /// The environment variable read by [`from_env`](Self::from_env) for a device index.
+    pub const INDEX_ENV_VAR: &'static str = "VULKANO_DEVICE_INDEX";
+
+    /// Reads a device selector from the environment.
+    ///
+    /// If `VULKANO_DEVICE_UUID` is set, it is parsed as 32 hexadecimal digits, optionally
+    /// separated by dashes, as printed by `vulkaninfo`. Otherwise, if `VULKANO_DEVICE_INDEX` is
+    /// set, it is parsed as a device index. Returns `None` if neither variable is set, and an
+    /// error if the value of the variable can't be parsed.
+    pub fn from_env() -> Result<Option<Self>, DeviceSelectorEnvError> {
+        Self::from_vars(|name| env::var_os(name))
+    }
+
+    fn from_vars(
+        var: impl Fn(&str) -> Option<OsString>,
+    ) -> Result<Option<Self>, DeviceSelectorEnvError> {
+        if let Some(value) = var(Self::UUID_ENV_VAR) {
+            return match value.to_str().and_then(parse_uuid) {
+                Some(uuid) => Ok(Some(DeviceSelector::Uuid(uuid))),
+                None => Err(DeviceSelectorEnvError::InvalidUuid(value)),
+            };
+        }
+
+        if let Some(value) = var(Self::INDEX_ENV_VAR) {
+            return match value.to_str().and_then(parse_index) {
+                Some(index) => Ok(Some(DeviceSelector::Index(index))),
+                None => Err(DeviceSelectorEnvError::InvalidIndex(value)),
+            };
+        }
+
+        Ok(None)
+    }
+
+    /// Returns whether `physical_device`, which was enumerated at `index`, is the device selected
+    /// by `self`.
+    pub fn matches(&self, index: usize, physical_device: &PhysicalDevice) -> bool {
+        let properties = physical_device.properties();
+
+        match *self {
+            DeviceSelector::Index(selected) => index == selected,
+            DeviceSelector::Uuid(uuid) => properties.device_uuid == Some(uuid),
+            DeviceSelector::Luid(luid) => {
+                properties.device_luid_valid == Some(true) && properties.device_luid == Some(luid)
+            }
+        }
+    }
+}
+
+/// Error that can happen when reading a [`DeviceSelector`] from the environment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeviceSelectorEnvError {
+    /// The value of [`DeviceSelector::UUID_ENV_VAR`] is not a valid UUID.
+    InvalidUuid(OsString),
+
+    /// The value of [`DeviceSelector::INDEX_ENV_VAR`] is not a valid device index.
+    InvalidIndex(OsString),
+}
+
+impl Error for DeviceSelectorEnvError {}
+
+impl Display for DeviceSelectorEnvError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::InvalidUuid(value) => write!(
+                f,
+                "the value {:?} of {} is not a valid device UUID",
+                value,
+                DeviceSelector::UUID_ENV_VAR,
+            ),
+            Self::InvalidIndex(value) => write!(
+                f,
+                "the value {:?} of {} is not a valid device index",
+                value,
+                DeviceSelector::INDEX_ENV_VAR,
+            ),
         }
     }
 }
 
+fn parse_index(value: &str) -> Option<usize> {
+    value.trim().parse().ok()
+}
+
+fn parse_uuid(value: &str) -> Option<[u8; 16]> {
+    let digits: Vec<u8> = value
+        .trim()
+        .chars()
+        .filter(|&c| c != '-')
+        .map(|c| c.to_digit(16).map(|digit| digit as u8))
+        .collect::<Option<_>>()?;
+
+    if digits.len() != 32 {
+        return None;
+    }
+
+    let mut uuid = [0; 16];
+
+    for (byte, pair) in uuid.iter_mut().zip(digits.chunks_exact(2)) {
+        *byte = pair[0] << 4 | pair[1];
+    }
+
+    Some(uuid)
+}
+
 /// A utility struct to create, access and hold alive Vulkano device, instance and queues.
 ///
 /// Vulkano context is used in the creation of your graphics or compute pipelines, images and
@@ -109,6 +297,7 @@ pub struct VulkanoContext {
     device: Arc<Device>,
     graphics_queue: Arc<Queue>,
     compute_queue: Arc<Queue>,
+    transfer_queue: Arc<Queue>,
     memory_allocator: Arc<StandardMemoryAllocator>,
 }
 
@@ -155,8 +344,8 @@ impl VulkanoContext {
             .union(&config.instance_create_info.enabled_extensions);
 
         // Create instance
-        let instance =
-            Instance::new(library, config.instance_create_info).expect("failed to create instance");
+        let instance = Instance::new(library, mem::take(&mut config.instance_create_info))
+            .expect("failed to create instance");
 
         // Create debug callback
         let _debug_utils_messenger = config.debug_create_info.take().map(|dbg_create_info| {
@@ -164,13 +353,34 @@ impl VulkanoContext {
                 .expect("failed to create debug callback")
         });
 
-        // Get prioritized device
-        let physical_device = instance
+        let mut physical_devices = instance
             .enumerate_physical_devices()
             .expect("failed to enumerate physical devices")
-            .filter(|p| (config.device_filter_fn)(p))
-            .min_by_key(|p| (config.device_priority_fn)(p))
-            .expect("failed to create physical device");
+            .enumerate()
+            .filter(|(_, p)| config.accepts_device(p));
+
+        let physical_device = if let Some(selector) = config.device_selector {
+            // Get the selected device
+            physical_devices
+                .find(|(i, p)| selector.matches(*i, p))
+                .map(|(_, p)| p)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "the selected physical device {:?} doesn't exist, doesn't support the \
+                        required extensions and features, or is rejected by `device_filter_fn`",
+                        selector,
+                    )
+                })
+        } else {
+            // Get prioritized device
+            physical_devices
+                .map(|(_, p)| p)
+                .min_by_key(|p| (config.device_priority_fn)(p))
+                .expect(
+                    "failed to find a physical device that supports the required extensions and \
+                    features",
+                )
+        };
         // Print used device
         if config.print_device_name {
             println!(
@@ -181,10 +391,11 @@ impl VulkanoContext {
         }
 
         // Create device
-        let (device, graphics_queue, compute_queue) = Self::create_device(
+        let (device, graphics_queue, compute_queue, transfer_queue) = Self::create_device(
             physical_device,
             config.device_extensions,
             config.device_features,
+            config.separate_queues,
         );
 
         let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
@@ -195,74 +406,59 @@ impl VulkanoContext {
             device,
             graphics_queue,
             compute_queue,
+            transfer_queue,
             memory_allocator,
         }
     }
 
-    /// Creates vulkano device with required queue families and required extensions. Creates a
-    /// separate queue for compute if possible. If not, same queue as graphics is used.
+    /// Creates vulkano device with required queue families and required extensions. Creates
+    /// separate queues for compute and transfers if possible, preferring dedicated queue families.
+    /// If not, the same queue as graphics is used. Queues of the graphics queue family are only
+    /// used for compute and transfers if `separate_queues` is true.
     fn create_device(
         physical_device: Arc<PhysicalDevice>,
         device_extensions: DeviceExtensions,
         features: Features,
-    ) -> (Arc<Device>, Arc<Queue>, Arc<Queue>) {
-        let queue_family_graphics = physical_device
-            .queue_family_properties()
-            .iter()
-            .enumerate()
-            .map(|(i, q)| (i as u32, q))
-            .find(|(_i, q)| q.queue_flags.intersects(QueueFlags::GRAPHICS))
-            .map(|(i, _)| i)
-            .expect("could not find a queue that supports graphics");
-        // Try finding a separate queue for compute
-        let queue_family_compute = physical_device
-            .queue_family_properties()
-            .iter()
-            .enumerate()
-            .map(|(i, q)| (i as u32, q))
-            .find(|(i, q)| {
-                q.queue_flags.intersects(QueueFlags::COMPUTE) && *i != queue_family_graphics
-            })
-            .map(|(i, _)| i);
-        let is_separate_compute_queue = queue_family_compute.is_some();
-
-        let queue_create_infos = if let Some(queue_family_compute) = queue_family_compute {
-            vec![
-                QueueCreateInfo {
-                    queue_family_index: queue_family_graphics,
-                    ..Default::default()
+        separate_queues: bool,
+    ) -> (Arc<Device>, Arc<Queue>, Arc<Queue>, Arc<Queue>) {
+        let queue_assignment = QueueAssignment::new(
+            &physical_device,
+            &[
+                QueueRequest::new(QueueFlags::GRAPHICS),
+                QueueRequest {
+                    avoid_flags: QueueFlags::GRAPHICS,
+                    share_queue: !separate_queues,
+                    ..QueueRequest::new(QueueFlags::COMPUTE)
                 },
-                QueueCreateInfo {
-                    queue_family_index: queue_family_compute,
-                    ..Default::default()
+                QueueRequest {
+                    avoid_flags: QueueFlags::GRAPHICS | QueueFlags::COMPUTE,
+                    share_queue: !separate_queues,
+                    ..QueueRequest::new(QueueFlags::TRANSFER)
                 },
-            ]
-        } else {
-            vec![QueueCreateInfo {
-                queue_family_index: queue_family_graphics,
-                ..Default::default()
-            }]
-        };
+            ],
+        )
+        .unwrap_or_else(|err| match err {
+            QueueAssignmentError::Unsupported { queue_flags, .. } => {
+                panic!("could not find a queue that supports {:?}", queue_flags)
+            }
+        });
 
-        let (device, mut queues) = {
-            Device::new(
-                physical_device,
-                DeviceCreateInfo {
-                    queue_create_infos,
-                    enabled_extensions: device_extensions,
-                    enabled_features: features,
-                    ..Default::default()
-                },
-            )
-            .expect("failed to create device")
-        };
+        let (device, queues) = Device::new(
+            physical_device,
+            DeviceCreateInfo {
+                queue_create_infos: queue_assignment.queue_create_infos(),
+                enabled_extensions: device_extensions,
+                enabled_features: features,
+                ..Default::default()
+            },
+        )
+        .expect("failed to create device");
+        let mut queues = queue_assignment.assign(queues).into_iter();
         let gfx_queue = queues.next().unwrap();
-        let compute_queue = if is_separate_compute_queue {
-            queues.next().unwrap()
-        } else {
-            gfx_queue.clone()
-        };
-        (device, gfx_queue, compute_queue)
+        let compute_queue = queues.next().unwrap();
+        let transfer_queue = queues.next().unwrap();
+
+        (device, gfx_queue, compute_queue, transfer_queue)
     }
 
     /// Returns the name of the device.
@@ -306,15 +502,134 @@ impl VulkanoContext {
 
     /// Returns the compute queue.
     ///
-    /// Depending on your device, this might be the same as graphics queue.
+    /// Depending on your device, this might be the same as graphics queue. It is only a different
+    /// queue of the same queue family if [`VulkanoConfig::separate_queues`] is true.
     #[inline]
     pub fn compute_queue(&self) -> &Arc<Queue> {
         &self.compute_queue
     }
 
+    /// Returns the transfer queue.
+    ///
+    /// Depending on your device, this might be the same as the graphics or compute queue.
+    #[inline]
+    pub fn transfer_queue(&self) -> &Arc<Queue> {
+        &self.transfer_queue
+    }
+
     /// Returns the memory allocator.
     #[inline]
     pub fn memory_allocator(&self) -> &Arc<StandardMemoryAllocator> {
         &self.memory_allocator
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_index, parse_uuid, DeviceSelector, DeviceSelectorEnvError, VulkanoConfig};
+    use std::{ffi::OsString, sync::Arc};
+    use vulkano::{
+        device::{DeviceExtensions, Features},
+        instance::Instance,
+        library::{null::NullLoader, VulkanLibrary},
+    };
+
+    #[test]
+    fn parse_device_uuid() {
+        let uuid = [
+            0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54,
+            0x32, 0x10,
+        ];
+
+        assert_eq!(parse_uuid("0123456789abcdeffedcba9876543210"), Some(uuid));
+        assert_eq!(
+            parse_uuid(" 01234567-89AB-CDEF-FEDC-BA9876543210\n"),
+            Some(uuid),
+        );
+        // Too short, too long, and not hexadecimal.
+        assert_eq!(parse_uuid("0123456789abcdeffedcba987654321"), None);
+        assert_eq!(parse_uuid("0123456789abcdeffedcba98765432100"), None);
+        assert_eq!(parse_uuid("0123456789abcdeffedcba987654321g"), None);
+        assert_eq!(parse_uuid(""), None);
+    }
+
+    #[test]
+    fn parse_device_index() {
+        assert_eq!(parse_index("0"), Some(0));
+        assert_eq!(parse_index(" 3\n"), Some(3));
+        assert_eq!(parse_index("-1"), None);
+        assert_eq!(parse_index("first"), None);
+        assert_eq!(parse_index(""), None);
+    }
+
+    #[test]
+    fn device_selector_from_vars() {
+        fn vars(
+            uuid: Option<&'static str>,
+            index: Option<&'static str>,
+        ) -> impl Fn(&str) -> Option<OsString> {
+            move |name| match name {
+                DeviceSelector::UUID_ENV_VAR => uuid.map(OsString::from),
+                DeviceSelector::INDEX_ENV_VAR => index.map(OsString::from),
+                _ => None,
+            }
+        }
+
+        assert_eq!(DeviceSelector::from_vars(vars(None, None)), Ok(None));
+        assert_eq!(
+            DeviceSelector::from_vars(vars(None, Some("1"))),
+            Ok(Some(DeviceSelector::Index(1))),
+        );
+        // The UUID takes precedence over the index.
+        assert_eq!(
+            DeviceSelector::from_vars(vars(
+                Some("00000000-0000-0000-0000-000000000001"),
+                Some("1"),
+            )),
+            Ok(Some(DeviceSelector::Uuid([
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+            ]))),
+        );
+        assert_eq!(
+            DeviceSelector::from_vars(vars(None, Some("first"))),
+            Err(DeviceSelectorEnvError::InvalidIndex("first".into())),
+        );
+        assert_eq!(
+            DeviceSelector::from_vars(vars(Some("not-a-uuid"), Some("1"))),
+            Err(DeviceSelectorEnvError::InvalidUuid("not-a-uuid".into())),
+        );
+    }
+
+    #[test]
+    fn device_filter() {
+        let library = VulkanLibrary::with_loader(NullLoader::new()).unwrap();
+        let instance = Instance::new(library, Default::default()).unwrap();
+        let physical_device = instance
+            .enumerate_physical_devices()
+            .unwrap()
+            .next()
+            .unwrap();
+
+        let config = |device_features| VulkanoConfig {
+            device_filter_fn: Arc::new(|_| true),
+            device_extensions: DeviceExtensions::empty(),
+            device_features,
+            ..Default::default()
+        };
+
+        // The null device supports all core features apart from the sparse ones.
+        assert!(config(Features {
+            geometry_shader: true,
+            ..Features::empty()
+        })
+        .accepts_device(&physical_device));
+        assert!(!config(Features {
+            sparse_binding: true,
+            ..Features::empty()
+        })
+        .accepts_device(&physical_device));
+
+        // The null device supports no extensions, and `khr_swapchain` is required by default.
+        assert!(!VulkanoConfig::default().accepts_device(&physical_device));
+    }
+}
@@ -10,6 +10,13 @@
 #![allow(clippy::missing_safety_doc)]
 #![warn(rust_2018_idioms, rust_2021_compatibility)]
 
+pub mod blit;
+pub mod capture;
+pub mod compute;
 pub mod context;
+pub mod queues;
+pub mod readback;
+pub mod render_graph;
 pub mod renderer;
+pub mod upload;
 pub mod window;
@@ -0,0 +1,405 @@
+// Copyright (c) 2024 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Requesting several queues with individual priorities at device creation.
+//!
+//! [`Device::new`] takes a list of queue families, each with a list of queue priorities, and
+//! returns the created queues as one flat iterator. Mapping the roles of an application, such as
+//! rendering, asynchronous compute and streaming uploads, onto the queue families of a particular
+//! device, and then finding the right queues in the returned iterator, is easy to get wrong.
+//!
+//! [`QueueAssignment`] does this mapping from a list of [`QueueRequest`]s. Each request is
+//! assigned to a queue of a family that supports the requested operations, preferring families
+//! that are dedicated to them. If a family runs out of queues, requests share the last queue of
+//! the family. Requests can also opt to share a queue that was already assigned in their family
+//! instead of taking a new one, with [`QueueRequest::share_queue`].
+//!
+//! ```no_run
+//! # use std::sync::Arc;
+//! # use vulkano::device::{physical::PhysicalDevice, Device, DeviceCreateInfo, QueueFlags};
+//! # use vulkano_util::queues::{QueueAssignment, QueueRequest};
+//! # let physical_device: Arc<PhysicalDevice> = todo!();
+//! let assignment = QueueAssignment::new(
+//!     &physical_device,
+//!     &[
+//!         QueueRequest::new(QueueFlags::GRAPHICS),
+//!         QueueRequest {
+//!             avoid_flags: QueueFlags::GRAPHICS,
+//!             priority: 0.5,
+//!             ..QueueRequest::new(QueueFlags::COMPUTE)
+//!         },
+//!     ],
+//! )
+//! .unwrap();
+//!
+//! let (device, queues) = Device::new(
+//!     physical_device,
+//!     DeviceCreateInfo {
+//!         queue_create_infos: assignment.queue_create_infos(),
+//!         ..Default::default()
+//!     },
+//! )
+//! .unwrap();
+//!
+//! let [graphics_queue, async_compute_queue]: [_; 2] =
+//!     assignment.assign(queues).try_into().unwrap();
+//! ```
+//!
+//! [`Device::new`]: vulkano::device::Device::new
+
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+};
+use vulkano::device::{physical::PhysicalDevice, Queue, QueueCreateInfo, QueueFlags};
+
+/// A queue requested from a [`QueueAssignment`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QueueRequest {
+    /// The operations that the queue must support.
+    ///
+    /// Queue families that support graphics or compute operations are considered to support
+    /// transfer operations as well, even if they don't report it.
+    pub queue_flags: QueueFlags,
+
+    /// Operations that the queue family should preferably not support.
+    ///
+    /// Families that support none of these operations are chosen over the others when possible,
+    /// which is how dedicated compute and transfer queues are found.
+    ///
+    /// The default value is empty.
+    pub avoid_flags: QueueFlags,
+
+    /// The priority of the queue, between 0.0 and 1.0.
+    ///
+    /// If the request shares a queue with an earlier request, the priority of the earlier request
+    /// is used.
+    ///
+    /// The default value is 1.0.
+    pub priority: f32,
+
+    /// Whether the request shares the first queue already assigned in its queue family, instead
+    /// of taking a new queue of the family.
+    ///
+    /// Families that have no queue assigned yet are then chosen over the others when possible, so
+    /// the request only shares a queue if no other family supports its operations. This is how
+    /// to get a separate queue only if it comes from a different queue family.
+    ///
+    /// The default value is `false`.
+    pub share_queue: bool,
+}
+
+impl QueueRequest {
+    /// Returns a `QueueRequest` for a queue supporting `queue_flags`.
+    #[inline]
+    pub fn new(queue_flags: QueueFlags) -> Self {
+        Self {
+            queue_flags,
+            avoid_flags: QueueFlags::empty(),
+            priority: 1.0,
+            share_queue: false,
+        }
+    }
+}
+
+/// The queues assigned to a list of [`QueueRequest`]s on a physical device.
+///
+/// See the [module-level documentation](self) for more.
+#[derive(Clone, Debug)]
+pub struct QueueAssignment {
+    families: Vec<(u32, Vec<f32>)>,
+    slots: Vec<(usize, usize)>,
+}
+
+impl QueueAssignment {
+    /// Assigns a queue of `physical_device` to each of `requests`.
+    ///
+    /// The requests are processed in order, so earlier requests get the more suitable queues.
+    pub fn new(
+        physical_device: &PhysicalDevice,
+        requests: &[QueueRequest],
+    ) -> Result<Self, QueueAssignmentError> {
+        let queue_families: Vec<_> = physical_device
+            .queue_family_properties()
+            .iter()
+            .map(|properties| (properties.queue_flags, properties.queue_count))
+            .collect();
+
+        Self::from_queue_families(&queue_families, requests)
+    }
+
+    /// Assigns the requests to the queue families described by `queue_families`, which holds the
+    /// queue flags and the queue count of each family.
+    fn from_queue_families(
+        queue_families: &[(QueueFlags, u32)],
+        requests: &[QueueRequest],
+    ) -> Result<Self, QueueAssignmentError> {
+        let mut families: Vec<(u32, Vec<f32>)> = Vec::new();
+        let mut slots = Vec::with_capacity(requests.len());
+
+        let used_count = |families: &[(u32, Vec<f32>)], queue_family_index: u32| {
+            families
+                .iter()
+                .find(|(index, _)| *index == queue_family_index)
+                .map_or(0, |(_, priorities)| priorities.len())
+        };
+
+        for (request_index, request) in requests.iter().enumerate() {
+            let queue_family_index = (0..queue_families.len() as u32)
+                .filter(|&queue_family_index| {
+                    family_supports(
+                        queue_families[queue_family_index as usize].0,
+                        request.queue_flags,
+                    )
+                })
+                .min_by_key(|&queue_family_index| {
+                    let (queue_flags, queue_count) = queue_families[queue_family_index as usize];
+                    let used_count = used_count(&families, queue_family_index);
+
+                    (
+                        queue_flags.intersects(request.avoid_flags),
+                        if request.share_queue {
+                            used_count != 0
+                        } else {
+                            used_count >= queue_count as usize
+                        },
+                        queue_family_index,
+                    )
+                })
+                .ok_or(QueueAssignmentError::Unsupported {
+                    request_index,
+                    queue_flags: request.queue_flags,
+                })?;
+
+            let family_position = match families
+                .iter()
+                .position(|(index, _)| *index == queue_family_index)
+            {
+                Some(family_position) => family_position,
+                None => {
+                    families.push((queue_family_index, Vec::new()));
+                    families.len() - 1
+                }
+            };
+            let priorities = &mut families[family_position].1;
+
+            if request.share_queue && !priorities.is_empty() {
+                slots.push((family_position, 0));
+                continue;
+            }
+
+            if priorities.len() < queue_families[queue_family_index as usize].1 as usize {
+                priorities.push(request.priority);
+            }
+
+            slots.push((family_position, priorities.len() - 1));
+        }
+
+        Ok(QueueAssignment { families, slots })
+    }
+
+    /// Returns the queue family index and the queue index within the family of the queue assigned
+    /// to the request at `request_index`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `request_index` is out of range.
+    #[inline]
+    pub fn queue(&self, request_index: usize) -> (u32, u32) {
+        let (family_position, queue_index) = self.slots[request_index];
+
+        (self.families[family_position].0, queue_index as u32)
+    }
+
+    /// Returns the queue create infos to pass to [`DeviceCreateInfo::queue_create_infos`].
+    ///
+    /// [`DeviceCreateInfo::queue_create_infos`]: vulkano::device::DeviceCreateInfo::queue_create_infos
+    pub fn queue_create_infos(&self) -> Vec<QueueCreateInfo> {
+        self.families
+            .iter()
+            .map(|(queue_family_index, priorities)| QueueCreateInfo {
+                queue_family_index: *queue_family_index,
+                queues: priorities.clone(),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    /// Takes the queues returned by [`Device::new`], and returns the queue of each request, in
+    /// the order of the requests.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `queues` weren't created from [`queue_create_infos`](Self::queue_create_infos).
+    ///
+    /// [`Device::new`]: vulkano::device::Device::new
+    pub fn assign(&self, queues: impl IntoIterator<Item = Arc<Queue>>) -> Vec<Arc<Queue>> {
+        let queues: Vec<_> = queues.into_iter().collect();
+        let mut family_offsets = Vec::with_capacity(self.families.len());
+        let mut offset = 0;
+
+        for (_, priorities) in &self.families {
+            family_offsets.push(offset);
+            offset += priorities.len();
+        }
+
+        assert_eq!(queues.len(), offset);
+
+        self.slots
+            .iter()
+            .map(|&(family_position, queue_index)| {
+                let queue = &queues[family_offsets[family_position] + queue_index];
+                assert_eq!(queue.queue_family_index(), self.families[family_position].0,);
+
+                queue.clone()
+            })
+            .collect()
+    }
+}
+
+/// Returns the indices of the queue families of `physical_device` that support all of
+/// `queue_flags`.
+///
+/// Queue families that support graphics or compute operations are considered to support transfer
+/// operations as well, even if they don't report it.
+pub fn queue_families_supporting(
+    physical_device: &PhysicalDevice,
+    queue_flags: QueueFlags,
+) -> impl Iterator<Item = u32> + '_ {
+    physical_device
+        .queue_family_properties()
+        .iter()
+        .enumerate()
+        .filter(move |(_, properties)| family_supports(properties.queue_flags, queue_flags))
+        .map(|(index, _)| index as u32)
+}
+
+fn family_supports(mut supported_flags: QueueFlags, queue_flags: QueueFlags) -> bool {
+    if supported_flags.intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE) {
+        supported_flags |= QueueFlags::TRANSFER;
+    }
+
+    supported_flags.contains(queue_flags)
+}
+
+/// Error that can happen when assigning queues.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QueueAssignmentError {
+    /// No queue family of the physical device supports the operations of a request.
+    Unsupported {
+        /// The index of the request.
+        request_index: usize,
+
+        /// The operations of the request.
+        queue_flags: QueueFlags,
+    },
+}
+
+impl Error for QueueAssignmentError {}
+
+impl Display for QueueAssignmentError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::Unsupported {
+                request_index,
+                queue_flags,
+            } => write!(
+                f,
+                "no queue family supports the operations of request {} ({:?})",
+                request_index, queue_flags,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{QueueAssignment, QueueAssignmentError, QueueRequest};
+    use vulkano::device::QueueFlags;
+
+    const GRAPHICS: QueueFlags = QueueFlags::GRAPHICS
+        .union(QueueFlags::COMPUTE)
+        .union(QueueFlags::TRANSFER);
+
+    fn requests(share_queue: bool) -> [QueueRequest; 3] {
+        [
+            QueueRequest::new(QueueFlags::GRAPHICS),
+            QueueRequest {
+                avoid_flags: QueueFlags::GRAPHICS,
+                priority: 0.5,
+                share_queue,
+                ..QueueRequest::new(QueueFlags::COMPUTE)
+            },
+            QueueRequest {
+                avoid_flags: QueueFlags::GRAPHICS | QueueFlags::COMPUTE,
+                share_queue,
+                ..QueueRequest::new(QueueFlags::TRANSFER)
+            },
+        ]
+    }
+
+    fn queues(assignment: &QueueAssignment, count: usize) -> Vec<(u32, u32)> {
+        (0..count).map(|index| assignment.queue(index)).collect()
+    }
+
+    #[test]
+    fn dedicated_families() {
+        let families = [
+            (GRAPHICS, 16),
+            (QueueFlags::COMPUTE | QueueFlags::TRANSFER, 8),
+            (QueueFlags::TRANSFER, 2),
+        ];
+
+        for share_queue in [false, true] {
+            let assignment =
+                QueueAssignment::from_queue_families(&families, &requests(share_queue)).unwrap();
+            assert_eq!(queues(&assignment, 3), [(0, 0), (1, 0), (2, 0)]);
+
+            let create_infos = assignment.queue_create_infos();
+            assert_eq!(create_infos.len(), 3);
+            assert_eq!(create_infos[1].queue_family_index, 1);
+            assert_eq!(create_infos[1].queues, [0.5]);
+        }
+    }
+
+    #[test]
+    fn single_family() {
+        let families = [(GRAPHICS, 2)];
+
+        let assignment = QueueAssignment::from_queue_families(&families, &requests(false)).unwrap();
+        assert_eq!(queues(&assignment, 3), [(0, 0), (0, 1), (0, 1)]);
+        assert_eq!(assignment.queue_create_infos()[0].queues, [1.0, 0.5]);
+
+        let assignment = QueueAssignment::from_queue_families(&families, &requests(true)).unwrap();
+        assert_eq!(queues(&assignment, 3), [(0, 0), (0, 0), (0, 0)]);
+        assert_eq!(assignment.queue_create_infos()[0].queues, [1.0]);
+    }
+
+    #[test]
+    fn share_queue_prefers_other_family() {
+        let families = [(GRAPHICS, 4), (GRAPHICS, 1)];
+
+        let assignment = QueueAssignment::from_queue_families(&families, &requests(true)).unwrap();
+        assert_eq!(queues(&assignment, 3), [(0, 0), (1, 0), (0, 0)]);
+    }
+
+    #[test]
+    fn unsupported() {
+        let families = [(QueueFlags::COMPUTE, 1)];
+
+        assert_eq!(
+            QueueAssignment::from_queue_families(&families, &requests(false)).unwrap_err(),
+            QueueAssignmentError::Unsupported {
+                request_index: 0,
+                queue_flags: QueueFlags::GRAPHICS,
+            },
+        );
+    }
+}
@@ -0,0 +1,396 @@
+// Copyright (c) 2024 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Reading back the contents of a large buffer in chunks, with bounded staging memory.
+//!
+//! Reading a device-local buffer requires copying it to a host-visible buffer first. For very
+//! large buffers, allocating a host-visible copy of the whole buffer can double the memory use.
+//! [`ChunkedReadback`] instead copies the buffer through a small number of staging buffers of a
+//! fixed size, and delivers the data one chunk at a time as an iterator. While one chunk is being
+//! read on the host, the copy of the next chunk is already executing on the device.
+//!
+//! ```no_run
+//! # use std::sync::Arc;
+//! # use vulkano::{
+//! #     buffer::Subbuffer,
+//! #     command_buffer::allocator::StandardCommandBufferAllocator,
+//! #     device::Queue,
+//! #     memory::allocator::StandardMemoryAllocator,
+//! # };
+//! # use vulkano_util::readback::ChunkedReadback;
+//! # let memory_allocator: Arc<StandardMemoryAllocator> = todo!();
+//! # let command_buffer_allocator: StandardCommandBufferAllocator = todo!();
+//! # let queue: Arc<Queue> = todo!();
+//! # let results: Subbuffer<[f32]> = todo!();
+//! let mut readback = ChunkedReadback::new(
+//!     memory_allocator,
+//!     &command_buffer_allocator,
+//!     queue,
+//!     results,
+//!     64 * 1024 * 1024,
+//! )
+//! .unwrap();
+//!
+//! while let Some(chunk) = readback.next() {
+//!     let chunk = chunk.unwrap();
+//!     // Process `chunk.data`, which starts at byte `chunk.offset` of the buffer.
+//!     println!("{} of {} bytes", readback.bytes_read(), readback.size());
+//! }
+//! ```
+
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+};
+use vulkano::{
+    buffer::{AllocateBufferError, Buffer, BufferCreateInfo, BufferUsage, Subbuffer},
+    command_buffer::{
+        allocator::CommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferExecError,
+        CommandBufferExecFuture, CommandBufferUsage, CopyBufferInfo,
+    },
+    device::Queue,
+    memory::allocator::{AllocationCreateInfo, MemoryAllocator, MemoryTypeFilter},
+    sync::{
+        self,
+        future::{FenceSignalFuture, GpuFuture, NowFuture},
+        HostAccessError,
+    },
+    DeviceSize, Validated, ValidationError, VulkanError,
+};
+
+/// The number of staging buffers, and therefore the number of chunks that can be in flight at
+/// the same time.
+const STAGING_BUFFER_COUNT: usize = 2;
+
+/// Reads back the contents of a buffer in chunks of a fixed maximum size.
+///
+/// See the [module-level documentation](self) for more.
+pub struct ChunkedReadback<'a, A>
+where
+    A: CommandBufferAllocator,
+{
+    command_buffer_allocator: &'a A,
+    queue: Arc<Queue>,
+    buffer: Subbuffer<[u8]>,
+    chunk_size: DeviceSize,
+
+    next_offset: DeviceSize,
+    bytes_read: DeviceSize,
+    free_staging_buffers: Vec<Subbuffer<[u8]>>,
+    in_flight: VecDeque<InFlightChunk>,
+}
+
+struct InFlightChunk {
+    future: FenceSignalFuture<CommandBufferExecFuture<NowFuture>>,
+    staging_buffer: Subbuffer<[u8]>,
+    offset: DeviceSize,
+    size: DeviceSize,
+}
+
+impl<'a, A> ChunkedReadback<'a, A>
+where
+    A: CommandBufferAllocator + 'static,
+{
+    /// Starts reading back `buffer` in chunks of at most `chunk_size` bytes.
+    ///
+    /// The copies are executed on `queue`. The first chunks are submitted right away, and the
+    /// next ones are submitted as the previous chunks are consumed. The staging memory used is at
+    /// most twice `chunk_size`.
+    ///
+    /// `buffer` must have been created with the [`TRANSFER_SRC`] usage. If `buffer` is in use by
+    /// other command buffers, the copies are synchronized with them by the automatic
+    /// synchronization, but those command buffers must already have been submitted.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `chunk_size` is zero.
+    ///
+    /// [`TRANSFER_SRC`]: vulkano::buffer::BufferUsage::TRANSFER_SRC
+    pub fn new(
+        memory_allocator: Arc<dyn MemoryAllocator>,
+        command_buffer_allocator: &'a A,
+        queue: Arc<Queue>,
+        buffer: Subbuffer<impl ?Sized>,
+        chunk_size: DeviceSize,
+    ) -> Result<Self, ReadbackError> {
+        assert!(chunk_size != 0);
+
+        let buffer = buffer.into_bytes();
+        let staging_size = chunk_size.min(buffer.size()).max(1);
+        let free_staging_buffers = (0..STAGING_BUFFER_COUNT)
+            .map(|_| {
+                Buffer::new_slice::<u8>(
+                    memory_allocator.clone(),
+                    BufferCreateInfo {
+                        usage: BufferUsage::TRANSFER_DST,
+                        ..Default::default()
+                    },
+                    AllocationCreateInfo {
+                        memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                            | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                        ..Default::default()
+                    },
+                    staging_size,
+                )
+                .map_err(ReadbackError::AllocateBuffer)
+            })
+            .collect::<Result<_, _>>()?;
+
+        let mut readback = ChunkedReadback {
+            command_buffer_allocator,
+            queue,
+            buffer,
+            chunk_size,
+
+            next_offset: 0,
+            bytes_read: 0,
+            free_staging_buffers,
+            in_flight: VecDeque::with_capacity(STAGING_BUFFER_COUNT),
+        };
+        readback.submit_chunks()?;
+
+        Ok(readback)
+    }
+
+    /// Returns the total size of the buffer being read back, in bytes.
+    #[inline]
+    pub fn size(&self) -> DeviceSize {
+        self.buffer.size()
+    }
+
+    /// Returns the number of bytes that have been delivered so far.
+    #[inline]
+    pub fn bytes_read(&self) -> DeviceSize {
+        self.bytes_read
+    }
+
+    /// Submits copies for as many chunks as there are free staging buffers.
+    fn submit_chunks(&mut self) -> Result<(), ReadbackError> {
+        while self.next_offset < self.buffer.size() {
+            let staging_buffer = match self.free_staging_buffers.pop() {
+                Some(staging_buffer) => staging_buffer,
+                None => break,
+            };
+
+            let offset = self.next_offset;
+            let size = self.chunk_size.min(self.buffer.size() - offset);
+
+            let mut builder = AutoCommandBufferBuilder::primary(
+                self.command_buffer_allocator,
+                self.queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .map_err(ReadbackError::CreateCommandBuffer)?;
+            builder
+                .copy_buffer(CopyBufferInfo::buffers(
+                    self.buffer.clone().slice(offset..offset + size),
+                    staging_buffer.clone().slice(0..size),
+                ))
+                .map_err(ReadbackError::RecordCommands)?;
+            let command_buffer = builder
+                .build()
+                .map_err(ReadbackError::CreateCommandBuffer)?;
+
+            let future = sync::now(self.queue.device().clone())
+                .then_execute(self.queue.clone(), command_buffer)
+                .map_err(ReadbackError::Execute)?
+                .then_signal_fence_and_flush()
+                .map_err(ReadbackError::Submit)?;
+
+            self.in_flight.push_back(InFlightChunk {
+                future,
+                staging_buffer,
+                offset,
+                size,
+            });
+            self.next_offset += size;
+        }
+
+        Ok(())
+    }
+
+    fn read_chunk(&mut self, chunk: InFlightChunk) -> Result<ReadbackChunk, ReadbackError> {
+        let InFlightChunk {
+            future,
+            staging_buffer,
+            offset,
+            size,
+        } = chunk;
+
+        future.wait(None).map_err(ReadbackError::Submit)?;
+        drop(future);
+
+        let data =
+            staging_buffer.read().map_err(ReadbackError::ReadBuffer)?[..size as usize].to_vec();
+        self.free_staging_buffers.push(staging_buffer);
+        self.bytes_read += size;
+
+        Ok(ReadbackChunk { offset, data })
+    }
+}
+
+impl<'a, A> Iterator for ChunkedReadback<'a, A>
+where
+    A: CommandBufferAllocator + 'static,
+{
+    type Item = Result<ReadbackChunk, ReadbackError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.in_flight.pop_front()?;
+
+        let result = self
+            .read_chunk(chunk)
+            .and_then(|chunk| self.submit_chunks().map(|()| chunk));
+
+        if result.is_err() {
+            // Stop after the first error, the remaining chunks can't be delivered in order.
+            self.next_offset = self.buffer.size();
+            self.in_flight.clear();
+        }
+
+        Some(result)
+    }
+}
+
+/// A chunk of data read back by a [`ChunkedReadback`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReadbackChunk {
+    /// The offset in bytes of the chunk within the buffer.
+    pub offset: DeviceSize,
+
+    /// The contents of the chunk.
+    pub data: Vec<u8>,
+}
+
+/// Error that can happen when reading back a buffer.
+#[derive(Clone, Debug)]
+pub enum ReadbackError {
+    /// Allocating a staging buffer failed.
+    AllocateBuffer(Validated<AllocateBufferError>),
+
+    /// Allocating or building a command buffer failed.
+    CreateCommandBuffer(Validated<VulkanError>),
+
+    /// Recording a copy command failed.
+    RecordCommands(Box<ValidationError>),
+
+    /// Executing a command buffer failed.
+    Execute(CommandBufferExecError),
+
+    /// Submitting a command buffer or waiting for it to complete failed.
+    Submit(Validated<VulkanError>),
+
+    /// Reading the data from a staging buffer failed.
+    ReadBuffer(HostAccessError),
+}
+
+impl Error for ReadbackError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::AllocateBuffer(err) => Some(err),
+            Self::CreateCommandBuffer(err) => Some(err),
+            Self::RecordCommands(err) => Some(err),
+            Self::Execute(err) => Some(err),
+            Self::Submit(err) => Some(err),
+            Self::ReadBuffer(err) => Some(err),
+        }
+    }
+}
+
+impl Display for ReadbackError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::AllocateBuffer(_) => write!(f, "allocating a staging buffer failed"),
+            Self::CreateCommandBuffer(_) => write!(f, "creating a command buffer failed"),
+            Self::RecordCommands(_) => write!(f, "recording a copy command failed"),
+            Self::Execute(_) => write!(f, "executing a command buffer failed"),
+            Self::Submit(_) => write!(f, "submitting a command buffer failed"),
+            Self::ReadBuffer(_) => write!(f, "reading a staging buffer failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChunkedReadback;
+    use std::sync::Arc;
+    use vulkano::{
+        buffer::{Buffer, BufferCreateInfo, BufferUsage},
+        command_buffer::allocator::StandardCommandBufferAllocator,
+        device::{Device, DeviceCreateInfo, QueueCreateInfo},
+        instance::Instance,
+        library::{null::NullLoader, VulkanLibrary},
+        memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
+    };
+
+    #[test]
+    fn chunked_readback() {
+        let library = VulkanLibrary::with_loader(NullLoader::new()).unwrap();
+        let instance = Instance::new(library, Default::default()).unwrap();
+        let physical_device = instance
+            .enumerate_physical_devices()
+            .unwrap()
+            .next()
+            .unwrap();
+        let (device, mut queues) = Device::new(
+            physical_device,
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo::default()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let queue = queues.next().unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let contents: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let buffer = Buffer::from_iter(
+            memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_SRC,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            contents.iter().copied(),
+        )
+        .unwrap();
+
+        let command_buffer_allocator =
+            StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let readback = ChunkedReadback::new(
+            memory_allocator,
+            &command_buffer_allocator,
+            queue,
+            buffer,
+            384,
+        )
+        .unwrap();
+        assert_eq!(readback.size(), 1000);
+
+        let chunks: Vec<_> = readback.map(Result::unwrap).collect();
+        let offsets_and_sizes: Vec<_> = chunks
+            .iter()
+            .map(|chunk| (chunk.offset, chunk.data.len()))
+            .collect();
+        assert_eq!(offsets_and_sizes, [(0, 384), (384, 384), (768, 232)]);
+
+        // The null driver doesn't execute the copies, so the data can only be checked on a
+        // real device.
+        if device.physical_device().properties().device_name != "vulkano null device" {
+            let data: Vec<u8> = chunks.into_iter().flat_map(|chunk| chunk.data).collect();
+            assert_eq!(data, contents);
+        }
+    }
+}
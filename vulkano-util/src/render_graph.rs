@@ -0,0 +1,1406 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! A frame graph that orders passes and manages transient images.
+//!
+//! Instead of recording commands directly, you declare a number of *passes*. Each pass lists the
+//! images and buffers that it reads and writes, and provides a closure that records its commands.
+//! When the graph is compiled, it:
+//!
+//! - culls passes whose results are never used by an imported resource,
+//! - checks that no transient image is read before something has written to it,
+//! - computes the lifetime of each transient image, and lets transient images whose lifetimes do
+//!   not overlap share the same physical image.
+//!
+//! When the compiled graph is executed, physical images whose lifetimes don't overlap are also
+//! bound to the same device memory, even if their descriptions differ. A chain of post-processing
+//! passes that each read the previous result and write a new one therefore needs only as much
+//! memory as the largest pair of images that are alive at the same time. The memory is kept in a
+//! [`TransientMemory`], which reuses it for later executions.
+//!
+//! The passes are recorded into a single [`AutoCommandBufferBuilder`] in dependency order. The
+//! automatic synchronization of the command buffer builder inserts the barriers and layout
+//! transitions between passes, except for the physical images that share memory with other
+//! physical images. The automatic synchronization would transition those to their final layout at
+//! the end of the command buffer, after the memory has been reused by another image, so they are
+//! excluded from it, and the graph records their barriers itself:
+//!
+//! - Before the first use of such an image, it is transitioned from the `Undefined` layout, after
+//!   all previously recorded commands. This discards whatever the images that used the memory
+//!   before it left there.
+//! - Before each pass that uses the image, it is transitioned to the [layout] of the declared
+//!   access, after the accesses of the previous pass that used it.
+//! - After the last use of the image, its accesses are completed, and it is transitioned to the
+//!   final layout that the command buffer expects, before any image that uses the memory next.
+//!
+//! The commands of a pass must therefore leave the images that it accesses in the layout of the
+//! declared access. For example, a render pass that has a transient image as a color attachment
+//! must use [`ImageLayout::ColorAttachmentOptimal`] as its final layout.
+//!
+//! [layout]: ImageAccess::layout
+//!
+//! ```no_run
+//! # use std::sync::Arc;
+//! # use vulkano::{
+//! #     command_buffer::{AutoCommandBufferBuilder, PrimaryAutoCommandBuffer},
+//! #     format::Format,
+//! #     image::view::ImageView,
+//! #     memory::allocator::StandardMemoryAllocator,
+//! # };
+//! # use vulkano_util::render_graph::{
+//! #     ImageAccess, RenderGraph, TransientImageInfo, TransientMemory,
+//! # };
+//! # let memory_allocator: Arc<StandardMemoryAllocator> = todo!();
+//! # let swapchain_view: Arc<ImageView> = todo!();
+//! # let mut builder: AutoCommandBufferBuilder<PrimaryAutoCommandBuffer> = todo!();
+//! // Keep this around, to reuse the memory of the transient images in later frames.
+//! let mut transient_memory = TransientMemory::new(memory_allocator);
+//!
+//! let mut graph = RenderGraph::new();
+//! let output = graph.import_image(swapchain_view);
+//! let hdr = graph.create_transient_image(TransientImageInfo {
+//!     format: Format::R16G16B16A16_SFLOAT,
+//!     extent: [1920, 1080, 1],
+//!     ..Default::default()
+//! });
+//!
+//! graph
+//!     .add_pass("scene")
+//!     .write_image(hdr, ImageAccess::ColorAttachment)
+//!     .execute(|builder, resources| {
+//!         // Record the scene using `resources.image(hdr)` as the color attachment.
+//!         Ok(())
+//!     });
+//!
+//! graph
+//!     .add_pass("tonemap")
+//!     .read_image(hdr, ImageAccess::Sampled)
+//!     .write_image(output, ImageAccess::ColorAttachment)
+//!     .execute(|builder, resources| {
+//!         // Sample `resources.image(hdr)` and write to `resources.image(output)`.
+//!         Ok(())
+//!     });
+//!
+//! graph
+//!     .compile()
+//!     .unwrap()
+//!     .execute(&mut transient_memory, &mut builder)
+//!     .unwrap();
+//! ```
+
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+};
+use vulkano::{
+    buffer::Subbuffer,
+    command_buffer::{
+        allocator::{CommandBufferAllocator, StandardCommandBufferAllocator},
+        AutoCommandBufferBuilder, PrimaryAutoCommandBuffer,
+    },
+    device::DeviceOwned,
+    format::Format,
+    image::{
+        sys::RawImage, view::ImageView, AllocateImageError, Image, ImageCreateInfo, ImageLayout,
+        ImageType, ImageUsage, SampleCount,
+    },
+    memory::{
+        allocator::{AllocationCreateInfo, MemoryAllocator, MemoryTypeFilter},
+        DeviceMemory, MemoryAllocateInfo, ResourceMemory,
+    },
+    sync::{AccessFlags, DependencyInfo, ImageMemoryBarrier, PipelineStages},
+    DeviceSize, Validated, ValidationError, VulkanError,
+};
+
+type RecordFn<'a, A> = Box<
+    dyn FnOnce(
+            &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer<A>, A>,
+            &PassResources,
+        ) -> Result<(), Box<ValidationError>>
+        + 'a,
+>;
+
+/// A collection of passes and the resources that they access.
+pub struct RenderGraph<'a, A = StandardCommandBufferAllocator>
+where
+    A: CommandBufferAllocator,
+{
+    images: Vec<ImageResource>,
+    buffers: Vec<Subbuffer<[u8]>>,
+    passes: Vec<Pass<'a, A>>,
+}
+
+impl<'a, A> RenderGraph<'a, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Creates an empty `RenderGraph`.
+    #[inline]
+    pub fn new() -> Self {
+        RenderGraph {
+            images: Vec::new(),
+            buffers: Vec::new(),
+            passes: Vec::new(),
+        }
+    }
+
+    /// Adds an existing image view to the graph.
+    ///
+    /// Imported images are considered to be outputs of the graph: any pass that writes to one
+    /// is never culled.
+    #[inline]
+    pub fn import_image(&mut self, image_view: Arc<ImageView>) -> ImageId {
+        self.images.push(ImageResource::Imported(image_view));
+
+        ImageId(self.images.len() as u32 - 1)
+    }
+
+    /// Declares an image that only lives for the duration of the graph.
+    ///
+    /// The image is created when the graph is executed. Its usage is derived from the way that
+    /// the passes access it.
+    #[inline]
+    pub fn create_transient_image(&mut self, info: TransientImageInfo) -> ImageId {
+        self.images.push(ImageResource::Transient(info));
+
+        ImageId(self.images.len() as u32 - 1)
+    }
+
+    /// Adds an existing buffer to the graph.
+    ///
+    /// Imported buffers are considered to be outputs of the graph: any pass that writes to one
+    /// is never culled.
+    #[inline]
+    pub fn import_buffer<T: ?Sized>(&mut self, buffer: Subbuffer<T>) -> BufferId {
+        self.buffers.push(buffer.into_bytes());
+
+        BufferId(self.buffers.len() as u32 - 1)
+    }
+
+    /// Starts declaring a new pass. The pass is added to the graph when
+    /// [`PassBuilder::execute`] is called.
+    ///
+    /// Passes are executed in the order in which they are added, so a pass can only read the
+    /// results of passes that were added before it.
+    #[inline]
+    pub fn add_pass(&mut self, name: impl Into<String>) -> PassBuilder<'_, 'a, A> {
+        PassBuilder {
+            graph: self,
+            name: name.into(),
+            image_accesses: Vec::new(),
+            buffer_accesses: Vec::new(),
+        }
+    }
+
+    /// Culls unused passes, validates the graph and assigns physical images to the transient
+    /// images.
+    pub fn compile(self) -> Result<CompiledRenderGraph<'a, A>, RenderGraphError> {
+        let RenderGraph {
+            images,
+            buffers,
+            passes,
+        } = self;
+
+        // Walk the passes backwards, keeping every pass that has an observable effect or that
+        // produces something that a kept pass accesses afterwards.
+        let mut is_needed = vec![false; passes.len()];
+        let mut image_needed = vec![false; images.len()];
+
+        for (pass_index, pass) in passes.iter().enumerate().rev() {
+            let writes_output = pass.image_accesses.iter().any(|&(id, _, write)| {
+                write && (image_needed[id.0 as usize] || images[id.0 as usize].is_imported())
+            }) || pass.buffer_accesses.iter().any(|&(_, write)| write);
+            let has_writes = pass.image_accesses.iter().any(|&(_, _, write)| write)
+                || pass.buffer_accesses.iter().any(|&(_, write)| write);
+
+            if writes_output || !has_writes {
+                is_needed[pass_index] = true;
+
+                for &(id, _, _) in &pass.image_accesses {
+                    image_needed[id.0 as usize] = true;
+                }
+            }
+        }
+
+        // Compute the lifetime and usage of each transient image, in terms of the index of the
+        // kept passes.
+        let mut lifetimes: Vec<Option<(usize, usize)>> = vec![None; images.len()];
+        let mut usages = vec![ImageUsage::empty(); images.len()];
+        let mut kept_passes = Vec::with_capacity(passes.len());
+
+        for (pass, is_needed) in passes.into_iter().zip(is_needed) {
+            if !is_needed {
+                continue;
+            }
+
+            let order = kept_passes.len();
+
+            if let Some(&(id, _, _)) = pass.image_accesses.iter().find(|&&(id, _, write)| {
+                let index = id.0 as usize;
+                !write && !images[index].is_imported() && lifetimes[index].is_none()
+            }) {
+                return Err(RenderGraphError::UninitializedImageRead {
+                    pass: pass.name,
+                    image: id,
+                });
+            }
+
+            for &(id, access, _) in &pass.image_accesses {
+                let index = id.0 as usize;
+                let lifetime = lifetimes[index].get_or_insert((order, order));
+                lifetime.1 = order;
+                usages[index] |= access.usage();
+            }
+
+            kept_passes.push(pass);
+        }
+
+        // Greedily assign physical images to transient images. Two transient images can share
+        // a physical image if their descriptions are equal and their lifetimes don't overlap.
+        let mut physical_images: Vec<PhysicalImage> = Vec::new();
+        let mut image_mapping = vec![None; images.len()];
+        let mut transients: Vec<_> = (images.iter().enumerate())
+            .filter_map(|(index, image)| match image {
+                ImageResource::Transient(info) => lifetimes[index].map(|l| (index, *info, l)),
+                ImageResource::Imported(_) => None,
+            })
+            .collect();
+        transients.sort_by_key(|&(_, _, (first_use, _))| first_use);
+
+        for (index, info, (first_use, last_use)) in transients {
+            let physical_index = match physical_images
+                .iter()
+                .position(|physical| physical.info == info && physical.last_use < first_use)
+            {
+                Some(physical_index) => physical_index,
+                None => {
+                    physical_images.push(PhysicalImage {
+                        info,
+                        usage: ImageUsage::empty(),
+                        first_use,
+                        last_use,
+                    });
+                    physical_images.len() - 1
+                }
+            };
+
+            let physical = &mut physical_images[physical_index];
+            physical.usage |= usages[index];
+            physical.last_use = last_use;
+            image_mapping[index] = Some(physical_index);
+        }
+
+        Ok(CompiledRenderGraph {
+            images,
+            buffers,
+            passes: kept_passes,
+            physical_images,
+            image_mapping,
+        })
+    }
+}
+
+impl<'a, A> Default for RenderGraph<'a, A>
+where
+    A: CommandBufferAllocator,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A pass that is being declared. Created with [`RenderGraph::add_pass`].
+pub struct PassBuilder<'g, 'a, A>
+where
+    A: CommandBufferAllocator,
+{
+    graph: &'g mut RenderGraph<'a, A>,
+    name: String,
+    image_accesses: Vec<(ImageId, ImageAccess, bool)>,
+    buffer_accesses: Vec<(BufferId, bool)>,
+}
+
+impl<'g, 'a, A> PassBuilder<'g, 'a, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Declares that the pass reads from `image`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `image` does not belong to the graph.
+    #[inline]
+    pub fn read_image(mut self, image: ImageId, access: ImageAccess) -> Self {
+        assert!((image.0 as usize) < self.graph.images.len());
+        self.image_accesses.push((image, access, false));
+
+        self
+    }
+
+    /// Declares that the pass writes to `image`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `image` does not belong to the graph.
+    #[inline]
+    pub fn write_image(mut self, image: ImageId, access: ImageAccess) -> Self {
+        assert!((image.0 as usize) < self.graph.images.len());
+        self.image_accesses.push((image, access, true));
+
+        self
+    }
+
+    /// Declares that the pass reads from `buffer`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `buffer` does not belong to the graph.
+    #[inline]
+    pub fn read_buffer(mut self, buffer: BufferId) -> Self {
+        assert!((buffer.0 as usize) < self.graph.buffers.len());
+        self.buffer_accesses.push((buffer, false));
+
+        self
+    }
+
+    /// Declares that the pass writes to `buffer`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `buffer` does not belong to the graph.
+    #[inline]
+    pub fn write_buffer(mut self, buffer: BufferId) -> Self {
+        assert!((buffer.0 as usize) < self.graph.buffers.len());
+        self.buffer_accesses.push((buffer, true));
+
+        self
+    }
+
+    /// Adds the pass to the graph, with `record` being called to record the pass's commands
+    /// when the graph is executed.
+    ///
+    /// A pass that doesn't declare any writes is assumed to have side effects, and is never
+    /// culled.
+    #[inline]
+    pub fn execute(
+        self,
+        record: impl FnOnce(
+                &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer<A>, A>,
+                &PassResources,
+            ) -> Result<(), Box<ValidationError>>
+            + 'a,
+    ) {
+        let PassBuilder {
+            graph,
+            name,
+            image_accesses,
+            buffer_accesses,
+        } = self;
+
+        graph.passes.push(Pass {
+            name,
+            image_accesses,
+            buffer_accesses,
+            record: Box::new(record),
+        });
+    }
+}
+
+/// A render graph that has been compiled and is ready to be executed.
+pub struct CompiledRenderGraph<'a, A = StandardCommandBufferAllocator>
+where
+    A: CommandBufferAllocator,
+{
+    images: Vec<ImageResource>,
+    buffers: Vec<Subbuffer<[u8]>>,
+    passes: Vec<Pass<'a, A>>,
+    physical_images: Vec<PhysicalImage>,
+    image_mapping: Vec<Option<usize>>,
+}
+
+impl<'a, A> CompiledRenderGraph<'a, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Returns the names of the passes that will be executed, in execution order.
+    #[inline]
+    pub fn passes(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.passes.iter().map(|pass| pass.name.as_str())
+    }
+
+    /// Returns the number of physical images that will be created for the transient images of
+    /// the graph.
+    #[inline]
+    pub fn physical_image_count(&self) -> usize {
+        self.physical_images.len()
+    }
+
+    /// Creates the transient images and records all passes into `builder`.
+    ///
+    /// Physical images whose lifetimes don't overlap are bound to the same memory block of
+    /// `memory`, as long as they have a memory type in common and don't require a dedicated
+    /// allocation. The other physical images are allocated with the memory allocator of `memory`.
+    pub fn execute(
+        self,
+        memory: &mut TransientMemory,
+        builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer<A>, A>,
+    ) -> Result<(), RenderGraphError> {
+        let CompiledRenderGraph {
+            images,
+            buffers,
+            passes,
+            physical_images,
+            image_mapping,
+        } = self;
+
+        let device = memory.memory_allocator.device().clone();
+
+        // Create the physical images without memory first, so that their memory requirements are
+        // known.
+        let raw_images = physical_images
+            .iter()
+            .map(|physical| {
+                RawImage::new(device.clone(), physical.create_info())
+                    .map_err(RenderGraphError::CreateImage)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let memory_allocator = memory.memory_allocator.clone();
+        let (memory_blocks, memory_mapping) = assign_memory_blocks(
+            physical_images
+                .iter()
+                .zip(&raw_images)
+                .map(|(physical, raw_image)| {
+                    let requirements = &raw_image.memory_requirements()[0];
+
+                    (
+                        physical.first_use,
+                        physical.last_use,
+                        (!requirements.requires_dedicated_allocation)
+                            .then_some((requirements.memory_type_bits, requirements.layout.size())),
+                    )
+                }),
+            |memory_type_bits| {
+                memory_allocator
+                    .find_memory_type_index(memory_type_bits, MemoryTypeFilter::PREFER_DEVICE)
+            },
+        );
+
+        let memories = memory_blocks
+            .iter()
+            .map(|block| {
+                memory
+                    .block(block.memory_type_index, block.size)
+                    .map_err(RenderGraphError::AllocateMemory)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Images that share their memory block with other images are synchronized by the graph.
+        let mut is_aliased = vec![false; physical_images.len()];
+
+        for (physical_index, &block_index) in memory_mapping.iter().enumerate() {
+            is_aliased[physical_index] = block_index.is_some_and(|block_index| {
+                memory_mapping
+                    .iter()
+                    .filter(|&&other| other == Some(block_index))
+                    .count()
+                    > 1
+            });
+        }
+
+        let physical_views = physical_images
+            .iter()
+            .zip(raw_images)
+            .zip(&memory_mapping)
+            .map(|((physical, raw_image), &block_index)| {
+                let image = match block_index {
+                    Some(block_index) => {
+                        // SAFETY: The images that share the memory have disjoint lifetimes. They
+                        // are excluded from automatic synchronization, and the barriers that are
+                        // recorded below order every access to one of them before the first use
+                        // of the next, which discards the contents. No other command buffer uses
+                        // the memory while `builder` holds the images.
+                        let memory = unsafe {
+                            ResourceMemory::new_dedicated_unchecked(memories[block_index].clone())
+                        };
+
+                        raw_image
+                            .bind_memory([memory])
+                            .map(Arc::new)
+                            .map_err(|(err, _, _)| RenderGraphError::BindMemory(err))?
+                    }
+                    None => Image::new(
+                        memory_allocator.clone(),
+                        physical.create_info(),
+                        AllocationCreateInfo::default(),
+                    )
+                    .map_err(RenderGraphError::AllocateImage)?,
+                };
+
+                ImageView::new_default(image).map_err(RenderGraphError::CreateImageView)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (view, _) in physical_views
+            .iter()
+            .zip(&is_aliased)
+            .filter(|&(_, &is_aliased)| is_aliased)
+        {
+            // SAFETY: The accesses are synchronized with the barriers that are recorded below.
+            unsafe { builder.disable_auto_sync_for_image(view.image().clone()) };
+        }
+
+        let resources = PassResources {
+            images: images
+                .into_iter()
+                .zip(&image_mapping)
+                .map(|(image, mapping)| match image {
+                    ImageResource::Imported(image_view) => Some(image_view),
+                    ImageResource::Transient(_) => mapping.map(|i| physical_views[i].clone()),
+                })
+                .collect(),
+            buffers,
+        };
+
+        // The last access of each aliased image, or `None` before its first use.
+        let mut last_accesses: Vec<Option<AccessState>> = vec![None; physical_images.len()];
+
+        for (order, pass) in passes.into_iter().enumerate() {
+            let record_error = |error| RenderGraphError::RecordPass {
+                pass: pass.name.clone(),
+                error,
+            };
+
+            // Combine the accesses of the pass to each aliased image.
+            let mut accesses: Vec<(usize, AccessState)> = Vec::new();
+
+            for &(id, access, write) in &pass.image_accesses {
+                let Some(physical_index) = image_mapping[id.0 as usize] else {
+                    continue;
+                };
+
+                if !is_aliased[physical_index] {
+                    continue;
+                }
+
+                let state = AccessState::new(access, write);
+
+                match accesses
+                    .iter_mut()
+                    .find(|(index, _)| *index == physical_index)
+                {
+                    Some((_, combined)) => combined.combine(state),
+                    None => accesses.push((physical_index, state)),
+                }
+            }
+
+            let image_memory_barriers = accesses
+                .iter()
+                .filter_map(|&(physical_index, state)| {
+                    let last_access = last_accesses[physical_index].replace(state);
+                    let image = physical_views[physical_index].image().clone();
+
+                    match last_access {
+                        // The first use discards the contents that an earlier image left in the
+                        // memory, after all previous commands.
+                        None => Some(ImageMemoryBarrier {
+                            src_stages: PipelineStages::ALL_COMMANDS,
+                            src_access: AccessFlags::MEMORY_READ | AccessFlags::MEMORY_WRITE,
+                            dst_stages: state.stages,
+                            dst_access: state.access,
+                            old_layout: ImageLayout::Undefined,
+                            new_layout: state.layout,
+                            subresource_range: image.subresource_range(),
+                            ..ImageMemoryBarrier::image(image)
+                        }),
+                        Some(last_access)
+                            if last_access.write
+                                || state.write
+                                || last_access.layout != state.layout =>
+                        {
+                            Some(ImageMemoryBarrier {
+                                src_stages: last_access.stages,
+                                src_access: last_access.access,
+                                dst_stages: state.stages,
+                                dst_access: state.access,
+                                old_layout: last_access.layout,
+                                new_layout: state.layout,
+                                subresource_range: image.subresource_range(),
+                                ..ImageMemoryBarrier::image(image)
+                            })
+                        }
+                        Some(_) => None,
+                    }
+                })
+                .collect();
+            pipeline_barrier(builder, image_memory_barriers).map_err(record_error)?;
+
+            (pass.record)(builder, &resources).map_err(record_error)?;
+
+            // After the last use, complete all accesses before the memory is used by the next
+            // image, and leave the image in the layout that the command buffer expects.
+            let image_memory_barriers = accesses
+                .iter()
+                .filter(|&&(physical_index, _)| physical_images[physical_index].last_use == order)
+                .map(|&(physical_index, state)| {
+                    let image = physical_views[physical_index].image().clone();
+
+                    ImageMemoryBarrier {
+                        src_stages: state.stages,
+                        src_access: state.access,
+                        dst_stages: PipelineStages::ALL_COMMANDS,
+                        dst_access: AccessFlags::MEMORY_READ | AccessFlags::MEMORY_WRITE,
+                        old_layout: state.layout,
+                        new_layout: image.final_layout_requirement(),
+                        subresource_range: image.subresource_range(),
+                        ..ImageMemoryBarrier::image(image)
+                    }
+                })
+                .collect();
+            pipeline_barrier(builder, image_memory_barriers).map_err(record_error)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Records a pipeline barrier with `image_memory_barriers`, if there are any.
+fn pipeline_barrier<L, A>(
+    builder: &mut AutoCommandBufferBuilder<L, A>,
+    image_memory_barriers: Vec<ImageMemoryBarrier>,
+) -> Result<(), Box<ValidationError>>
+where
+    A: CommandBufferAllocator,
+{
+    if !image_memory_barriers.is_empty() {
+        // SAFETY: The barriers are only recorded for images that are excluded from automatic
+        // synchronization, and the old layout is always the layout of the previous access.
+        unsafe {
+            builder.pipeline_barrier(DependencyInfo {
+                image_memory_barriers: image_memory_barriers.into(),
+                ..Default::default()
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Greedily assigns memory blocks to images, which are given as their first and last use and,
+/// unless they need a dedicated allocation, their memory type bits and size. The images must be
+/// sorted by first use.
+///
+/// Two images can share a memory block if their lifetimes don't overlap and `find_memory_type`
+/// finds a memory type that is suitable for both. Returns the blocks and the block of each image.
+fn assign_memory_blocks(
+    images: impl IntoIterator<Item = (usize, usize, Option<(u32, DeviceSize)>)>,
+    find_memory_type: impl Fn(u32) -> Option<u32>,
+) -> (Vec<MemoryBlock>, Vec<Option<usize>>) {
+    let mut memory_blocks: Vec<MemoryBlock> = Vec::new();
+    let mut memory_mapping = Vec::new();
+
+    for (first_use, last_use, requirements) in images {
+        let Some((memory_type_bits, size)) = requirements
+            .filter(|&(memory_type_bits, _)| find_memory_type(memory_type_bits).is_some())
+        else {
+            memory_mapping.push(None);
+            continue;
+        };
+
+        let block_index = match memory_blocks.iter().position(|block| {
+            block.last_use < first_use
+                && find_memory_type(block.memory_type_bits & memory_type_bits).is_some()
+        }) {
+            Some(block_index) => block_index,
+            None => {
+                memory_blocks.push(MemoryBlock {
+                    memory_type_bits,
+                    memory_type_index: 0,
+                    size: 0,
+                    last_use: 0,
+                });
+                memory_blocks.len() - 1
+            }
+        };
+
+        // Every image is bound at offset 0, so the alignment is always satisfied.
+        let block = &mut memory_blocks[block_index];
+        block.memory_type_bits &= memory_type_bits;
+        block.memory_type_index = find_memory_type(block.memory_type_bits).unwrap();
+        block.size = block.size.max(size);
+        block.last_use = last_use;
+        memory_mapping.push(Some(block_index));
+    }
+
+    (memory_blocks, memory_mapping)
+}
+
+/// Device memory that the transient images of [`CompiledRenderGraph`]s are bound to.
+///
+/// Pass the same `TransientMemory` to every execution of a render graph, so that the memory
+/// blocks are reused instead of being allocated each time. A block is only reused once no image
+/// of an earlier execution is bound to it anymore, which is the case when the command buffer
+/// that the earlier execution was recorded into has been dropped.
+#[derive(Debug)]
+pub struct TransientMemory {
+    memory_allocator: Arc<dyn MemoryAllocator>,
+    blocks: Vec<Arc<DeviceMemory>>,
+}
+
+impl TransientMemory {
+    /// Creates an empty `TransientMemory`.
+    ///
+    /// The memory type of each block is chosen by `memory_allocator`, which also allocates the
+    /// transient images that can't share memory.
+    #[inline]
+    pub fn new(memory_allocator: Arc<dyn MemoryAllocator>) -> Self {
+        TransientMemory {
+            memory_allocator,
+            blocks: Vec::new(),
+        }
+    }
+
+    /// Returns the memory allocator.
+    #[inline]
+    pub fn memory_allocator(&self) -> &Arc<dyn MemoryAllocator> {
+        &self.memory_allocator
+    }
+
+    /// Returns the number of memory blocks, including the ones that are in use.
+    #[inline]
+    pub fn block_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Frees the memory blocks that no image is bound to.
+    ///
+    /// The blocks are otherwise kept until the `TransientMemory` is dropped, even if the graph
+    /// changes and no longer needs them.
+    #[inline]
+    pub fn free_unused(&mut self) {
+        self.blocks.retain(|block| Arc::strong_count(block) > 1);
+    }
+
+    /// Returns an unused block of at least `size` bytes, allocating a new one if needed.
+    fn block(
+        &mut self,
+        memory_type_index: u32,
+        size: DeviceSize,
+    ) -> Result<Arc<DeviceMemory>, Validated<VulkanError>> {
+        // A count of 1 means that only `self` holds the block, and nobody else can clone it.
+        if let Some(block) = self
+            .blocks
+            .iter()
+            .filter(|block| {
+                Arc::strong_count(block) == 1
+                    && block.memory_type_index() == memory_type_index
+                    && block.allocation_size() >= size
+            })
+            .min_by_key(|block| block.allocation_size())
+        {
+            return Ok(block.clone());
+        }
+
+        let block = Arc::new(DeviceMemory::allocate(
+            self.memory_allocator.device().clone(),
+            MemoryAllocateInfo {
+                allocation_size: size,
+                memory_type_index,
+                ..Default::default()
+            },
+        )?);
+        self.blocks.push(block.clone());
+
+        Ok(block)
+    }
+}
+
+/// The resources that are available to a pass while it is being recorded.
+pub struct PassResources {
+    images: Vec<Option<Arc<ImageView>>>,
+    buffers: Vec<Subbuffer<[u8]>>,
+}
+
+impl PassResources {
+    /// Returns the image view that backs `image`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `image` does not belong to the graph, or is a transient image that is not
+    ///   accessed by any executed pass.
+    #[inline]
+    pub fn image(&self, image: ImageId) -> &Arc<ImageView> {
+        self.images[image.0 as usize]
+            .as_ref()
+            .expect("the image is not used by any executed pass")
+    }
+
+    /// Returns the buffer that backs `buffer`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `buffer` does not belong to the graph.
+    #[inline]
+    pub fn buffer(&self, buffer: BufferId) -> &Subbuffer<[u8]> {
+        &self.buffers[buffer.0 as usize]
+    }
+}
+
+/// Identifies an image within a [`RenderGraph`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ImageId(u32);
+
+/// Identifies a buffer within a [`RenderGraph`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BufferId(u32);
+
+/// Describes a transient image of a [`RenderGraph`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TransientImageInfo {
+    /// The format of the image.
+    ///
+    /// The default value is `Format::UNDEFINED`, which must be overridden.
+    pub format: Format,
+
+    /// The extent of the image. If the depth is greater than 1, a 3D image is created.
+    ///
+    /// The default value is `[0; 3]`, which must be overridden.
+    pub extent: [u32; 3],
+
+    /// The number of array layers of the image.
+    ///
+    /// The default value is `1`.
+    pub array_layers: u32,
+
+    /// The number of mip levels of the image.
+    ///
+    /// The default value is `1`.
+    pub mip_levels: u32,
+
+    /// The number of samples per texel of the image.
+    ///
+    /// The default value is [`SampleCount::Sample1`].
+    pub samples: SampleCount,
+}
+
+impl Default for TransientImageInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            format: Format::UNDEFINED,
+            extent: [0; 3],
+            array_layers: 1,
+            mip_levels: 1,
+            samples: SampleCount::Sample1,
+        }
+    }
+}
+
+/// The way in which a pass accesses an image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ImageAccess {
+    /// The image is sampled in a shader.
+    Sampled,
+
+    /// The image is a storage image in a shader.
+    Storage,
+
+    /// The image is a color attachment.
+    ColorAttachment,
+
+    /// The image is a depth/stencil attachment.
+    DepthStencilAttachment,
+
+    /// The image is an input attachment.
+    InputAttachment,
+
+    /// The image is the source of a transfer command.
+    TransferSrc,
+
+    /// The image is the destination of a transfer command.
+    TransferDst,
+}
+
+impl ImageAccess {
+    /// Returns the image usage that is required for this kind of access.
+    #[inline]
+    pub fn usage(self) -> ImageUsage {
+        match self {
+            Self::Sampled => ImageUsage::SAMPLED,
+            Self::Storage => ImageUsage::STORAGE,
+            Self::ColorAttachment => ImageUsage::COLOR_ATTACHMENT,
+            Self::DepthStencilAttachment => ImageUsage::DEPTH_STENCIL_ATTACHMENT,
+            Self::InputAttachment => ImageUsage::INPUT_ATTACHMENT,
+            Self::TransferSrc => ImageUsage::TRANSFER_SRC,
+            Self::TransferDst => ImageUsage::TRANSFER_DST,
+        }
+    }
+
+    /// Returns the layout that an image is in during this kind of access, if the image is
+    /// synchronized by the graph.
+    #[inline]
+    pub fn layout(self) -> ImageLayout {
+        match self {
+            Self::Sampled | Self::InputAttachment => ImageLayout::ShaderReadOnlyOptimal,
+            Self::Storage => ImageLayout::General,
+            Self::ColorAttachment => ImageLayout::ColorAttachmentOptimal,
+            Self::DepthStencilAttachment => ImageLayout::DepthStencilAttachmentOptimal,
+            Self::TransferSrc => ImageLayout::TransferSrcOptimal,
+            Self::TransferDst => ImageLayout::TransferDstOptimal,
+        }
+    }
+}
+
+/// Error that can happen when compiling or executing a [`RenderGraph`].
+#[derive(Debug)]
+pub enum RenderGraphError {
+    /// A pass reads a transient image before any pass has written to it.
+    UninitializedImageRead { pass: String, image: ImageId },
+
+    /// Creating a transient image failed.
+    CreateImage(Validated<VulkanError>),
+
+    /// Allocating the memory that is shared by transient images failed.
+    AllocateMemory(Validated<VulkanError>),
+
+    /// Binding shared memory to a transient image failed.
+    BindMemory(Validated<VulkanError>),
+
+    /// Creating a transient image that has its own memory allocation failed.
+    AllocateImage(Validated<AllocateImageError>),
+
+    /// Creating a view of a transient image failed.
+    CreateImageView(Validated<VulkanError>),
+
+    /// Recording the commands of a pass failed.
+    RecordPass {
+        pass: String,
+        error: Box<ValidationError>,
+    },
+}
+
+impl Error for RenderGraphError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::UninitializedImageRead { .. } => None,
+            Self::CreateImage(err) => Some(err),
+            Self::AllocateMemory(err) => Some(err),
+            Self::BindMemory(err) => Some(err),
+            Self::AllocateImage(err) => Some(err),
+            Self::CreateImageView(err) => Some(err),
+            Self::RecordPass { error, .. } => Some(error),
+        }
+    }
+}
+
+impl Display for RenderGraphError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::UninitializedImageRead { pass, image } => write!(
+                f,
+                "pass `{}` reads transient image {:?} before any pass has written to it",
+                pass, image,
+            ),
+            Self::CreateImage(_) => write!(f, "creating a transient image failed"),
+            Self::AllocateMemory(_) => {
+                write!(f, "allocating memory for transient images failed")
+            }
+            Self::BindMemory(_) => write!(f, "binding memory to a transient image failed"),
+            Self::AllocateImage(_) => write!(f, "allocating a transient image failed"),
+            Self::CreateImageView(_) => write!(f, "creating a transient image view failed"),
+            Self::RecordPass { pass, .. } => {
+                write!(f, "recording the commands of pass `{}` failed", pass)
+            }
+        }
+    }
+}
+
+struct Pass<'a, A>
+where
+    A: CommandBufferAllocator,
+{
+    name: String,
+    image_accesses: Vec<(ImageId, ImageAccess, bool)>,
+    buffer_accesses: Vec<(BufferId, bool)>,
+    record: RecordFn<'a, A>,
+}
+
+enum ImageResource {
+    Imported(Arc<ImageView>),
+    Transient(TransientImageInfo),
+}
+
+impl ImageResource {
+    fn is_imported(&self) -> bool {
+        matches!(self, Self::Imported(_))
+    }
+}
+
+struct PhysicalImage {
+    info: TransientImageInfo,
+    usage: ImageUsage,
+    first_use: usize,
+    last_use: usize,
+}
+
+impl PhysicalImage {
+    fn create_info(&self) -> ImageCreateInfo {
+        let TransientImageInfo {
+            format,
+            extent,
+            array_layers,
+            mip_levels,
+            samples,
+        } = self.info;
+
+        ImageCreateInfo {
+            image_type: if extent[2] > 1 {
+                ImageType::Dim3d
+            } else {
+                ImageType::Dim2d
+            },
+            format,
+            extent,
+            array_layers,
+            mip_levels,
+            samples,
+            usage: self.usage,
+            ..Default::default()
+        }
+    }
+}
+
+struct MemoryBlock {
+    memory_type_bits: u32,
+    memory_type_index: u32,
+    size: DeviceSize,
+    last_use: usize,
+}
+
+/// How a pass accesses an aliased image, which the barriers synchronize.
+#[derive(Clone, Copy, Debug)]
+struct AccessState {
+    layout: ImageLayout,
+    stages: PipelineStages,
+    access: AccessFlags,
+    write: bool,
+}
+
+impl AccessState {
+    fn new(image_access: ImageAccess, write: bool) -> Self {
+        let (stages, read_access, write_access) = match image_access {
+            // The shader stages are not known, and depend on the queue.
+            ImageAccess::Sampled => (
+                PipelineStages::ALL_COMMANDS,
+                AccessFlags::SHADER_READ,
+                AccessFlags::empty(),
+            ),
+            ImageAccess::Storage => (
+                PipelineStages::ALL_COMMANDS,
+                AccessFlags::SHADER_READ,
+                AccessFlags::SHADER_WRITE,
+            ),
+            ImageAccess::ColorAttachment => (
+                PipelineStages::COLOR_ATTACHMENT_OUTPUT,
+                AccessFlags::COLOR_ATTACHMENT_READ,
+                AccessFlags::COLOR_ATTACHMENT_WRITE,
+            ),
+            ImageAccess::DepthStencilAttachment => (
+                PipelineStages::EARLY_FRAGMENT_TESTS | PipelineStages::LATE_FRAGMENT_TESTS,
+                AccessFlags::DEPTH_STENCIL_ATTACHMENT_READ,
+                AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE,
+            ),
+            ImageAccess::InputAttachment => (
+                PipelineStages::FRAGMENT_SHADER,
+                AccessFlags::INPUT_ATTACHMENT_READ,
+                AccessFlags::empty(),
+            ),
+            ImageAccess::TransferSrc => (
+                PipelineStages::ALL_TRANSFER,
+                AccessFlags::TRANSFER_READ,
+                AccessFlags::empty(),
+            ),
+            ImageAccess::TransferDst => (
+                PipelineStages::ALL_TRANSFER,
+                AccessFlags::empty(),
+                AccessFlags::TRANSFER_WRITE,
+            ),
+        };
+
+        AccessState {
+            layout: image_access.layout(),
+            stages,
+            access: if write {
+                read_access | write_access
+            } else {
+                read_access
+            },
+            write,
+        }
+    }
+
+    /// Adds another access of the same pass. If the layouts differ, `General` is used.
+    fn combine(&mut self, other: Self) {
+        if self.layout != other.layout {
+            self.layout = ImageLayout::General;
+        }
+
+        self.stages |= other.stages;
+        self.access |= other.access;
+        self.write |= other.write;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        assign_memory_blocks, ImageAccess, ImageId, RenderGraph, RenderGraphError,
+        TransientImageInfo, TransientMemory,
+    };
+    use std::sync::Arc;
+    use vulkano::{
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder,
+            ClearColorImageInfo, CommandBufferUsage,
+        },
+        device::{Device, DeviceCreateInfo, QueueCreateInfo},
+        format::Format,
+        image::{view::ImageView, Image, ImageCreateInfo, ImageUsage},
+        instance::Instance,
+        library::{
+            null::{recorded_commands, NullLoader},
+            VulkanLibrary,
+        },
+        memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+    };
+
+    fn transient_image_info(width: u32) -> TransientImageInfo {
+        TransientImageInfo {
+            format: Format::R8G8B8A8_UNORM,
+            extent: [width, 64, 1],
+            ..Default::default()
+        }
+    }
+
+    /// Adds a chain of passes that each read the image of the previous pass and write `images`
+    /// in turn, followed by a pass that reads the last image.
+    fn add_chain(graph: &mut RenderGraph<'_>, images: &[ImageId]) {
+        for (index, &image) in images.iter().enumerate() {
+            let mut pass = graph.add_pass(format!("pass {}", index));
+
+            if index > 0 {
+                pass = pass.read_image(images[index - 1], ImageAccess::Sampled);
+            }
+
+            pass.write_image(image, ImageAccess::ColorAttachment)
+                .execute(|_, _| Ok(()));
+        }
+
+        graph
+            .add_pass("present")
+            .read_image(*images.last().unwrap(), ImageAccess::Sampled)
+            .execute(|_, _| Ok(()));
+    }
+
+    #[test]
+    fn lifetimes() {
+        let mut graph = RenderGraph::new();
+        let images = [
+            graph.create_transient_image(transient_image_info(64)),
+            graph.create_transient_image(transient_image_info(64)),
+            graph.create_transient_image(transient_image_info(64)),
+            graph.create_transient_image(transient_image_info(128)),
+        ];
+        let unused = graph.create_transient_image(transient_image_info(64));
+        graph
+            .add_pass("unused")
+            .write_image(unused, ImageAccess::ColorAttachment)
+            .execute(|_, _| Ok(()));
+        add_chain(&mut graph, &images);
+
+        let compiled = graph.compile().unwrap();
+        assert_eq!(
+            compiled.passes().collect::<Vec<_>>(),
+            ["pass 0", "pass 1", "pass 2", "pass 3", "present"],
+        );
+
+        // The first and third image don't overlap and have the same description, so they share
+        // a physical image. The unused image is not created at all.
+        assert_eq!(compiled.physical_image_count(), 3);
+        assert_eq!(
+            compiled.image_mapping,
+            [Some(0), Some(1), Some(0), Some(2), None],
+        );
+        let lifetimes: Vec<_> = (compiled.physical_images.iter())
+            .map(|physical| (physical.first_use, physical.last_use))
+            .collect();
+        assert_eq!(lifetimes, [(0, 3), (1, 2), (3, 4)]);
+        assert_eq!(
+            compiled.physical_images[0].usage,
+            ImageUsage::COLOR_ATTACHMENT | ImageUsage::SAMPLED,
+        );
+    }
+
+    #[test]
+    fn uninitialized_read() {
+        let mut graph: RenderGraph<'_> = RenderGraph::new();
+        let image = graph.create_transient_image(transient_image_info(64));
+        graph
+            .add_pass("read")
+            .read_image(image, ImageAccess::Sampled)
+            .execute(|_, _| Ok(()));
+
+        assert!(matches!(
+            graph.compile(),
+            Err(RenderGraphError::UninitializedImageRead { pass, image: id })
+                if pass == "read" && id == image,
+        ));
+    }
+
+    #[test]
+    fn memory_blocks() {
+        // Memory types 0 and 1 exist.
+        let find_memory_type = |memory_type_bits: u32| {
+            (memory_type_bits & 0b11 != 0).then(|| memory_type_bits.trailing_zeros())
+        };
+
+        let (blocks, mapping) = assign_memory_blocks(
+            [
+                (0, 1, Some((0b11, 100))),
+                // Overlaps with the first image.
+                (1, 2, Some((0b11, 200))),
+                // Can use the block of the first image, restricting it to memory type 0.
+                (2, 3, Some((0b01, 300))),
+                // Needs a dedicated allocation.
+                (3, 4, None),
+                // The block of the first image has no memory type in common.
+                (4, 4, Some((0b10, 50))),
+                // No memory type is suitable.
+                (4, 4, Some((0b100, 50))),
+            ],
+            find_memory_type,
+        );
+
+        assert_eq!(mapping, [Some(0), Some(1), Some(0), None, Some(1), None]);
+        let blocks: Vec<_> = (blocks.iter())
+            .map(|block| (block.memory_type_index, block.size, block.last_use))
+            .collect();
+        assert_eq!(blocks, [(0, 300, 3), (1, 200, 4)]);
+    }
+
+    #[test]
+    fn execute() {
+        let library = VulkanLibrary::with_loader(NullLoader::new()).unwrap();
+        let instance = Instance::new(library, Default::default()).unwrap();
+        let physical_device = instance
+            .enumerate_physical_devices()
+            .unwrap()
+            .next()
+            .unwrap();
+        let (device, mut queues) = Device::new(
+            physical_device,
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo::default()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let queue = queues.next().unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let command_buffer_allocator =
+            StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let output = ImageView::new_default(
+            Image::new(
+                memory_allocator.clone(),
+                ImageCreateInfo {
+                    format: Format::R8G8B8A8_UNORM,
+                    extent: [64, 64, 1],
+                    usage: ImageUsage::TRANSFER_DST | ImageUsage::SAMPLED,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let mut transient_memory = TransientMemory::new(memory_allocator);
+
+        let record = |transient_memory: &mut TransientMemory| {
+            // Three images of different sizes, where the first and last can share memory.
+            let mut graph = RenderGraph::new();
+            let images = [
+                graph.create_transient_image(transient_image_info(64)),
+                graph.create_transient_image(transient_image_info(128)),
+                graph.create_transient_image(transient_image_info(32)),
+            ];
+            let output = graph.import_image(output.clone());
+
+            for (index, &image) in images.iter().chain([&output]).enumerate() {
+                let mut pass = graph.add_pass(format!("pass {}", index));
+
+                if index > 0 {
+                    pass = pass.read_image(images[index - 1], ImageAccess::Sampled);
+                }
+
+                pass.write_image(image, ImageAccess::TransferDst).execute(
+                    move |builder, resources| {
+                        builder.clear_color_image(ClearColorImageInfo::image(
+                            resources.image(image).image().clone(),
+                        ))?;
+
+                        Ok(())
+                    },
+                );
+            }
+
+            let mut builder = AutoCommandBufferBuilder::primary(
+                &command_buffer_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap();
+            graph
+                .compile()
+                .unwrap()
+                .execute(transient_memory, &mut builder)
+                .unwrap();
+
+            builder.build().unwrap()
+        };
+
+        let command_buffer = record(&mut transient_memory);
+        assert_eq!(transient_memory.block_count(), 2);
+        // The first and last image share memory, so the graph records six barriers for them:
+        // before each of the two passes that use each image, and after their last use. The
+        // automatic synchronization adds two more for the other images.
+        assert_eq!(
+            recorded_commands(&*command_buffer)
+                .into_iter()
+                .filter(|&command| command == "vkCmdPipelineBarrier")
+                .count(),
+            8,
+        );
+
+        // The blocks are still in use by the first command buffer.
+        let second_command_buffer = record(&mut transient_memory);
+        assert_eq!(transient_memory.block_count(), 4);
+
+        drop(command_buffer);
+        let _third_command_buffer = record(&mut transient_memory);
+        assert_eq!(transient_memory.block_count(), 4);
+
+        drop(second_command_buffer);
+        transient_memory.free_unused();
+        assert_eq!(transient_memory.block_count(), 2);
+    }
+}
@@ -13,7 +13,7 @@ use std::sync::Arc;
 use vulkano::{
     device::{Device, Queue},
     format::Format,
-    image::{view::ImageView, Image, ImageCreateInfo, ImageType, ImageUsage},
+    image::{view::ImageView, Image, ImageCreateInfo, ImageFormatInfo, ImageType, ImageUsage},
     memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
     swapchain::{self, PresentMode, Surface, Swapchain, SwapchainCreateInfo, SwapchainPresentInfo},
     sync::{self, GpuFuture},
@@ -97,17 +97,49 @@ impl VulkanoWindowRenderer {
             .physical_device()
             .surface_capabilities(&surface, Default::default())
             .unwrap();
+        let extra_image_usage = window_descriptor.swapchain_image_usage;
+        assert!(
+            surface_capabilities
+                .supported_usage_flags
+                .contains(extra_image_usage),
+            "the surface doesn't support the swapchain image usage {:?}",
+            extra_image_usage,
+        );
+        // Pick the first format that supports the requested usages
         let image_format = device
             .physical_device()
             .surface_formats(&surface, Default::default())
-            .unwrap()[0]
-            .0;
+            .unwrap()
+            .into_iter()
+            .map(|(format, _)| format)
+            .find(|&format| {
+                device
+                    .physical_device()
+                    .image_format_properties(ImageFormatInfo {
+                        format,
+                        usage: ImageUsage::COLOR_ATTACHMENT | extra_image_usage,
+                        ..Default::default()
+                    })
+                    .unwrap()
+                    .is_some()
+            })
+            .unwrap_or_else(|| {
+                panic!(
+                    "no surface format supports the swapchain image usage {:?}",
+                    extra_image_usage,
+                )
+            });
         let (swapchain, images) = Swapchain::new(device, surface, {
             let mut create_info = SwapchainCreateInfo {
                 min_image_count: surface_capabilities.min_image_count.max(2),
                 image_format,
                 image_extent: window.inner_size().into(),
-                image_usage: ImageUsage::COLOR_ATTACHMENT,
+                // Allow transfers to and from the swapchain images when possible, for
+                // `blit_fullscreen` and `capture_image`.
+                image_usage: ImageUsage::COLOR_ATTACHMENT
+                    | extra_image_usage
+                    | (surface_capabilities.supported_usage_flags
+                        & (ImageUsage::TRANSFER_SRC | ImageUsage::TRANSFER_DST)),
                 composite_alpha: surface_capabilities
                     .supported_composite_alpha
                     .into_iter()
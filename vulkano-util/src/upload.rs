@@ -0,0 +1,457 @@
+// Copyright (c) 2024 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Uploading many buffers and images to device-local memory with a single submission.
+//!
+//! Filling a device-local resource requires copying the data from a host-visible staging buffer.
+//! Doing this with a separate command buffer for every resource results in one queue submission
+//! and one future per resource, which becomes expensive when loading a scene with thousands of
+//! assets. [`UploadBatch`] instead records all staging copies into one command buffer, which is
+//! submitted once with [`UploadBatch::submit`].
+//!
+//! The resources returned by the batch can be used in other command buffers right away. The
+//! automatic synchronization makes sure that those command buffers wait for the copies, as long
+//! as they are executed after the future returned by `submit`.
+//!
+//! ```no_run
+//! # use std::sync::Arc;
+//! # use vulkano::{
+//! #     buffer::BufferUsage,
+//! #     command_buffer::allocator::StandardCommandBufferAllocator,
+//! #     device::Queue,
+//! #     format::Format,
+//! #     image::{ImageCreateInfo, ImageType, ImageUsage},
+//! #     memory::allocator::StandardMemoryAllocator,
+//! #     sync::{self, GpuFuture},
+//! # };
+//! # use vulkano_util::upload::UploadBatch;
+//! # let memory_allocator: Arc<StandardMemoryAllocator> = todo!();
+//! # let command_buffer_allocator: StandardCommandBufferAllocator = todo!();
+//! # let queue: Arc<Queue> = todo!();
+//! let mut batch =
+//!     UploadBatch::new(memory_allocator, &command_buffer_allocator, queue.clone()).unwrap();
+//!
+//! let vertices = [[0.0f32, 0.0], [1.0, 0.0], [0.0, 1.0]];
+//! let vertex_buffer = batch
+//!     .upload_buffer_from_iter(vertices, BufferUsage::VERTEX_BUFFER)
+//!     .unwrap();
+//! let texture = batch
+//!     .upload_image(
+//!         [255u8; 4 * 16 * 16],
+//!         ImageCreateInfo {
+//!             image_type: ImageType::Dim2d,
+//!             format: Format::R8G8B8A8_SRGB,
+//!             extent: [16, 16, 1],
+//!             usage: ImageUsage::SAMPLED,
+//!             ..Default::default()
+//!         },
+//!     )
+//!     .unwrap();
+//!
+//! batch
+//!     .submit(sync::now(queue.device().clone()))
+//!     .unwrap()
+//!     .wait(None)
+//!     .unwrap();
+//! ```
+
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+};
+use vulkano::{
+    buffer::{
+        AllocateBufferError, Buffer, BufferContents, BufferCreateInfo, BufferUsage, Subbuffer,
+    },
+    command_buffer::{
+        allocator::CommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferExecError,
+        CommandBufferExecFuture, CommandBufferUsage, CopyBufferInfo, CopyBufferToImageInfo,
+        PrimaryAutoCommandBuffer,
+    },
+    device::Queue,
+    image::{AllocateImageError, Image, ImageCreateInfo, ImageUsage},
+    memory::allocator::{AllocationCreateInfo, MemoryAllocator, MemoryTypeFilter},
+    sync::future::{FenceSignalFuture, GpuFuture},
+    Validated, ValidationError, VulkanError,
+};
+
+/// Records the copies of many uploads into a single command buffer.
+///
+/// See the [module-level documentation](self) for more.
+pub struct UploadBatch<A>
+where
+    A: CommandBufferAllocator,
+{
+    memory_allocator: Arc<dyn MemoryAllocator>,
+    queue: Arc<Queue>,
+    builder: AutoCommandBufferBuilder<PrimaryAutoCommandBuffer<A>, A>,
+    upload_count: usize,
+}
+
+impl<A> UploadBatch<A>
+where
+    A: CommandBufferAllocator + 'static,
+{
+    /// Creates a new `UploadBatch`, whose copies will be executed on `queue`.
+    pub fn new(
+        memory_allocator: Arc<dyn MemoryAllocator>,
+        command_buffer_allocator: &A,
+        queue: Arc<Queue>,
+    ) -> Result<Self, UploadError> {
+        let builder = AutoCommandBufferBuilder::primary(
+            command_buffer_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .map_err(UploadError::CreateCommandBuffer)?;
+
+        Ok(UploadBatch {
+            memory_allocator,
+            queue,
+            builder,
+            upload_count: 0,
+        })
+    }
+
+    /// Returns the queue that the copies will be executed on.
+    #[inline]
+    pub fn queue(&self) -> &Arc<Queue> {
+        &self.queue
+    }
+
+    /// Returns the number of uploads that have been recorded so far.
+    #[inline]
+    pub fn upload_count(&self) -> usize {
+        self.upload_count
+    }
+
+    /// Creates a device-local buffer containing `data`.
+    ///
+    /// `BufferUsage::TRANSFER_DST` is added to `usage` automatically.
+    pub fn upload_buffer<T>(
+        &mut self,
+        data: T,
+        usage: BufferUsage,
+    ) -> Result<Subbuffer<T>, UploadError>
+    where
+        T: BufferContents,
+    {
+        let staging_buffer = Buffer::from_data(
+            self.memory_allocator.clone(),
+            staging_buffer_create_info(),
+            staging_allocation_create_info(),
+            data,
+        )
+        .map_err(UploadError::AllocateBuffer)?;
+
+        let buffer = Buffer::new_sized(
+            self.memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: usage | BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            device_local_allocation_create_info(),
+        )
+        .map_err(UploadError::AllocateBuffer)?;
+
+        self.builder
+            .copy_buffer(CopyBufferInfo::buffers(staging_buffer, buffer.clone()))
+            .map_err(UploadError::RecordCommands)?;
+        self.upload_count += 1;
+
+        Ok(buffer)
+    }
+
+    /// Creates a device-local buffer containing the elements of `iter`.
+    ///
+    /// `BufferUsage::TRANSFER_DST` is added to `usage` automatically.
+    pub fn upload_buffer_from_iter<T, I>(
+        &mut self,
+        iter: I,
+        usage: BufferUsage,
+    ) -> Result<Subbuffer<[T]>, UploadError>
+    where
+        T: BufferContents,
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let staging_buffer = Buffer::from_iter(
+            self.memory_allocator.clone(),
+            staging_buffer_create_info(),
+            staging_allocation_create_info(),
+            iter,
+        )
+        .map_err(UploadError::AllocateBuffer)?;
+
+        let buffer = Buffer::new_slice(
+            self.memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: usage | BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            device_local_allocation_create_info(),
+            staging_buffer.len(),
+        )
+        .map_err(UploadError::AllocateBuffer)?;
+
+        self.builder
+            .copy_buffer(CopyBufferInfo::buffers(staging_buffer, buffer.clone()))
+            .map_err(UploadError::RecordCommands)?;
+        self.upload_count += 1;
+
+        Ok(buffer)
+    }
+
+    /// Creates a device-local image, and fills its first mip level with the texels of `iter`.
+    ///
+    /// The texels must be tightly packed, with all array layers one after another. The other mip
+    /// levels of the image, if any, are left uninitialized.
+    ///
+    /// `ImageUsage::TRANSFER_DST` is added to `create_info.usage` automatically.
+    pub fn upload_image<T, I>(
+        &mut self,
+        iter: I,
+        create_info: ImageCreateInfo,
+    ) -> Result<Arc<Image>, UploadError>
+    where
+        T: BufferContents,
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let staging_buffer = Buffer::from_iter(
+            self.memory_allocator.clone(),
+            staging_buffer_create_info(),
+            staging_allocation_create_info(),
+            iter,
+        )
+        .map_err(UploadError::AllocateBuffer)?;
+
+        let image = Image::new(
+            self.memory_allocator.clone(),
+            ImageCreateInfo {
+                usage: create_info.usage | ImageUsage::TRANSFER_DST,
+                ..create_info
+            },
+            device_local_allocation_create_info(),
+        )
+        .map_err(UploadError::AllocateImage)?;
+
+        self.builder
+            .copy_buffer_to_image(CopyBufferToImageInfo::buffer_image(
+                staging_buffer,
+                image.clone(),
+            ))
+            .map_err(UploadError::RecordCommands)?;
+        self.upload_count += 1;
+
+        Ok(image)
+    }
+
+    /// Builds the command buffer containing all recorded copies, and submits it to the queue
+    /// after `after` has completed.
+    ///
+    /// The staging buffers are kept alive until the returned future has been waited on.
+    pub fn submit<F>(
+        self,
+        after: F,
+    ) -> Result<FenceSignalFuture<CommandBufferExecFuture<F>>, UploadError>
+    where
+        F: GpuFuture,
+    {
+        let queue = self.queue.clone();
+        let command_buffer = self.build()?;
+
+        after
+            .then_execute(queue, command_buffer)
+            .map_err(UploadError::Execute)?
+            .then_signal_fence_and_flush()
+            .map_err(UploadError::Submit)
+    }
+
+    fn build(self) -> Result<Arc<PrimaryAutoCommandBuffer<A>>, UploadError> {
+        self.builder
+            .build()
+            .map_err(UploadError::CreateCommandBuffer)
+    }
+}
+
+fn staging_buffer_create_info() -> BufferCreateInfo {
+    BufferCreateInfo {
+        usage: BufferUsage::TRANSFER_SRC,
+        ..Default::default()
+    }
+}
+
+fn staging_allocation_create_info() -> AllocationCreateInfo {
+    AllocationCreateInfo {
+        memory_type_filter: MemoryTypeFilter::PREFER_HOST | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+        ..Default::default()
+    }
+}
+
+fn device_local_allocation_create_info() -> AllocationCreateInfo {
+    AllocationCreateInfo {
+        memory_type_filter: MemoryTypeFilter::PREFER_DEVICE,
+        ..Default::default()
+    }
+}
+
+/// Error that can happen when uploading resources with an [`UploadBatch`].
+#[derive(Clone, Debug)]
+pub enum UploadError {
+    /// Allocating a staging buffer or a destination buffer failed.
+    AllocateBuffer(Validated<AllocateBufferError>),
+
+    /// Allocating a destination image failed.
+    AllocateImage(Validated<AllocateImageError>),
+
+    /// Allocating or building the command buffer failed.
+    CreateCommandBuffer(Validated<VulkanError>),
+
+    /// Recording a copy command failed.
+    RecordCommands(Box<ValidationError>),
+
+    /// Executing the command buffer failed.
+    Execute(CommandBufferExecError),
+
+    /// Submitting the command buffer failed.
+    Submit(Validated<VulkanError>),
+}
+
+impl Error for UploadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::AllocateBuffer(err) => Some(err),
+            Self::AllocateImage(err) => Some(err),
+            Self::CreateCommandBuffer(err) => Some(err),
+            Self::RecordCommands(err) => Some(err),
+            Self::Execute(err) => Some(err),
+            Self::Submit(err) => Some(err),
+        }
+    }
+}
+
+impl Display for UploadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::AllocateBuffer(_) => write!(f, "allocating a buffer failed"),
+            Self::AllocateImage(_) => write!(f, "allocating an image failed"),
+            Self::CreateCommandBuffer(_) => write!(f, "creating the command buffer failed"),
+            Self::RecordCommands(_) => write!(f, "recording a copy command failed"),
+            Self::Execute(_) => write!(f, "executing the command buffer failed"),
+            Self::Submit(_) => write!(f, "submitting the command buffer failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UploadBatch;
+    use std::sync::Arc;
+    use vulkano::{
+        buffer::BufferUsage,
+        command_buffer::allocator::StandardCommandBufferAllocator,
+        device::{Device, DeviceCreateInfo, QueueCreateInfo},
+        format::Format,
+        image::{ImageCreateInfo, ImageUsage},
+        instance::Instance,
+        library::{
+            null::{recorded_commands, NullLoader},
+            VulkanLibrary,
+        },
+        memory::allocator::StandardMemoryAllocator,
+        sync,
+    };
+
+    #[test]
+    fn upload_batch() {
+        let library = VulkanLibrary::with_loader(NullLoader::new()).unwrap();
+        let instance = Instance::new(library, Default::default()).unwrap();
+        let physical_device = instance
+            .enumerate_physical_devices()
+            .unwrap()
+            .next()
+            .unwrap();
+        let (device, mut queues) = Device::new(
+            physical_device,
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo::default()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let queue = queues.next().unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let command_buffer_allocator =
+            StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let new_batch = || {
+            let mut batch = UploadBatch::new(
+                memory_allocator.clone(),
+                &command_buffer_allocator,
+                queue.clone(),
+            )
+            .unwrap();
+
+            let uniform_buffer = batch
+                .upload_buffer([1.0f32; 4], BufferUsage::UNIFORM_BUFFER)
+                .unwrap();
+            assert!(uniform_buffer
+                .buffer()
+                .usage()
+                .contains(BufferUsage::UNIFORM_BUFFER | BufferUsage::TRANSFER_DST));
+
+            let vertex_buffer = batch
+                .upload_buffer_from_iter([[0.0f32, 0.0], [1.0, 0.0]], BufferUsage::VERTEX_BUFFER)
+                .unwrap();
+            assert_eq!(vertex_buffer.len(), 2);
+
+            let image = batch
+                .upload_image(
+                    [255u8; 4 * 4 * 4],
+                    ImageCreateInfo {
+                        format: Format::R8G8B8A8_UNORM,
+                        extent: [4, 4, 1],
+                        usage: ImageUsage::SAMPLED,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            assert!(image
+                .usage()
+                .contains(ImageUsage::SAMPLED | ImageUsage::TRANSFER_DST));
+
+            assert_eq!(batch.upload_count(), 3);
+
+            batch
+        };
+
+        // All copies are recorded into one command buffer.
+        let command_buffer = new_batch().build().unwrap();
+        let commands: Vec<_> = recorded_commands(&*command_buffer)
+            .into_iter()
+            .filter(|&command| command != "vkCmdPipelineBarrier")
+            .collect();
+        assert_eq!(
+            commands,
+            [
+                "vkCmdCopyBuffer",
+                "vkCmdCopyBuffer",
+                "vkCmdCopyBufferToImage",
+            ],
+        );
+
+        new_batch()
+            .submit(sync::now(device))
+            .unwrap()
+            .wait(None)
+            .unwrap();
+    }
+}
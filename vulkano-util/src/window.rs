@@ -16,7 +16,10 @@
 use crate::{context::VulkanoContext, renderer::VulkanoWindowRenderer};
 use ahash::HashMap;
 use std::collections::hash_map::{Iter, IterMut};
-use vulkano::swapchain::{PresentMode, SwapchainCreateInfo};
+use vulkano::{
+    image::ImageUsage,
+    swapchain::{PresentMode, SwapchainCreateInfo},
+};
 use winit::{
     // dpi::LogicalSize,
     // window::{CursorGrabMode, WindowId},
@@ -355,6 +358,12 @@ pub struct WindowDescriptor {
     ///
     /// Used to select whether or not VSync is used
     pub present_mode: PresentMode,
+    /// Additional usages of the swapchain images, on top of `COLOR_ATTACHMENT`.
+    ///
+    /// For example, `STORAGE` allows a compute shader to write the final image directly, and
+    /// `TRANSFER_SRC` allows copying it for screenshots. The usages must be supported by the
+    /// surface, and the swapchain image format is chosen among the formats that support them.
+    pub swapchain_image_usage: ImageUsage,
     /// Sets whether the window is resizable.
     pub resizable: bool,
     /// Sets whether the window should have borders and bars.
@@ -380,6 +389,7 @@ impl Default for WindowDescriptor {
             resize_constraints: WindowResizeConstraints::default(),
             scale_factor_override: None,
             present_mode: PresentMode::Fifo,
+            swapchain_image_usage: ImageUsage::empty(),
             resizable: true,
             decorations: true,
             cursor_locked: false,
@@ -231,6 +231,24 @@ fn formats_output(members: &[FormatMember]) -> TokenStream {
                 .map(|ty| quote! { Self::#name => Some(ChromaSampling::#ty), })
         },
     );
+    let srgb_pairs: Vec<_> = members
+        .iter()
+        .filter_map(|FormatMember { name, .. }| {
+            let name_string = name.to_string();
+            let unorm_name = name_string.replace("_SRGB", "_UNORM");
+
+            (unorm_name != name_string)
+                .then(|| members.iter().find(|member| member.name == unorm_name))
+                .flatten()
+                .map(|unorm| (&unorm.name, name))
+        })
+        .collect();
+    let srgb_counterpart_items = srgb_pairs
+        .iter()
+        .map(|(unorm, srgb)| quote! { Self::#unorm => Some(Self::#srgb), });
+    let unorm_counterpart_items = srgb_pairs
+        .iter()
+        .map(|(unorm, srgb)| quote! { Self::#srgb => Some(Self::#unorm), });
     let try_from_items = members.iter().map(|FormatMember { name, ffi_name, .. }| {
         quote! { ash::vk::Format::#ffi_name => Ok(Self::#name), }
     });
@@ -345,6 +363,7 @@ fn formats_output(members: &[FormatMember]) -> TokenStream {
     quote! {
         /// An enumeration of all the possible formats.
         #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         #[repr(i32)]
         #[allow(non_camel_case_types)]
         #[non_exhaustive]
@@ -471,6 +490,28 @@ fn formats_output(members: &[FormatMember]) -> TokenStream {
                 }
             }
 
+            /// For UNORM formats that have an equivalent sRGB format, returns that format.
+            /// Returns `None` for all other formats.
+            ///
+            /// The two formats have the same layout, but reading from the sRGB format converts the
+            /// color components from the sRGB color space to linear, and writing to it converts
+            /// them back.
+            pub fn srgb_counterpart(self) -> Option<Format> {
+                match self {
+                    #(#srgb_counterpart_items)*
+                    _ => None,
+                }
+            }
+
+            /// For sRGB formats, returns the equivalent UNORM format.
+            /// Returns `None` for all other formats.
+            pub fn unorm_counterpart(self) -> Option<Format> {
+                match self {
+                    #(#unorm_counterpart_items)*
+                    _ => None,
+                }
+            }
+
             /// For YCbCr (YUV) formats, returns the way in which the chroma components are
             /// represented. Returns `None` for non-YCbCr formats.
             ///
@@ -29,7 +29,12 @@ use crate::{
     VulkanError, VulkanObject,
 };
 use smallvec::SmallVec;
-use std::{mem::MaybeUninit, num::NonZeroU64, ptr, sync::Arc};
+use std::{
+    mem::MaybeUninit,
+    num::NonZeroU64,
+    ptr,
+    sync::{atomic::Ordering, Arc},
+};
 
 /// A raw buffer, with no memory backing it.
 ///
@@ -194,6 +199,8 @@ impl RawBuffer {
                 .unwrap();
         }
 
+        device.counters.buffers.fetch_add(1, Ordering::Relaxed);
+
         RawBuffer {
             handle,
             device: InstanceOwnedDebugWrapper(device),
@@ -590,6 +597,8 @@ impl Drop for RawBuffer {
             let fns = self.device.fns();
             (fns.v1_0.destroy_buffer)(self.device.handle(), self.handle, ptr::null());
         }
+
+        self.device.counters.buffers.fetch_sub(1, Ordering::Relaxed);
     }
 }
 
@@ -73,26 +73,23 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(khr_video_decode_queue)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be bound as a transform feedback buffer, to capture vertex outputs.
     TRANSFORM_FEEDBACK_BUFFER = TRANSFORM_FEEDBACK_BUFFER_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_transform_feedback)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be used as a transform feedback counter buffer.
     TRANSFORM_FEEDBACK_COUNTER_BUFFER = TRANSFORM_FEEDBACK_COUNTER_BUFFER_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_transform_feedback)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be used as the predicate of conditional rendering.
     CONDITIONAL_RENDERING = CONDITIONAL_RENDERING_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_conditional_rendering)]),
-    ]),*/
+    ]),
 
     /// The buffer can be used as input data for an acceleration structure build operation.
     ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY = ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_KHR
@@ -106,13 +103,12 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(khr_acceleration_structure)]),
     ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be used as a shader binding table for ray tracing commands.
     SHADER_BINDING_TABLE = SHADER_BINDING_TABLE_KHR
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(khr_ray_tracing_pipeline)]),
         RequiresAllOf([DeviceExtension(nv_ray_tracing)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -23,7 +23,7 @@ use super::{
 use crate::{
     device::{Device, DeviceOwned},
     instance::InstanceOwnedDebugWrapper,
-    Validated, VulkanError,
+    Validated, ValidationError, VulkanError,
 };
 use crossbeam_queue::ArrayQueue;
 use smallvec::{IntoIter, SmallVec};
@@ -173,6 +173,34 @@ impl StandardCommandBufferAllocator {
         }
     }
 
+    /// Trims the [`CommandPool`] that's currently in use for the given queue family index on the
+    /// current thread, which returns memory that the pool doesn't use anymore to the system.
+    ///
+    /// Unlike [`try_reset_pool`], this can be done while command buffers allocated from the pool
+    /// still exist. It is useful after a frame that allocated many more command buffers than
+    /// usual.
+    ///
+    /// This has no effect if the entry wasn't initialized yet or if the entry was [cleared].
+    ///
+    /// The device API version must be at least 1.1, or the
+    /// [`khr_maintenance1`](crate::device::DeviceExtensions::khr_maintenance1) extension must be
+    /// enabled on the device.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `queue_family_index` is not less than the number of queue families.
+    ///
+    /// [`try_reset_pool`]: Self::try_reset_pool
+    /// [cleared]: Self::clear
+    #[inline]
+    pub fn trim_pool(&self, queue_family_index: u32) -> Result<(), Box<ValidationError>> {
+        if let Some(entry) = unsafe { &*self.entry(queue_family_index) }.as_ref() {
+            entry.pool.inner.inner.trim()
+        } else {
+            Ok(())
+        }
+    }
+
     /// Clears the entry for the given queue family index and the current thread. This does not
     /// mean that the pools are dropped immediately. A pool is kept alive for as long as command
     /// buffers allocated from it exist.
@@ -11,6 +11,8 @@ use super::{
     CommandInfo, PrimaryAutoCommandBuffer, RenderPassCommand, Resource, ResourceUseRef2,
     SubmitState,
 };
+#[cfg(feature = "trace")]
+use crate::command_buffer::trace::{CommandTrace, TraceRecorder, TracedCommand};
 use crate::{
     buffer::{Buffer, IndexBuffer, Subbuffer},
     command_buffer::{
@@ -25,29 +27,40 @@ use crate::{
     },
     descriptor_set::{DescriptorSetResources, DescriptorSetWithOffsets},
     device::{Device, DeviceOwned},
-    image::{view::ImageView, Image, ImageAspects, ImageLayout, ImageSubresourceRange},
+    image::{
+        view::ImageView, Image, ImageAspects, ImageLayout, ImageSubresourceRange, SampleCount,
+    },
     pipeline::{
         graphics::{
-            color_blend::LogicOp,
+            color_blend::{AttachmentBlend, ColorComponents, LogicOp},
             depth_stencil::{CompareOp, StencilOps},
+            discard_rectangle::DiscardRectangleMode,
+            fragment_shading_rate::FragmentShadingRateState,
             input_assembly::PrimitiveTopology,
-            rasterization::{CullMode, DepthBiasState, FrontFace, LineStipple},
+            multisample::SampleLocationsInfo,
+            rasterization::{
+                CullMode, DepthBiasState, FrontFace, LineStipple, PolygonMode, ProvokingVertexMode,
+            },
             subpass::PipelineRenderingCreateInfo,
+            tessellation::TessellationDomainOrigin,
+            vertex_input::VertexInputState,
             viewport::{Scissor, Viewport},
         },
         ComputePipeline, DynamicState, GraphicsPipeline, PipelineBindPoint, PipelineLayout,
+        RayTracingPipeline,
     },
     query::{QueryControlFlags, QueryPool},
     range_map::RangeMap,
     range_set::RangeSet,
     render_pass::{Framebuffer, Subpass},
+    shader::{object::ShaderObject, ShaderStage},
     sync::{
         AccessFlags, BufferMemoryBarrier, DependencyFlags, DependencyInfo, ImageMemoryBarrier,
         PipelineStageAccessFlags, PipelineStages,
     },
-    DeviceSize, Validated, ValidationError, VulkanError,
+    DeviceSize, Validated, ValidationError, VulkanError, VulkanObject,
 };
-use ahash::HashMap;
+use ahash::{HashMap, HashSet};
 use parking_lot::Mutex;
 use smallvec::SmallVec;
 use std::{
@@ -73,6 +86,11 @@ where
         Box<dyn Fn(&mut UnsafeCommandBufferBuilder<A>) + Send + Sync + 'static>,
     )>,
     pub(in crate::command_buffer) builder_state: CommandBufferBuilderState,
+    pub(in crate::command_buffer) auto_sync_disabled: bool,
+    auto_sync_disabled_buffers: HashSet<Arc<Buffer>>,
+    pub(in crate::command_buffer) auto_sync_disabled_images: HashSet<Arc<Image>>,
+    #[cfg(feature = "trace")]
+    trace: Option<TraceRecorder>,
     _data: PhantomData<L>,
 }
 
@@ -245,6 +263,11 @@ where
             inner,
             commands: Vec::new(),
             builder_state,
+            auto_sync_disabled: false,
+            auto_sync_disabled_buffers: HashSet::default(),
+            auto_sync_disabled_images: HashSet::default(),
+            #[cfg(feature = "trace")]
+            trace: None,
             _data: PhantomData,
         })
     }
@@ -260,32 +283,7 @@ where
         ),
         Validated<VulkanError>,
     > {
-        let mut auto_sync_state = AutoSyncState::new(
-            self.device().clone(),
-            self.inner.level(),
-            self.inner
-                .inheritance_info()
-                .as_ref()
-                .map_or(false, |info| info.render_pass.is_some()),
-        );
-
-        // Add barriers between the commands.
-        for (command_info, _) in self.commands.iter() {
-            auto_sync_state.add_command(command_info).map_err(|err| {
-                Box::new(ValidationError {
-                    problem: format!(
-                        "unsolvable resource conflict between:\n\
-                        command resource use: {:?}\n\
-                        previous conflicting command resource use: {:?}",
-                        err.current_use_ref, err.previous_use_ref,
-                    )
-                    .into(),
-                    ..Default::default()
-                })
-            })?;
-        }
-
-        let (mut barriers, resources_usage, secondary_resources_usage) = auto_sync_state.build();
+        let (mut barriers, resources_usage, secondary_resources_usage) = self.auto_sync()?;
         let final_barrier_index = self.commands.len();
 
         // Record all the commands and barriers to the inner command buffer.
@@ -334,6 +332,49 @@ where
             secondary_resources_usage,
         ))
     }
+
+    /// Computes the barriers that automatic synchronization inserts between the commands, keyed
+    /// by the index of the command that they must be recorded before.
+    pub(super) fn auto_sync(
+        &mut self,
+    ) -> Result<
+        (
+            HashMap<usize, Vec<DependencyInfo>>,
+            CommandBufferResourcesUsage,
+            SecondaryCommandBufferResourcesUsage,
+        ),
+        Box<ValidationError>,
+    > {
+        let mut auto_sync_state = AutoSyncState::new(
+            self.device().clone(),
+            self.inner.level(),
+            self.inner
+                .inheritance_info()
+                .as_ref()
+                .map_or(false, |info| info.render_pass.is_some()),
+            self.auto_sync_disabled,
+            take(&mut self.auto_sync_disabled_buffers),
+            take(&mut self.auto_sync_disabled_images),
+        );
+
+        // Add barriers between the commands.
+        for (command_info, _) in self.commands.iter() {
+            auto_sync_state.add_command(command_info).map_err(|err| {
+                Box::new(ValidationError {
+                    problem: format!(
+                        "unsolvable resource conflict between:\n\
+                        command resource use: {:?}\n\
+                        previous conflicting command resource use: {:?}",
+                        err.current_use_ref, err.previous_use_ref,
+                    )
+                    .into(),
+                    ..Default::default()
+                })
+            })?;
+        }
+
+        Ok(auto_sync_state.build())
+    }
 }
 
 impl<A> AutoCommandBufferBuilder<PrimaryAutoCommandBuffer<A>, A>
@@ -360,6 +401,15 @@ where
             .into());
         }
 
+        if self.builder_state.conditional_rendering.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "conditional rendering is still active".into(),
+                vuids: &["VUID-vkEndCommandBuffer-None-01978"],
+                ..Default::default()
+            })
+            .into());
+        }
+
         // TODO:
         // VUID-vkEndCommandBuffer-commandBuffer-01815
 
@@ -389,6 +439,15 @@ where
             .into());
         }
 
+        if self.builder_state.conditional_rendering.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "conditional rendering is still active".into(),
+                vuids: &["VUID-vkEndCommandBuffer-None-01978"],
+                ..Default::default()
+            })
+            .into());
+        }
+
         let submit_state = match self.inner.usage() {
             CommandBufferUsage::MultipleSubmit => SubmitState::ExclusiveUse {
                 in_use: AtomicBool::new(false),
@@ -414,20 +473,143 @@ impl<L, A> AutoCommandBufferBuilder<L, A>
 where
     A: CommandBufferAllocator,
 {
+    /// Disables automatic synchronization for all resources in this command buffer.
+    ///
+    /// This is equivalent to calling
+    /// [`disable_auto_sync_for_buffer`](Self::disable_auto_sync_for_buffer) and
+    /// [`disable_auto_sync_for_image`](Self::disable_auto_sync_for_image) for every resource that
+    /// the command buffer uses, including resources used by commands that were added before
+    /// calling this function. Resource uses are still tracked and validated, so that the command
+    /// buffer can check for conflicting uses when it is submitted, but no pipeline barriers or
+    /// layout transitions are inserted.
+    ///
+    /// The builder also stops leaving out descriptor sets and vertex buffers that are already
+    /// bound, and push constants that are already set, so every bind and push is recorded.
+    ///
+    /// # Safety
+    ///
+    /// - All accesses to resources within the command buffer must be synchronized manually with
+    ///   [`pipeline_barrier`](Self::pipeline_barrier) or events, both with each other and with
+    ///   the commands that were submitted to the queue previously.
+    /// - Each command that accesses an image must find it in the layout that the command expects,
+    ///   and at the end of a primary command buffer, each image must have been transitioned to
+    ///   its [`final_layout_requirement`](Image::final_layout_requirement).
+    #[inline]
+    pub unsafe fn disable_auto_sync(&mut self) -> &mut Self {
+        self.auto_sync_disabled = true;
+
+        self
+    }
+
+    /// Excludes `buffer` from automatic synchronization in this command buffer.
+    ///
+    /// Commands that access `buffer` are still tracked, so that the command buffer can check for
+    /// conflicting uses when it is submitted, but no pipeline barriers are inserted for `buffer`.
+    /// This can improve performance when the automatic barriers are more conservative than
+    /// necessary, such as when ping-ponging between the halves of a buffer.
+    ///
+    /// # Safety
+    ///
+    /// - All accesses to `buffer` within the command buffer must be synchronized manually with
+    ///   [`pipeline_barrier`](Self::pipeline_barrier) or events, both with each other and with
+    ///   the commands that were submitted to the queue previously.
+    #[inline]
+    pub unsafe fn disable_auto_sync_for_buffer(&mut self, buffer: Arc<Buffer>) -> &mut Self {
+        self.auto_sync_disabled_buffers.insert(buffer);
+
+        self
+    }
+
+    /// Excludes `image` from automatic synchronization in this command buffer.
+    ///
+    /// Commands that access `image` are still tracked, so that the command buffer can check for
+    /// conflicting uses when it is submitted, but no pipeline barriers or layout transitions are
+    /// inserted for `image`.
+    ///
+    /// # Safety
+    ///
+    /// - All accesses to `image` within the command buffer must be synchronized manually with
+    ///   [`pipeline_barrier`](Self::pipeline_barrier) or events, both with each other and with
+    ///   the commands that were submitted to the queue previously.
+    /// - Each command that accesses `image` must find it in the layout that the command expects,
+    ///   and at the end of a primary command buffer, `image` must have been transitioned to its
+    ///   [`final_layout_requirement`](Image::final_layout_requirement).
+    #[inline]
+    pub unsafe fn disable_auto_sync_for_image(&mut self, image: Arc<Image>) -> &mut Self {
+        self.auto_sync_disabled_images.insert(image);
+
+        self
+    }
+
+    /// Starts recording the commands that are added to the builder into a [`CommandTrace`].
+    ///
+    /// Commands that were added before calling this function are not included in the trace. If a
+    /// trace was already being recorded, it is discarded.
+    ///
+    /// Only transfer and clear commands are recorded in a form that can be replayed. Other
+    /// commands are recorded with only their name and the resources they access.
+    #[cfg(feature = "trace")]
+    #[inline]
+    pub fn start_trace(&mut self) -> &mut Self {
+        self.trace = Some(TraceRecorder::default());
+
+        self
+    }
+
+    /// Stops recording commands, and returns the trace of the commands that were added since
+    /// [`start_trace`](Self::start_trace) was called.
+    ///
+    /// Returns `None` if no trace was being recorded.
+    #[cfg(feature = "trace")]
+    #[inline]
+    pub fn take_trace(&mut self) -> Option<CommandTrace> {
+        self.trace.take().map(TraceRecorder::into_trace)
+    }
+
+    /// If a trace is being recorded, records the detailed version of the command that is about
+    /// to be added.
+    #[cfg(feature = "trace")]
+    pub(in crate::command_buffer) fn trace_command(
+        &mut self,
+        command: impl FnOnce(&mut TraceRecorder) -> TracedCommand,
+    ) {
+        if let Some(trace) = &mut self.trace {
+            let command = command(trace);
+            trace.set_pending(command);
+        }
+    }
+
     pub(in crate::command_buffer) fn add_command(
         &mut self,
         name: &'static str,
         used_resources: Vec<(ResourceUseRef2, Resource)>,
         record_func: impl Fn(&mut UnsafeCommandBufferBuilder<A>) + Send + Sync + 'static,
     ) {
-        self.commands.push((
-            CommandInfo {
-                name,
-                used_resources,
-                render_pass: RenderPassCommand::None,
-            },
-            Box::new(record_func),
-        ));
+        self.add_command_with_bound_resources(name, None, used_resources, record_func);
+    }
+
+    /// Like `add_command`, but also takes the resources that the command uses through the state
+    /// that is bound for the pipeline, as returned by `bound_resources`.
+    pub(in crate::command_buffer) fn add_command_with_bound_resources(
+        &mut self,
+        name: &'static str,
+        bound_resources: Option<Arc<[(ResourceUseRef2, Resource)]>>,
+        used_resources: Vec<(ResourceUseRef2, Resource)>,
+        record_func: impl Fn(&mut UnsafeCommandBufferBuilder<A>) + Send + Sync + 'static,
+    ) {
+        let command_info = CommandInfo {
+            name,
+            bound_resources,
+            used_resources,
+            render_pass: RenderPassCommand::None,
+        };
+
+        #[cfg(feature = "trace")]
+        if let Some(trace) = &mut self.trace {
+            trace.add_command(name, command_info.used_resources());
+        }
+
+        self.commands.push((command_info, Box::new(record_func)));
     }
 
     pub(in crate::command_buffer) fn add_render_pass_begin(
@@ -436,9 +618,15 @@ where
         used_resources: Vec<(ResourceUseRef2, Resource)>,
         record_func: impl Fn(&mut UnsafeCommandBufferBuilder<A>) + Send + Sync + 'static,
     ) {
+        #[cfg(feature = "trace")]
+        if let Some(trace) = &mut self.trace {
+            trace.add_command(name, &used_resources);
+        }
+
         self.commands.push((
             CommandInfo {
                 name,
+                bound_resources: None,
                 used_resources,
                 render_pass: RenderPassCommand::Begin,
             },
@@ -452,9 +640,15 @@ where
         used_resources: Vec<(ResourceUseRef2, Resource)>,
         record_func: impl Fn(&mut UnsafeCommandBufferBuilder<A>) + Send + Sync + 'static,
     ) {
+        #[cfg(feature = "trace")]
+        if let Some(trace) = &mut self.trace {
+            trace.add_command(name, &used_resources);
+        }
+
         self.commands.push((
             CommandInfo {
                 name,
+                bound_resources: None,
                 used_resources,
                 render_pass: RenderPassCommand::End,
             },
@@ -484,6 +678,12 @@ struct AutoSyncState {
     buffers: HashMap<Arc<Buffer>, RangeMap<DeviceSize, BufferState>>,
     images: HashMap<Arc<Image>, RangeMap<DeviceSize, ImageState>>,
     secondary_resources_usage: SecondaryCommandBufferResourcesUsage,
+
+    // Resources for which the user has taken over the responsibility of synchronization.
+    // If `disabled_all` is true, this applies to all resources.
+    disabled_all: bool,
+    disabled_buffers: HashSet<Arc<Buffer>>,
+    disabled_images: HashSet<Arc<Image>>,
 }
 
 impl AutoSyncState {
@@ -491,6 +691,9 @@ impl AutoSyncState {
         device: Arc<Device>,
         level: CommandBufferLevel,
         has_inherited_render_pass: bool,
+        disabled_all: bool,
+        disabled_buffers: HashSet<Arc<Buffer>>,
+        disabled_images: HashSet<Arc<Image>>,
     ) -> Self {
         Self {
             device,
@@ -507,6 +710,9 @@ impl AutoSyncState {
             buffers: HashMap::default(),
             images: HashMap::default(),
             secondary_resources_usage: Default::default(),
+            disabled_all,
+            disabled_buffers,
+            disabled_images,
         }
     }
 
@@ -533,6 +739,10 @@ impl AutoSyncState {
             };
 
             for (image, range_map) in self.images.iter_mut() {
+                if self.disabled_all || self.disabled_images.contains(image) {
+                    continue;
+                }
+
                 for (range, state) in range_map
                     .iter_mut()
                     .filter(|(_range, state)| state.final_layout != state.current_layout)
@@ -562,6 +772,10 @@ impl AutoSyncState {
                 .push(final_barrier);
         }
 
+        for dependency_infos in self.barriers.values_mut() {
+            merge_dependency_infos(dependency_infos);
+        }
+
         let mut resources_usage = CommandBufferResourcesUsage {
             buffers: self
                 .buffers
@@ -667,13 +881,9 @@ impl AutoSyncState {
         &self,
         command_info: &CommandInfo,
     ) -> Result<(), UnsolvableResourceConflict> {
-        let &CommandInfo {
-            name: command_name,
-            ref used_resources,
-            ..
-        } = command_info;
+        let command_name = command_info.name;
 
-        for (use_ref, resource) in used_resources {
+        for (use_ref, resource) in command_info.used_resources() {
             match *resource {
                 Resource::Buffer {
                     ref buffer,
@@ -756,6 +966,10 @@ impl AutoSyncState {
         // barrier before the start of the render pass.
         let last_allowed_barrier_index = self.latest_render_pass_enter.unwrap_or(command_index);
 
+        if self.disabled_all || self.disabled_buffers.contains(buffer.buffer()) {
+            return None;
+        }
+
         range.start += buffer.offset();
         range.end += buffer.offset();
 
@@ -799,6 +1013,10 @@ impl AutoSyncState {
         let last_allowed_barrier_index =
             self.latest_render_pass_enter.unwrap_or(self.command_index);
 
+        if self.disabled_all || self.disabled_images.contains(image) {
+            return None;
+        }
+
         let range_map = self.images.get(image)?;
 
         for range in image.iter_ranges(subresource_range) {
@@ -849,13 +1067,9 @@ impl AutoSyncState {
     ///   be in when the command starts, and the image layout that the image will be transitioned to
     ///   during the command. When it comes to buffers, you should pass `Undefined` for both.
     fn add_resources(&mut self, command_info: &CommandInfo) {
-        let &CommandInfo {
-            name: command_name,
-            ref used_resources,
-            ..
-        } = command_info;
+        let command_name = command_info.name;
 
-        for (use_ref, resource) in used_resources {
+        for (use_ref, resource) in command_info.used_resources() {
             match *resource {
                 Resource::Buffer {
                     ref buffer,
@@ -869,7 +1083,7 @@ impl AutoSyncState {
                             resource_in_command: use_ref.resource_in_command,
                             secondary_use_ref: use_ref.secondary_use_ref,
                         },
-                        buffer.clone(),
+                        buffer,
                         range.clone(),
                         memory_access,
                     );
@@ -888,7 +1102,7 @@ impl AutoSyncState {
                             resource_in_command: use_ref.resource_in_command,
                             secondary_use_ref: use_ref.secondary_use_ref,
                         },
-                        image.clone(),
+                        image,
                         subresource_range.clone(),
                         memory_access,
                         start_layout,
@@ -902,18 +1116,22 @@ impl AutoSyncState {
     fn add_buffer(
         &mut self,
         use_ref: ResourceUseRef,
-        buffer: Subbuffer<[u8]>,
+        buffer: &Subbuffer<[u8]>,
         mut range: Range<DeviceSize>,
         memory_access: PipelineStageAccessFlags,
     ) {
-        self.secondary_resources_usage
-            .buffers
-            .push(SecondaryCommandBufferBufferUsage {
-                use_ref,
-                buffer: buffer.clone(),
-                range: range.clone(),
-                memory_access,
-            });
+        // Only secondary command buffers need to keep a list of all uses, for when they are
+        // executed in a primary command buffer.
+        if self.level == CommandBufferLevel::Secondary {
+            self.secondary_resources_usage
+                .buffers
+                .push(SecondaryCommandBufferBufferUsage {
+                    use_ref,
+                    buffer: buffer.clone(),
+                    range: range.clone(),
+                    memory_access,
+                });
+        }
 
         // Barriers work differently in render passes, so if we're in one, we can only insert a
         // barrier before the start of the render pass.
@@ -923,10 +1141,12 @@ impl AutoSyncState {
         range.start += buffer.offset();
         range.end += buffer.offset();
 
-        let range_map = self
-            .buffers
-            .entry(buffer.buffer().clone())
-            .or_insert_with(|| {
+        let auto_sync = !(self.disabled_all || self.disabled_buffers.contains(buffer.buffer()));
+
+        // Look the buffer up before inserting it, so that it's only cloned on its first use.
+        if !self.buffers.contains_key(buffer.buffer()) {
+            self.buffers.insert(
+                buffer.buffer().clone(),
                 [(
                     0..buffer.buffer().size(),
                     BufferState {
@@ -936,8 +1156,11 @@ impl AutoSyncState {
                     },
                 )]
                 .into_iter()
-                .collect()
-            });
+                .collect(),
+            );
+        }
+
+        let range_map = self.buffers.get_mut(buffer.buffer()).unwrap();
         range_map.split_at(&range.start);
         range_map.split_at(&range.end);
 
@@ -949,6 +1172,7 @@ impl AutoSyncState {
                 state.is_written = memory_access.contains_write();
 
                 match self.level {
+                    CommandBufferLevel::Primary if !auto_sync => (),
                     CommandBufferLevel::Primary => {
                         // To be safe, we insert a barrier for all stages and accesses before
                         // the first use, so that there are no hazards with any command buffer
@@ -972,8 +1196,12 @@ impl AutoSyncState {
             } else {
                 // This resource range was used before in this command buffer.
 
-                // Find out if we have a collision with the pending commands.
-                if memory_access.contains_write() || state.memory_access.contains_write() {
+                if !auto_sync {
+                    // The user synchronizes this buffer, so only keep track of its state.
+                    state.memory_access = memory_access;
+                    state.is_written |= memory_access.contains_write();
+                } else if memory_access.contains_write() || state.memory_access.contains_write() {
+                    // Find out if we have a collision with the pending commands.
                     // Collision found between `latest_command_id` and `collision_cmd_id`.
 
                     // We now want to modify the current pipeline barrier in order to handle the
@@ -1025,22 +1253,24 @@ impl AutoSyncState {
     fn add_image(
         &mut self,
         use_ref: ResourceUseRef,
-        image: Arc<Image>,
+        image: &Arc<Image>,
         mut subresource_range: ImageSubresourceRange,
         memory_access: PipelineStageAccessFlags,
         start_layout: ImageLayout,
         end_layout: ImageLayout,
     ) {
-        self.secondary_resources_usage
-            .images
-            .push(SecondaryCommandBufferImageUsage {
-                use_ref,
-                image: image.clone(),
-                subresource_range: subresource_range.clone(),
-                memory_access,
-                start_layout,
-                end_layout,
-            });
+        if self.level == CommandBufferLevel::Secondary {
+            self.secondary_resources_usage
+                .images
+                .push(SecondaryCommandBufferImageUsage {
+                    use_ref,
+                    image: image.clone(),
+                    subresource_range: subresource_range.clone(),
+                    memory_access,
+                    start_layout,
+                    end_layout,
+                });
+        }
 
         // Barriers work differently in render passes, so if we're in one, we can only insert a
         // barrier before the start of the render pass.
@@ -1060,49 +1290,56 @@ impl AutoSyncState {
             subresource_range.aspects = ImageAspects::DEPTH | ImageAspects::STENCIL;
         }
 
-        let range_map = self.images.entry(image.clone()).or_insert_with(|| {
-            [(
-                0..image.range_size(),
-                match self.level {
-                    CommandBufferLevel::Primary => {
-                        // In a primary command buffer, the initial layout is determined
-                        // by the image.
-                        let initial_layout = if !image.is_layout_initialized() {
-                            unsafe {
-                                image.layout_initialized();
-                            }
+        let auto_sync = !(self.disabled_all || self.disabled_images.contains(image));
 
-                            image.initial_layout()
-                        } else {
-                            image.initial_layout_requirement()
-                        };
+        if !self.images.contains_key(image) {
+            self.images.insert(
+                image.clone(),
+                [(
+                    0..image.range_size(),
+                    match self.level {
+                        CommandBufferLevel::Primary => {
+                            // In a primary command buffer, the initial layout is determined
+                            // by the image.
+                            let initial_layout = if !image.is_layout_initialized() {
+                                unsafe {
+                                    image.layout_initialized();
+                                }
 
-                        ImageState {
-                            resource_uses: Vec::new(),
-                            memory_access: PipelineStageAccessFlags::empty(),
-                            is_written: false,
-                            initial_layout,
-                            current_layout: initial_layout,
-                            final_layout: image.final_layout_requirement(),
+                                image.initial_layout()
+                            } else {
+                                image.initial_layout_requirement()
+                            };
+
+                            ImageState {
+                                resource_uses: Vec::new(),
+                                memory_access: PipelineStageAccessFlags::empty(),
+                                is_written: false,
+                                initial_layout,
+                                current_layout: initial_layout,
+                                final_layout: image.final_layout_requirement(),
+                            }
                         }
-                    }
-                    CommandBufferLevel::Secondary => {
-                        // In a secondary command buffer, the initial layout is the layout
-                        // of the first use.
-                        ImageState {
-                            resource_uses: Vec::new(),
-                            memory_access: PipelineStageAccessFlags::empty(),
-                            is_written: false,
-                            initial_layout: ImageLayout::Undefined,
-                            current_layout: ImageLayout::Undefined,
-                            final_layout: ImageLayout::Undefined,
+                        CommandBufferLevel::Secondary => {
+                            // In a secondary command buffer, the initial layout is the layout
+                            // of the first use.
+                            ImageState {
+                                resource_uses: Vec::new(),
+                                memory_access: PipelineStageAccessFlags::empty(),
+                                is_written: false,
+                                initial_layout: ImageLayout::Undefined,
+                                current_layout: ImageLayout::Undefined,
+                                final_layout: ImageLayout::Undefined,
+                            }
                         }
-                    }
-                },
-            )]
-            .into_iter()
-            .collect()
-        });
+                    },
+                )]
+                .into_iter()
+                .collect(),
+            );
+        }
+
+        let range_map = self.images.get_mut(image).unwrap();
 
         for range in image.iter_ranges(subresource_range) {
             range_map.split_at(&range.start);
@@ -1120,6 +1357,7 @@ impl AutoSyncState {
                     state.current_layout = end_layout;
 
                     match self.level {
+                        CommandBufferLevel::Primary if !auto_sync => (),
                         CommandBufferLevel::Primary => {
                             // To be safe, we insert a barrier for all stages and accesses before
                             // the first use, so that there are no hazards with any command buffer
@@ -1210,11 +1448,20 @@ impl AutoSyncState {
                         start_layout
                     };
 
-                    // Find out if we have a collision with the pending commands.
-                    if memory_access.contains_write()
+                    if !auto_sync {
+                        // The user synchronizes this image, so only keep track of its state.
+                        state.is_written |=
+                            memory_access.contains_write() || state.current_layout != start_layout;
+                        state.memory_access = memory_access;
+
+                        if memory_access.contains_write() || end_layout != ImageLayout::Undefined {
+                            state.current_layout = end_layout;
+                        }
+                    } else if memory_access.contains_write()
                         || state.memory_access.contains_write()
                         || state.current_layout != start_layout
                     {
+                        // Find out if we have a collision with the pending commands.
                         // Collision found between `latest_command_id` and `collision_cmd_id`.
 
                         // We now want to modify the current pipeline barrier in order to handle the
@@ -1272,6 +1519,92 @@ impl AutoSyncState {
     }
 }
 
+/// Merges barriers that are recorded at the same point in the command buffer, so that they are
+/// recorded with as few pipeline barrier commands as possible.
+///
+/// Barriers within a single pipeline barrier command are not ordered relative to each other, so
+/// a barrier is only merged into the previous one if they don't apply to the same resource.
+/// Buffer barriers for adjacent ranges of the same buffer are also combined into one.
+pub(super) fn merge_dependency_infos(dependency_infos: &mut Vec<DependencyInfo>) {
+    let mut merged: Vec<DependencyInfo> = Vec::with_capacity(dependency_infos.len());
+
+    for dependency_info in dependency_infos.drain(..) {
+        if dependency_info.is_empty() {
+            continue;
+        }
+
+        match merged.last_mut() {
+            Some(previous) if can_merge_dependency_infos(previous, &dependency_info) => {
+                let DependencyInfo {
+                    dependency_flags: _,
+                    memory_barriers,
+                    buffer_memory_barriers,
+                    image_memory_barriers,
+                    _ne: _,
+                } = dependency_info;
+
+                previous.memory_barriers.extend(memory_barriers);
+                previous
+                    .buffer_memory_barriers
+                    .extend(buffer_memory_barriers);
+                previous.image_memory_barriers.extend(image_memory_barriers);
+            }
+            _ => merged.push(dependency_info),
+        }
+    }
+
+    for dependency_info in &mut merged {
+        coalesce_buffer_memory_barriers(&mut dependency_info.buffer_memory_barriers);
+    }
+
+    *dependency_infos = merged;
+}
+
+fn can_merge_dependency_infos(first: &DependencyInfo, second: &DependencyInfo) -> bool {
+    // A global memory barrier applies to every resource, so it can't be reordered with anything.
+    first.dependency_flags == second.dependency_flags
+        && first.memory_barriers.is_empty()
+        && second.memory_barriers.is_empty()
+        && !second.buffer_memory_barriers.iter().any(|second| {
+            first.buffer_memory_barriers.iter().any(|first| {
+                first.buffer == second.buffer
+                    && first.range.start < second.range.end
+                    && second.range.start < first.range.end
+            })
+        })
+        && !second.image_memory_barriers.iter().any(|second| {
+            first
+                .image_memory_barriers
+                .iter()
+                .any(|first| first.image == second.image)
+        })
+}
+
+fn coalesce_buffer_memory_barriers(barriers: &mut SmallVec<[BufferMemoryBarrier; 8]>) {
+    let mut coalesced: SmallVec<[BufferMemoryBarrier; 8]> = SmallVec::new();
+
+    for barrier in barriers.drain(..) {
+        if let Some(previous) = coalesced.last_mut() {
+            if previous.buffer == barrier.buffer
+                && previous.range.end == barrier.range.start
+                && previous.src_stages == barrier.src_stages
+                && previous.src_access == barrier.src_access
+                && previous.dst_stages == barrier.dst_stages
+                && previous.dst_access == barrier.dst_access
+                && previous.queue_family_ownership_transfer.is_none()
+                && barrier.queue_family_ownership_transfer.is_none()
+            {
+                previous.range.end = barrier.range.end;
+                continue;
+            }
+        }
+
+        coalesced.push(barrier);
+    }
+
+    *barriers = coalesced;
+}
+
 /// Error returned if the builder detects that there's an unsolvable conflict.
 #[derive(Clone, Debug)]
 struct UnsolvableResourceConflict {
@@ -1329,29 +1662,57 @@ pub(in crate::command_buffer) struct CommandBufferBuilderState {
     pub(in crate::command_buffer) index_buffer: Option<IndexBuffer>,
     pub(in crate::command_buffer) pipeline_compute: Option<Arc<ComputePipeline>>,
     pub(in crate::command_buffer) pipeline_graphics: Option<Arc<GraphicsPipeline>>,
+    pub(in crate::command_buffer) pipeline_ray_tracing: Option<Arc<RayTracingPipeline>>,
+    // A stage that was unbound with `None` is present; a stage that was never bound, or whose
+    // bind point had a pipeline bound since, is absent.
+    pub(in crate::command_buffer) shaders: HashMap<ShaderStage, Option<Arc<ShaderObject>>>,
     pub(in crate::command_buffer) vertex_buffers: HashMap<u32, Subbuffer<[u8]>>,
     pub(in crate::command_buffer) push_constants: RangeSet<u32>,
     pub(in crate::command_buffer) push_constants_pipeline_layout: Option<Arc<PipelineLayout>>,
+    pub(in crate::command_buffer) push_constants_cache: PushConstantsCache,
+    pub(in crate::command_buffer) transform_feedback_buffers: HashMap<u32, Subbuffer<[u8]>>,
+
+    // Resources used through the bound descriptor sets and vertex buffers, for each bind point.
+    // Cleared whenever the bound state changes, and reused by commands until then.
+    pub(in crate::command_buffer) bound_resources:
+        HashMap<PipelineBindPoint, Arc<[(ResourceUseRef2, Resource)]>>,
 
     // Dynamic state
+    pub(in crate::command_buffer) alpha_to_coverage_enable: Option<bool>,
+    pub(in crate::command_buffer) alpha_to_one_enable: Option<bool>,
     pub(in crate::command_buffer) blend_constants: Option<[f32; 4]>,
+    pub(in crate::command_buffer) color_blend_enable: HashMap<u32, bool>,
+    pub(in crate::command_buffer) color_blend_equation: HashMap<u32, AttachmentBlend>,
     pub(in crate::command_buffer) color_write_enable: Option<SmallVec<[bool; 4]>>,
+    pub(in crate::command_buffer) color_write_mask: HashMap<u32, ColorComponents>,
     pub(in crate::command_buffer) cull_mode: Option<CullMode>,
     pub(in crate::command_buffer) depth_bias: Option<DepthBiasState>,
     pub(in crate::command_buffer) depth_bias_enable: Option<bool>,
     pub(in crate::command_buffer) depth_bounds: Option<RangeInclusive<f32>>,
     pub(in crate::command_buffer) depth_bounds_test_enable: Option<bool>,
+    pub(in crate::command_buffer) depth_clamp_enable: Option<bool>,
+    pub(in crate::command_buffer) depth_clip_enable: Option<bool>,
     pub(in crate::command_buffer) depth_compare_op: Option<CompareOp>,
     pub(in crate::command_buffer) depth_test_enable: Option<bool>,
     pub(in crate::command_buffer) depth_write_enable: Option<bool>,
     pub(in crate::command_buffer) discard_rectangle: HashMap<u32, Scissor>,
+    pub(in crate::command_buffer) discard_rectangle_enable: Option<bool>,
+    pub(in crate::command_buffer) discard_rectangle_mode: Option<DiscardRectangleMode>,
+    pub(in crate::command_buffer) fragment_shading_rate: Option<FragmentShadingRateState>,
     pub(in crate::command_buffer) front_face: Option<FrontFace>,
     pub(in crate::command_buffer) line_stipple: Option<LineStipple>,
     pub(in crate::command_buffer) line_width: Option<f32>,
     pub(in crate::command_buffer) logic_op: Option<LogicOp>,
+    pub(in crate::command_buffer) logic_op_enable: Option<bool>,
     pub(in crate::command_buffer) patch_control_points: Option<u32>,
+    pub(in crate::command_buffer) polygon_mode: Option<PolygonMode>,
     pub(in crate::command_buffer) primitive_restart_enable: Option<bool>,
     pub(in crate::command_buffer) primitive_topology: Option<PrimitiveTopology>,
+    pub(in crate::command_buffer) provoking_vertex_mode: Option<ProvokingVertexMode>,
+    pub(in crate::command_buffer) sample_locations: Option<SampleLocationsInfo>,
+    pub(in crate::command_buffer) sample_locations_enable: Option<bool>,
+    pub(in crate::command_buffer) sample_mask: Option<(SampleCount, [u32; 2])>,
+    pub(in crate::command_buffer) rasterization_samples: Option<SampleCount>,
     pub(in crate::command_buffer) rasterizer_discard_enable: Option<bool>,
     pub(in crate::command_buffer) scissor: HashMap<u32, Scissor>,
     pub(in crate::command_buffer) scissor_with_count: Option<SmallVec<[Scissor; 2]>>,
@@ -1360,17 +1721,28 @@ pub(in crate::command_buffer) struct CommandBufferBuilderState {
     pub(in crate::command_buffer) stencil_reference: StencilStateDynamic,
     pub(in crate::command_buffer) stencil_test_enable: Option<bool>,
     pub(in crate::command_buffer) stencil_write_mask: StencilStateDynamic,
+    pub(in crate::command_buffer) tessellation_domain_origin: Option<TessellationDomainOrigin>,
+    pub(in crate::command_buffer) vertex_input: Option<VertexInputState>,
+    pub(in crate::command_buffer) vertex_input_binding_stride: HashMap<u32, u32>,
     pub(in crate::command_buffer) viewport: HashMap<u32, Viewport>,
     pub(in crate::command_buffer) viewport_with_count: Option<SmallVec<[Viewport; 2]>>,
 
     // Active queries
     pub(in crate::command_buffer) queries: HashMap<ash::vk::QueryType, QueryState>,
+
+    // Active conditional rendering
+    pub(in crate::command_buffer) conditional_rendering: Option<ConditionalRenderingState>,
+
+    // Active transform feedback
+    pub(in crate::command_buffer) transform_feedback_active: bool,
 }
 
 impl CommandBufferBuilderState {
     pub(in crate::command_buffer) fn reset_non_render_pass_states(&mut self) {
         *self = Self {
             render_pass: take(&mut self.render_pass),
+            conditional_rendering: take(&mut self.conditional_rendering),
+            transform_feedback_active: self.transform_feedback_active,
             ..Default::default()
         }
     }
@@ -1392,8 +1764,10 @@ impl CommandBufferBuilderState {
                 DynamicState::DepthTestEnable => self.depth_test_enable = None,
                 DynamicState::DepthWriteEnable => self.depth_write_enable = None,
                 DynamicState::DiscardRectangle => self.discard_rectangle.clear(),
+                DynamicState::DiscardRectangleEnable => self.discard_rectangle_enable = None,
+                DynamicState::DiscardRectangleMode => self.discard_rectangle_mode = None,
                 // DynamicState::ExclusiveScissor => todo!(),
-                // DynamicState::FragmentShadingRate => todo!(),
+                DynamicState::FragmentShadingRate => self.fragment_shading_rate = None,
                 DynamicState::FrontFace => self.front_face = None,
                 DynamicState::LineStipple => self.line_stipple = None,
                 DynamicState::LineWidth => self.line_width = None,
@@ -1403,7 +1777,7 @@ impl CommandBufferBuilderState {
                 DynamicState::PrimitiveTopology => self.primitive_topology = None,
                 DynamicState::RasterizerDiscardEnable => self.rasterizer_discard_enable = None,
                 // DynamicState::RayTracingPipelineStackSize => todo!(),
-                // DynamicState::SampleLocations => todo!(),
+                DynamicState::SampleLocations => self.sample_locations = None,
                 DynamicState::Scissor => self.scissor.clear(),
                 DynamicState::ScissorWithCount => self.scissor_with_count = None,
                 DynamicState::StencilCompareMask => self.stencil_compare_mask = Default::default(),
@@ -1412,14 +1786,14 @@ impl CommandBufferBuilderState {
                 DynamicState::StencilTestEnable => self.stencil_test_enable = None,
                 DynamicState::StencilWriteMask => self.stencil_write_mask = Default::default(),
                 // DynamicState::VertexInput => todo!(),
-                // DynamicState::VertexInputBindingStride => todo!(),
+                DynamicState::VertexInputBindingStride => self.vertex_input_binding_stride.clear(),
                 DynamicState::Viewport => self.viewport.clear(),
                 // DynamicState::ViewportCoarseSampleOrder => todo!(),
                 // DynamicState::ViewportShadingRatePalette => todo!(),
                 // DynamicState::ViewportWScaling => todo!(),
                 DynamicState::ViewportWithCount => self.viewport_with_count = None,
                 // DynamicState::TessellationDomainOrigin => todo!(),
-                // DynamicState::DepthClampEnable => todo!(),
+                DynamicState::DepthClampEnable => self.depth_clamp_enable = None,
                 // DynamicState::PolygonMode => todo!(),
                 // DynamicState::RasterizationSamples => todo!(),
                 // DynamicState::SampleMask => todo!(),
@@ -1432,10 +1806,10 @@ impl CommandBufferBuilderState {
                 // DynamicState::RasterizationStream => todo!(),
                 // DynamicState::ConservativeRasterizationMode => todo!(),
                 // DynamicState::ExtraPrimitiveOverestimationSize => todo!(),
-                // DynamicState::DepthClipEnable => todo!(),
-                // DynamicState::SampleLocationsEnable => todo!(),
+                DynamicState::DepthClipEnable => self.depth_clip_enable = None,
+                DynamicState::SampleLocationsEnable => self.sample_locations_enable = None,
                 // DynamicState::ColorBlendAdvanced => todo!(),
-                // DynamicState::ProvokingVertexMode => todo!(),
+                DynamicState::ProvokingVertexMode => self.provoking_vertex_mode = None,
                 // DynamicState::LineRasterizationMode => todo!(),
                 // DynamicState::LineStippleEnable => todo!(),
                 // DynamicState::DepthClipNegativeOneToOne => todo!(),
@@ -1453,6 +1827,38 @@ impl CommandBufferBuilderState {
         }
     }
 
+    /// Resets the states that graphics pipelines always contain statically, and that can
+    /// therefore only have been set for shader objects.
+    pub(in crate::command_buffer) fn reset_shader_object_states(&mut self) {
+        self.alpha_to_coverage_enable = None;
+        self.alpha_to_one_enable = None;
+        self.color_blend_enable.clear();
+        self.color_blend_equation.clear();
+        self.color_write_mask.clear();
+        self.logic_op_enable = None;
+        self.polygon_mode = None;
+        self.rasterization_samples = None;
+        self.sample_mask = None;
+        self.tessellation_domain_origin = None;
+        self.vertex_input = None;
+    }
+
+    /// Returns the range of `vertex_buffers` that differs from the vertex buffers that are
+    /// currently bound, starting at `first_binding`, or `None` if all of them are already bound.
+    pub(in crate::command_buffer) fn changed_vertex_buffers(
+        &self,
+        first_binding: u32,
+        vertex_buffers: &[Subbuffer<[u8]>],
+    ) -> Option<Range<usize>> {
+        let is_bound = |(i, buffer): &(usize, &Subbuffer<[u8]>)| {
+            self.vertex_buffers.get(&(first_binding + *i as u32)) == Some(*buffer)
+        };
+        let start = vertex_buffers.iter().enumerate().find(|x| !is_bound(x))?.0;
+        let end = vertex_buffers.iter().enumerate().rfind(|x| !is_bound(x))?.0 + 1;
+
+        Some(start..end)
+    }
+
     pub(in crate::command_buffer) fn invalidate_descriptor_sets(
         &mut self,
         pipeline_bind_point: PipelineBindPoint,
@@ -1460,6 +1866,8 @@ impl CommandBufferBuilderState {
         first_set: u32,
         num_descriptor_sets: u32,
     ) -> &mut DescriptorSetState {
+        self.bound_resources.remove(&pipeline_bind_point);
+
         match self.descriptor_sets.entry(pipeline_bind_point) {
             Entry::Vacant(entry) => entry.insert(DescriptorSetState {
                 descriptor_sets: Default::default(),
@@ -1510,6 +1918,64 @@ impl CommandBufferBuilderState {
     }
 }
 
+/// The push constant data that was last pushed, used to skip pushes that wouldn't change anything.
+#[derive(Default)]
+pub(in crate::command_buffer) struct PushConstantsCache {
+    pipeline_layout: Option<Arc<PipelineLayout>>,
+    ranges: RangeSet<u32>,
+    data: Vec<u8>,
+}
+
+impl PushConstantsCache {
+    /// Forgets the cached data if `pipeline_layout` is not compatible for push constants with the
+    /// layout that the data was pushed with.
+    pub(in crate::command_buffer) fn invalidate(&mut self, pipeline_layout: &PipelineLayout) {
+        if !self.is_compatible_with(pipeline_layout) {
+            *self = Default::default();
+        }
+    }
+
+    /// Records that `data` is pushed at `offset` with `pipeline_layout`. Returns `true` if the
+    /// same data was already pushed with a compatible layout, in which case the push can be
+    /// skipped.
+    pub(in crate::command_buffer) fn update(
+        &mut self,
+        pipeline_layout: &Arc<PipelineLayout>,
+        offset: u32,
+        data: &[u8],
+    ) -> bool {
+        let range = offset..offset + data.len() as u32;
+        let data_range = range.start as usize..range.end as usize;
+
+        if !self.is_compatible_with(pipeline_layout) {
+            *self = Default::default();
+        } else if self.ranges.contains(range.clone()) && self.data[data_range.clone()] == *data {
+            return true;
+        }
+
+        if self.data.len() < data_range.end {
+            self.data.resize(data_range.end, 0);
+        }
+
+        self.data[data_range].copy_from_slice(data);
+        self.ranges.insert(range);
+        self.pipeline_layout = Some(pipeline_layout.clone());
+
+        false
+    }
+
+    fn is_compatible_with(&self, pipeline_layout: &PipelineLayout) -> bool {
+        // Pipeline layouts are compatible for push constants if they were created with identical
+        // push constant ranges.
+        match &self.pipeline_layout {
+            Some(cached_layout) => {
+                cached_layout.push_constant_ranges() == pipeline_layout.push_constant_ranges()
+            }
+            None => false,
+        }
+    }
+}
+
 pub(in crate::command_buffer) struct RenderPassState {
     pub(in crate::command_buffer) contents: SubpassContents,
     pub(in crate::command_buffer) render_area_offset: [u32; 2],
@@ -1732,6 +2198,40 @@ pub(in crate::command_buffer) struct DescriptorSetState {
     pub(in crate::command_buffer) pipeline_layout: Arc<PipelineLayout>,
 }
 
+impl DescriptorSetState {
+    /// Returns the range of `descriptor_sets` that differs from the descriptor sets that are
+    /// currently bound, starting at `first_set`, or `None` if all of them are already bound.
+    ///
+    /// A descriptor set only counts as bound if it was bound with the same dynamic offsets.
+    pub(in crate::command_buffer) fn changed_descriptor_sets(
+        &self,
+        first_set: u32,
+        descriptor_sets: &[DescriptorSetWithOffsets],
+    ) -> Option<Range<usize>> {
+        let is_bound = |(i, set): &(usize, &DescriptorSetWithOffsets)| match self
+            .descriptor_sets
+            .get(&(first_set + *i as u32))
+        {
+            Some(SetOrPush::Set(bound_set)) => {
+                let (bound_set, bound_offsets) = bound_set.as_ref();
+                let (set, offsets) = set.as_ref();
+
+                bound_set.handle() == set.handle() && bound_offsets == offsets
+            }
+            Some(SetOrPush::Push(_)) | None => false,
+        };
+        let start = descriptor_sets.iter().enumerate().find(|x| !is_bound(x))?.0;
+        let end = descriptor_sets
+            .iter()
+            .enumerate()
+            .rfind(|x| !is_bound(x))?
+            .0
+            + 1;
+
+        Some(start..end)
+    }
+}
+
 #[derive(Clone)]
 pub(in crate::command_buffer) enum SetOrPush {
     Set(DescriptorSetWithOffsets),
@@ -1773,3 +2273,7 @@ pub(in crate::command_buffer) struct QueryState {
     pub(in crate::command_buffer) flags: QueryControlFlags,
     pub(in crate::command_buffer) in_subpass: bool,
 }
+
+pub(in crate::command_buffer) struct ConditionalRenderingState {
+    pub(in crate::command_buffer) in_subpass: bool,
+}
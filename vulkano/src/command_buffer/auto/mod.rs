@@ -67,8 +67,8 @@
 
 pub use self::builder::*;
 pub(in crate::command_buffer) use self::builder::{
-    BeginRenderPassState, BeginRenderingState, QueryState, RenderPassState,
-    RenderPassStateAttachments, RenderPassStateType, SetOrPush,
+    BeginRenderPassState, BeginRenderingState, ConditionalRenderingState, QueryState,
+    RenderPassState, RenderPassStateAttachments, RenderPassStateType, SetOrPush,
 };
 use super::{
     allocator::{CommandBufferAllocator, StandardCommandBufferAllocator},
@@ -135,6 +135,10 @@ where
         self.inner.usage()
     }
 
+    fn queue_family_index(&self) -> u32 {
+        self.inner.queue_family_index()
+    }
+
     fn state(&self) -> MutexGuard<'_, CommandBufferState> {
         self.state.lock()
     }
@@ -299,10 +303,23 @@ pub(super) enum Resource {
 
 struct CommandInfo {
     name: &'static str,
+    /// The resources that the command uses through the descriptor sets and vertex buffers that
+    /// are bound to the pipeline. Consecutive commands that use the same bound state share the
+    /// same list, so that the resources don't need to be collected and cloned for every command.
+    bound_resources: Option<Arc<[(ResourceUseRef2, Resource)]>>,
     used_resources: Vec<(ResourceUseRef2, Resource)>,
     render_pass: RenderPassCommand,
 }
 
+impl CommandInfo {
+    fn used_resources(&self) -> impl Iterator<Item = &(ResourceUseRef2, Resource)> {
+        self.bound_resources
+            .iter()
+            .flat_map(|bound_resources| bound_resources.iter())
+            .chain(&self.used_resources)
+    }
+}
+
 #[derive(Debug)]
 enum RenderPassCommand {
     None,
@@ -318,12 +335,14 @@ impl Debug for CommandInfo {
 
 #[cfg(test)]
 mod tests {
+    use super::builder::{merge_dependency_infos, PushConstantsCache};
     use crate::{
         buffer::{Buffer, BufferCreateInfo, BufferUsage},
         command_buffer::{
             allocator::{StandardCommandBufferAllocator, StandardCommandBufferAllocatorCreateInfo},
-            AutoCommandBufferBuilder, BufferCopy, CommandBufferUsage, CopyBufferInfoTyped,
-            PrimaryCommandBufferAbstract,
+            AutoCommandBufferBuilder, BufferCopy, ClearColorImageInfo, CommandBufferUsage,
+            CopyBufferInfoTyped, CopyImageToBufferInfo, PrimaryCommandBufferAbstract,
+            RenderPassBeginInfo, ResourceInCommand, SecondaryCommandBufferAbstract,
         },
         descriptor_set::{
             allocator::StandardDescriptorSetAllocator,
@@ -331,16 +350,44 @@ mod tests {
                 DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo,
                 DescriptorType,
             },
-            PersistentDescriptorSet, WriteDescriptorSet,
+            DescriptorSetWithOffsets, PersistentDescriptorSet, WriteDescriptorSet,
+        },
+        device::{
+            Device, DeviceCreateInfo, DeviceExtensions, Features, QueueCreateInfo, QueueFlags,
+        },
+        format::Format,
+        image::{
+            sampler::{Sampler, SamplerCreateInfo},
+            Image, ImageCreateInfo, ImageLayout, ImageUsage,
         },
-        device::{Device, DeviceCreateInfo, QueueCreateInfo},
-        image::sampler::{Sampler, SamplerCreateInfo},
         memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
-        pipeline::{layout::PipelineLayoutCreateInfo, PipelineBindPoint, PipelineLayout},
-        shader::ShaderStages,
-        sync::GpuFuture,
+        pipeline::{
+            graphics::rasterization::PolygonMode,
+            layout::{
+                PipelineDescriptorSetLayoutCreateInfo, PipelineLayoutCreateInfo, PushConstantRange,
+            },
+            ray_tracing::{
+                RayTracingPipeline, RayTracingPipelineCreateInfo, RayTracingShaderGroupCreateInfo,
+                ShaderBindingTable,
+            },
+            PipelineBindPoint, PipelineLayout, PipelineShaderStageCreateInfo,
+        },
+        render_pass::{
+            Framebuffer, FramebufferCreateInfo, RenderPass, RenderPassCreateInfo,
+            SubpassDependency, SubpassDescription,
+        },
+        shader::{
+            object::{ShaderCreateInfo, ShaderObject},
+            ShaderModule, ShaderModuleCreateInfo, ShaderStage, ShaderStages,
+        },
+        sync::{
+            event::Event, AccessFlags, BufferMemoryBarrier, DependencyFlags, DependencyInfo,
+            GpuFuture, ImageMemoryBarrier, MemoryBarrier, PipelineStages,
+        },
+        Requires, RequiresAllOf, RequiresOneOf, ValidationError, Version,
     };
-    use std::sync::Arc;
+    use smallvec::smallvec;
+    use std::{slice, sync::Arc};
 
     #[test]
     fn basic_creation() {
@@ -376,6 +423,7 @@ mod tests {
             },
         )
         .unwrap();
+        skip_if_null_device!(device);
 
         let queue = queues.next().unwrap();
         let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
@@ -513,6 +561,7 @@ mod tests {
     #[test]
     fn buffer_self_copy_overlapping() {
         let (device, queue) = gfx_dev_and_queue!();
+        skip_if_null_device!(device);
 
         let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
         let source = Buffer::from_iter(
@@ -749,7 +798,7 @@ mod tests {
     }
 
     #[test]
-    fn descriptor_set_binding() {
+    fn redundant_vertex_buffer_binding() {
         unsafe {
             let (device, queue) = gfx_dev_and_queue!();
 
@@ -761,132 +810,1318 @@ mod tests {
                 CommandBufferUsage::MultipleSubmit,
             )
             .unwrap();
-            let set_layout = DescriptorSetLayout::new(
-                device.clone(),
-                DescriptorSetLayoutCreateInfo {
-                    bindings: [(
-                        0,
-                        DescriptorSetLayoutBinding {
-                            stages: ShaderStages::all_graphics(),
-                            ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::Sampler)
-                        },
-                    )]
-                    .into(),
-                    ..Default::default()
-                },
-            )
-            .unwrap();
-            let pipeline_layout = PipelineLayout::new(
-                device.clone(),
-                PipelineLayoutCreateInfo {
-                    set_layouts: [set_layout.clone(), set_layout.clone()].into(),
+
+            let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device));
+            let buf = Buffer::new_slice::<u32>(
+                memory_allocator,
+                BufferCreateInfo {
+                    usage: BufferUsage::VERTEX_BUFFER,
                     ..Default::default()
                 },
+                AllocationCreateInfo::default(),
+                3,
             )
-            .unwrap();
+            .unwrap()
+            .into_bytes();
+            let [a, b, c] = [0, 1, 2].map(|i| buf.clone().slice(i * 4..i * 4 + 4));
+            sync.bind_vertex_buffers_unchecked(0, [a.clone(), b.clone()]);
+
+            let state = &sync.builder_state;
+            assert_eq!(
+                state.changed_vertex_buffers(0, &[a.clone(), b.clone()]),
+                None,
+            );
+            assert_eq!(state.changed_vertex_buffers(1, slice::from_ref(&b)), None);
+            assert_eq!(
+                state.changed_vertex_buffers(0, &[a.clone(), c.clone(), b.clone()]),
+                Some(1..3),
+            );
+            assert_eq!(state.changed_vertex_buffers(0, &[c, b.clone()]), Some(0..1));
 
-            let ds_allocator =
-                StandardDescriptorSetAllocator::new(device.clone(), Default::default());
+            // Binding buffers that are already bound leaves the other bindings alone.
+            sync.bind_vertex_buffers_unchecked(1, [b.clone(), a.clone()]);
+            assert_eq!(sync.builder_state.vertex_buffers.get(&0), Some(&a));
+            assert_eq!(sync.builder_state.vertex_buffers.get(&1), Some(&b));
+            assert_eq!(sync.builder_state.vertex_buffers.get(&2), Some(&a));
+        }
+    }
 
-            let set = PersistentDescriptorSet::new(
-                &ds_allocator,
-                set_layout.clone(),
-                [WriteDescriptorSet::sampler(
-                    0,
-                    Sampler::new(device.clone(), SamplerCreateInfo::simple_repeat_linear())
-                        .unwrap(),
-                )],
-                [],
-            )
-            .unwrap();
+    #[test]
+    fn conditional_rendering_scope() {
+        let (device, queue) = gfx_dev_and_queue!();
 
-            sync.bind_descriptor_sets_unchecked(
-                PipelineBindPoint::Graphics,
-                pipeline_layout.clone(),
-                1,
-                set.clone(),
-            );
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
 
-            assert!(!sync
-                .builder_state
-                .descriptor_sets
-                .get(&PipelineBindPoint::Compute)
-                .map_or(false, |state| state.descriptor_sets.contains_key(&0)));
-            assert!(!sync
-                .builder_state
-                .descriptor_sets
-                .get(&PipelineBindPoint::Graphics)
-                .map_or(false, |state| state.descriptor_sets.contains_key(&0)));
-            assert!(sync
-                .builder_state
-                .descriptor_sets
-                .get(&PipelineBindPoint::Graphics)
-                .map_or(false, |state| state.descriptor_sets.contains_key(&1)));
-            assert!(!sync
-                .builder_state
-                .descriptor_sets
-                .get(&PipelineBindPoint::Graphics)
-                .map_or(false, |state| state.descriptor_sets.contains_key(&2)));
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device));
+        let predicate = Buffer::new_sized::<u32>(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
 
-            sync.bind_descriptor_sets_unchecked(
-                PipelineBindPoint::Graphics,
-                pipeline_layout,
-                0,
-                set,
-            );
+        // The `conditional_rendering` feature is not enabled.
+        assert!(cbb
+            .begin_conditional_rendering(predicate.clone(), false)
+            .is_err());
 
-            assert!(sync
-                .builder_state
-                .descriptor_sets
-                .get(&PipelineBindPoint::Graphics)
-                .map_or(false, |state| state.descriptor_sets.contains_key(&0)));
-            assert!(sync
-                .builder_state
-                .descriptor_sets
-                .get(&PipelineBindPoint::Graphics)
-                .map_or(false, |state| state.descriptor_sets.contains_key(&1)));
+        unsafe {
+            cbb.begin_conditional_rendering_unchecked(predicate, false);
+        }
 
-            let pipeline_layout = PipelineLayout::new(
-                device.clone(),
-                PipelineLayoutCreateInfo {
-                    set_layouts: [
-                        DescriptorSetLayout::new(device.clone(), Default::default()).unwrap(),
-                        set_layout.clone(),
-                    ]
-                    .into(),
-                    ..Default::default()
-                },
-            )
-            .unwrap();
+        // The conditional rendering block must be ended before building.
+        assert!(cbb.build().is_err());
+    }
 
-            let set = PersistentDescriptorSet::new(
-                &ds_allocator,
-                set_layout,
-                [WriteDescriptorSet::sampler(
-                    0,
-                    Sampler::new(device, SamplerCreateInfo::simple_repeat_linear()).unwrap(),
-                )],
-                [],
-            )
+    #[test]
+    fn manual_sync_layout_transition() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let image = Image::new(
+            memory_allocator,
+            ImageCreateInfo {
+                format: Format::R8G8B8A8_UNORM,
+                extent: [4, 4, 1],
+                usage: ImageUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+        let event = Arc::new(Event::new(device, Default::default()).unwrap());
+        let dependency_info = DependencyInfo {
+            image_memory_barriers: [ImageMemoryBarrier {
+                src_stages: PipelineStages::ALL_TRANSFER,
+                dst_stages: PipelineStages::ALL_TRANSFER,
+                old_layout: ImageLayout::Undefined,
+                new_layout: ImageLayout::TransferDstOptimal,
+                subresource_range: image.subresource_range(),
+                ..ImageMemoryBarrier::image(image.clone())
+            }]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+
+        unsafe {
+            // The auto-sync layer tracks the layout of `image`, so it can't be transitioned by
+            // an event. A pipeline barrier is taken into account instead.
+            assert!(cbb
+                .set_event(event.clone(), dependency_info.clone())
+                .is_err());
+            cbb.pipeline_barrier(dependency_info.clone()).unwrap();
+
+            cbb.disable_auto_sync_for_image(image);
+            cbb.set_event(event, dependency_info.clone()).unwrap();
+            cbb.pipeline_barrier(dependency_info).unwrap();
+        }
+
+        cbb.build().unwrap();
+    }
+
+    #[test]
+    fn disable_auto_sync() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device));
+        let image = Image::new(
+            memory_allocator,
+            ImageCreateInfo {
+                format: Format::R8G8B8A8_UNORM,
+                extent: [4, 4, 1],
+                usage: ImageUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+
+        unsafe {
+            cbb.disable_auto_sync();
+            cbb.pipeline_barrier(DependencyInfo {
+                image_memory_barriers: [ImageMemoryBarrier {
+                    src_stages: PipelineStages::ALL_TRANSFER,
+                    dst_stages: PipelineStages::ALL_TRANSFER,
+                    old_layout: ImageLayout::Undefined,
+                    new_layout: ImageLayout::TransferDstOptimal,
+                    subresource_range: image.subresource_range(),
+                    ..ImageMemoryBarrier::image(image)
+                }]
+                .into_iter()
+                .collect(),
+                ..Default::default()
+            })
             .unwrap();
+        }
 
-            sync.bind_descriptor_sets_unchecked(
-                PipelineBindPoint::Graphics,
-                pipeline_layout,
-                1,
-                set,
-            );
+        cbb.build().unwrap();
+    }
 
-            assert!(!sync
-                .builder_state
-                .descriptor_sets
-                .get(&PipelineBindPoint::Graphics)
-                .map_or(false, |state| state.descriptor_sets.contains_key(&0)));
-            assert!(sync
-                .builder_state
-                .descriptor_sets
-                .get(&PipelineBindPoint::Graphics)
-                .map_or(false, |state| state.descriptor_sets.contains_key(&1)));
+    #[test]
+    fn pipeline_barrier_layout_transition() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let image = Image::new(
+            memory_allocator.clone(),
+            ImageCreateInfo {
+                format: Format::R8G8B8A8_UNORM,
+                extent: [4, 4, 1],
+                usage: ImageUsage::TRANSFER_SRC | ImageUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+        let buffer = Buffer::new_slice::<u32>(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+            16,
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(
+            device,
+            StandardCommandBufferAllocatorCreateInfo {
+                secondary_buffer_count: 1,
+                ..Default::default()
+            },
+        );
+        let mut cbb = AutoCommandBufferBuilder::secondary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+            Default::default(),
+        )
+        .unwrap();
+
+        cbb.clear_color_image(ClearColorImageInfo::image(image.clone()))
+            .unwrap();
+        unsafe {
+            cbb.pipeline_barrier(DependencyInfo {
+                image_memory_barriers: smallvec![ImageMemoryBarrier {
+                    src_stages: PipelineStages::ALL_TRANSFER,
+                    src_access: AccessFlags::TRANSFER_WRITE,
+                    dst_stages: PipelineStages::ALL_TRANSFER,
+                    dst_access: AccessFlags::TRANSFER_READ,
+                    old_layout: ImageLayout::TransferDstOptimal,
+                    new_layout: ImageLayout::TransferSrcOptimal,
+                    subresource_range: image.subresource_range(),
+                    ..ImageMemoryBarrier::image(image.clone())
+                }],
+                ..Default::default()
+            })
         }
+        .unwrap();
+        cbb.copy_image_to_buffer(CopyImageToBufferInfo::image_buffer(image, buffer))
+            .unwrap();
+
+        // The layout transition is tracked as a use of the image, after which the image is in
+        // the new layout.
+        let cb = cbb.build().unwrap();
+        let image_uses: Vec<_> = cb
+            .resources_usage()
+            .images
+            .iter()
+            .map(|usage| {
+                (
+                    usage.use_ref.resource_in_command,
+                    usage.start_layout,
+                    usage.end_layout,
+                )
+            })
+            .collect();
+        assert_eq!(
+            image_uses,
+            [
+                (
+                    ResourceInCommand::Destination,
+                    ImageLayout::TransferDstOptimal,
+                    ImageLayout::TransferDstOptimal,
+                ),
+                (
+                    ResourceInCommand::ImageMemoryBarrier { index: 0 },
+                    ImageLayout::TransferDstOptimal,
+                    ImageLayout::TransferSrcOptimal,
+                ),
+                (
+                    ResourceInCommand::Source,
+                    ImageLayout::TransferSrcOptimal,
+                    ImageLayout::TransferSrcOptimal,
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn pipeline_barrier_in_render_pass() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let render_pass = RenderPass::new(
+            device.clone(),
+            RenderPassCreateInfo {
+                subpasses: vec![SubpassDescription::default()],
+                dependencies: vec![SubpassDependency {
+                    src_subpass: Some(0),
+                    dst_subpass: Some(0),
+                    src_stages: PipelineStages::FRAGMENT_SHADER,
+                    dst_stages: PipelineStages::FRAGMENT_SHADER,
+                    src_access: AccessFlags::SHADER_WRITE,
+                    dst_access: AccessFlags::SHADER_READ,
+                    dependency_flags: DependencyFlags::BY_REGION,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let framebuffer = Framebuffer::new(
+            render_pass,
+            FramebufferCreateInfo {
+                extent: [4, 4],
+                layers: 1,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let buffer = Buffer::new_slice::<u32>(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::STORAGE_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+            16,
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        cbb.begin_render_pass(
+            RenderPassBeginInfo::framebuffer(framebuffer),
+            Default::default(),
+        )
+        .unwrap();
+
+        let memory_barrier = |src_stages, dependency_flags| DependencyInfo {
+            dependency_flags,
+            memory_barriers: smallvec![MemoryBarrier {
+                src_stages,
+                src_access: AccessFlags::SHADER_WRITE,
+                dst_stages: PipelineStages::FRAGMENT_SHADER,
+                dst_access: AccessFlags::SHADER_READ,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        unsafe {
+            // The barrier is included in the self-dependency of the subpass.
+            cbb.pipeline_barrier(memory_barrier(
+                PipelineStages::FRAGMENT_SHADER,
+                DependencyFlags::BY_REGION,
+            ))
+            .unwrap();
+
+            // The stages are not included in the self-dependency.
+            assert!(cbb
+                .pipeline_barrier(memory_barrier(
+                    PipelineStages::VERTEX_SHADER,
+                    DependencyFlags::BY_REGION,
+                ))
+                .is_err());
+
+            // The self-dependency is by region, but the barrier isn't.
+            assert!(cbb
+                .pipeline_barrier(memory_barrier(
+                    PipelineStages::FRAGMENT_SHADER,
+                    DependencyFlags::empty(),
+                ))
+                .is_err());
+
+            // Buffer memory barriers are not allowed in a render pass.
+            assert!(cbb
+                .pipeline_barrier(DependencyInfo {
+                    dependency_flags: DependencyFlags::BY_REGION,
+                    buffer_memory_barriers: smallvec![BufferMemoryBarrier {
+                        src_stages: PipelineStages::FRAGMENT_SHADER,
+                        src_access: AccessFlags::SHADER_WRITE,
+                        dst_stages: PipelineStages::FRAGMENT_SHADER,
+                        dst_access: AccessFlags::SHADER_READ,
+                        range: 0..buffer.size(),
+                        ..BufferMemoryBarrier::buffer(buffer.buffer().clone())
+                    }],
+                    ..Default::default()
+                })
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn trace_rays_shader_binding_table_pipeline() {
+        let instance = instance!();
+
+        let enabled_extensions = DeviceExtensions {
+            khr_ray_tracing_pipeline: true,
+            ..DeviceExtensions::empty()
+        };
+        let enabled_features = Features {
+            ray_tracing_pipeline: true,
+            buffer_device_address: true,
+            ..Features::empty()
+        };
+
+        let (physical_device, queue_family_index) = match instance
+            .enumerate_physical_devices()
+            .unwrap()
+            .filter(|p| {
+                p.supported_extensions().contains(&enabled_extensions)
+                    && p.supported_features().contains(&enabled_features)
+            })
+            .find_map(|p| {
+                p.queue_family_properties()
+                    .iter()
+                    .position(|q| q.queue_flags.intersects(QueueFlags::COMPUTE))
+                    .map(|i| (p, i as u32))
+            }) {
+            Some(x) => x,
+            None => return,
+        };
+
+        let (device, _) = Device::new(
+            physical_device,
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo {
+                    queue_family_index,
+                    ..Default::default()
+                }],
+                enabled_extensions,
+                enabled_features,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let module = unsafe {
+            /*
+            #version 460
+            #extension GL_EXT_ray_tracing : require
+
+            void main() {}
+            */
+            const MODULE: [u32; 35] = [
+                119734787, 66560, 0, 5, 0, 131089, 4479, 393226, 1599492179, 1599227979,
+                1601790322, 1667330676, 6778473, 196622, 0, 1, 327695, 5313, 3, 1852399981, 0,
+                131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4, 65789, 65592,
+            ];
+            ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+        let new_pipeline = || {
+            RayTracingPipeline::new(
+                device.clone(),
+                None,
+                RayTracingPipelineCreateInfo {
+                    stages: smallvec![PipelineShaderStageCreateInfo::new(
+                        module.entry_point("main").unwrap()
+                    )],
+                    groups: smallvec![RayTracingShaderGroupCreateInfo::General {
+                        general_shader: 0
+                    }],
+                    ..RayTracingPipelineCreateInfo::layout(layout.clone())
+                },
+            )
+            .unwrap()
+        };
+        let pipeline = new_pipeline();
+        let other_pipeline = new_pipeline();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let shader_binding_table =
+            ShaderBindingTable::new(memory_allocator, pipeline.clone()).unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue_family_index,
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // The shader group handles in the table don't belong to the bound pipeline.
+        cbb.bind_pipeline_ray_tracing(other_pipeline).unwrap();
+        assert!(cbb.trace_rays(&shader_binding_table, [1, 1, 1]).is_err());
+
+        cbb.bind_pipeline_ray_tracing(pipeline)
+            .unwrap()
+            .trace_rays(&shader_binding_table, [1, 1, 1])
+            .unwrap();
+        cbb.build().unwrap();
+    }
+
+    #[cfg(feature = "null_driver")]
+    #[test]
+    fn disable_auto_sync_rebinds() {
+        let (device, queue) = gfx_dev_and_queue!();
+        skip_unless_null_device!(device);
+
+        let set_layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::all_graphics(),
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::Sampler)
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let pipeline_layout = PipelineLayout::new(
+            device.clone(),
+            PipelineLayoutCreateInfo {
+                set_layouts: vec![set_layout.clone()],
+                push_constant_ranges: vec![PushConstantRange {
+                    stages: ShaderStages::all_graphics(),
+                    offset: 0,
+                    size: 16,
+                }],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let ds_allocator = StandardDescriptorSetAllocator::new(device.clone(), Default::default());
+        let sampler =
+            Sampler::new(device.clone(), SamplerCreateInfo::simple_repeat_linear()).unwrap();
+        let descriptor_set = PersistentDescriptorSet::new(
+            &ds_allocator,
+            set_layout,
+            [WriteDescriptorSet::sampler(0, sampler)],
+            [],
+        )
+        .unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let vertex_buffer = Buffer::new_slice::<u32>(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::VERTEX_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+            16,
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        unsafe { cbb.disable_auto_sync() };
+
+        // The same state is bound twice, with a manual barrier in between. Without automatic
+        // synchronization, the builder must not skip the second binds as redundant.
+        for _ in 0..2 {
+            cbb.bind_descriptor_sets(
+                PipelineBindPoint::Graphics,
+                pipeline_layout.clone(),
+                0,
+                descriptor_set.clone(),
+            )
+            .unwrap()
+            .bind_vertex_buffers(0, vertex_buffer.clone())
+            .unwrap()
+            .push_constants_if_changed(pipeline_layout.clone(), 0, [1u32; 4])
+            .unwrap();
+
+            unsafe {
+                cbb.pipeline_barrier(DependencyInfo {
+                    memory_barriers: smallvec![MemoryBarrier {
+                        src_stages: PipelineStages::ALL_COMMANDS,
+                        dst_stages: PipelineStages::ALL_COMMANDS,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                })
+            }
+            .unwrap();
+        }
+
+        let cb = cbb.build().unwrap();
+        let count = |name| {
+            crate::library::null::recorded_commands(&*cb)
+                .into_iter()
+                .filter(|&command| command == name)
+                .count()
+        };
+        assert_eq!(count("vkCmdBindDescriptorSets"), 2);
+        assert_eq!(count("vkCmdBindVertexBuffers"), 2);
+        assert_eq!(count("vkCmdPushConstants"), 2);
+        assert_eq!(count("vkCmdPipelineBarrier"), 2);
+    }
+
+    #[test]
+    fn disable_auto_sync_for_buffer() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device));
+        let buffer = Buffer::new_slice::<u32>(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+            16,
+        )
+        .unwrap();
+
+        let has_buffer_barrier = |disabled: bool| {
+            let mut cbb = AutoCommandBufferBuilder::primary(
+                &cb_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap();
+
+            if disabled {
+                unsafe { cbb.disable_auto_sync_for_buffer(buffer.buffer().clone()) };
+            }
+
+            cbb.fill_buffer(buffer.clone(), 0)
+                .unwrap()
+                .fill_buffer(buffer.clone(), 1)
+                .unwrap();
+
+            let (barriers, _, _) = cbb.auto_sync().unwrap();
+            barriers
+                .values()
+                .flatten()
+                .flat_map(|dependency_info| &dependency_info.buffer_memory_barriers)
+                .any(|barrier| &barrier.buffer == buffer.buffer())
+        };
+
+        // The second fill must wait for the first one.
+        assert!(has_buffer_barrier(false));
+        // Unless the buffer is excluded, in which case no barrier may be inserted for it.
+        assert!(!has_buffer_barrier(true));
+    }
+
+    #[test]
+    fn disable_auto_sync_for_image() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device));
+        let image = Image::new(
+            memory_allocator,
+            ImageCreateInfo {
+                format: Format::R8G8B8A8_UNORM,
+                extent: [4, 4, 1],
+                usage: ImageUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+
+        let has_image_barrier = |disabled: bool| {
+            let mut cbb = AutoCommandBufferBuilder::primary(
+                &cb_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap();
+
+            if disabled {
+                unsafe { cbb.disable_auto_sync_for_image(image.clone()) };
+            }
+
+            cbb.clear_color_image(ClearColorImageInfo::image(image.clone()))
+                .unwrap();
+
+            let (barriers, _, _) = cbb.auto_sync().unwrap();
+            barriers
+                .values()
+                .flatten()
+                .flat_map(|dependency_info| &dependency_info.image_memory_barriers)
+                .any(|barrier| barrier.image == image)
+        };
+
+        // The image is transitioned into the transfer layout and back to its final layout.
+        assert!(has_image_barrier(false));
+        // Neither transition may be inserted for an excluded image.
+        assert!(!has_image_barrier(true));
+    }
+
+    #[test]
+    fn transform_feedback_requires_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device));
+        let buffer = Buffer::new_slice::<u32>(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::VERTEX_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+            16,
+        )
+        .unwrap();
+
+        assert!(cbb
+            .bind_transform_feedback_buffers(0, [buffer.into_bytes()])
+            .is_err());
+        assert!(cbb.begin_transform_feedback(0, [None]).is_err());
+        assert!(cbb.end_transform_feedback(0, []).is_err());
+    }
+
+    #[test]
+    fn vertex_buffers_with_strides() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let supported = device.api_version() >= Version::V1_3
+            || device.enabled_features().extended_dynamic_state;
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device));
+        let buffer = Buffer::new_slice::<u32>(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::VERTEX_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+            16,
+        )
+        .unwrap();
+
+        if !supported {
+            assert!(cbb
+                .bind_vertex_buffers_with_strides(0, buffer.clone(), smallvec![4])
+                .is_err());
+        }
+
+        unsafe {
+            cbb.bind_vertex_buffers_with_strides_unchecked(
+                1,
+                [buffer.clone(), buffer],
+                smallvec![4, 8],
+            );
+        }
+
+        assert!(!cbb
+            .builder_state
+            .vertex_input_binding_stride
+            .contains_key(&0));
+        assert_eq!(
+            cbb.builder_state.vertex_input_binding_stride.get(&1),
+            Some(&4)
+        );
+        assert_eq!(
+            cbb.builder_state.vertex_input_binding_stride.get(&2),
+            Some(&8)
+        );
+        assert!(cbb.builder_state.vertex_buffers.contains_key(&2));
+    }
+
+    #[test]
+    fn descriptor_set_binding() {
+        unsafe {
+            let (device, queue) = gfx_dev_and_queue!();
+
+            let cb_allocator =
+                StandardCommandBufferAllocator::new(device.clone(), Default::default());
+            let mut sync = AutoCommandBufferBuilder::primary(
+                &cb_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::MultipleSubmit,
+            )
+            .unwrap();
+            let set_layout = DescriptorSetLayout::new(
+                device.clone(),
+                DescriptorSetLayoutCreateInfo {
+                    bindings: [(
+                        0,
+                        DescriptorSetLayoutBinding {
+                            stages: ShaderStages::all_graphics(),
+                            ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::Sampler)
+                        },
+                    )]
+                    .into(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let pipeline_layout = PipelineLayout::new(
+                device.clone(),
+                PipelineLayoutCreateInfo {
+                    set_layouts: [set_layout.clone(), set_layout.clone()].into(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            let ds_allocator =
+                StandardDescriptorSetAllocator::new(device.clone(), Default::default());
+
+            let set = PersistentDescriptorSet::new(
+                &ds_allocator,
+                set_layout.clone(),
+                [WriteDescriptorSet::sampler(
+                    0,
+                    Sampler::new(device.clone(), SamplerCreateInfo::simple_repeat_linear())
+                        .unwrap(),
+                )],
+                [],
+            )
+            .unwrap();
+
+            sync.bind_descriptor_sets_unchecked(
+                PipelineBindPoint::Graphics,
+                pipeline_layout.clone(),
+                1,
+                set.clone(),
+            );
+
+            assert!(!sync
+                .builder_state
+                .descriptor_sets
+                .get(&PipelineBindPoint::Compute)
+                .map_or(false, |state| state.descriptor_sets.contains_key(&0)));
+            assert!(!sync
+                .builder_state
+                .descriptor_sets
+                .get(&PipelineBindPoint::Graphics)
+                .map_or(false, |state| state.descriptor_sets.contains_key(&0)));
+            assert!(sync
+                .builder_state
+                .descriptor_sets
+                .get(&PipelineBindPoint::Graphics)
+                .map_or(false, |state| state.descriptor_sets.contains_key(&1)));
+            assert!(!sync
+                .builder_state
+                .descriptor_sets
+                .get(&PipelineBindPoint::Graphics)
+                .map_or(false, |state| state.descriptor_sets.contains_key(&2)));
+
+            sync.bind_descriptor_sets_unchecked(
+                PipelineBindPoint::Graphics,
+                pipeline_layout,
+                0,
+                set,
+            );
+
+            assert!(sync
+                .builder_state
+                .descriptor_sets
+                .get(&PipelineBindPoint::Graphics)
+                .map_or(false, |state| state.descriptor_sets.contains_key(&0)));
+            assert!(sync
+                .builder_state
+                .descriptor_sets
+                .get(&PipelineBindPoint::Graphics)
+                .map_or(false, |state| state.descriptor_sets.contains_key(&1)));
+
+            let pipeline_layout = PipelineLayout::new(
+                device.clone(),
+                PipelineLayoutCreateInfo {
+                    set_layouts: [
+                        DescriptorSetLayout::new(device.clone(), Default::default()).unwrap(),
+                        set_layout.clone(),
+                    ]
+                    .into(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            let set = PersistentDescriptorSet::new(
+                &ds_allocator,
+                set_layout,
+                [WriteDescriptorSet::sampler(
+                    0,
+                    Sampler::new(device, SamplerCreateInfo::simple_repeat_linear()).unwrap(),
+                )],
+                [],
+            )
+            .unwrap();
+
+            sync.bind_descriptor_sets_unchecked(
+                PipelineBindPoint::Graphics,
+                pipeline_layout,
+                1,
+                set,
+            );
+
+            assert!(!sync
+                .builder_state
+                .descriptor_sets
+                .get(&PipelineBindPoint::Graphics)
+                .map_or(false, |state| state.descriptor_sets.contains_key(&0)));
+            assert!(sync
+                .builder_state
+                .descriptor_sets
+                .get(&PipelineBindPoint::Graphics)
+                .map_or(false, |state| state.descriptor_sets.contains_key(&1)));
+        }
+    }
+
+    #[test]
+    fn redundant_descriptor_set_binding() {
+        unsafe {
+            let (device, queue) = gfx_dev_and_queue!();
+
+            let cb_allocator =
+                StandardCommandBufferAllocator::new(device.clone(), Default::default());
+            let mut sync = AutoCommandBufferBuilder::primary(
+                &cb_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::MultipleSubmit,
+            )
+            .unwrap();
+            let set_layout = DescriptorSetLayout::new(
+                device.clone(),
+                DescriptorSetLayoutCreateInfo {
+                    bindings: [(
+                        0,
+                        DescriptorSetLayoutBinding {
+                            stages: ShaderStages::all_graphics(),
+                            ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::Sampler)
+                        },
+                    )]
+                    .into(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let pipeline_layout = PipelineLayout::new(
+                device.clone(),
+                PipelineLayoutCreateInfo {
+                    set_layouts: [set_layout.clone(), set_layout.clone()].into(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            let ds_allocator =
+                StandardDescriptorSetAllocator::new(device.clone(), Default::default());
+            let sampler = Sampler::new(device, SamplerCreateInfo::simple_repeat_linear()).unwrap();
+            let [set_a, set_b] = [(); 2].map(|_| -> DescriptorSetWithOffsets {
+                PersistentDescriptorSet::new(
+                    &ds_allocator,
+                    set_layout.clone(),
+                    [WriteDescriptorSet::sampler(0, sampler.clone())],
+                    [],
+                )
+                .unwrap()
+                .into()
+            });
+
+            sync.bind_descriptor_sets_unchecked(
+                PipelineBindPoint::Graphics,
+                pipeline_layout,
+                0,
+                vec![set_a.clone(), set_b.clone()],
+            );
+
+            let state = &sync.builder_state.descriptor_sets[&PipelineBindPoint::Graphics];
+            assert_eq!(
+                state.changed_descriptor_sets(0, &[set_a.clone(), set_b.clone()]),
+                None,
+            );
+            assert_eq!(
+                state.changed_descriptor_sets(0, &[set_b.clone(), set_b.clone()]),
+                Some(0..1),
+            );
+
+            // Changing only the dynamic offsets requires the set to be bound again.
+            let (set, _) = set_b.as_ref();
+            let set_b_offset = DescriptorSetWithOffsets::new(set.clone(), [256]);
+            assert_eq!(
+                state.changed_descriptor_sets(1, slice::from_ref(&set_b_offset)),
+                Some(0..1),
+            );
+            assert_eq!(
+                state.changed_descriptor_sets(0, &[set_a, set_b_offset]),
+                Some(1..2),
+            );
+        }
+    }
+
+    #[test]
+    fn merge_barriers() {
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device));
+        let create_buffer = || {
+            Buffer::new_slice::<u32>(
+                memory_allocator.clone(),
+                BufferCreateInfo {
+                    usage: BufferUsage::TRANSFER_DST,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+                16,
+            )
+            .unwrap()
+            .buffer()
+            .clone()
+        };
+        let buffer_a = create_buffer();
+        let buffer_b = create_buffer();
+
+        let barrier = |buffer: &Arc<Buffer>, range| DependencyInfo {
+            buffer_memory_barriers: [BufferMemoryBarrier {
+                src_stages: PipelineStages::ALL_TRANSFER,
+                src_access: AccessFlags::TRANSFER_WRITE,
+                dst_stages: PipelineStages::ALL_TRANSFER,
+                dst_access: AccessFlags::TRANSFER_WRITE,
+                range,
+                ..BufferMemoryBarrier::buffer(buffer.clone())
+            }]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+
+        // Barriers for different resources and adjacent ranges are merged into one, and empty
+        // barriers are removed.
+        let mut dependency_infos = vec![
+            barrier(&buffer_a, 0..16),
+            DependencyInfo::default(),
+            barrier(&buffer_a, 16..32),
+            barrier(&buffer_b, 0..64),
+        ];
+        merge_dependency_infos(&mut dependency_infos);
+        assert_eq!(dependency_infos.len(), 1);
+
+        let buffer_memory_barriers = &dependency_infos[0].buffer_memory_barriers;
+        assert_eq!(buffer_memory_barriers.len(), 2);
+        assert_eq!(buffer_memory_barriers[0].range, 0..32);
+        assert_eq!(buffer_memory_barriers[1].range, 0..64);
+
+        // Barriers for overlapping ranges of the same buffer must stay ordered.
+        let mut dependency_infos = vec![barrier(&buffer_a, 0..32), barrier(&buffer_a, 16..48)];
+        merge_dependency_infos(&mut dependency_infos);
+        assert_eq!(dependency_infos.len(), 2);
+    }
+
+    #[test]
+    fn push_constants_cache() {
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        let create_layout = |size| {
+            PipelineLayout::new(
+                device.clone(),
+                PipelineLayoutCreateInfo {
+                    push_constant_ranges: vec![PushConstantRange {
+                        stages: ShaderStages::all_graphics(),
+                        offset: 0,
+                        size,
+                    }],
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        };
+        let layout_a = create_layout(16);
+        let layout_b = create_layout(16);
+        let layout_c = create_layout(32);
+
+        let mut cache = PushConstantsCache::default();
+        assert!(!cache.update(&layout_a, 0, &[1; 16]));
+
+        // Pushing the same data with a compatible layout can be skipped, also for a subrange.
+        assert!(cache.update(&layout_b, 0, &[1; 16]));
+        assert!(cache.update(&layout_b, 4, &[1; 8]));
+
+        // Pushing different data, or to a range that wasn't pushed before, can't.
+        assert!(!cache.update(&layout_a, 4, &[2; 4]));
+        assert!(!cache.update(&layout_a, 0, &[1; 16]));
+        assert!(!cache.update(&layout_a, 12, &[1; 8]));
+
+        // Binding a pipeline with an incompatible layout forgets the data.
+        cache.invalidate(&layout_b);
+        assert!(cache.update(&layout_a, 0, &[1; 16]));
+        cache.invalidate(&layout_c);
+        assert!(!cache.update(&layout_c, 0, &[1; 16]));
+    }
+
+    #[cfg(feature = "null_driver")]
+    #[test]
+    fn push_constants_if_changed() {
+        let (device, queue) = gfx_dev_and_queue!();
+        skip_unless_null_device!(device);
+
+        let layout = PipelineLayout::new(
+            device.clone(),
+            PipelineLayoutCreateInfo {
+                push_constant_ranges: vec![PushConstantRange {
+                    stages: ShaderStages::all_graphics(),
+                    offset: 0,
+                    size: 16,
+                }],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        cbb.push_constants_if_changed(layout.clone(), 0, [1u32; 4])
+            .unwrap()
+            // Skipped, the data is already set.
+            .push_constants_if_changed(layout.clone(), 0, [1u32; 4])
+            .unwrap()
+            .push_constants_if_changed(layout.clone(), 4, [1u32; 2])
+            .unwrap()
+            // Not skipped, the data differs.
+            .push_constants_if_changed(layout.clone(), 0, [2u32; 4])
+            .unwrap()
+            // `push_constants` always records a command, and makes the cached data unknown.
+            .push_constants(layout.clone(), 0, [2u32; 4])
+            .unwrap()
+            .push_constants_if_changed(layout, 0, [2u32; 4])
+            .unwrap();
+
+        let cb = cbb.build().unwrap();
+        let push_count = crate::library::null::recorded_commands(&*cb)
+            .into_iter()
+            .filter(|&command| command == "vkCmdPushConstants")
+            .count();
+        assert_eq!(push_count, 4);
+    }
+
+    #[test]
+    fn shader_objects_require_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        match cbb.bind_shaders([(ShaderStage::Compute, None)]) {
+            Err(err)
+                if matches!(
+                    *err,
+                    ValidationError {
+                        requires_one_of: RequiresOneOf([RequiresAllOf([Requires::Feature(
+                            "shader_object"
+                        )])]),
+                        ..
+                    }
+                ) => {}
+            _ => panic!(),
+        }
+        assert!(cbb.set_polygon_mode(PolygonMode::Line).is_err());
+
+        // Neither a pipeline nor a shader object is bound.
+        assert!(cbb.dispatch([1, 1, 1]).is_err());
+    }
+
+    #[test]
+    fn dispatch_with_shader_object() {
+        let instance = instance!();
+
+        let enabled_extensions = DeviceExtensions {
+            ext_shader_object: true,
+            ..DeviceExtensions::empty()
+        };
+        let enabled_features = Features {
+            shader_object: true,
+            dynamic_rendering: true,
+            ..Features::empty()
+        };
+
+        let (physical_device, queue_family_index) = match instance
+            .enumerate_physical_devices()
+            .unwrap()
+            .filter(|p| {
+                p.api_version() >= Version::V1_3
+                    && p.supported_extensions().contains(&enabled_extensions)
+                    && p.supported_features().contains(&enabled_features)
+            })
+            .find_map(|p| {
+                p.queue_family_properties()
+                    .iter()
+                    .position(|q| q.queue_flags.intersects(QueueFlags::COMPUTE))
+                    .map(|i| (p, i as u32))
+            }) {
+            Some(x) => x,
+            None => return,
+        };
+
+        let (device, _) = Device::new(
+            physical_device,
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo {
+                    queue_family_index,
+                    ..Default::default()
+                }],
+                enabled_extensions,
+                enabled_features,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+            layout(constant_id = 83) const int VALUE = 0xdeadbeef;
+
+            layout(set = 0, binding = 0) buffer Output {
+                int write;
+            } write;
+
+            void main() {
+                write.write = VALUE;
+            }
+            */
+            const MODULE: [u32; 120] = [
+                119734787, 65536, 524289, 14, 0, 131089, 1, 393227, 1, 1280527431, 1685353262,
+                808793134, 0, 196622, 0, 1, 327695, 5, 4, 1852399981, 0, 393232, 4, 17, 1, 1, 1,
+                196611, 2, 450, 262149, 4, 1852399981, 0, 262149, 7, 1886680399, 29813, 327686, 7,
+                0, 1953067639, 101, 262149, 9, 1953067639, 101, 262149, 11, 1431060822, 69, 327752,
+                7, 0, 35, 0, 196679, 7, 3, 262215, 9, 34, 0, 262215, 9, 33, 0, 262215, 11, 1, 83,
+                131091, 2, 196641, 3, 2, 262165, 6, 32, 1, 196638, 7, 6, 262176, 8, 2, 7, 262203,
+                8, 9, 2, 262187, 6, 10, 0, 262194, 6, 11, 3735928559, 262176, 12, 2, 6, 327734, 2,
+                4, 0, 3, 131320, 5, 327745, 12, 13, 9, 10, 196670, 13, 11, 65789, 65592,
+            ];
+            ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE))
+                .unwrap()
+                .entry_point("main")
+                .unwrap()
+        };
+        let layout = PipelineLayout::new(
+            device.clone(),
+            PipelineDescriptorSetLayoutCreateInfo::from_stages([
+                &PipelineShaderStageCreateInfo::new(cs.clone()),
+            ])
+            .into_pipeline_layout_create_info(device.clone())
+            .unwrap(),
+        )
+        .unwrap();
+        let shader =
+            ShaderObject::new(device.clone(), ShaderCreateInfo::new(cs, layout.clone())).unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let buffer = Buffer::new_sized::<u32>(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::STORAGE_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+        let ds_allocator = StandardDescriptorSetAllocator::new(device.clone(), Default::default());
+        let set = PersistentDescriptorSet::new(
+            &ds_allocator,
+            layout.set_layouts()[0].clone(),
+            [WriteDescriptorSet::buffer(0, buffer)],
+            [],
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue_family_index,
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        cbb.bind_shaders([(ShaderStage::Compute, Some(shader))])
+            .unwrap();
+
+        // The shader object uses a descriptor set that isn't bound yet.
+        assert!(cbb.dispatch([1, 1, 1]).is_err());
+
+        cbb.bind_descriptor_sets(PipelineBindPoint::Compute, layout, 0, set)
+            .unwrap()
+            .dispatch([1, 1, 1])
+            .unwrap();
+
+        // The shader object wasn't created with `ShaderCreateFlags::DISPATCH_BASE`.
+        assert!(cbb.dispatch_base([1, 0, 0], [1, 1, 1]).is_err());
+
+        cbb.build().unwrap();
     }
 }
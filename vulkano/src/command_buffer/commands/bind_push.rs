@@ -23,12 +23,14 @@ use crate::{
     memory::is_aligned,
     pipeline::{
         graphics::vertex_input::VertexBuffersCollection, ComputePipeline, GraphicsPipeline,
-        PipelineBindPoint, PipelineLayout,
+        Pipeline, PipelineBindPoint, PipelineCreateFlags, PipelineLayout, RayTracingPipeline,
     },
-    DeviceSize, Requires, RequiresAllOf, RequiresOneOf, ValidationError, VulkanObject,
+    shader::{object::ShaderObject, ShaderStage},
+    DeviceSize, Requires, RequiresAllOf, RequiresOneOf, ValidationError, Version, VulkanObject,
 };
+use bytemuck::NoUninit;
 use smallvec::SmallVec;
-use std::{cmp::min, ffi::c_void, mem::size_of, sync::Arc};
+use std::{cmp::min, ffi::c_void, mem::size_of, ptr, sync::Arc};
 
 /// # Commands to bind or push state for pipeline execution commands.
 ///
@@ -38,6 +40,10 @@ where
     A: CommandBufferAllocator,
 {
     /// Binds descriptor sets for future dispatch or draw calls.
+    ///
+    /// Leading and trailing descriptor sets that are already bound to the same set number, with
+    /// the same dynamic offsets, are left out of the recorded command. If all of them are already
+    /// bound, no command is recorded.
     pub fn bind_descriptor_sets(
         &mut self,
         pipeline_bind_point: PipelineBindPoint,
@@ -88,25 +94,41 @@ where
         first_set: u32,
         descriptor_sets: impl DescriptorSetsCollection,
     ) -> &mut Self {
-        let descriptor_sets = descriptor_sets.into_vec();
+        let mut descriptor_sets = descriptor_sets.into_vec();
 
         if descriptor_sets.is_empty() {
             return self;
         }
 
+        let auto_sync_disabled = self.auto_sync_disabled;
         let state = self.builder_state.invalidate_descriptor_sets(
             pipeline_bind_point,
             pipeline_layout.clone(),
             first_set,
             descriptor_sets.len() as u32,
         );
+        let changed_range = if auto_sync_disabled {
+            Some(0..descriptor_sets.len())
+        } else {
+            state.changed_descriptor_sets(first_set, &descriptor_sets)
+        };
 
+        // The sets are stored even if they are already bound, because they may refer to
+        // different resources than the set that was bound with the same handle.
         for (set_num, set) in descriptor_sets.iter().enumerate() {
             state
                 .descriptor_sets
                 .insert(first_set + set_num as u32, SetOrPush::Set(set.clone()));
         }
 
+        let changed_range = match changed_range {
+            Some(changed_range) => changed_range,
+            None => return self,
+        };
+        descriptor_sets.truncate(changed_range.end);
+        descriptor_sets.drain(..changed_range.start);
+        let first_set = first_set + changed_range.start as u32;
+
         self.add_command(
             "bind_descriptor_sets",
             Default::default(),
@@ -185,7 +207,14 @@ where
         &mut self,
         pipeline: Arc<ComputePipeline>,
     ) -> &mut Self {
+        self.builder_state
+            .push_constants_cache
+            .invalidate(pipeline.layout());
         self.builder_state.pipeline_compute = Some(pipeline.clone());
+        self.builder_state.shaders.remove(&ShaderStage::Compute);
+        self.builder_state
+            .bound_resources
+            .remove(&PipelineBindPoint::Compute);
         self.add_command(
             "bind_pipeline_compute",
             Default::default(),
@@ -213,6 +242,14 @@ where
     ) -> Result<(), Box<ValidationError>> {
         self.inner.validate_bind_pipeline_graphics(pipeline)?;
 
+        if self.builder_state.transform_feedback_active {
+            return Err(Box::new(ValidationError {
+                problem: "transform feedback is active".into(),
+                vuids: &["VUID-vkCmdBindPipeline-None-02323"],
+                ..Default::default()
+            }));
+        }
+
         // VUID-vkCmdBindPipeline-pipeline-00781
         // TODO:
 
@@ -228,7 +265,17 @@ where
         // overwrite these states.
         self.builder_state
             .reset_dynamic_states(pipeline.fixed_state().iter().copied());
+        self.builder_state.reset_shader_object_states();
+        self.builder_state
+            .push_constants_cache
+            .invalidate(pipeline.layout());
         self.builder_state.pipeline_graphics = Some(pipeline.clone());
+        self.builder_state
+            .shaders
+            .retain(|&stage, _| stage == ShaderStage::Compute);
+        self.builder_state
+            .bound_resources
+            .remove(&PipelineBindPoint::Graphics);
         self.add_command(
             "bind_pipeline_graphics",
             Default::default(),
@@ -240,7 +287,144 @@ where
         self
     }
 
+    /// Binds a ray tracing pipeline for future ray tracing calls.
+    pub fn bind_pipeline_ray_tracing(
+        &mut self,
+        pipeline: Arc<RayTracingPipeline>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_bind_pipeline_ray_tracing(&pipeline)?;
+
+        unsafe { Ok(self.bind_pipeline_ray_tracing_unchecked(pipeline)) }
+    }
+
+    fn validate_bind_pipeline_ray_tracing(
+        &self,
+        pipeline: &RayTracingPipeline,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_bind_pipeline_ray_tracing(pipeline)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn bind_pipeline_ray_tracing_unchecked(
+        &mut self,
+        pipeline: Arc<RayTracingPipeline>,
+    ) -> &mut Self {
+        self.builder_state
+            .push_constants_cache
+            .invalidate(pipeline.layout());
+        self.builder_state.pipeline_ray_tracing = Some(pipeline.clone());
+        self.builder_state
+            .bound_resources
+            .remove(&PipelineBindPoint::RayTracing);
+        self.add_command(
+            "bind_pipeline_ray_tracing",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.bind_pipeline_ray_tracing_unchecked(&pipeline);
+            },
+        );
+
+        self
+    }
+
+    /// Binds shader objects for future dispatch or draw calls, or unbinds them.
+    ///
+    /// Each element of `shaders` specifies a shader stage, and the shader object to bind to that
+    /// stage. If the shader object is `None`, then any shader object bound to that stage is
+    /// unbound.
+    ///
+    /// Binding a shader object to the compute stage unbinds the bound compute pipeline, and
+    /// binding a shader object to a graphics stage unbinds the bound graphics pipeline. Binding a
+    /// pipeline in turn unbinds all shader objects of its bind point.
+    ///
+    /// Before drawing, every graphics stage that is supported by the enabled features must have
+    /// been bound, to a shader object or to `None`, and all state that would otherwise be part of
+    /// a graphics pipeline must have been set with the dynamic state commands. See the
+    /// [`shader::object`](crate::shader::object) module.
+    pub fn bind_shaders(
+        &mut self,
+        shaders: impl IntoIterator<Item = (ShaderStage, Option<Arc<ShaderObject>>)>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        let shaders: SmallVec<[_; 5]> = shaders.into_iter().collect();
+        self.validate_bind_shaders(&shaders)?;
+
+        unsafe { Ok(self.bind_shaders_unchecked(shaders)) }
+    }
+
+    fn validate_bind_shaders(
+        &self,
+        shaders: &[(ShaderStage, Option<Arc<ShaderObject>>)],
+    ) -> Result<(), Box<ValidationError>> {
+        let shaders_ref: SmallVec<[_; 5]> = shaders
+            .iter()
+            .map(|(stage, shader)| (*stage, shader.as_deref()))
+            .collect();
+        self.inner.validate_bind_shaders(&shaders_ref)?;
+
+        if self.builder_state.transform_feedback_active
+            && shaders
+                .iter()
+                .any(|&(stage, _)| stage != ShaderStage::Compute)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "transform feedback is active".into(),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn bind_shaders_unchecked(
+        &mut self,
+        shaders: impl IntoIterator<Item = (ShaderStage, Option<Arc<ShaderObject>>)>,
+    ) -> &mut Self {
+        let shaders: SmallVec<[_; 5]> = shaders.into_iter().collect();
+
+        for (stage, shader) in &shaders {
+            let pipeline_bind_point = if *stage == ShaderStage::Compute {
+                self.builder_state.pipeline_compute = None;
+                PipelineBindPoint::Compute
+            } else {
+                self.builder_state.pipeline_graphics = None;
+                PipelineBindPoint::Graphics
+            };
+
+            if let Some(shader) = shader {
+                self.builder_state
+                    .push_constants_cache
+                    .invalidate(shader.layout());
+            }
+
+            self.builder_state.shaders.insert(*stage, shader.clone());
+            self.builder_state
+                .bound_resources
+                .remove(&pipeline_bind_point);
+        }
+
+        self.add_command(
+            "bind_shaders",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                let shaders: SmallVec<[_; 5]> = shaders
+                    .iter()
+                    .map(|(stage, shader)| (*stage, shader.as_deref()))
+                    .collect();
+                out.bind_shaders_unchecked(&shaders);
+            },
+        );
+
+        self
+    }
+
     /// Binds vertex buffers for future draw calls.
+    ///
+    /// Leading and trailing vertex buffers that are already bound to the same binding are left
+    /// out of the recorded command, and if all of them are already bound, no command is recorded.
     pub fn bind_vertex_buffers(
         &mut self,
         first_binding: u32,
@@ -269,7 +453,22 @@ where
         first_binding: u32,
         vertex_buffers: impl VertexBuffersCollection,
     ) -> &mut Self {
-        let vertex_buffers = vertex_buffers.into_vec();
+        let mut vertex_buffers = vertex_buffers.into_vec();
+
+        let changed_range = if self.auto_sync_disabled {
+            0..vertex_buffers.len()
+        } else {
+            match self
+                .builder_state
+                .changed_vertex_buffers(first_binding, &vertex_buffers)
+            {
+                Some(changed_range) => changed_range,
+                None => return self,
+            }
+        };
+        vertex_buffers.truncate(changed_range.end);
+        vertex_buffers.drain(..changed_range.start);
+        let first_binding = first_binding + changed_range.start as u32;
 
         for (i, buffer) in vertex_buffers.iter().enumerate() {
             self.builder_state
@@ -277,6 +476,10 @@ where
                 .insert(first_binding + i as u32, buffer.clone());
         }
 
+        self.builder_state
+            .bound_resources
+            .remove(&PipelineBindPoint::Graphics);
+
         self.add_command(
             "bind_vertex_buffers",
             Default::default(),
@@ -288,7 +491,95 @@ where
         self
     }
 
+    /// Binds vertex buffers for future draw calls, and sets the stride of each binding.
+    ///
+    /// The strides are only used if the currently bound graphics pipeline has the
+    /// [`DynamicState::VertexInputBindingStride`] dynamic state enabled. In that case, this
+    /// command must be called for every vertex input binding of the pipeline, and the strides
+    /// of the pipeline's [`VertexInputState`] are ignored.
+    ///
+    /// `strides` must contain one element for each element of `vertex_buffers`.
+    ///
+    /// Unlike [`bind_vertex_buffers`](Self::bind_vertex_buffers), a command is always recorded.
+    ///
+    /// [`DynamicState::VertexInputBindingStride`]: crate::pipeline::DynamicState::VertexInputBindingStride
+    /// [`VertexInputState`]: crate::pipeline::graphics::vertex_input::VertexInputState
+    pub fn bind_vertex_buffers_with_strides(
+        &mut self,
+        first_binding: u32,
+        vertex_buffers: impl VertexBuffersCollection,
+        strides: SmallVec<[u32; 2]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        let vertex_buffers = vertex_buffers.into_vec();
+        self.validate_bind_vertex_buffers_with_strides(first_binding, &vertex_buffers, &strides)?;
+
+        unsafe {
+            Ok(self.bind_vertex_buffers_with_strides_unchecked(
+                first_binding,
+                vertex_buffers,
+                strides,
+            ))
+        }
+    }
+
+    fn validate_bind_vertex_buffers_with_strides(
+        &self,
+        first_binding: u32,
+        vertex_buffers: &[Subbuffer<[u8]>],
+        strides: &[u32],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_bind_vertex_buffers_with_strides(
+            first_binding,
+            vertex_buffers,
+            strides,
+        )?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn bind_vertex_buffers_with_strides_unchecked(
+        &mut self,
+        first_binding: u32,
+        vertex_buffers: impl VertexBuffersCollection,
+        strides: SmallVec<[u32; 2]>,
+    ) -> &mut Self {
+        let vertex_buffers = vertex_buffers.into_vec();
+
+        for (i, (buffer, &stride)) in vertex_buffers.iter().zip(&strides).enumerate() {
+            let binding = first_binding + i as u32;
+            self.builder_state
+                .vertex_buffers
+                .insert(binding, buffer.clone());
+            self.builder_state
+                .vertex_input_binding_stride
+                .insert(binding, stride);
+        }
+
+        self.builder_state
+            .bound_resources
+            .remove(&PipelineBindPoint::Graphics);
+
+        self.add_command(
+            "bind_vertex_buffers_with_strides",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.bind_vertex_buffers_with_strides_unchecked(
+                    first_binding,
+                    &vertex_buffers,
+                    &strides,
+                );
+            },
+        );
+
+        self
+    }
+
     /// Sets push constants for future dispatch or draw calls.
+    ///
+    /// A command is always recorded. Use
+    /// [`push_constants_if_changed`](Self::push_constants_if_changed) to skip pushes of data that
+    /// is already set.
     pub fn push_constants<Pc>(
         &mut self,
         pipeline_layout: Arc<PipelineLayout>,
@@ -331,6 +622,78 @@ where
     where
         Pc: BufferContents,
     {
+        // `Pc` may contain padding, so its bytes can't be read to update the cache. Forget the
+        // cached data instead, as it may be overwritten.
+        self.builder_state.push_constants_cache = Default::default();
+
+        self.push_constants_unchecked_uncached(pipeline_layout, offset, push_constants)
+    }
+
+    /// Sets push constants for future dispatch or draw calls, unless the same data is already
+    /// set.
+    ///
+    /// No command is recorded if the same data was already pushed to the same range with
+    /// `push_constants_if_changed` and a pipeline layout that is compatible for push constants,
+    /// and since then no pipeline with an incompatible layout has been bound and no other push
+    /// constants have been pushed with [`push_constants`](Self::push_constants).
+    ///
+    /// The data is compared byte by byte, which is why `Pc` must not contain padding.
+    pub fn push_constants_if_changed<Pc>(
+        &mut self,
+        pipeline_layout: Arc<PipelineLayout>,
+        offset: u32,
+        push_constants: Pc,
+    ) -> Result<&mut Self, Box<ValidationError>>
+    where
+        Pc: BufferContents + NoUninit,
+    {
+        if size_of::<Pc>() == 0 {
+            return Ok(self);
+        }
+
+        self.validate_push_constants(&pipeline_layout, offset, &push_constants)?;
+
+        unsafe {
+            Ok(self.push_constants_if_changed_unchecked(pipeline_layout, offset, push_constants))
+        }
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn push_constants_if_changed_unchecked<Pc>(
+        &mut self,
+        pipeline_layout: Arc<PipelineLayout>,
+        offset: u32,
+        push_constants: Pc,
+    ) -> &mut Self
+    where
+        Pc: BufferContents + NoUninit,
+    {
+        if self.auto_sync_disabled {
+            return self.push_constants_unchecked(pipeline_layout, offset, push_constants);
+        }
+
+        if self.builder_state.push_constants_cache.update(
+            &pipeline_layout,
+            offset,
+            bytemuck::bytes_of(&push_constants),
+        ) {
+            return self;
+        }
+
+        self.push_constants_unchecked_uncached(pipeline_layout, offset, push_constants)
+    }
+
+    unsafe fn push_constants_unchecked_uncached<Pc>(
+        &mut self,
+        pipeline_layout: Arc<PipelineLayout>,
+        offset: u32,
+        push_constants: Pc,
+    ) -> &mut Self
+    where
+        Pc: BufferContents,
+    {
+        let size = size_of::<Pc>();
+
         // TODO: Push constant invalidations.
         // The Vulkan spec currently is unclear about this, so Vulkano currently just marks
         // push constants as set, and never unsets them. See:
@@ -338,7 +701,7 @@ where
         // https://github.com/KhronosGroup/Vulkan-ValidationLayers/issues/2711
         self.builder_state
             .push_constants
-            .insert(offset..offset + size_of::<Pc>() as u32);
+            .insert(offset..offset + size as u32);
         self.builder_state.push_constants_pipeline_layout = Some(pipeline_layout.clone());
 
         self.add_command(
@@ -524,6 +887,25 @@ where
                     }));
                 }
             }
+            PipelineBindPoint::RayTracing => {
+                if !queue_family_properties
+                    .queue_flags
+                    .intersects(QueueFlags::COMPUTE)
+                {
+                    return Err(Box::new(ValidationError {
+                        context: "pipeline_bind_point".into(),
+                        problem: "is `PipelineBindPoint::RayTracing`, but \
+                            the queue family of the command buffer does not support \
+                            compute operations"
+                            .into(),
+                        vuids: &[
+                            "VUID-vkCmdBindDescriptorSets-pipelineBindPoint-00361",
+                            "VUID-vkCmdBindDescriptorSets-commandBuffer-cmdpool",
+                        ],
+                        ..Default::default()
+                    }));
+                }
+            }
         }
 
         if first_set + descriptor_sets.len() as u32 > pipeline_layout.set_layouts().len() as u32 {
@@ -873,6 +1255,15 @@ where
         // VUID-vkCmdBindPipeline-commonparent
         assert_eq!(self.device(), pipeline.device());
 
+        if pipeline.flags().intersects(PipelineCreateFlags::LIBRARY) {
+            return Err(Box::new(ValidationError {
+                context: "pipeline.flags()".into(),
+                problem: "contains `PipelineCreateFlags::LIBRARY`".into(),
+                vuids: &["VUID-vkCmdBindPipeline-pipeline-03382"],
+                ..Default::default()
+            }));
+        }
+
         Ok(())
     }
 
@@ -891,6 +1282,217 @@ where
         self
     }
 
+    pub unsafe fn bind_pipeline_ray_tracing(
+        &mut self,
+        pipeline: &RayTracingPipeline,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_bind_pipeline_ray_tracing(pipeline)?;
+
+        Ok(self.bind_pipeline_ray_tracing_unchecked(pipeline))
+    }
+
+    fn validate_bind_pipeline_ray_tracing(
+        &self,
+        pipeline: &RayTracingPipeline,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::COMPUTE)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    compute operations"
+                    .into(),
+                vuids: &["VUID-vkCmdBindPipeline-pipelineBindPoint-02391"],
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkCmdBindPipeline-commonparent
+        assert_eq!(self.device(), pipeline.device());
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn bind_pipeline_ray_tracing_unchecked(
+        &mut self,
+        pipeline: &RayTracingPipeline,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.v1_0.cmd_bind_pipeline)(
+            self.handle(),
+            ash::vk::PipelineBindPoint::RAY_TRACING_KHR,
+            pipeline.handle(),
+        );
+
+        self
+    }
+
+    /// Binds shader objects for future dispatch or draw calls, or unbinds them.
+    ///
+    /// Each element of `shaders` specifies a shader stage, and the shader object to bind to that
+    /// stage. If the shader object is `None`, then any shader object bound to that stage is
+    /// unbound.
+    ///
+    /// Unlike [`AutoCommandBufferBuilder::bind_shaders`], this does not unbind or validate any
+    /// other state.
+    pub unsafe fn bind_shaders(
+        &mut self,
+        shaders: &[(ShaderStage, Option<&ShaderObject>)],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_bind_shaders(shaders)?;
+
+        Ok(self.bind_shaders_unchecked(shaders))
+    }
+
+    fn validate_bind_shaders(
+        &self,
+        shaders: &[(ShaderStage, Option<&ShaderObject>)],
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_features().shader_object {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "shader_object",
+                )])]),
+                vuids: &["VUID-vkCmdBindShadersEXT-None-08462"],
+                ..Default::default()
+            }));
+        }
+
+        let queue_flags = self.queue_family_properties().queue_flags;
+
+        for (index, &(stage, shader)) in shaders.iter().enumerate() {
+            stage.validate_device(self.device()).map_err(|err| {
+                err.add_context(format!("shaders[{}].0", index))
+                    .set_vuids(&["VUID-vkCmdBindShadersEXT-pStages-parameter"])
+            })?;
+
+            if shaders[..index]
+                .iter()
+                .any(|&(other_stage, _)| other_stage == stage)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`shaders[{}].0` is also specified in an earlier element of `shaders`",
+                        index
+                    )
+                    .into(),
+                    vuids: &["VUID-vkCmdBindShadersEXT-pStages-08463"],
+                    ..Default::default()
+                }));
+            }
+
+            match stage {
+                ShaderStage::Compute => {
+                    if !queue_flags.intersects(QueueFlags::COMPUTE) {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "`shaders[{}].0` is `ShaderStage::Compute`, but the queue \
+                                family of the command buffer does not support compute \
+                                operations",
+                                index
+                            )
+                            .into(),
+                            vuids: &["VUID-vkCmdBindShadersEXT-pShaders-08476"],
+                            ..Default::default()
+                        }));
+                    }
+                }
+                ShaderStage::Vertex
+                | ShaderStage::TessellationControl
+                | ShaderStage::TessellationEvaluation
+                | ShaderStage::Geometry
+                | ShaderStage::Fragment
+                | ShaderStage::Task
+                | ShaderStage::Mesh => {
+                    if !queue_flags.intersects(QueueFlags::GRAPHICS) {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "`shaders[{}].0` is a graphics shader stage, but the queue \
+                                family of the command buffer does not support graphics \
+                                operations",
+                                index
+                            )
+                            .into(),
+                            vuids: &[
+                                "VUID-vkCmdBindShadersEXT-pShaders-08477",
+                                "VUID-vkCmdBindShadersEXT-pShaders-08478",
+                            ],
+                            ..Default::default()
+                        }));
+                    }
+                }
+                _ => {
+                    return Err(Box::new(ValidationError {
+                        context: format!("shaders[{}].0", index).into(),
+                        problem: "is not a vertex, tessellation, geometry, fragment, compute, \
+                            task or mesh shader stage"
+                            .into(),
+                        vuids: &[
+                            "VUID-vkCmdBindShadersEXT-pStages-08464",
+                            "VUID-vkCmdBindShadersEXT-pStages-08465",
+                            "VUID-vkCmdBindShadersEXT-pStages-08467",
+                            "VUID-vkCmdBindShadersEXT-pStages-08468",
+                        ],
+                        ..Default::default()
+                    }));
+                }
+            }
+
+            if let Some(shader) = shader {
+                // VUID-vkCmdBindShadersEXT-commonparent
+                assert_eq!(self.device(), shader.device());
+
+                if shader.stage() != stage {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`shaders[{0}].1` is `Some`, but its stage does not equal \
+                            `shaders[{0}].0`",
+                            index
+                        )
+                        .into(),
+                        vuids: &["VUID-vkCmdBindShadersEXT-pShaders-08469"],
+                        ..Default::default()
+                    }));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn bind_shaders_unchecked(
+        &mut self,
+        shaders: &[(ShaderStage, Option<&ShaderObject>)],
+    ) -> &mut Self {
+        if shaders.is_empty() {
+            return self;
+        }
+
+        let (stages_vk, shaders_vk): (SmallVec<[_; 5]>, SmallVec<[_; 5]>) = shaders
+            .iter()
+            .map(|&(stage, shader)| {
+                (
+                    ash::vk::ShaderStageFlags::from(stage),
+                    shader.map_or(ash::vk::ShaderEXT::null(), VulkanObject::handle),
+                )
+            })
+            .unzip();
+
+        let fns = self.device().fns();
+        (fns.ext_shader_object.cmd_bind_shaders_ext)(
+            self.handle(),
+            stages_vk.len() as u32,
+            stages_vk.as_ptr(),
+            shaders_vk.as_ptr(),
+        );
+
+        self
+    }
+
     pub unsafe fn bind_vertex_buffers(
         &mut self,
         first_binding: u32,
@@ -983,6 +1585,150 @@ where
         self
     }
 
+    pub unsafe fn bind_vertex_buffers_with_strides(
+        &mut self,
+        first_binding: u32,
+        vertex_buffers: &[Subbuffer<[u8]>],
+        strides: &[u32],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_bind_vertex_buffers_with_strides(first_binding, vertex_buffers, strides)?;
+
+        Ok(self.bind_vertex_buffers_with_strides_unchecked(first_binding, vertex_buffers, strides))
+    }
+
+    fn validate_bind_vertex_buffers_with_strides(
+        &self,
+        first_binding: u32,
+        vertex_buffers: &[Subbuffer<[u8]>],
+        strides: &[u32],
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self.device().api_version() >= Version::V1_3
+            || self.device().enabled_features().extended_dynamic_state)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
+                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state")]),
+                ]),
+                vuids: &["VUID-vkCmdBindVertexBuffers2-None-08971"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdBindVertexBuffers2-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        let properties = self.device().physical_device().properties();
+
+        if first_binding + vertex_buffers.len() as u32 > properties.max_vertex_input_bindings {
+            return Err(Box::new(ValidationError {
+                problem: "`first_binding + vertex_buffers.len()` is greater than the \
+                    `max_vertex_input_bindings` limit"
+                    .into(),
+                vuids: &[
+                    "VUID-vkCmdBindVertexBuffers2-firstBinding-03355",
+                    "VUID-vkCmdBindVertexBuffers2-firstBinding-03356",
+                ],
+                ..Default::default()
+            }));
+        }
+
+        if strides.len() != vertex_buffers.len() {
+            return Err(Box::new(ValidationError {
+                problem: "`strides.len()` does not equal `vertex_buffers.len()`".into(),
+                vuids: &["VUID-vkCmdBindVertexBuffers2-pStrides-parameter"],
+                ..Default::default()
+            }));
+        }
+
+        for (vertex_buffers_index, buffer) in vertex_buffers.iter().enumerate() {
+            // VUID-vkCmdBindVertexBuffers2-commonparent
+            assert_eq!(self.device(), buffer.device());
+
+            if !buffer
+                .buffer()
+                .usage()
+                .intersects(BufferUsage::VERTEX_BUFFER)
+            {
+                return Err(Box::new(ValidationError {
+                    context: format!("vertex_buffers[{}].usage()", vertex_buffers_index).into(),
+                    problem: "does not contain `BufferUsage::VERTEX_BUFFER`".into(),
+                    vuids: &["VUID-vkCmdBindVertexBuffers2-pBuffers-03359"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        for (strides_index, &stride) in strides.iter().enumerate() {
+            if stride > properties.max_vertex_input_binding_stride {
+                return Err(Box::new(ValidationError {
+                    context: format!("strides[{}]", strides_index).into(),
+                    problem: "exceeds the `max_vertex_input_binding_stride` limit".into(),
+                    vuids: &["VUID-vkCmdBindVertexBuffers2-pStrides-03362"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn bind_vertex_buffers_with_strides_unchecked(
+        &mut self,
+        first_binding: u32,
+        vertex_buffers: &[Subbuffer<[u8]>],
+        strides: &[u32],
+    ) -> &mut Self {
+        if vertex_buffers.is_empty() {
+            return self;
+        }
+
+        let (buffers_vk, offsets_vk): (SmallVec<[_; 2]>, SmallVec<[_; 2]>) = vertex_buffers
+            .iter()
+            .map(|buffer| (buffer.buffer().handle(), buffer.offset()))
+            .unzip();
+        let strides_vk: SmallVec<[_; 2]> =
+            strides.iter().map(|&stride| stride as DeviceSize).collect();
+
+        let fns = self.device().fns();
+
+        if self.device().api_version() >= Version::V1_3 {
+            (fns.v1_3.cmd_bind_vertex_buffers2)(
+                self.handle(),
+                first_binding,
+                buffers_vk.len() as u32,
+                buffers_vk.as_ptr(),
+                offsets_vk.as_ptr(),
+                ptr::null(),
+                strides_vk.as_ptr(),
+            );
+        } else {
+            (fns.ext_extended_dynamic_state.cmd_bind_vertex_buffers2_ext)(
+                self.handle(),
+                first_binding,
+                buffers_vk.len() as u32,
+                buffers_vk.as_ptr(),
+                offsets_vk.as_ptr(),
+                ptr::null(),
+                strides_vk.as_ptr(),
+            );
+        }
+
+        self
+    }
+
     pub unsafe fn push_constants<Pc>(
         &mut self,
         pipeline_layout: &PipelineLayout,
@@ -1224,6 +1970,24 @@ where
                     }));
                 }
             }
+            PipelineBindPoint::RayTracing => {
+                if !queue_family_properties
+                    .queue_flags
+                    .intersects(QueueFlags::COMPUTE)
+                {
+                    return Err(Box::new(ValidationError {
+                        context: "self".into(),
+                        problem: "`pipeline_bind_point` is `PipelineBindPoint::RayTracing`, and \
+                            the queue family does not support compute operations"
+                            .into(),
+                        vuids: &[
+                            "VUID-vkCmdPushDescriptorSetKHR-pipelineBindPoint-00363",
+                            "VUID-vkCmdPushDescriptorSetKHR-commandBuffer-cmdpool",
+                        ],
+                        ..Default::default()
+                    }));
+                }
+            }
         }
 
         // VUID-vkCmdPushDescriptorSetKHR-commonparent
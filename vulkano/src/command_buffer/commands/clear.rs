@@ -7,6 +7,8 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+#[cfg(feature = "trace")]
+use crate::command_buffer::trace::TracedCommand;
 use crate::{
     buffer::{BufferContents, BufferUsage, Subbuffer},
     command_buffer::{
@@ -68,6 +70,14 @@ where
             _ne: _,
         } = &clear_info;
 
+        #[cfg(feature = "trace")]
+        self.trace_command(|trace| TracedCommand::ClearColorImage {
+            image: trace.image(&clear_info.image),
+            image_layout: clear_info.image_layout,
+            clear_value: clear_info.clear_value,
+            regions: clear_info.regions.to_vec(),
+        });
+
         self.add_command(
             "clear_color_image",
             regions
@@ -134,6 +144,14 @@ where
             _ne: _,
         } = &clear_info;
 
+        #[cfg(feature = "trace")]
+        self.trace_command(|trace| TracedCommand::ClearDepthStencilImage {
+            image: trace.image(&clear_info.image),
+            image_layout: clear_info.image_layout,
+            clear_value: clear_info.clear_value,
+            regions: clear_info.regions.to_vec(),
+        });
+
         self.add_command(
             "clear_depth_stencil_image",
             regions
@@ -198,6 +216,12 @@ where
         dst_buffer: Subbuffer<[u32]>,
         data: u32,
     ) -> &mut Self {
+        #[cfg(feature = "trace")]
+        self.trace_command(|trace| TracedCommand::FillBuffer {
+            dst_buffer: trace.buffer(&dst_buffer),
+            data,
+        });
+
         self.add_command(
             "fill_buffer",
             [(
@@ -264,6 +288,19 @@ where
         D: BufferContents + ?Sized,
         Dd: SafeDeref<Target = D> + Send + Sync + 'static,
     {
+        #[cfg(feature = "trace")]
+        self.trace_command(|trace| TracedCommand::UpdateBuffer {
+            dst_buffer: trace.buffer(&dst_buffer),
+            // SAFETY: `BufferContents` types can be viewed as bytes.
+            data: unsafe {
+                std::slice::from_raw_parts(
+                    data.deref() as *const D as *const u8,
+                    size_of_val(data.deref()),
+                )
+            }
+            .to_vec(),
+        });
+
         self.add_command(
             "update_buffer",
             [(
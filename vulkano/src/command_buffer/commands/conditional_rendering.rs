@@ -0,0 +1,303 @@
+// Copyright (c) 2024 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    buffer::{BufferUsage, Subbuffer},
+    command_buffer::{
+        allocator::CommandBufferAllocator,
+        auto::{ConditionalRenderingState, Resource},
+        sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder, ResourceInCommand,
+    },
+    device::{DeviceOwned, QueueFlags},
+    sync::PipelineStageAccessFlags,
+    Requires, RequiresAllOf, RequiresOneOf, ValidationError, VulkanObject,
+};
+
+/// # Commands for conditional rendering.
+///
+/// These commands require the [`conditional_rendering`] feature to be enabled on the device.
+///
+/// [`conditional_rendering`]: crate::device::Features::conditional_rendering
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Begins a conditional rendering block.
+    ///
+    /// Until [`end_conditional_rendering`](Self::end_conditional_rendering) is called, draw,
+    /// dispatch and `clear_attachments` commands are discarded by the device if the value in
+    /// `buffer` is zero at the time the commands are executed. If `inverted` is `true`, the
+    /// commands are instead discarded if the value is not zero.
+    ///
+    /// The value can be written by the device, for example by copying the result of an
+    /// occlusion query into `buffer` with
+    /// [`copy_query_pool_results`](Self::copy_query_pool_results), so that no readback to the
+    /// host is needed.
+    pub fn begin_conditional_rendering(
+        &mut self,
+        buffer: Subbuffer<u32>,
+        inverted: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_begin_conditional_rendering(&buffer, inverted)?;
+
+        unsafe { Ok(self.begin_conditional_rendering_unchecked(buffer, inverted)) }
+    }
+
+    fn validate_begin_conditional_rendering(
+        &self,
+        buffer: &Subbuffer<u32>,
+        inverted: bool,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_begin_conditional_rendering(buffer, inverted)?;
+
+        if self.builder_state.conditional_rendering.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "conditional rendering is already active".into(),
+                vuids: &["VUID-vkCmdBeginConditionalRenderingEXT-None-01980"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn begin_conditional_rendering_unchecked(
+        &mut self,
+        buffer: Subbuffer<u32>,
+        inverted: bool,
+    ) -> &mut Self {
+        self.builder_state.conditional_rendering = Some(ConditionalRenderingState {
+            in_subpass: self.builder_state.render_pass.is_some(),
+        });
+
+        self.add_command(
+            "begin_conditional_rendering",
+            [(
+                ResourceInCommand::ConditionalRenderingBuffer.into(),
+                Resource::Buffer {
+                    buffer: buffer.as_bytes().clone(),
+                    range: 0..buffer.size(),
+                    memory_access:
+                        PipelineStageAccessFlags::ConditionalRendering_ConditionalRenderingRead,
+                },
+            )]
+            .into_iter()
+            .collect(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.begin_conditional_rendering_unchecked(&buffer, inverted);
+            },
+        );
+
+        self
+    }
+
+    /// Ends the active conditional rendering block.
+    pub fn end_conditional_rendering(&mut self) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_end_conditional_rendering()?;
+
+        unsafe { Ok(self.end_conditional_rendering_unchecked()) }
+    }
+
+    fn validate_end_conditional_rendering(&self) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_end_conditional_rendering()?;
+
+        let conditional_rendering_state = self
+            .builder_state
+            .conditional_rendering
+            .as_ref()
+            .ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "conditional rendering is not active".into(),
+                    vuids: &["VUID-vkCmdEndConditionalRenderingEXT-None-01985"],
+                    ..Default::default()
+                })
+            })?;
+
+        if !conditional_rendering_state.in_subpass && self.builder_state.render_pass.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "conditional rendering was begun outside a render pass instance, but \
+                    a render pass instance is active"
+                    .into(),
+                vuids: &["VUID-vkCmdEndConditionalRenderingEXT-None-01986"],
+                ..Default::default()
+            }));
+        }
+
+        if conditional_rendering_state.in_subpass && self.builder_state.render_pass.is_none() {
+            return Err(Box::new(ValidationError {
+                problem: "conditional rendering was begun inside a render pass instance, but \
+                    no render pass instance is active"
+                    .into(),
+                vuids: &["VUID-vkCmdEndConditionalRenderingEXT-None-01987"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn end_conditional_rendering_unchecked(&mut self) -> &mut Self {
+        self.builder_state.conditional_rendering = None;
+
+        self.add_command(
+            "end_conditional_rendering",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.end_conditional_rendering_unchecked();
+            },
+        );
+
+        self
+    }
+}
+
+impl<A> UnsafeCommandBufferBuilder<A>
+where
+    A: CommandBufferAllocator,
+{
+    pub unsafe fn begin_conditional_rendering(
+        &mut self,
+        buffer: &Subbuffer<u32>,
+        inverted: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_begin_conditional_rendering(buffer, inverted)?;
+
+        Ok(self.begin_conditional_rendering_unchecked(buffer, inverted))
+    }
+
+    fn validate_begin_conditional_rendering(
+        &self,
+        buffer: &Subbuffer<u32>,
+        _inverted: bool,
+    ) -> Result<(), Box<ValidationError>> {
+        let device = self.device();
+
+        if !device.enabled_features().conditional_rendering {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "conditional_rendering",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics or compute operations"
+                    .into(),
+                vuids: &["VUID-vkCmdBeginConditionalRenderingEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkCmdBeginConditionalRenderingEXT-commonparent
+        assert_eq!(device, buffer.device());
+
+        if !buffer
+            .buffer()
+            .usage()
+            .intersects(BufferUsage::CONDITIONAL_RENDERING)
+        {
+            return Err(Box::new(ValidationError {
+                context: "buffer.usage()".into(),
+                problem: "does not contain `BufferUsage::CONDITIONAL_RENDERING`".into(),
+                vuids: &["VUID-VkConditionalRenderingBeginInfoEXT-buffer-01982"],
+                ..Default::default()
+            }));
+        }
+
+        if !buffer.offset().is_multiple_of(4) {
+            return Err(Box::new(ValidationError {
+                context: "buffer.offset()".into(),
+                problem: "is not a multiple of 4".into(),
+                vuids: &["VUID-VkConditionalRenderingBeginInfoEXT-offset-01984"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn begin_conditional_rendering_unchecked(
+        &mut self,
+        buffer: &Subbuffer<u32>,
+        inverted: bool,
+    ) -> &mut Self {
+        let conditional_rendering_begin_info = ash::vk::ConditionalRenderingBeginInfoEXT {
+            buffer: buffer.buffer().handle(),
+            offset: buffer.offset(),
+            flags: if inverted {
+                ash::vk::ConditionalRenderingFlagsEXT::INVERTED
+            } else {
+                ash::vk::ConditionalRenderingFlagsEXT::empty()
+            },
+            ..Default::default()
+        };
+
+        let fns = self.device().fns();
+        (fns.ext_conditional_rendering
+            .cmd_begin_conditional_rendering_ext)(
+            self.handle(), &conditional_rendering_begin_info
+        );
+
+        self
+    }
+
+    pub unsafe fn end_conditional_rendering(&mut self) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_end_conditional_rendering()?;
+
+        Ok(self.end_conditional_rendering_unchecked())
+    }
+
+    fn validate_end_conditional_rendering(&self) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_features().conditional_rendering {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "conditional_rendering",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics or compute operations"
+                    .into(),
+                vuids: &["VUID-vkCmdEndConditionalRenderingEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn end_conditional_rendering_unchecked(&mut self) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_conditional_rendering
+            .cmd_end_conditional_rendering_ext)(self.handle());
+
+        self
+    }
+}
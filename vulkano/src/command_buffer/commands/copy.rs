@@ -7,6 +7,8 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+#[cfg(feature = "trace")]
+use crate::command_buffer::trace::TracedCommand;
 use crate::{
     buffer::{BufferUsage, Subbuffer},
     command_buffer::{
@@ -26,6 +28,7 @@ use smallvec::{smallvec, SmallVec};
 use std::{
     cmp::{max, min},
     mem::size_of,
+    ptr,
     sync::Arc,
 };
 
@@ -80,6 +83,13 @@ where
             _ne: _,
         } = &copy_buffer_info;
 
+        #[cfg(feature = "trace")]
+        self.trace_command(|trace| TracedCommand::CopyBuffer {
+            src_buffer: trace.buffer(&copy_buffer_info.src_buffer),
+            dst_buffer: trace.buffer(&copy_buffer_info.dst_buffer),
+            regions: copy_buffer_info.regions.to_vec(),
+        });
+
         self.add_command(
             "copy_buffer",
             regions
@@ -177,6 +187,15 @@ where
             _ne: _,
         } = &copy_image_info;
 
+        #[cfg(feature = "trace")]
+        self.trace_command(|trace| TracedCommand::CopyImage {
+            src_image: trace.image(&copy_image_info.src_image),
+            src_image_layout: copy_image_info.src_image_layout,
+            dst_image: trace.image(&copy_image_info.dst_image),
+            dst_image_layout: copy_image_info.dst_image_layout,
+            regions: copy_image_info.regions.to_vec(),
+        });
+
         self.add_command(
             "copy_image",
             regions
@@ -264,6 +283,14 @@ where
             _ne: _,
         } = &copy_buffer_to_image_info;
 
+        #[cfg(feature = "trace")]
+        self.trace_command(|trace| TracedCommand::CopyBufferToImage {
+            src_buffer: trace.buffer(&copy_buffer_to_image_info.src_buffer),
+            dst_image: trace.image(&copy_buffer_to_image_info.dst_image),
+            dst_image_layout: copy_buffer_to_image_info.dst_image_layout,
+            regions: copy_buffer_to_image_info.regions.to_vec(),
+        });
+
         self.add_command(
             "copy_buffer_to_image",
             regions
@@ -276,6 +303,7 @@ where
                         ref image_subresource,
                         image_offset: _,
                         image_extent: _,
+                        transform: _,
                         _ne: _,
                     } = region;
 
@@ -351,6 +379,14 @@ where
             _ne: _,
         } = &copy_image_to_buffer_info;
 
+        #[cfg(feature = "trace")]
+        self.trace_command(|trace| TracedCommand::CopyImageToBuffer {
+            src_image: trace.image(&copy_image_to_buffer_info.src_image),
+            src_image_layout: copy_image_to_buffer_info.src_image_layout,
+            dst_buffer: trace.buffer(&copy_image_to_buffer_info.dst_buffer),
+            regions: copy_image_to_buffer_info.regions.to_vec(),
+        });
+
         self.add_command(
             "copy_image_to_buffer",
             regions
@@ -363,6 +399,7 @@ where
                         ref image_subresource,
                         image_offset: _,
                         image_extent: _,
+                        transform: _,
                         _ne: _,
                     } = region;
 
@@ -465,6 +502,16 @@ where
             _ne: _,
         } = &blit_image_info;
 
+        #[cfg(feature = "trace")]
+        self.trace_command(|trace| TracedCommand::BlitImage {
+            src_image: trace.image(&blit_image_info.src_image),
+            src_image_layout: blit_image_info.src_image_layout,
+            dst_image: trace.image(&blit_image_info.dst_image),
+            dst_image_layout: blit_image_info.dst_image_layout,
+            regions: blit_image_info.regions.to_vec(),
+            filter: blit_image_info.filter,
+        });
+
         self.add_command(
             "blit_image",
             regions
@@ -475,6 +522,7 @@ where
                         src_offsets: _,
                         ref dst_subresource,
                         dst_offsets: _,
+                        transform: _,
                         _ne: _,
                     } = region;
 
@@ -556,6 +604,15 @@ where
             _ne: _,
         } = &resolve_image_info;
 
+        #[cfg(feature = "trace")]
+        self.trace_command(|trace| TracedCommand::ResolveImage {
+            src_image: trace.image(&resolve_image_info.src_image),
+            src_image_layout: resolve_image_info.src_image_layout,
+            dst_image: trace.image(&resolve_image_info.dst_image),
+            dst_image_layout: resolve_image_info.dst_image_layout,
+            regions: resolve_image_info.regions.to_vec(),
+        });
+
         self.add_command(
             "resolve_image",
             regions
@@ -1234,6 +1291,7 @@ where
                     ref image_subresource,
                     image_offset,
                     image_extent,
+                    transform: _,
                     _ne,
                 } = region;
 
@@ -1391,9 +1449,15 @@ where
         if self.device().api_version() >= Version::V1_3
             || self.device().enabled_extensions().khr_copy_commands2
         {
+            let transforms_vk: SmallVec<[_; 8]> = regions
+                .iter()
+                .map(BufferImageCopy::to_vk_transform)
+                .collect();
+
             let regions: SmallVec<[_; 8]> = regions
                 .iter()
-                .map(|region| {
+                .zip(&transforms_vk)
+                .map(|(region, transform_vk)| {
                     let &BufferImageCopy {
                         buffer_offset,
                         buffer_row_length,
@@ -1401,10 +1465,17 @@ where
                         ref image_subresource,
                         image_offset,
                         image_extent,
+                        transform,
                         _ne: _,
                     } = region;
+                    let is_identity_transform = transform == CopyCommandTransform::Identity;
 
                     ash::vk::BufferImageCopy2 {
+                        p_next: if !is_identity_transform {
+                            transform_vk as *const _ as *const _
+                        } else {
+                            ptr::null()
+                        },
                         buffer_offset: buffer_offset + src_buffer.offset(),
                         buffer_row_length,
                         buffer_image_height,
@@ -1452,6 +1523,7 @@ where
                         ref image_subresource,
                         image_offset,
                         image_extent,
+                        transform: _,
                         _ne: _,
                     } = region;
 
@@ -1576,6 +1648,7 @@ where
                     ref image_subresource,
                     image_offset,
                     image_extent,
+                    transform: _,
                     _ne,
                 } = region;
 
@@ -1712,9 +1785,15 @@ where
         if self.device().api_version() >= Version::V1_3
             || self.device().enabled_extensions().khr_copy_commands2
         {
+            let transforms_vk: SmallVec<[_; 8]> = regions
+                .iter()
+                .map(BufferImageCopy::to_vk_transform)
+                .collect();
+
             let regions: SmallVec<[_; 8]> = regions
                 .iter()
-                .map(|region| {
+                .zip(&transforms_vk)
+                .map(|(region, transform_vk)| {
                     let &BufferImageCopy {
                         buffer_offset,
                         buffer_row_length,
@@ -1722,10 +1801,17 @@ where
                         ref image_subresource,
                         image_offset,
                         image_extent,
+                        transform,
                         _ne: _,
                     } = region;
+                    let is_identity_transform = transform == CopyCommandTransform::Identity;
 
                     ash::vk::BufferImageCopy2 {
+                        p_next: if !is_identity_transform {
+                            transform_vk as *const _ as *const _
+                        } else {
+                            ptr::null()
+                        },
                         buffer_offset: buffer_offset + dst_buffer.offset(),
                         buffer_row_length,
                         buffer_image_height,
@@ -1773,6 +1859,7 @@ where
                         ref image_subresource,
                         image_offset,
                         image_extent,
+                        transform: _,
                         _ne: _,
                     } = region;
 
@@ -1863,18 +1950,29 @@ where
         if self.device().api_version() >= Version::V1_3
             || self.device().enabled_extensions().khr_copy_commands2
         {
+            let transforms_vk: SmallVec<[_; 8]> =
+                regions.iter().map(ImageBlit::to_vk_transform).collect();
+
             let regions: SmallVec<[_; 8]> = regions
                 .iter()
-                .map(|region| {
+                .zip(&transforms_vk)
+                .map(|(region, transform_vk)| {
                     let &ImageBlit {
                         ref src_subresource,
                         src_offsets,
                         ref dst_subresource,
                         dst_offsets,
+                        transform,
                         _ne: _,
                     } = region;
+                    let is_identity_transform = transform == CopyCommandTransform::Identity;
 
                     ash::vk::ImageBlit2 {
+                        p_next: if !is_identity_transform {
+                            transform_vk as *const _ as *const _
+                        } else {
+                            ptr::null()
+                        },
                         src_subresource: src_subresource.into(),
                         src_offsets: [
                             ash::vk::Offset3D {
@@ -1931,6 +2029,7 @@ where
                         src_offsets,
                         ref dst_subresource,
                         dst_offsets,
+                        transform: _,
                         _ne: _,
                     } = region;
 
@@ -2371,6 +2470,7 @@ impl<T> From<CopyBufferInfoTyped<T>> for CopyBufferInfo {
 
 /// A region of data to copy between buffers.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BufferCopy {
     /// The offset in bytes or elements from the start of `src_buffer` that copying will
     /// start from.
@@ -2389,6 +2489,7 @@ pub struct BufferCopy {
     /// The default value is `0`, which must be overridden.
     pub size: DeviceSize,
 
+    #[cfg_attr(feature = "serde", serde(skip, default = "crate::NonExhaustive::new"))]
     pub _ne: crate::NonExhaustive,
 }
 
@@ -3686,6 +3787,7 @@ impl CopyImageInfo {
 
 /// A region of data to copy between images.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImageCopy {
     /// The subresource of `src_image` to copy from.
     ///
@@ -3712,6 +3814,7 @@ pub struct ImageCopy {
     /// The default value is `[0; 3]`, which must be overridden.
     pub extent: [u32; 3],
 
+    #[cfg_attr(feature = "serde", serde(skip, default = "crate::NonExhaustive::new"))]
     pub _ne: crate::NonExhaustive,
 }
 
@@ -3960,8 +4063,10 @@ impl CopyBufferToImageInfo {
                 ref image_subresource,
                 image_offset,
                 image_extent,
+                transform,
                 _ne: _,
             } = region;
+            let is_identity_transform = transform == CopyCommandTransform::Identity;
 
             /*
                Check image
@@ -4153,6 +4258,50 @@ impl CopyBufferToImageInfo {
                 }
             }
 
+            if !is_identity_transform {
+                if dst_image.image_type() != ImageType::Dim2d {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`regions[{}].transform` is not `CopyCommandTransform::Identity`, but \
+                            `dst_image.image_type()` is not `ImageType::Dim2d`",
+                            region_index
+                        )
+                        .into(),
+                        vuids: &["VUID-VkCopyBufferToImageInfo2-pRegions-06203"],
+                        ..Default::default()
+                    }));
+                }
+
+                if !dst_image_format_planes.is_empty() {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`regions[{}].transform` is not `CopyCommandTransform::Identity`, but \
+                            `dst_image.format()` is a multi-planar format",
+                            region_index
+                        )
+                        .into(),
+                        vuids: &["VUID-VkCopyBufferToImageInfo2-pRegions-06204"],
+                        ..Default::default()
+                    }));
+                }
+
+                if dst_image_format.block_extent() != [1; 3] {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`regions[{}].transform` is not `CopyCommandTransform::Identity`, but \
+                            `dst_image.format()` does not have a block extent of `[1, 1, 1]`",
+                            region_index
+                        )
+                        .into(),
+                        vuids: &["VUID-VkCopyBufferToImageInfo2-pRegions-04555"],
+                        ..Default::default()
+                    }));
+                }
+
+                // VUID-VkCopyBufferToImageInfo2-pRegions-04554
+                // TODO: check that the rotated region is contained within the image.
+            }
+
             if image_subresource.array_layers.end > dst_image.array_layers() {
                 return Err(Box::new(ValidationError {
                     problem: format!(
@@ -4166,7 +4315,9 @@ impl CopyBufferToImageInfo {
                 }));
             }
 
-            if image_offset[0] + image_extent[0] > image_subresource_extent[0] {
+            if is_identity_transform
+                && image_offset[0] + image_extent[0] > image_subresource_extent[0]
+            {
                 return Err(Box::new(ValidationError {
                     problem: format!(
                         "`regions[{0}].image_offset[0] + regions[{0}].image_extent[0]` is greater \
@@ -4180,7 +4331,9 @@ impl CopyBufferToImageInfo {
                 }));
             }
 
-            if image_offset[1] + image_extent[1] > image_subresource_extent[1] {
+            if is_identity_transform
+                && image_offset[1] + image_extent[1] > image_subresource_extent[1]
+            {
                 return Err(Box::new(ValidationError {
                     problem: format!(
                         "`regions[{0}].image_offset[1] + regions[{0}].image_extent[1]` is greater \
@@ -4569,8 +4722,10 @@ impl CopyImageToBufferInfo {
                 ref image_subresource,
                 image_offset,
                 image_extent,
+                transform,
                 _ne: _,
             } = region;
+            let is_identity_transform = transform == CopyCommandTransform::Identity;
 
             /*
                Check image
@@ -4762,6 +4917,50 @@ impl CopyImageToBufferInfo {
                 }
             }
 
+            if !is_identity_transform {
+                if src_image.image_type() != ImageType::Dim2d {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`regions[{}].transform` is not `CopyCommandTransform::Identity`, but \
+                            `src_image.image_type()` is not `ImageType::Dim2d`",
+                            region_index
+                        )
+                        .into(),
+                        vuids: &["VUID-VkCopyImageToBufferInfo2-pRegions-06205"],
+                        ..Default::default()
+                    }));
+                }
+
+                if !src_image_format_planes.is_empty() {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`regions[{}].transform` is not `CopyCommandTransform::Identity`, but \
+                            `src_image.format()` is a multi-planar format",
+                            region_index
+                        )
+                        .into(),
+                        vuids: &["VUID-VkCopyImageToBufferInfo2-pRegions-06206"],
+                        ..Default::default()
+                    }));
+                }
+
+                if src_image_format.block_extent() != [1; 3] {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`regions[{}].transform` is not `CopyCommandTransform::Identity`, but \
+                            `src_image.format()` does not have a block extent of `[1, 1, 1]`",
+                            region_index
+                        )
+                        .into(),
+                        vuids: &["VUID-VkCopyImageToBufferInfo2-pRegions-04567"],
+                        ..Default::default()
+                    }));
+                }
+
+                // VUID-VkCopyImageToBufferInfo2-pRegions-04566
+                // TODO: check that the rotated region is contained within the image.
+            }
+
             if image_subresource.array_layers.end > src_image.array_layers() {
                 return Err(Box::new(ValidationError {
                     problem: format!(
@@ -4775,7 +4974,9 @@ impl CopyImageToBufferInfo {
                 }));
             }
 
-            if image_offset[0] + image_extent[0] > image_subresource_extent[0] {
+            if is_identity_transform
+                && image_offset[0] + image_extent[0] > image_subresource_extent[0]
+            {
                 return Err(Box::new(ValidationError {
                     problem: format!(
                         "`regions[{0}].image_offset[0] + regions[{0}].image_extent[0]` is greater \
@@ -4789,7 +4990,9 @@ impl CopyImageToBufferInfo {
                 }));
             }
 
-            if image_offset[1] + image_extent[1] > image_subresource_extent[1] {
+            if is_identity_transform
+                && image_offset[1] + image_extent[1] > image_subresource_extent[1]
+            {
                 return Err(Box::new(ValidationError {
                     problem: format!(
                         "`regions[{0}].image_offset[1] + regions[{0}].image_extent[1]` is greater \
@@ -5029,6 +5232,7 @@ impl CopyImageToBufferInfo {
 
 /// A region of data to copy between a buffer and an image.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BufferImageCopy {
     /// The offset in bytes from the start of the buffer that copying will start from.
     ///
@@ -5064,6 +5268,17 @@ pub struct BufferImageCopy {
     /// The default value is `[0; 3]`, which must be overridden.
     pub image_extent: [u32; 3],
 
+    /// The rotation to apply to the region when copying.
+    ///
+    /// If this is not [`CopyCommandTransform::Identity`], then the
+    /// [`qcom_rotated_copy_commands`](crate::device::DeviceExtensions::qcom_rotated_copy_commands)
+    /// extension must be enabled on the device, and the image must be a 2D image with a
+    /// non-compressed, single-plane format.
+    ///
+    /// The default value is [`CopyCommandTransform::Identity`].
+    pub transform: CopyCommandTransform,
+
+    #[cfg_attr(feature = "serde", serde(skip, default = "crate::NonExhaustive::new"))]
     pub _ne: crate::NonExhaustive,
 }
 
@@ -5081,6 +5296,7 @@ impl Default for BufferImageCopy {
             },
             image_offset: [0; 3],
             image_extent: [0; 3],
+            transform: CopyCommandTransform::Identity,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -5097,6 +5313,7 @@ impl BufferImageCopy {
             ref image_subresource,
             image_offset: _,
             mut image_extent,
+            transform: _,
             _ne: _,
         } = self;
 
@@ -5141,6 +5358,7 @@ impl BufferImageCopy {
             ref image_subresource,
             image_offset: _,
             image_extent,
+            transform,
             _ne: _,
         } = self;
 
@@ -5148,6 +5366,19 @@ impl BufferImageCopy {
             .validate(device)
             .map_err(|err| err.add_context("image_subresource"))?;
 
+        if transform != CopyCommandTransform::Identity
+            && !device.enabled_extensions().qcom_rotated_copy_commands
+        {
+            return Err(Box::new(ValidationError {
+                context: "transform".into(),
+                problem: "is not `CopyCommandTransform::Identity`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "qcom_rotated_copy_commands",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
         if !(buffer_row_length == 0 || buffer_row_length >= image_extent[0]) {
             return Err(Box::new(ValidationError {
                 problem: "`buffer_row_length` is not either zero, or greater than or equal to \
@@ -5206,6 +5437,13 @@ impl BufferImageCopy {
 
         Ok(())
     }
+
+    pub(crate) fn to_vk_transform(&self) -> ash::vk::CopyCommandTransformInfoQCOM {
+        ash::vk::CopyCommandTransformInfoQCOM {
+            transform: self.transform.into(),
+            ..Default::default()
+        }
+    }
 }
 
 /// Parameters to blit image data.
@@ -5530,8 +5768,53 @@ impl BlitImageInfo {
                 src_offsets,
                 ref dst_subresource,
                 dst_offsets,
+                transform,
                 _ne: _,
             } = region;
+            let is_identity_transform = transform == CopyCommandTransform::Identity;
+
+            if !is_identity_transform {
+                if src_image.image_type() != ImageType::Dim2d {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`regions[{}].transform` is not `CopyCommandTransform::Identity`, but \
+                            `src_image.image_type()` is not `ImageType::Dim2d`",
+                            region_index
+                        )
+                        .into(),
+                        vuids: &["VUID-VkBlitImageInfo2-pRegions-06207"],
+                        ..Default::default()
+                    }));
+                }
+
+                if !src_image_format.planes().is_empty() {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`regions[{}].transform` is not `CopyCommandTransform::Identity`, but \
+                            `src_image.format()` is a multi-planar format",
+                            region_index
+                        )
+                        .into(),
+                        vuids: &["VUID-VkBlitImageInfo2-pRegions-06208"],
+                        ..Default::default()
+                    }));
+                }
+
+                if src_image_format.compression().is_some()
+                    || dst_image_format.compression().is_some()
+                {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`regions[{}].transform` is not `CopyCommandTransform::Identity`, but \
+                            `src_image.format()` or `dst_image.format()` is a compressed format",
+                            region_index
+                        )
+                        .into(),
+                        vuids: &["VUID-VkBlitImageInfo2-pRegions-04561"],
+                        ..Default::default()
+                    }));
+                }
+            }
 
             /*
                Check src
@@ -6020,6 +6303,7 @@ impl BlitImageInfo {
 
 /// A region of data to blit between images.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImageBlit {
     /// The subresource of `src_image` to blit from.
     ///
@@ -6047,6 +6331,17 @@ pub struct ImageBlit {
     /// The default value is `[[0; 3]; 2]`, which must be overridden.
     pub dst_offsets: [[u32; 3]; 2],
 
+    /// The rotation to apply to the region when blitting.
+    ///
+    /// If this is not [`CopyCommandTransform::Identity`], then the
+    /// [`qcom_rotated_copy_commands`](crate::device::DeviceExtensions::qcom_rotated_copy_commands)
+    /// extension must be enabled on the device, `src_image` must be a 2D image with a
+    /// single-plane format, and neither image may have a compressed format.
+    ///
+    /// The default value is [`CopyCommandTransform::Identity`].
+    pub transform: CopyCommandTransform,
+
+    #[cfg_attr(feature = "serde", serde(skip, default = "crate::NonExhaustive::new"))]
     pub _ne: crate::NonExhaustive,
 }
 
@@ -6066,6 +6361,7 @@ impl Default for ImageBlit {
                 array_layers: 0..0,
             },
             dst_offsets: [[0; 3]; 2],
+            transform: CopyCommandTransform::Identity,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -6078,6 +6374,7 @@ impl ImageBlit {
             src_offsets: _,
             ref dst_subresource,
             dst_offsets: _,
+            transform,
             _ne: _,
         } = self;
 
@@ -6089,6 +6386,19 @@ impl ImageBlit {
             .validate(device)
             .map_err(|err| err.add_context("dst_subresource"))?;
 
+        if transform != CopyCommandTransform::Identity
+            && !device.enabled_extensions().qcom_rotated_copy_commands
+        {
+            return Err(Box::new(ValidationError {
+                context: "transform".into(),
+                problem: "is not `CopyCommandTransform::Identity`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "qcom_rotated_copy_commands",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
         if src_subresource.aspects != dst_subresource.aspects {
             return Err(Box::new(ValidationError {
                 problem: "`src_subresource.aspects` does not equal `dst_subresource.aspects`"
@@ -6110,6 +6420,54 @@ impl ImageBlit {
 
         Ok(())
     }
+
+    pub(crate) fn to_vk_transform(&self) -> ash::vk::CopyCommandTransformInfoQCOM {
+        ash::vk::CopyCommandTransformInfoQCOM {
+            transform: self.transform.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A rotation that is applied to a region by a copy or blit command.
+///
+/// Any value other than `Identity` requires the
+/// [`qcom_rotated_copy_commands`](crate::device::DeviceExtensions::qcom_rotated_copy_commands)
+/// extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum CopyCommandTransform {
+    /// The region is not rotated.
+    Identity,
+
+    /// The region is rotated 90 degrees clockwise.
+    Rotate90,
+
+    /// The region is rotated 180 degrees.
+    Rotate180,
+
+    /// The region is rotated 270 degrees clockwise.
+    Rotate270,
+}
+
+impl Default for CopyCommandTransform {
+    #[inline]
+    fn default() -> Self {
+        CopyCommandTransform::Identity
+    }
+}
+
+impl From<CopyCommandTransform> for ash::vk::SurfaceTransformFlagsKHR {
+    #[inline]
+    fn from(val: CopyCommandTransform) -> Self {
+        match val {
+            CopyCommandTransform::Identity => Self::IDENTITY,
+            CopyCommandTransform::Rotate90 => Self::ROTATE_90,
+            CopyCommandTransform::Rotate180 => Self::ROTATE_180,
+            CopyCommandTransform::Rotate270 => Self::ROTATE_270,
+        }
+    }
 }
 
 /// Parameters to resolve image data.
@@ -6702,6 +7060,7 @@ impl ResolveImageInfo {
 
 /// A region of data to resolve between images.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImageResolve {
     /// The subresource of `src_image` to resolve from.
     ///
@@ -6728,6 +7087,7 @@ pub struct ImageResolve {
     /// The default value is `[0; 3]`, which must be overridden.
     pub extent: [u32; 3],
 
+    #[cfg_attr(feature = "serde", serde(skip, default = "crate::NonExhaustive::new"))]
     pub _ne: crate::NonExhaustive,
 }
 
@@ -13,12 +13,23 @@ use crate::{
         AutoCommandBufferBuilder,
     },
     device::{DeviceOwned, QueueFlags},
+    image::SampleCount,
     pipeline::{
         graphics::{
-            color_blend::LogicOp,
+            color_blend::{AttachmentBlend, ColorComponents, LogicOp},
             depth_stencil::{CompareOp, StencilFaces, StencilOp, StencilOps},
+            discard_rectangle::DiscardRectangleMode,
+            fragment_shading_rate::{
+                validate_combiner_ops, validate_fragment_size, FragmentShadingRateCombinerOp,
+                FragmentShadingRateState,
+            },
             input_assembly::PrimitiveTopology,
-            rasterization::{CullMode, DepthBiasState, FrontFace, LineStipple},
+            multisample::SampleLocationsInfo,
+            rasterization::{
+                CullMode, DepthBiasState, FrontFace, LineStipple, PolygonMode, ProvokingVertexMode,
+            },
+            tessellation::TessellationDomainOrigin,
+            vertex_input::{VertexInputRate, VertexInputState},
             viewport::{Scissor, Viewport},
         },
         DynamicState,
@@ -58,6 +69,89 @@ where
         Ok(())
     }
 
+    // Helper function for dynamic state that can't yet be made dynamic in a graphics pipeline,
+    // and so can only be used with shader objects.
+    fn validate_graphics_pipeline_not_bound(&self) -> Result<(), Box<ValidationError>> {
+        if self.builder_state.pipeline_graphics.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "a graphics pipeline is currently bound, and the state for this value \
+                    is fixed in it"
+                    .into(),
+                vuids: &["VUID-vkCmdDraw-None-08608"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Sets whether alpha to coverage is enabled for future draw calls.
+    pub fn set_alpha_to_coverage_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_alpha_to_coverage_enable(enable)?;
+
+        unsafe { Ok(self.set_alpha_to_coverage_enable_unchecked(enable)) }
+    }
+
+    fn validate_set_alpha_to_coverage_enable(
+        &self,
+        enable: bool,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_alpha_to_coverage_enable(enable)?;
+
+        self.validate_graphics_pipeline_not_bound()?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_alpha_to_coverage_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        self.builder_state.alpha_to_coverage_enable = Some(enable);
+        self.add_command(
+            "set_alpha_to_coverage_enable",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_alpha_to_coverage_enable_unchecked(enable);
+            },
+        );
+
+        self
+    }
+
+    /// Sets whether alpha to one is enabled for future draw calls.
+    pub fn set_alpha_to_one_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_alpha_to_one_enable(enable)?;
+
+        unsafe { Ok(self.set_alpha_to_one_enable_unchecked(enable)) }
+    }
+
+    fn validate_set_alpha_to_one_enable(&self, enable: bool) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_alpha_to_one_enable(enable)?;
+
+        self.validate_graphics_pipeline_not_bound()?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_alpha_to_one_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        self.builder_state.alpha_to_one_enable = Some(enable);
+        self.add_command(
+            "set_alpha_to_one_enable",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_alpha_to_one_enable_unchecked(enable);
+            },
+        );
+
+        self
+    }
+
     /// Sets the dynamic blend constants for future draw calls.
     pub fn set_blend_constants(
         &mut self,
@@ -93,6 +187,100 @@ where
         self
     }
 
+    /// Sets whether blending is enabled for each color attachment, starting at
+    /// `first_attachment`, for future draw calls.
+    pub fn set_color_blend_enable(
+        &mut self,
+        first_attachment: u32,
+        enables: SmallVec<[bool; 4]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_color_blend_enable(first_attachment, &enables)?;
+
+        unsafe { Ok(self.set_color_blend_enable_unchecked(first_attachment, enables)) }
+    }
+
+    fn validate_set_color_blend_enable(
+        &self,
+        first_attachment: u32,
+        enables: &[bool],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_set_color_blend_enable(first_attachment, enables)?;
+
+        self.validate_graphics_pipeline_not_bound()?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_color_blend_enable_unchecked(
+        &mut self,
+        first_attachment: u32,
+        enables: SmallVec<[bool; 4]>,
+    ) -> &mut Self {
+        for (num, &value) in enables.iter().enumerate() {
+            let num = num as u32 + first_attachment;
+            self.builder_state.color_blend_enable.insert(num, value);
+        }
+
+        self.add_command(
+            "set_color_blend_enable",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_color_blend_enable_unchecked(first_attachment, &enables);
+            },
+        );
+
+        self
+    }
+
+    /// Sets the blend equation for each color attachment, starting at `first_attachment`, for
+    /// future draw calls.
+    pub fn set_color_blend_equation(
+        &mut self,
+        first_attachment: u32,
+        equations: SmallVec<[AttachmentBlend; 4]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_color_blend_equation(first_attachment, &equations)?;
+
+        unsafe { Ok(self.set_color_blend_equation_unchecked(first_attachment, equations)) }
+    }
+
+    fn validate_set_color_blend_equation(
+        &self,
+        first_attachment: u32,
+        equations: &[AttachmentBlend],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_set_color_blend_equation(first_attachment, equations)?;
+
+        self.validate_graphics_pipeline_not_bound()?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_color_blend_equation_unchecked(
+        &mut self,
+        first_attachment: u32,
+        equations: SmallVec<[AttachmentBlend; 4]>,
+    ) -> &mut Self {
+        for (num, &value) in equations.iter().enumerate() {
+            let num = num as u32 + first_attachment;
+            self.builder_state.color_blend_equation.insert(num, value);
+        }
+
+        self.add_command(
+            "set_color_blend_equation",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_color_blend_equation_unchecked(first_attachment, &equations);
+            },
+        );
+
+        self
+    }
+
     /// Sets whether dynamic color writes should be enabled for each attachment in the
     /// framebuffer.
     pub fn set_color_write_enable(
@@ -150,6 +338,53 @@ where
         self
     }
 
+    /// Sets the color write mask for each color attachment, starting at `first_attachment`,
+    /// for future draw calls.
+    pub fn set_color_write_mask(
+        &mut self,
+        first_attachment: u32,
+        masks: SmallVec<[ColorComponents; 4]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_color_write_mask(first_attachment, &masks)?;
+
+        unsafe { Ok(self.set_color_write_mask_unchecked(first_attachment, masks)) }
+    }
+
+    fn validate_set_color_write_mask(
+        &self,
+        first_attachment: u32,
+        masks: &[ColorComponents],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_set_color_write_mask(first_attachment, masks)?;
+
+        self.validate_graphics_pipeline_not_bound()?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_color_write_mask_unchecked(
+        &mut self,
+        first_attachment: u32,
+        masks: SmallVec<[ColorComponents; 4]>,
+    ) -> &mut Self {
+        for (num, &value) in masks.iter().enumerate() {
+            let num = num as u32 + first_attachment;
+            self.builder_state.color_write_mask.insert(num, value);
+        }
+
+        self.add_command(
+            "set_color_write_mask",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_color_write_mask_unchecked(first_attachment, &masks);
+            },
+        );
+
+        self
+    }
+
     /// Sets the dynamic cull mode for future draw calls.
     pub fn set_cull_mode(
         &mut self,
@@ -333,6 +568,70 @@ where
         self
     }
 
+    /// Sets whether depth clamping is enabled for future draw calls.
+    pub fn set_depth_clamp_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_depth_clamp_enable(enable)?;
+
+        unsafe { Ok(self.set_depth_clamp_enable_unchecked(enable)) }
+    }
+
+    fn validate_set_depth_clamp_enable(&self, enable: bool) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_depth_clamp_enable(enable)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::DepthClampEnable)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_depth_clamp_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        self.builder_state.depth_clamp_enable = Some(enable);
+        self.add_command(
+            "set_depth_clamp_enable",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_depth_clamp_enable_unchecked(enable);
+            },
+        );
+
+        self
+    }
+
+    /// Sets whether depth clipping is enabled for future draw calls.
+    pub fn set_depth_clip_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_depth_clip_enable(enable)?;
+
+        unsafe { Ok(self.set_depth_clip_enable_unchecked(enable)) }
+    }
+
+    fn validate_set_depth_clip_enable(&self, enable: bool) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_depth_clip_enable(enable)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::DepthClipEnable)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_depth_clip_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        self.builder_state.depth_clip_enable = Some(enable);
+        self.add_command(
+            "set_depth_clip_enable",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_depth_clip_enable_unchecked(enable);
+            },
+        );
+
+        self
+    }
+
     /// Sets the dynamic depth compare op for future draw calls.
     pub fn set_depth_compare_op(
         &mut self,
@@ -478,6 +777,125 @@ where
         self
     }
 
+    /// Sets whether the discard rectangle test is enabled for future draw calls.
+    pub fn set_discard_rectangle_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_discard_rectangle_enable(enable)?;
+
+        unsafe { Ok(self.set_discard_rectangle_enable_unchecked(enable)) }
+    }
+
+    fn validate_set_discard_rectangle_enable(
+        &self,
+        enable: bool,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_discard_rectangle_enable(enable)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::DiscardRectangleEnable)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_discard_rectangle_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        self.builder_state.discard_rectangle_enable = Some(enable);
+        self.add_command(
+            "set_discard_rectangle_enable",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_discard_rectangle_enable_unchecked(enable);
+            },
+        );
+
+        self
+    }
+
+    /// Sets the dynamic discard rectangle mode for future draw calls.
+    pub fn set_discard_rectangle_mode(
+        &mut self,
+        mode: DiscardRectangleMode,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_discard_rectangle_mode(mode)?;
+
+        unsafe { Ok(self.set_discard_rectangle_mode_unchecked(mode)) }
+    }
+
+    fn validate_set_discard_rectangle_mode(
+        &self,
+        mode: DiscardRectangleMode,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_discard_rectangle_mode(mode)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::DiscardRectangleMode)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_discard_rectangle_mode_unchecked(
+        &mut self,
+        mode: DiscardRectangleMode,
+    ) -> &mut Self {
+        self.builder_state.discard_rectangle_mode = Some(mode);
+        self.add_command(
+            "set_discard_rectangle_mode",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_discard_rectangle_mode_unchecked(mode);
+            },
+        );
+
+        self
+    }
+
+    /// Sets the dynamic fragment shading rate for future draw calls.
+    pub fn set_fragment_shading_rate(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_fragment_shading_rate(fragment_size, combiner_ops)?;
+
+        unsafe { Ok(self.set_fragment_shading_rate_unchecked(fragment_size, combiner_ops)) }
+    }
+
+    fn validate_set_fragment_shading_rate(
+        &self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_set_fragment_shading_rate(fragment_size, combiner_ops)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::FragmentShadingRate)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_fragment_shading_rate_unchecked(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) -> &mut Self {
+        self.builder_state.fragment_shading_rate = Some(FragmentShadingRateState {
+            fragment_size,
+            combiner_ops,
+            ..Default::default()
+        });
+        self.add_command(
+            "set_fragment_shading_rate",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_fragment_shading_rate_unchecked(fragment_size, combiner_ops);
+            },
+        );
+
+        self
+    }
+
     /// Sets the dynamic front face for future draw calls.
     pub fn set_front_face(&mut self, face: FrontFace) -> Result<&mut Self, Box<ValidationError>> {
         self.validate_set_front_face(face)?;
@@ -602,6 +1020,35 @@ where
         self
     }
 
+    /// Sets whether the logic op is enabled for future draw calls.
+    pub fn set_logic_op_enable(&mut self, enable: bool) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_logic_op_enable(enable)?;
+
+        unsafe { Ok(self.set_logic_op_enable_unchecked(enable)) }
+    }
+
+    fn validate_set_logic_op_enable(&self, enable: bool) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_logic_op_enable(enable)?;
+
+        self.validate_graphics_pipeline_not_bound()?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_logic_op_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        self.builder_state.logic_op_enable = Some(enable);
+        self.add_command(
+            "set_logic_op_enable",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_logic_op_enable_unchecked(enable);
+            },
+        );
+
+        self
+    }
+
     /// Sets the dynamic number of patch control points for future draw calls.
     pub fn set_patch_control_points(
         &mut self,
@@ -634,6 +1081,41 @@ where
         self
     }
 
+    /// Sets the dynamic polygon mode for future draw calls.
+    pub fn set_polygon_mode(
+        &mut self,
+        polygon_mode: PolygonMode,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_polygon_mode(polygon_mode)?;
+
+        unsafe { Ok(self.set_polygon_mode_unchecked(polygon_mode)) }
+    }
+
+    fn validate_set_polygon_mode(
+        &self,
+        polygon_mode: PolygonMode,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_polygon_mode(polygon_mode)?;
+
+        self.validate_graphics_pipeline_not_bound()?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_polygon_mode_unchecked(&mut self, polygon_mode: PolygonMode) -> &mut Self {
+        self.builder_state.polygon_mode = Some(polygon_mode);
+        self.add_command(
+            "set_polygon_mode",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_polygon_mode_unchecked(polygon_mode);
+            },
+        );
+
+        self
+    }
+
     /// Sets whether dynamic primitive restart is enabled for future draw calls.
     pub fn set_primitive_restart_enable(
         &mut self,
@@ -707,6 +1189,83 @@ where
         self
     }
 
+    /// Sets the dynamic provoking vertex mode for future draw calls.
+    pub fn set_provoking_vertex_mode(
+        &mut self,
+        mode: ProvokingVertexMode,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_provoking_vertex_mode(mode)?;
+
+        unsafe { Ok(self.set_provoking_vertex_mode_unchecked(mode)) }
+    }
+
+    fn validate_set_provoking_vertex_mode(
+        &self,
+        mode: ProvokingVertexMode,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_provoking_vertex_mode(mode)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::ProvokingVertexMode)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_provoking_vertex_mode_unchecked(
+        &mut self,
+        mode: ProvokingVertexMode,
+    ) -> &mut Self {
+        self.builder_state.provoking_vertex_mode = Some(mode);
+        self.add_command(
+            "set_provoking_vertex_mode",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_provoking_vertex_mode_unchecked(mode);
+            },
+        );
+
+        self
+    }
+
+    /// Sets the dynamic number of rasterization samples for future draw calls.
+    pub fn set_rasterization_samples(
+        &mut self,
+        rasterization_samples: SampleCount,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_rasterization_samples(rasterization_samples)?;
+
+        unsafe { Ok(self.set_rasterization_samples_unchecked(rasterization_samples)) }
+    }
+
+    fn validate_set_rasterization_samples(
+        &self,
+        rasterization_samples: SampleCount,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_set_rasterization_samples(rasterization_samples)?;
+
+        self.validate_graphics_pipeline_not_bound()?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_rasterization_samples_unchecked(
+        &mut self,
+        rasterization_samples: SampleCount,
+    ) -> &mut Self {
+        self.builder_state.rasterization_samples = Some(rasterization_samples);
+        self.add_command(
+            "set_rasterization_samples",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_rasterization_samples_unchecked(rasterization_samples);
+            },
+        );
+
+        self
+    }
+
     /// Sets whether dynamic rasterizer discard is enabled for future draw calls.
     pub fn set_rasterizer_discard_enable(
         &mut self,
@@ -742,6 +1301,123 @@ where
         self
     }
 
+    /// Sets the dynamic custom sample locations for future draw calls.
+    pub fn set_sample_locations(
+        &mut self,
+        sample_locations: SampleLocationsInfo,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_sample_locations(&sample_locations)?;
+
+        unsafe { Ok(self.set_sample_locations_unchecked(sample_locations)) }
+    }
+
+    fn validate_set_sample_locations(
+        &self,
+        sample_locations: &SampleLocationsInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_sample_locations(sample_locations)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::SampleLocations)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_sample_locations_unchecked(
+        &mut self,
+        sample_locations: SampleLocationsInfo,
+    ) -> &mut Self {
+        self.builder_state.sample_locations = Some(sample_locations.clone());
+        self.add_command(
+            "set_sample_locations",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_sample_locations_unchecked(&sample_locations);
+            },
+        );
+
+        self
+    }
+
+    /// Sets whether custom sample locations are enabled for future draw calls.
+    pub fn set_sample_locations_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_sample_locations_enable(enable)?;
+
+        unsafe { Ok(self.set_sample_locations_enable_unchecked(enable)) }
+    }
+
+    fn validate_set_sample_locations_enable(
+        &self,
+        enable: bool,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_sample_locations_enable(enable)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::SampleLocationsEnable)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_sample_locations_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        self.builder_state.sample_locations_enable = Some(enable);
+        self.add_command(
+            "set_sample_locations_enable",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_sample_locations_enable_unchecked(enable);
+            },
+        );
+
+        self
+    }
+
+    /// Sets the dynamic sample mask for future draw calls.
+    ///
+    /// `samples` is the number of samples that `sample_mask` applies to; bits beyond that number
+    /// are ignored.
+    pub fn set_sample_mask(
+        &mut self,
+        samples: SampleCount,
+        sample_mask: [u32; 2],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_sample_mask(samples, sample_mask)?;
+
+        unsafe { Ok(self.set_sample_mask_unchecked(samples, sample_mask)) }
+    }
+
+    fn validate_set_sample_mask(
+        &self,
+        samples: SampleCount,
+        sample_mask: [u32; 2],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_sample_mask(samples, sample_mask)?;
+
+        self.validate_graphics_pipeline_not_bound()?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_sample_mask_unchecked(
+        &mut self,
+        samples: SampleCount,
+        sample_mask: [u32; 2],
+    ) -> &mut Self {
+        self.builder_state.sample_mask = Some((samples, sample_mask));
+        self.add_command(
+            "set_sample_mask",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_sample_mask_unchecked(samples, sample_mask);
+            },
+        );
+
+        self
+    }
+
     /// Sets the dynamic scissors for future draw calls.
     pub fn set_scissor(
         &mut self,
@@ -1084,6 +1760,83 @@ where
         self
     }
 
+    /// Sets the dynamic tessellation domain origin for future draw calls.
+    pub fn set_tessellation_domain_origin(
+        &mut self,
+        domain_origin: TessellationDomainOrigin,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_tessellation_domain_origin(domain_origin)?;
+
+        unsafe { Ok(self.set_tessellation_domain_origin_unchecked(domain_origin)) }
+    }
+
+    fn validate_set_tessellation_domain_origin(
+        &self,
+        domain_origin: TessellationDomainOrigin,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_set_tessellation_domain_origin(domain_origin)?;
+
+        self.validate_graphics_pipeline_not_bound()?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_tessellation_domain_origin_unchecked(
+        &mut self,
+        domain_origin: TessellationDomainOrigin,
+    ) -> &mut Self {
+        self.builder_state.tessellation_domain_origin = Some(domain_origin);
+        self.add_command(
+            "set_tessellation_domain_origin",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_tessellation_domain_origin_unchecked(domain_origin);
+            },
+        );
+
+        self
+    }
+
+    /// Sets the dynamic vertex input state for future draw calls.
+    pub fn set_vertex_input(
+        &mut self,
+        vertex_input_state: VertexInputState,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_vertex_input(&vertex_input_state)?;
+
+        unsafe { Ok(self.set_vertex_input_unchecked(vertex_input_state)) }
+    }
+
+    fn validate_set_vertex_input(
+        &self,
+        vertex_input_state: &VertexInputState,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_vertex_input(vertex_input_state)?;
+
+        self.validate_graphics_pipeline_not_bound()?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_vertex_input_unchecked(
+        &mut self,
+        vertex_input_state: VertexInputState,
+    ) -> &mut Self {
+        self.builder_state.vertex_input = Some(vertex_input_state.clone());
+        self.add_command(
+            "set_vertex_input",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_vertex_input_unchecked(&vertex_input_state);
+            },
+        );
+
+        self
+    }
+
     /// Sets the dynamic viewports for future draw calls.
     pub fn set_viewport(
         &mut self,
@@ -1173,19 +1926,35 @@ impl<A> UnsafeCommandBufferBuilder<A>
 where
     A: CommandBufferAllocator,
 {
-    pub unsafe fn set_blend_constants(
+    pub unsafe fn set_alpha_to_coverage_enable(
         &mut self,
-        constants: [f32; 4],
+        enable: bool,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_set_blend_constants(constants)?;
+        self.validate_set_alpha_to_coverage_enable(enable)?;
 
-        Ok(self.set_blend_constants_unchecked(constants))
+        Ok(self.set_alpha_to_coverage_enable_unchecked(enable))
     }
 
-    fn validate_set_blend_constants(
+    fn validate_set_alpha_to_coverage_enable(
         &self,
-        _constants: [f32; 4],
+        _enable: bool,
     ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_alpha_to_coverage_enable
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state3_alpha_to_coverage_enable")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetAlphaToCoverageEnableEXT-extendedDynamicState3AlphaToCoverageEnable-07343"],
+                ..Default::default()
+            }));
+        }
+
         if !self
             .queue_family_properties()
             .queue_flags
@@ -1195,7 +1964,7 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdSetBlendConstants-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdSetAlphaToCoverageEnableEXT-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
@@ -1204,32 +1973,52 @@ where
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_blend_constants_unchecked(&mut self, constants: [f32; 4]) -> &mut Self {
+    pub unsafe fn set_alpha_to_coverage_enable_unchecked(&mut self, enable: bool) -> &mut Self {
         let fns = self.device().fns();
-        (fns.v1_0.cmd_set_blend_constants)(self.handle(), &constants);
+
+        if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state3
+        {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_alpha_to_coverage_enable_ext)(self.handle(), enable.into());
+        } else {
+            (fns.ext_shader_object.cmd_set_alpha_to_coverage_enable_ext)(
+                self.handle(),
+                enable.into(),
+            );
+        }
 
         self
     }
 
-    pub unsafe fn set_color_write_enable(
+    pub unsafe fn set_alpha_to_one_enable(
         &mut self,
-        enables: &[bool],
+        enable: bool,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_set_color_write_enable(enables)?;
+        self.validate_set_alpha_to_one_enable(enable)?;
 
-        Ok(self.set_color_write_enable_unchecked(enables))
+        Ok(self.set_alpha_to_one_enable_unchecked(enable))
     }
 
-    fn validate_set_color_write_enable(
-        &self,
-        _enables: &[bool],
-    ) -> Result<(), Box<ValidationError>> {
-        if !self.device().enabled_features().color_write_enable {
+    fn validate_set_alpha_to_one_enable(&self, enable: bool) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_alpha_to_one_enable
+            || self.device().enabled_features().shader_object)
+        {
             return Err(Box::new(ValidationError {
-                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
-                    "ext_color_write_enable",
-                )])]),
-                vuids: &["VUID-vkCmdSetColorWriteEnableEXT-None-04803"],
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::Feature(
+                        "extended_dynamic_state3_alpha_to_one_enable",
+                    )]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
+                ]),
+                vuids: &[
+                    "VUID-vkCmdSetAlphaToOneEnableEXT-extendedDynamicState3AlphaToOneEnable-07345",
+                ],
                 ..Default::default()
             }));
         }
@@ -1243,59 +2032,56 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdSetColorWriteEnableEXT-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdSetAlphaToOneEnableEXT-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
 
+        if enable && !self.device().enabled_features().alpha_to_one {
+            return Err(Box::new(ValidationError {
+                context: "enable".into(),
+                problem: "is `true`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "alpha_to_one",
+                )])]),
+                vuids: &["VUID-vkCmdSetAlphaToOneEnableEXT-alphaToOne-07607"],
+            }));
+        }
+
         Ok(())
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_color_write_enable_unchecked(&mut self, enables: &[bool]) -> &mut Self {
-        let enables = enables
-            .iter()
-            .copied()
-            .map(|v| v as ash::vk::Bool32)
-            .collect::<SmallVec<[_; 4]>>();
+    pub unsafe fn set_alpha_to_one_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
 
-        if enables.is_empty() {
-            return self;
+        if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state3
+        {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_alpha_to_one_enable_ext)(self.handle(), enable.into());
+        } else {
+            (fns.ext_shader_object.cmd_set_alpha_to_one_enable_ext)(self.handle(), enable.into());
         }
 
-        let fns = self.device().fns();
-        (fns.ext_color_write_enable.cmd_set_color_write_enable_ext)(
-            self.handle(),
-            enables.len() as u32,
-            enables.as_ptr(),
-        );
-
         self
     }
 
-    pub unsafe fn set_cull_mode(
+    pub unsafe fn set_blend_constants(
         &mut self,
-        cull_mode: CullMode,
+        constants: [f32; 4],
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_set_cull_mode(cull_mode)?;
+        self.validate_set_blend_constants(constants)?;
 
-        Ok(self.set_cull_mode_unchecked(cull_mode))
+        Ok(self.set_blend_constants_unchecked(constants))
     }
 
-    fn validate_set_cull_mode(&self, cull_mode: CullMode) -> Result<(), Box<ValidationError>> {
-        if !(self.device().api_version() >= Version::V1_3
-            || self.device().enabled_features().extended_dynamic_state)
-        {
-            return Err(Box::new(ValidationError {
-                requires_one_of: RequiresOneOf(&[
-                    RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
-                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state")]),
-                ]),
-                vuids: &["VUID-vkCmdSetCullMode-None-03384"],
-                ..Default::default()
-            }));
-        }
-
+    fn validate_set_blend_constants(
+        &self,
+        _constants: [f32; 4],
+    ) -> Result<(), Box<ValidationError>> {
         if !self
             .queue_family_properties()
             .queue_flags
@@ -1305,49 +2091,57 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdSetCullMode-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdSetBlendConstants-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
 
-        cull_mode.validate_device(self.device()).map_err(|err| {
-            err.add_context("cull_mode")
-                .set_vuids(&["VUID-vkCmdSetCullMode-cullMode-parameter"])
-        })?;
-
         Ok(())
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_cull_mode_unchecked(&mut self, cull_mode: CullMode) -> &mut Self {
+    pub unsafe fn set_blend_constants_unchecked(&mut self, constants: [f32; 4]) -> &mut Self {
         let fns = self.device().fns();
-
-        if self.device().api_version() >= Version::V1_3 {
-            (fns.v1_3.cmd_set_cull_mode)(self.handle(), cull_mode.into());
-        } else {
-            (fns.ext_extended_dynamic_state.cmd_set_cull_mode_ext)(self.handle(), cull_mode.into());
-        }
+        (fns.v1_0.cmd_set_blend_constants)(self.handle(), &constants);
 
         self
     }
 
-    pub unsafe fn set_depth_bias(
+    pub unsafe fn set_color_blend_enable(
         &mut self,
-        constant_factor: f32,
-        clamp: f32,
-        slope_factor: f32,
+        first_attachment: u32,
+        enables: &[bool],
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_set_depth_bias(constant_factor, clamp, slope_factor)?;
+        self.validate_set_color_blend_enable(first_attachment, enables)?;
 
-        Ok(self.set_depth_bias_unchecked(constant_factor, clamp, slope_factor))
+        Ok(self.set_color_blend_enable_unchecked(first_attachment, enables))
     }
 
-    fn validate_set_depth_bias(
+    fn validate_set_color_blend_enable(
         &self,
-        _constant_factor: f32,
-        clamp: f32,
-        _slope_factor: f32,
+        _first_attachment: u32,
+        enables: &[bool],
     ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_color_blend_enable
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::Feature(
+                        "extended_dynamic_state3_color_blend_enable",
+                    )]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
+                ]),
+                vuids: &[
+                    "VUID-vkCmdSetColorBlendEnableEXT-extendedDynamicState3ColorBlendEnable-07355",
+                ],
+                ..Default::default()
+            }));
+        }
+
         if !self
             .queue_family_properties()
             .queue_flags
@@ -1357,19 +2151,17 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdSetDepthBias-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdSetColorBlendEnableEXT-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
 
-        if clamp != 0.0 && !self.device().enabled_features().depth_bias_clamp {
+        if enables.is_empty() {
             return Err(Box::new(ValidationError {
-                context: "clamp".into(),
-                problem: "is not `0.0`".into(),
-                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
-                    "depth_bias_clamp",
-                )])]),
-                vuids: &["VUID-vkCmdSetDepthBias-depthBiasClamp-00790"],
+                context: "enables".into(),
+                problem: "is empty".into(),
+                vuids: &["VUID-vkCmdSetColorBlendEnableEXT-attachmentCount-arraylength"],
+                ..Default::default()
             }));
         }
 
@@ -1377,37 +2169,70 @@ where
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_depth_bias_unchecked(
+    pub unsafe fn set_color_blend_enable_unchecked(
         &mut self,
-        constant_factor: f32,
-        clamp: f32,
-        slope_factor: f32,
+        first_attachment: u32,
+        enables: &[bool],
     ) -> &mut Self {
+        let enables = enables
+            .iter()
+            .copied()
+            .map(|v| v as ash::vk::Bool32)
+            .collect::<SmallVec<[_; 4]>>();
+
         let fns = self.device().fns();
-        (fns.v1_0.cmd_set_depth_bias)(self.handle(), constant_factor, clamp, slope_factor);
+
+        if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state3
+        {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_color_blend_enable_ext)(
+                self.handle(),
+                first_attachment,
+                enables.len() as u32,
+                enables.as_ptr(),
+            );
+        } else {
+            (fns.ext_shader_object.cmd_set_color_blend_enable_ext)(
+                self.handle(),
+                first_attachment,
+                enables.len() as u32,
+                enables.as_ptr(),
+            );
+        }
 
         self
     }
 
-    pub unsafe fn set_depth_bias_enable(
+    pub unsafe fn set_color_blend_equation(
         &mut self,
-        enable: bool,
+        first_attachment: u32,
+        equations: &[AttachmentBlend],
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_set_depth_bias_enable(enable)?;
+        self.validate_set_color_blend_equation(first_attachment, equations)?;
 
-        Ok(self.set_depth_bias_enable_unchecked(enable))
+        Ok(self.set_color_blend_equation_unchecked(first_attachment, equations))
     }
 
-    fn validate_set_depth_bias_enable(&self, _enable: bool) -> Result<(), Box<ValidationError>> {
-        if !(self.device().api_version() >= Version::V1_3
-            || self.device().enabled_features().extended_dynamic_state2)
+    fn validate_set_color_blend_equation(
+        &self,
+        _first_attachment: u32,
+        equations: &[AttachmentBlend],
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_color_blend_equation
+            || self.device().enabled_features().shader_object)
         {
             return Err(Box::new(ValidationError {
                 requires_one_of: RequiresOneOf(&[
-                    RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
-                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state2")]),
+                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state3_color_blend_equation")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
                 ]),
-                vuids: &["VUID-vkCmdSetDepthBiasEnable-None-04872"],
+                vuids: &["VUID-vkCmdSetColorBlendEquationEXT-extendedDynamicState3ColorBlendEquation-07356"],
                 ..Default::default()
             }));
         }
@@ -1421,41 +2246,96 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdSetDepthBiasEnable-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdSetColorBlendEquationEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        if equations.is_empty() {
+            return Err(Box::new(ValidationError {
+                context: "equations".into(),
+                problem: "is empty".into(),
+                vuids: &["VUID-vkCmdSetColorBlendEquationEXT-attachmentCount-arraylength"],
                 ..Default::default()
             }));
         }
 
+        for (index, equation) in equations.iter().enumerate() {
+            equation
+                .validate(self.device())
+                .map_err(|err| err.add_context(format!("equations[{}]", index)))?;
+        }
+
         Ok(())
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_depth_bias_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+    pub unsafe fn set_color_blend_equation_unchecked(
+        &mut self,
+        first_attachment: u32,
+        equations: &[AttachmentBlend],
+    ) -> &mut Self {
+        let equations = equations
+            .iter()
+            .map(|equation| ash::vk::ColorBlendEquationEXT {
+                src_color_blend_factor: equation.src_color_blend_factor.into(),
+                dst_color_blend_factor: equation.dst_color_blend_factor.into(),
+                color_blend_op: equation.color_blend_op.into(),
+                src_alpha_blend_factor: equation.src_alpha_blend_factor.into(),
+                dst_alpha_blend_factor: equation.dst_alpha_blend_factor.into(),
+                alpha_blend_op: equation.alpha_blend_op.into(),
+            })
+            .collect::<SmallVec<[_; 4]>>();
+
         let fns = self.device().fns();
 
-        if self.device().api_version() >= Version::V1_3 {
-            (fns.v1_3.cmd_set_depth_bias_enable)(self.handle(), enable.into());
+        if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state3
+        {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_color_blend_equation_ext)(
+                self.handle(),
+                first_attachment,
+                equations.len() as u32,
+                equations.as_ptr(),
+            );
         } else {
-            (fns.ext_extended_dynamic_state2
-                .cmd_set_depth_bias_enable_ext)(self.handle(), enable.into());
+            (fns.ext_shader_object.cmd_set_color_blend_equation_ext)(
+                self.handle(),
+                first_attachment,
+                equations.len() as u32,
+                equations.as_ptr(),
+            );
         }
 
         self
     }
 
-    pub unsafe fn set_depth_bounds(
+    pub unsafe fn set_color_write_enable(
         &mut self,
-        bounds: RangeInclusive<f32>,
+        enables: &[bool],
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_set_depth_bounds(bounds.clone())?;
+        self.validate_set_color_write_enable(enables)?;
 
-        Ok(self.set_depth_bounds_unchecked(bounds))
+        Ok(self.set_color_write_enable_unchecked(enables))
     }
 
-    fn validate_set_depth_bounds(
+    fn validate_set_color_write_enable(
         &self,
-        bounds: RangeInclusive<f32>,
+        _enables: &[bool],
     ) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_features().color_write_enable {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "ext_color_write_enable",
+                )])]),
+                vuids: &["VUID-vkCmdSetColorWriteEnableEXT-None-04803"],
+                ..Default::default()
+            }));
+        }
+
         if !self
             .queue_family_properties()
             .queue_flags
@@ -1465,72 +2345,67 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdSetDepthBounds-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdSetColorWriteEnableEXT-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
 
-        if !self
-            .device()
-            .enabled_extensions()
-            .ext_depth_range_unrestricted
-        {
-            if !(0.0..=1.0).contains(bounds.start()) {
-                return Err(Box::new(ValidationError {
-                    context: "bounds.start()".into(),
-                    problem: "is not between `0.0` and `1.0` inclusive".into(),
-                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
-                        "ext_depth_range_unrestricted",
-                    )])]),
-                    vuids: &["VUID-vkCmdSetDepthBounds-minDepthBounds-00600"],
-                }));
-            }
-
-            if !(0.0..=1.0).contains(bounds.end()) {
-                return Err(Box::new(ValidationError {
-                    context: "bounds.end()".into(),
-                    problem: "is not between `0.0` and `1.0` inclusive".into(),
-                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
-                        "ext_depth_range_unrestricted",
-                    )])]),
-                    vuids: &["VUID-vkCmdSetDepthBounds-maxDepthBounds-00601"],
-                }));
-            }
-        }
-
         Ok(())
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_depth_bounds_unchecked(&mut self, bounds: RangeInclusive<f32>) -> &mut Self {
+    pub unsafe fn set_color_write_enable_unchecked(&mut self, enables: &[bool]) -> &mut Self {
+        let enables = enables
+            .iter()
+            .copied()
+            .map(|v| v as ash::vk::Bool32)
+            .collect::<SmallVec<[_; 4]>>();
+
+        if enables.is_empty() {
+            return self;
+        }
+
         let fns = self.device().fns();
-        (fns.v1_0.cmd_set_depth_bounds)(self.handle(), *bounds.start(), *bounds.end());
+        (fns.ext_color_write_enable.cmd_set_color_write_enable_ext)(
+            self.handle(),
+            enables.len() as u32,
+            enables.as_ptr(),
+        );
 
         self
     }
 
-    pub unsafe fn set_depth_bounds_test_enable(
+    pub unsafe fn set_color_write_mask(
         &mut self,
-        enable: bool,
+        first_attachment: u32,
+        masks: &[ColorComponents],
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_set_depth_bounds_test_enable(enable)?;
+        self.validate_set_color_write_mask(first_attachment, masks)?;
 
-        Ok(self.set_depth_bounds_test_enable_unchecked(enable))
+        Ok(self.set_color_write_mask_unchecked(first_attachment, masks))
     }
 
-    fn validate_set_depth_bounds_test_enable(
+    fn validate_set_color_write_mask(
         &self,
-        _enable: bool,
+        _first_attachment: u32,
+        masks: &[ColorComponents],
     ) -> Result<(), Box<ValidationError>> {
-        if !(self.device().api_version() >= Version::V1_3
-            || self.device().enabled_features().extended_dynamic_state)
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_color_write_mask
+            || self.device().enabled_features().shader_object)
         {
             return Err(Box::new(ValidationError {
                 requires_one_of: RequiresOneOf(&[
-                    RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
-                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state")]),
+                    RequiresAllOf(&[Requires::Feature(
+                        "extended_dynamic_state3_color_write_mask",
+                    )]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
                 ]),
-                vuids: &["VUID-vkCmdSetDepthBoundsTestEnable-None-03349"],
+                vuids: &[
+                    "VUID-vkCmdSetColorWriteMaskEXT-extendedDynamicState3ColorWriteMask-07364",
+                ],
                 ..Default::default()
             }));
         }
@@ -1544,7 +2419,16 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdSetDepthBoundsTestEnable-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdSetColorWriteMaskEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        if masks.is_empty() {
+            return Err(Box::new(ValidationError {
+                context: "masks".into(),
+                problem: "is empty".into(),
+                vuids: &["VUID-vkCmdSetColorWriteMaskEXT-attachmentCount-arraylength"],
                 ..Default::default()
             }));
         }
@@ -1553,41 +2437,63 @@ where
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_depth_bounds_test_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+    pub unsafe fn set_color_write_mask_unchecked(
+        &mut self,
+        first_attachment: u32,
+        masks: &[ColorComponents],
+    ) -> &mut Self {
+        let masks = masks
+            .iter()
+            .copied()
+            .map(ash::vk::ColorComponentFlags::from)
+            .collect::<SmallVec<[_; 4]>>();
+
         let fns = self.device().fns();
 
-        if self.device().api_version() >= Version::V1_3 {
-            (fns.v1_3.cmd_set_depth_bounds_test_enable)(self.handle(), enable.into());
+        if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state3
+        {
+            (fns.ext_extended_dynamic_state3.cmd_set_color_write_mask_ext)(
+                self.handle(),
+                first_attachment,
+                masks.len() as u32,
+                masks.as_ptr(),
+            );
         } else {
-            (fns.ext_extended_dynamic_state
-                .cmd_set_depth_bounds_test_enable_ext)(self.handle(), enable.into());
+            (fns.ext_shader_object.cmd_set_color_write_mask_ext)(
+                self.handle(),
+                first_attachment,
+                masks.len() as u32,
+                masks.as_ptr(),
+            );
         }
 
         self
     }
 
-    pub unsafe fn set_depth_compare_op(
+    pub unsafe fn set_cull_mode(
         &mut self,
-        compare_op: CompareOp,
+        cull_mode: CullMode,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_set_depth_compare_op(compare_op)?;
+        self.validate_set_cull_mode(cull_mode)?;
 
-        Ok(self.set_depth_compare_op_unchecked(compare_op))
+        Ok(self.set_cull_mode_unchecked(cull_mode))
     }
 
-    fn validate_set_depth_compare_op(
-        &self,
-        compare_op: CompareOp,
-    ) -> Result<(), Box<ValidationError>> {
+    fn validate_set_cull_mode(&self, cull_mode: CullMode) -> Result<(), Box<ValidationError>> {
         if !(self.device().api_version() >= Version::V1_3
-            || self.device().enabled_features().extended_dynamic_state)
+            || self.device().enabled_features().extended_dynamic_state
+            || self.device().enabled_features().shader_object)
         {
             return Err(Box::new(ValidationError {
                 requires_one_of: RequiresOneOf(&[
                     RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
                     RequiresAllOf(&[Requires::Feature("extended_dynamic_state")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
                 ]),
-                vuids: &["VUID-vkCmdSetDepthCompareOp-None-03353"],
+                vuids: &["VUID-vkCmdSetCullMode-None-03384"],
                 ..Default::default()
             }));
         }
@@ -1601,58 +2507,55 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdSetDepthCompareOp-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdSetCullMode-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
 
-        compare_op.validate_device(self.device()).map_err(|err| {
-            err.add_context("compare_op")
-                .set_vuids(&["VUID-vkCmdSetDepthCompareOp-depthCompareOp-parameter"])
+        cull_mode.validate_device(self.device()).map_err(|err| {
+            err.add_context("cull_mode")
+                .set_vuids(&["VUID-vkCmdSetCullMode-cullMode-parameter"])
         })?;
 
         Ok(())
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_depth_compare_op_unchecked(&mut self, compare_op: CompareOp) -> &mut Self {
+    pub unsafe fn set_cull_mode_unchecked(&mut self, cull_mode: CullMode) -> &mut Self {
         let fns = self.device().fns();
 
         if self.device().api_version() >= Version::V1_3 {
-            (fns.v1_3.cmd_set_depth_compare_op)(self.handle(), compare_op.into());
+            (fns.v1_3.cmd_set_cull_mode)(self.handle(), cull_mode.into());
+        } else if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state
+        {
+            (fns.ext_extended_dynamic_state.cmd_set_cull_mode_ext)(self.handle(), cull_mode.into());
         } else {
-            (fns.ext_extended_dynamic_state.cmd_set_depth_compare_op_ext)(
-                self.handle(),
-                compare_op.into(),
-            );
+            (fns.ext_shader_object.cmd_set_cull_mode_ext)(self.handle(), cull_mode.into());
         }
 
         self
     }
 
-    pub unsafe fn set_depth_test_enable(
+    pub unsafe fn set_depth_bias(
         &mut self,
-        enable: bool,
+        constant_factor: f32,
+        clamp: f32,
+        slope_factor: f32,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_set_depth_test_enable(enable)?;
+        self.validate_set_depth_bias(constant_factor, clamp, slope_factor)?;
 
-        Ok(self.set_depth_test_enable_unchecked(enable))
+        Ok(self.set_depth_bias_unchecked(constant_factor, clamp, slope_factor))
     }
 
-    fn validate_set_depth_test_enable(&self, _enable: bool) -> Result<(), Box<ValidationError>> {
-        if !(self.device().api_version() >= Version::V1_3
-            || self.device().enabled_features().extended_dynamic_state)
-        {
-            return Err(Box::new(ValidationError {
-                requires_one_of: RequiresOneOf(&[
-                    RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
-                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state")]),
-                ]),
-                vuids: &["VUID-vkCmdSetDepthTestEnable-None-03352"],
-                ..Default::default()
-            }));
-        }
-
+    fn validate_set_depth_bias(
+        &self,
+        _constant_factor: f32,
+        clamp: f32,
+        _slope_factor: f32,
+    ) -> Result<(), Box<ValidationError>> {
         if !self
             .queue_family_properties()
             .queue_flags
@@ -1662,49 +2565,59 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdSetDepthTestEnable-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdSetDepthBias-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
 
+        if clamp != 0.0 && !self.device().enabled_features().depth_bias_clamp {
+            return Err(Box::new(ValidationError {
+                context: "clamp".into(),
+                problem: "is not `0.0`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "depth_bias_clamp",
+                )])]),
+                vuids: &["VUID-vkCmdSetDepthBias-depthBiasClamp-00790"],
+            }));
+        }
+
         Ok(())
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_depth_test_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+    pub unsafe fn set_depth_bias_unchecked(
+        &mut self,
+        constant_factor: f32,
+        clamp: f32,
+        slope_factor: f32,
+    ) -> &mut Self {
         let fns = self.device().fns();
-
-        if self.device().api_version() >= Version::V1_3 {
-            (fns.v1_3.cmd_set_depth_test_enable)(self.handle(), enable.into());
-        } else {
-            (fns.ext_extended_dynamic_state.cmd_set_depth_test_enable_ext)(
-                self.handle(),
-                enable.into(),
-            );
-        }
+        (fns.v1_0.cmd_set_depth_bias)(self.handle(), constant_factor, clamp, slope_factor);
 
         self
     }
 
-    pub unsafe fn set_depth_write_enable(
+    pub unsafe fn set_depth_bias_enable(
         &mut self,
         enable: bool,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_set_depth_write_enable(enable)?;
+        self.validate_set_depth_bias_enable(enable)?;
 
-        Ok(self.set_depth_write_enable_unchecked(enable))
+        Ok(self.set_depth_bias_enable_unchecked(enable))
     }
 
-    fn validate_set_depth_write_enable(&self, _enable: bool) -> Result<(), Box<ValidationError>> {
+    fn validate_set_depth_bias_enable(&self, _enable: bool) -> Result<(), Box<ValidationError>> {
         if !(self.device().api_version() >= Version::V1_3
-            || self.device().enabled_features().extended_dynamic_state)
+            || self.device().enabled_features().extended_dynamic_state2
+            || self.device().enabled_features().shader_object)
         {
             return Err(Box::new(ValidationError {
                 requires_one_of: RequiresOneOf(&[
                     RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
-                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state")]),
+                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state2")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
                 ]),
-                vuids: &["VUID-vkCmdSetDepthWriteEnable-None-03354"],
+                vuids: &["VUID-vkCmdSetDepthBiasEnable-None-04872"],
                 ..Default::default()
             }));
         }
@@ -1718,7 +2631,7 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdSetDepthWriteEnable-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdSetDepthBiasEnable-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
@@ -1727,43 +2640,38 @@ where
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_depth_write_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+    pub unsafe fn set_depth_bias_enable_unchecked(&mut self, enable: bool) -> &mut Self {
         let fns = self.device().fns();
 
         if self.device().api_version() >= Version::V1_3 {
-            (fns.v1_3.cmd_set_depth_write_enable)(self.handle(), enable.into());
+            (fns.v1_3.cmd_set_depth_bias_enable)(self.handle(), enable.into());
+        } else if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state2
+        {
+            (fns.ext_extended_dynamic_state2
+                .cmd_set_depth_bias_enable_ext)(self.handle(), enable.into());
         } else {
-            (fns.ext_extended_dynamic_state
-                .cmd_set_depth_write_enable_ext)(self.handle(), enable.into());
+            (fns.ext_shader_object.cmd_set_depth_bias_enable_ext)(self.handle(), enable.into());
         }
 
         self
     }
 
-    pub unsafe fn set_discard_rectangle(
+    pub unsafe fn set_depth_bounds(
         &mut self,
-        first_rectangle: u32,
-        rectangles: &[Scissor],
+        bounds: RangeInclusive<f32>,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_set_discard_rectangle(first_rectangle, rectangles)?;
+        self.validate_set_depth_bounds(bounds.clone())?;
 
-        Ok(self.set_discard_rectangle_unchecked(first_rectangle, rectangles))
+        Ok(self.set_depth_bounds_unchecked(bounds))
     }
 
-    fn validate_set_discard_rectangle(
+    fn validate_set_depth_bounds(
         &self,
-        first_rectangle: u32,
-        rectangles: &[Scissor],
+        bounds: RangeInclusive<f32>,
     ) -> Result<(), Box<ValidationError>> {
-        if self.device().enabled_extensions().ext_discard_rectangles {
-            return Err(Box::new(ValidationError {
-                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
-                    "ext_discard_rectangles",
-                )])]),
-                ..Default::default()
-            }));
-        }
-
         if !self
             .queue_family_properties()
             .queue_flags
@@ -1773,70 +2681,74 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdSetDiscardRectangle-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdSetDepthBounds-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
 
-        let properties = self.device().physical_device().properties();
+        if !self
+            .device()
+            .enabled_extensions()
+            .ext_depth_range_unrestricted
+        {
+            if !(0.0..=1.0).contains(bounds.start()) {
+                return Err(Box::new(ValidationError {
+                    context: "bounds.start()".into(),
+                    problem: "is not between `0.0` and `1.0` inclusive".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                        "ext_depth_range_unrestricted",
+                    )])]),
+                    vuids: &["VUID-vkCmdSetDepthBounds-minDepthBounds-00600"],
+                }));
+            }
 
-        if first_rectangle + rectangles.len() as u32 > properties.max_discard_rectangles.unwrap() {
-            return Err(Box::new(ValidationError {
-                problem: "`first_rectangle + rectangles.len()` exceeds the \
-                    `max_discard_rectangles` limit"
-                    .into(),
-                vuids: &["VUID-vkCmdSetDiscardRectangleEXT-firstDiscardRectangle-00585"],
-                ..Default::default()
-            }));
+            if !(0.0..=1.0).contains(bounds.end()) {
+                return Err(Box::new(ValidationError {
+                    context: "bounds.end()".into(),
+                    problem: "is not between `0.0` and `1.0` inclusive".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                        "ext_depth_range_unrestricted",
+                    )])]),
+                    vuids: &["VUID-vkCmdSetDepthBounds-maxDepthBounds-00601"],
+                }));
+            }
         }
 
         Ok(())
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_discard_rectangle_unchecked(
-        &mut self,
-        first_rectangle: u32,
-        rectangles: &[Scissor],
-    ) -> &mut Self {
-        let rectangles = rectangles
-            .iter()
-            .map(|v| v.into())
-            .collect::<SmallVec<[_; 2]>>();
-        if rectangles.is_empty() {
-            return self;
-        }
-
+    pub unsafe fn set_depth_bounds_unchecked(&mut self, bounds: RangeInclusive<f32>) -> &mut Self {
         let fns = self.device().fns();
-        (fns.ext_discard_rectangles.cmd_set_discard_rectangle_ext)(
-            self.handle(),
-            first_rectangle,
-            rectangles.len() as u32,
-            rectangles.as_ptr(),
-        );
+        (fns.v1_0.cmd_set_depth_bounds)(self.handle(), *bounds.start(), *bounds.end());
 
         self
     }
 
-    pub unsafe fn set_front_face(
+    pub unsafe fn set_depth_bounds_test_enable(
         &mut self,
-        face: FrontFace,
+        enable: bool,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_set_front_face(face)?;
+        self.validate_set_depth_bounds_test_enable(enable)?;
 
-        Ok(self.set_front_face_unchecked(face))
+        Ok(self.set_depth_bounds_test_enable_unchecked(enable))
     }
 
-    fn validate_set_front_face(&self, face: FrontFace) -> Result<(), Box<ValidationError>> {
+    fn validate_set_depth_bounds_test_enable(
+        &self,
+        enable: bool,
+    ) -> Result<(), Box<ValidationError>> {
         if !(self.device().api_version() >= Version::V1_3
-            || self.device().enabled_features().extended_dynamic_state)
+            || self.device().enabled_features().extended_dynamic_state
+            || self.device().enabled_features().shader_object)
         {
             return Err(Box::new(ValidationError {
                 requires_one_of: RequiresOneOf(&[
                     RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
                     RequiresAllOf(&[Requires::Feature("extended_dynamic_state")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
                 ]),
-                vuids: &["VUID-vkCmdSetFrontFace-None-03383"],
+                vuids: &["VUID-vkCmdSetDepthBoundsTestEnable-None-03349"],
                 ..Default::default()
             }));
         }
@@ -1850,52 +2762,75 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdSetFrontFace-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdSetDepthBoundsTestEnable-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
 
-        face.validate_device(self.device()).map_err(|err| {
-            err.add_context("face")
-                .set_vuids(&["VUID-vkCmdSetFrontFace-frontFace-parameter"])
-        })?;
+        if enable && !self.device().enabled_features().depth_bounds {
+            return Err(Box::new(ValidationError {
+                context: "enable".into(),
+                problem: "is `true`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "depth_bounds",
+                )])]),
+                // vuids?
+                ..Default::default()
+            }));
+        }
 
         Ok(())
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_front_face_unchecked(&mut self, face: FrontFace) -> &mut Self {
+    pub unsafe fn set_depth_bounds_test_enable_unchecked(&mut self, enable: bool) -> &mut Self {
         let fns = self.device().fns();
 
         if self.device().api_version() >= Version::V1_3 {
-            (fns.v1_3.cmd_set_front_face)(self.handle(), face.into());
+            (fns.v1_3.cmd_set_depth_bounds_test_enable)(self.handle(), enable.into());
+        } else if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state
+        {
+            (fns.ext_extended_dynamic_state
+                .cmd_set_depth_bounds_test_enable_ext)(self.handle(), enable.into());
         } else {
-            (fns.ext_extended_dynamic_state.cmd_set_front_face_ext)(self.handle(), face.into());
+            (fns.ext_shader_object.cmd_set_depth_bounds_test_enable_ext)(
+                self.handle(),
+                enable.into(),
+            );
         }
 
         self
     }
 
-    pub unsafe fn set_line_stipple(
+    pub unsafe fn set_depth_clamp_enable(
         &mut self,
-        factor: u32,
-        pattern: u16,
+        enable: bool,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_set_line_stipple(factor, pattern)?;
+        self.validate_set_depth_clamp_enable(enable)?;
 
-        Ok(self.set_line_stipple_unchecked(factor, pattern))
+        Ok(self.set_depth_clamp_enable_unchecked(enable))
     }
 
-    fn validate_set_line_stipple(
-        &self,
-        factor: u32,
-        _pattern: u16,
-    ) -> Result<(), Box<ValidationError>> {
-        if !self.device().enabled_extensions().ext_line_rasterization {
+    fn validate_set_depth_clamp_enable(&self, enable: bool) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_depth_clamp_enable
+            || self.device().enabled_features().shader_object)
+        {
             return Err(Box::new(ValidationError {
-                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
-                    "ext_line_rasterization",
-                )])]),
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::Feature(
+                        "extended_dynamic_state3_depth_clamp_enable",
+                    )]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
+                ]),
+                vuids: &[
+                    "VUID-vkCmdSetDepthClampEnableEXT-extendedDynamicState3DepthClampEnable-07448",
+                ],
                 ..Default::default()
             }));
         }
@@ -1909,17 +2844,19 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdSetLineStippleEXT-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdSetDepthClampEnableEXT-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
 
-        if !(1..=256).contains(&factor) {
+        if enable && !self.device().enabled_features().depth_clamp {
             return Err(Box::new(ValidationError {
-                context: "factor".into(),
-                problem: "is not between 1 and 256 inclusive".into(),
-                vuids: &["VUID-vkCmdSetLineStippleEXT-lineStippleFactor-02776"],
-                ..Default::default()
+                context: "enable".into(),
+                problem: "is `true`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "depth_clamp",
+                )])]),
+                vuids: &["VUID-vkCmdSetDepthClampEnableEXT-depthClamp-07449"],
             }));
         }
 
@@ -1927,23 +2864,53 @@ where
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_line_stipple_unchecked(&mut self, factor: u32, pattern: u16) -> &mut Self {
+    pub unsafe fn set_depth_clamp_enable_unchecked(&mut self, enable: bool) -> &mut Self {
         let fns = self.device().fns();
-        (fns.ext_line_rasterization.cmd_set_line_stipple_ext)(self.handle(), factor, pattern);
+
+        if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state3
+        {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_depth_clamp_enable_ext)(self.handle(), enable.into());
+        } else {
+            (fns.ext_shader_object.cmd_set_depth_clamp_enable_ext)(self.handle(), enable.into());
+        }
 
         self
     }
 
-    pub unsafe fn set_line_width(
+    pub unsafe fn set_depth_clip_enable(
         &mut self,
-        line_width: f32,
+        enable: bool,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_set_line_width(line_width)?;
+        self.validate_set_depth_clip_enable(enable)?;
 
-        Ok(self.set_line_width_unchecked(line_width))
+        Ok(self.set_depth_clip_enable_unchecked(enable))
     }
 
-    fn validate_set_line_width(&self, line_width: f32) -> Result<(), Box<ValidationError>> {
+    fn validate_set_depth_clip_enable(&self, _enable: bool) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_depth_clip_enable
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::Feature(
+                        "extended_dynamic_state3_depth_clip_enable",
+                    )]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
+                ]),
+                vuids: &[
+                    "VUID-vkCmdSetDepthClipEnableEXT-extendedDynamicState3DepthClipEnable-07450",
+                ],
+                ..Default::default()
+            }));
+        }
+
         if !self
             .queue_family_properties()
             .queue_flags
@@ -1953,19 +2920,18 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdSetLineWidth-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdSetDepthClipEnableEXT-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
 
-        if line_width != 1.0 && !self.device().enabled_features().wide_lines {
+        if !self.device().enabled_features().depth_clip_enable {
             return Err(Box::new(ValidationError {
-                context: "line_width".into(),
-                problem: "is not 1.0".into(),
                 requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
-                    "wide_lines",
+                    "depth_clip_enable",
                 )])]),
-                vuids: &["VUID-vkCmdSetLineWidth-lineWidth-00788"],
+                vuids: &["VUID-vkCmdSetDepthClipEnableEXT-depthClipEnable-07451"],
+                ..Default::default()
             }));
         }
 
@@ -1973,33 +2939,47 @@ where
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_line_width_unchecked(&mut self, line_width: f32) -> &mut Self {
+    pub unsafe fn set_depth_clip_enable_unchecked(&mut self, enable: bool) -> &mut Self {
         let fns = self.device().fns();
-        (fns.v1_0.cmd_set_line_width)(self.handle(), line_width);
+
+        if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state3
+        {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_depth_clip_enable_ext)(self.handle(), enable.into());
+        } else {
+            (fns.ext_shader_object.cmd_set_depth_clip_enable_ext)(self.handle(), enable.into());
+        }
 
         self
     }
 
-    pub unsafe fn set_logic_op(
+    pub unsafe fn set_depth_compare_op(
         &mut self,
-        logic_op: LogicOp,
+        compare_op: CompareOp,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_set_logic_op(logic_op)?;
+        self.validate_set_depth_compare_op(compare_op)?;
 
-        Ok(self.set_logic_op_unchecked(logic_op))
+        Ok(self.set_depth_compare_op_unchecked(compare_op))
     }
 
-    fn validate_set_logic_op(&self, logic_op: LogicOp) -> Result<(), Box<ValidationError>> {
-        if !self
-            .device()
-            .enabled_features()
-            .extended_dynamic_state2_logic_op
+    fn validate_set_depth_compare_op(
+        &self,
+        compare_op: CompareOp,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self.device().api_version() >= Version::V1_3
+            || self.device().enabled_features().extended_dynamic_state
+            || self.device().enabled_features().shader_object)
         {
             return Err(Box::new(ValidationError {
-                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
-                    "extended_dynamic_state2_logic_op",
-                )])]),
-                vuids: &["VUID-vkCmdSetLogicOpEXT-None-04867"],
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
+                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetDepthCompareOp-None-03353"],
                 ..Default::default()
             }));
         }
@@ -2013,47 +2993,62 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdSetLogicOpEXT-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdSetDepthCompareOp-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
 
-        logic_op.validate_device(self.device()).map_err(|err| {
-            err.add_context("logic_op")
-                .set_vuids(&["VUID-vkCmdSetLogicOpEXT-logicOp-parameter"])
+        compare_op.validate_device(self.device()).map_err(|err| {
+            err.add_context("compare_op")
+                .set_vuids(&["VUID-vkCmdSetDepthCompareOp-depthCompareOp-parameter"])
         })?;
 
         Ok(())
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_logic_op_unchecked(&mut self, logic_op: LogicOp) -> &mut Self {
+    pub unsafe fn set_depth_compare_op_unchecked(&mut self, compare_op: CompareOp) -> &mut Self {
         let fns = self.device().fns();
-        (fns.ext_extended_dynamic_state2.cmd_set_logic_op_ext)(self.handle(), logic_op.into());
+
+        if self.device().api_version() >= Version::V1_3 {
+            (fns.v1_3.cmd_set_depth_compare_op)(self.handle(), compare_op.into());
+        } else if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state
+        {
+            (fns.ext_extended_dynamic_state.cmd_set_depth_compare_op_ext)(
+                self.handle(),
+                compare_op.into(),
+            );
+        } else {
+            (fns.ext_shader_object.cmd_set_depth_compare_op_ext)(self.handle(), compare_op.into());
+        }
 
         self
     }
 
-    pub unsafe fn set_patch_control_points(
+    pub unsafe fn set_depth_test_enable(
         &mut self,
-        num: u32,
+        enable: bool,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_set_patch_control_points(num)?;
+        self.validate_set_depth_test_enable(enable)?;
 
-        Ok(self.set_patch_control_points_unchecked(num))
+        Ok(self.set_depth_test_enable_unchecked(enable))
     }
 
-    fn validate_set_patch_control_points(&self, num: u32) -> Result<(), Box<ValidationError>> {
-        if !self
-            .device()
-            .enabled_features()
-            .extended_dynamic_state2_patch_control_points
+    fn validate_set_depth_test_enable(&self, _enable: bool) -> Result<(), Box<ValidationError>> {
+        if !(self.device().api_version() >= Version::V1_3
+            || self.device().enabled_features().extended_dynamic_state
+            || self.device().enabled_features().shader_object)
         {
             return Err(Box::new(ValidationError {
-                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
-                    "extended_dynamic_state2_patch_control_points",
-                )])]),
-                vuids: &["VUID-vkCmdSetPatchControlPointsEXT-None-04873"],
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
+                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetDepthTestEnable-None-03352"],
                 ..Default::default()
             }));
         }
@@ -2067,27 +3062,7 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdSetPatchControlPointsEXT-commandBuffer-cmdpool"],
-                ..Default::default()
-            }));
-        }
-
-        if num == 0 {
-            return Err(Box::new(ValidationError {
-                context: "num".into(),
-                problem: "is zero".into(),
-                vuids: &["VUID-vkCmdSetPatchControlPointsEXT-patchControlPoints-04874"],
-                ..Default::default()
-            }));
-        }
-
-        let properties = self.device().physical_device().properties();
-
-        if num > properties.max_tessellation_patch_size {
-            return Err(Box::new(ValidationError {
-                context: "num".into(),
-                problem: "exceeds the `max_tessellation_patch_size` limit".into(),
-                vuids: &["VUID-vkCmdSetPatchControlPointsEXT-patchControlPoints-04874"],
+                vuids: &["VUID-vkCmdSetDepthTestEnable-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
@@ -2096,36 +3071,48 @@ where
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_patch_control_points_unchecked(&mut self, num: u32) -> &mut Self {
+    pub unsafe fn set_depth_test_enable_unchecked(&mut self, enable: bool) -> &mut Self {
         let fns = self.device().fns();
-        (fns.ext_extended_dynamic_state2
-            .cmd_set_patch_control_points_ext)(self.handle(), num);
+
+        if self.device().api_version() >= Version::V1_3 {
+            (fns.v1_3.cmd_set_depth_test_enable)(self.handle(), enable.into());
+        } else if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state
+        {
+            (fns.ext_extended_dynamic_state.cmd_set_depth_test_enable_ext)(
+                self.handle(),
+                enable.into(),
+            );
+        } else {
+            (fns.ext_shader_object.cmd_set_depth_test_enable_ext)(self.handle(), enable.into());
+        }
 
         self
     }
 
-    pub unsafe fn set_primitive_restart_enable(
+    pub unsafe fn set_depth_write_enable(
         &mut self,
         enable: bool,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_set_primitive_restart_enable(enable)?;
+        self.validate_set_depth_write_enable(enable)?;
 
-        Ok(self.set_primitive_restart_enable_unchecked(enable))
+        Ok(self.set_depth_write_enable_unchecked(enable))
     }
 
-    fn validate_set_primitive_restart_enable(
-        &self,
-        _enable: bool,
-    ) -> Result<(), Box<ValidationError>> {
+    fn validate_set_depth_write_enable(&self, _enable: bool) -> Result<(), Box<ValidationError>> {
         if !(self.device().api_version() >= Version::V1_3
-            || self.device().enabled_features().extended_dynamic_state2)
+            || self.device().enabled_features().extended_dynamic_state
+            || self.device().enabled_features().shader_object)
         {
             return Err(Box::new(ValidationError {
                 requires_one_of: RequiresOneOf(&[
                     RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
-                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state2")]),
+                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
                 ]),
-                vuids: &["VUID-vkCmdSetPrimitiveRestartEnable-None-04866"],
+                vuids: &["VUID-vkCmdSetDepthWriteEnable-None-03354"],
                 ..Default::default()
             }));
         }
@@ -2139,7 +3126,7 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdSetPrimitiveRestartEnable-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdSetDepthWriteEnable-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
@@ -2148,41 +3135,1302 @@ where
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_primitive_restart_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+    pub unsafe fn set_depth_write_enable_unchecked(&mut self, enable: bool) -> &mut Self {
         let fns = self.device().fns();
 
         if self.device().api_version() >= Version::V1_3 {
-            (fns.v1_3.cmd_set_primitive_restart_enable)(self.handle(), enable.into());
+            (fns.v1_3.cmd_set_depth_write_enable)(self.handle(), enable.into());
+        } else if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state
+        {
+            (fns.ext_extended_dynamic_state
+                .cmd_set_depth_write_enable_ext)(self.handle(), enable.into());
         } else {
-            (fns.ext_extended_dynamic_state2
-                .cmd_set_primitive_restart_enable_ext)(self.handle(), enable.into());
+            (fns.ext_shader_object.cmd_set_depth_write_enable_ext)(self.handle(), enable.into());
         }
 
         self
     }
 
-    pub unsafe fn set_primitive_topology(
+    pub unsafe fn set_discard_rectangle(
         &mut self,
-        topology: PrimitiveTopology,
+        first_rectangle: u32,
+        rectangles: &[Scissor],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_discard_rectangle(first_rectangle, rectangles)?;
+
+        Ok(self.set_discard_rectangle_unchecked(first_rectangle, rectangles))
+    }
+
+    fn validate_set_discard_rectangle(
+        &self,
+        first_rectangle: u32,
+        rectangles: &[Scissor],
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_extensions().ext_discard_rectangles {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "ext_discard_rectangles",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetDiscardRectangleEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        let properties = self.device().physical_device().properties();
+
+        if first_rectangle + rectangles.len() as u32 > properties.max_discard_rectangles.unwrap() {
+            return Err(Box::new(ValidationError {
+                problem: "`first_rectangle + rectangles.len()` exceeds the \
+                    `max_discard_rectangles` limit"
+                    .into(),
+                vuids: &["VUID-vkCmdSetDiscardRectangleEXT-firstDiscardRectangle-00585"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_discard_rectangle_unchecked(
+        &mut self,
+        first_rectangle: u32,
+        rectangles: &[Scissor],
+    ) -> &mut Self {
+        let rectangles = rectangles
+            .iter()
+            .map(|v| v.into())
+            .collect::<SmallVec<[_; 2]>>();
+        if rectangles.is_empty() {
+            return self;
+        }
+
+        let fns = self.device().fns();
+        (fns.ext_discard_rectangles.cmd_set_discard_rectangle_ext)(
+            self.handle(),
+            first_rectangle,
+            rectangles.len() as u32,
+            rectangles.as_ptr(),
+        );
+
+        self
+    }
+
+    pub unsafe fn set_discard_rectangle_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_discard_rectangle_enable(enable)?;
+
+        Ok(self.set_discard_rectangle_enable_unchecked(enable))
+    }
+
+    fn validate_set_discard_rectangle_enable(
+        &self,
+        _enable: bool,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_extensions().ext_discard_rectangles {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "ext_discard_rectangles",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetDiscardRectangleEnableEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_discard_rectangle_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_discard_rectangles
+            .cmd_set_discard_rectangle_enable_ext)(self.handle(), enable.into());
+
+        self
+    }
+
+    pub unsafe fn set_discard_rectangle_mode(
+        &mut self,
+        mode: DiscardRectangleMode,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_discard_rectangle_mode(mode)?;
+
+        Ok(self.set_discard_rectangle_mode_unchecked(mode))
+    }
+
+    fn validate_set_discard_rectangle_mode(
+        &self,
+        mode: DiscardRectangleMode,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_extensions().ext_discard_rectangles {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "ext_discard_rectangles",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetDiscardRectangleModeEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        mode.validate_device(self.device()).map_err(|err| {
+            err.add_context("mode")
+                .set_vuids(&["VUID-vkCmdSetDiscardRectangleModeEXT-discardRectangleMode-parameter"])
+        })?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_discard_rectangle_mode_unchecked(
+        &mut self,
+        mode: DiscardRectangleMode,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_discard_rectangles
+            .cmd_set_discard_rectangle_mode_ext)(self.handle(), mode.into());
+
+        self
+    }
+
+    pub unsafe fn set_fragment_shading_rate(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_fragment_shading_rate(fragment_size, combiner_ops)?;
+
+        Ok(self.set_fragment_shading_rate_unchecked(fragment_size, combiner_ops))
+    }
+
+    fn validate_set_fragment_shading_rate(
+        &self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) -> Result<(), Box<ValidationError>> {
+        let device = self.device();
+
+        if !device.enabled_extensions().khr_fragment_shading_rate {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "khr_fragment_shading_rate",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetFragmentShadingRateKHR-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        let enabled_features = device.enabled_features();
+
+        if !(enabled_features.pipeline_fragment_shading_rate
+            || enabled_features.primitive_fragment_shading_rate
+            || enabled_features.attachment_fragment_shading_rate)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::Feature("pipeline_fragment_shading_rate")]),
+                    RequiresAllOf(&[Requires::Feature("primitive_fragment_shading_rate")]),
+                    RequiresAllOf(&[Requires::Feature("attachment_fragment_shading_rate")]),
+                ]),
+                vuids: &["VUID-vkCmdSetFragmentShadingRateKHR-pipelineFragmentShadingRate-04509"],
+                ..Default::default()
+            }));
+        }
+
+        validate_fragment_size(
+            device,
+            fragment_size,
+            &[
+                "VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04513",
+                "VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04515",
+                "VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04517",
+            ],
+            &[
+                "VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04514",
+                "VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04516",
+                "VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04518",
+            ],
+            &[
+                "VUID-vkCmdSetFragmentShadingRateKHR-pipelineFragmentShadingRate-04507",
+                "VUID-vkCmdSetFragmentShadingRateKHR-pipelineFragmentShadingRate-04508",
+            ],
+        )?;
+
+        validate_combiner_ops(
+            device,
+            combiner_ops,
+            [
+                &["VUID-vkCmdSetFragmentShadingRateKHR-combinerOps-parameter"],
+                &["VUID-vkCmdSetFragmentShadingRateKHR-combinerOps-parameter"],
+            ],
+            &["VUID-vkCmdSetFragmentShadingRateKHR-primitiveFragmentShadingRate-04510"],
+            &["VUID-vkCmdSetFragmentShadingRateKHR-attachmentFragmentShadingRate-04511"],
+            &["VUID-vkCmdSetFragmentShadingRateKHR-fragmentSizeNonTrivialCombinerOps-04512"],
+        )?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_fragment_shading_rate_unchecked(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) -> &mut Self {
+        let fragment_size_vk = ash::vk::Extent2D {
+            width: fragment_size[0],
+            height: fragment_size[1],
+        };
+        let combiner_ops_vk = combiner_ops.map(Into::into);
+
+        let fns = self.device().fns();
+        (fns.khr_fragment_shading_rate
+            .cmd_set_fragment_shading_rate_khr)(
+            self.handle(), &fragment_size_vk, &combiner_ops_vk
+        );
+
+        self
+    }
+
+    pub unsafe fn set_front_face(
+        &mut self,
+        face: FrontFace,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_front_face(face)?;
+
+        Ok(self.set_front_face_unchecked(face))
+    }
+
+    fn validate_set_front_face(&self, face: FrontFace) -> Result<(), Box<ValidationError>> {
+        if !(self.device().api_version() >= Version::V1_3
+            || self.device().enabled_features().extended_dynamic_state
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
+                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetFrontFace-None-03383"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetFrontFace-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        face.validate_device(self.device()).map_err(|err| {
+            err.add_context("face")
+                .set_vuids(&["VUID-vkCmdSetFrontFace-frontFace-parameter"])
+        })?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_front_face_unchecked(&mut self, face: FrontFace) -> &mut Self {
+        let fns = self.device().fns();
+
+        if self.device().api_version() >= Version::V1_3 {
+            (fns.v1_3.cmd_set_front_face)(self.handle(), face.into());
+        } else if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state
+        {
+            (fns.ext_extended_dynamic_state.cmd_set_front_face_ext)(self.handle(), face.into());
+        } else {
+            (fns.ext_shader_object.cmd_set_front_face_ext)(self.handle(), face.into());
+        }
+
+        self
+    }
+
+    pub unsafe fn set_line_stipple(
+        &mut self,
+        factor: u32,
+        pattern: u16,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_line_stipple(factor, pattern)?;
+
+        Ok(self.set_line_stipple_unchecked(factor, pattern))
+    }
+
+    fn validate_set_line_stipple(
+        &self,
+        factor: u32,
+        _pattern: u16,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_extensions().ext_line_rasterization {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "ext_line_rasterization",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetLineStippleEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        if !(1..=256).contains(&factor) {
+            return Err(Box::new(ValidationError {
+                context: "factor".into(),
+                problem: "is not between 1 and 256 inclusive".into(),
+                vuids: &["VUID-vkCmdSetLineStippleEXT-lineStippleFactor-02776"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_line_stipple_unchecked(&mut self, factor: u32, pattern: u16) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_line_rasterization.cmd_set_line_stipple_ext)(self.handle(), factor, pattern);
+
+        self
+    }
+
+    pub unsafe fn set_line_width(
+        &mut self,
+        line_width: f32,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_line_width(line_width)?;
+
+        Ok(self.set_line_width_unchecked(line_width))
+    }
+
+    fn validate_set_line_width(&self, line_width: f32) -> Result<(), Box<ValidationError>> {
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetLineWidth-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        if line_width != 1.0 && !self.device().enabled_features().wide_lines {
+            return Err(Box::new(ValidationError {
+                context: "line_width".into(),
+                problem: "is not 1.0".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "wide_lines",
+                )])]),
+                vuids: &["VUID-vkCmdSetLineWidth-lineWidth-00788"],
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_line_width_unchecked(&mut self, line_width: f32) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.v1_0.cmd_set_line_width)(self.handle(), line_width);
+
+        self
+    }
+
+    pub unsafe fn set_logic_op(
+        &mut self,
+        logic_op: LogicOp,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_logic_op(logic_op)?;
+
+        Ok(self.set_logic_op_unchecked(logic_op))
+    }
+
+    fn validate_set_logic_op(&self, logic_op: LogicOp) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state2_logic_op
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state2_logic_op")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetLogicOpEXT-None-04867"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetLogicOpEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        logic_op.validate_device(self.device()).map_err(|err| {
+            err.add_context("logic_op")
+                .set_vuids(&["VUID-vkCmdSetLogicOpEXT-logicOp-parameter"])
+        })?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_logic_op_unchecked(&mut self, logic_op: LogicOp) -> &mut Self {
+        let fns = self.device().fns();
+
+        if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state2
+        {
+            (fns.ext_extended_dynamic_state2.cmd_set_logic_op_ext)(self.handle(), logic_op.into());
+        } else {
+            (fns.ext_shader_object.cmd_set_logic_op_ext)(self.handle(), logic_op.into());
+        }
+
+        self
+    }
+
+    pub unsafe fn set_logic_op_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_logic_op_enable(enable)?;
+
+        Ok(self.set_logic_op_enable_unchecked(enable))
+    }
+
+    fn validate_set_logic_op_enable(&self, enable: bool) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_logic_op_enable
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state3_logic_op_enable")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetLogicOpEnableEXT-extendedDynamicState3LogicOpEnable-07365"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetLogicOpEnableEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        if enable && !self.device().enabled_features().logic_op {
+            return Err(Box::new(ValidationError {
+                context: "enable".into(),
+                problem: "is `true`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature("logic_op")])]),
+                vuids: &["VUID-vkCmdSetLogicOpEnableEXT-logicOp-07366"],
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_logic_op_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
+
+        if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state3
+        {
+            (fns.ext_extended_dynamic_state3.cmd_set_logic_op_enable_ext)(
+                self.handle(),
+                enable.into(),
+            );
+        } else {
+            (fns.ext_shader_object.cmd_set_logic_op_enable_ext)(self.handle(), enable.into());
+        }
+
+        self
+    }
+
+    pub unsafe fn set_patch_control_points(
+        &mut self,
+        num: u32,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_patch_control_points(num)?;
+
+        Ok(self.set_patch_control_points_unchecked(num))
+    }
+
+    fn validate_set_patch_control_points(&self, num: u32) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state2_patch_control_points
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::Feature(
+                        "extended_dynamic_state2_patch_control_points",
+                    )]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetPatchControlPointsEXT-None-04873"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetPatchControlPointsEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        if num == 0 {
+            return Err(Box::new(ValidationError {
+                context: "num".into(),
+                problem: "is zero".into(),
+                vuids: &["VUID-vkCmdSetPatchControlPointsEXT-patchControlPoints-04874"],
+                ..Default::default()
+            }));
+        }
+
+        let properties = self.device().physical_device().properties();
+
+        if num > properties.max_tessellation_patch_size {
+            return Err(Box::new(ValidationError {
+                context: "num".into(),
+                problem: "exceeds the `max_tessellation_patch_size` limit".into(),
+                vuids: &["VUID-vkCmdSetPatchControlPointsEXT-patchControlPoints-04874"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_patch_control_points_unchecked(&mut self, num: u32) -> &mut Self {
+        let fns = self.device().fns();
+
+        if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state2
+        {
+            (fns.ext_extended_dynamic_state2
+                .cmd_set_patch_control_points_ext)(self.handle(), num);
+        } else {
+            (fns.ext_shader_object.cmd_set_patch_control_points_ext)(self.handle(), num);
+        }
+
+        self
+    }
+
+    pub unsafe fn set_polygon_mode(
+        &mut self,
+        polygon_mode: PolygonMode,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_polygon_mode(polygon_mode)?;
+
+        Ok(self.set_polygon_mode_unchecked(polygon_mode))
+    }
+
+    fn validate_set_polygon_mode(
+        &self,
+        polygon_mode: PolygonMode,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_polygon_mode
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state3_polygon_mode")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetPolygonModeEXT-extendedDynamicState3PolygonMode-07422"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetPolygonModeEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        polygon_mode.validate_device(self.device()).map_err(|err| {
+            err.add_context("polygon_mode")
+                .set_vuids(&["VUID-vkCmdSetPolygonModeEXT-polygonMode-parameter"])
+        })?;
+
+        if polygon_mode != PolygonMode::Fill
+            && !self.device().enabled_features().fill_mode_non_solid
+        {
+            return Err(Box::new(ValidationError {
+                context: "polygon_mode".into(),
+                problem: "is not `PolygonMode::Fill`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "fill_mode_non_solid",
+                )])]),
+                vuids: &["VUID-vkCmdSetPolygonModeEXT-fillModeNonSolid-07424"],
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_polygon_mode_unchecked(&mut self, polygon_mode: PolygonMode) -> &mut Self {
+        let fns = self.device().fns();
+
+        if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state3
+        {
+            (fns.ext_extended_dynamic_state3.cmd_set_polygon_mode_ext)(
+                self.handle(),
+                polygon_mode.into(),
+            );
+        } else {
+            (fns.ext_shader_object.cmd_set_polygon_mode_ext)(self.handle(), polygon_mode.into());
+        }
+
+        self
+    }
+
+    pub unsafe fn set_primitive_restart_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_primitive_restart_enable(enable)?;
+
+        Ok(self.set_primitive_restart_enable_unchecked(enable))
+    }
+
+    fn validate_set_primitive_restart_enable(
+        &self,
+        _enable: bool,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self.device().api_version() >= Version::V1_3
+            || self.device().enabled_features().extended_dynamic_state2
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
+                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state2")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetPrimitiveRestartEnable-None-04866"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetPrimitiveRestartEnable-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_primitive_restart_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
+
+        if self.device().api_version() >= Version::V1_3 {
+            (fns.v1_3.cmd_set_primitive_restart_enable)(self.handle(), enable.into());
+        } else if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state2
+        {
+            (fns.ext_extended_dynamic_state2
+                .cmd_set_primitive_restart_enable_ext)(self.handle(), enable.into());
+        } else {
+            (fns.ext_shader_object.cmd_set_primitive_restart_enable_ext)(
+                self.handle(),
+                enable.into(),
+            );
+        }
+
+        self
+    }
+
+    pub unsafe fn set_primitive_topology(
+        &mut self,
+        topology: PrimitiveTopology,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_primitive_topology(topology)?;
+
+        Ok(self.set_primitive_topology_unchecked(topology))
+    }
+
+    fn validate_set_primitive_topology(
+        &self,
+        topology: PrimitiveTopology,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self.device().api_version() >= Version::V1_3
+            || self.device().enabled_features().extended_dynamic_state
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
+                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetPrimitiveTopology-None-03347"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetPrimitiveTopology-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        topology.validate_device(self.device()).map_err(|err| {
+            err.add_context("topology")
+                .set_vuids(&["VUID-vkCmdSetPrimitiveTopology-primitiveTopology-parameter"])
+        })?;
+
+        // VUID?
+        // Since these requirements exist for fixed state when creating the pipeline,
+        // I assume they exist for dynamic state as well.
+        match topology {
+            PrimitiveTopology::TriangleFan => {
+                if self.device().enabled_extensions().khr_portability_subset
+                    && !self.device().enabled_features().triangle_fans
+                {
+                    return Err(Box::new(ValidationError {
+                        problem: "this device is a portability subset device, and `topology` \
+                            is `PrimitiveTopology::TriangleFan`"
+                            .into(),
+                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                            "triangle_fans",
+                        )])]),
+                        ..Default::default()
+                    }));
+                }
+            }
+            PrimitiveTopology::LineListWithAdjacency
+            | PrimitiveTopology::LineStripWithAdjacency
+            | PrimitiveTopology::TriangleListWithAdjacency
+            | PrimitiveTopology::TriangleStripWithAdjacency => {
+                if !self.device().enabled_features().geometry_shader {
+                    return Err(Box::new(ValidationError {
+                        problem: "`topology` is `PrimitiveTopology::*WithAdjacency`".into(),
+                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                            "geometry_shader",
+                        )])]),
+                        ..Default::default()
+                    }));
+                }
+            }
+            PrimitiveTopology::PatchList => {
+                if !self.device().enabled_features().tessellation_shader {
+                    return Err(Box::new(ValidationError {
+                        problem: "`topology` is `PrimitiveTopology::PatchList`".into(),
+                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                            "tessellation_shader",
+                        )])]),
+                        ..Default::default()
+                    }));
+                }
+            }
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_primitive_topology_unchecked(
+        &mut self,
+        topology: PrimitiveTopology,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+
+        if self.device().api_version() >= Version::V1_3 {
+            (fns.v1_3.cmd_set_primitive_topology)(self.handle(), topology.into());
+        } else if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state
+        {
+            (fns.ext_extended_dynamic_state
+                .cmd_set_primitive_topology_ext)(self.handle(), topology.into());
+        } else {
+            (fns.ext_shader_object.cmd_set_primitive_topology_ext)(self.handle(), topology.into());
+        }
+
+        self
+    }
+
+    pub unsafe fn set_provoking_vertex_mode(
+        &mut self,
+        mode: ProvokingVertexMode,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_provoking_vertex_mode(mode)?;
+
+        Ok(self.set_provoking_vertex_mode_unchecked(mode))
+    }
+
+    fn validate_set_provoking_vertex_mode(
+        &self,
+        mode: ProvokingVertexMode,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_provoking_vertex_mode
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state3_provoking_vertex_mode")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetProvokingVertexModeEXT-extendedDynamicState3ProvokingVertexMode-07446"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetProvokingVertexModeEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        mode.validate_device(self.device()).map_err(|err| {
+            err.add_context("mode")
+                .set_vuids(&["VUID-vkCmdSetProvokingVertexModeEXT-provokingVertexMode-parameter"])
+        })?;
+
+        if mode == ProvokingVertexMode::LastVertex
+            && !self.device().enabled_features().provoking_vertex_last
+        {
+            return Err(Box::new(ValidationError {
+                context: "mode".into(),
+                problem: "is `ProvokingVertexMode::LastVertex`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "provoking_vertex_last",
+                )])]),
+                vuids: &["VUID-vkCmdSetProvokingVertexModeEXT-provokingVertexMode-07447"],
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_provoking_vertex_mode_unchecked(
+        &mut self,
+        mode: ProvokingVertexMode,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+
+        if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state3
+        {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_provoking_vertex_mode_ext)(self.handle(), mode.into());
+        } else {
+            (fns.ext_shader_object.cmd_set_provoking_vertex_mode_ext)(self.handle(), mode.into());
+        }
+
+        self
+    }
+
+    pub unsafe fn set_rasterization_samples(
+        &mut self,
+        rasterization_samples: SampleCount,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_rasterization_samples(rasterization_samples)?;
+
+        Ok(self.set_rasterization_samples_unchecked(rasterization_samples))
+    }
+
+    fn validate_set_rasterization_samples(
+        &self,
+        rasterization_samples: SampleCount,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_rasterization_samples
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state3_rasterization_samples")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetRasterizationSamplesEXT-extendedDynamicState3RasterizationSamples-07414"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetRasterizationSamplesEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        rasterization_samples
+            .validate_device(self.device())
+            .map_err(|err| {
+                err.add_context("rasterization_samples").set_vuids(&[
+                    "VUID-vkCmdSetRasterizationSamplesEXT-rasterizationSamples-parameter",
+                ])
+            })?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_rasterization_samples_unchecked(
+        &mut self,
+        rasterization_samples: SampleCount,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+
+        if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state3
+        {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_rasterization_samples_ext)(
+                self.handle(), rasterization_samples.into()
+            );
+        } else {
+            (fns.ext_shader_object.cmd_set_rasterization_samples_ext)(
+                self.handle(),
+                rasterization_samples.into(),
+            );
+        }
+
+        self
+    }
+
+    pub unsafe fn set_rasterizer_discard_enable(
+        &mut self,
+        enable: bool,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_rasterizer_discard_enable(enable)?;
+
+        Ok(self.set_rasterizer_discard_enable_unchecked(enable))
+    }
+
+    fn validate_set_rasterizer_discard_enable(
+        &self,
+        _enable: bool,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self.device().api_version() >= Version::V1_3
+            || self.device().enabled_features().extended_dynamic_state2
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
+                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state2")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetRasterizerDiscardEnable-None-04871"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetRasterizerDiscardEnable-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_rasterizer_discard_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        let fns = self.device().fns();
+
+        if self.device().api_version() >= Version::V1_3 {
+            (fns.v1_3.cmd_set_rasterizer_discard_enable)(self.handle(), enable.into());
+        } else if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state2
+        {
+            (fns.ext_extended_dynamic_state2
+                .cmd_set_rasterizer_discard_enable_ext)(self.handle(), enable.into());
+        } else {
+            (fns.ext_shader_object.cmd_set_rasterizer_discard_enable_ext)(
+                self.handle(),
+                enable.into(),
+            );
+        }
+
+        self
+    }
+
+    pub unsafe fn set_sample_locations(
+        &mut self,
+        sample_locations: &SampleLocationsInfo,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_sample_locations(sample_locations)?;
+
+        Ok(self.set_sample_locations_unchecked(sample_locations))
+    }
+
+    fn validate_set_sample_locations(
+        &self,
+        sample_locations: &SampleLocationsInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_extensions().ext_sample_locations {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "ext_sample_locations",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetSampleLocationsEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        sample_locations
+            .validate(self.device())
+            .map_err(|err| err.add_context("sample_locations"))?;
+
+        // TODO:
+        // VUID-vkCmdSetSampleLocationsEXT-variableSampleLocations-01530
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_sample_locations_unchecked(
+        &mut self,
+        sample_locations: &SampleLocationsInfo,
+    ) -> &mut Self {
+        let sample_locations_vk = sample_locations.to_vk_sample_locations();
+        let sample_locations_info_vk = sample_locations.to_vk(&sample_locations_vk);
+
+        let fns = self.device().fns();
+        (fns.ext_sample_locations.cmd_set_sample_locations_ext)(
+            self.handle(),
+            &sample_locations_info_vk,
+        );
+
+        self
+    }
+
+    pub unsafe fn set_sample_locations_enable(
+        &mut self,
+        enable: bool,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_set_primitive_topology(topology)?;
+        self.validate_set_sample_locations_enable(enable)?;
 
-        Ok(self.set_primitive_topology_unchecked(topology))
+        Ok(self.set_sample_locations_enable_unchecked(enable))
     }
 
-    fn validate_set_primitive_topology(
+    fn validate_set_sample_locations_enable(
         &self,
-        topology: PrimitiveTopology,
+        _enable: bool,
     ) -> Result<(), Box<ValidationError>> {
-        if !(self.device().api_version() >= Version::V1_3
-            || self.device().enabled_features().extended_dynamic_state)
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_sample_locations_enable
+            || self.device().enabled_features().shader_object)
         {
             return Err(Box::new(ValidationError {
                 requires_one_of: RequiresOneOf(&[
-                    RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
-                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state")]),
+                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state3_sample_locations_enable")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
                 ]),
-                vuids: &["VUID-vkCmdSetPrimitiveTopology-None-03347"],
+                vuids: &["VUID-vkCmdSetSampleLocationsEnableEXT-extendedDynamicState3SampleLocationsEnable-07415"],
                 ..Default::default()
             }));
         }
@@ -2196,105 +4444,62 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdSetPrimitiveTopology-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdSetSampleLocationsEnableEXT-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
 
-        topology.validate_device(self.device()).map_err(|err| {
-            err.add_context("topology")
-                .set_vuids(&["VUID-vkCmdSetPrimitiveTopology-primitiveTopology-parameter"])
-        })?;
-
-        // VUID?
-        // Since these requirements exist for fixed state when creating the pipeline,
-        // I assume they exist for dynamic state as well.
-        match topology {
-            PrimitiveTopology::TriangleFan => {
-                if self.device().enabled_extensions().khr_portability_subset
-                    && !self.device().enabled_features().triangle_fans
-                {
-                    return Err(Box::new(ValidationError {
-                        problem: "this device is a portability subset device, and `topology` \
-                            is `PrimitiveTopology::TriangleFan`"
-                            .into(),
-                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
-                            "triangle_fans",
-                        )])]),
-                        ..Default::default()
-                    }));
-                }
-            }
-            PrimitiveTopology::LineListWithAdjacency
-            | PrimitiveTopology::LineStripWithAdjacency
-            | PrimitiveTopology::TriangleListWithAdjacency
-            | PrimitiveTopology::TriangleStripWithAdjacency => {
-                if !self.device().enabled_features().geometry_shader {
-                    return Err(Box::new(ValidationError {
-                        problem: "`topology` is `PrimitiveTopology::*WithAdjacency`".into(),
-                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
-                            "geometry_shader",
-                        )])]),
-                        ..Default::default()
-                    }));
-                }
-            }
-            PrimitiveTopology::PatchList => {
-                if !self.device().enabled_features().tessellation_shader {
-                    return Err(Box::new(ValidationError {
-                        problem: "`topology` is `PrimitiveTopology::PatchList`".into(),
-                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
-                            "tessellation_shader",
-                        )])]),
-                        ..Default::default()
-                    }));
-                }
-            }
-            _ => (),
-        }
-
         Ok(())
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_primitive_topology_unchecked(
-        &mut self,
-        topology: PrimitiveTopology,
-    ) -> &mut Self {
+    pub unsafe fn set_sample_locations_enable_unchecked(&mut self, enable: bool) -> &mut Self {
         let fns = self.device().fns();
 
-        if self.device().api_version() >= Version::V1_3 {
-            (fns.v1_3.cmd_set_primitive_topology)(self.handle(), topology.into());
+        if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state3
+        {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_sample_locations_enable_ext)(self.handle(), enable.into());
         } else {
-            (fns.ext_extended_dynamic_state
-                .cmd_set_primitive_topology_ext)(self.handle(), topology.into());
+            (fns.ext_shader_object.cmd_set_sample_locations_enable_ext)(
+                self.handle(),
+                enable.into(),
+            );
         }
 
         self
     }
 
-    pub unsafe fn set_rasterizer_discard_enable(
+    pub unsafe fn set_sample_mask(
         &mut self,
-        enable: bool,
+        samples: SampleCount,
+        sample_mask: [u32; 2],
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_set_rasterizer_discard_enable(enable)?;
+        self.validate_set_sample_mask(samples, sample_mask)?;
 
-        Ok(self.set_rasterizer_discard_enable_unchecked(enable))
+        Ok(self.set_sample_mask_unchecked(samples, sample_mask))
     }
 
-    fn validate_set_rasterizer_discard_enable(
+    fn validate_set_sample_mask(
         &self,
-        _enable: bool,
+        samples: SampleCount,
+        _sample_mask: [u32; 2],
     ) -> Result<(), Box<ValidationError>> {
-        if !(self.device().api_version() >= Version::V1_3
-            || self.device().enabled_features().extended_dynamic_state2)
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_sample_mask
+            || self.device().enabled_features().shader_object)
         {
             return Err(Box::new(ValidationError {
                 requires_one_of: RequiresOneOf(&[
-                    RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
-                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state2")]),
+                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state3_sample_mask")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
                 ]),
-                vuids: &["VUID-vkCmdSetRasterizerDiscardEnable-None-04871"],
+                vuids: &["VUID-vkCmdSetSampleMaskEXT-extendedDynamicState3SampleMask-07342"],
                 ..Default::default()
             }));
         }
@@ -2308,23 +4513,43 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdSetRasterizerDiscardEnable-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdSetSampleMaskEXT-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
 
+        samples.validate_device(self.device()).map_err(|err| {
+            err.add_context("samples")
+                .set_vuids(&["VUID-vkCmdSetSampleMaskEXT-samples-parameter"])
+        })?;
+
         Ok(())
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn set_rasterizer_discard_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+    pub unsafe fn set_sample_mask_unchecked(
+        &mut self,
+        samples: SampleCount,
+        sample_mask: [u32; 2],
+    ) -> &mut Self {
         let fns = self.device().fns();
 
-        if self.device().api_version() >= Version::V1_3 {
-            (fns.v1_3.cmd_set_rasterizer_discard_enable)(self.handle(), enable.into());
+        if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state3
+        {
+            (fns.ext_extended_dynamic_state3.cmd_set_sample_mask_ext)(
+                self.handle(),
+                samples.into(),
+                sample_mask.as_ptr(),
+            );
         } else {
-            (fns.ext_extended_dynamic_state2
-                .cmd_set_rasterizer_discard_enable_ext)(self.handle(), enable.into());
+            (fns.ext_shader_object.cmd_set_sample_mask_ext)(
+                self.handle(),
+                samples.into(),
+                sample_mask.as_ptr(),
+            );
         }
 
         self
@@ -2436,12 +4661,14 @@ where
         scissors: &[Scissor],
     ) -> Result<(), Box<ValidationError>> {
         if !(self.device().api_version() >= Version::V1_3
-            || self.device().enabled_features().extended_dynamic_state)
+            || self.device().enabled_features().extended_dynamic_state
+            || self.device().enabled_features().shader_object)
         {
             return Err(Box::new(ValidationError {
                 requires_one_of: RequiresOneOf(&[
                     RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
                     RequiresAllOf(&[Requires::Feature("extended_dynamic_state")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
                 ]),
                 vuids: &["VUID-vkCmdSetScissorWithCount-None-03396"],
                 ..Default::default()
@@ -2504,13 +4731,23 @@ where
                 scissors.len() as u32,
                 scissors.as_ptr(),
             );
-        } else {
+        } else if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state
+        {
             (fns.ext_extended_dynamic_state
                 .cmd_set_scissor_with_count_ext)(
                 self.handle(),
                 scissors.len() as u32,
                 scissors.as_ptr(),
             );
+        } else {
+            (fns.ext_shader_object.cmd_set_scissor_with_count_ext)(
+                self.handle(),
+                scissors.len() as u32,
+                scissors.as_ptr(),
+            );
         }
 
         self
@@ -2587,12 +4824,14 @@ where
         compare_op: CompareOp,
     ) -> Result<(), Box<ValidationError>> {
         if !(self.device().api_version() >= Version::V1_3
-            || self.device().enabled_features().extended_dynamic_state)
+            || self.device().enabled_features().extended_dynamic_state
+            || self.device().enabled_features().shader_object)
         {
             return Err(Box::new(ValidationError {
                 requires_one_of: RequiresOneOf(&[
                     RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
                     RequiresAllOf(&[Requires::Feature("extended_dynamic_state")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
                 ]),
                 vuids: &["VUID-vkCmdSetStencilOp-None-03351"],
                 ..Default::default()
@@ -2663,7 +4902,11 @@ where
                 depth_fail_op.into(),
                 compare_op.into(),
             );
-        } else {
+        } else if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state
+        {
             (fns.ext_extended_dynamic_state.cmd_set_stencil_op_ext)(
                 self.handle(),
                 faces.into(),
@@ -2672,6 +4915,15 @@ where
                 depth_fail_op.into(),
                 compare_op.into(),
             );
+        } else {
+            (fns.ext_shader_object.cmd_set_stencil_op_ext)(
+                self.handle(),
+                faces.into(),
+                fail_op.into(),
+                pass_op.into(),
+                depth_fail_op.into(),
+                compare_op.into(),
+            );
         }
 
         self
@@ -2737,12 +4989,14 @@ where
 
     fn validate_set_stencil_test_enable(&self, _enable: bool) -> Result<(), Box<ValidationError>> {
         if !(self.device().api_version() >= Version::V1_3
-            || self.device().enabled_features().extended_dynamic_state)
+            || self.device().enabled_features().extended_dynamic_state
+            || self.device().enabled_features().shader_object)
         {
             return Err(Box::new(ValidationError {
                 requires_one_of: RequiresOneOf(&[
                     RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
                     RequiresAllOf(&[Requires::Feature("extended_dynamic_state")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
                 ]),
                 vuids: &["VUID-vkCmdSetStencilTestEnable-None-03350"],
                 ..Default::default()
@@ -2772,9 +5026,15 @@ where
 
         if self.device().api_version() >= Version::V1_3 {
             (fns.v1_3.cmd_set_stencil_test_enable)(self.handle(), enable.into());
-        } else {
+        } else if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state
+        {
             (fns.ext_extended_dynamic_state
                 .cmd_set_stencil_test_enable_ext)(self.handle(), enable.into());
+        } else {
+            (fns.ext_shader_object.cmd_set_stencil_test_enable_ext)(self.handle(), enable.into());
         }
 
         self
@@ -2829,6 +5089,194 @@ where
         self
     }
 
+    pub unsafe fn set_tessellation_domain_origin(
+        &mut self,
+        domain_origin: TessellationDomainOrigin,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_tessellation_domain_origin(domain_origin)?;
+
+        Ok(self.set_tessellation_domain_origin_unchecked(domain_origin))
+    }
+
+    fn validate_set_tessellation_domain_origin(
+        &self,
+        domain_origin: TessellationDomainOrigin,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_tessellation_domain_origin
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::Feature("extended_dynamic_state3_tessellation_domain_origin")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetTessellationDomainOriginEXT-extendedDynamicState3TessellationDomainOrigin-07444"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetTessellationDomainOriginEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        domain_origin
+            .validate_device(self.device())
+            .map_err(|err| {
+                err.add_context("domain_origin")
+                    .set_vuids(&["VUID-vkCmdSetTessellationDomainOriginEXT-domainOrigin-parameter"])
+            })?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_tessellation_domain_origin_unchecked(
+        &mut self,
+        domain_origin: TessellationDomainOrigin,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+
+        if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state3
+        {
+            (fns.ext_extended_dynamic_state3
+                .cmd_set_tessellation_domain_origin_ext)(
+                self.handle(), domain_origin.into()
+            );
+        } else {
+            (fns.ext_shader_object.cmd_set_tessellation_domain_origin_ext)(
+                self.handle(),
+                domain_origin.into(),
+            );
+        }
+
+        self
+    }
+
+    pub unsafe fn set_vertex_input(
+        &mut self,
+        vertex_input_state: &VertexInputState,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_vertex_input(vertex_input_state)?;
+
+        Ok(self.set_vertex_input_unchecked(vertex_input_state))
+    }
+
+    fn validate_set_vertex_input(
+        &self,
+        vertex_input_state: &VertexInputState,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self.device().enabled_features().vertex_input_dynamic_state
+            || self.device().enabled_features().shader_object)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::Feature("vertex_input_dynamic_state")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
+                ]),
+                vuids: &["VUID-vkCmdSetVertexInputEXT-None-08546"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetVertexInputEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        vertex_input_state
+            .validate(self.device())
+            .map_err(|err| err.add_context("vertex_input_state"))?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_vertex_input_unchecked(
+        &mut self,
+        vertex_input_state: &VertexInputState,
+    ) -> &mut Self {
+        let binding_descriptions = vertex_input_state
+            .bindings
+            .iter()
+            .map(
+                |(&binding, binding_desc)| ash::vk::VertexInputBindingDescription2EXT {
+                    binding,
+                    stride: binding_desc.stride,
+                    input_rate: binding_desc.input_rate.into(),
+                    divisor: match binding_desc.input_rate {
+                        VertexInputRate::Vertex => 1,
+                        VertexInputRate::Instance { divisor } => divisor,
+                    },
+                    ..Default::default()
+                },
+            )
+            .collect::<SmallVec<[_; 8]>>();
+        let attribute_descriptions = vertex_input_state
+            .attributes
+            .iter()
+            .map(
+                |(&location, attribute_desc)| ash::vk::VertexInputAttributeDescription2EXT {
+                    location,
+                    binding: attribute_desc.binding,
+                    format: attribute_desc.format.into(),
+                    offset: attribute_desc.offset,
+                    ..Default::default()
+                },
+            )
+            .collect::<SmallVec<[_; 8]>>();
+
+        let fns = self.device().fns();
+
+        if self
+            .device()
+            .enabled_extensions()
+            .ext_vertex_input_dynamic_state
+        {
+            (fns.ext_vertex_input_dynamic_state.cmd_set_vertex_input_ext)(
+                self.handle(),
+                binding_descriptions.len() as u32,
+                binding_descriptions.as_ptr(),
+                attribute_descriptions.len() as u32,
+                attribute_descriptions.as_ptr(),
+            );
+        } else {
+            (fns.ext_shader_object.cmd_set_vertex_input_ext)(
+                self.handle(),
+                binding_descriptions.len() as u32,
+                binding_descriptions.as_ptr(),
+                attribute_descriptions.len() as u32,
+                attribute_descriptions.as_ptr(),
+            );
+        }
+
+        self
+    }
+
     pub unsafe fn set_viewport(
         &mut self,
         first_viewport: u32,
@@ -2935,12 +5383,14 @@ where
         viewports: &[Viewport],
     ) -> Result<(), Box<ValidationError>> {
         if !(self.device().api_version() >= Version::V1_3
-            || self.device().enabled_features().extended_dynamic_state)
+            || self.device().enabled_features().extended_dynamic_state
+            || self.device().enabled_features().shader_object)
         {
             return Err(Box::new(ValidationError {
                 requires_one_of: RequiresOneOf(&[
                     RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
                     RequiresAllOf(&[Requires::Feature("extended_dynamic_state")]),
+                    RequiresAllOf(&[Requires::Feature("shader_object")]),
                 ]),
                 vuids: &["VUID-vkCmdSetViewportWithCount-None-03393"],
                 ..Default::default()
@@ -3006,13 +5456,23 @@ where
                 viewports.len() as u32,
                 viewports.as_ptr(),
             );
-        } else {
+        } else if self
+            .device()
+            .enabled_extensions()
+            .ext_extended_dynamic_state
+        {
             (fns.ext_extended_dynamic_state
                 .cmd_set_viewport_with_count_ext)(
                 self.handle(),
                 viewports.len() as u32,
                 viewports.as_ptr(),
             );
+        } else {
+            (fns.ext_shader_object.cmd_set_viewport_with_count_ext)(
+                self.handle(),
+                viewports.len() as u32,
+                viewports.as_ptr(),
+            );
         }
 
         self
@@ -15,37 +15,57 @@ use crate::{
         auto::{RenderPassState, RenderPassStateType, Resource, ResourceUseRef2},
         sys::UnsafeCommandBufferBuilder,
         AutoCommandBufferBuilder, DispatchIndirectCommand, DrawIndexedIndirectCommand,
-        DrawIndirectCommand, ResourceInCommand, SubpassContents,
+        DrawIndirectCommand, DrawMeshTasksIndirectCommand, MultiDrawIndexedInfo, MultiDrawInfo,
+        ResourceInCommand, SubpassContents, TraceRaysIndirectCommand,
     },
     descriptor_set::{
-        layout::DescriptorType, DescriptorBindingResources, DescriptorBufferInfo,
-        DescriptorImageViewInfo,
+        layout::{DescriptorBindingFlags, DescriptorType},
+        DescriptorBindingResources, DescriptorBufferInfo, DescriptorImageViewInfo,
     },
-    device::{DeviceOwned, QueueFlags},
+    device::{Device, DeviceOwned, QueueFlags},
     format::{FormatFeatures, NumericType},
     image::{sampler::Sampler, view::ImageView, ImageAspects, ImageLayout, SampleCount},
     pipeline::{
         graphics::{
-            input_assembly::PrimitiveTopology, subpass::PipelineSubpassType,
-            vertex_input::VertexInputRate,
+            color_blend::BlendFactor,
+            input_assembly::PrimitiveTopology,
+            rasterization::PolygonMode,
+            subpass::PipelineSubpassType,
+            vertex_input::{VertexInputBindingDescription, VertexInputRate, VertexInputState},
         },
-        DynamicState, GraphicsPipeline, Pipeline, PipelineLayout,
+        ray_tracing::{RayTracingPipeline, ShaderBindingTable, ShaderBindingTableAddresses},
+        ComputePipeline, DynamicState, GraphicsPipeline, Pipeline, PipelineBindPoint,
+        PipelineCreateFlags, PipelineLayout,
+    },
+    shader::{
+        object::{ShaderCreateFlags, ShaderObject},
+        DescriptorBindingRequirements, DescriptorIdentifier, ShaderStage, ShaderStages,
     },
-    shader::{DescriptorBindingRequirements, DescriptorIdentifier, ShaderStage, ShaderStages},
     sync::{PipelineStageAccess, PipelineStageAccessFlags},
-    DeviceSize, Requires, RequiresAllOf, RequiresOneOf, ValidationError, VulkanObject,
+    DeviceSize, Requires, RequiresAllOf, RequiresOneOf, ValidationError, Version, VulkanObject,
 };
-use std::{mem::size_of, sync::Arc};
+use ahash::HashMap;
+use smallvec::SmallVec;
+use std::{collections::hash_map::Entry, mem::size_of, sync::Arc};
 
 macro_rules! vuids {
     ($vuid_type:ident, $($id:literal),+ $(,)?) => {
         match $vuid_type {
             VUIDType::Dispatch => &[$(concat!("VUID-vkCmdDispatch-", $id)),+],
+            VUIDType::DispatchBase => &[$(concat!("VUID-vkCmdDispatchBase-", $id)),+],
             VUIDType::DispatchIndirect => &[$(concat!("VUID-vkCmdDispatchIndirect-", $id)),+],
             VUIDType::Draw => &[$(concat!("VUID-vkCmdDraw-", $id)),+],
             VUIDType::DrawIndirect => &[$(concat!("VUID-vkCmdDrawIndirect-", $id)),+],
+            VUIDType::DrawIndirectCount => &[$(concat!("VUID-vkCmdDrawIndirectCount-", $id)),+],
             VUIDType::DrawIndexed => &[$(concat!("VUID-vkCmdDrawIndexed-", $id)),+],
             VUIDType::DrawIndexedIndirect => &[$(concat!("VUID-vkCmdDrawIndexedIndirect-", $id)),+],
+            VUIDType::DrawIndexedIndirectCount => &[$(concat!("VUID-vkCmdDrawIndexedIndirectCount-", $id)),+],
+            VUIDType::DrawMultiEXT => &[$(concat!("VUID-vkCmdDrawMultiEXT-", $id)),+],
+            VUIDType::DrawMultiIndexedEXT => &[$(concat!("VUID-vkCmdDrawMultiIndexedEXT-", $id)),+],
+            VUIDType::DrawMeshTasksEXT => &[$(concat!("VUID-vkCmdDrawMeshTasksEXT-", $id)),+],
+            VUIDType::DrawMeshTasksIndirectEXT => &[$(concat!("VUID-vkCmdDrawMeshTasksIndirectEXT-", $id)),+],
+            VUIDType::TraceRaysKHR => &[$(concat!("VUID-vkCmdTraceRaysKHR-", $id)),+],
+            VUIDType::TraceRaysIndirectKHR => &[$(concat!("VUID-vkCmdTraceRaysIndirectKHR-", $id)),+],
         }
     };
 }
@@ -62,6 +82,9 @@ where
     /// A compute pipeline must have been bound using
     /// [`bind_pipeline_compute`](Self::bind_pipeline_compute). Any resources used by the compute
     /// pipeline, such as descriptor sets, must have been set beforehand.
+    ///
+    /// Instead of a pipeline, a compute shader object can be bound using
+    /// [`bind_shaders`](Self::bind_shaders).
     pub fn dispatch(&mut self, group_counts: [u32; 3]) -> Result<&mut Self, Box<ValidationError>> {
         self.validate_dispatch(group_counts)?;
 
@@ -79,41 +102,20 @@ where
             }));
         }
 
-        let pipeline = self
-            .builder_state
-            .pipeline_compute
-            .as_ref()
-            .ok_or_else(|| {
-                Box::new(ValidationError {
-                    problem: "no compute pipeline is currently bound".into(),
-                    vuids: &["VUID-vkCmdDispatch-None-08606"],
-                    ..Default::default()
-                })
-            })?
-            .as_ref();
-
         const VUID_TYPE: VUIDType = VUIDType::Dispatch;
-        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+        self.validate_bound_compute(VUID_TYPE)?;
 
         Ok(())
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
     pub unsafe fn dispatch_unchecked(&mut self, group_counts: [u32; 3]) -> &mut Self {
-        let pipeline = self
-            .builder_state
-            .pipeline_compute
-            .as_ref()
-            .unwrap()
-            .as_ref();
-
-        let mut used_resources = Vec::new();
-        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
+        let bound_resources = self.bound_resources(PipelineBindPoint::Compute);
 
-        self.add_command(
+        self.add_command_with_bound_resources(
             "dispatch",
-            used_resources,
+            Some(bound_resources),
+            Vec::new(),
             move |out: &mut UnsafeCommandBufferBuilder<A>| {
                 out.dispatch_unchecked(group_counts);
             },
@@ -122,12 +124,123 @@ where
         self
     }
 
-    /// Perform multiple compute operations using a compute pipeline. One dispatch is performed for
-    /// each [`DispatchIndirectCommand`] struct in `indirect_buffer`.
+    /// Perform a single compute operation using a compute pipeline, with a nonzero base for the
+    /// workgroup IDs.
+    ///
+    /// This is the same as [`dispatch`](Self::dispatch), except that the `WorkgroupId` built-in
+    /// in the shader starts at `base_group` instead of zero. This makes it possible to split a
+    /// large dispatch into several smaller ones, each processing a part of the workgroups.
+    /// The sum of `base_group` and `group_counts` must not exceed the
+    /// [`max_compute_work_group_count`](crate::device::Properties::max_compute_work_group_count)
+    /// limit.
+    ///
+    /// If `base_group` is not zero, then the bound compute pipeline must have been created with
+    /// [`PipelineCreateFlags::DISPATCH_BASE`].
+    ///
+    /// A compute pipeline must have been bound using
+    /// [`bind_pipeline_compute`](Self::bind_pipeline_compute). Any resources used by the compute
+    /// pipeline, such as descriptor sets, must have been set beforehand.
+    ///
+    /// Instead of a pipeline, a compute shader object can be bound using
+    /// [`bind_shaders`](Self::bind_shaders).
+    ///
+    /// The device API version must be at least 1.1, or the
+    /// [`khr_device_group`](crate::device::DeviceExtensions::khr_device_group) extension must be
+    /// enabled on the device.
+    pub fn dispatch_base(
+        &mut self,
+        base_group: [u32; 3],
+        group_counts: [u32; 3],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_dispatch_base(base_group, group_counts)?;
+
+        unsafe { Ok(self.dispatch_base_unchecked(base_group, group_counts)) }
+    }
+
+    fn validate_dispatch_base(
+        &self,
+        base_group: [u32; 3],
+        group_counts: [u32; 3],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_dispatch_base(base_group, group_counts)?;
+
+        if self.builder_state.render_pass.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "a render pass instance is active".into(),
+                vuids: &["VUID-vkCmdDispatchBase-renderpass"],
+                ..Default::default()
+            }));
+        }
+
+        const VUID_TYPE: VUIDType = VUIDType::DispatchBase;
+        let pipeline = self.validate_bound_compute(VUID_TYPE)?;
+
+        if base_group != [0; 3] {
+            if let Some(pipeline) = pipeline {
+                if !pipeline
+                    .flags()
+                    .intersects(PipelineCreateFlags::DISPATCH_BASE)
+                {
+                    return Err(Box::new(ValidationError {
+                        problem: "`base_group` is not `[0; 3]`, but the currently bound compute \
+                            pipeline was not created with `PipelineCreateFlags::DISPATCH_BASE`"
+                            .into(),
+                        vuids: &["VUID-vkCmdDispatchBase-baseGroupX-00427"],
+                        ..Default::default()
+                    }));
+                }
+            } else if self
+                .bound_shader_objects_iter(PipelineBindPoint::Compute)
+                .any(|shader| !shader.flags().intersects(ShaderCreateFlags::DISPATCH_BASE))
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "`base_group` is not `[0; 3]`, but the currently bound compute \
+                        shader object was not created with `ShaderCreateFlags::DISPATCH_BASE`"
+                        .into(),
+                    vuids: &["VUID-vkCmdDispatchBase-baseGroupX-09427"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn dispatch_base_unchecked(
+        &mut self,
+        base_group: [u32; 3],
+        group_counts: [u32; 3],
+    ) -> &mut Self {
+        let bound_resources = self.bound_resources(PipelineBindPoint::Compute);
+
+        self.add_command_with_bound_resources(
+            "dispatch_base",
+            Some(bound_resources),
+            Vec::new(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.dispatch_base_unchecked(base_group, group_counts);
+            },
+        );
+
+        self
+    }
+
+    /// Perform a single compute operation using a compute pipeline, reading the number of
+    /// workgroups from a buffer.
+    ///
+    /// The number of workgroups is read from the first [`DispatchIndirectCommand`] struct in
+    /// `indirect_buffer` when the command is executed, so it can be written by previous commands,
+    /// such as a compute shader that decides how much work to do. The indirect buffer is
+    /// synchronized with those commands like any other resource.
     ///
     /// A compute pipeline must have been bound using
     /// [`bind_pipeline_compute`](Self::bind_pipeline_compute). Any resources used by the compute
     /// pipeline, such as descriptor sets, must have been set beforehand.
+    ///
+    /// Instead of a pipeline, a compute shader object can be bound using
+    /// [`bind_shaders`](Self::bind_shaders).
     pub fn dispatch_indirect(
         &mut self,
         indirect_buffer: Subbuffer<[DispatchIndirectCommand]>,
@@ -151,22 +264,8 @@ where
             }));
         }
 
-        let pipeline = self
-            .builder_state
-            .pipeline_compute
-            .as_ref()
-            .ok_or_else(|| {
-                Box::new(ValidationError {
-                    problem: "no compute pipeline is currently bound".into(),
-                    vuids: &["VUID-vkCmdDispatchIndirect-None-08606"],
-                    ..Default::default()
-                })
-            })?
-            .as_ref();
-
         const VUID_TYPE: VUIDType = VUIDType::DispatchIndirect;
-        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+        self.validate_bound_compute(VUID_TYPE)?;
 
         Ok(())
     }
@@ -176,19 +275,14 @@ where
         &mut self,
         indirect_buffer: Subbuffer<[DispatchIndirectCommand]>,
     ) -> &mut Self {
-        let pipeline = self
-            .builder_state
-            .pipeline_compute
-            .as_ref()
-            .unwrap()
-            .as_ref();
+        let bound_resources = self.bound_resources(PipelineBindPoint::Compute);
 
         let mut used_resources = Vec::new();
-        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
         self.add_indirect_buffer_resources(&mut used_resources, indirect_buffer.as_bytes());
 
-        self.add_command(
-            "dispatch",
+        self.add_command_with_bound_resources(
+            "dispatch_indirect",
+            Some(bound_resources),
             used_resources,
             move |out: &mut UnsafeCommandBufferBuilder<A>| {
                 out.dispatch_indirect_unchecked(&indirect_buffer);
@@ -209,6 +303,9 @@ where
     /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
     /// beforehand. If the bound graphics pipeline uses vertex buffers, then the provided vertex and
     /// instance ranges must be in range of the bound vertex buffers.
+    ///
+    /// Instead of a pipeline, graphics shader objects can be bound using
+    /// [`bind_shaders`](Self::bind_shaders).
     pub fn draw(
         &mut self,
         vertex_count: u32,
@@ -241,30 +338,11 @@ where
             })
         })?;
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .ok_or_else(|| {
-                Box::new(ValidationError {
-                    problem: "no graphics pipeline is currently bound".into(),
-                    vuids: &["VUID-vkCmdDraw-None-08606"],
-                    ..Default::default()
-                })
-            })?
-            .as_ref();
-
         const VUID_TYPE: VUIDType = VUIDType::Draw;
-        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
-        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
-        self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
+        let graphics = self.validate_bound_graphics(VUID_TYPE, render_pass_state)?;
+        let vertex_input_state = self.validate_graphics_vertex_buffers(VUID_TYPE, graphics)?;
 
-        let view_mask = match pipeline.subpass() {
-            PipelineSubpassType::BeginRenderPass(subpass) => subpass.render_pass().views_used(),
-            PipelineSubpassType::BeginRendering(rendering_info) => rendering_info.view_mask,
-        };
+        let view_mask = graphics.view_mask(render_pass_state);
 
         if view_mask != 0 {
             let properties = self.device().physical_device().properties();
@@ -283,16 +361,16 @@ where
             }
         }
 
-        for (&binding_num, binding_desc) in &pipeline.vertex_input_state().bindings {
+        for (&binding_num, binding_desc) in &vertex_input_state.bindings {
             let vertex_buffer = &self.builder_state.vertex_buffers[&binding_num];
+            let stride = self.vertex_input_binding_stride(graphics, binding_num, binding_desc);
 
             // Per spec:
             // https://registry.khronos.org/vulkan/specs/1.3-extensions/html/chap22.html#fxvertex-input-address-calculation
             match binding_desc.input_rate {
                 VertexInputRate::Vertex => {
-                    let max_vertex_offset = (first_vertex as DeviceSize
-                        + vertex_count as DeviceSize)
-                        * binding_desc.stride as DeviceSize;
+                    let max_vertex_offset =
+                        (first_vertex as DeviceSize + vertex_count as DeviceSize) * stride;
 
                     if max_vertex_offset > vertex_buffer.size() {
                         return Err(Box::new(ValidationError {
@@ -311,11 +389,11 @@ where
                 }
                 VertexInputRate::Instance { divisor } => {
                     let max_vertex_offset = if divisor == 0 {
-                        (first_instance as DeviceSize + 1) * binding_desc.stride as DeviceSize
+                        (first_instance as DeviceSize + 1) * stride
                     } else {
                         (first_instance as DeviceSize
                             + instance_count as DeviceSize / divisor as DeviceSize)
-                            * binding_desc.stride as DeviceSize
+                            * stride
                     };
 
                     if max_vertex_offset > vertex_buffer.size() {
@@ -353,20 +431,12 @@ where
             state.pipeline_used = true;
         }
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .unwrap()
-            .as_ref();
-
-        let mut used_resources = Vec::new();
-        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
-        self.add_vertex_buffers_resources(&mut used_resources, pipeline);
+        let bound_resources = self.bound_resources(PipelineBindPoint::Graphics);
 
-        self.add_command(
+        self.add_command_with_bound_resources(
             "draw",
-            used_resources,
+            Some(bound_resources),
+            Vec::new(),
             move |out: &mut UnsafeCommandBufferBuilder<A>| {
                 out.draw_unchecked(vertex_count, instance_count, first_vertex, first_instance);
             },
@@ -375,6 +445,185 @@ where
         self
     }
 
+    /// Perform multiple draw operations using a graphics pipeline, with the vertex ranges
+    /// provided directly.
+    ///
+    /// One draw is performed for each element of `vertex_info`, each using the same
+    /// `instance_count` and `first_instance`. This is equivalent to calling [`draw`](Self::draw)
+    /// once for each element, but if the [`multi_draw`](crate::device::Features::multi_draw)
+    /// feature is enabled on the device, all draws are recorded as a single command, which has
+    /// less overhead when there are many small draws. Otherwise, a separate draw command is
+    /// recorded for each element.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
+    /// beforehand. If the bound graphics pipeline uses vertex buffers, then the provided vertex and
+    /// instance ranges must be in range of the bound vertex buffers.
+    ///
+    /// Instead of a pipeline, graphics shader objects can be bound using
+    /// [`bind_shaders`](Self::bind_shaders).
+    pub fn draw_multi(
+        &mut self,
+        vertex_info: &[MultiDrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw_multi(vertex_info, instance_count, first_instance)?;
+
+        unsafe { Ok(self.draw_multi_unchecked(vertex_info, instance_count, first_instance)) }
+    }
+
+    fn validate_draw_multi(
+        &self,
+        vertex_info: &[MultiDrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        if self.device().enabled_features().multi_draw {
+            self.inner
+                .validate_draw_multi(vertex_info, instance_count, first_instance)?;
+        } else {
+            self.inner
+                .validate_draw(0, instance_count, 0, first_instance)?;
+        }
+
+        let render_pass_state = self.builder_state.render_pass.as_ref().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "a render pass instance is not active".into(),
+                vuids: &["VUID-vkCmdDrawMultiEXT-renderpass"],
+                ..Default::default()
+            })
+        })?;
+
+        const VUID_TYPE: VUIDType = VUIDType::DrawMultiEXT;
+        let graphics = self.validate_bound_graphics(VUID_TYPE, render_pass_state)?;
+        let vertex_input_state = self.validate_graphics_vertex_buffers(VUID_TYPE, graphics)?;
+
+        let view_mask = graphics.view_mask(render_pass_state);
+
+        if view_mask != 0 {
+            let properties = self.device().physical_device().properties();
+
+            if (first_instance + instance_count).saturating_sub(1)
+                > properties.max_multiview_instance_index.unwrap_or(0)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "the current render pass instance has a nonzero view mask, but \
+                        `first_instance + instance_count - 1` is greater than the \
+                        `max_multiview_instance_index` limit"
+                        .into(),
+                    vuids: &["VUID-vkCmdDrawMultiEXT-maxMultiviewInstanceIndex-02688"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        // Only the draw that reaches the furthest into the vertex buffers needs to be checked.
+        let max_vertex = vertex_info
+            .iter()
+            .map(|info| info.first_vertex as DeviceSize + info.vertex_count as DeviceSize)
+            .max()
+            .unwrap_or(0);
+
+        for (&binding_num, binding_desc) in &vertex_input_state.bindings {
+            let vertex_buffer = &self.builder_state.vertex_buffers[&binding_num];
+            let stride = self.vertex_input_binding_stride(graphics, binding_num, binding_desc);
+
+            // Per spec:
+            // https://registry.khronos.org/vulkan/specs/1.3-extensions/html/chap22.html#fxvertex-input-address-calculation
+            match binding_desc.input_rate {
+                VertexInputRate::Vertex => {
+                    let max_vertex_offset = max_vertex * stride;
+
+                    if max_vertex_offset > vertex_buffer.size() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the size of the vertex buffer bound to binding {} is less \
+                                than the minimum size required, for the provided \
+                                `vertex_info` values, and the vertex \
+                                input state bindings of the currently bound graphics pipeline",
+                                binding_num
+                            )
+                            .into(),
+                            vuids: &["VUID-vkCmdDrawMultiEXT-None-02721"],
+                            ..Default::default()
+                        }));
+                    }
+                }
+                VertexInputRate::Instance { divisor } => {
+                    if vertex_info.is_empty() {
+                        continue;
+                    }
+
+                    let max_vertex_offset = if divisor == 0 {
+                        (first_instance as DeviceSize + 1) * stride
+                    } else {
+                        (first_instance as DeviceSize
+                            + instance_count as DeviceSize / divisor as DeviceSize)
+                            * stride
+                    };
+
+                    if max_vertex_offset > vertex_buffer.size() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the size of the vertex buffer bound to binding {} is less \
+                                than the minimum size required, for the provided \
+                                `first_instance` and `instance_count` values, and the vertex \
+                                input state bindings of the currently bound graphics pipeline",
+                                binding_num
+                            )
+                            .into(),
+                            vuids: &["VUID-vkCmdDrawMultiEXT-None-02721"],
+                            ..Default::default()
+                        }));
+                    }
+                }
+            };
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_multi_unchecked(
+        &mut self,
+        vertex_info: &[MultiDrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> &mut Self {
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        let bound_resources = self.bound_resources(PipelineBindPoint::Graphics);
+        let vertex_info = vertex_info.to_vec();
+
+        self.add_command_with_bound_resources(
+            "draw_multi",
+            Some(bound_resources),
+            Vec::new(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                if out.device().enabled_features().multi_draw {
+                    out.draw_multi_unchecked(&vertex_info, instance_count, first_instance);
+                } else {
+                    for info in &vertex_info {
+                        out.draw_unchecked(
+                            info.vertex_count,
+                            instance_count,
+                            info.first_vertex,
+                            first_instance,
+                        );
+                    }
+                }
+            },
+        );
+
+        self
+    }
+
     /// Perform multiple draw operations using a graphics pipeline.
     ///
     /// One draw is performed for each [`DrawIndirectCommand`] struct in `indirect_buffer`.
@@ -390,6 +639,9 @@ where
     /// beforehand. If the bound graphics pipeline uses vertex buffers, then the vertex and instance
     /// ranges of each `DrawIndirectCommand` in the indirect buffer must be in range of the bound
     /// vertex buffers.
+    ///
+    /// Instead of a pipeline, graphics shader objects can be bound using
+    /// [`bind_shaders`](Self::bind_shaders).
     pub fn draw_indirect(
         &mut self,
         indirect_buffer: Subbuffer<[DrawIndirectCommand]>,
@@ -418,25 +670,9 @@ where
             })
         })?;
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .ok_or_else(|| {
-                Box::new(ValidationError {
-                    problem: "no graphics pipeline is currently bound".into(),
-                    vuids: &["VUID-vkCmdDrawIndirect-None-08606"],
-                    ..Default::default()
-                })
-            })?
-            .as_ref();
-
         const VUID_TYPE: VUIDType = VUIDType::DrawIndirect;
-        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
-        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
-        self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
+        let graphics = self.validate_bound_graphics(VUID_TYPE, render_pass_state)?;
+        self.validate_graphics_vertex_buffers(VUID_TYPE, graphics)?;
 
         Ok(())
     }
@@ -454,20 +690,14 @@ where
             state.pipeline_used = true;
         }
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .unwrap()
-            .as_ref();
+        let bound_resources = self.bound_resources(PipelineBindPoint::Graphics);
 
         let mut used_resources = Vec::new();
-        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
-        self.add_vertex_buffers_resources(&mut used_resources, pipeline);
         self.add_indirect_buffer_resources(&mut used_resources, indirect_buffer.as_bytes());
 
-        self.add_command(
+        self.add_command_with_bound_resources(
             "draw_indirect",
+            Some(bound_resources),
             used_resources,
             move |out: &mut UnsafeCommandBufferBuilder<A>| {
                 out.draw_indirect_unchecked(&indirect_buffer, draw_count, stride);
@@ -477,9 +707,120 @@ where
         self
     }
 
-    /// Perform a single draw operation using a graphics pipeline, using an index buffer.
+    /// Perform multiple draw operations using a graphics pipeline, reading the number of draws
+    /// from a buffer.
     ///
-    /// The parameters specify the first index and the number of indices in the index buffer that
+    /// One draw is performed for each [`DrawIndirectCommand`] struct in `indirect_buffer`, up to
+    /// the number that is read from `count_buffer` when the command is executed. The maximum
+    /// number of draws is the length of `indirect_buffer`. The number that is read from
+    /// `count_buffer` must not be greater than the
+    /// [`max_draw_indirect_count`](crate::device::Properties::max_draw_indirect_count) limit.
+    ///
+    /// The [`draw_indirect_count`](crate::device::Features::draw_indirect_count) feature must be
+    /// enabled on the device.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
+    /// beforehand. If the bound graphics pipeline uses vertex buffers, then the vertex and instance
+    /// ranges of each `DrawIndirectCommand` in the indirect buffer must be in range of the bound
+    /// vertex buffers.
+    ///
+    /// Instead of a pipeline, graphics shader objects can be bound using
+    /// [`bind_shaders`](Self::bind_shaders).
+    pub fn draw_indirect_count(
+        &mut self,
+        indirect_buffer: Subbuffer<[DrawIndirectCommand]>,
+        count_buffer: Subbuffer<u32>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        let max_draw_count = indirect_buffer.len() as u32;
+        let stride = size_of::<DrawIndirectCommand>() as u32;
+        self.validate_draw_indirect_count(
+            indirect_buffer.as_bytes(),
+            count_buffer.as_bytes(),
+            max_draw_count,
+            stride,
+        )?;
+
+        unsafe {
+            Ok(self.draw_indirect_count_unchecked(
+                indirect_buffer,
+                count_buffer,
+                max_draw_count,
+                stride,
+            ))
+        }
+    }
+
+    fn validate_draw_indirect_count(
+        &self,
+        indirect_buffer: &Subbuffer<[u8]>,
+        count_buffer: &Subbuffer<[u8]>,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_draw_indirect_count(
+            indirect_buffer,
+            count_buffer,
+            max_draw_count,
+            stride,
+        )?;
+
+        let render_pass_state = self.builder_state.render_pass.as_ref().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "a render pass instance is not active".into(),
+                vuids: &["VUID-vkCmdDrawIndirectCount-renderpass"],
+                ..Default::default()
+            })
+        })?;
+
+        const VUID_TYPE: VUIDType = VUIDType::DrawIndirectCount;
+        let graphics = self.validate_bound_graphics(VUID_TYPE, render_pass_state)?;
+        self.validate_graphics_vertex_buffers(VUID_TYPE, graphics)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_indirect_count_unchecked(
+        &mut self,
+        indirect_buffer: Subbuffer<[DrawIndirectCommand]>,
+        count_buffer: Subbuffer<u32>,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> &mut Self {
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        let bound_resources = self.bound_resources(PipelineBindPoint::Graphics);
+
+        let mut used_resources = Vec::new();
+        self.add_indirect_buffer_resources(&mut used_resources, indirect_buffer.as_bytes());
+        self.add_count_buffer_resources(&mut used_resources, count_buffer.as_bytes());
+
+        self.add_command_with_bound_resources(
+            "draw_indirect_count",
+            Some(bound_resources),
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.draw_indirect_count_unchecked(
+                    &indirect_buffer,
+                    &count_buffer,
+                    max_draw_count,
+                    stride,
+                );
+            },
+        );
+
+        self
+    }
+
+    /// Perform a single draw operation using a graphics pipeline, using an index buffer.
+    ///
+    /// The parameters specify the first index and the number of indices in the index buffer that
     /// should be used, and the first instance and number of instances. For non-instanced drawing,
     /// specify `instance_count` as 1 and `first_instance` as 0. The `vertex_offset` is a constant
     /// value that should be added to each index in the index buffer to produce the final vertex
@@ -495,6 +836,9 @@ where
     /// beforehand. If the bound graphics pipeline uses vertex buffers, then the provided instance
     /// range must be in range of the bound vertex buffers. The vertex indices in the index buffer
     /// must be in range of the bound vertex buffers.
+    ///
+    /// Instead of a pipeline, graphics shader objects can be bound using
+    /// [`bind_shaders`](Self::bind_shaders).
     pub fn draw_indexed(
         &mut self,
         index_count: u32,
@@ -546,25 +890,9 @@ where
             })
         })?;
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .ok_or_else(|| {
-                Box::new(ValidationError {
-                    problem: "no graphics pipeline is currently bound".into(),
-                    vuids: &["VUID-vkCmdDrawIndexed-None-08606"],
-                    ..Default::default()
-                })
-            })?
-            .as_ref();
-
         const VUID_TYPE: VUIDType = VUIDType::DrawIndexed;
-        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
-        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
-        self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
+        let graphics = self.validate_bound_graphics(VUID_TYPE, render_pass_state)?;
+        let vertex_input_state = self.validate_graphics_vertex_buffers(VUID_TYPE, graphics)?;
 
         let index_buffer = self.builder_state.index_buffer.as_ref().ok_or_else(|| {
             Box::new(ValidationError {
@@ -595,10 +923,7 @@ where
             }
         }
 
-        let view_mask = match pipeline.subpass() {
-            PipelineSubpassType::BeginRenderPass(subpass) => subpass.render_pass().views_used(),
-            PipelineSubpassType::BeginRendering(rendering_info) => rendering_info.view_mask,
-        };
+        let view_mask = graphics.view_mask(render_pass_state);
 
         if view_mask != 0 {
             let properties = self.device().physical_device().properties();
@@ -617,8 +942,9 @@ where
             }
         }
 
-        for (&binding_num, binding_desc) in &pipeline.vertex_input_state().bindings {
+        for (&binding_num, binding_desc) in &vertex_input_state.bindings {
             let vertex_buffer = &self.builder_state.vertex_buffers[&binding_num];
+            let stride = self.vertex_input_binding_stride(graphics, binding_num, binding_desc);
 
             // Per spec:
             // https://registry.khronos.org/vulkan/specs/1.3-extensions/html/chap22.html#fxvertex-input-address-calculation
@@ -626,11 +952,11 @@ where
                 VertexInputRate::Vertex => (),
                 VertexInputRate::Instance { divisor } => {
                     let max_vertex_offset = if divisor == 0 {
-                        (first_instance as DeviceSize + 1) * binding_desc.stride as DeviceSize
+                        (first_instance as DeviceSize + 1) * stride
                     } else {
                         (first_instance as DeviceSize
                             + instance_count as DeviceSize / divisor as DeviceSize)
-                            * binding_desc.stride as DeviceSize
+                            * stride
                     };
 
                     if max_vertex_offset > vertex_buffer.size() {
@@ -669,20 +995,14 @@ where
             state.pipeline_used = true;
         }
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .unwrap()
-            .as_ref();
+        let bound_resources = self.bound_resources(PipelineBindPoint::Graphics);
 
         let mut used_resources = Vec::new();
-        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
-        self.add_vertex_buffers_resources(&mut used_resources, pipeline);
         self.add_index_buffer_resources(&mut used_resources);
 
-        self.add_command(
+        self.add_command_with_bound_resources(
             "draw_indexed",
+            Some(bound_resources),
             used_resources,
             move |out: &mut UnsafeCommandBufferBuilder<A>| {
                 out.draw_indexed_unchecked(
@@ -698,6 +1018,201 @@ where
         self
     }
 
+    /// Perform multiple draw operations using a graphics pipeline, using an index buffer, with the
+    /// index ranges provided directly.
+    ///
+    /// One draw is performed for each element of `index_info`, each using the same
+    /// `instance_count` and `first_instance`. This is equivalent to calling
+    /// [`draw_indexed`](Self::draw_indexed) once for each element, but if the
+    /// [`multi_draw`](crate::device::Features::multi_draw) feature is enabled on the device, all
+    /// draws are recorded as a single command, which has less overhead when there are many small
+    /// draws. Otherwise, a separate draw command is recorded for each element.
+    ///
+    /// An index buffer must have been bound using
+    /// [`bind_index_buffer`](Self::bind_index_buffer), and the provided index ranges must be in
+    /// range of the bound index buffer.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
+    /// beforehand. If the bound graphics pipeline uses vertex buffers, then the provided instance
+    /// range must be in range of the bound vertex buffers. The vertex indices in the index buffer
+    /// must be in range of the bound vertex buffers.
+    ///
+    /// Instead of a pipeline, graphics shader objects can be bound using
+    /// [`bind_shaders`](Self::bind_shaders).
+    pub fn draw_multi_indexed(
+        &mut self,
+        index_info: &[MultiDrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw_multi_indexed(index_info, instance_count, first_instance)?;
+
+        unsafe { Ok(self.draw_multi_indexed_unchecked(index_info, instance_count, first_instance)) }
+    }
+
+    fn validate_draw_multi_indexed(
+        &self,
+        index_info: &[MultiDrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        if self.device().enabled_features().multi_draw {
+            self.inner
+                .validate_draw_multi_indexed(index_info, instance_count, first_instance)?;
+        } else {
+            self.inner
+                .validate_draw_indexed(0, instance_count, 0, 0, first_instance)?;
+        }
+
+        let render_pass_state = self.builder_state.render_pass.as_ref().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "a render pass instance is not active".into(),
+                vuids: &["VUID-vkCmdDrawMultiIndexedEXT-renderpass"],
+                ..Default::default()
+            })
+        })?;
+
+        const VUID_TYPE: VUIDType = VUIDType::DrawMultiIndexedEXT;
+        let graphics = self.validate_bound_graphics(VUID_TYPE, render_pass_state)?;
+        let vertex_input_state = self.validate_graphics_vertex_buffers(VUID_TYPE, graphics)?;
+
+        let index_buffer = self.builder_state.index_buffer.as_ref().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "no index buffer is currently bound".into(),
+                vuids: &["VUID-vkCmdDrawMultiIndexedEXT-None-07312"],
+                ..Default::default()
+            })
+        })?;
+
+        let index_buffer_bytes = index_buffer.as_bytes();
+
+        if !self.device().enabled_features().robust_buffer_access2 {
+            for (index, info) in index_info.iter().enumerate() {
+                if index_buffer.index_type().size()
+                    * (info.first_index as DeviceSize + info.index_count as DeviceSize)
+                    > index_buffer_bytes.size()
+                {
+                    return Err(Box::new(ValidationError {
+                        context: format!("index_info[{}]", index).into(),
+                        problem: "`first_index + index_count`, \
+                            multiplied by the size of the indices in the bound index buffer, \
+                            is greater than the size of the bound index buffer"
+                            .into(),
+                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                            "robust_buffer_access2",
+                        )])]),
+                        vuids: &["VUID-vkCmdDrawMultiIndexedEXT-robustBufferAccess2-07825"],
+                    }));
+                }
+            }
+        }
+
+        let view_mask = graphics.view_mask(render_pass_state);
+
+        if view_mask != 0 {
+            let properties = self.device().physical_device().properties();
+
+            if (first_instance + instance_count).saturating_sub(1)
+                > properties.max_multiview_instance_index.unwrap_or(0)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "the current render pass instance has a nonzero view mask, but \
+                        `first_instance + instance_count - 1` is greater than the \
+                        `max_multiview_instance_index` limit"
+                        .into(),
+                    vuids: &["VUID-vkCmdDrawMultiIndexedEXT-maxMultiviewInstanceIndex-02688"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if !index_info.is_empty() {
+            for (&binding_num, binding_desc) in &vertex_input_state.bindings {
+                let vertex_buffer = &self.builder_state.vertex_buffers[&binding_num];
+                let stride = self.vertex_input_binding_stride(graphics, binding_num, binding_desc);
+
+                // Per spec:
+                // https://registry.khronos.org/vulkan/specs/1.3-extensions/html/chap22.html#fxvertex-input-address-calculation
+                match binding_desc.input_rate {
+                    VertexInputRate::Vertex => (),
+                    VertexInputRate::Instance { divisor } => {
+                        let max_vertex_offset = if divisor == 0 {
+                            (first_instance as DeviceSize + 1) * stride
+                        } else {
+                            (first_instance as DeviceSize
+                                + instance_count as DeviceSize / divisor as DeviceSize)
+                                * stride
+                        };
+
+                        if max_vertex_offset > vertex_buffer.size() {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "the size of the vertex buffer bound to binding {} is less \
+                                    than the minimum size required, for the provided \
+                                    `first_instance` and `instance_count` values, and the \
+                                    vertex input state bindings of the currently bound \
+                                    graphics pipeline",
+                                    binding_num
+                                )
+                                .into(),
+                                vuids: &["VUID-vkCmdDrawMultiIndexedEXT-None-02721"],
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                };
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_multi_indexed_unchecked(
+        &mut self,
+        index_info: &[MultiDrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> &mut Self {
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        let bound_resources = self.bound_resources(PipelineBindPoint::Graphics);
+
+        let mut used_resources = Vec::new();
+        self.add_index_buffer_resources(&mut used_resources);
+
+        let index_info = index_info.to_vec();
+
+        self.add_command_with_bound_resources(
+            "draw_multi_indexed",
+            Some(bound_resources),
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                if out.device().enabled_features().multi_draw {
+                    out.draw_multi_indexed_unchecked(&index_info, instance_count, first_instance);
+                } else {
+                    for info in &index_info {
+                        out.draw_indexed_unchecked(
+                            info.index_count,
+                            instance_count,
+                            info.first_index,
+                            info.vertex_offset,
+                            first_instance,
+                        );
+                    }
+                }
+            },
+        );
+
+        self
+    }
+
     /// Perform multiple draw operations using a graphics pipeline, using an index buffer.
     ///
     /// One draw is performed for each [`DrawIndexedIndirectCommand`] struct in `indirect_buffer`.
@@ -718,6 +1233,9 @@ where
     /// beforehand. If the bound graphics pipeline uses vertex buffers, then the instance ranges of
     /// each `DrawIndexedIndirectCommand` in the indirect buffer must be in range of the bound
     /// vertex buffers.
+    ///
+    /// Instead of a pipeline, graphics shader objects can be bound using
+    /// [`bind_shaders`](Self::bind_shaders).
     pub fn draw_indexed_indirect(
         &mut self,
         indirect_buffer: Subbuffer<[DrawIndexedIndirectCommand]>,
@@ -746,25 +1264,9 @@ where
             })
         })?;
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .ok_or_else(|| {
-                Box::new(ValidationError {
-                    problem: "no graphics pipeline is currently bound".into(),
-                    vuids: &["VUID-vkCmdDrawIndexedIndirect-None-08606"],
-                    ..Default::default()
-                })
-            })?
-            .as_ref();
-
         const VUID_TYPE: VUIDType = VUIDType::DrawIndexedIndirect;
-        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
-        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
-        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
-        self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
+        let graphics = self.validate_bound_graphics(VUID_TYPE, render_pass_state)?;
+        self.validate_graphics_vertex_buffers(VUID_TYPE, graphics)?;
 
         let _index_buffer = self.builder_state.index_buffer.as_ref().ok_or_else(|| {
             Box::new(ValidationError {
@@ -790,21 +1292,15 @@ where
             state.pipeline_used = true;
         }
 
-        let pipeline = self
-            .builder_state
-            .pipeline_graphics
-            .as_ref()
-            .unwrap()
-            .as_ref();
+        let bound_resources = self.bound_resources(PipelineBindPoint::Graphics);
 
         let mut used_resources = Vec::new();
-        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
-        self.add_vertex_buffers_resources(&mut used_resources, pipeline);
         self.add_index_buffer_resources(&mut used_resources);
         self.add_indirect_buffer_resources(&mut used_resources, indirect_buffer.as_bytes());
 
-        self.add_command(
+        self.add_command_with_bound_resources(
             "draw_indexed_indirect",
+            Some(bound_resources),
             used_resources,
             move |out: &mut UnsafeCommandBufferBuilder<A>| {
                 out.draw_indexed_indirect_unchecked(&indirect_buffer, draw_count, stride);
@@ -814,697 +1310,774 @@ where
         self
     }
 
-    fn validate_pipeline_descriptor_sets<Pl: Pipeline>(
+    /// Perform multiple draw operations using a graphics pipeline, using an index buffer, and
+    /// reading the number of draws from a buffer.
+    ///
+    /// One draw is performed for each [`DrawIndexedIndirectCommand`] struct in `indirect_buffer`,
+    /// up to the number that is read from `count_buffer` when the command is executed. The
+    /// maximum number of draws is the length of `indirect_buffer`. The number that is read from
+    /// `count_buffer` must not be greater than the
+    /// [`max_draw_indirect_count`](crate::device::Properties::max_draw_indirect_count) limit.
+    ///
+    /// The [`draw_indirect_count`](crate::device::Features::draw_indirect_count) feature must be
+    /// enabled on the device.
+    ///
+    /// An index buffer must have been bound using
+    /// [`bind_index_buffer`](Self::bind_index_buffer), and the index ranges of each
+    /// `DrawIndexedIndirectCommand` in the indirect buffer must be in range of the bound index
+    /// buffer.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
+    /// beforehand. If the bound graphics pipeline uses vertex buffers, then the instance ranges of
+    /// each `DrawIndexedIndirectCommand` in the indirect buffer must be in range of the bound
+    /// vertex buffers.
+    ///
+    /// Instead of a pipeline, graphics shader objects can be bound using
+    /// [`bind_shaders`](Self::bind_shaders).
+    pub fn draw_indexed_indirect_count(
+        &mut self,
+        indirect_buffer: Subbuffer<[DrawIndexedIndirectCommand]>,
+        count_buffer: Subbuffer<u32>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        let max_draw_count = indirect_buffer.len() as u32;
+        let stride = size_of::<DrawIndexedIndirectCommand>() as u32;
+        self.validate_draw_indexed_indirect_count(
+            indirect_buffer.as_bytes(),
+            count_buffer.as_bytes(),
+            max_draw_count,
+            stride,
+        )?;
+
+        unsafe {
+            Ok(self.draw_indexed_indirect_count_unchecked(
+                indirect_buffer,
+                count_buffer,
+                max_draw_count,
+                stride,
+            ))
+        }
+    }
+
+    fn validate_draw_indexed_indirect_count(
         &self,
-        vuid_type: VUIDType,
-        pipeline: &Pl,
+        indirect_buffer: &Subbuffer<[u8]>,
+        count_buffer: &Subbuffer<[u8]>,
+        max_draw_count: u32,
+        stride: u32,
     ) -> Result<(), Box<ValidationError>> {
-        fn validate_resources<T>(
-            vuid_type: VUIDType,
-            set_num: u32,
-            binding_num: u32,
-            binding_reqs: &DescriptorBindingRequirements,
-            elements: &[Option<T>],
-            mut extra_check: impl FnMut(u32, u32, u32, &T) -> Result<(), Box<ValidationError>>,
-        ) -> Result<(), Box<ValidationError>> {
-            let elements_to_check = if let Some(descriptor_count) = binding_reqs.descriptor_count {
-                // The shader has a fixed-sized array, so it will never access more than
-                // the first `descriptor_count` elements.
-                elements.get(..descriptor_count as usize).ok_or_else(|| {
-                    // There are less than `descriptor_count` elements in `elements`
-                    Box::new(ValidationError {
-                        problem: format!(
-                            "the currently bound pipeline accesses the resource bound to \
-                            descriptor set {set_num}, binding {binding_num}, \
-                            descriptor index {}, but no descriptor was written to the \
-                            descriptor set currently bound to set {set_num}",
-                            elements.len()
-                        )
-                        .into(),
-                        vuids: vuids!(vuid_type, "None-02699"),
-                        ..Default::default()
-                    })
-                })?
-            } else {
-                // The shader has a runtime-sized array, so any element could potentially
-                // be accessed. We must check them all.
-                elements
-            };
+        self.inner.validate_draw_indexed_indirect_count(
+            indirect_buffer,
+            count_buffer,
+            max_draw_count,
+            stride,
+        )?;
 
-            for (index, element) in elements_to_check.iter().enumerate() {
-                let index = index as u32;
+        let render_pass_state = self.builder_state.render_pass.as_ref().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "a render pass instance is not active".into(),
+                vuids: &["VUID-vkCmdDrawIndexedIndirectCount-renderpass"],
+                ..Default::default()
+            })
+        })?;
 
-                let element = match element {
-                    Some(x) => x,
-                    None => {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound pipeline accesses the resource bound to \
-                                descriptor set {set_num}, binding {binding_num}, \
-                                descriptor index {index}, but no descriptor was written to the \
-                                descriptor set currently bound to set {set_num}"
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-02699"),
-                            ..Default::default()
-                        }));
-                    }
-                };
+        const VUID_TYPE: VUIDType = VUIDType::DrawIndexedIndirectCount;
+        let graphics = self.validate_bound_graphics(VUID_TYPE, render_pass_state)?;
+        self.validate_graphics_vertex_buffers(VUID_TYPE, graphics)?;
 
-                extra_check(set_num, binding_num, index, element)?;
-            }
+        let _index_buffer = self.builder_state.index_buffer.as_ref().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "no index buffer is currently bound".into(),
+                vuids: &["VUID-vkCmdDrawIndexedIndirectCount-None-07312"],
+                ..Default::default()
+            })
+        })?;
 
-            Ok(())
-        }
+        Ok(())
+    }
 
-        if pipeline.num_used_descriptor_sets() == 0 {
-            return Ok(());
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_indexed_indirect_count_unchecked(
+        &mut self,
+        indirect_buffer: Subbuffer<[DrawIndexedIndirectCommand]>,
+        count_buffer: Subbuffer<u32>,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> &mut Self {
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
         }
 
-        let descriptor_set_state = self
-            .builder_state
-            .descriptor_sets
-            .get(&pipeline.bind_point())
-            .ok_or_else(|| {
-                Box::new(ValidationError {
-                    problem: "the currently bound pipeline accesses descriptor sets, but no \
-                    descriptor sets were previously bound"
-                        .into(),
-                    vuids: vuids!(vuid_type, "None-02697"),
+        let bound_resources = self.bound_resources(PipelineBindPoint::Graphics);
+
+        let mut used_resources = Vec::new();
+        self.add_index_buffer_resources(&mut used_resources);
+        self.add_indirect_buffer_resources(&mut used_resources, indirect_buffer.as_bytes());
+        self.add_count_buffer_resources(&mut used_resources, count_buffer.as_bytes());
+
+        self.add_command_with_bound_resources(
+            "draw_indexed_indirect_count",
+            Some(bound_resources),
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.draw_indexed_indirect_count_unchecked(
+                    &indirect_buffer,
+                    &count_buffer,
+                    max_draw_count,
+                    stride,
+                );
+            },
+        );
+
+        self
+    }
+
+    /// Perform a single mesh shading draw operation using a graphics pipeline.
+    ///
+    /// `group_counts` specifies the number of workgroups that are dispatched to the task shader
+    /// of the bound pipeline or, if it has no task shader, to its mesh shader. The counts must not
+    /// exceed the [`max_task_work_group_count`] or [`max_mesh_work_group_count`] limit
+    /// respectively, and their product must not exceed the corresponding `*_total_count` limit.
+    ///
+    /// The [`mesh_shader`](crate::device::Features::mesh_shader) feature must be enabled on the
+    /// device.
+    ///
+    /// A graphics pipeline containing a mesh shader must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets and dynamic state, must have been set beforehand.
+    ///
+    /// Instead of a pipeline, graphics shader objects can be bound using
+    /// [`bind_shaders`](Self::bind_shaders).
+    ///
+    /// [`max_task_work_group_count`]: crate::device::Properties::max_task_work_group_count
+    /// [`max_mesh_work_group_count`]: crate::device::Properties::max_mesh_work_group_count
+    pub fn draw_mesh_tasks(
+        &mut self,
+        group_counts: [u32; 3],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw_mesh_tasks(group_counts)?;
+
+        unsafe { Ok(self.draw_mesh_tasks_unchecked(group_counts)) }
+    }
+
+    fn validate_draw_mesh_tasks(&self, group_counts: [u32; 3]) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_draw_mesh_tasks(group_counts)?;
+
+        let render_pass_state = self.builder_state.render_pass.as_ref().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "a render pass instance is not active".into(),
+                vuids: &["VUID-vkCmdDrawMeshTasksEXT-renderpass"],
+                ..Default::default()
+            })
+        })?;
+
+        const VUID_TYPE: VUIDType = VUIDType::DrawMeshTasksEXT;
+        let graphics = self.validate_bound_graphics(VUID_TYPE, render_pass_state)?;
+        self.validate_graphics_mesh_shading(VUID_TYPE, graphics)?;
+
+        let properties = self.device().physical_device().properties();
+
+        let (max_work_group_count, max_work_group_total_count, limit_name, vuids) =
+            if graphics.has_shader(ShaderStage::Task) {
+                (
+                    properties.max_task_work_group_count.unwrap_or_default(),
+                    properties.max_task_work_group_total_count.unwrap_or(0),
+                    "max_task_work_group",
+                    &[
+                        "VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07322",
+                        "VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07323",
+                        "VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07324",
+                        "VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07325",
+                    ],
+                )
+            } else {
+                (
+                    properties.max_mesh_work_group_count.unwrap_or_default(),
+                    properties.max_mesh_work_group_total_count.unwrap_or(0),
+                    "max_mesh_work_group",
+                    &[
+                        "VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07326",
+                        "VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07327",
+                        "VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07328",
+                        "VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07329",
+                    ],
+                )
+            };
+
+        for index in 0..3 {
+            if group_counts[index] > max_work_group_count[index] {
+                return Err(Box::new(ValidationError {
+                    context: format!("group_counts[{}]", index).into(),
+                    problem: format!(
+                        "is greater than the `{}_count[{}]` limit",
+                        limit_name, index
+                    )
+                    .into(),
+                    vuids: &vuids[index..index + 1],
                     ..Default::default()
-                })
-            })?;
+                }));
+            }
+        }
 
-        if !pipeline.layout().is_compatible_with(
-            &descriptor_set_state.pipeline_layout,
-            pipeline.num_used_descriptor_sets(),
-        ) {
+        if group_counts.into_iter().map(u64::from).product::<u64>()
+            > max_work_group_total_count as u64
+        {
             return Err(Box::new(ValidationError {
-                problem: "the currently bound pipeline accesses descriptor sets, but the \
-                    pipeline layouts that were used to bind the descriptor sets are \
-                    not compatible with the pipeline layout of the currently bound pipeline"
-                    .into(),
-                vuids: vuids!(vuid_type, "None-02697"),
+                context: "group_counts".into(),
+                problem: format!(
+                    "the product of the elements is greater than the `{}_total_count` limit",
+                    limit_name
+                )
+                .into(),
+                vuids: &vuids[3..],
                 ..Default::default()
             }));
         }
 
-        for (&(set_num, binding_num), binding_reqs) in pipeline.descriptor_binding_requirements() {
-            let layout_binding =
-                &pipeline.layout().set_layouts()[set_num as usize].bindings()[&binding_num];
+        Ok(())
+    }
 
-            let check_buffer =
-                |_set_num: u32,
-                 _binding_num: u32,
-                 _index: u32,
-                 _buffer_info: &DescriptorBufferInfo| Ok(());
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_mesh_tasks_unchecked(&mut self, group_counts: [u32; 3]) -> &mut Self {
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
 
-            let check_buffer_view =
-                |set_num: u32, binding_num: u32, index: u32, buffer_view: &Arc<BufferView>| {
-                    for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
-                        .chain(binding_reqs.descriptors.get(&None))
-                    {
-                        if layout_binding.descriptor_type == DescriptorType::StorageTexelBuffer {
-                            if binding_reqs.image_format.is_none()
-                                && !desc_reqs.memory_write.is_empty()
-                                && !buffer_view
-                                    .format_features()
-                                    .intersects(FormatFeatures::STORAGE_WRITE_WITHOUT_FORMAT)
-                            {
-                                return Err(Box::new(ValidationError {
-                                    problem: format!(
-                                        "the currently bound pipeline writes to the buffer view \
-                                        bound to descriptor set {set_num}, binding {binding_num}, \
-                                        descriptor index {index}, without specifying a format, \
-                                        but the format features of the buffer view's format do \
-                                        not contain `FormatFeatures::STORAGE_WRITE_WITHOUT_FORMAT`"
-                                    )
-                                    .into(),
-                                    vuids: vuids!(vuid_type, "OpTypeImage-06423"),
-                                    ..Default::default()
-                                }));
-                            }
+        let bound_resources = self.bound_resources(PipelineBindPoint::Graphics);
 
-                            if binding_reqs.image_format.is_none()
-                                && !desc_reqs.memory_read.is_empty()
-                                && !buffer_view
-                                    .format_features()
-                                    .intersects(FormatFeatures::STORAGE_READ_WITHOUT_FORMAT)
-                            {
-                                return Err(Box::new(ValidationError {
-                                    problem: format!(
-                                        "the currently bound pipeline reads from the buffer view \
-                                        bound to descriptor set {set_num}, binding {binding_num}, \
-                                        descriptor index {index}, without specifying a format, \
-                                        but the format features of the buffer view's format do \
-                                        not contain `FormatFeatures::STORAGE_READ_WITHOUT_FORMAT`"
-                                    )
-                                    .into(),
-                                    vuids: vuids!(vuid_type, "OpTypeImage-06424"),
-                                    ..Default::default()
-                                }));
-                            }
-                        }
-                    }
+        self.add_command_with_bound_resources(
+            "draw_mesh_tasks",
+            Some(bound_resources),
+            Vec::new(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.draw_mesh_tasks_unchecked(group_counts);
+            },
+        );
 
-                    Ok(())
-                };
+        self
+    }
 
-            let check_image_view_common =
-                |set_num: u32, binding_num: u32, index: u32, image_view: &Arc<ImageView>| {
-                    for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
-                        .chain(binding_reqs.descriptors.get(&None))
-                    {
-                        if desc_reqs.storage_image_atomic
-                            && !image_view
-                                .format_features()
-                                .intersects(FormatFeatures::STORAGE_IMAGE_ATOMIC)
-                        {
-                            return Err(Box::new(ValidationError {
-                                problem: format!(
-                                    "the currently bound pipeline performs atomic operations on \
-                                    the image view bound to descriptor set {set_num}, \
-                                    binding {binding_num}, descriptor index {index}, but \
-                                    the format features of the image view's format do \
-                                    not contain `FormatFeatures::STORAGE_IMAGE_ATOMIC`"
-                                )
-                                .into(),
-                                vuids: vuids!(vuid_type, "None-02691"),
-                                ..Default::default()
-                            }));
-                        }
+    /// Perform multiple mesh shading draw operations using a graphics pipeline.
+    ///
+    /// One draw is performed for each [`DrawMeshTasksIndirectCommand`] struct in
+    /// `indirect_buffer`. The maximum number of draw commands in the buffer is limited by the
+    /// [`max_draw_indirect_count`](crate::device::Properties::max_draw_indirect_count) limit.
+    /// This limit is 1 unless the
+    /// [`multi_draw_indirect`](crate::device::Features::multi_draw_indirect) feature has been
+    /// enabled.
+    ///
+    /// The [`mesh_shader`](crate::device::Features::mesh_shader) feature must be enabled on the
+    /// device.
+    ///
+    /// A graphics pipeline containing a mesh shader must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets and dynamic state, must have been set beforehand. The
+    /// group counts of each `DrawMeshTasksIndirectCommand` in the indirect buffer must be within
+    /// the same limits as for [`draw_mesh_tasks`](Self::draw_mesh_tasks).
+    ///
+    /// Instead of a pipeline, graphics shader objects can be bound using
+    /// [`bind_shaders`](Self::bind_shaders).
+    pub fn draw_mesh_tasks_indirect(
+        &mut self,
+        indirect_buffer: Subbuffer<[DrawMeshTasksIndirectCommand]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        let draw_count = indirect_buffer.len() as u32;
+        let stride = size_of::<DrawMeshTasksIndirectCommand>() as u32;
+        self.validate_draw_mesh_tasks_indirect(indirect_buffer.as_bytes(), draw_count, stride)?;
 
-                        if layout_binding.descriptor_type == DescriptorType::StorageImage {
-                            if binding_reqs.image_format.is_none()
-                                && !desc_reqs.memory_write.is_empty()
-                                && !image_view
-                                    .format_features()
-                                    .intersects(FormatFeatures::STORAGE_WRITE_WITHOUT_FORMAT)
-                            {
-                                return Err(Box::new(ValidationError {
-                                    problem: format!(
-                                        "the currently bound pipeline writes to the image view \
-                                        bound to descriptor set {set_num}, binding {binding_num}, \
-                                        descriptor index {index}, without specifying a format, \
-                                        but the format features of the image view's format do \
-                                        not contain `FormatFeatures::STORAGE_WRITE_WITHOUT_FORMAT`"
-                                    )
-                                    .into(),
-                                    vuids: vuids!(vuid_type, "OpTypeImage-06423"),
-                                    ..Default::default()
-                                }));
-                            }
+        unsafe { Ok(self.draw_mesh_tasks_indirect_unchecked(indirect_buffer, draw_count, stride)) }
+    }
 
-                            if binding_reqs.image_format.is_none()
-                                && !desc_reqs.memory_read.is_empty()
-                                && !image_view
-                                    .format_features()
-                                    .intersects(FormatFeatures::STORAGE_READ_WITHOUT_FORMAT)
-                            {
-                                return Err(Box::new(ValidationError {
-                                    problem: format!(
-                                        "the currently bound pipeline reads from the image view \
-                                        bound to descriptor set {set_num}, binding {binding_num}, \
-                                        descriptor index {index}, without specifying a format, \
-                                        but the format features of the image view's format do \
-                                        not contain `FormatFeatures::STORAGE_READ_WITHOUT_FORMAT`"
-                                    )
-                                    .into(),
-                                    vuids: vuids!(vuid_type, "OpTypeImage-06424"),
-                                    ..Default::default()
-                                }));
-                            }
-                        }
-                    }
+    fn validate_draw_mesh_tasks_indirect(
+        &self,
+        indirect_buffer: &Subbuffer<[u8]>,
+        draw_count: u32,
+        stride: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_draw_mesh_tasks_indirect(indirect_buffer, draw_count, stride)?;
 
-                    /*
-                       Instruction/Sampler/Image View Validation
-                       https://registry.khronos.org/vulkan/specs/1.3-extensions/html/chap16.html#textures-input-validation
-                    */
+        let render_pass_state = self.builder_state.render_pass.as_ref().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "a render pass instance is not active".into(),
+                vuids: &["VUID-vkCmdDrawMeshTasksIndirectEXT-renderpass"],
+                ..Default::default()
+            })
+        })?;
 
-                    // The SPIR-V Image Format is not compatible with the image view’s format.
-                    if let Some(format) = binding_reqs.image_format {
-                        if image_view.format() != format {
-                            return Err(Box::new(ValidationError {
-                                problem: format!(
-                                    "the currently bound pipeline accesses the image view \
-                                    bound to descriptor set {set_num}, binding {binding_num}, \
-                                    descriptor index {index}, but the format of the image view \
-                                    is not equal to the format required by the pipeline"
-                                )
-                                .into(),
-                                // vuids?
-                                ..Default::default()
-                            }));
-                        }
-                    }
+        const VUID_TYPE: VUIDType = VUIDType::DrawMeshTasksIndirectEXT;
+        let graphics = self.validate_bound_graphics(VUID_TYPE, render_pass_state)?;
+        self.validate_graphics_mesh_shading(VUID_TYPE, graphics)?;
 
-                    // Rules for viewType
-                    if let Some(image_view_type) = binding_reqs.image_view_type {
-                        if image_view.view_type() != image_view_type {
-                            return Err(Box::new(ValidationError {
-                                problem: format!(
-                                    "the currently bound pipeline accesses the image view \
-                                    bound to descriptor set {set_num}, binding {binding_num}, \
-                                    descriptor index {index}, but the view type of the image view \
-                                    is not equal to the view type required by the pipeline"
-                                )
-                                .into(),
-                                // vuids?
-                                ..Default::default()
-                            }));
-                        }
-                    }
+        Ok(())
+    }
 
-                    // - If the image was created with VkImageCreateInfo::samples equal to
-                    //   VK_SAMPLE_COUNT_1_BIT, the instruction must have MS = 0.
-                    // - If the image was created with VkImageCreateInfo::samples not equal to
-                    //   VK_SAMPLE_COUNT_1_BIT, the instruction must have MS = 1.
-                    if binding_reqs.image_multisampled
-                        && image_view.image().samples() == SampleCount::Sample1
-                    {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound pipeline accesses the image view \
-                                bound to descriptor set {set_num}, binding {binding_num}, \
-                                descriptor index {index}, and the pipeline requires a \
-                                multisampled image, but the image view has only one sample"
-                            )
-                            .into(),
-                            // vuids?
-                            ..Default::default()
-                        }));
-                    } else if !binding_reqs.image_multisampled
-                        && image_view.image().samples() != SampleCount::Sample1
-                    {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound pipeline accesses the image view \
-                                bound to descriptor set {set_num}, binding {binding_num}, \
-                                descriptor index {index}, and the pipeline requires a non-\
-                                multisampled image, but the image view has more than one sample"
-                            )
-                            .into(),
-                            // vuids?
-                            ..Default::default()
-                        }));
-                    }
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_mesh_tasks_indirect_unchecked(
+        &mut self,
+        indirect_buffer: Subbuffer<[DrawMeshTasksIndirectCommand]>,
+        draw_count: u32,
+        stride: u32,
+    ) -> &mut Self {
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
 
-                    // - If the Sampled Type of the OpTypeImage does not match the numeric format of the
-                    //   image, as shown in the SPIR-V Sampled Type column of the
-                    //   Interpretation of Numeric Format table.
-                    // - If the signedness of any read or sample operation does not match the signedness of
-                    //   the image’s format.
-                    if let Some(shader_numeric_type) = binding_reqs.image_scalar_type {
-                        let aspects = image_view.subresource_range().aspects;
-                        let view_numeric_type = NumericType::from(
-                            if aspects.intersects(
-                                ImageAspects::COLOR
-                                    | ImageAspects::PLANE_0
-                                    | ImageAspects::PLANE_1
-                                    | ImageAspects::PLANE_2,
-                            ) {
-                                image_view.format().numeric_format_color().unwrap()
-                            } else if aspects.intersects(ImageAspects::DEPTH) {
-                                image_view.format().numeric_format_depth().unwrap()
-                            } else if aspects.intersects(ImageAspects::STENCIL) {
-                                image_view.format().numeric_format_stencil().unwrap()
-                            } else {
-                                // Per `ImageViewBuilder::aspects` and
-                                // VUID-VkDescriptorImageInfo-imageView-01976
-                                unreachable!()
-                            },
-                        );
+        let bound_resources = self.bound_resources(PipelineBindPoint::Graphics);
 
-                        if shader_numeric_type != view_numeric_type {
-                            return Err(Box::new(ValidationError {
-                                problem: format!(
-                                    "the currently bound pipeline accesses the image view \
-                                    bound to descriptor set {set_num}, binding {binding_num}, \
-                                    descriptor index {index}, and the pipeline requires an \
-                                    image view whose format has a `{shader_numeric_type:?}` \
-                                    numeric type, but the format of the image view has a \
-                                    `{view_numeric_type:?}` numeric type"
-                                )
-                                .into(),
-                                // vuids?
-                                ..Default::default()
-                            }));
-                        }
-                    }
+        let mut used_resources = Vec::new();
+        self.add_indirect_buffer_resources(&mut used_resources, indirect_buffer.as_bytes());
 
-                    Ok(())
-                };
+        self.add_command_with_bound_resources(
+            "draw_mesh_tasks_indirect",
+            Some(bound_resources),
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.draw_mesh_tasks_indirect_unchecked(&indirect_buffer, draw_count, stride);
+            },
+        );
 
-            let check_sampler_common =
-                |set_num: u32, binding_num: u32, index: u32, sampler: &Arc<Sampler>| {
-                    for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
-                        .chain(binding_reqs.descriptors.get(&None))
-                    {
-                        if desc_reqs.sampler_no_unnormalized_coordinates
-                            && sampler.unnormalized_coordinates()
-                        {
-                            return Err(Box::new(ValidationError {
-                                problem: format!(
-                                    "the currently bound pipeline accesses the sampler bound to \
-                                    descriptor set {set_num}, binding {binding_num}, \
-                                    descriptor index {index}, in a way that does not support \
-                                    samplers with unnormalized coordinates, but \
-                                    the sampler currently bound to that descriptor \
-                                    uses unnormalized coordinates"
-                                )
-                                .into(),
-                                vuids: vuids!(vuid_type, "None-02703", "None-02704"),
-                                ..Default::default()
-                            }));
-                        }
+        self
+    }
 
-                        // - OpImageFetch, OpImageSparseFetch, OpImage*Gather, and OpImageSparse*Gather must not
-                        //   be used with a sampler that enables sampler Y′CBCR conversion.
-                        // - The ConstOffset and Offset operands must not be used with a sampler that enables
-                        //   sampler Y′CBCR conversion.
-                        if desc_reqs.sampler_no_ycbcr_conversion
-                            && sampler.sampler_ycbcr_conversion().is_some()
-                        {
-                            return Err(Box::new(ValidationError {
-                                problem: format!(
-                                    "the currently bound pipeline accesses the sampler bound to \
-                                    descriptor set {set_num}, binding {binding_num}, \
-                                    descriptor index {index}, in a way that does not support \
-                                    samplers with a sampler YCbCr conversion, but the sampler \
-                                    currently bound to that descriptor has a \
-                                    sampler YCbCr conversion"
-                                )
-                                .into(),
-                                // vuids?
-                                ..Default::default()
-                            }));
-                        }
+    /// Perform a single ray tracing operation using a ray tracing pipeline.
+    ///
+    /// One ray generation shader invocation is performed for each element of a grid with the
+    /// given `dimensions`. The shader groups that are invoked are taken from
+    /// `shader_binding_table`, which must have been created for the bound pipeline.
+    ///
+    /// A ray tracing pipeline must have been bound using
+    /// [`bind_pipeline_ray_tracing`](Self::bind_pipeline_ray_tracing). Any resources used by the
+    /// ray tracing pipeline, such as descriptor sets, must have been set beforehand.
+    pub fn trace_rays(
+        &mut self,
+        shader_binding_table: &ShaderBindingTable,
+        dimensions: [u32; 3],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_trace_rays(shader_binding_table, dimensions)?;
 
-                        /*
-                            Instruction/Sampler/Image View Validation
-                            https://registry.khronos.org/vulkan/specs/1.3-extensions/html/chap16.html#textures-input-validation
-                        */
+        unsafe { Ok(self.trace_rays_unchecked(shader_binding_table, dimensions)) }
+    }
 
-                        if desc_reqs.sampler_compare && sampler.compare().is_none() {
-                            // - The SPIR-V instruction is one of the OpImage*Dref* instructions and the sampler
-                            //   compareEnable is VK_FALSE
-                            return Err(Box::new(ValidationError {
-                                problem: format!(
-                                    "the currently bound pipeline accesses the sampler bound to \
-                                    descriptor set {set_num}, binding {binding_num}, \
-                                    descriptor index {index}, in a way that requires a sampler \
-                                    with a compare operation, but the sampler currently bound to \
-                                    that descriptor does not have a compare operation"
-                                )
-                                .into(),
-                                // vuids?
-                                ..Default::default()
-                            }));
-                        } else if !desc_reqs.sampler_compare && sampler.compare().is_some() {
-                            // - The SPIR-V instruction is not one of the OpImage*Dref* instructions and the sampler
-                            //   compareEnable is VK_TRUE
-                            return Err(Box::new(ValidationError {
-                                problem: format!(
-                                    "the currently bound pipeline accesses the sampler bound to \
-                                    descriptor set {set_num}, binding {binding_num}, \
-                                    descriptor index {index}, in a way that requires a sampler \
-                                    without a compare operation, but the sampler currently bound \
-                                    to that descriptor has a compare operation"
-                                )
-                                .into(),
-                                // vuids?
-                                ..Default::default()
-                            }));
-                        }
-                    }
+    fn validate_trace_rays(
+        &self,
+        shader_binding_table: &ShaderBindingTable,
+        dimensions: [u32; 3],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_trace_rays(shader_binding_table.addresses(), dimensions)?;
 
-                    Ok(())
-                };
+        if self.builder_state.render_pass.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "a render pass instance is active".into(),
+                vuids: &["VUID-vkCmdTraceRaysKHR-renderpass"],
+                ..Default::default()
+            }));
+        }
 
-            let check_image_view =
-                |set_num: u32,
-                 binding_num: u32,
-                 index: u32,
-                 image_view_info: &DescriptorImageViewInfo| {
-                    let DescriptorImageViewInfo {
-                        image_view,
-                        image_layout: _,
-                    } = image_view_info;
+        let pipeline = self
+            .builder_state
+            .pipeline_ray_tracing
+            .as_ref()
+            .ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "no ray tracing pipeline is currently bound".into(),
+                    vuids: &["VUID-vkCmdTraceRaysKHR-None-08606"],
+                    ..Default::default()
+                })
+            })?
+            .as_ref();
 
-                    check_image_view_common(set_num, binding_num, index, image_view)?;
+        const VUID_TYPE: VUIDType = VUIDType::TraceRaysKHR;
+        Self::validate_shader_binding_table_pipeline(shader_binding_table, pipeline)?;
+        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
 
-                    if let Some(sampler) = layout_binding.immutable_samplers.get(index as usize) {
-                        check_sampler_common(set_num, binding_num, index, sampler)?;
-                    }
+        Ok(())
+    }
 
-                    Ok(())
-                };
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn trace_rays_unchecked(
+        &mut self,
+        shader_binding_table: &ShaderBindingTable,
+        dimensions: [u32; 3],
+    ) -> &mut Self {
+        let bound_resources = self.bound_resources(PipelineBindPoint::RayTracing);
 
-            let check_image_view_sampler = |set_num: u32,
-                                            binding_num: u32,
-                                            index: u32,
-                                            (image_view_info, sampler): &(
-                DescriptorImageViewInfo,
-                Arc<Sampler>,
-            )| {
-                let DescriptorImageViewInfo {
-                    image_view,
-                    image_layout: _,
-                } = image_view_info;
+        let mut used_resources = Vec::new();
+        self.add_shader_binding_table_resources(&mut used_resources, shader_binding_table);
 
-                check_image_view_common(set_num, binding_num, index, image_view)?;
-                check_sampler_common(set_num, binding_num, index, sampler)?;
+        let addresses = *shader_binding_table.addresses();
 
-                Ok(())
-            };
+        self.add_command_with_bound_resources(
+            "trace_rays",
+            Some(bound_resources),
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.trace_rays_unchecked(&addresses, dimensions);
+            },
+        );
 
-            let check_sampler =
-                |set_num: u32, binding_num: u32, index: u32, sampler: &Arc<Sampler>| {
-                    check_sampler_common(set_num, binding_num, index, sampler)?;
+        self
+    }
 
-                    for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
-                        .chain(binding_reqs.descriptors.get(&None))
-                    {
-                        // Check sampler-image compatibility. Only done for separate samplers;
-                        // combined image samplers are checked when updating the descriptor set.
+    /// Perform a single ray tracing operation using a ray tracing pipeline, reading the
+    /// dimensions from a buffer.
+    ///
+    /// The dimensions are read from the first [`TraceRaysIndirectCommand`] struct in
+    /// `indirect_buffer` when the command is executed. The indirect buffer must have been
+    /// created with the [`SHADER_DEVICE_ADDRESS`](crate::buffer::BufferUsage::SHADER_DEVICE_ADDRESS)
+    /// usage.
+    ///
+    /// The [`ray_tracing_pipeline_trace_rays_indirect`] feature must be enabled on the device.
+    ///
+    /// A ray tracing pipeline must have been bound using
+    /// [`bind_pipeline_ray_tracing`](Self::bind_pipeline_ray_tracing). Any resources used by the
+    /// ray tracing pipeline, such as descriptor sets, must have been set beforehand.
+    ///
+    /// [`ray_tracing_pipeline_trace_rays_indirect`]: crate::device::Features::ray_tracing_pipeline_trace_rays_indirect
+    pub fn trace_rays_indirect(
+        &mut self,
+        shader_binding_table: &ShaderBindingTable,
+        indirect_buffer: Subbuffer<[TraceRaysIndirectCommand]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_trace_rays_indirect(shader_binding_table, &indirect_buffer)?;
 
-                        // If the image view isn't actually present in the resources, then just skip it.
-                        // It will be caught later by check_resources.
-                        let iter = desc_reqs.sampler_with_images.iter().filter_map(|id| {
-                            descriptor_set_state
-                                .descriptor_sets
-                                .get(&id.set)
-                                .and_then(|set| set.resources().binding(id.binding))
-                                .and_then(|res| match res {
-                                    DescriptorBindingResources::ImageView(elements) => elements
-                                        .get(id.index as usize)
-                                        .and_then(|opt| opt.as_ref().map(|opt| (id, opt))),
-                                    _ => None,
-                                })
-                        });
+        unsafe { Ok(self.trace_rays_indirect_unchecked(shader_binding_table, indirect_buffer)) }
+    }
 
-                        for (id, image_view_info) in iter {
-                            let DescriptorIdentifier {
-                                set: iset_num,
-                                binding: ibinding_num,
-                                index: iindex,
-                            } = id;
-                            let DescriptorImageViewInfo {
-                                image_view,
-                                image_layout: _,
-                            } = image_view_info;
+    fn validate_trace_rays_indirect(
+        &self,
+        shader_binding_table: &ShaderBindingTable,
+        indirect_buffer: &Subbuffer<[TraceRaysIndirectCommand]>,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_trace_rays_indirect(shader_binding_table.addresses(), indirect_buffer)?;
 
-                            if let Err(error) = sampler.check_can_sample(image_view.as_ref()) {
-                                return Err(Box::new(ValidationError {
-                                    problem: format!(
-                                        "the currently bound pipeline uses the sampler bound to \
-                                        descriptor set {set_num}, binding {binding_num}, \
-                                        descriptor index {index}, to sample the image bound to \
-                                        descriptor set {iset_num}, binding {ibinding_num}, \
-                                        descriptor index {iindex}, but the sampler is not \
-                                        compatible with the image: {}",
-                                        error,
-                                    )
-                                    .into(),
-                                    // vuids?
-                                    ..Default::default()
-                                }));
-                            }
-                        }
-                    }
+        if self.builder_state.render_pass.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "a render pass instance is active".into(),
+                vuids: &["VUID-vkCmdTraceRaysIndirectKHR-renderpass"],
+                ..Default::default()
+            }));
+        }
 
-                    Ok(())
-                };
+        let pipeline = self
+            .builder_state
+            .pipeline_ray_tracing
+            .as_ref()
+            .ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "no ray tracing pipeline is currently bound".into(),
+                    vuids: &["VUID-vkCmdTraceRaysIndirectKHR-None-08606"],
+                    ..Default::default()
+                })
+            })?
+            .as_ref();
 
-            let check_acceleration_structure =
-                |_set_num: u32,
-                 _binding_num: u32,
-                 _index: u32,
-                 _acceleration_structure: &Arc<AccelerationStructure>| Ok(());
+        const VUID_TYPE: VUIDType = VUIDType::TraceRaysIndirectKHR;
+        Self::validate_shader_binding_table_pipeline(shader_binding_table, pipeline)?;
+        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
 
-            let check_none = |set_num: u32, binding_num: u32, index: u32, _: &()| {
-                if let Some(sampler) = layout_binding.immutable_samplers.get(index as usize) {
-                    check_sampler(set_num, binding_num, index, sampler)?;
-                }
+        Ok(())
+    }
 
-                Ok(())
-            };
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn trace_rays_indirect_unchecked(
+        &mut self,
+        shader_binding_table: &ShaderBindingTable,
+        indirect_buffer: Subbuffer<[TraceRaysIndirectCommand]>,
+    ) -> &mut Self {
+        let bound_resources = self.bound_resources(PipelineBindPoint::RayTracing);
 
-            let set_resources = descriptor_set_state
-                .descriptor_sets
-                .get(&set_num)
-                .ok_or_else(|| {
-                    Box::new(ValidationError {
-                        problem: format!(
-                            "the currently bound pipeline accesses descriptor set {set_num}, but \
-                        no descriptor set was previously bound"
-                        )
+        let mut used_resources = Vec::new();
+        self.add_shader_binding_table_resources(&mut used_resources, shader_binding_table);
+        self.add_indirect_buffer_resources(&mut used_resources, indirect_buffer.as_bytes());
+
+        let addresses = *shader_binding_table.addresses();
+
+        self.add_command_with_bound_resources(
+            "trace_rays_indirect",
+            Some(bound_resources),
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.trace_rays_indirect_unchecked(&addresses, &indirect_buffer);
+            },
+        );
+
+        self
+    }
+
+    fn validate_shader_binding_table_pipeline(
+        shader_binding_table: &ShaderBindingTable,
+        pipeline: &RayTracingPipeline,
+    ) -> Result<(), Box<ValidationError>> {
+        // The shader group handles are only valid for the pipeline they were taken from.
+        if shader_binding_table.pipeline().as_ref() != pipeline {
+            return Err(Box::new(ValidationError {
+                context: "shader_binding_table.pipeline()".into(),
+                problem: "is not the currently bound ray tracing pipeline".into(),
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Validates the compute pipeline or compute shader object that is currently bound, and the
+    /// state that it uses. Returns the pipeline if a pipeline is bound.
+    fn validate_bound_compute(
+        &self,
+        vuid_type: VUIDType,
+    ) -> Result<Option<&ComputePipeline>, Box<ValidationError>> {
+        if let Some(pipeline) = self.builder_state.pipeline_compute.as_deref() {
+            self.validate_pipeline_descriptor_sets(vuid_type, pipeline)?;
+            self.validate_pipeline_push_constants(vuid_type, pipeline.layout())?;
+
+            return Ok(Some(pipeline));
+        }
+
+        let shader_objects = self
+            .bound_shader_objects(PipelineBindPoint::Compute)?
+            .ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "no compute pipeline or compute shader object is currently bound"
                         .into(),
-                        // vuids?
-                        ..Default::default()
-                    })
-                })?
-                .resources();
+                    vuids: vuids!(vuid_type, "None-08606", "None-08607"),
+                    ..Default::default()
+                })
+            })?;
 
-            let binding_resources = set_resources.binding(binding_num).unwrap();
+        self.validate_pipeline_descriptor_sets(vuid_type, &shader_objects)?;
+        self.validate_pipeline_push_constants(vuid_type, shader_objects.layout())?;
 
-            match binding_resources {
-                DescriptorBindingResources::None(elements) => {
-                    validate_resources(
-                        vuid_type,
-                        set_num,
-                        binding_num,
-                        binding_reqs,
-                        elements,
-                        check_none,
-                    )?;
-                }
-                DescriptorBindingResources::Buffer(elements) => {
-                    validate_resources(
-                        vuid_type,
-                        set_num,
-                        binding_num,
-                        binding_reqs,
-                        elements,
-                        check_buffer,
-                    )?;
-                }
-                DescriptorBindingResources::BufferView(elements) => {
-                    validate_resources(
-                        vuid_type,
-                        set_num,
-                        binding_num,
-                        binding_reqs,
-                        elements,
-                        check_buffer_view,
-                    )?;
-                }
-                DescriptorBindingResources::ImageView(elements) => {
-                    validate_resources(
-                        vuid_type,
-                        set_num,
-                        binding_num,
-                        binding_reqs,
-                        elements,
-                        check_image_view,
-                    )?;
-                }
-                DescriptorBindingResources::ImageViewSampler(elements) => {
-                    validate_resources(
-                        vuid_type,
-                        set_num,
-                        binding_num,
-                        binding_reqs,
-                        elements,
-                        check_image_view_sampler,
-                    )?;
-                }
-                DescriptorBindingResources::Sampler(elements) => {
-                    validate_resources(
-                        vuid_type,
-                        set_num,
-                        binding_num,
-                        binding_reqs,
-                        elements,
-                        check_sampler,
-                    )?;
-                }
-                // Spec:
-                // Descriptor bindings with descriptor type of
-                // VK_DESCRIPTOR_TYPE_INLINE_UNIFORM_BLOCK can be undefined when
-                // the descriptor set is consumed; though values in that block will be undefined.
-                //
-                // TODO: We *may* still want to validate this?
-                DescriptorBindingResources::InlineUniformBlock => (),
-                DescriptorBindingResources::AccelerationStructure(elements) => {
-                    validate_resources(
-                        vuid_type,
-                        set_num,
-                        binding_num,
-                        binding_reqs,
-                        elements,
-                        check_acceleration_structure,
-                    )?;
-                }
-            }
-        }
-
-        Ok(())
+        Ok(None)
     }
 
-    fn validate_pipeline_push_constants(
+    /// Validates the graphics pipeline or graphics shader objects that are currently bound, and
+    /// the state that they use.
+    fn validate_bound_graphics(
         &self,
         vuid_type: VUIDType,
-        pipeline_layout: &PipelineLayout,
-    ) -> Result<(), Box<ValidationError>> {
-        if pipeline_layout.push_constant_ranges().is_empty()
-            || self.device().enabled_features().maintenance4
-        {
-            return Ok(());
+        render_pass_state: &RenderPassState,
+    ) -> Result<BoundGraphics<'_>, Box<ValidationError>> {
+        if let Some(pipeline) = self.builder_state.pipeline_graphics.as_deref() {
+            self.validate_pipeline_descriptor_sets(vuid_type, pipeline)?;
+            self.validate_pipeline_push_constants(vuid_type, pipeline.layout())?;
+            self.validate_pipeline_graphics_dynamic_state(vuid_type, pipeline)?;
+            self.validate_pipeline_graphics_render_pass(vuid_type, pipeline, render_pass_state)?;
+
+            return Ok(BoundGraphics::Pipeline(pipeline));
         }
 
-        let constants_pipeline_layout = self
-            .builder_state
-            .push_constants_pipeline_layout
-            .as_ref()
+        let shader_objects = self
+            .bound_shader_objects(PipelineBindPoint::Graphics)?
             .ok_or_else(|| {
                 Box::new(ValidationError {
-                    problem: "the currently bound pipeline accesses push constants, but no \
-                    push constants were previously set"
+                    problem: "no graphics pipeline or graphics shader objects are currently bound"
                         .into(),
-                    vuids: vuids!(vuid_type, "maintenance4-06425"),
+                    vuids: vuids!(vuid_type, "None-08606", "None-08607"),
                     ..Default::default()
                 })
             })?;
 
-        if pipeline_layout.handle() != constants_pipeline_layout.handle()
-            && pipeline_layout.push_constant_ranges()
-                != constants_pipeline_layout.push_constant_ranges()
-        {
+        self.validate_shader_objects_layouts(vuid_type, PipelineBindPoint::Graphics)?;
+        self.validate_pipeline_descriptor_sets(vuid_type, &shader_objects)?;
+        self.validate_pipeline_push_constants(vuid_type, shader_objects.layout())?;
+        self.validate_shader_objects_graphics_stages(vuid_type)?;
+        self.validate_shader_objects_graphics_render_pass(vuid_type, render_pass_state)?;
+        self.validate_shader_objects_graphics_dynamic_state(vuid_type, render_pass_state)?;
+
+        Ok(BoundGraphics::ShaderObjects(&self.builder_state.shaders))
+    }
+
+    fn validate_shader_objects_layouts(
+        &self,
+        vuid_type: VUIDType,
+        pipeline_bind_point: PipelineBindPoint,
+    ) -> Result<(), Box<ValidationError>> {
+        let mut layouts = self
+            .bound_shader_objects_iter(pipeline_bind_point)
+            .map(|shader| shader.layout());
+
+        let Some(first_layout) = layouts.next() else {
+            return Ok(());
+        };
+
+        for layout in layouts {
+            if layout.push_constant_ranges() != first_layout.push_constant_ranges() {
+                return Err(Box::new(ValidationError {
+                    problem: "the shader objects that are currently bound were created with \
+                        pipeline layouts that have different push constant ranges"
+                        .into(),
+                    vuids: vuids!(vuid_type, "None-08878"),
+                    ..Default::default()
+                }));
+            }
+
+            if layout.set_layouts().len() != first_layout.set_layouts().len()
+                || !layout.is_compatible_with(first_layout, layout.set_layouts().len() as u32)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "the shader objects that are currently bound were created with \
+                        pipeline layouts that have incompatible descriptor set layouts"
+                        .into(),
+                    vuids: vuids!(vuid_type, "None-08879"),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_shader_objects_graphics_stages(
+        &self,
+        vuid_type: VUIDType,
+    ) -> Result<(), Box<ValidationError>> {
+        let enabled_features = self.device().enabled_features();
+        let shaders = &self.builder_state.shaders;
+        let shader = |stage| shaders.get(&stage).and_then(Option::as_deref);
+
+        for (stage, is_supported, vuids) in [
+            (ShaderStage::Vertex, true, vuids!(vuid_type, "None-08684")),
+            (
+                ShaderStage::TessellationControl,
+                enabled_features.tessellation_shader,
+                vuids!(vuid_type, "None-08685"),
+            ),
+            (
+                ShaderStage::TessellationEvaluation,
+                enabled_features.tessellation_shader,
+                vuids!(vuid_type, "None-08686"),
+            ),
+            (
+                ShaderStage::Geometry,
+                enabled_features.geometry_shader,
+                vuids!(vuid_type, "None-08687"),
+            ),
+            (ShaderStage::Fragment, true, vuids!(vuid_type, "None-08688")),
+            (
+                ShaderStage::Task,
+                enabled_features.task_shader,
+                vuids!(vuid_type, "None-08689"),
+            ),
+            (
+                ShaderStage::Mesh,
+                enabled_features.mesh_shader,
+                vuids!(vuid_type, "None-08690"),
+            ),
+        ] {
+            if is_supported && !shaders.contains_key(&stage) {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "graphics shader objects are bound, but no shader object or `None` has \
+                        been bound to `ShaderStage::{:?}`",
+                        stage,
+                    )
+                    .into(),
+                    vuids,
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if shader(ShaderStage::Vertex).is_some() == shader(ShaderStage::Mesh).is_some() {
             return Err(Box::new(ValidationError {
-                problem: "the currently bound pipeline accesses push constants, but the \
-                    pipeline layouts that were used to set the push constants are \
-                    not compatible with the pipeline layout of the currently bound pipeline"
+                problem: "graphics shader objects are bound, but not exactly one of \
+                    `ShaderStage::Vertex` and `ShaderStage::Mesh` has a shader object bound"
                     .into(),
-                vuids: vuids!(vuid_type, "maintenance4-06425"),
+                // vuids?
                 ..Default::default()
             }));
         }
 
-        let set_bytes = &self.builder_state.push_constants;
+        if shader(ShaderStage::TessellationControl).is_some()
+            != shader(ShaderStage::TessellationEvaluation).is_some()
+        {
+            return Err(Box::new(ValidationError {
+                problem: "graphics shader objects are bound, but only one of \
+                    `ShaderStage::TessellationControl` and `ShaderStage::TessellationEvaluation` \
+                    has a shader object bound"
+                    .into(),
+                // vuids?
+                ..Default::default()
+            }));
+        }
 
-        if !pipeline_layout
-            .push_constant_ranges()
+        let stage_order: &[ShaderStage] = if shader(ShaderStage::Mesh).is_some() {
+            &[ShaderStage::Task, ShaderStage::Mesh, ShaderStage::Fragment]
+        } else {
+            &[
+                ShaderStage::Vertex,
+                ShaderStage::TessellationControl,
+                ShaderStage::TessellationEvaluation,
+                ShaderStage::Geometry,
+                ShaderStage::Fragment,
+            ]
+        };
+        let bound_stages: SmallVec<[&ShaderObject; 5]> = stage_order
             .iter()
-            .all(|pc_range| set_bytes.contains(pc_range.offset..pc_range.offset + pc_range.size))
-        {
+            .filter_map(|&stage| shader(stage))
+            .collect();
+
+        for stages in bound_stages.windows(2) {
+            let (current, next) = (stages[0], stages[1]);
+
+            if !current
+                .next_stages()
+                .contains(ShaderStages::from(next.stage()))
+            {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "the shader object bound to `ShaderStage::{:?}` is followed by the \
+                        shader object bound to `ShaderStage::{:?}`, but its `next_stages` does \
+                        not contain that stage",
+                        current.stage(),
+                        next.stage(),
+                    )
+                    .into(),
+                    // vuids?
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_shader_objects_graphics_render_pass(
+        &self,
+        vuid_type: VUIDType,
+        render_pass_state: &RenderPassState,
+    ) -> Result<(), Box<ValidationError>> {
+        if render_pass_state.contents != SubpassContents::Inline {
             return Err(Box::new(ValidationError {
-                problem: "the currently bound pipeline accesses push constants, but \
-                    not all bytes in the push constant ranges of the pipeline layout of the \
-                    currently bound pipeline have been set"
+                problem: "the contents of the current subpass instance is not \
+                    `SubpassContents::Inline`"
                     .into(),
-                vuids: vuids!(vuid_type, "maintenance4-06425"),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
+        if !matches!(
+            render_pass_state.render_pass,
+            RenderPassStateType::BeginRendering(_)
+        ) {
+            return Err(Box::new(ValidationError {
+                problem: "graphics shader objects are bound, but the current render pass \
+                    instance was not begun with `begin_rendering`"
+                    .into(),
+                vuids: vuids!(vuid_type, "None-08876"),
                 ..Default::default()
             }));
         }
@@ -1512,1266 +2085,3071 @@ where
         Ok(())
     }
 
-    fn validate_pipeline_graphics_dynamic_state(
+    fn validate_shader_objects_graphics_dynamic_state(
         &self,
         vuid_type: VUIDType,
-        pipeline: &GraphicsPipeline,
+        render_pass_state: &RenderPassState,
     ) -> Result<(), Box<ValidationError>> {
-        let device = pipeline.device();
+        let enabled_features = self.device().enabled_features();
+        let state = &self.builder_state;
+        let has_shader = |stage| matches!(state.shaders.get(&stage), Some(Some(_)));
+        let require = |is_set: bool, command: &str| {
+            if is_set {
+                Ok(())
+            } else {
+                Err(Box::new(ValidationError {
+                    problem: format!(
+                        "graphics shader objects are bound, which require the state set by \
+                        `{}`, but this state was either not set, or it was overwritten by a \
+                        more recent `bind_pipeline_graphics` command",
+                        command,
+                    )
+                    .into(),
+                    // vuids?
+                    ..Default::default()
+                }))
+            }
+        };
 
-        for dynamic_state in pipeline.dynamic_state().iter().copied() {
-            match dynamic_state {
-                DynamicState::BlendConstants => {
-                    if self.builder_state.blend_constants.is_none() {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-07835"),
-                            ..Default::default()
-                        }));
-                    }
-                }
-                DynamicState::ColorWriteEnable => {
-                    let enables = if let Some(enables) = &self.builder_state.color_write_enable {
-                        enables
-                    } else {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-07749"),
-                            ..Default::default()
-                        }));
-                    };
+        require(
+            state.viewport_with_count.is_some(),
+            "set_viewport_with_count",
+        )?;
+        require(state.scissor_with_count.is_some(), "set_scissor_with_count")?;
+        require(
+            state.rasterizer_discard_enable.is_some(),
+            "set_rasterizer_discard_enable",
+        )?;
 
-                    if enables.len() < pipeline.color_blend_state().unwrap().attachments.len() {
-                        return Err(Box::new(ValidationError {
-                            problem: "the currently bound graphics pipeline requires the \
-                                `DynamicState::ColorWriteEnable` dynamic state, but \
-                                the number of enable values that were set is less than the number \
-                                of color attachments in the color blend state of the \
-                                graphics pipeline"
-                                .into(),
-                            vuids: vuids!(vuid_type, "attachmentCount-07750"),
-                            ..Default::default()
-                        }));
-                    }
-                }
-                DynamicState::CullMode => {
-                    if self.builder_state.cull_mode.is_none() {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-07840"),
-                            ..Default::default()
-                        }));
-                    }
-                }
-                DynamicState::DepthBias => {
-                    if self.builder_state.depth_bias.is_none() {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-07834"),
-                            ..Default::default()
-                        }));
-                    }
-                }
-                DynamicState::DepthBiasEnable => {
-                    if self.builder_state.depth_bias_enable.is_none() {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-04877"),
-                            ..Default::default()
-                        }));
-                    }
-                }
-                DynamicState::DepthBounds => {
-                    if self.builder_state.depth_bounds.is_none() {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-07836"),
-                            ..Default::default()
-                        }));
-                    }
-                }
-                DynamicState::DepthBoundsTestEnable => {
-                    if self.builder_state.depth_bounds_test_enable.is_none() {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-07846"),
-                            ..Default::default()
-                        }));
-                    }
-                }
-                DynamicState::DepthCompareOp => {
-                    if self.builder_state.depth_compare_op.is_none() {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-07845"),
-                            ..Default::default()
-                        }));
-                    }
-                }
-                DynamicState::DepthTestEnable => {
-                    if self.builder_state.depth_test_enable.is_none() {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-07843"),
-                            ..Default::default()
-                        }));
-                    }
-                }
-                DynamicState::DepthWriteEnable => {
-                    if self.builder_state.depth_write_enable.is_none() {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-07844"),
-                            ..Default::default()
-                        }));
+        if let (Some(viewports), Some(scissors)) =
+            (&state.viewport_with_count, &state.scissor_with_count)
+        {
+            if viewports.len() != scissors.len() {
+                return Err(Box::new(ValidationError {
+                    problem: "graphics shader objects are bound, but the dynamic viewport \
+                        count is not equal to the dynamic scissor count"
+                        .into(),
+                    vuids: vuids!(vuid_type, "viewportCount-03419"),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if has_shader(ShaderStage::Vertex) {
+            require(state.vertex_input.is_some(), "set_vertex_input")?;
+            require(state.primitive_topology.is_some(), "set_primitive_topology")?;
+            require(
+                state.primitive_restart_enable.is_some(),
+                "set_primitive_restart_enable",
+            )?;
+        }
+
+        if has_shader(ShaderStage::TessellationControl) {
+            require(
+                state.patch_control_points.is_some(),
+                "set_patch_control_points",
+            )?;
+        }
+
+        if has_shader(ShaderStage::TessellationEvaluation) {
+            require(
+                state.tessellation_domain_origin.is_some(),
+                "set_tessellation_domain_origin",
+            )?;
+        }
+
+        if state.rasterizer_discard_enable != Some(false) {
+            return Ok(());
+        }
+
+        require(state.polygon_mode.is_some(), "set_polygon_mode")?;
+        require(
+            state.rasterization_samples.is_some(),
+            "set_rasterization_samples",
+        )?;
+        require(state.sample_mask.is_some(), "set_sample_mask")?;
+        require(
+            state.alpha_to_coverage_enable.is_some(),
+            "set_alpha_to_coverage_enable",
+        )?;
+        require(state.cull_mode.is_some(), "set_cull_mode")?;
+        require(state.front_face.is_some(), "set_front_face")?;
+        require(state.depth_test_enable.is_some(), "set_depth_test_enable")?;
+        require(state.depth_write_enable.is_some(), "set_depth_write_enable")?;
+        require(state.depth_bias_enable.is_some(), "set_depth_bias_enable")?;
+        require(
+            state.stencil_test_enable.is_some(),
+            "set_stencil_test_enable",
+        )?;
+
+        if let (Some((samples, _)), Some(rasterization_samples)) =
+            (state.sample_mask, state.rasterization_samples)
+        {
+            if (samples as u32) < (rasterization_samples as u32) {
+                return Err(Box::new(ValidationError {
+                    problem: "graphics shader objects are bound, but the `samples` value \
+                        given to `set_sample_mask` is less than the dynamic rasterization \
+                        sample count"
+                        .into(),
+                    // vuids?
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if enabled_features.alpha_to_one {
+            require(
+                state.alpha_to_one_enable.is_some(),
+                "set_alpha_to_one_enable",
+            )?;
+        }
+
+        if enabled_features.depth_clamp {
+            require(state.depth_clamp_enable.is_some(), "set_depth_clamp_enable")?;
+        }
+
+        if enabled_features.depth_bounds {
+            require(
+                state.depth_bounds_test_enable.is_some(),
+                "set_depth_bounds_test_enable",
+            )?;
+
+            if state.depth_bounds_test_enable == Some(true) {
+                require(state.depth_bounds.is_some(), "set_depth_bounds")?;
+            }
+        }
+
+        if state.depth_test_enable == Some(true) {
+            require(state.depth_compare_op.is_some(), "set_depth_compare_op")?;
+        }
+
+        if state.depth_bias_enable == Some(true) {
+            require(state.depth_bias.is_some(), "set_depth_bias")?;
+        }
+
+        if state.stencil_test_enable == Some(true) {
+            require(
+                state.stencil_op.front.is_some() && state.stencil_op.back.is_some(),
+                "set_stencil_op",
+            )?;
+            require(
+                state.stencil_compare_mask.front.is_some()
+                    && state.stencil_compare_mask.back.is_some(),
+                "set_stencil_compare_mask",
+            )?;
+            require(
+                state.stencil_write_mask.front.is_some() && state.stencil_write_mask.back.is_some(),
+                "set_stencil_write_mask",
+            )?;
+            require(
+                state.stencil_reference.front.is_some() && state.stencil_reference.back.is_some(),
+                "set_stencil_reference",
+            )?;
+        }
+
+        if enabled_features.logic_op {
+            require(state.logic_op_enable.is_some(), "set_logic_op_enable")?;
+
+            if state.logic_op_enable == Some(true) {
+                require(state.logic_op.is_some(), "set_logic_op")?;
+            }
+        }
+
+        let is_line_topology = matches!(
+            state.primitive_topology,
+            Some(
+                PrimitiveTopology::LineList
+                    | PrimitiveTopology::LineStrip
+                    | PrimitiveTopology::LineListWithAdjacency
+                    | PrimitiveTopology::LineStripWithAdjacency
+            )
+        );
+
+        if is_line_topology || state.polygon_mode == Some(PolygonMode::Line) {
+            require(state.line_width.is_some(), "set_line_width")?;
+        }
+
+        let mut uses_blend_constants = false;
+
+        for (index, format) in render_pass_state
+            .rendering_info
+            .color_attachment_formats
+            .iter()
+            .enumerate()
+        {
+            if format.is_none() {
+                continue;
+            }
+
+            let index = index as u32;
+            require(
+                state.color_blend_enable.contains_key(&index),
+                "set_color_blend_enable",
+            )?;
+            require(
+                state.color_write_mask.contains_key(&index),
+                "set_color_write_mask",
+            )?;
+
+            if state.color_blend_enable.get(&index) == Some(&true) {
+                let Some(equation) = state.color_blend_equation.get(&index) else {
+                    return require(false, "set_color_blend_equation");
+                };
+
+                uses_blend_constants |= [
+                    equation.src_color_blend_factor,
+                    equation.dst_color_blend_factor,
+                    equation.src_alpha_blend_factor,
+                    equation.dst_alpha_blend_factor,
+                ]
+                .into_iter()
+                .any(|factor| {
+                    matches!(
+                        factor,
+                        BlendFactor::ConstantColor
+                            | BlendFactor::OneMinusConstantColor
+                            | BlendFactor::ConstantAlpha
+                            | BlendFactor::OneMinusConstantAlpha
+                    )
+                });
+            }
+        }
+
+        if uses_blend_constants {
+            require(state.blend_constants.is_some(), "set_blend_constants")?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the shader objects that are currently bound for `pipeline_bind_point`.
+    fn bound_shader_objects_iter(
+        &self,
+        pipeline_bind_point: PipelineBindPoint,
+    ) -> impl Iterator<Item = &ShaderObject> {
+        self.builder_state
+            .shaders
+            .iter()
+            .filter(move |(&stage, _)| {
+                (stage == ShaderStage::Compute)
+                    == (pipeline_bind_point == PipelineBindPoint::Compute)
+            })
+            .filter_map(|(_, shader)| shader.as_deref())
+    }
+
+    /// Combines the shader objects that are currently bound for `pipeline_bind_point`, so that
+    /// they can be validated like a pipeline. Returns `None` if no shader objects are bound.
+    fn bound_shader_objects(
+        &self,
+        pipeline_bind_point: PipelineBindPoint,
+    ) -> Result<Option<BoundShaderObjects>, Box<ValidationError>> {
+        let mut shaders = self.bound_shader_objects_iter(pipeline_bind_point);
+
+        let Some(first_shader) = shaders.next() else {
+            return Ok(None);
+        };
+
+        let mut descriptor_binding_requirements = first_shader
+            .entry_point()
+            .info()
+            .descriptor_binding_requirements
+            .clone();
+
+        for shader in shaders {
+            for (&loc, reqs) in &shader.entry_point().info().descriptor_binding_requirements {
+                match descriptor_binding_requirements.entry(loc) {
+                    Entry::Occupied(entry) => {
+                        entry.into_mut().merge(reqs).map_err(|err| {
+                            err.add_context(format!(
+                                "the requirements of the bound shader objects for descriptor \
+                                set {}, binding {}",
+                                loc.0, loc.1,
+                            ))
+                        })?;
                     }
-                }
-                DynamicState::DiscardRectangle => {
-                    for num in
-                        0..pipeline.discard_rectangle_state().unwrap().rectangles.len() as u32
-                    {
-                        if !self.builder_state.discard_rectangle.contains_key(&num) {
-                            return Err(Box::new(ValidationError {
-                                problem: format!(
-                                    "the currently bound graphics pipeline requires the \
-                                    `DynamicState::{:?}` dynamic state, but \
-                                    this state was either not set for discard rectangle {1}, or \
-                                    it was overwritten by a more recent \
-                                    `bind_pipeline_graphics` command",
-                                    dynamic_state, num,
-                                )
-                                .into(),
-                                vuids: vuids!(vuid_type, "None-07751"),
-                                ..Default::default()
-                            }));
-                        }
+                    Entry::Vacant(entry) => {
+                        entry.insert(reqs.clone());
                     }
                 }
-                // DynamicState::ExclusiveScissor => todo!(),
-                // DynamicState::FragmentShadingRate => todo!(),
-                DynamicState::FrontFace => {
-                    if self.builder_state.front_face.is_none() {
+            }
+        }
+
+        let num_used_descriptor_sets = descriptor_binding_requirements
+            .keys()
+            .map(|loc| loc.0 + 1)
+            .max()
+            .unwrap_or(0);
+
+        Ok(Some(BoundShaderObjects {
+            bind_point: pipeline_bind_point,
+            layout: first_shader.layout().clone(),
+            num_used_descriptor_sets,
+            descriptor_binding_requirements,
+        }))
+    }
+
+    fn validate_pipeline_descriptor_sets<Pl: Pipeline>(
+        &self,
+        vuid_type: VUIDType,
+        pipeline: &Pl,
+    ) -> Result<(), Box<ValidationError>> {
+        fn validate_resources<T>(
+            vuid_type: VUIDType,
+            set_num: u32,
+            binding_num: u32,
+            binding_reqs: &DescriptorBindingRequirements,
+            partially_bound: bool,
+            elements: &[Option<T>],
+            mut extra_check: impl FnMut(u32, u32, u32, &T) -> Result<(), Box<ValidationError>>,
+        ) -> Result<(), Box<ValidationError>> {
+            let elements_to_check = if let Some(descriptor_count) = binding_reqs.descriptor_count {
+                // The shader has a fixed-sized array, so it will never access more than
+                // the first `descriptor_count` elements.
+                elements.get(..descriptor_count as usize).ok_or_else(|| {
+                    // There are less than `descriptor_count` elements in `elements`
+                    Box::new(ValidationError {
+                        problem: format!(
+                            "the currently bound pipeline accesses the resource bound to \
+                            descriptor set {set_num}, binding {binding_num}, \
+                            descriptor index {}, but no descriptor was written to the \
+                            descriptor set currently bound to set {set_num}",
+                            elements.len()
+                        )
+                        .into(),
+                        vuids: vuids!(vuid_type, "None-02699"),
+                        ..Default::default()
+                    })
+                })?
+            } else {
+                // The shader has a runtime-sized array, so any element could potentially
+                // be accessed. We must check them all.
+                elements
+            };
+
+            for (index, element) in elements_to_check.iter().enumerate() {
+                let index = index as u32;
+
+                let element = match element {
+                    Some(x) => x,
+                    // Descriptors in a partially bound binding only need to be valid if they
+                    // are dynamically used, which can't be known here.
+                    None if partially_bound => continue,
+                    None => {
                         return Err(Box::new(ValidationError {
                             problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
+                                "the currently bound pipeline accesses the resource bound to \
+                                descriptor set {set_num}, binding {binding_num}, \
+                                descriptor index {index}, but no descriptor was written to the \
+                                descriptor set currently bound to set {set_num}"
                             )
                             .into(),
-                            vuids: vuids!(vuid_type, "None-0784"),
+                            vuids: vuids!(vuid_type, "None-02699"),
                             ..Default::default()
                         }));
                     }
-                }
-                DynamicState::LineStipple => {
-                    if self.builder_state.line_stipple.is_none() {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-07849"),
-                            ..Default::default()
-                        }));
+                };
+
+                extra_check(set_num, binding_num, index, element)?;
+            }
+
+            Ok(())
+        }
+
+        if pipeline.num_used_descriptor_sets() == 0 {
+            return Ok(());
+        }
+
+        let descriptor_set_state = self
+            .builder_state
+            .descriptor_sets
+            .get(&pipeline.bind_point())
+            .ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "the currently bound pipeline accesses descriptor sets, but no \
+                    descriptor sets were previously bound"
+                        .into(),
+                    vuids: vuids!(vuid_type, "None-02697"),
+                    ..Default::default()
+                })
+            })?;
+
+        if !pipeline.layout().is_compatible_with(
+            &descriptor_set_state.pipeline_layout,
+            pipeline.num_used_descriptor_sets(),
+        ) {
+            return Err(Box::new(ValidationError {
+                problem: "the currently bound pipeline accesses descriptor sets, but the \
+                    pipeline layouts that were used to bind the descriptor sets are \
+                    not compatible with the pipeline layout of the currently bound pipeline"
+                    .into(),
+                vuids: vuids!(vuid_type, "None-02697"),
+                ..Default::default()
+            }));
+        }
+
+        for (&(set_num, binding_num), binding_reqs) in pipeline.descriptor_binding_requirements() {
+            let layout_binding =
+                &pipeline.layout().set_layouts()[set_num as usize].bindings()[&binding_num];
+
+            let check_buffer =
+                |_set_num: u32,
+                 _binding_num: u32,
+                 _index: u32,
+                 _buffer_info: &DescriptorBufferInfo| Ok(());
+
+            let check_buffer_view =
+                |set_num: u32, binding_num: u32, index: u32, buffer_view: &Arc<BufferView>| {
+                    for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
+                        .chain(binding_reqs.descriptors.get(&None))
+                    {
+                        if layout_binding.descriptor_type == DescriptorType::StorageTexelBuffer {
+                            if binding_reqs.image_format.is_none()
+                                && !desc_reqs.memory_write.is_empty()
+                                && !buffer_view
+                                    .format_features()
+                                    .intersects(FormatFeatures::STORAGE_WRITE_WITHOUT_FORMAT)
+                            {
+                                return Err(Box::new(ValidationError {
+                                    problem: format!(
+                                        "the currently bound pipeline writes to the buffer view \
+                                        bound to descriptor set {set_num}, binding {binding_num}, \
+                                        descriptor index {index}, without specifying a format, \
+                                        but the format features of the buffer view's format do \
+                                        not contain `FormatFeatures::STORAGE_WRITE_WITHOUT_FORMAT`"
+                                    )
+                                    .into(),
+                                    vuids: vuids!(vuid_type, "OpTypeImage-06423"),
+                                    ..Default::default()
+                                }));
+                            }
+
+                            if binding_reqs.image_format.is_none()
+                                && !desc_reqs.memory_read.is_empty()
+                                && !buffer_view
+                                    .format_features()
+                                    .intersects(FormatFeatures::STORAGE_READ_WITHOUT_FORMAT)
+                            {
+                                return Err(Box::new(ValidationError {
+                                    problem: format!(
+                                        "the currently bound pipeline reads from the buffer view \
+                                        bound to descriptor set {set_num}, binding {binding_num}, \
+                                        descriptor index {index}, without specifying a format, \
+                                        but the format features of the buffer view's format do \
+                                        not contain `FormatFeatures::STORAGE_READ_WITHOUT_FORMAT`"
+                                    )
+                                    .into(),
+                                    vuids: vuids!(vuid_type, "OpTypeImage-06424"),
+                                    ..Default::default()
+                                }));
+                            }
+                        }
                     }
-                }
-                DynamicState::LineWidth => {
-                    if self.builder_state.line_width.is_none() {
+
+                    Ok(())
+                };
+
+            let check_image_view_common =
+                |set_num: u32, binding_num: u32, index: u32, image_view: &Arc<ImageView>| {
+                    for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
+                        .chain(binding_reqs.descriptors.get(&None))
+                    {
+                        if desc_reqs.storage_image_atomic
+                            && !image_view
+                                .format_features()
+                                .intersects(FormatFeatures::STORAGE_IMAGE_ATOMIC)
+                        {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "the currently bound pipeline performs atomic operations on \
+                                    the image view bound to descriptor set {set_num}, \
+                                    binding {binding_num}, descriptor index {index}, but \
+                                    the format features of the image view's format do \
+                                    not contain `FormatFeatures::STORAGE_IMAGE_ATOMIC`"
+                                )
+                                .into(),
+                                vuids: vuids!(vuid_type, "None-02691"),
+                                ..Default::default()
+                            }));
+                        }
+
+                        if layout_binding.descriptor_type == DescriptorType::StorageImage {
+                            if binding_reqs.image_format.is_none()
+                                && !desc_reqs.memory_write.is_empty()
+                                && !image_view
+                                    .format_features()
+                                    .intersects(FormatFeatures::STORAGE_WRITE_WITHOUT_FORMAT)
+                            {
+                                return Err(Box::new(ValidationError {
+                                    problem: format!(
+                                        "the currently bound pipeline writes to the image view \
+                                        bound to descriptor set {set_num}, binding {binding_num}, \
+                                        descriptor index {index}, without specifying a format, \
+                                        but the format features of the image view's format do \
+                                        not contain `FormatFeatures::STORAGE_WRITE_WITHOUT_FORMAT`"
+                                    )
+                                    .into(),
+                                    vuids: vuids!(vuid_type, "OpTypeImage-06423"),
+                                    ..Default::default()
+                                }));
+                            }
+
+                            if binding_reqs.image_format.is_none()
+                                && !desc_reqs.memory_read.is_empty()
+                                && !image_view
+                                    .format_features()
+                                    .intersects(FormatFeatures::STORAGE_READ_WITHOUT_FORMAT)
+                            {
+                                return Err(Box::new(ValidationError {
+                                    problem: format!(
+                                        "the currently bound pipeline reads from the image view \
+                                        bound to descriptor set {set_num}, binding {binding_num}, \
+                                        descriptor index {index}, without specifying a format, \
+                                        but the format features of the image view's format do \
+                                        not contain `FormatFeatures::STORAGE_READ_WITHOUT_FORMAT`"
+                                    )
+                                    .into(),
+                                    vuids: vuids!(vuid_type, "OpTypeImage-06424"),
+                                    ..Default::default()
+                                }));
+                            }
+                        }
+                    }
+
+                    /*
+                       Instruction/Sampler/Image View Validation
+                       https://registry.khronos.org/vulkan/specs/1.3-extensions/html/chap16.html#textures-input-validation
+                    */
+
+                    // The SPIR-V Image Format is not compatible with the image view’s format.
+                    if let Some(format) = binding_reqs.image_format {
+                        if image_view.format() != format {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "the currently bound pipeline accesses the image view \
+                                    bound to descriptor set {set_num}, binding {binding_num}, \
+                                    descriptor index {index}, but the format of the image view \
+                                    is not equal to the format required by the pipeline"
+                                )
+                                .into(),
+                                // vuids?
+                                ..Default::default()
+                            }));
+                        }
+                    }
+
+                    // Rules for viewType
+                    if let Some(image_view_type) = binding_reqs.image_view_type {
+                        if image_view.view_type() != image_view_type {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "the currently bound pipeline accesses the image view \
+                                    bound to descriptor set {set_num}, binding {binding_num}, \
+                                    descriptor index {index}, but the view type of the image view \
+                                    is not equal to the view type required by the pipeline"
+                                )
+                                .into(),
+                                // vuids?
+                                ..Default::default()
+                            }));
+                        }
+                    }
+
+                    // - If the image was created with VkImageCreateInfo::samples equal to
+                    //   VK_SAMPLE_COUNT_1_BIT, the instruction must have MS = 0.
+                    // - If the image was created with VkImageCreateInfo::samples not equal to
+                    //   VK_SAMPLE_COUNT_1_BIT, the instruction must have MS = 1.
+                    if binding_reqs.image_multisampled
+                        && image_view.image().samples() == SampleCount::Sample1
+                    {
                         return Err(Box::new(ValidationError {
                             problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
+                                "the currently bound pipeline accesses the image view \
+                                bound to descriptor set {set_num}, binding {binding_num}, \
+                                descriptor index {index}, and the pipeline requires a \
+                                multisampled image, but the image view has only one sample"
                             )
                             .into(),
-                            vuids: vuids!(vuid_type, "None-07833"),
+                            // vuids?
                             ..Default::default()
                         }));
-                    }
-                }
-                DynamicState::LogicOp => {
-                    if self.builder_state.logic_op.is_none() {
+                    } else if !binding_reqs.image_multisampled
+                        && image_view.image().samples() != SampleCount::Sample1
+                    {
                         return Err(Box::new(ValidationError {
                             problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
+                                "the currently bound pipeline accesses the image view \
+                                bound to descriptor set {set_num}, binding {binding_num}, \
+                                descriptor index {index}, and the pipeline requires a non-\
+                                multisampled image, but the image view has more than one sample"
                             )
                             .into(),
-                            vuids: vuids!(vuid_type, "logicOp-04878"),
+                            // vuids?
                             ..Default::default()
                         }));
                     }
-                }
-                DynamicState::PatchControlPoints => {
-                    if self.builder_state.patch_control_points.is_none() {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-04875"),
-                            ..Default::default()
-                        }));
-                    }
-                }
-                DynamicState::PrimitiveRestartEnable => {
-                    let primitive_restart_enable =
-                        if let Some(enable) = self.builder_state.primitive_restart_enable {
-                            enable
-                        } else {
+
+                    // - If the Sampled Type of the OpTypeImage does not match the numeric format of the
+                    //   image, as shown in the SPIR-V Sampled Type column of the
+                    //   Interpretation of Numeric Format table.
+                    // - If the signedness of any read or sample operation does not match the signedness of
+                    //   the image’s format.
+                    if let Some(shader_numeric_type) = binding_reqs.image_scalar_type {
+                        let aspects = image_view.subresource_range().aspects;
+                        let view_numeric_type = NumericType::from(
+                            if aspects.intersects(
+                                ImageAspects::COLOR
+                                    | ImageAspects::PLANE_0
+                                    | ImageAspects::PLANE_1
+                                    | ImageAspects::PLANE_2,
+                            ) {
+                                image_view.format().numeric_format_color().unwrap()
+                            } else if aspects.intersects(ImageAspects::DEPTH) {
+                                image_view.format().numeric_format_depth().unwrap()
+                            } else if aspects.intersects(ImageAspects::STENCIL) {
+                                image_view.format().numeric_format_stencil().unwrap()
+                            } else {
+                                // Per `ImageViewBuilder::aspects` and
+                                // VUID-VkDescriptorImageInfo-imageView-01976
+                                unreachable!()
+                            },
+                        );
+
+                        if shader_numeric_type != view_numeric_type {
                             return Err(Box::new(ValidationError {
                                 problem: format!(
-                                    "the currently bound graphics pipeline requires the \
-                                    `DynamicState::{:?}` dynamic state, but \
-                                    this state was either not set, or it was overwritten by a \
-                                    more recent `bind_pipeline_graphics` command",
-                                    dynamic_state
+                                    "the currently bound pipeline accesses the image view \
+                                    bound to descriptor set {set_num}, binding {binding_num}, \
+                                    descriptor index {index}, and the pipeline requires an \
+                                    image view whose format has a `{shader_numeric_type:?}` \
+                                    numeric type, but the format of the image view has a \
+                                    `{view_numeric_type:?}` numeric type"
                                 )
                                 .into(),
-                                vuids: vuids!(vuid_type, "None-04879"),
+                                // vuids?
                                 ..Default::default()
                             }));
-                        };
-
-                    if primitive_restart_enable {
-                        let topology = if pipeline
-                            .dynamic_state()
-                            .contains(&DynamicState::PrimitiveTopology)
-                        {
-                            if let Some(topology) = self.builder_state.primitive_topology {
-                                topology
-                            } else {
-                                return Err(Box::new(ValidationError {
-                                    problem: "the currently bound graphics pipeline requires \
-                                        the `DynamicState::PrimitiveTopology` dynamic state, \
-                                        but this state was either not set, or it was \
-                                        overwritten by a more recent `bind_pipeline_graphics` \
-                                        command"
-                                        .into(),
-                                    vuids: vuids!(vuid_type, "None-07842"),
-                                    ..Default::default()
-                                }));
-                            }
-                        } else {
-                            pipeline.input_assembly_state().topology
-                        };
-
-                        match topology {
-                            PrimitiveTopology::PointList
-                            | PrimitiveTopology::LineList
-                            | PrimitiveTopology::TriangleList
-                            | PrimitiveTopology::LineListWithAdjacency
-                            | PrimitiveTopology::TriangleListWithAdjacency => {
-                                if !device.enabled_features().primitive_topology_list_restart {
-                                    return Err(Box::new(ValidationError {
-                                        problem: "primitive restart is enabled for the currently \
-                                            bound graphics pipeline, but the currently set \
-                                            dynamic primitive topology is \
-                                            `PrimitiveTopology::*List`"
-                                            .into(),
-                                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[
-                                            Requires::Feature("primitive_topology_list_restart"),
-                                        ])]),
-                                        // vuids?
-                                        ..Default::default()
-                                    }));
-                                }
-                            }
-                            PrimitiveTopology::PatchList => {
-                                if !device
-                                    .enabled_features()
-                                    .primitive_topology_patch_list_restart
-                                {
-                                    return Err(Box::new(ValidationError {
-                                        problem: "primitive restart is enabled for the currently \
-                                            bound graphics pipeline, but the currently set \
-                                            dynamic primitive topology is \
-                                            `PrimitiveTopology::PatchList`"
-                                            .into(),
-                                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[
-                                            Requires::Feature(
-                                                "primitive_topology_patch_list_restart",
-                                            ),
-                                        ])]),
-                                        // vuids?
-                                        ..Default::default()
-                                    }));
-                                }
-                            }
-                            _ => (),
                         }
                     }
-                }
-                DynamicState::PrimitiveTopology => {
-                    let topology = if let Some(topology) = self.builder_state.primitive_topology {
-                        topology
-                    } else {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-07842"),
-                            ..Default::default()
-                        }));
-                    };
 
-                    if pipeline.shader(ShaderStage::TessellationControl).is_some() {
-                        if !matches!(topology, PrimitiveTopology::PatchList) {
+                    Ok(())
+                };
+
+            let check_sampler_common =
+                |set_num: u32, binding_num: u32, index: u32, sampler: &Arc<Sampler>| {
+                    for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
+                        .chain(binding_reqs.descriptors.get(&None))
+                    {
+                        if desc_reqs.sampler_no_unnormalized_coordinates
+                            && sampler.unnormalized_coordinates()
+                        {
                             return Err(Box::new(ValidationError {
                                 problem: format!(
-                                    "the currently bound graphics pipeline requires the \
-                                    `DynamicState::{:?}` dynamic state, and the graphics pipeline \
-                                    includes tessellation shader stages, but the dynamic \
-                                    primitive topology is not `PrimitiveTopology::PatchList`",
-                                    dynamic_state
+                                    "the currently bound pipeline accesses the sampler bound to \
+                                    descriptor set {set_num}, binding {binding_num}, \
+                                    descriptor index {index}, in a way that does not support \
+                                    samplers with unnormalized coordinates, but \
+                                    the sampler currently bound to that descriptor \
+                                    uses unnormalized coordinates"
                                 )
                                 .into(),
-                                // vuids?
+                                vuids: vuids!(vuid_type, "None-02703", "None-02704"),
                                 ..Default::default()
                             }));
                         }
-                    } else {
-                        if matches!(topology, PrimitiveTopology::PatchList) {
+
+                        // - OpImageFetch, OpImageSparseFetch, OpImage*Gather, and OpImageSparse*Gather must not
+                        //   be used with a sampler that enables sampler Y′CBCR conversion.
+                        // - The ConstOffset and Offset operands must not be used with a sampler that enables
+                        //   sampler Y′CBCR conversion.
+                        if desc_reqs.sampler_no_ycbcr_conversion
+                            && sampler.sampler_ycbcr_conversion().is_some()
+                        {
                             return Err(Box::new(ValidationError {
                                 problem: format!(
-                                    "the currently bound graphics pipeline requires the \
-                                    `DynamicState::{:?}` dynamic state, and the graphics pipeline \
-                                    does not include tessellation shader stages, but the dynamic \
-                                    primitive topology is `PrimitiveTopology::PatchList`",
-                                    dynamic_state
+                                    "the currently bound pipeline accesses the sampler bound to \
+                                    descriptor set {set_num}, binding {binding_num}, \
+                                    descriptor index {index}, in a way that does not support \
+                                    samplers with a sampler YCbCr conversion, but the sampler \
+                                    currently bound to that descriptor has a \
+                                    sampler YCbCr conversion"
                                 )
                                 .into(),
                                 // vuids?
                                 ..Default::default()
                             }));
                         }
-                    }
-
-                    let properties = device.physical_device().properties();
 
-                    if !properties
-                        .dynamic_primitive_topology_unrestricted
-                        .unwrap_or(false)
-                    {
-                        let is_same_topology_class = matches!(
-                            (topology, pipeline.input_assembly_state().topology),
-                            (PrimitiveTopology::PointList, PrimitiveTopology::PointList)
-                                | (
-                                    PrimitiveTopology::LineList
-                                        | PrimitiveTopology::LineStrip
-                                        | PrimitiveTopology::LineListWithAdjacency
-                                        | PrimitiveTopology::LineStripWithAdjacency,
-                                    PrimitiveTopology::LineList
-                                        | PrimitiveTopology::LineStrip
-                                        | PrimitiveTopology::LineListWithAdjacency
-                                        | PrimitiveTopology::LineStripWithAdjacency,
-                                )
-                                | (
-                                    PrimitiveTopology::TriangleList
-                                        | PrimitiveTopology::TriangleStrip
-                                        | PrimitiveTopology::TriangleFan
-                                        | PrimitiveTopology::TriangleListWithAdjacency
-                                        | PrimitiveTopology::TriangleStripWithAdjacency,
-                                    PrimitiveTopology::TriangleList
-                                        | PrimitiveTopology::TriangleStrip
-                                        | PrimitiveTopology::TriangleFan
-                                        | PrimitiveTopology::TriangleListWithAdjacency
-                                        | PrimitiveTopology::TriangleStripWithAdjacency,
-                                )
-                                | (PrimitiveTopology::PatchList, PrimitiveTopology::PatchList)
-                        );
+                        /*
+                            Instruction/Sampler/Image View Validation
+                            https://registry.khronos.org/vulkan/specs/1.3-extensions/html/chap16.html#textures-input-validation
+                        */
 
-                        if !is_same_topology_class {
+                        if desc_reqs.sampler_compare && sampler.compare().is_none() {
+                            // - The SPIR-V instruction is one of the OpImage*Dref* instructions and the sampler
+                            //   compareEnable is VK_FALSE
                             return Err(Box::new(ValidationError {
                                 problem: format!(
-                                    "the currently bound graphics pipeline requires the \
-                                    `DynamicState::{:?}` dynamic state, and the \
-                                    `dynamic_primitive_topology_unrestricted` device property is \
-                                    `false`, but the dynamic primitive topology does not belong \
-                                    to the same topology class as the topology that the \
-                                    graphics pipeline was created with",
-                                    dynamic_state
+                                    "the currently bound pipeline accesses the sampler bound to \
+                                    descriptor set {set_num}, binding {binding_num}, \
+                                    descriptor index {index}, in a way that requires a sampler \
+                                    with a compare operation, but the sampler currently bound to \
+                                    that descriptor does not have a compare operation"
                                 )
                                 .into(),
-                                vuids: vuids!(
-                                    vuid_type,
-                                    "dynamicPrimitiveTopologyUnrestricted-07500"
-                                ),
+                                // vuids?
                                 ..Default::default()
                             }));
-                        }
-                    }
+                        } else if !desc_reqs.sampler_compare && sampler.compare().is_some() {
+                            // - The SPIR-V instruction is not one of the OpImage*Dref* instructions and the sampler
+                            //   compareEnable is VK_TRUE
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "the currently bound pipeline accesses the sampler bound to \
+                                    descriptor set {set_num}, binding {binding_num}, \
+                                    descriptor index {index}, in a way that requires a sampler \
+                                    without a compare operation, but the sampler currently bound \
+                                    to that descriptor has a compare operation"
+                                )
+                                .into(),
+                                // vuids?
+                                ..Default::default()
+                            }));
+                        }
+                    }
+
+                    Ok(())
+                };
+
+            let check_image_view =
+                |set_num: u32,
+                 binding_num: u32,
+                 index: u32,
+                 image_view_info: &DescriptorImageViewInfo| {
+                    let DescriptorImageViewInfo {
+                        image_view,
+                        image_layout: _,
+                    } = image_view_info;
+
+                    check_image_view_common(set_num, binding_num, index, image_view)?;
+
+                    if let Some(sampler) = layout_binding.immutable_samplers.get(index as usize) {
+                        check_sampler_common(set_num, binding_num, index, sampler)?;
+                    }
+
+                    Ok(())
+                };
+
+            let check_image_view_sampler = |set_num: u32,
+                                            binding_num: u32,
+                                            index: u32,
+                                            (image_view_info, sampler): &(
+                DescriptorImageViewInfo,
+                Arc<Sampler>,
+            )| {
+                let DescriptorImageViewInfo {
+                    image_view,
+                    image_layout: _,
+                } = image_view_info;
+
+                check_image_view_common(set_num, binding_num, index, image_view)?;
+                check_sampler_common(set_num, binding_num, index, sampler)?;
+
+                Ok(())
+            };
+
+            let check_sampler =
+                |set_num: u32, binding_num: u32, index: u32, sampler: &Arc<Sampler>| {
+                    check_sampler_common(set_num, binding_num, index, sampler)?;
+
+                    for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
+                        .chain(binding_reqs.descriptors.get(&None))
+                    {
+                        // Check sampler-image compatibility. Only done for separate samplers;
+                        // combined image samplers are checked when updating the descriptor set.
+
+                        // If the image view isn't actually present in the resources, then just skip it.
+                        // It will be caught later by check_resources.
+                        let iter = desc_reqs.sampler_with_images.iter().filter_map(|id| {
+                            descriptor_set_state
+                                .descriptor_sets
+                                .get(&id.set)
+                                .and_then(|set| set.resources().binding(id.binding))
+                                .and_then(|res| match res {
+                                    DescriptorBindingResources::ImageView(elements) => elements
+                                        .get(id.index as usize)
+                                        .and_then(|opt| opt.as_ref().map(|opt| (id, opt))),
+                                    _ => None,
+                                })
+                        });
+
+                        for (id, image_view_info) in iter {
+                            let DescriptorIdentifier {
+                                set: iset_num,
+                                binding: ibinding_num,
+                                index: iindex,
+                            } = id;
+                            let DescriptorImageViewInfo {
+                                image_view,
+                                image_layout: _,
+                            } = image_view_info;
+
+                            if let Err(error) = sampler.check_can_sample(image_view.as_ref()) {
+                                return Err(Box::new(ValidationError {
+                                    problem: format!(
+                                        "the currently bound pipeline uses the sampler bound to \
+                                        descriptor set {set_num}, binding {binding_num}, \
+                                        descriptor index {index}, to sample the image bound to \
+                                        descriptor set {iset_num}, binding {ibinding_num}, \
+                                        descriptor index {iindex}, but the sampler is not \
+                                        compatible with the image: {}",
+                                        error,
+                                    )
+                                    .into(),
+                                    // vuids?
+                                    ..Default::default()
+                                }));
+                            }
+                        }
+                    }
+
+                    Ok(())
+                };
+
+            let check_acceleration_structure =
+                |_set_num: u32,
+                 _binding_num: u32,
+                 _index: u32,
+                 _acceleration_structure: &Arc<AccelerationStructure>| Ok(());
+
+            let check_none = |set_num: u32, binding_num: u32, index: u32, _: &()| {
+                if let Some(sampler) = layout_binding.immutable_samplers.get(index as usize) {
+                    check_sampler(set_num, binding_num, index, sampler)?;
+                }
+
+                Ok(())
+            };
+
+            let set_resources = descriptor_set_state
+                .descriptor_sets
+                .get(&set_num)
+                .ok_or_else(|| {
+                    Box::new(ValidationError {
+                        problem: format!(
+                            "the currently bound pipeline accesses descriptor set {set_num}, but \
+                        no descriptor set was previously bound"
+                        )
+                        .into(),
+                        // vuids?
+                        ..Default::default()
+                    })
+                })?
+                .resources();
+
+            let binding_resources = set_resources.binding(binding_num).unwrap();
+            let partially_bound = layout_binding
+                .binding_flags
+                .intersects(DescriptorBindingFlags::PARTIALLY_BOUND);
+
+            match binding_resources {
+                DescriptorBindingResources::None(elements) => {
+                    validate_resources(
+                        vuid_type,
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        partially_bound,
+                        elements,
+                        check_none,
+                    )?;
+                }
+                DescriptorBindingResources::Buffer(elements) => {
+                    validate_resources(
+                        vuid_type,
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        partially_bound,
+                        elements,
+                        check_buffer,
+                    )?;
+                }
+                DescriptorBindingResources::BufferView(elements) => {
+                    validate_resources(
+                        vuid_type,
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        partially_bound,
+                        elements,
+                        check_buffer_view,
+                    )?;
+                }
+                DescriptorBindingResources::ImageView(elements) => {
+                    validate_resources(
+                        vuid_type,
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        partially_bound,
+                        elements,
+                        check_image_view,
+                    )?;
+                }
+                DescriptorBindingResources::ImageViewSampler(elements) => {
+                    validate_resources(
+                        vuid_type,
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        partially_bound,
+                        elements,
+                        check_image_view_sampler,
+                    )?;
+                }
+                DescriptorBindingResources::Sampler(elements) => {
+                    validate_resources(
+                        vuid_type,
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        partially_bound,
+                        elements,
+                        check_sampler,
+                    )?;
+                }
+                // Spec:
+                // Descriptor bindings with descriptor type of
+                // VK_DESCRIPTOR_TYPE_INLINE_UNIFORM_BLOCK can be undefined when
+                // the descriptor set is consumed; though values in that block will be undefined.
+                //
+                // TODO: We *may* still want to validate this?
+                DescriptorBindingResources::InlineUniformBlock => (),
+                DescriptorBindingResources::AccelerationStructure(elements) => {
+                    validate_resources(
+                        vuid_type,
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        partially_bound,
+                        elements,
+                        check_acceleration_structure,
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_pipeline_push_constants(
+        &self,
+        vuid_type: VUIDType,
+        pipeline_layout: &PipelineLayout,
+    ) -> Result<(), Box<ValidationError>> {
+        if pipeline_layout.push_constant_ranges().is_empty()
+            || self.device().enabled_features().maintenance4
+        {
+            return Ok(());
+        }
+
+        let constants_pipeline_layout = self
+            .builder_state
+            .push_constants_pipeline_layout
+            .as_ref()
+            .ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "the currently bound pipeline accesses push constants, but no \
+                    push constants were previously set"
+                        .into(),
+                    vuids: vuids!(vuid_type, "maintenance4-06425"),
+                    ..Default::default()
+                })
+            })?;
+
+        if pipeline_layout.handle() != constants_pipeline_layout.handle()
+            && pipeline_layout.push_constant_ranges()
+                != constants_pipeline_layout.push_constant_ranges()
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the currently bound pipeline accesses push constants, but the \
+                    pipeline layouts that were used to set the push constants are \
+                    not compatible with the pipeline layout of the currently bound pipeline"
+                    .into(),
+                vuids: vuids!(vuid_type, "maintenance4-06425"),
+                ..Default::default()
+            }));
+        }
+
+        let set_bytes = &self.builder_state.push_constants;
+
+        if !pipeline_layout
+            .push_constant_ranges()
+            .iter()
+            .all(|pc_range| set_bytes.contains(pc_range.offset..pc_range.offset + pc_range.size))
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the currently bound pipeline accesses push constants, but \
+                    not all bytes in the push constant ranges of the pipeline layout of the \
+                    currently bound pipeline have been set"
+                    .into(),
+                vuids: vuids!(vuid_type, "maintenance4-06425"),
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    fn validate_pipeline_graphics_dynamic_state(
+        &self,
+        vuid_type: VUIDType,
+        pipeline: &GraphicsPipeline,
+    ) -> Result<(), Box<ValidationError>> {
+        let device = pipeline.device();
+
+        for dynamic_state in pipeline.dynamic_state().iter().copied() {
+            match dynamic_state {
+                DynamicState::BlendConstants => {
+                    if self.builder_state.blend_constants.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07835"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::ColorWriteEnable => {
+                    let enables = if let Some(enables) = &self.builder_state.color_write_enable {
+                        enables
+                    } else {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07749"),
+                            ..Default::default()
+                        }));
+                    };
+
+                    if enables.len() < pipeline.color_blend_state().unwrap().attachments.len() {
+                        return Err(Box::new(ValidationError {
+                            problem: "the currently bound graphics pipeline requires the \
+                                `DynamicState::ColorWriteEnable` dynamic state, but \
+                                the number of enable values that were set is less than the number \
+                                of color attachments in the color blend state of the \
+                                graphics pipeline"
+                                .into(),
+                            vuids: vuids!(vuid_type, "attachmentCount-07750"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::CullMode => {
+                    if self.builder_state.cull_mode.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07840"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::DepthBias => {
+                    if self.builder_state.depth_bias.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07834"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::DepthBiasEnable => {
+                    if self.builder_state.depth_bias_enable.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-04877"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::DepthBounds => {
+                    if self.builder_state.depth_bounds.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07836"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::DepthBoundsTestEnable => {
+                    if self.builder_state.depth_bounds_test_enable.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07846"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::DepthClampEnable => {
+                    if self.builder_state.depth_clamp_enable.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07620"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::DepthClipEnable => {
+                    if self.builder_state.depth_clip_enable.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07633"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::DepthCompareOp => {
+                    if self.builder_state.depth_compare_op.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07845"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::DepthTestEnable => {
+                    if self.builder_state.depth_test_enable.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07843"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::DepthWriteEnable => {
+                    if self.builder_state.depth_write_enable.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07844"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::DiscardRectangle => {
+                    let rectangle_count = pipeline
+                        .discard_rectangle_state()
+                        .map_or(0, |state| state.rectangles.len() as u32);
+
+                    for num in 0..rectangle_count {
+                        if !self.builder_state.discard_rectangle.contains_key(&num) {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "the currently bound graphics pipeline requires the \
+                                    `DynamicState::{:?}` dynamic state, but \
+                                    this state was either not set for discard rectangle {1}, or \
+                                    it was overwritten by a more recent \
+                                    `bind_pipeline_graphics` command",
+                                    dynamic_state, num,
+                                )
+                                .into(),
+                                vuids: vuids!(vuid_type, "None-07751"),
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                }
+                DynamicState::DiscardRectangleEnable => {
+                    if self.builder_state.discard_rectangle_enable.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07880"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::DiscardRectangleMode => {
+                    if self.builder_state.discard_rectangle_mode.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07881"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                // DynamicState::ExclusiveScissor => todo!(),
+                DynamicState::FragmentShadingRate => {
+                    if self.builder_state.fragment_shading_rate.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-09238"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::FrontFace => {
+                    if self.builder_state.front_face.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-0784"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::LineStipple => {
+                    if self.builder_state.line_stipple.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07849"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::LineWidth => {
+                    if self.builder_state.line_width.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07833"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::LogicOp => {
+                    if self.builder_state.logic_op.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "logicOp-04878"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::PatchControlPoints => {
+                    if self.builder_state.patch_control_points.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-04875"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::PrimitiveRestartEnable => {
+                    let primitive_restart_enable =
+                        if let Some(enable) = self.builder_state.primitive_restart_enable {
+                            enable
+                        } else {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "the currently bound graphics pipeline requires the \
+                                    `DynamicState::{:?}` dynamic state, but \
+                                    this state was either not set, or it was overwritten by a \
+                                    more recent `bind_pipeline_graphics` command",
+                                    dynamic_state
+                                )
+                                .into(),
+                                vuids: vuids!(vuid_type, "None-04879"),
+                                ..Default::default()
+                            }));
+                        };
+
+                    if primitive_restart_enable {
+                        let topology = if pipeline
+                            .dynamic_state()
+                            .contains(&DynamicState::PrimitiveTopology)
+                        {
+                            if let Some(topology) = self.builder_state.primitive_topology {
+                                topology
+                            } else {
+                                return Err(Box::new(ValidationError {
+                                    problem: "the currently bound graphics pipeline requires \
+                                        the `DynamicState::PrimitiveTopology` dynamic state, \
+                                        but this state was either not set, or it was \
+                                        overwritten by a more recent `bind_pipeline_graphics` \
+                                        command"
+                                        .into(),
+                                    vuids: vuids!(vuid_type, "None-07842"),
+                                    ..Default::default()
+                                }));
+                            }
+                        } else {
+                            pipeline
+                                .input_assembly_state()
+                                .expect(
+                                    "mesh shading pipelines can't have \
+                                    `DynamicState::PrimitiveRestartEnable`, so the pipeline has \
+                                    input assembly state",
+                                )
+                                .topology
+                        };
+
+                        match topology {
+                            PrimitiveTopology::PointList
+                            | PrimitiveTopology::LineList
+                            | PrimitiveTopology::TriangleList
+                            | PrimitiveTopology::LineListWithAdjacency
+                            | PrimitiveTopology::TriangleListWithAdjacency => {
+                                if !device.enabled_features().primitive_topology_list_restart {
+                                    return Err(Box::new(ValidationError {
+                                        problem: "primitive restart is enabled for the currently \
+                                            bound graphics pipeline, but the currently set \
+                                            dynamic primitive topology is \
+                                            `PrimitiveTopology::*List`"
+                                            .into(),
+                                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[
+                                            Requires::Feature("primitive_topology_list_restart"),
+                                        ])]),
+                                        // vuids?
+                                        ..Default::default()
+                                    }));
+                                }
+                            }
+                            PrimitiveTopology::PatchList => {
+                                if !device
+                                    .enabled_features()
+                                    .primitive_topology_patch_list_restart
+                                {
+                                    return Err(Box::new(ValidationError {
+                                        problem: "primitive restart is enabled for the currently \
+                                            bound graphics pipeline, but the currently set \
+                                            dynamic primitive topology is \
+                                            `PrimitiveTopology::PatchList`"
+                                            .into(),
+                                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[
+                                            Requires::Feature(
+                                                "primitive_topology_patch_list_restart",
+                                            ),
+                                        ])]),
+                                        // vuids?
+                                        ..Default::default()
+                                    }));
+                                }
+                            }
+                            _ => (),
+                        }
+                    }
+                }
+                DynamicState::PrimitiveTopology => {
+                    let topology = if let Some(topology) = self.builder_state.primitive_topology {
+                        topology
+                    } else {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07842"),
+                            ..Default::default()
+                        }));
+                    };
+
+                    if pipeline.shader(ShaderStage::TessellationControl).is_some() {
+                        if !matches!(topology, PrimitiveTopology::PatchList) {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "the currently bound graphics pipeline requires the \
+                                    `DynamicState::{:?}` dynamic state, and the graphics pipeline \
+                                    includes tessellation shader stages, but the dynamic \
+                                    primitive topology is not `PrimitiveTopology::PatchList`",
+                                    dynamic_state
+                                )
+                                .into(),
+                                // vuids?
+                                ..Default::default()
+                            }));
+                        }
+                    } else {
+                        if matches!(topology, PrimitiveTopology::PatchList) {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "the currently bound graphics pipeline requires the \
+                                    `DynamicState::{:?}` dynamic state, and the graphics pipeline \
+                                    does not include tessellation shader stages, but the dynamic \
+                                    primitive topology is `PrimitiveTopology::PatchList`",
+                                    dynamic_state
+                                )
+                                .into(),
+                                // vuids?
+                                ..Default::default()
+                            }));
+                        }
+                    }
+
+                    let properties = device.physical_device().properties();
+
+                    if !properties
+                        .dynamic_primitive_topology_unrestricted
+                        .unwrap_or(false)
+                    {
+                        let pipeline_topology = pipeline
+                            .input_assembly_state()
+                            .expect(
+                                "mesh shading pipelines can't have \
+                                `DynamicState::PrimitiveTopology`, so the pipeline has input \
+                                assembly state",
+                            )
+                            .topology;
+                        let is_same_topology_class = matches!(
+                            (topology, pipeline_topology),
+                            (PrimitiveTopology::PointList, PrimitiveTopology::PointList)
+                                | (
+                                    PrimitiveTopology::LineList
+                                        | PrimitiveTopology::LineStrip
+                                        | PrimitiveTopology::LineListWithAdjacency
+                                        | PrimitiveTopology::LineStripWithAdjacency,
+                                    PrimitiveTopology::LineList
+                                        | PrimitiveTopology::LineStrip
+                                        | PrimitiveTopology::LineListWithAdjacency
+                                        | PrimitiveTopology::LineStripWithAdjacency,
+                                )
+                                | (
+                                    PrimitiveTopology::TriangleList
+                                        | PrimitiveTopology::TriangleStrip
+                                        | PrimitiveTopology::TriangleFan
+                                        | PrimitiveTopology::TriangleListWithAdjacency
+                                        | PrimitiveTopology::TriangleStripWithAdjacency,
+                                    PrimitiveTopology::TriangleList
+                                        | PrimitiveTopology::TriangleStrip
+                                        | PrimitiveTopology::TriangleFan
+                                        | PrimitiveTopology::TriangleListWithAdjacency
+                                        | PrimitiveTopology::TriangleStripWithAdjacency,
+                                )
+                                | (PrimitiveTopology::PatchList, PrimitiveTopology::PatchList)
+                        );
+
+                        if !is_same_topology_class {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "the currently bound graphics pipeline requires the \
+                                    `DynamicState::{:?}` dynamic state, and the \
+                                    `dynamic_primitive_topology_unrestricted` device property is \
+                                    `false`, but the dynamic primitive topology does not belong \
+                                    to the same topology class as the topology that the \
+                                    graphics pipeline was created with",
+                                    dynamic_state
+                                )
+                                .into(),
+                                vuids: vuids!(
+                                    vuid_type,
+                                    "dynamicPrimitiveTopologyUnrestricted-07500"
+                                ),
+                                ..Default::default()
+                            }));
+                        }
+                    }
+
+                    // TODO: check that the topology matches the geometry shader
+                }
+                DynamicState::SampleLocationsEnable => {
+                    if self.builder_state.sample_locations_enable.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "VUID-vkCmdDraw-None-07634"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::SampleLocations => {
+                    if self.builder_state.sample_locations.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "VUID-vkCmdDraw-None-06666"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::ProvokingVertexMode => {
+                    if self.builder_state.provoking_vertex_mode.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07636"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::RasterizerDiscardEnable => {
+                    if self.builder_state.rasterizer_discard_enable.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-04876"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                // DynamicState::RayTracingPipelineStackSize => unreachable!(
+                //     "RayTracingPipelineStackSize dynamic state should not occur on a graphics pipeline"
+                // ),
+                DynamicState::Scissor => {
+                    let viewport_state = pipeline.viewport_state().unwrap();
+
+                    for num in 0..viewport_state.scissors.len() as u32 {
+                        if !self.builder_state.scissor.contains_key(&num) {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "the currently bound graphics pipeline requires the \
+                                    `DynamicState::{:?}` dynamic state, but \
+                                    this state was either not set, or it was overwritten by a \
+                                    more recent `bind_pipeline_graphics` command",
+                                    dynamic_state
+                                )
+                                .into(),
+                                vuids: vuids!(vuid_type, "None-07832"),
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                }
+                DynamicState::ScissorWithCount => {
+                    if let Some(scissors) = &self.builder_state.scissor_with_count {
+                        let viewport_state = pipeline.viewport_state().unwrap();
+                        let viewport_count = viewport_state.viewports.len() as u32;
+                        let scissor_count = scissors.len() as u32;
+
+                        if viewport_count != 0 {
+                            // Check if the counts match, but only if the viewport count is fixed.
+                            // If the viewport count is also dynamic, then the
+                            // DynamicState::ViewportWithCount match arm will handle it.
+                            if viewport_count != scissor_count {
+                                return Err(Box::new(ValidationError {
+                                    problem: "the currently bound graphics pipeline requires the \
+                                        `DynamicState::ScissorWithCount` dynamic state, and \
+                                        not the `DynamicState::ViewportWithCount` dynamic state, \
+                                        but the dynamic scissor count is not equal to the \
+                                        viewport count specified when creating the pipeline"
+                                        .into(),
+                                    vuids: vuids!(vuid_type, "scissorCount-03418"),
+                                    ..Default::default()
+                                }));
+                            }
+                        }
+                    } else {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "scissorCount-03418", "viewportCount-03419"),
+                            ..Default::default()
+                        }));
+                    };
+                }
+                DynamicState::StencilCompareMask => {
+                    let state = self.builder_state.stencil_compare_mask;
+
+                    if state.front.is_none() || state.back.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07837"),
+                            ..Default::default() //
+                        }));
+                    }
+                }
+                DynamicState::StencilOp => {
+                    let state = self.builder_state.stencil_op;
+
+                    if state.front.is_none() || state.back.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07848"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::StencilReference => {
+                    let state = self.builder_state.stencil_reference;
+
+                    if state.front.is_none() || state.back.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07839"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::StencilTestEnable => {
+                    if self.builder_state.stencil_test_enable.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07847"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::StencilWriteMask => {
+                    let state = self.builder_state.stencil_write_mask;
+
+                    if state.front.is_none() || state.back.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-07838"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                // DynamicState::VertexInput => todo!(),
+                DynamicState::VertexInputBindingStride => {
+                    let vertex_input_state = pipeline.vertex_input_state().expect(
+                        "mesh shading pipelines can't have \
+                        `DynamicState::VertexInputBindingStride`, so the pipeline has vertex \
+                        input state",
+                    );
+
+                    for &binding_num in vertex_input_state.bindings.keys() {
+                        if !self
+                            .builder_state
+                            .vertex_input_binding_stride
+                            .contains_key(&binding_num)
+                        {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "the currently bound graphics pipeline requires the \
+                                    `DynamicState::{:?}` dynamic state, but \
+                                    this state was either not set for binding {}, or it was \
+                                    overwritten by a more recent `bind_pipeline_graphics` command",
+                                    dynamic_state, binding_num
+                                )
+                                .into(),
+                                vuids: vuids!(vuid_type, "pStrides-04913"),
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                }
+                DynamicState::Viewport => {
+                    let viewport_state = pipeline.viewport_state().unwrap();
+
+                    for num in 0..viewport_state.viewports.len() as u32 {
+                        if !self.builder_state.viewport.contains_key(&num) {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "the currently bound graphics pipeline requires the \
+                                    `DynamicState::{:?}` dynamic state, but \
+                                    this state was either not set, or it was overwritten by a \
+                                    more recent `bind_pipeline_graphics` command",
+                                    dynamic_state
+                                )
+                                .into(),
+                                vuids: vuids!(vuid_type, "None-07831"),
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                }
+                // DynamicState::ViewportCoarseSampleOrder => todo!(),
+                // DynamicState::ViewportShadingRatePalette => todo!(),
+                DynamicState::ViewportWithCount => {
+                    if let Some(viewports) = &self.builder_state.viewport_with_count {
+                        let viewport_state = pipeline.viewport_state().unwrap();
+                        let scissor_count = viewport_state.scissors.len() as u32;
+                        let viewport_count = viewports.len() as u32;
+
+                        if scissor_count != 0 {
+                            if viewport_count != scissor_count {
+                                return Err(Box::new(ValidationError {
+                                    problem: "the currently bound graphics pipeline requires the \
+                                        `DynamicState::ViewportWithCount` dynamic state, and \
+                                        not the `DynamicState::ScissorWithCount` dynamic state, \
+                                        but the dynamic scissor count is not equal to the scissor \
+                                        count specified when creating the pipeline"
+                                        .into(),
+                                    vuids: vuids!(vuid_type, "viewportCount-03417"),
+                                    ..Default::default()
+                                }));
+                            }
+                        } else {
+                            if let Some(scissors) = &self.builder_state.scissor_with_count {
+                                if viewport_count != scissors.len() as u32 {
+                                    return Err(Box::new(ValidationError {
+                                        problem:
+                                            "the currently bound graphics pipeline requires both \
+                                            the `DynamicState::ViewportWithCount` and the \
+                                            `DynamicState::ScissorWithCount` dynamic states, but \
+                                            the dynamic scissor count is not equal to the \
+                                            dynamic scissor count "
+                                                .into(),
+                                        vuids: vuids!(vuid_type, "viewportCount-03419"),
+                                        ..Default::default()
+                                    }));
+                                }
+                            } else {
+                                return Err(Box::new(ValidationError {
+                                    problem: format!(
+                                        "the currently bound graphics pipeline requires the \
+                                        `DynamicState::{:?}` dynamic state, but \
+                                        this state was either not set, or it was overwritten by a \
+                                        more recent `bind_pipeline_graphics` command",
+                                        dynamic_state
+                                    )
+                                    .into(),
+                                    vuids: vuids!(
+                                        vuid_type,
+                                        "scissorCount-03418",
+                                        "viewportCount-03419"
+                                    ),
+                                    ..Default::default()
+                                }));
+                            }
+                        }
+                    } else {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "viewportCount-03417", "viewportCount-03419"),
+                            ..Default::default()
+                        }));
+                    }
+
+                    // TODO: VUID-vkCmdDrawIndexed-primitiveFragmentShadingRateWithMultipleViewports-04552
+                    // If the primitiveFragmentShadingRateWithMultipleViewports limit is not supported,
+                    // the bound graphics pipeline was created with the
+                    // VK_DYNAMIC_STATE_VIEWPORT_WITH_COUNT_EXT dynamic state enabled, and any of the
+                    // shader stages of the bound graphics pipeline write to the PrimitiveShadingRateKHR
+                    // built-in, then vkCmdSetViewportWithCountEXT must have been called in the current
+                    // command buffer prior to this drawing command, and the viewportCount parameter of
+                    // vkCmdSetViewportWithCountEXT must be 1
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_pipeline_graphics_render_pass(
+        &self,
+        vuid_type: VUIDType,
+        pipeline: &GraphicsPipeline,
+        render_pass_state: &RenderPassState,
+    ) -> Result<(), Box<ValidationError>> {
+        if render_pass_state.contents != SubpassContents::Inline {
+            return Err(Box::new(ValidationError {
+                problem: "the contents of the current subpass instance is not \
+                    `SubpassContents::Inline`"
+                    .into(),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
+        let pipeline_subpass = pipeline
+            .subpass()
+            .expect("a bound graphics pipeline is never a library, so it always has a subpass");
+
+        match (&render_pass_state.render_pass, pipeline_subpass) {
+            (
+                RenderPassStateType::BeginRenderPass(state),
+                PipelineSubpassType::BeginRenderPass(pipeline_subpass),
+            ) => {
+                if !pipeline_subpass
+                    .render_pass()
+                    .is_compatible_with(state.subpass.render_pass())
+                {
+                    return Err(Box::new(ValidationError {
+                        problem: "the current render pass instance is not compatible with the \
+                            render pass that the currently bound graphics pipeline was \
+                            created with"
+                            .into(),
+                        vuids: vuids!(vuid_type, "renderPass-02684"),
+                        ..Default::default()
+                    }));
+                }
+
+                if pipeline_subpass.index() != state.subpass.index() {
+                    return Err(Box::new(ValidationError {
+                        problem: "the subpass index of the current render pass instance is not \
+                            equal to the index of the subpass that the bound graphics pipeline was \
+                            created with"
+                            .into(),
+                        vuids: vuids!(vuid_type, "subpass-02685"),
+                        ..Default::default()
+                    }));
+                }
+            }
+            (
+                RenderPassStateType::BeginRendering(_),
+                PipelineSubpassType::BeginRendering(pipeline_rendering_info),
+            ) => {
+                if pipeline_rendering_info.view_mask != render_pass_state.rendering_info.view_mask {
+                    return Err(Box::new(ValidationError {
+                        problem: "the `view_mask` of the current render pass instance is not \
+                            equal to the `view_mask` the bound graphics pipeline was created with"
+                            .into(),
+                        vuids: vuids!(vuid_type, "viewMask-06178"),
+                        ..Default::default()
+                    }));
+                }
+
+                if pipeline_rendering_info.color_attachment_formats.len()
+                    != render_pass_state
+                        .rendering_info
+                        .color_attachment_formats
+                        .len()
+                {
+                    return Err(Box::new(ValidationError {
+                        problem: "`color_attachments.len()` of the current render pass \
+                            instance is not equal to `color_attachment_formats.len()` that the
+                            currently bound graphics pipeline was created with"
+                            .into(),
+                        vuids: vuids!(vuid_type, "colorAttachmentCount-06179"),
+                        ..Default::default()
+                    }));
+                }
+
+                for (color_attachment_index, required_format, pipeline_format) in render_pass_state
+                    .rendering_info
+                    .color_attachment_formats
+                    .iter()
+                    .zip(
+                        pipeline_rendering_info
+                            .color_attachment_formats
+                            .iter()
+                            .copied(),
+                    )
+                    .enumerate()
+                    .filter_map(|(i, (r, p))| r.map(|r| (i as u32, r, p)))
+                {
+                    if Some(required_format) != pipeline_format {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "`color_attachments[{0}].image_view.format()` of the current \
+                                render pass instance is not equal to \
+                                `color_attachment_formats[{0}]` that the currently bound \
+                                graphics pipeline was created with",
+                                color_attachment_index,
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "colorAttachmentCount-06180"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+
+                if let Some((required_format, pipeline_format)) = render_pass_state
+                    .rendering_info
+                    .depth_attachment_format
+                    .map(|r| (r, pipeline_rendering_info.depth_attachment_format))
+                {
+                    if Some(required_format) != pipeline_format {
+                        return Err(Box::new(ValidationError {
+                            problem: "`depth_attachment.image_view.format()` of the current \
+                                render pass instance is not equal to \
+                                `depth_attachment_format` that the currently bound \
+                                graphics pipeline was created with"
+                                .into(),
+                            vuids: vuids!(vuid_type, "pDepthAttachment-06181"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+
+                if let Some((required_format, pipeline_format)) = render_pass_state
+                    .rendering_info
+                    .stencil_attachment_format
+                    .map(|r| (r, pipeline_rendering_info.stencil_attachment_format))
+                {
+                    if Some(required_format) != pipeline_format {
+                        return Err(Box::new(ValidationError {
+                            problem: "`stencil_attachment.image_view.format()` of the current \
+                                render pass instance is not equal to \
+                                `stencil_attachment_format` that the currently bound \
+                                graphics pipeline was created with"
+                                .into(),
+                            vuids: vuids!(vuid_type, "pStencilAttachment-06182"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+
+                // VUID-vkCmdDraw-imageView-06172
+                // VUID-vkCmdDraw-imageView-06173
+                // VUID-vkCmdDraw-imageView-06174
+                // VUID-vkCmdDraw-imageView-06175
+                // VUID-vkCmdDraw-imageView-06176
+                // VUID-vkCmdDraw-imageView-06177
+                // TODO:
+            }
+            (RenderPassStateType::BeginRenderPass(_), PipelineSubpassType::BeginRendering(_)) => {
+                return Err(Box::new(ValidationError {
+                    problem: "the current render pass instance was begun with \
+                        `begin_render_pass`, but the currently bound graphics pipeline requires \
+                        a render pass instance begun with `begin_rendering`"
+                        .into(),
+                    // vuids?
+                    ..Default::default()
+                }));
+            }
+            (RenderPassStateType::BeginRendering(_), PipelineSubpassType::BeginRenderPass(_)) => {
+                return Err(Box::new(ValidationError {
+                    problem: "the current render pass instance was begun with \
+                        `begin_rendering`, but the currently bound graphics pipeline requires \
+                        a render pass instance begun with `begin_render_pass`"
+                        .into(),
+                    // vuids?
+                    ..Default::default()
+                }));
+            }
+        }
+
+        // VUID-vkCmdDraw-None-02686
+        // TODO:
+
+        Ok(())
+    }
+
+    /// Returns the vertex input state of the bound pipeline or shader objects if the validation
+    /// succeeds.
+    fn validate_graphics_vertex_buffers<'a>(
+        &'a self,
+        vuid_type: VUIDType,
+        graphics: BoundGraphics<'a>,
+    ) -> Result<&'a VertexInputState, Box<ValidationError>> {
+        let vertex_input = match graphics {
+            BoundGraphics::Pipeline(pipeline) => {
+                pipeline.vertex_input_state().ok_or_else(|| {
+                    Box::new(ValidationError {
+                        problem: "the currently bound graphics pipeline uses mesh shading; \
+                        use the `draw_mesh_tasks` commands instead"
+                            .into(),
+                        vuids: vuids!(vuid_type, "stage-06481"),
+                        ..Default::default()
+                    })
+                })?
+            }
+            BoundGraphics::ShaderObjects(_) => {
+                if !graphics.has_shader(ShaderStage::Vertex) {
+                    return Err(Box::new(ValidationError {
+                        problem: "the currently bound graphics shader objects use mesh shading; \
+                            use the `draw_mesh_tasks` commands instead"
+                            .into(),
+                        // vuids?
+                        ..Default::default()
+                    }));
+                }
+
+                self.builder_state.vertex_input.as_ref().ok_or_else(|| {
+                    Box::new(ValidationError {
+                        problem: "graphics shader objects are bound, which require the state \
+                            set by `set_vertex_input`, but this state was either not set, or it \
+                            was overwritten by a more recent `bind_pipeline_graphics` command"
+                            .into(),
+                        // vuids?
+                        ..Default::default()
+                    })
+                })?
+            }
+        };
+
+        for &binding_num in vertex_input.bindings.keys() {
+            if !self.builder_state.vertex_buffers.contains_key(&binding_num) {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "the currently bound graphics pipeline or shader objects use \
+                        vertex buffer binding {0}, but \
+                        no vertex buffer is currently bound to binding {0}",
+                        binding_num
+                    )
+                    .into(),
+                    vuids: vuids!(vuid_type, "None-04007"),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(vertex_input)
+    }
+
+    fn validate_graphics_mesh_shading(
+        &self,
+        vuid_type: VUIDType,
+        graphics: BoundGraphics<'_>,
+    ) -> Result<(), Box<ValidationError>> {
+        if !graphics.has_shader(ShaderStage::Mesh) {
+            return Err(Box::new(ValidationError {
+                problem: "the currently bound graphics pipeline or shader objects do not \
+                    contain a `ShaderStage::Mesh` stage"
+                    .into(),
+                vuids: vuids!(vuid_type, "MeshEXT-07087"),
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the stride of a vertex input binding, taking into account the stride that was set
+    /// dynamically if the bound pipeline uses `DynamicState::VertexInputBindingStride`.
+    fn vertex_input_binding_stride(
+        &self,
+        graphics: BoundGraphics<'_>,
+        binding_num: u32,
+        binding_desc: &VertexInputBindingDescription,
+    ) -> DeviceSize {
+        match graphics {
+            BoundGraphics::Pipeline(pipeline)
+                if pipeline
+                    .dynamic_state()
+                    .contains(&DynamicState::VertexInputBindingStride) =>
+            {
+                self.builder_state.vertex_input_binding_stride[&binding_num] as DeviceSize
+            }
+            _ => binding_desc.stride as DeviceSize,
+        }
+    }
+
+    /// Returns the resources that are used through the descriptor sets and vertex buffers bound
+    /// for `pipeline_bind_point`, for the currently bound pipeline.
+    ///
+    /// The list is cached until the bound state changes, so that consecutive draws or dispatches
+    /// with the same state share it instead of each cloning every resource again.
+    fn bound_resources(
+        &mut self,
+        pipeline_bind_point: PipelineBindPoint,
+    ) -> Arc<[(ResourceUseRef2, Resource)]> {
+        if let Some(bound_resources) = self.builder_state.bound_resources.get(&pipeline_bind_point)
+        {
+            return bound_resources.clone();
+        }
+
+        let mut used_resources = Vec::new();
+
+        match pipeline_bind_point {
+            PipelineBindPoint::Compute => {
+                if let Some(pipeline) = self.builder_state.pipeline_compute.as_deref() {
+                    self.add_descriptor_sets_resources(&mut used_resources, pipeline);
+                } else {
+                    let shader_objects = self.expect_bound_shader_objects(pipeline_bind_point);
+                    self.add_descriptor_sets_resources(&mut used_resources, &shader_objects);
+                }
+            }
+            PipelineBindPoint::Graphics => {
+                if let Some(pipeline) = self.builder_state.pipeline_graphics.as_deref() {
+                    self.add_descriptor_sets_resources(&mut used_resources, pipeline);
+                    self.add_vertex_buffers_resources(
+                        &mut used_resources,
+                        pipeline.vertex_input_state(),
+                    );
+                } else {
+                    let shader_objects = self.expect_bound_shader_objects(pipeline_bind_point);
+                    self.add_descriptor_sets_resources(&mut used_resources, &shader_objects);
+
+                    if BoundGraphics::ShaderObjects(&self.builder_state.shaders)
+                        .has_shader(ShaderStage::Vertex)
+                    {
+                        self.add_vertex_buffers_resources(
+                            &mut used_resources,
+                            self.builder_state.vertex_input.as_ref(),
+                        );
+                    }
+                }
+            }
+            PipelineBindPoint::RayTracing => {
+                let pipeline = self.builder_state.pipeline_ray_tracing.as_deref().unwrap();
+                self.add_descriptor_sets_resources(&mut used_resources, pipeline);
+            }
+        }
+
+        let bound_resources: Arc<[_]> = used_resources.into();
+        self.builder_state
+            .bound_resources
+            .insert(pipeline_bind_point, bound_resources.clone());
+
+        bound_resources
+    }
+
+    fn expect_bound_shader_objects(
+        &self,
+        pipeline_bind_point: PipelineBindPoint,
+    ) -> BoundShaderObjects {
+        self.bound_shader_objects(pipeline_bind_point)
+            .ok()
+            .flatten()
+            .expect("the bound pipeline or shader objects were validated before recording")
+    }
+
+    fn add_descriptor_sets_resources<Pl: Pipeline>(
+        &self,
+        used_resources: &mut Vec<(ResourceUseRef2, Resource)>,
+        pipeline: &Pl,
+    ) {
+        let descriptor_sets_state = match self
+            .builder_state
+            .descriptor_sets
+            .get(&pipeline.bind_point())
+        {
+            Some(x) => x,
+            None => return,
+        };
+
+        for (&(set, binding), binding_reqs) in pipeline.descriptor_binding_requirements() {
+            let descriptor_type = descriptor_sets_state.pipeline_layout.set_layouts()[set as usize]
+                .bindings()[&binding]
+                .descriptor_type;
+
+            // TODO: Should input attachments be handled here or in attachment access?
+            if descriptor_type == DescriptorType::InputAttachment {
+                continue;
+            }
+
+            let default_image_layout = descriptor_type.default_image_layout();
+
+            let use_iter = move |index: u32| {
+                let (stages_read, stages_write) = [Some(index), None]
+                    .into_iter()
+                    .filter_map(|index| binding_reqs.descriptors.get(&index))
+                    .fold(
+                        (ShaderStages::empty(), ShaderStages::empty()),
+                        |(stages_read, stages_write), desc_reqs| {
+                            (
+                                stages_read | desc_reqs.memory_read,
+                                stages_write | desc_reqs.memory_write,
+                            )
+                        },
+                    );
+                let use_ref = ResourceUseRef2::from(ResourceInCommand::DescriptorSet {
+                    set,
+                    binding,
+                    index,
+                });
+                let memory_access = PipelineStageAccess::iter_descriptor_stages(
+                    descriptor_type,
+                    stages_read,
+                    stages_write,
+                )
+                .fold(PipelineStageAccessFlags::empty(), |total, val| {
+                    total | val.into()
+                });
+                (use_ref, memory_access)
+            };
+
+            let descriptor_set_state = &descriptor_sets_state.descriptor_sets[&set];
+
+            match descriptor_set_state.resources().binding(binding).unwrap() {
+                DescriptorBindingResources::None(_) => (),
+                DescriptorBindingResources::Buffer(elements) => {
+                    if matches!(
+                        descriptor_type,
+                        DescriptorType::UniformBufferDynamic | DescriptorType::StorageBufferDynamic
+                    ) {
+                        let dynamic_offsets = descriptor_set_state.dynamic_offsets();
 
-                    // TODO: check that the topology matches the geometry shader
-                }
-                DynamicState::RasterizerDiscardEnable => {
-                    if self.builder_state.rasterizer_discard_enable.is_none() {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-04876"),
-                            ..Default::default()
-                        }));
-                    }
-                }
-                // DynamicState::RayTracingPipelineStackSize => unreachable!(
-                //     "RayTracingPipelineStackSize dynamic state should not occur on a graphics pipeline"
-                // ),
-                // DynamicState::SampleLocations => todo!(),
-                DynamicState::Scissor => {
-                    let viewport_state = pipeline.viewport_state().unwrap();
+                        for (index, element) in elements.iter().enumerate() {
+                            if let Some(buffer_info) = element {
+                                let DescriptorBufferInfo { buffer, range } = buffer_info;
 
-                    for num in 0..viewport_state.scissors.len() as u32 {
-                        if !self.builder_state.scissor.contains_key(&num) {
-                            return Err(Box::new(ValidationError {
-                                problem: format!(
-                                    "the currently bound graphics pipeline requires the \
-                                    `DynamicState::{:?}` dynamic state, but \
-                                    this state was either not set, or it was overwritten by a \
-                                    more recent `bind_pipeline_graphics` command",
-                                    dynamic_state
-                                )
-                                .into(),
-                                vuids: vuids!(vuid_type, "None-07832"),
-                                ..Default::default()
-                            }));
-                        }
-                    }
-                }
-                DynamicState::ScissorWithCount => {
-                    if let Some(scissors) = &self.builder_state.scissor_with_count {
-                        let viewport_state = pipeline.viewport_state().unwrap();
-                        let viewport_count = viewport_state.viewports.len() as u32;
-                        let scissor_count = scissors.len() as u32;
+                                let dynamic_offset = dynamic_offsets[index] as DeviceSize;
+                                let (use_ref, memory_access) = use_iter(index as u32);
 
-                        if viewport_count != 0 {
-                            // Check if the counts match, but only if the viewport count is fixed.
-                            // If the viewport count is also dynamic, then the
-                            // DynamicState::ViewportWithCount match arm will handle it.
-                            if viewport_count != scissor_count {
-                                return Err(Box::new(ValidationError {
-                                    problem: "the currently bound graphics pipeline requires the \
-                                        `DynamicState::ScissorWithCount` dynamic state, and \
-                                        not the `DynamicState::ViewportWithCount` dynamic state, \
-                                        but the dynamic scissor count is not equal to the \
-                                        viewport count specified when creating the pipeline"
-                                        .into(),
-                                    vuids: vuids!(vuid_type, "scissorCount-03418"),
-                                    ..Default::default()
-                                }));
+                                let mut range = range.clone();
+                                range.start += dynamic_offset;
+                                range.end += dynamic_offset;
+
+                                used_resources.push((
+                                    use_ref,
+                                    Resource::Buffer {
+                                        buffer: buffer.clone(),
+                                        range,
+                                        memory_access,
+                                    },
+                                ));
                             }
                         }
                     } else {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "scissorCount-03418", "viewportCount-03419"),
-                            ..Default::default()
-                        }));
-                    };
-                }
-                DynamicState::StencilCompareMask => {
-                    let state = self.builder_state.stencil_compare_mask;
+                        for (index, element) in elements.iter().enumerate() {
+                            if let Some(buffer_info) = element {
+                                let DescriptorBufferInfo { buffer, range } = buffer_info;
 
-                    if state.front.is_none() || state.back.is_none() {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-07837"),
-                            ..Default::default() //
-                        }));
-                    }
-                }
-                DynamicState::StencilOp => {
-                    let state = self.builder_state.stencil_op;
+                                let (use_ref, memory_access) = use_iter(index as u32);
 
-                    if state.front.is_none() || state.back.is_none() {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-07848"),
-                            ..Default::default()
-                        }));
+                                used_resources.push((
+                                    use_ref,
+                                    Resource::Buffer {
+                                        buffer: buffer.clone(),
+                                        range: range.clone(),
+                                        memory_access,
+                                    },
+                                ));
+                            }
+                        }
                     }
                 }
-                DynamicState::StencilReference => {
-                    let state = self.builder_state.stencil_reference;
+                DescriptorBindingResources::BufferView(elements) => {
+                    for (index, element) in elements.iter().enumerate() {
+                        if let Some(buffer_view) = element {
+                            let buffer = buffer_view.buffer();
+                            let (use_ref, memory_access) = use_iter(index as u32);
 
-                    if state.front.is_none() || state.back.is_none() {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-07839"),
-                            ..Default::default()
-                        }));
-                    }
-                }
-                DynamicState::StencilTestEnable => {
-                    if self.builder_state.stencil_test_enable.is_none() {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-07847"),
-                            ..Default::default()
-                        }));
+                            used_resources.push((
+                                use_ref,
+                                Resource::Buffer {
+                                    buffer: buffer.clone(),
+                                    range: buffer_view.range().clone(),
+                                    memory_access,
+                                },
+                            ));
+                        }
                     }
                 }
-                DynamicState::StencilWriteMask => {
-                    let state = self.builder_state.stencil_write_mask;
+                DescriptorBindingResources::ImageView(elements) => {
+                    for (index, element) in elements.iter().enumerate() {
+                        if let Some(image_view_info) = element {
+                            let &DescriptorImageViewInfo {
+                                ref image_view,
+                                mut image_layout,
+                            } = image_view_info;
 
-                    if state.front.is_none() || state.back.is_none() {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "None-07838"),
-                            ..Default::default()
-                        }));
+                            if image_layout == ImageLayout::Undefined {
+                                image_layout = default_image_layout;
+                            }
+
+                            let (use_ref, memory_access) = use_iter(index as u32);
+
+                            used_resources.push((
+                                use_ref,
+                                Resource::Image {
+                                    image: image_view.image().clone(),
+                                    subresource_range: image_view.subresource_range().clone(),
+                                    memory_access,
+                                    start_layout: image_layout,
+                                    end_layout: image_layout,
+                                },
+                            ));
+                        }
                     }
                 }
-                // DynamicState::VertexInput => todo!(),
-                // DynamicState::VertexInputBindingStride => todo!(),
-                DynamicState::Viewport => {
-                    let viewport_state = pipeline.viewport_state().unwrap();
+                DescriptorBindingResources::ImageViewSampler(elements) => {
+                    for (index, element) in elements.iter().enumerate() {
+                        if let Some((image_view_info, _sampler)) = element {
+                            let &DescriptorImageViewInfo {
+                                ref image_view,
+                                mut image_layout,
+                            } = image_view_info;
 
-                    for num in 0..viewport_state.viewports.len() as u32 {
-                        if !self.builder_state.viewport.contains_key(&num) {
-                            return Err(Box::new(ValidationError {
-                                problem: format!(
-                                    "the currently bound graphics pipeline requires the \
-                                    `DynamicState::{:?}` dynamic state, but \
-                                    this state was either not set, or it was overwritten by a \
-                                    more recent `bind_pipeline_graphics` command",
-                                    dynamic_state
-                                )
-                                .into(),
-                                vuids: vuids!(vuid_type, "None-07831"),
-                                ..Default::default()
-                            }));
+                            if image_layout == ImageLayout::Undefined {
+                                image_layout = default_image_layout;
+                            }
+
+                            let (use_ref, memory_access) = use_iter(index as u32);
+
+                            used_resources.push((
+                                use_ref,
+                                Resource::Image {
+                                    image: image_view.image().clone(),
+                                    subresource_range: image_view.subresource_range().clone(),
+                                    memory_access,
+                                    start_layout: image_layout,
+                                    end_layout: image_layout,
+                                },
+                            ));
                         }
                     }
                 }
-                // DynamicState::ViewportCoarseSampleOrder => todo!(),
-                // DynamicState::ViewportShadingRatePalette => todo!(),
-                DynamicState::ViewportWithCount => {
-                    if let Some(viewports) = &self.builder_state.viewport_with_count {
-                        let viewport_state = pipeline.viewport_state().unwrap();
-                        let scissor_count = viewport_state.scissors.len() as u32;
-                        let viewport_count = viewports.len() as u32;
+                DescriptorBindingResources::Sampler(_) => (),
+                DescriptorBindingResources::InlineUniformBlock => (),
+                DescriptorBindingResources::AccelerationStructure(elements) => {
+                    for (index, element) in elements.iter().enumerate() {
+                        if let Some(acceleration_structure) = element {
+                            let buffer = acceleration_structure.buffer();
+                            let (use_ref, memory_access) = use_iter(index as u32);
 
-                        if scissor_count != 0 {
-                            if viewport_count != scissor_count {
-                                return Err(Box::new(ValidationError {
-                                    problem: "the currently bound graphics pipeline requires the \
-                                        `DynamicState::ViewportWithCount` dynamic state, and \
-                                        not the `DynamicState::ScissorWithCount` dynamic state, \
-                                        but the dynamic scissor count is not equal to the scissor \
-                                        count specified when creating the pipeline"
-                                        .into(),
-                                    vuids: vuids!(vuid_type, "viewportCount-03417"),
-                                    ..Default::default()
-                                }));
-                            }
-                        } else {
-                            if let Some(scissors) = &self.builder_state.scissor_with_count {
-                                if viewport_count != scissors.len() as u32 {
-                                    return Err(Box::new(ValidationError {
-                                        problem:
-                                            "the currently bound graphics pipeline requires both \
-                                            the `DynamicState::ViewportWithCount` and the \
-                                            `DynamicState::ScissorWithCount` dynamic states, but \
-                                            the dynamic scissor count is not equal to the \
-                                            dynamic scissor count "
-                                                .into(),
-                                        vuids: vuids!(vuid_type, "viewportCount-03419"),
-                                        ..Default::default()
-                                    }));
-                                }
-                            } else {
-                                return Err(Box::new(ValidationError {
-                                    problem: format!(
-                                        "the currently bound graphics pipeline requires the \
-                                        `DynamicState::{:?}` dynamic state, but \
-                                        this state was either not set, or it was overwritten by a \
-                                        more recent `bind_pipeline_graphics` command",
-                                        dynamic_state
-                                    )
-                                    .into(),
-                                    vuids: vuids!(
-                                        vuid_type,
-                                        "scissorCount-03418",
-                                        "viewportCount-03419"
-                                    ),
-                                    ..Default::default()
-                                }));
-                            }
+                            used_resources.push((
+                                use_ref,
+                                Resource::Buffer {
+                                    buffer: buffer.clone(),
+                                    range: 0..buffer.size(),
+                                    memory_access,
+                                },
+                            ));
                         }
-                    } else {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the currently bound graphics pipeline requires the \
-                                `DynamicState::{:?}` dynamic state, but \
-                                this state was either not set, or it was overwritten by a \
-                                more recent `bind_pipeline_graphics` command",
-                                dynamic_state
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "viewportCount-03417", "viewportCount-03419"),
-                            ..Default::default()
-                        }));
                     }
-
-                    // TODO: VUID-vkCmdDrawIndexed-primitiveFragmentShadingRateWithMultipleViewports-04552
-                    // If the primitiveFragmentShadingRateWithMultipleViewports limit is not supported,
-                    // the bound graphics pipeline was created with the
-                    // VK_DYNAMIC_STATE_VIEWPORT_WITH_COUNT_EXT dynamic state enabled, and any of the
-                    // shader stages of the bound graphics pipeline write to the PrimitiveShadingRateKHR
-                    // built-in, then vkCmdSetViewportWithCountEXT must have been called in the current
-                    // command buffer prior to this drawing command, and the viewportCount parameter of
-                    // vkCmdSetViewportWithCountEXT must be 1
                 }
             }
         }
-
-        Ok(())
     }
 
-    fn validate_pipeline_graphics_render_pass(
+    fn add_vertex_buffers_resources(
         &self,
-        vuid_type: VUIDType,
-        pipeline: &GraphicsPipeline,
-        render_pass_state: &RenderPassState,
-    ) -> Result<(), Box<ValidationError>> {
-        if render_pass_state.contents != SubpassContents::Inline {
-            return Err(Box::new(ValidationError {
-                problem: "the contents of the current subpass instance is not \
-                    `SubpassContents::Inline`"
-                    .into(),
-                // vuids?
-                ..Default::default()
-            }));
-        }
+        used_resources: &mut Vec<(ResourceUseRef2, Resource)>,
+        vertex_input_state: Option<&VertexInputState>,
+    ) {
+        // Mesh shading doesn't have vertex input state.
+        let Some(vertex_input_state) = vertex_input_state else {
+            return;
+        };
 
-        match (&render_pass_state.render_pass, pipeline.subpass()) {
+        used_resources.extend(vertex_input_state.bindings.keys().map(|&binding| {
+            let vertex_buffer = &self.builder_state.vertex_buffers[&binding];
             (
-                RenderPassStateType::BeginRenderPass(state),
-                PipelineSubpassType::BeginRenderPass(pipeline_subpass),
-            ) => {
-                if !pipeline_subpass
-                    .render_pass()
-                    .is_compatible_with(state.subpass.render_pass())
-                {
-                    return Err(Box::new(ValidationError {
-                        problem: "the current render pass instance is not compatible with the \
-                            render pass that the currently bound graphics pipeline was \
-                            created with"
-                            .into(),
-                        vuids: vuids!(vuid_type, "renderPass-02684"),
-                        ..Default::default()
-                    }));
-                }
+                ResourceInCommand::VertexBuffer { binding }.into(),
+                Resource::Buffer {
+                    buffer: vertex_buffer.clone(),
+                    range: 0..vertex_buffer.size(), // TODO:
+                    memory_access:
+                        PipelineStageAccessFlags::VertexAttributeInput_VertexAttributeRead,
+                },
+            )
+        }));
+    }
 
-                if pipeline_subpass.index() != state.subpass.index() {
-                    return Err(Box::new(ValidationError {
-                        problem: "the subpass index of the current render pass instance is not \
-                            equal to the index of the subpass that the bound graphics pipeline was \
-                            created with"
-                            .into(),
-                        vuids: vuids!(vuid_type, "subpass-02685"),
-                        ..Default::default()
-                    }));
-                }
-            }
-            (
-                RenderPassStateType::BeginRendering(_),
-                PipelineSubpassType::BeginRendering(pipeline_rendering_info),
-            ) => {
-                if pipeline_rendering_info.view_mask != render_pass_state.rendering_info.view_mask {
-                    return Err(Box::new(ValidationError {
-                        problem: "the `view_mask` of the current render pass instance is not \
-                            equal to the `view_mask` the bound graphics pipeline was created with"
-                            .into(),
-                        vuids: vuids!(vuid_type, "viewMask-06178"),
-                        ..Default::default()
-                    }));
-                }
+    fn add_index_buffer_resources(&self, used_resources: &mut Vec<(ResourceUseRef2, Resource)>) {
+        let index_buffer_bytes = self.builder_state.index_buffer.as_ref().unwrap().as_bytes();
+        used_resources.push((
+            ResourceInCommand::IndexBuffer.into(),
+            Resource::Buffer {
+                buffer: index_buffer_bytes.clone(),
+                range: 0..index_buffer_bytes.size(), // TODO:
+                memory_access: PipelineStageAccessFlags::IndexInput_IndexRead,
+            },
+        ));
+    }
 
-                if pipeline_rendering_info.color_attachment_formats.len()
-                    != render_pass_state
-                        .rendering_info
-                        .color_attachment_formats
-                        .len()
-                {
-                    return Err(Box::new(ValidationError {
-                        problem: "`color_attachments.len()` of the current render pass \
-                            instance is not equal to `color_attachment_formats.len()` that the
-                            currently bound graphics pipeline was created with"
-                            .into(),
-                        vuids: vuids!(vuid_type, "colorAttachmentCount-06179"),
-                        ..Default::default()
-                    }));
-                }
+    fn add_indirect_buffer_resources(
+        &self,
+        used_resources: &mut Vec<(ResourceUseRef2, Resource)>,
+        indirect_buffer: &Subbuffer<[u8]>,
+    ) {
+        used_resources.push((
+            ResourceInCommand::IndirectBuffer.into(),
+            Resource::Buffer {
+                buffer: indirect_buffer.clone(),
+                range: 0..indirect_buffer.size(), // TODO:
+                memory_access: PipelineStageAccessFlags::DrawIndirect_IndirectCommandRead,
+            },
+        ));
+    }
 
-                for (color_attachment_index, required_format, pipeline_format) in render_pass_state
-                    .rendering_info
-                    .color_attachment_formats
-                    .iter()
-                    .zip(
-                        pipeline_rendering_info
-                            .color_attachment_formats
-                            .iter()
-                            .copied(),
-                    )
-                    .enumerate()
-                    .filter_map(|(i, (r, p))| r.map(|r| (i as u32, r, p)))
-                {
-                    if Some(required_format) != pipeline_format {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "`color_attachments[{0}].image_view.format()` of the current \
-                                render pass instance is not equal to \
-                                `color_attachment_formats[{0}]` that the currently bound \
-                                graphics pipeline was created with",
-                                color_attachment_index,
-                            )
-                            .into(),
-                            vuids: vuids!(vuid_type, "colorAttachmentCount-06180"),
-                            ..Default::default()
-                        }));
-                    }
-                }
+    fn add_shader_binding_table_resources(
+        &self,
+        used_resources: &mut Vec<(ResourceUseRef2, Resource)>,
+        shader_binding_table: &ShaderBindingTable,
+    ) {
+        let buffer = shader_binding_table.buffer();
+        used_resources.push((
+            ResourceInCommand::ShaderBindingTable.into(),
+            Resource::Buffer {
+                buffer: buffer.clone(),
+                range: 0..buffer.size(),
+                memory_access: PipelineStageAccessFlags::RayTracingShader_ShaderBindingTableRead,
+            },
+        ));
+    }
+
+    fn add_count_buffer_resources(
+        &self,
+        used_resources: &mut Vec<(ResourceUseRef2, Resource)>,
+        count_buffer: &Subbuffer<[u8]>,
+    ) {
+        used_resources.push((
+            ResourceInCommand::CountBuffer.into(),
+            Resource::Buffer {
+                buffer: count_buffer.clone(),
+                range: 0..count_buffer.size(),
+                memory_access: PipelineStageAccessFlags::DrawIndirect_IndirectCommandRead,
+            },
+        ));
+    }
+}
+
+impl<A> UnsafeCommandBufferBuilder<A>
+where
+    A: CommandBufferAllocator,
+{
+    pub unsafe fn dispatch(
+        &mut self,
+        group_counts: [u32; 3],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_dispatch(group_counts)?;
 
-                if let Some((required_format, pipeline_format)) = render_pass_state
-                    .rendering_info
-                    .depth_attachment_format
-                    .map(|r| (r, pipeline_rendering_info.depth_attachment_format))
-                {
-                    if Some(required_format) != pipeline_format {
-                        return Err(Box::new(ValidationError {
-                            problem: "`depth_attachment.image_view.format()` of the current \
-                                render pass instance is not equal to \
-                                `depth_attachment_format` that the currently bound \
-                                graphics pipeline was created with"
-                                .into(),
-                            vuids: vuids!(vuid_type, "pDepthAttachment-06181"),
-                            ..Default::default()
-                        }));
-                    }
-                }
+        Ok(self.dispatch_unchecked(group_counts))
+    }
 
-                if let Some((required_format, pipeline_format)) = render_pass_state
-                    .rendering_info
-                    .stencil_attachment_format
-                    .map(|r| (r, pipeline_rendering_info.stencil_attachment_format))
-                {
-                    if Some(required_format) != pipeline_format {
-                        return Err(Box::new(ValidationError {
-                            problem: "`stencil_attachment.image_view.format()` of the current \
-                                render pass instance is not equal to \
-                                `stencil_attachment_format` that the currently bound \
-                                graphics pipeline was created with"
-                                .into(),
-                            vuids: vuids!(vuid_type, "pStencilAttachment-06182"),
-                            ..Default::default()
-                        }));
-                    }
-                }
+    fn validate_dispatch(&self, group_counts: [u32; 3]) -> Result<(), Box<ValidationError>> {
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::COMPUTE)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    compute operations"
+                    .into(),
+                vuids: &["VUID-vkCmdDispatch-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
 
-                // VUID-vkCmdDraw-imageView-06172
-                // VUID-vkCmdDraw-imageView-06173
-                // VUID-vkCmdDraw-imageView-06174
-                // VUID-vkCmdDraw-imageView-06175
-                // VUID-vkCmdDraw-imageView-06176
-                // VUID-vkCmdDraw-imageView-06177
-                // TODO:
-            }
-            (RenderPassStateType::BeginRenderPass(_), PipelineSubpassType::BeginRendering(_)) => {
-                return Err(Box::new(ValidationError {
-                    problem: "the current render pass instance was begun with \
-                        `begin_render_pass`, but the currently bound graphics pipeline requires \
-                        a render pass instance begun with `begin_rendering`"
-                        .into(),
-                    // vuids?
-                    ..Default::default()
-                }));
-            }
-            (RenderPassStateType::BeginRendering(_), PipelineSubpassType::BeginRenderPass(_)) => {
-                return Err(Box::new(ValidationError {
-                    problem: "the current render pass instance was begun with \
-                        `begin_rendering`, but the currently bound graphics pipeline requires \
-                        a render pass instance begun with `begin_render_pass`"
-                        .into(),
-                    // vuids?
-                    ..Default::default()
-                }));
-            }
+        let properties = self.device().physical_device().properties();
+
+        if group_counts[0] > properties.max_compute_work_group_count[0] {
+            return Err(Box::new(ValidationError {
+                context: "group_counts[0]".into(),
+                problem: "is greater than the `max_compute_work_group_count[0]` limit".into(),
+                vuids: &["VUID-vkCmdDispatch-groupCountX-00386"],
+                ..Default::default()
+            }));
         }
 
-        // VUID-vkCmdDraw-None-02686
-        // TODO:
+        if group_counts[1] > properties.max_compute_work_group_count[1] {
+            return Err(Box::new(ValidationError {
+                context: "group_counts[1]".into(),
+                problem: "is greater than the `max_compute_work_group_count[1]` limit".into(),
+                vuids: &["VUID-vkCmdDispatch-groupCountY-00387"],
+                ..Default::default()
+            }));
+        }
+
+        if group_counts[2] > properties.max_compute_work_group_count[2] {
+            return Err(Box::new(ValidationError {
+                context: "group_counts[2]".into(),
+                problem: "is greater than the `max_compute_work_group_count[2]` limit".into(),
+                vuids: &["VUID-vkCmdDispatch-groupCountZ-00388"],
+                ..Default::default()
+            }));
+        }
 
         Ok(())
     }
 
-    fn validate_pipeline_graphics_vertex_buffers(
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn dispatch_unchecked(&mut self, group_counts: [u32; 3]) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.v1_0.cmd_dispatch)(
+            self.handle(),
+            group_counts[0],
+            group_counts[1],
+            group_counts[2],
+        );
+
+        self
+    }
+
+    pub unsafe fn dispatch_base(
+        &mut self,
+        base_group: [u32; 3],
+        group_counts: [u32; 3],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_dispatch_base(base_group, group_counts)?;
+
+        Ok(self.dispatch_base_unchecked(base_group, group_counts))
+    }
+
+    fn validate_dispatch_base(
         &self,
-        vuid_type: VUIDType,
-        pipeline: &GraphicsPipeline,
+        base_group: [u32; 3],
+        group_counts: [u32; 3],
     ) -> Result<(), Box<ValidationError>> {
-        let vertex_input = pipeline.vertex_input_state();
+        if !(self.device().api_version() >= Version::V1_1
+            || self.device().enabled_extensions().khr_device_group)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::APIVersion(Version::V1_1)]),
+                    RequiresAllOf(&[Requires::DeviceExtension("khr_device_group")]),
+                ]),
+                ..Default::default()
+            }));
+        }
 
-        for &binding_num in vertex_input.bindings.keys() {
-            if !self.builder_state.vertex_buffers.contains_key(&binding_num) {
-                return Err(Box::new(ValidationError {
-                    problem: format!(
-                        "the currently bound graphics pipeline uses \
-                        vertex buffer binding {0}, but \
-                        no vertex buffer is currently bound to binding {0}",
-                        binding_num
-                    )
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::COMPUTE)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    compute operations"
                     .into(),
-                    vuids: vuids!(vuid_type, "None-04007"),
-                    ..Default::default()
-                }));
-            }
+                vuids: &["VUID-vkCmdDispatchBase-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
         }
 
-        Ok(())
-    }
+        let properties = self.device().physical_device().properties();
 
-    fn add_descriptor_sets_resources<Pl: Pipeline>(
-        &self,
-        used_resources: &mut Vec<(ResourceUseRef2, Resource)>,
-        pipeline: &Pl,
-    ) {
-        let descriptor_sets_state = match self
-            .builder_state
-            .descriptor_sets
-            .get(&pipeline.bind_point())
-        {
-            Some(x) => x,
-            None => return,
-        };
+        if base_group[0] >= properties.max_compute_work_group_count[0] {
+            return Err(Box::new(ValidationError {
+                context: "base_group[0]".into(),
+                problem: "is not less than the `max_compute_work_group_count[0]` limit".into(),
+                vuids: &["VUID-vkCmdDispatchBase-baseGroupX-00421"],
+                ..Default::default()
+            }));
+        }
 
-        for (&(set, binding), binding_reqs) in pipeline.descriptor_binding_requirements() {
-            let descriptor_type = descriptor_sets_state.pipeline_layout.set_layouts()[set as usize]
-                .bindings()[&binding]
-                .descriptor_type;
+        if group_counts[0] > properties.max_compute_work_group_count[0] - base_group[0] {
+            return Err(Box::new(ValidationError {
+                problem: "`base_group[0] + group_counts[0]` is greater than the \
+                    `max_compute_work_group_count[0]` limit"
+                    .into(),
+                vuids: &["VUID-vkCmdDispatchBase-groupCountX-00424"],
+                ..Default::default()
+            }));
+        }
 
-            // TODO: Should input attachments be handled here or in attachment access?
-            if descriptor_type == DescriptorType::InputAttachment {
-                continue;
-            }
+        if base_group[1] >= properties.max_compute_work_group_count[1] {
+            return Err(Box::new(ValidationError {
+                context: "base_group[1]".into(),
+                problem: "is not less than the `max_compute_work_group_count[1]` limit".into(),
+                vuids: &["VUID-vkCmdDispatchBase-baseGroupY-00422"],
+                ..Default::default()
+            }));
+        }
 
-            let default_image_layout = descriptor_type.default_image_layout();
+        if group_counts[1] > properties.max_compute_work_group_count[1] - base_group[1] {
+            return Err(Box::new(ValidationError {
+                problem: "`base_group[1] + group_counts[1]` is greater than the \
+                    `max_compute_work_group_count[1]` limit"
+                    .into(),
+                vuids: &["VUID-vkCmdDispatchBase-groupCountY-00425"],
+                ..Default::default()
+            }));
+        }
 
-            let use_iter = move |index: u32| {
-                let (stages_read, stages_write) = [Some(index), None]
-                    .into_iter()
-                    .filter_map(|index| binding_reqs.descriptors.get(&index))
-                    .fold(
-                        (ShaderStages::empty(), ShaderStages::empty()),
-                        |(stages_read, stages_write), desc_reqs| {
-                            (
-                                stages_read | desc_reqs.memory_read,
-                                stages_write | desc_reqs.memory_write,
-                            )
-                        },
-                    );
-                let use_ref = ResourceUseRef2::from(ResourceInCommand::DescriptorSet {
-                    set,
-                    binding,
-                    index,
-                });
-                let memory_access = PipelineStageAccess::iter_descriptor_stages(
-                    descriptor_type,
-                    stages_read,
-                    stages_write,
-                )
-                .fold(PipelineStageAccessFlags::empty(), |total, val| {
-                    total | val.into()
-                });
-                (use_ref, memory_access)
-            };
+        if base_group[2] >= properties.max_compute_work_group_count[2] {
+            return Err(Box::new(ValidationError {
+                context: "base_group[2]".into(),
+                problem: "is not less than the `max_compute_work_group_count[2]` limit".into(),
+                vuids: &["VUID-vkCmdDispatchBase-baseGroupZ-00423"],
+                ..Default::default()
+            }));
+        }
 
-            let descriptor_set_state = &descriptor_sets_state.descriptor_sets[&set];
+        if group_counts[2] > properties.max_compute_work_group_count[2] - base_group[2] {
+            return Err(Box::new(ValidationError {
+                problem: "`base_group[2] + group_counts[2]` is greater than the \
+                    `max_compute_work_group_count[2]` limit"
+                    .into(),
+                vuids: &["VUID-vkCmdDispatchBase-groupCountZ-00426"],
+                ..Default::default()
+            }));
+        }
 
-            match descriptor_set_state.resources().binding(binding).unwrap() {
-                DescriptorBindingResources::None(_) => (),
-                DescriptorBindingResources::Buffer(elements) => {
-                    if matches!(
-                        descriptor_type,
-                        DescriptorType::UniformBufferDynamic | DescriptorType::StorageBufferDynamic
-                    ) {
-                        let dynamic_offsets = descriptor_set_state.dynamic_offsets();
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn dispatch_base_unchecked(
+        &mut self,
+        base_group: [u32; 3],
+        group_counts: [u32; 3],
+    ) -> &mut Self {
+        let fns = self.device().fns();
+
+        if self.device().api_version() >= Version::V1_1 {
+            (fns.v1_1.cmd_dispatch_base)(
+                self.handle(),
+                base_group[0],
+                base_group[1],
+                base_group[2],
+                group_counts[0],
+                group_counts[1],
+                group_counts[2],
+            );
+        } else {
+            (fns.khr_device_group.cmd_dispatch_base_khr)(
+                self.handle(),
+                base_group[0],
+                base_group[1],
+                base_group[2],
+                group_counts[0],
+                group_counts[1],
+                group_counts[2],
+            );
+        }
 
-                        for (index, element) in elements.iter().enumerate() {
-                            if let Some(buffer_info) = element {
-                                let DescriptorBufferInfo { buffer, range } = buffer_info;
+        self
+    }
 
-                                let dynamic_offset = dynamic_offsets[index] as DeviceSize;
-                                let (use_ref, memory_access) = use_iter(index as u32);
+    pub unsafe fn dispatch_indirect(
+        &mut self,
+        indirect_buffer: &Subbuffer<[DispatchIndirectCommand]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_dispatch_indirect(indirect_buffer.as_bytes())?;
 
-                                let mut range = range.clone();
-                                range.start += dynamic_offset;
-                                range.end += dynamic_offset;
+        Ok(self.dispatch_indirect_unchecked(indirect_buffer))
+    }
 
-                                used_resources.push((
-                                    use_ref,
-                                    Resource::Buffer {
-                                        buffer: buffer.clone(),
-                                        range,
-                                        memory_access,
-                                    },
-                                ));
-                            }
-                        }
-                    } else {
-                        for (index, element) in elements.iter().enumerate() {
-                            if let Some(buffer_info) = element {
-                                let DescriptorBufferInfo { buffer, range } = buffer_info;
+    fn validate_dispatch_indirect(
+        &self,
+        indirect_buffer: &Subbuffer<[u8]>,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::COMPUTE)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    compute operations"
+                    .into(),
+                vuids: &["VUID-vkCmdDispatchIndirect-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
 
-                                let (use_ref, memory_access) = use_iter(index as u32);
+        // VUID-vkCmdDispatchIndirect-commonparent
+        assert_eq!(self.device(), indirect_buffer.device());
 
-                                used_resources.push((
-                                    use_ref,
-                                    Resource::Buffer {
-                                        buffer: buffer.clone(),
-                                        range: range.clone(),
-                                        memory_access,
-                                    },
-                                ));
-                            }
-                        }
-                    }
-                }
-                DescriptorBindingResources::BufferView(elements) => {
-                    for (index, element) in elements.iter().enumerate() {
-                        if let Some(buffer_view) = element {
-                            let buffer = buffer_view.buffer();
-                            let (use_ref, memory_access) = use_iter(index as u32);
+        if !indirect_buffer
+            .buffer()
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER)
+        {
+            return Err(Box::new(ValidationError {
+                context: "indirect_buffer.usage()".into(),
+                problem: "does not contain `BufferUsage::INDIRECT_BUFFER`".into(),
+                vuids: &["VUID-vkCmdDispatchIndirect-buffer-02709"],
+                ..Default::default()
+            }));
+        }
 
-                            used_resources.push((
-                                use_ref,
-                                Resource::Buffer {
-                                    buffer: buffer.clone(),
-                                    range: buffer_view.range().clone(),
-                                    memory_access,
-                                },
-                            ));
-                        }
-                    }
-                }
-                DescriptorBindingResources::ImageView(elements) => {
-                    for (index, element) in elements.iter().enumerate() {
-                        if let Some(image_view_info) = element {
-                            let &DescriptorImageViewInfo {
-                                ref image_view,
-                                mut image_layout,
-                            } = image_view_info;
+        if size_of::<DispatchIndirectCommand>() as DeviceSize > indirect_buffer.size() {
+            return Err(Box::new(ValidationError {
+                problem: "`size_of::<DrawIndirectCommand>()` is greater than \
+                    `indirect_buffer.size()`"
+                    .into(),
+                vuids: &["VUID-vkCmdDispatchIndirect-offset-00407"],
+                ..Default::default()
+            }));
+        }
 
-                            if image_layout == ImageLayout::Undefined {
-                                image_layout = default_image_layout;
-                            }
+        // VUID-vkCmdDispatchIndirect-offset-02710
+        // TODO:
 
-                            let (use_ref, memory_access) = use_iter(index as u32);
+        Ok(())
+    }
 
-                            used_resources.push((
-                                use_ref,
-                                Resource::Image {
-                                    image: image_view.image().clone(),
-                                    subresource_range: image_view.subresource_range().clone(),
-                                    memory_access,
-                                    start_layout: image_layout,
-                                    end_layout: image_layout,
-                                },
-                            ));
-                        }
-                    }
-                }
-                DescriptorBindingResources::ImageViewSampler(elements) => {
-                    for (index, element) in elements.iter().enumerate() {
-                        if let Some((image_view_info, _sampler)) = element {
-                            let &DescriptorImageViewInfo {
-                                ref image_view,
-                                mut image_layout,
-                            } = image_view_info;
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn dispatch_indirect_unchecked(
+        &mut self,
+        indirect_buffer: &Subbuffer<[DispatchIndirectCommand]>,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.v1_0.cmd_dispatch_indirect)(
+            self.handle(),
+            indirect_buffer.buffer().handle(),
+            indirect_buffer.offset(),
+        );
 
-                            if image_layout == ImageLayout::Undefined {
-                                image_layout = default_image_layout;
-                            }
+        self
+    }
 
-                            let (use_ref, memory_access) = use_iter(index as u32);
+    pub unsafe fn draw(
+        &mut self,
+        vertex_count: u32,
+        instance_count: u32,
+        first_vertex: u32,
+        first_instance: u32,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw(vertex_count, instance_count, first_vertex, first_instance)?;
 
-                            used_resources.push((
-                                use_ref,
-                                Resource::Image {
-                                    image: image_view.image().clone(),
-                                    subresource_range: image_view.subresource_range().clone(),
-                                    memory_access,
-                                    start_layout: image_layout,
-                                    end_layout: image_layout,
-                                },
-                            ));
-                        }
-                    }
-                }
-                DescriptorBindingResources::Sampler(_) => (),
-                DescriptorBindingResources::InlineUniformBlock => (),
-                DescriptorBindingResources::AccelerationStructure(elements) => {
-                    for (index, element) in elements.iter().enumerate() {
-                        if let Some(acceleration_structure) = element {
-                            let buffer = acceleration_structure.buffer();
-                            let (use_ref, memory_access) = use_iter(index as u32);
+        Ok(self.draw_unchecked(vertex_count, instance_count, first_vertex, first_instance))
+    }
 
-                            used_resources.push((
-                                use_ref,
-                                Resource::Buffer {
-                                    buffer: buffer.clone(),
-                                    range: 0..buffer.size(),
-                                    memory_access,
-                                },
-                            ));
-                        }
-                    }
-                }
-            }
+    fn validate_draw(
+        &self,
+        _vertex_count: u32,
+        _instance_count: u32,
+        _first_vertex: u32,
+        _first_instance: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdDraw-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
         }
+
+        Ok(())
     }
 
-    fn add_vertex_buffers_resources(
-        &self,
-        used_resources: &mut Vec<(ResourceUseRef2, Resource)>,
-        pipeline: &GraphicsPipeline,
-    ) {
-        used_resources.extend(pipeline.vertex_input_state().bindings.iter().map(
-            |(&binding, _)| {
-                let vertex_buffer = &self.builder_state.vertex_buffers[&binding];
-                (
-                    ResourceInCommand::VertexBuffer { binding }.into(),
-                    Resource::Buffer {
-                        buffer: vertex_buffer.clone(),
-                        range: 0..vertex_buffer.size(), // TODO:
-                        memory_access:
-                            PipelineStageAccessFlags::VertexAttributeInput_VertexAttributeRead,
-                    },
-                )
-            },
-        ));
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_unchecked(
+        &mut self,
+        vertex_count: u32,
+        instance_count: u32,
+        first_vertex: u32,
+        first_instance: u32,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.v1_0.cmd_draw)(
+            self.handle(),
+            vertex_count,
+            instance_count,
+            first_vertex,
+            first_instance,
+        );
+
+        self
     }
 
-    fn add_index_buffer_resources(&self, used_resources: &mut Vec<(ResourceUseRef2, Resource)>) {
-        let index_buffer_bytes = self.builder_state.index_buffer.as_ref().unwrap().as_bytes();
-        used_resources.push((
-            ResourceInCommand::IndexBuffer.into(),
-            Resource::Buffer {
-                buffer: index_buffer_bytes.clone(),
-                range: 0..index_buffer_bytes.size(), // TODO:
-                memory_access: PipelineStageAccessFlags::IndexInput_IndexRead,
-            },
-        ));
+    pub unsafe fn draw_multi(
+        &mut self,
+        vertex_info: &[MultiDrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw_multi(vertex_info, instance_count, first_instance)?;
+
+        Ok(self.draw_multi_unchecked(vertex_info, instance_count, first_instance))
     }
 
-    fn add_indirect_buffer_resources(
+    fn validate_draw_multi(
         &self,
-        used_resources: &mut Vec<(ResourceUseRef2, Resource)>,
-        indirect_buffer: &Subbuffer<[u8]>,
-    ) {
-        used_resources.push((
-            ResourceInCommand::IndirectBuffer.into(),
-            Resource::Buffer {
-                buffer: indirect_buffer.clone(),
-                range: 0..indirect_buffer.size(), // TODO:
-                memory_access: PipelineStageAccessFlags::DrawIndirect_IndirectCommandRead,
-            },
-        ));
+        vertex_info: &[MultiDrawInfo],
+        _instance_count: u32,
+        _first_instance: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        self.validate_draw_multi_common(VUIDType::DrawMultiEXT, vertex_info.len())
     }
-}
 
-impl<A> UnsafeCommandBufferBuilder<A>
-where
-    A: CommandBufferAllocator,
-{
-    pub unsafe fn dispatch(
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_multi_unchecked(
         &mut self,
-        group_counts: [u32; 3],
+        vertex_info: &[MultiDrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> &mut Self {
+        if vertex_info.is_empty() {
+            return self;
+        }
+
+        let fns = self.device().fns();
+        (fns.ext_multi_draw.cmd_draw_multi_ext)(
+            self.handle(),
+            vertex_info.len() as u32,
+            vertex_info.as_ptr() as *const ash::vk::MultiDrawInfoEXT,
+            instance_count,
+            first_instance,
+            size_of::<MultiDrawInfo>() as u32,
+        );
+
+        self
+    }
+
+    pub unsafe fn draw_indirect(
+        &mut self,
+        indirect_buffer: &Subbuffer<[DrawIndirectCommand]>,
+        draw_count: u32,
+        stride: u32,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_dispatch(group_counts)?;
+        self.validate_draw_indirect(indirect_buffer.as_bytes(), draw_count, stride)?;
 
-        Ok(self.dispatch_unchecked(group_counts))
+        Ok(self.draw_indirect_unchecked(indirect_buffer, draw_count, stride))
     }
 
-    fn validate_dispatch(&self, group_counts: [u32; 3]) -> Result<(), Box<ValidationError>> {
+    fn validate_draw_indirect(
+        &self,
+        indirect_buffer: &Subbuffer<[u8]>,
+        draw_count: u32,
+        stride: u32,
+    ) -> Result<(), Box<ValidationError>> {
         if !self
             .queue_family_properties()
             .queue_flags
-            .intersects(QueueFlags::COMPUTE)
+            .intersects(QueueFlags::GRAPHICS)
         {
             return Err(Box::new(ValidationError {
                 problem: "the queue family of the command buffer does not support \
-                    compute operations"
+                    graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdDispatch-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdDrawIndirect-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
 
-        let properties = self.device().physical_device().properties();
-
-        if group_counts[0] > properties.max_compute_work_group_count[0] {
+        if !indirect_buffer
+            .buffer()
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER)
+        {
             return Err(Box::new(ValidationError {
-                context: "group_counts[0]".into(),
-                problem: "is greater than the `max_compute_work_group_count[0]` limit".into(),
-                vuids: &["VUID-vkCmdDispatch-groupCountX-00386"],
+                context: "indirect_buffer.usage()".into(),
+                problem: "does not contain `BufferUsage::INDIRECT_BUFFER`".into(),
+                vuids: &["VUID-vkCmdDrawIndirect-buffer-02709"],
                 ..Default::default()
             }));
         }
 
-        if group_counts[1] > properties.max_compute_work_group_count[1] {
-            return Err(Box::new(ValidationError {
-                context: "group_counts[1]".into(),
-                problem: "is greater than the `max_compute_work_group_count[1]` limit".into(),
-                vuids: &["VUID-vkCmdDispatch-groupCountY-00387"],
-                ..Default::default()
-            }));
+        if draw_count > 1 {
+            if !self.device().enabled_features().multi_draw_indirect {
+                return Err(Box::new(ValidationError {
+                    context: "draw_count".into(),
+                    problem: "is greater than 1".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "multi_draw_indirect",
+                    )])]),
+                    vuids: &["VUID-vkCmdDrawIndirect-drawCount-02718"],
+                }));
+            }
+
+            if stride % 4 != 0 {
+                return Err(Box::new(ValidationError {
+                    problem: "`draw_count` is greater than 1, but \
+                        `stride` is not a multiple of 4"
+                        .into(),
+                    vuids: &["VUID-vkCmdDrawIndirect-drawCount-00476"],
+                    ..Default::default()
+                }));
+            }
+
+            if (stride as DeviceSize) < size_of::<DrawIndirectCommand>() as DeviceSize {
+                return Err(Box::new(ValidationError {
+                    problem: "`draw_count` is greater than 1, but \
+                        `stride` is not greater than `size_of::<DrawIndirectCommand>()`"
+                        .into(),
+                    vuids: &["VUID-vkCmdDrawIndirect-drawCount-00476"],
+                    ..Default::default()
+                }));
+            }
+
+            if stride as DeviceSize * (draw_count as DeviceSize - 1)
+                + size_of::<DrawIndirectCommand>() as DeviceSize
+                > indirect_buffer.size()
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "`draw_count` is greater than 1, but \
+                        `stride * (draw_count - 1) + size_of::<DrawIndirectCommand>()` is \
+                        greater than `indirect_buffer.size()`"
+                        .into(),
+                    vuids: &["VUID-vkCmdDrawIndirect-drawCount-00488"],
+                    ..Default::default()
+                }));
+            }
+        } else {
+            if size_of::<DrawIndirectCommand>() as DeviceSize > indirect_buffer.size() {
+                return Err(Box::new(ValidationError {
+                    problem: "`draw_count` is 1, but `size_of::<DrawIndirectCommand>()` is \
+                        greater than `indirect_buffer.size()`"
+                        .into(),
+                    vuids: &["VUID-vkCmdDrawIndirect-drawCount-00487"],
+                    ..Default::default()
+                }));
+            }
         }
 
-        if group_counts[2] > properties.max_compute_work_group_count[2] {
+        let properties = self.device().physical_device().properties();
+
+        if draw_count > properties.max_draw_indirect_count {
             return Err(Box::new(ValidationError {
-                context: "group_counts[2]".into(),
-                problem: "is greater than the `max_compute_work_group_count[2]` limit".into(),
-                vuids: &["VUID-vkCmdDispatch-groupCountZ-00388"],
+                context: "draw_count".into(),
+                problem: "is greater than the `max_draw_indirect_count` limit".into(),
+                vuids: &["VUID-vkCmdDrawIndirect-drawCount-02719"],
                 ..Default::default()
             }));
         }
@@ -2780,47 +5158,80 @@ where
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn dispatch_unchecked(&mut self, group_counts: [u32; 3]) -> &mut Self {
+    pub unsafe fn draw_indirect_unchecked(
+        &mut self,
+        indirect_buffer: &Subbuffer<[DrawIndirectCommand]>,
+        draw_count: u32,
+        stride: u32,
+    ) -> &mut Self {
         let fns = self.device().fns();
-        (fns.v1_0.cmd_dispatch)(
+        (fns.v1_0.cmd_draw_indirect)(
             self.handle(),
-            group_counts[0],
-            group_counts[1],
-            group_counts[2],
+            indirect_buffer.buffer().handle(),
+            indirect_buffer.offset(),
+            draw_count,
+            stride,
         );
 
         self
     }
 
-    pub unsafe fn dispatch_indirect(
+    pub unsafe fn draw_indirect_count(
         &mut self,
-        indirect_buffer: &Subbuffer<[DispatchIndirectCommand]>,
+        indirect_buffer: &Subbuffer<[DrawIndirectCommand]>,
+        count_buffer: &Subbuffer<u32>,
+        max_draw_count: u32,
+        stride: u32,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_dispatch_indirect(indirect_buffer.as_bytes())?;
+        self.validate_draw_indirect_count(
+            indirect_buffer.as_bytes(),
+            count_buffer.as_bytes(),
+            max_draw_count,
+            stride,
+        )?;
 
-        Ok(self.dispatch_indirect_unchecked(indirect_buffer))
+        Ok(self.draw_indirect_count_unchecked(
+            indirect_buffer,
+            count_buffer,
+            max_draw_count,
+            stride,
+        ))
     }
 
-    fn validate_dispatch_indirect(
+    fn validate_draw_indirect_count(
         &self,
         indirect_buffer: &Subbuffer<[u8]>,
+        count_buffer: &Subbuffer<[u8]>,
+        max_draw_count: u32,
+        stride: u32,
     ) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_features().draw_indirect_count {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "draw_indirect_count",
+                )])]),
+                vuids: &["VUID-vkCmdDrawIndirectCount-None-04445"],
+                ..Default::default()
+            }));
+        }
+
         if !self
             .queue_family_properties()
             .queue_flags
-            .intersects(QueueFlags::COMPUTE)
+            .intersects(QueueFlags::GRAPHICS)
         {
             return Err(Box::new(ValidationError {
                 problem: "the queue family of the command buffer does not support \
-                    compute operations"
+                    graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdDispatchIndirect-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdDrawIndirectCount-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
 
-        // VUID-vkCmdDispatchIndirect-commonparent
+        // VUID-vkCmdDrawIndirectCount-commonparent
         assert_eq!(self.device(), indirect_buffer.device());
+        assert_eq!(self.device(), count_buffer.device());
 
         if !indirect_buffer
             .buffer()
@@ -2830,60 +5241,220 @@ where
             return Err(Box::new(ValidationError {
                 context: "indirect_buffer.usage()".into(),
                 problem: "does not contain `BufferUsage::INDIRECT_BUFFER`".into(),
-                vuids: &["VUID-vkCmdDispatchIndirect-buffer-02709"],
+                vuids: &["VUID-vkCmdDrawIndirectCount-buffer-02709"],
+                ..Default::default()
+            }));
+        }
+
+        if !count_buffer
+            .buffer()
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER)
+        {
+            return Err(Box::new(ValidationError {
+                context: "count_buffer.usage()".into(),
+                problem: "does not contain `BufferUsage::INDIRECT_BUFFER`".into(),
+                vuids: &["VUID-vkCmdDrawIndirectCount-countBuffer-02714"],
+                ..Default::default()
+            }));
+        }
+
+        if !count_buffer.offset().is_multiple_of(4) {
+            return Err(Box::new(ValidationError {
+                context: "count_buffer.offset()".into(),
+                problem: "is not a multiple of 4".into(),
+                vuids: &["VUID-vkCmdDrawIndirectCount-countBufferOffset-02716"],
+                ..Default::default()
+            }));
+        }
+
+        if !stride.is_multiple_of(4)
+            || (stride as DeviceSize) < size_of::<DrawIndirectCommand>() as DeviceSize
+        {
+            return Err(Box::new(ValidationError {
+                context: "stride".into(),
+                problem: "is not a multiple of 4, or is less than \
+                    `size_of::<DrawIndirectCommand>()`"
+                    .into(),
+                vuids: &["VUID-vkCmdDrawIndirectCount-stride-03110"],
+                ..Default::default()
+            }));
+        }
+
+        if max_draw_count >= 1
+            && stride as DeviceSize * (max_draw_count as DeviceSize - 1)
+                + size_of::<DrawIndirectCommand>() as DeviceSize
+                > indirect_buffer.size()
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`max_draw_count` is not zero, but \
+                    `stride * (max_draw_count - 1) + size_of::<DrawIndirectCommand>()` is \
+                    greater than `indirect_buffer.size()`"
+                    .into(),
+                vuids: &["VUID-vkCmdDrawIndirectCount-maxDrawCount-03111"],
                 ..Default::default()
             }));
         }
 
-        if size_of::<DispatchIndirectCommand>() as DeviceSize > indirect_buffer.size() {
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_indirect_count_unchecked(
+        &mut self,
+        indirect_buffer: &Subbuffer<[DrawIndirectCommand]>,
+        count_buffer: &Subbuffer<u32>,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+
+        if self.device().api_version() >= Version::V1_2 {
+            (fns.v1_2.cmd_draw_indirect_count)(
+                self.handle(),
+                indirect_buffer.buffer().handle(),
+                indirect_buffer.offset(),
+                count_buffer.buffer().handle(),
+                count_buffer.offset(),
+                max_draw_count,
+                stride,
+            );
+        } else {
+            (fns.khr_draw_indirect_count.cmd_draw_indirect_count_khr)(
+                self.handle(),
+                indirect_buffer.buffer().handle(),
+                indirect_buffer.offset(),
+                count_buffer.buffer().handle(),
+                count_buffer.offset(),
+                max_draw_count,
+                stride,
+            );
+        }
+
+        self
+    }
+
+    pub unsafe fn draw_indexed(
+        &mut self,
+        index_count: u32,
+        instance_count: u32,
+        first_index: u32,
+        vertex_offset: i32,
+        first_instance: u32,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw_indexed(
+            index_count,
+            instance_count,
+            first_index,
+            vertex_offset,
+            first_instance,
+        )?;
+
+        Ok(self.draw_indexed_unchecked(
+            index_count,
+            instance_count,
+            first_index,
+            vertex_offset,
+            first_instance,
+        ))
+    }
+
+    fn validate_draw_indexed(
+        &self,
+        _index_count: u32,
+        _instance_count: u32,
+        _first_index: u32,
+        _vertex_offset: i32,
+        _first_instance: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
             return Err(Box::new(ValidationError {
-                problem: "`size_of::<DrawIndirectCommand>()` is greater than \
-                    `indirect_buffer.size()`"
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdDispatchIndirect-offset-00407"],
+                vuids: &["VUID-vkCmdDrawIndexed-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
 
-        // VUID-vkCmdDispatchIndirect-offset-02710
-        // TODO:
-
         Ok(())
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn dispatch_indirect_unchecked(
+    pub unsafe fn draw_indexed_unchecked(
         &mut self,
-        indirect_buffer: &Subbuffer<[DispatchIndirectCommand]>,
+        index_count: u32,
+        instance_count: u32,
+        first_index: u32,
+        vertex_offset: i32,
+        first_instance: u32,
     ) -> &mut Self {
         let fns = self.device().fns();
-        (fns.v1_0.cmd_dispatch_indirect)(
+        (fns.v1_0.cmd_draw_indexed)(
             self.handle(),
-            indirect_buffer.buffer().handle(),
-            indirect_buffer.offset(),
+            index_count,
+            instance_count,
+            first_index,
+            vertex_offset,
+            first_instance,
         );
 
         self
     }
 
-    pub unsafe fn draw(
+    pub unsafe fn draw_multi_indexed(
         &mut self,
-        vertex_count: u32,
+        index_info: &[MultiDrawIndexedInfo],
         instance_count: u32,
-        first_vertex: u32,
         first_instance: u32,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_draw(vertex_count, instance_count, first_vertex, first_instance)?;
+        self.validate_draw_multi_indexed(index_info, instance_count, first_instance)?;
 
-        Ok(self.draw_unchecked(vertex_count, instance_count, first_vertex, first_instance))
+        Ok(self.draw_multi_indexed_unchecked(index_info, instance_count, first_instance))
     }
 
-    fn validate_draw(
+    fn validate_draw_multi_indexed(
         &self,
-        _vertex_count: u32,
+        index_info: &[MultiDrawIndexedInfo],
         _instance_count: u32,
-        _first_vertex: u32,
         _first_instance: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        self.validate_draw_multi_common(VUIDType::DrawMultiIndexedEXT, index_info.len())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_multi_indexed_unchecked(
+        &mut self,
+        index_info: &[MultiDrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> &mut Self {
+        if index_info.is_empty() {
+            return self;
+        }
+
+        let fns = self.device().fns();
+        (fns.ext_multi_draw.cmd_draw_multi_indexed_ext)(
+            self.handle(),
+            index_info.len() as u32,
+            index_info.as_ptr() as *const ash::vk::MultiDrawIndexedInfoEXT,
+            instance_count,
+            first_instance,
+            size_of::<MultiDrawIndexedInfo>() as u32,
+            std::ptr::null(),
+        );
+
+        self
+    }
+
+    fn validate_draw_multi_common(
+        &self,
+        vuid_type: VUIDType,
+        draw_count: usize,
     ) -> Result<(), Box<ValidationError>> {
         if !self
             .queue_family_properties()
@@ -2894,46 +5465,47 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdDraw-commandBuffer-cmdpool"],
+                vuids: vuids!(vuid_type, "commandBuffer-cmdpool"),
                 ..Default::default()
             }));
         }
 
-        Ok(())
-    }
+        if !self.device().enabled_features().multi_draw {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "multi_draw",
+                )])]),
+                vuids: vuids!(vuid_type, "None-04933"),
+                ..Default::default()
+            }));
+        }
 
-    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn draw_unchecked(
-        &mut self,
-        vertex_count: u32,
-        instance_count: u32,
-        first_vertex: u32,
-        first_instance: u32,
-    ) -> &mut Self {
-        let fns = self.device().fns();
-        (fns.v1_0.cmd_draw)(
-            self.handle(),
-            vertex_count,
-            instance_count,
-            first_vertex,
-            first_instance,
-        );
+        let properties = self.device().physical_device().properties();
 
-        self
+        if draw_count as u32 > properties.max_multi_draw_count.unwrap_or(0) {
+            return Err(Box::new(ValidationError {
+                problem: "the number of draws is greater than the `max_multi_draw_count` limit"
+                    .into(),
+                vuids: vuids!(vuid_type, "drawCount-04934"),
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
     }
 
-    pub unsafe fn draw_indirect(
+    pub unsafe fn draw_indexed_indirect(
         &mut self,
-        indirect_buffer: &Subbuffer<[DrawIndirectCommand]>,
+        indirect_buffer: &Subbuffer<[DrawIndexedIndirectCommand]>,
         draw_count: u32,
         stride: u32,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_draw_indirect(indirect_buffer.as_bytes(), draw_count, stride)?;
+        self.validate_draw_indexed_indirect(indirect_buffer.as_bytes(), draw_count, stride)?;
 
-        Ok(self.draw_indirect_unchecked(indirect_buffer, draw_count, stride))
+        Ok(self.draw_indexed_indirect_unchecked(indirect_buffer, draw_count, stride))
     }
 
-    fn validate_draw_indirect(
+    fn validate_draw_indexed_indirect(
         &self,
         indirect_buffer: &Subbuffer<[u8]>,
         draw_count: u32,
@@ -2948,7 +5520,7 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdDrawIndirect-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdDrawIndexedIndirect-commandBuffer-cmdpool"],
                 ..Default::default()
             }));
         }
@@ -2961,7 +5533,7 @@ where
             return Err(Box::new(ValidationError {
                 context: "indirect_buffer.usage()".into(),
                 problem: "does not contain `BufferUsage::INDIRECT_BUFFER`".into(),
-                vuids: &["VUID-vkCmdDrawIndirect-buffer-02709"],
+                vuids: &["VUID-vkCmdDrawIndexedIndirect-buffer-02709"],
                 ..Default::default()
             }));
         }
@@ -2974,7 +5546,7 @@ where
                     requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
                         "multi_draw_indirect",
                     )])]),
-                    vuids: &["VUID-vkCmdDrawIndirect-drawCount-02718"],
+                    vuids: &["VUID-vkCmdDrawIndexedIndirect-drawCount-02718"],
                 }));
             }
 
@@ -2983,41 +5555,41 @@ where
                     problem: "`draw_count` is greater than 1, but \
                         `stride` is not a multiple of 4"
                         .into(),
-                    vuids: &["VUID-vkCmdDrawIndirect-drawCount-00476"],
+                    vuids: &["VUID-vkCmdDrawIndexedIndirect-drawCount-00528"],
                     ..Default::default()
                 }));
             }
 
-            if (stride as DeviceSize) < size_of::<DrawIndirectCommand>() as DeviceSize {
+            if (stride as DeviceSize) < size_of::<DrawIndexedIndirectCommand>() as DeviceSize {
                 return Err(Box::new(ValidationError {
                     problem: "`draw_count` is greater than 1, but \
-                        `stride` is not greater than `size_of::<DrawIndirectCommand>()`"
+                        `stride` is not greater than `size_of::<DrawIndexedIndirectCommand>()`"
                         .into(),
-                    vuids: &["VUID-vkCmdDrawIndirect-drawCount-00476"],
+                    vuids: &["VUID-vkCmdDrawIndexedIndirect-drawCount-00528"],
                     ..Default::default()
                 }));
             }
 
             if stride as DeviceSize * (draw_count as DeviceSize - 1)
-                + size_of::<DrawIndirectCommand>() as DeviceSize
+                + size_of::<DrawIndexedIndirectCommand>() as DeviceSize
                 > indirect_buffer.size()
             {
                 return Err(Box::new(ValidationError {
                     problem: "`draw_count` is greater than 1, but \
-                        `stride * (draw_count - 1) + size_of::<DrawIndirectCommand>()` is \
+                        `stride * (draw_count - 1) + size_of::<DrawIndexedIndirectCommand>()` is \
                         greater than `indirect_buffer.size()`"
                         .into(),
-                    vuids: &["VUID-vkCmdDrawIndirect-drawCount-00488"],
+                    vuids: &["VUID-vkCmdDrawIndexedIndirect-drawCount-00540"],
                     ..Default::default()
                 }));
             }
         } else {
-            if size_of::<DrawIndirectCommand>() as DeviceSize > indirect_buffer.size() {
+            if size_of::<DrawIndexedIndirectCommand>() as DeviceSize > indirect_buffer.size() {
                 return Err(Box::new(ValidationError {
-                    problem: "`draw_count` is 1, but `size_of::<DrawIndirectCommand>()` is \
+                    problem: "`draw_count` is 1, but `size_of::<DrawIndexedIndirectCommand>()` is \
                         greater than `indirect_buffer.size()`"
                         .into(),
-                    vuids: &["VUID-vkCmdDrawIndirect-drawCount-00487"],
+                    vuids: &["VUID-vkCmdDrawIndexedIndirect-drawCount-00539"],
                     ..Default::default()
                 }));
             }
@@ -3029,7 +5601,7 @@ where
             return Err(Box::new(ValidationError {
                 context: "draw_count".into(),
                 problem: "is greater than the `max_draw_indirect_count` limit".into(),
-                vuids: &["VUID-vkCmdDrawIndirect-drawCount-02719"],
+                vuids: &["VUID-vkCmdDrawIndexedIndirect-drawCount-02719"],
                 ..Default::default()
             }));
         }
@@ -3038,14 +5610,14 @@ where
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn draw_indirect_unchecked(
+    pub unsafe fn draw_indexed_indirect_unchecked(
         &mut self,
-        indirect_buffer: &Subbuffer<[DrawIndirectCommand]>,
+        indirect_buffer: &Subbuffer<[DrawIndexedIndirectCommand]>,
         draw_count: u32,
         stride: u32,
     ) -> &mut Self {
         let fns = self.device().fns();
-        (fns.v1_0.cmd_draw_indirect)(
+        (fns.v1_0.cmd_draw_indexed_indirect)(
             self.handle(),
             indirect_buffer.buffer().handle(),
             indirect_buffer.offset(),
@@ -3056,39 +5628,45 @@ where
         self
     }
 
-    pub unsafe fn draw_indexed(
+    pub unsafe fn draw_indexed_indirect_count(
         &mut self,
-        index_count: u32,
-        instance_count: u32,
-        first_index: u32,
-        vertex_offset: i32,
-        first_instance: u32,
+        indirect_buffer: &Subbuffer<[DrawIndexedIndirectCommand]>,
+        count_buffer: &Subbuffer<u32>,
+        max_draw_count: u32,
+        stride: u32,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_draw_indexed(
-            index_count,
-            instance_count,
-            first_index,
-            vertex_offset,
-            first_instance,
+        self.validate_draw_indexed_indirect_count(
+            indirect_buffer.as_bytes(),
+            count_buffer.as_bytes(),
+            max_draw_count,
+            stride,
         )?;
 
-        Ok(self.draw_indexed_unchecked(
-            index_count,
-            instance_count,
-            first_index,
-            vertex_offset,
-            first_instance,
+        Ok(self.draw_indexed_indirect_count_unchecked(
+            indirect_buffer,
+            count_buffer,
+            max_draw_count,
+            stride,
         ))
     }
 
-    fn validate_draw_indexed(
+    fn validate_draw_indexed_indirect_count(
         &self,
-        _index_count: u32,
-        _instance_count: u32,
-        _first_index: u32,
-        _vertex_offset: i32,
-        _first_instance: u32,
+        indirect_buffer: &Subbuffer<[u8]>,
+        count_buffer: &Subbuffer<[u8]>,
+        max_draw_count: u32,
+        stride: u32,
     ) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_features().draw_indirect_count {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "draw_indirect_count",
+                )])]),
+                vuids: &["VUID-vkCmdDrawIndexedIndirectCount-None-04445"],
+                ..Default::default()
+            }));
+        }
+
         if !self
             .queue_family_properties()
             .queue_flags
@@ -3098,7 +5676,74 @@ where
                 problem: "the queue family of the command buffer does not support \
                     graphics operations"
                     .into(),
-                vuids: &["VUID-vkCmdDrawIndexed-commandBuffer-cmdpool"],
+                vuids: &["VUID-vkCmdDrawIndexedIndirectCount-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkCmdDrawIndexedIndirectCount-commonparent
+        assert_eq!(self.device(), indirect_buffer.device());
+        assert_eq!(self.device(), count_buffer.device());
+
+        if !indirect_buffer
+            .buffer()
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER)
+        {
+            return Err(Box::new(ValidationError {
+                context: "indirect_buffer.usage()".into(),
+                problem: "does not contain `BufferUsage::INDIRECT_BUFFER`".into(),
+                vuids: &["VUID-vkCmdDrawIndexedIndirectCount-buffer-02709"],
+                ..Default::default()
+            }));
+        }
+
+        if !count_buffer
+            .buffer()
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER)
+        {
+            return Err(Box::new(ValidationError {
+                context: "count_buffer.usage()".into(),
+                problem: "does not contain `BufferUsage::INDIRECT_BUFFER`".into(),
+                vuids: &["VUID-vkCmdDrawIndexedIndirectCount-countBuffer-02714"],
+                ..Default::default()
+            }));
+        }
+
+        if !count_buffer.offset().is_multiple_of(4) {
+            return Err(Box::new(ValidationError {
+                context: "count_buffer.offset()".into(),
+                problem: "is not a multiple of 4".into(),
+                vuids: &["VUID-vkCmdDrawIndexedIndirectCount-countBufferOffset-02716"],
+                ..Default::default()
+            }));
+        }
+
+        if !stride.is_multiple_of(4)
+            || (stride as DeviceSize) < size_of::<DrawIndexedIndirectCommand>() as DeviceSize
+        {
+            return Err(Box::new(ValidationError {
+                context: "stride".into(),
+                problem: "is not a multiple of 4, or is less than \
+                    `size_of::<DrawIndexedIndirectCommand>()`"
+                    .into(),
+                vuids: &["VUID-vkCmdDrawIndexedIndirectCount-stride-03142"],
+                ..Default::default()
+            }));
+        }
+
+        if max_draw_count >= 1
+            && stride as DeviceSize * (max_draw_count as DeviceSize - 1)
+                + size_of::<DrawIndexedIndirectCommand>() as DeviceSize
+                > indirect_buffer.size()
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`max_draw_count` is not zero, but \
+                    `stride * (max_draw_count - 1) + size_of::<DrawIndexedIndirectCommand>()` is \
+                    greater than `indirect_buffer.size()`"
+                    .into(),
+                vuids: &["VUID-vkCmdDrawIndexedIndirectCount-maxDrawCount-03143"],
                 ..Default::default()
             }));
         }
@@ -3107,57 +5752,87 @@ where
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn draw_indexed_unchecked(
+    pub unsafe fn draw_indexed_indirect_count_unchecked(
         &mut self,
-        index_count: u32,
-        instance_count: u32,
-        first_index: u32,
-        vertex_offset: i32,
-        first_instance: u32,
+        indirect_buffer: &Subbuffer<[DrawIndexedIndirectCommand]>,
+        count_buffer: &Subbuffer<u32>,
+        max_draw_count: u32,
+        stride: u32,
     ) -> &mut Self {
         let fns = self.device().fns();
-        (fns.v1_0.cmd_draw_indexed)(
+
+        if self.device().api_version() >= Version::V1_2 {
+            (fns.v1_2.cmd_draw_indexed_indirect_count)(
+                self.handle(),
+                indirect_buffer.buffer().handle(),
+                indirect_buffer.offset(),
+                count_buffer.buffer().handle(),
+                count_buffer.offset(),
+                max_draw_count,
+                stride,
+            );
+        } else {
+            (fns.khr_draw_indirect_count
+                .cmd_draw_indexed_indirect_count_khr)(
+                self.handle(),
+                indirect_buffer.buffer().handle(),
+                indirect_buffer.offset(),
+                count_buffer.buffer().handle(),
+                count_buffer.offset(),
+                max_draw_count,
+                stride,
+            );
+        }
+
+        self
+    }
+    pub unsafe fn draw_mesh_tasks(
+        &mut self,
+        group_counts: [u32; 3],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw_mesh_tasks(group_counts)?;
+
+        Ok(self.draw_mesh_tasks_unchecked(group_counts))
+    }
+
+    fn validate_draw_mesh_tasks(
+        &self,
+        _group_counts: [u32; 3],
+    ) -> Result<(), Box<ValidationError>> {
+        self.validate_draw_mesh_tasks_common(VUIDType::DrawMeshTasksEXT)
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_mesh_tasks_unchecked(&mut self, group_counts: [u32; 3]) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_mesh_shader.cmd_draw_mesh_tasks_ext)(
             self.handle(),
-            index_count,
-            instance_count,
-            first_index,
-            vertex_offset,
-            first_instance,
+            group_counts[0],
+            group_counts[1],
+            group_counts[2],
         );
 
         self
     }
 
-    pub unsafe fn draw_indexed_indirect(
+    pub unsafe fn draw_mesh_tasks_indirect(
         &mut self,
-        indirect_buffer: &Subbuffer<[DrawIndexedIndirectCommand]>,
+        indirect_buffer: &Subbuffer<[DrawMeshTasksIndirectCommand]>,
         draw_count: u32,
         stride: u32,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_draw_indexed_indirect(indirect_buffer.as_bytes(), draw_count, stride)?;
+        self.validate_draw_mesh_tasks_indirect(indirect_buffer.as_bytes(), draw_count, stride)?;
 
-        Ok(self.draw_indexed_indirect_unchecked(indirect_buffer, draw_count, stride))
+        Ok(self.draw_mesh_tasks_indirect_unchecked(indirect_buffer, draw_count, stride))
     }
 
-    fn validate_draw_indexed_indirect(
+    fn validate_draw_mesh_tasks_indirect(
         &self,
         indirect_buffer: &Subbuffer<[u8]>,
         draw_count: u32,
         stride: u32,
     ) -> Result<(), Box<ValidationError>> {
-        if !self
-            .queue_family_properties()
-            .queue_flags
-            .intersects(QueueFlags::GRAPHICS)
-        {
-            return Err(Box::new(ValidationError {
-                problem: "the queue family of the command buffer does not support \
-                    graphics operations"
-                    .into(),
-                vuids: &["VUID-vkCmdDrawIndexedIndirect-commandBuffer-cmdpool"],
-                ..Default::default()
-            }));
-        }
+        self.validate_draw_mesh_tasks_common(VUIDType::DrawMeshTasksIndirectEXT)?;
 
         if !indirect_buffer
             .buffer()
@@ -3167,7 +5842,7 @@ where
             return Err(Box::new(ValidationError {
                 context: "indirect_buffer.usage()".into(),
                 problem: "does not contain `BufferUsage::INDIRECT_BUFFER`".into(),
-                vuids: &["VUID-vkCmdDrawIndexedIndirect-buffer-02709"],
+                vuids: &["VUID-vkCmdDrawMeshTasksIndirectEXT-buffer-02709"],
                 ..Default::default()
             }));
         }
@@ -3180,50 +5855,51 @@ where
                     requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
                         "multi_draw_indirect",
                     )])]),
-                    vuids: &["VUID-vkCmdDrawIndexedIndirect-drawCount-02718"],
+                    vuids: &["VUID-vkCmdDrawMeshTasksIndirectEXT-drawCount-02718"],
                 }));
             }
 
-            if stride % 4 != 0 {
+            if !stride.is_multiple_of(4) {
                 return Err(Box::new(ValidationError {
                     problem: "`draw_count` is greater than 1, but \
                         `stride` is not a multiple of 4"
                         .into(),
-                    vuids: &["VUID-vkCmdDrawIndexedIndirect-drawCount-00528"],
+                    vuids: &["VUID-vkCmdDrawMeshTasksIndirectEXT-drawCount-07088"],
                     ..Default::default()
                 }));
             }
 
-            if (stride as DeviceSize) < size_of::<DrawIndexedIndirectCommand>() as DeviceSize {
+            if (stride as DeviceSize) < size_of::<DrawMeshTasksIndirectCommand>() as DeviceSize {
                 return Err(Box::new(ValidationError {
                     problem: "`draw_count` is greater than 1, but \
-                        `stride` is not greater than `size_of::<DrawIndexedIndirectCommand>()`"
+                        `stride` is not greater than `size_of::<DrawMeshTasksIndirectCommand>()`"
                         .into(),
-                    vuids: &["VUID-vkCmdDrawIndexedIndirect-drawCount-00528"],
+                    vuids: &["VUID-vkCmdDrawMeshTasksIndirectEXT-drawCount-07088"],
                     ..Default::default()
                 }));
             }
 
             if stride as DeviceSize * (draw_count as DeviceSize - 1)
-                + size_of::<DrawIndexedIndirectCommand>() as DeviceSize
+                + size_of::<DrawMeshTasksIndirectCommand>() as DeviceSize
                 > indirect_buffer.size()
             {
                 return Err(Box::new(ValidationError {
                     problem: "`draw_count` is greater than 1, but \
-                        `stride * (draw_count - 1) + size_of::<DrawIndexedIndirectCommand>()` is \
-                        greater than `indirect_buffer.size()`"
+                        `stride * (draw_count - 1) + size_of::<DrawMeshTasksIndirectCommand>()` \
+                        is greater than `indirect_buffer.size()`"
                         .into(),
-                    vuids: &["VUID-vkCmdDrawIndexedIndirect-drawCount-00540"],
+                    vuids: &["VUID-vkCmdDrawMeshTasksIndirectEXT-drawCount-07090"],
                     ..Default::default()
                 }));
             }
         } else {
-            if size_of::<DrawIndexedIndirectCommand>() as DeviceSize > indirect_buffer.size() {
+            if size_of::<DrawMeshTasksIndirectCommand>() as DeviceSize > indirect_buffer.size() {
                 return Err(Box::new(ValidationError {
-                    problem: "`draw_count` is 1, but `size_of::<DrawIndexedIndirectCommand>()` is \
+                    problem: "`draw_count` is 1, but \
+                        `size_of::<DrawMeshTasksIndirectCommand>()` is \
                         greater than `indirect_buffer.size()`"
                         .into(),
-                    vuids: &["VUID-vkCmdDrawIndexedIndirect-drawCount-00539"],
+                    vuids: &["VUID-vkCmdDrawMeshTasksIndirectEXT-drawCount-07089"],
                     ..Default::default()
                 }));
             }
@@ -3235,7 +5911,7 @@ where
             return Err(Box::new(ValidationError {
                 context: "draw_count".into(),
                 problem: "is greater than the `max_draw_indirect_count` limit".into(),
-                vuids: &["VUID-vkCmdDrawIndexedIndirect-drawCount-02719"],
+                vuids: &["VUID-vkCmdDrawMeshTasksIndirectEXT-drawCount-02719"],
                 ..Default::default()
             }));
         }
@@ -3244,14 +5920,14 @@ where
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn draw_indexed_indirect_unchecked(
+    pub unsafe fn draw_mesh_tasks_indirect_unchecked(
         &mut self,
-        indirect_buffer: &Subbuffer<[DrawIndexedIndirectCommand]>,
+        indirect_buffer: &Subbuffer<[DrawMeshTasksIndirectCommand]>,
         draw_count: u32,
         stride: u32,
     ) -> &mut Self {
         let fns = self.device().fns();
-        (fns.v1_0.cmd_draw_indexed_indirect)(
+        (fns.ext_mesh_shader.cmd_draw_mesh_tasks_indirect_ext)(
             self.handle(),
             indirect_buffer.buffer().handle(),
             indirect_buffer.offset(),
@@ -3261,14 +5937,421 @@ where
 
         self
     }
+
+    fn validate_draw_mesh_tasks_common(
+        &self,
+        vuid_type: VUIDType,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: vuids!(vuid_type, "commandBuffer-cmdpool"),
+                ..Default::default()
+            }));
+        }
+
+        if !self.device().enabled_features().mesh_shader {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "mesh_shader",
+                )])]),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+    pub unsafe fn trace_rays(
+        &mut self,
+        shader_binding_table_addresses: &ShaderBindingTableAddresses,
+        dimensions: [u32; 3],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_trace_rays(shader_binding_table_addresses, dimensions)?;
+
+        Ok(self.trace_rays_unchecked(shader_binding_table_addresses, dimensions))
+    }
+
+    fn validate_trace_rays(
+        &self,
+        shader_binding_table_addresses: &ShaderBindingTableAddresses,
+        dimensions: [u32; 3],
+    ) -> Result<(), Box<ValidationError>> {
+        self.validate_trace_rays_common(VUIDType::TraceRaysKHR, shader_binding_table_addresses)?;
+
+        let properties = self.device().physical_device().properties();
+
+        let dimension_vuids: [&[&str]; 3] = [
+            &["VUID-vkCmdTraceRaysKHR-width-03638"],
+            &["VUID-vkCmdTraceRaysKHR-height-03639"],
+            &["VUID-vkCmdTraceRaysKHR-depth-03640"],
+        ];
+
+        for index in 0..3 {
+            if dimensions[index] as u64
+                > properties.max_compute_work_group_count[index] as u64
+                    * properties.max_compute_work_group_size[index] as u64
+            {
+                return Err(Box::new(ValidationError {
+                    context: format!("dimensions[{}]", index).into(),
+                    problem: format!(
+                        "is greater than the product of the \
+                        `max_compute_work_group_count[{0}]` and \
+                        `max_compute_work_group_size[{0}]` limits",
+                        index
+                    )
+                    .into(),
+                    vuids: dimension_vuids[index],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if dimensions.into_iter().map(u64::from).product::<u64>()
+            > properties.max_ray_dispatch_invocation_count.unwrap_or(0) as u64
+        {
+            return Err(Box::new(ValidationError {
+                context: "dimensions".into(),
+                problem: "the product of the elements is greater than the \
+                    `max_ray_dispatch_invocation_count` limit"
+                    .into(),
+                vuids: &["VUID-vkCmdTraceRaysKHR-width-03641"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn trace_rays_unchecked(
+        &mut self,
+        shader_binding_table_addresses: &ShaderBindingTableAddresses,
+        dimensions: [u32; 3],
+    ) -> &mut Self {
+        let raygen = shader_binding_table_addresses.raygen.to_vk();
+        let miss = shader_binding_table_addresses.miss.to_vk();
+        let hit = shader_binding_table_addresses.hit.to_vk();
+        let callable = shader_binding_table_addresses.callable.to_vk();
+
+        let fns = self.device().fns();
+        (fns.khr_ray_tracing_pipeline.cmd_trace_rays_khr)(
+            self.handle(),
+            &raygen,
+            &miss,
+            &hit,
+            &callable,
+            dimensions[0],
+            dimensions[1],
+            dimensions[2],
+        );
+
+        self
+    }
+
+    pub unsafe fn trace_rays_indirect(
+        &mut self,
+        shader_binding_table_addresses: &ShaderBindingTableAddresses,
+        indirect_buffer: &Subbuffer<[TraceRaysIndirectCommand]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_trace_rays_indirect(shader_binding_table_addresses, indirect_buffer)?;
+
+        Ok(self.trace_rays_indirect_unchecked(shader_binding_table_addresses, indirect_buffer))
+    }
+
+    fn validate_trace_rays_indirect(
+        &self,
+        shader_binding_table_addresses: &ShaderBindingTableAddresses,
+        indirect_buffer: &Subbuffer<[TraceRaysIndirectCommand]>,
+    ) -> Result<(), Box<ValidationError>> {
+        self.validate_trace_rays_common(
+            VUIDType::TraceRaysIndirectKHR,
+            shader_binding_table_addresses,
+        )?;
+
+        if !self
+            .device()
+            .enabled_features()
+            .ray_tracing_pipeline_trace_rays_indirect
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "ray_tracing_pipeline_trace_rays_indirect",
+                )])]),
+                vuids: &[
+                    "VUID-vkCmdTraceRaysIndirectKHR-rayTracingPipelineTraceRaysIndirect-03637",
+                ],
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkCmdTraceRaysIndirectKHR-commonparent
+        assert_eq!(self.device(), indirect_buffer.device());
+
+        if !indirect_buffer
+            .buffer()
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER)
+        {
+            return Err(Box::new(ValidationError {
+                context: "indirect_buffer.usage()".into(),
+                problem: "does not contain `BufferUsage::INDIRECT_BUFFER`".into(),
+                vuids: &["VUID-vkCmdTraceRaysIndirectKHR-indirectDeviceAddress-03633"],
+                ..Default::default()
+            }));
+        }
+
+        if !indirect_buffer
+            .buffer()
+            .usage()
+            .intersects(BufferUsage::SHADER_DEVICE_ADDRESS)
+        {
+            return Err(Box::new(ValidationError {
+                context: "indirect_buffer.usage()".into(),
+                problem: "does not contain `BufferUsage::SHADER_DEVICE_ADDRESS`".into(),
+                vuids: &["VUID-vkGetBufferDeviceAddress-buffer-02601"],
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkCmdTraceRaysIndirectKHR-indirectDeviceAddress-03634
+        // Ensured by the alignment of `TraceRaysIndirectCommand`.
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn trace_rays_indirect_unchecked(
+        &mut self,
+        shader_binding_table_addresses: &ShaderBindingTableAddresses,
+        indirect_buffer: &Subbuffer<[TraceRaysIndirectCommand]>,
+    ) -> &mut Self {
+        let raygen = shader_binding_table_addresses.raygen.to_vk();
+        let miss = shader_binding_table_addresses.miss.to_vk();
+        let hit = shader_binding_table_addresses.hit.to_vk();
+        let callable = shader_binding_table_addresses.callable.to_vk();
+
+        let fns = self.device().fns();
+        (fns.khr_ray_tracing_pipeline.cmd_trace_rays_indirect_khr)(
+            self.handle(),
+            &raygen,
+            &miss,
+            &hit,
+            &callable,
+            indirect_buffer.device_address_unchecked().get(),
+        );
+
+        self
+    }
+
+    fn validate_trace_rays_common(
+        &self,
+        vuid_type: VUIDType,
+        shader_binding_table_addresses: &ShaderBindingTableAddresses,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::COMPUTE)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    compute operations"
+                    .into(),
+                vuids: vuids!(vuid_type, "commandBuffer-cmdpool"),
+                ..Default::default()
+            }));
+        }
+
+        if !self.device().enabled_features().ray_tracing_pipeline {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "ray_tracing_pipeline",
+                )])]),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
+        let &ShaderBindingTableAddresses {
+            raygen,
+            miss,
+            hit,
+            callable,
+        } = shader_binding_table_addresses;
+
+        let properties = self.device().physical_device().properties();
+        let handle_alignment = properties.shader_group_handle_alignment.unwrap_or(1) as DeviceSize;
+        let base_alignment = properties.shader_group_base_alignment.unwrap_or(1) as DeviceSize;
+        let max_shader_group_stride = properties.max_shader_group_stride.unwrap_or(0) as DeviceSize;
+
+        if raygen.size != raygen.stride {
+            return Err(Box::new(ValidationError {
+                context: "shader_binding_table_addresses.raygen".into(),
+                problem: "`size` does not equal `stride`".into(),
+                vuids: vuids!(vuid_type, "size-04023"),
+                ..Default::default()
+            }));
+        }
+
+        if raygen.device_address % base_alignment != 0 {
+            return Err(Box::new(ValidationError {
+                context: "shader_binding_table_addresses.raygen.device_address".into(),
+                problem: "is not a multiple of the `shader_group_base_alignment` property".into(),
+                vuids: vuids!(vuid_type, "pRayGenShaderBindingTable-03682"),
+                ..Default::default()
+            }));
+        }
+
+        for (region, region_name, vuids_address, vuids_stride_alignment, vuids_stride_max) in [
+            (
+                miss,
+                "miss",
+                vuids!(vuid_type, "pMissShaderBindingTable-03685"),
+                vuids!(vuid_type, "stride-03686"),
+                vuids!(vuid_type, "stride-03687"),
+            ),
+            (
+                hit,
+                "hit",
+                vuids!(vuid_type, "pHitShaderBindingTable-03689"),
+                vuids!(vuid_type, "stride-03690"),
+                vuids!(vuid_type, "stride-03691"),
+            ),
+            (
+                callable,
+                "callable",
+                vuids!(vuid_type, "pCallableShaderBindingTable-03693"),
+                vuids!(vuid_type, "stride-03694"),
+                vuids!(vuid_type, "stride-03695"),
+            ),
+        ] {
+            if region.device_address % base_alignment != 0 {
+                return Err(Box::new(ValidationError {
+                    context: format!(
+                        "shader_binding_table_addresses.{}.device_address",
+                        region_name
+                    )
+                    .into(),
+                    problem: "is not a multiple of the `shader_group_base_alignment` property"
+                        .into(),
+                    vuids: vuids_address,
+                    ..Default::default()
+                }));
+            }
+
+            if region.stride % handle_alignment != 0 {
+                return Err(Box::new(ValidationError {
+                    context: format!("shader_binding_table_addresses.{}.stride", region_name)
+                        .into(),
+                    problem: "is not a multiple of the `shader_group_handle_alignment` property"
+                        .into(),
+                    vuids: vuids_stride_alignment,
+                    ..Default::default()
+                }));
+            }
+
+            if region.stride > max_shader_group_stride {
+                return Err(Box::new(ValidationError {
+                    context: format!("shader_binding_table_addresses.{}.stride", region_name)
+                        .into(),
+                    problem: "is greater than the `max_shader_group_stride` limit".into(),
+                    vuids: vuids_stride_max,
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The shader objects that are bound for a pipeline bind point, combined so that they can be
+/// validated in the same way as a pipeline.
+struct BoundShaderObjects {
+    bind_point: PipelineBindPoint,
+    layout: Arc<PipelineLayout>,
+    num_used_descriptor_sets: u32,
+    descriptor_binding_requirements: HashMap<(u32, u32), DescriptorBindingRequirements>,
+}
+
+unsafe impl DeviceOwned for BoundShaderObjects {
+    fn device(&self) -> &Arc<Device> {
+        self.layout.device()
+    }
+}
+
+impl Pipeline for BoundShaderObjects {
+    fn bind_point(&self) -> PipelineBindPoint {
+        self.bind_point
+    }
+
+    fn layout(&self) -> &Arc<PipelineLayout> {
+        &self.layout
+    }
+
+    fn num_used_descriptor_sets(&self) -> u32 {
+        self.num_used_descriptor_sets
+    }
+
+    fn descriptor_binding_requirements(
+        &self,
+    ) -> &HashMap<(u32, u32), DescriptorBindingRequirements> {
+        &self.descriptor_binding_requirements
+    }
+}
+
+/// What a draw command uses from the graphics bind point.
+#[derive(Clone, Copy)]
+enum BoundGraphics<'a> {
+    Pipeline(&'a GraphicsPipeline),
+    ShaderObjects(&'a HashMap<ShaderStage, Option<Arc<ShaderObject>>>),
+}
+
+impl BoundGraphics<'_> {
+    fn has_shader(self, stage: ShaderStage) -> bool {
+        match self {
+            BoundGraphics::Pipeline(pipeline) => pipeline.shader(stage).is_some(),
+            BoundGraphics::ShaderObjects(shaders) => matches!(shaders.get(&stage), Some(Some(_))),
+        }
+    }
+
+    fn view_mask(self, render_pass_state: &RenderPassState) -> u32 {
+        match self {
+            BoundGraphics::Pipeline(pipeline) => match pipeline
+                .subpass()
+                .expect("a bound graphics pipeline is never a library, so it always has a subpass")
+            {
+                PipelineSubpassType::BeginRenderPass(subpass) => subpass.render_pass().views_used(),
+                PipelineSubpassType::BeginRendering(rendering_info) => rendering_info.view_mask,
+            },
+            // Shader objects can only be used with `begin_rendering`.
+            BoundGraphics::ShaderObjects(_) => render_pass_state.rendering_info.view_mask,
+        }
+    }
 }
 
 #[derive(Clone, Copy)]
 enum VUIDType {
     Dispatch,
+    DispatchBase,
     DispatchIndirect,
     Draw,
     DrawIndirect,
+    DrawIndirectCount,
     DrawIndexed,
     DrawIndexedIndirect,
+    DrawIndexedIndirectCount,
+    DrawMultiEXT,
+    DrawMultiIndexedEXT,
+    DrawMeshTasksEXT,
+    DrawMeshTasksIndirectEXT,
+    TraceRaysKHR,
+    TraceRaysIndirectKHR,
 }
@@ -20,7 +20,7 @@ use crate::{
     device::{Device, DeviceOwned, QueueFlags},
     format::{ClearColorValue, ClearValue, NumericType},
     image::{view::ImageView, ImageAspects, ImageLayout, ImageUsage, SampleCount},
-    pipeline::graphics::subpass::PipelineRenderingCreateInfo,
+    pipeline::graphics::{multisample::SampleLocationsInfo, subpass::PipelineRenderingCreateInfo},
     render_pass::{
         AttachmentDescription, AttachmentLoadOp, AttachmentStoreOp, Framebuffer, RenderPass,
         ResolveMode, SubpassDescription,
@@ -98,6 +98,8 @@ where
             render_area_offset,
             render_area_extent,
             clear_values: _,
+            attachment_initial_sample_locations: _,
+            post_subpass_sample_locations: _,
             _ne: _,
         } = &render_pass_begin_info;
 
@@ -218,6 +220,27 @@ where
             }));
         }
 
+        if matches!(
+            &self.builder_state.conditional_rendering,
+            Some(state) if state.in_subpass
+        ) {
+            return Err(Box::new(ValidationError {
+                problem: "conditional rendering that was begun in the current subpass is \
+                    still active"
+                    .into(),
+                vuids: &["VUID-vkCmdEndConditionalRenderingEXT-None-01987"],
+                ..Default::default()
+            }));
+        }
+
+        if self.builder_state.transform_feedback_active {
+            return Err(Box::new(ValidationError {
+                problem: "transform feedback is active".into(),
+                vuids: &["VUID-vkCmdNextSubpass2-None-02350"],
+                ..Default::default()
+            }));
+        }
+
         Ok(())
     }
 
@@ -319,6 +342,27 @@ where
             }));
         }
 
+        if matches!(
+            &self.builder_state.conditional_rendering,
+            Some(state) if state.in_subpass
+        ) {
+            return Err(Box::new(ValidationError {
+                problem: "conditional rendering that was begun in the current subpass is \
+                    still active"
+                    .into(),
+                vuids: &["VUID-vkCmdEndConditionalRenderingEXT-None-01987"],
+                ..Default::default()
+            }));
+        }
+
+        if self.builder_state.transform_feedback_active {
+            return Err(Box::new(ValidationError {
+                problem: "transform feedback is active".into(),
+                vuids: &["VUID-vkCmdEndRenderPass2-None-02352"],
+                ..Default::default()
+            }));
+        }
+
         Ok(())
     }
 
@@ -630,6 +674,27 @@ where
             }));
         }
 
+        if matches!(
+            &self.builder_state.conditional_rendering,
+            Some(state) if state.in_subpass
+        ) {
+            return Err(Box::new(ValidationError {
+                problem: "conditional rendering that was begun in the current render pass \
+                    instance is still active"
+                    .into(),
+                vuids: &["VUID-vkCmdEndConditionalRenderingEXT-None-01987"],
+                ..Default::default()
+            }));
+        }
+
+        if self.builder_state.transform_feedback_active {
+            return Err(Box::new(ValidationError {
+                problem: "transform feedback is active".into(),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
         Ok(())
     }
 
@@ -955,6 +1020,8 @@ where
             render_area_offset: _,
             render_area_extent: _,
             clear_values: _,
+            attachment_initial_sample_locations: _,
+            post_subpass_sample_locations: _,
             _ne: _,
         } = render_pass_begin_info;
 
@@ -1087,6 +1154,24 @@ where
                             }));
                         }
                     }
+                    ImageLayout::FragmentShadingRateAttachmentOptimal => {
+                        if !image_view
+                            .usage()
+                            .intersects(ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT)
+                        {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "`framebuffer.attachments()[{0}]` is used in `render_pass` \
+                                    with the `ImageLayout::FragmentShadingRateAttachmentOptimal` \
+                                    layout, but `framebuffer.attachments()[{0}].usage()` does not \
+                                    contain `ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT`",
+                                    attachment_index,
+                                )
+                                .into(),
+                                ..Default::default()
+                            }));
+                        }
+                    }
                     ImageLayout::Undefined
                     | ImageLayout::General
                     | ImageLayout::Preinitialized
@@ -1106,6 +1191,8 @@ where
                 depth_stencil_resolve_attachment,
                 depth_resolve_mode: _,
                 stencil_resolve_mode: _,
+                fragment_shading_rate_attachment,
+                fragment_shading_rate_attachment_texel_size: _,
                 preserve_attachments: _,
                 _ne: _,
             } = subpass_desc;
@@ -1115,6 +1202,7 @@ where
                 .chain(color_resolve_attachments.iter().flatten())
                 .chain(depth_stencil_attachment.iter())
                 .chain(depth_stencil_resolve_attachment.iter())
+                .chain(fragment_shading_rate_attachment.iter())
             {
                 let image_view = &framebuffer.attachments()[atch_ref.attachment as usize];
 
@@ -1223,6 +1311,24 @@ where
                             }));
                         }
                     }
+                    ImageLayout::FragmentShadingRateAttachmentOptimal => {
+                        if !image_view
+                            .usage()
+                            .intersects(ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT)
+                        {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "`framebuffer.attachments()[{0}]` is used in `render_pass` \
+                                    with the `ImageLayout::FragmentShadingRateAttachmentOptimal` \
+                                    layout, but `framebuffer.attachments()[{0}].usage()` does not \
+                                    contain `ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT`",
+                                    atch_ref.attachment,
+                                )
+                                .into(),
+                                ..Default::default()
+                            }));
+                        }
+                    }
                     ImageLayout::Undefined
                     | ImageLayout::General
                     | ImageLayout::Preinitialized
@@ -1259,6 +1365,8 @@ where
             render_area_offset,
             render_area_extent,
             ref clear_values,
+            ref attachment_initial_sample_locations,
+            ref post_subpass_sample_locations,
             _ne: _,
         } = render_pass_begin_info;
 
@@ -1268,7 +1376,42 @@ where
             .map(|clear_value| clear_value.map(Into::into).unwrap_or_default())
             .collect();
 
-        let render_pass_begin_info = ash::vk::RenderPassBeginInfo {
+        let attachment_sample_locations_vk: SmallVec<[_; 4]> = attachment_initial_sample_locations
+            .iter()
+            .map(|(_, sample_locations)| sample_locations.to_vk_sample_locations())
+            .collect();
+        let attachment_initial_sample_locations_vk: SmallVec<[_; 4]> =
+            attachment_initial_sample_locations
+                .iter()
+                .zip(&attachment_sample_locations_vk)
+                .map(
+                    |(&(attachment_index, ref sample_locations), sample_locations_vk)| {
+                        ash::vk::AttachmentSampleLocationsEXT {
+                            attachment_index,
+                            sample_locations_info: sample_locations.to_vk(sample_locations_vk),
+                        }
+                    },
+                )
+                .collect();
+        let subpass_sample_locations_vk: SmallVec<[_; 4]> = post_subpass_sample_locations
+            .iter()
+            .map(|(_, sample_locations)| sample_locations.to_vk_sample_locations())
+            .collect();
+        let post_subpass_sample_locations_vk: SmallVec<[_; 4]> = post_subpass_sample_locations
+            .iter()
+            .zip(&subpass_sample_locations_vk)
+            .map(
+                |(&(subpass_index, ref sample_locations), sample_locations_vk)| {
+                    ash::vk::SubpassSampleLocationsEXT {
+                        subpass_index,
+                        sample_locations_info: sample_locations.to_vk(sample_locations_vk),
+                    }
+                },
+            )
+            .collect();
+        let mut sample_locations_begin_info_vk = None;
+
+        let mut render_pass_begin_info = ash::vk::RenderPassBeginInfo {
             render_pass: render_pass.handle(),
             framebuffer: framebuffer.handle(),
             render_area: ash::vk::Rect2D {
@@ -1286,6 +1429,26 @@ where
             ..Default::default()
         };
 
+        if !(attachment_initial_sample_locations_vk.is_empty()
+            && post_subpass_sample_locations_vk.is_empty())
+        {
+            let next = sample_locations_begin_info_vk.insert(
+                ash::vk::RenderPassSampleLocationsBeginInfoEXT {
+                    attachment_initial_sample_locations_count:
+                        attachment_initial_sample_locations_vk.len() as u32,
+                    p_attachment_initial_sample_locations: attachment_initial_sample_locations_vk
+                        .as_ptr(),
+                    post_subpass_sample_locations_count: post_subpass_sample_locations_vk.len()
+                        as u32,
+                    p_post_subpass_sample_locations: post_subpass_sample_locations_vk.as_ptr(),
+                    ..Default::default()
+                },
+            );
+
+            next.p_next = render_pass_begin_info.p_next;
+            render_pass_begin_info.p_next = next as *const _ as *const _;
+        }
+
         let &SubpassBeginInfo { contents, _ne: _ } = subpass_begin_info;
 
         let subpass_begin_info = ash::vk::SubpassBeginInfo {
@@ -1851,9 +2014,34 @@ pub struct RenderPassBeginInfo {
     ///
     /// To skip over an attachment whose load operation is something else, provide `None`.
     ///
+    /// [`ClearValues`] can be used to build this list, with each clear value checked against its
+    /// attachment as it is set.
+    ///
     /// The default value is empty, which must be overridden if the framebuffer has attachments.
     pub clear_values: Vec<Option<ClearValue>>,
 
+    /// Custom sample locations to use for the layout transitions of depth/stencil attachments
+    /// from their initial layout, given as pairs of an attachment index and the sample
+    /// locations to use for that attachment.
+    ///
+    /// If this is not empty, the
+    /// [`ext_sample_locations`](crate::device::DeviceExtensions::ext_sample_locations)
+    /// extension must be enabled on the device.
+    ///
+    /// The default value is empty.
+    pub attachment_initial_sample_locations: Vec<(u32, SampleLocationsInfo)>,
+
+    /// Custom sample locations to use for the automatic layout transitions of depth/stencil
+    /// attachments at the end of a subpass, given as pairs of a subpass index and the sample
+    /// locations that were used for rendering in that subpass.
+    ///
+    /// If this is not empty, the
+    /// [`ext_sample_locations`](crate::device::DeviceExtensions::ext_sample_locations)
+    /// extension must be enabled on the device.
+    ///
+    /// The default value is empty.
+    pub post_subpass_sample_locations: Vec<(u32, SampleLocationsInfo)>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -1868,6 +2056,8 @@ impl RenderPassBeginInfo {
             render_area_offset: [0, 0],
             render_area_extent,
             clear_values: Vec::new(),
+            attachment_initial_sample_locations: Vec::new(),
+            post_subpass_sample_locations: Vec::new(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -1879,6 +2069,8 @@ impl RenderPassBeginInfo {
             render_area_offset,
             render_area_extent,
             ref clear_values,
+            ref attachment_initial_sample_locations,
+            ref post_subpass_sample_locations,
             _ne,
         } = self;
 
@@ -1995,10 +2187,191 @@ impl RenderPassBeginInfo {
             }
         }
 
+        if !(attachment_initial_sample_locations.is_empty()
+            && post_subpass_sample_locations.is_empty())
+            && !device.enabled_extensions().ext_sample_locations
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`attachment_initial_sample_locations` or \
+                    `post_subpass_sample_locations` is not empty"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "ext_sample_locations",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        for (index, (attachment_index, sample_locations)) in
+            attachment_initial_sample_locations.iter().enumerate()
+        {
+            if *attachment_index as usize >= render_pass.attachments().len() {
+                return Err(Box::new(ValidationError {
+                    context: format!("attachment_initial_sample_locations[{}].0", index).into(),
+                    problem: "is not less than the number of attachments in `render_pass`".into(),
+                    vuids: &["VUID-VkAttachmentSampleLocationsEXT-attachmentIndex-01531"],
+                    ..Default::default()
+                }));
+            }
+
+            sample_locations.validate(device).map_err(|err| {
+                err.add_context(format!("attachment_initial_sample_locations[{}].1", index))
+            })?;
+        }
+
+        for (index, (subpass_index, sample_locations)) in
+            post_subpass_sample_locations.iter().enumerate()
+        {
+            if *subpass_index as usize >= render_pass.subpasses().len() {
+                return Err(Box::new(ValidationError {
+                    context: format!("post_subpass_sample_locations[{}].0", index).into(),
+                    problem: "is not less than the number of subpasses in `render_pass`".into(),
+                    vuids: &["VUID-VkSubpassSampleLocationsEXT-subpassIndex-01532"],
+                    ..Default::default()
+                }));
+            }
+
+            sample_locations.validate(device).map_err(|err| {
+                err.add_context(format!("post_subpass_sample_locations[{}].1", index))
+            })?;
+        }
+
         Ok(())
     }
 }
 
+/// A builder for the [`clear_values`] of a [`RenderPassBeginInfo`], that checks each clear value
+/// against the format and load operations of its attachment as soon as it is set.
+///
+/// Attachments that don't need to be cleared are left as `None`. Setting a clear value that
+/// doesn't match the attachment, for example a `ClearValue::Float` for an attachment with a
+/// `UINT` format, returns an error right away instead of when beginning the render pass.
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use vulkano::{
+/// #     command_buffer::{ClearValues, RenderPassBeginInfo},
+/// #     render_pass::Framebuffer,
+/// # };
+/// # let framebuffer: Arc<Framebuffer> = todo!();
+/// let clear_values = ClearValues::new(framebuffer.render_pass().clone())
+///     .set(0, [0.0, 0.0, 1.0, 1.0])
+///     .unwrap()
+///     .set(1, 1.0)
+///     .unwrap();
+///
+/// let render_pass_begin_info = RenderPassBeginInfo {
+///     clear_values: clear_values.into(),
+///     ..RenderPassBeginInfo::framebuffer(framebuffer)
+/// };
+/// ```
+///
+/// [`clear_values`]: RenderPassBeginInfo::clear_values
+#[derive(Clone, Debug)]
+pub struct ClearValues {
+    render_pass: Arc<RenderPass>,
+    clear_values: Vec<Option<ClearValue>>,
+}
+
+impl ClearValues {
+    /// Creates a new `ClearValues` for `render_pass`, with no clear values set.
+    #[inline]
+    pub fn new(render_pass: Arc<RenderPass>) -> Self {
+        let clear_values = vec![None; render_pass.attachments().len()];
+
+        Self {
+            render_pass,
+            clear_values,
+        }
+    }
+
+    /// Returns the render pass that the clear values are for.
+    #[inline]
+    pub fn render_pass(&self) -> &Arc<RenderPass> {
+        &self.render_pass
+    }
+
+    /// Sets the clear value of the attachment at index `attachment`.
+    ///
+    /// Returns an error if `attachment` is out of range, if the attachment doesn't need a clear
+    /// value, or if `clear_value` is not of the type that the attachment requires.
+    pub fn set(
+        mut self,
+        attachment: u32,
+        clear_value: impl Into<ClearValue>,
+    ) -> Result<Self, Box<ValidationError>> {
+        let clear_value = clear_value.into();
+
+        let attachment_desc = self
+            .render_pass
+            .attachments()
+            .get(attachment as usize)
+            .ok_or_else(|| {
+                Box::new(ValidationError {
+                    context: "attachment".into(),
+                    problem: "is not less than the number of attachments in the render pass".into(),
+                    ..Default::default()
+                })
+            })?;
+
+        match attachment_desc.required_clear_value() {
+            None => {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`render_pass.attachments()[{0}]` does not require a clear value",
+                        attachment
+                    )
+                    .into(),
+                    ..Default::default()
+                }));
+            }
+            Some(required_clear_value) => {
+                if required_clear_value != clear_value.clear_value_type() {
+                    return Err(Box::new(ValidationError {
+                        context: "clear_value".into(),
+                        problem: format!(
+                            "is `ClearValue::{1:?}`, but `render_pass.attachments()[{0}]` \
+                            requires a clear value of type `ClearValue::{2:?}`",
+                            attachment,
+                            clear_value.clear_value_type(),
+                            required_clear_value,
+                        )
+                        .into(),
+                        ..Default::default()
+                    }));
+                }
+            }
+        }
+
+        clear_value
+            .validate(self.render_pass.device())
+            .map_err(|err| err.add_context("clear_value"))?;
+
+        self.clear_values[attachment as usize] = Some(clear_value);
+
+        Ok(self)
+    }
+
+    /// Returns whether a clear value has been set for every attachment that needs one.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.render_pass
+            .attachments()
+            .iter()
+            .zip(&self.clear_values)
+            .all(|(attachment_desc, clear_value)| {
+                attachment_desc.required_clear_value().is_none() || clear_value.is_some()
+            })
+    }
+}
+
+impl From<ClearValues> for Vec<Option<ClearValue>> {
+    #[inline]
+    fn from(val: ClearValues) -> Self {
+        val.clear_values
+    }
+}
+
 /// Parameters to begin a new subpass within a render pass.
 #[derive(Clone, Debug)]
 pub struct SubpassBeginInfo {
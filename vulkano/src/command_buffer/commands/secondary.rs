@@ -467,6 +467,8 @@ where
         // VUID-vkCmdExecuteCommands-pCommandBuffers-00092
         // VUID-vkCmdExecuteCommands-pCommandBuffers-00093
         // VUID-vkCmdExecuteCommands-pCommandBuffers-00105
+        // Conditional rendering inheritance
+        // (`VkCommandBufferInheritanceConditionalRenderingInfoEXT`) is not supported yet.
 
         Ok(())
     }
@@ -8,17 +8,440 @@
 // according to those terms.
 
 use crate::{
-    command_buffer::{allocator::CommandBufferAllocator, sys::UnsafeCommandBufferBuilder},
+    command_buffer::{
+        allocator::CommandBufferAllocator,
+        auto::{RenderPassStateType, Resource},
+        sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder, ResourceInCommand,
+    },
     device::{DeviceOwned, QueueFlags},
+    render_pass::Subpass,
     sync::{
-        event::Event, BufferMemoryBarrier, DependencyFlags, DependencyInfo, ImageMemoryBarrier,
-        MemoryBarrier, PipelineStages,
+        event::Event, AccessFlags, BufferMemoryBarrier, DependencyFlags, DependencyInfo,
+        ImageMemoryBarrier, MemoryBarrier, PipelineStageAccessFlags, PipelineStages,
     },
     Requires, RequiresAllOf, RequiresOneOf, ValidationError, Version, VulkanObject,
 };
 use smallvec::SmallVec;
 use std::{ptr, sync::Arc};
 
+/// # Commands for manual synchronization.
+///
+/// `AutoCommandBufferBuilder` inserts pipeline barriers between commands automatically. These
+/// commands are meant for synchronizing the accesses that automatic synchronization doesn't know
+/// about, such as accesses through buffer device addresses, and the resources that were excluded
+/// from automatic synchronization with [`disable_auto_sync`](Self::disable_auto_sync),
+/// [`disable_auto_sync_for_buffer`](Self::disable_auto_sync_for_buffer) and
+/// [`disable_auto_sync_for_image`](Self::disable_auto_sync_for_image). Memory barriers on other
+/// resources are allowed, and are recorded in addition to the automatic barriers. Image layout
+/// transitions are taken into account by automatic synchronization when they are performed by
+/// [`pipeline_barrier`](Self::pipeline_barrier), but events can't perform layout transitions of
+/// images that are synchronized automatically.
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Inserts a pipeline barrier with the memory barriers and image layout transitions of
+    /// `dependency_info`.
+    ///
+    /// The barrier is recorded in addition to the barriers that are inserted automatically.
+    /// Automatic synchronization treats each image layout transition as a write to the image
+    /// subresources. It first brings the subresources into `old_layout` if they are in another
+    /// layout, and afterwards it expects them to be in `new_layout`.
+    ///
+    /// Within a render pass instance, the current subpass must have a dependency on itself that
+    /// includes the barriers, and only memory barriers and image memory barriers without a layout
+    /// transition or queue family ownership transfer are allowed.
+    ///
+    /// # Safety
+    ///
+    /// - For each image layout transition of an image that was excluded from automatic
+    ///   synchronization, `old_layout` must be the current layout of the image subresources, or
+    ///   [`ImageLayout::Undefined`].
+    ///
+    /// [`ImageLayout::Undefined`]: crate::image::ImageLayout::Undefined
+    pub unsafe fn pipeline_barrier(
+        &mut self,
+        dependency_info: DependencyInfo,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_pipeline_barrier(&dependency_info)?;
+
+        Ok(self.pipeline_barrier_unchecked(dependency_info))
+    }
+
+    fn validate_pipeline_barrier(
+        &self,
+        dependency_info: &DependencyInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_pipeline_barrier(dependency_info)?;
+
+        if let Some(render_pass_state) = &self.builder_state.render_pass {
+            let subpass = match &render_pass_state.render_pass {
+                RenderPassStateType::BeginRenderPass(state) => &state.subpass,
+                RenderPassStateType::BeginRendering(_) => {
+                    return Err(Box::new(ValidationError {
+                        problem: "a render pass instance that was begun with `begin_rendering` \
+                            is active"
+                            .into(),
+                        vuids: &["VUID-vkCmdPipelineBarrier2-None-06191"],
+                        ..Default::default()
+                    }));
+                }
+            };
+
+            Self::validate_pipeline_barrier_in_subpass(
+                subpass,
+                self.inner.queue_family_properties().queue_flags,
+                dependency_info,
+            )
+            .map_err(|err| err.add_context("dependency_info"))?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_pipeline_barrier_in_subpass(
+        subpass: &Subpass,
+        queue_flags: QueueFlags,
+        dependency_info: &DependencyInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        let &DependencyInfo {
+            dependency_flags,
+            ref memory_barriers,
+            ref buffer_memory_barriers,
+            ref image_memory_barriers,
+            _ne: _,
+        } = dependency_info;
+
+        if !buffer_memory_barriers.is_empty() {
+            return Err(Box::new(ValidationError {
+                context: "buffer_memory_barriers".into(),
+                problem: "is not empty, but a render pass instance is active".into(),
+                vuids: &["VUID-vkCmdPipelineBarrier2-bufferMemoryBarrierCount-01178"],
+                ..Default::default()
+            }));
+        }
+
+        let mut src_stages = PipelineStages::empty();
+        let mut src_access = AccessFlags::empty();
+        let mut dst_stages = PipelineStages::empty();
+        let mut dst_access = AccessFlags::empty();
+
+        for memory_barrier in memory_barriers {
+            src_stages |= memory_barrier.src_stages;
+            src_access |= memory_barrier.src_access;
+            dst_stages |= memory_barrier.dst_stages;
+            dst_access |= memory_barrier.dst_access;
+        }
+
+        for (barrier_index, image_memory_barrier) in image_memory_barriers.iter().enumerate() {
+            if image_memory_barrier.old_layout != image_memory_barrier.new_layout {
+                return Err(Box::new(ValidationError {
+                    context: format!("image_memory_barriers[{}]", barrier_index).into(),
+                    problem: "performs an image layout transition, but a render pass instance is \
+                        active"
+                        .into(),
+                    vuids: &["VUID-vkCmdPipelineBarrier2-oldLayout-01181"],
+                    ..Default::default()
+                }));
+            }
+
+            if image_memory_barrier
+                .queue_family_ownership_transfer
+                .is_some()
+            {
+                return Err(Box::new(ValidationError {
+                    context: format!("image_memory_barriers[{}]", barrier_index).into(),
+                    problem: "performs a queue family ownership transfer, but a render pass \
+                        instance is active"
+                        .into(),
+                    vuids: &["VUID-vkCmdPipelineBarrier2-srcQueueFamilyIndex-01182"],
+                    ..Default::default()
+                }));
+            }
+
+            src_stages |= image_memory_barrier.src_stages;
+            src_access |= image_memory_barrier.src_access;
+            dst_stages |= image_memory_barrier.dst_stages;
+            dst_access |= image_memory_barrier.dst_access;
+        }
+
+        let src_stages = src_stages.expand(queue_flags);
+        let dst_stages = dst_stages.expand(queue_flags);
+        let subpass_index = subpass.index();
+
+        let has_self_dependency = subpass
+            .render_pass()
+            .dependencies()
+            .iter()
+            .any(|dependency| {
+                dependency.src_subpass == Some(subpass_index)
+                    && dependency.dst_subpass == Some(subpass_index)
+                    && (dependency_flags.intersects(DependencyFlags::BY_REGION)
+                        || !dependency
+                            .dependency_flags
+                            .intersects(DependencyFlags::BY_REGION))
+                    && (dependency_flags.intersects(DependencyFlags::VIEW_LOCAL)
+                        || !dependency
+                            .dependency_flags
+                            .intersects(DependencyFlags::VIEW_LOCAL))
+                    && dependency
+                        .src_stages
+                        .expand(queue_flags)
+                        .contains(src_stages)
+                    && dependency
+                        .dst_stages
+                        .expand(queue_flags)
+                        .contains(dst_stages)
+                    && dependency.src_access.contains_accesses(src_access)
+                    && dependency.dst_access.contains_accesses(dst_access)
+            });
+
+        if !has_self_dependency {
+            return Err(Box::new(ValidationError {
+                problem: "a render pass instance is active, but the render pass does not have a \
+                    dependency from the current subpass to itself whose dependency flags, \
+                    stages and accesses include those of the barriers"
+                    .into(),
+                vuids: &["VUID-vkCmdPipelineBarrier2-None-06191"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn pipeline_barrier_unchecked(
+        &mut self,
+        dependency_info: DependencyInfo,
+    ) -> &mut Self {
+        // A layout transition is a write, after which the image is in a new layout, so it's
+        // added as a use of the image for automatic synchronization to take into account.
+        let used_resources = dependency_info
+            .image_memory_barriers
+            .iter()
+            .enumerate()
+            .filter(|(_, barrier)| barrier.old_layout != barrier.new_layout)
+            .map(|(index, barrier)| {
+                (
+                    ResourceInCommand::ImageMemoryBarrier {
+                        index: index as u32,
+                    }
+                    .into(),
+                    Resource::Image {
+                        image: barrier.image.clone(),
+                        subresource_range: barrier.subresource_range.clone(),
+                        memory_access: PipelineStageAccessFlags::AllCommands_MemoryWrite,
+                        start_layout: barrier.old_layout,
+                        end_layout: barrier.new_layout,
+                    },
+                )
+            })
+            .collect();
+
+        self.add_command(
+            "pipeline_barrier",
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.pipeline_barrier_unchecked(&dependency_info);
+            },
+        );
+
+        self
+    }
+
+    /// Signals `event` when the operations in the first synchronization scope of
+    /// `dependency_info` have completed.
+    ///
+    /// Unlike a pipeline barrier, the commands that are recorded between `set_event` and
+    /// [`wait_events`](Self::wait_events) can overlap with the operations that are being waited
+    /// on.
+    ///
+    /// # Safety
+    ///
+    /// - `event` must not be set or reset by the host or another command buffer while this
+    ///   command buffer is executing.
+    pub unsafe fn set_event(
+        &mut self,
+        event: Arc<Event>,
+        dependency_info: DependencyInfo,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_event(&event, &dependency_info)?;
+
+        Ok(self.set_event_unchecked(event, dependency_info))
+    }
+
+    fn validate_set_event(
+        &self,
+        event: &Event,
+        dependency_info: &DependencyInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_event(event, dependency_info)?;
+
+        if self.builder_state.render_pass.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "a render pass instance is active".into(),
+                vuids: &["VUID-vkCmdSetEvent2-renderpass"],
+                ..Default::default()
+            }));
+        }
+
+        self.validate_manual_dependency_info(dependency_info)
+            .map_err(|err| err.add_context("dependency_info"))?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_event_unchecked(
+        &mut self,
+        event: Arc<Event>,
+        dependency_info: DependencyInfo,
+    ) -> &mut Self {
+        self.add_command(
+            "set_event",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_event_unchecked(&event, &dependency_info);
+            },
+        );
+
+        self
+    }
+
+    /// Waits for `events` to be signaled, and then performs the second synchronization scope of
+    /// the dependency info of each event.
+    ///
+    /// # Safety
+    ///
+    /// - Each event must be signaled, either by a [`set_event`](Self::set_event) command that is
+    ///   earlier in submission order on the same queue, or on the host before this command
+    ///   buffer is submitted.
+    /// - If an event is signaled by `set_event`, the dependency info that is given with it must
+    ///   be the same as the dependency info that was given to `set_event`.
+    pub unsafe fn wait_events(
+        &mut self,
+        events: impl IntoIterator<Item = (Arc<Event>, DependencyInfo)>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        let events: SmallVec<[_; 4]> = events.into_iter().collect();
+        self.validate_wait_events(&events)?;
+
+        Ok(self.wait_events_unchecked(events))
+    }
+
+    fn validate_wait_events(
+        &self,
+        events: &[(Arc<Event>, DependencyInfo)],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_wait_events(events)?;
+
+        for (event_index, (_, dependency_info)) in events.iter().enumerate() {
+            self.validate_manual_dependency_info(dependency_info)
+                .map_err(|err| err.add_context(format!("events[{}].1", event_index)))?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn wait_events_unchecked(
+        &mut self,
+        events: impl IntoIterator<Item = (Arc<Event>, DependencyInfo)>,
+    ) -> &mut Self {
+        let events: SmallVec<[_; 4]> = events.into_iter().collect();
+
+        self.add_command(
+            "wait_events",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.wait_events_unchecked(&events);
+            },
+        );
+
+        self
+    }
+
+    /// Unsignals `event` when the operations in `stages` have completed.
+    ///
+    /// # Safety
+    ///
+    /// - `event` must not be set or reset by the host or another command buffer while this
+    ///   command buffer is executing.
+    /// - `event` must not be waited on by a [`wait_events`](Self::wait_events) command that is
+    ///   still executing.
+    pub unsafe fn reset_event(
+        &mut self,
+        event: Arc<Event>,
+        stages: PipelineStages,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_reset_event(&event, stages)?;
+
+        Ok(self.reset_event_unchecked(event, stages))
+    }
+
+    fn validate_reset_event(
+        &self,
+        event: &Event,
+        stages: PipelineStages,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_reset_event(event, stages)?;
+
+        if self.builder_state.render_pass.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "a render pass instance is active".into(),
+                vuids: &["VUID-vkCmdResetEvent2-renderpass"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn reset_event_unchecked(
+        &mut self,
+        event: Arc<Event>,
+        stages: PipelineStages,
+    ) -> &mut Self {
+        self.add_command(
+            "reset_event",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.reset_event_unchecked(&event, stages);
+            },
+        );
+
+        self
+    }
+
+    fn validate_manual_dependency_info(
+        &self,
+        dependency_info: &DependencyInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        for (barrier_index, image_memory_barrier) in
+            dependency_info.image_memory_barriers.iter().enumerate()
+        {
+            if image_memory_barrier.old_layout != image_memory_barrier.new_layout
+                && !self.auto_sync_disabled
+                && !self
+                    .auto_sync_disabled_images
+                    .contains(&image_memory_barrier.image)
+            {
+                return Err(Box::new(ValidationError {
+                    context: format!("image_memory_barriers[{}]", barrier_index).into(),
+                    problem: "performs an image layout transition, but `image` has not been \
+                        excluded from automatic synchronization"
+                        .into(),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl<A> UnsafeCommandBufferBuilder<A>
 where
     A: CommandBufferAllocator,
@@ -0,0 +1,677 @@
+// Copyright (c) 2024 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    buffer::{BufferUsage, Subbuffer},
+    command_buffer::{
+        allocator::CommandBufferAllocator,
+        auto::{Resource, ResourceUseRef2},
+        sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder, ResourceInCommand,
+    },
+    device::{DeviceOwned, QueueFlags},
+    sync::PipelineStageAccessFlags,
+    DeviceSize, Requires, RequiresAllOf, RequiresOneOf, ValidationError, VulkanObject,
+};
+use smallvec::SmallVec;
+
+/// # Commands for transform feedback.
+///
+/// Transform feedback captures the vertex outputs of the last pre-rasterization shader stage of
+/// a graphics pipeline into buffers. The shader must declare the `Xfb` execution mode, and
+/// decorate the captured outputs with `XfbBuffer`, `XfbStride` and `Offset`.
+///
+/// These commands require the [`transform_feedback`] feature to be enabled on the device.
+///
+/// [`transform_feedback`]: crate::device::Features::transform_feedback
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Binds transform feedback buffers to the binding slots starting at `first_binding`.
+    ///
+    /// Each buffer must have been created with the
+    /// [`TRANSFORM_FEEDBACK_BUFFER`](BufferUsage::TRANSFORM_FEEDBACK_BUFFER) usage.
+    pub fn bind_transform_feedback_buffers(
+        &mut self,
+        first_binding: u32,
+        transform_feedback_buffers: impl IntoIterator<Item = Subbuffer<[u8]>>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        let transform_feedback_buffers: SmallVec<[_; 4]> =
+            transform_feedback_buffers.into_iter().collect();
+        self.validate_bind_transform_feedback_buffers(first_binding, &transform_feedback_buffers)?;
+
+        unsafe {
+            Ok(self.bind_transform_feedback_buffers_unchecked(
+                first_binding,
+                transform_feedback_buffers,
+            ))
+        }
+    }
+
+    fn validate_bind_transform_feedback_buffers(
+        &self,
+        first_binding: u32,
+        transform_feedback_buffers: &[Subbuffer<[u8]>],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_bind_transform_feedback_buffers(first_binding, transform_feedback_buffers)?;
+
+        if self.builder_state.transform_feedback_active {
+            return Err(Box::new(ValidationError {
+                problem: "transform feedback is active".into(),
+                vuids: &["VUID-vkCmdBindTransformFeedbackBuffersEXT-None-02365"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn bind_transform_feedback_buffers_unchecked(
+        &mut self,
+        first_binding: u32,
+        transform_feedback_buffers: impl IntoIterator<Item = Subbuffer<[u8]>>,
+    ) -> &mut Self {
+        let transform_feedback_buffers: SmallVec<[_; 4]> =
+            transform_feedback_buffers.into_iter().collect();
+
+        for (i, buffer) in transform_feedback_buffers.iter().enumerate() {
+            self.builder_state
+                .transform_feedback_buffers
+                .insert(first_binding + i as u32, buffer.clone());
+        }
+
+        self.add_command(
+            "bind_transform_feedback_buffers",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.bind_transform_feedback_buffers_unchecked(
+                    first_binding,
+                    &transform_feedback_buffers,
+                );
+            },
+        );
+
+        self
+    }
+
+    /// Makes transform feedback active.
+    ///
+    /// Until [`end_transform_feedback`](Self::end_transform_feedback) is called, the vertex
+    /// outputs of draw commands are written to the bound transform feedback buffers.
+    ///
+    /// `counter_buffers` are assigned to the transform feedback buffer bindings starting at
+    /// `first_counter_buffer`. A counter buffer holds the byte offset in its transform feedback
+    /// buffer at which to resume writing, as written by a previous `end_transform_feedback`. If a
+    /// counter buffer is `None`, writing starts at the beginning of the transform feedback
+    /// buffer.
+    pub fn begin_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: impl IntoIterator<Item = Option<Subbuffer<u32>>>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        let counter_buffers: SmallVec<[_; 4]> = counter_buffers.into_iter().collect();
+        self.validate_begin_transform_feedback(first_counter_buffer, &counter_buffers)?;
+
+        unsafe {
+            Ok(self.begin_transform_feedback_unchecked(first_counter_buffer, counter_buffers))
+        }
+    }
+
+    fn validate_begin_transform_feedback(
+        &self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_begin_transform_feedback(first_counter_buffer, counter_buffers)?;
+
+        if self.builder_state.transform_feedback_active {
+            return Err(Box::new(ValidationError {
+                problem: "transform feedback is already active".into(),
+                vuids: &["VUID-vkCmdBeginTransformFeedbackEXT-None-02367"],
+                ..Default::default()
+            }));
+        }
+
+        let render_pass_state = self.builder_state.render_pass.as_ref().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "a render pass instance is not active".into(),
+                vuids: &["VUID-vkCmdBeginTransformFeedbackEXT-renderpass"],
+                ..Default::default()
+            })
+        })?;
+
+        if render_pass_state.rendering_info.view_mask != 0 {
+            return Err(Box::new(ValidationError {
+                problem: "the current subpass has a non-zero `view_mask`".into(),
+                vuids: &["VUID-vkCmdBeginTransformFeedbackEXT-None-02373"],
+                ..Default::default()
+            }));
+        }
+
+        if self.builder_state.pipeline_graphics.is_none() {
+            return Err(Box::new(ValidationError {
+                problem: "no graphics pipeline is currently bound".into(),
+                vuids: &["VUID-vkCmdBeginTransformFeedbackEXT-None-06233"],
+                ..Default::default()
+            }));
+        }
+
+        // TODO:
+        // VUID-vkCmdBeginTransformFeedbackEXT-None-06233
+        // Check that the last pre-rasterization shader stage declares the `Xfb` execution mode.
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn begin_transform_feedback_unchecked(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: impl IntoIterator<Item = Option<Subbuffer<u32>>>,
+    ) -> &mut Self {
+        let counter_buffers: SmallVec<[_; 4]> = counter_buffers.into_iter().collect();
+        self.builder_state.transform_feedback_active = true;
+
+        let mut used_resources: Vec<(ResourceUseRef2, Resource)> = self
+            .builder_state
+            .transform_feedback_buffers
+            .iter()
+            .map(|(&binding, buffer)| {
+                (
+                    ResourceInCommand::TransformFeedbackBuffer { binding }.into(),
+                    Resource::Buffer {
+                        buffer: buffer.clone(),
+                        range: 0..buffer.size(),
+                        memory_access:
+                            PipelineStageAccessFlags::TransformFeedback_TransformFeedbackWrite,
+                    },
+                )
+            })
+            .collect();
+        add_counter_buffer_resources(
+            &mut used_resources,
+            first_counter_buffer,
+            &counter_buffers,
+            PipelineStageAccessFlags::TransformFeedback_TransformFeedbackCounterRead,
+        );
+
+        self.add_command(
+            "begin_transform_feedback",
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.begin_transform_feedback_unchecked(first_counter_buffer, &counter_buffers);
+            },
+        );
+
+        self
+    }
+
+    /// Makes transform feedback inactive.
+    ///
+    /// The byte offsets in the transform feedback buffers up to which vertex outputs were
+    /// written are stored in `counter_buffers`, so that a later `begin_transform_feedback` can
+    /// resume writing there.
+    pub fn end_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: impl IntoIterator<Item = Option<Subbuffer<u32>>>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        let counter_buffers: SmallVec<[_; 4]> = counter_buffers.into_iter().collect();
+        self.validate_end_transform_feedback(first_counter_buffer, &counter_buffers)?;
+
+        unsafe { Ok(self.end_transform_feedback_unchecked(first_counter_buffer, counter_buffers)) }
+    }
+
+    fn validate_end_transform_feedback(
+        &self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_end_transform_feedback(first_counter_buffer, counter_buffers)?;
+
+        if !self.builder_state.transform_feedback_active {
+            return Err(Box::new(ValidationError {
+                problem: "transform feedback is not active".into(),
+                vuids: &["VUID-vkCmdEndTransformFeedbackEXT-None-02375"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn end_transform_feedback_unchecked(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: impl IntoIterator<Item = Option<Subbuffer<u32>>>,
+    ) -> &mut Self {
+        let counter_buffers: SmallVec<[_; 4]> = counter_buffers.into_iter().collect();
+        self.builder_state.transform_feedback_active = false;
+
+        let mut used_resources = Vec::new();
+        add_counter_buffer_resources(
+            &mut used_resources,
+            first_counter_buffer,
+            &counter_buffers,
+            PipelineStageAccessFlags::TransformFeedback_TransformFeedbackCounterWrite,
+        );
+
+        self.add_command(
+            "end_transform_feedback",
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.end_transform_feedback_unchecked(first_counter_buffer, &counter_buffers);
+            },
+        );
+
+        self
+    }
+}
+
+fn add_counter_buffer_resources(
+    used_resources: &mut Vec<(ResourceUseRef2, Resource)>,
+    first_counter_buffer: u32,
+    counter_buffers: &[Option<Subbuffer<u32>>],
+    memory_access: PipelineStageAccessFlags,
+) {
+    used_resources.extend(
+        counter_buffers
+            .iter()
+            .enumerate()
+            .filter_map(|(i, counter_buffer)| {
+                counter_buffer.as_ref().map(|counter_buffer| {
+                    (
+                        ResourceInCommand::TransformFeedbackCounterBuffer {
+                            index: first_counter_buffer + i as u32,
+                        }
+                        .into(),
+                        Resource::Buffer {
+                            buffer: counter_buffer.as_bytes().clone(),
+                            range: 0..counter_buffer.size(),
+                            memory_access,
+                        },
+                    )
+                })
+            }),
+    );
+}
+
+impl<A> UnsafeCommandBufferBuilder<A>
+where
+    A: CommandBufferAllocator,
+{
+    pub unsafe fn bind_transform_feedback_buffers(
+        &mut self,
+        first_binding: u32,
+        transform_feedback_buffers: &[Subbuffer<[u8]>],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_bind_transform_feedback_buffers(first_binding, transform_feedback_buffers)?;
+
+        Ok(self
+            .bind_transform_feedback_buffers_unchecked(first_binding, transform_feedback_buffers))
+    }
+
+    fn validate_bind_transform_feedback_buffers(
+        &self,
+        first_binding: u32,
+        transform_feedback_buffers: &[Subbuffer<[u8]>],
+    ) -> Result<(), Box<ValidationError>> {
+        let device = self.device();
+
+        if !device.enabled_features().transform_feedback {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "transform_feedback",
+                )])]),
+                vuids: &["VUID-vkCmdBindTransformFeedbackBuffersEXT-transformFeedback-02355"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdBindTransformFeedbackBuffersEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        let properties = device.physical_device().properties();
+        let max_transform_feedback_buffers = properties
+            .max_transform_feedback_buffers
+            .unwrap_or_default();
+
+        if first_binding >= max_transform_feedback_buffers {
+            return Err(Box::new(ValidationError {
+                context: "first_binding".into(),
+                problem: "is not less than the `max_transform_feedback_buffers` limit".into(),
+                vuids: &["VUID-vkCmdBindTransformFeedbackBuffersEXT-firstBinding-02356"],
+                ..Default::default()
+            }));
+        }
+
+        if first_binding + transform_feedback_buffers.len() as u32 > max_transform_feedback_buffers
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`first_binding` + the length of `transform_feedback_buffers` is \
+                    greater than the `max_transform_feedback_buffers` limit"
+                    .into(),
+                vuids: &["VUID-vkCmdBindTransformFeedbackBuffersEXT-firstBinding-02357"],
+                ..Default::default()
+            }));
+        }
+
+        for (buffer_index, buffer) in transform_feedback_buffers.iter().enumerate() {
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-commonparent
+            assert_eq!(device, buffer.device());
+
+            if buffer.offset() % 4 != 0 {
+                return Err(Box::new(ValidationError {
+                    context: format!("transform_feedback_buffers[{}].offset()", buffer_index)
+                        .into(),
+                    problem: "is not a multiple of 4".into(),
+                    vuids: &["VUID-vkCmdBindTransformFeedbackBuffersEXT-pOffsets-02359"],
+                    ..Default::default()
+                }));
+            }
+
+            if !buffer
+                .buffer()
+                .usage()
+                .intersects(BufferUsage::TRANSFORM_FEEDBACK_BUFFER)
+            {
+                return Err(Box::new(ValidationError {
+                    context: format!("transform_feedback_buffers[{}].usage()", buffer_index).into(),
+                    problem: "does not contain `BufferUsage::TRANSFORM_FEEDBACK_BUFFER`".into(),
+                    vuids: &["VUID-vkCmdBindTransformFeedbackBuffersEXT-pBuffers-02360"],
+                    ..Default::default()
+                }));
+            }
+
+            if buffer.size()
+                > properties
+                    .max_transform_feedback_buffer_size
+                    .unwrap_or_default()
+            {
+                return Err(Box::new(ValidationError {
+                    context: format!("transform_feedback_buffers[{}].size()", buffer_index).into(),
+                    problem: "is greater than the `max_transform_feedback_buffer_size` limit"
+                        .into(),
+                    vuids: &["VUID-vkCmdBindTransformFeedbackBuffersEXT-pSize-02361"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn bind_transform_feedback_buffers_unchecked(
+        &mut self,
+        first_binding: u32,
+        transform_feedback_buffers: &[Subbuffer<[u8]>],
+    ) -> &mut Self {
+        if transform_feedback_buffers.is_empty() {
+            return self;
+        }
+
+        let mut buffers_vk: SmallVec<[_; 4]> =
+            SmallVec::with_capacity(transform_feedback_buffers.len());
+        let mut offsets_vk: SmallVec<[_; 4]> =
+            SmallVec::with_capacity(transform_feedback_buffers.len());
+        let mut sizes_vk: SmallVec<[_; 4]> =
+            SmallVec::with_capacity(transform_feedback_buffers.len());
+
+        for buffer in transform_feedback_buffers {
+            buffers_vk.push(buffer.buffer().handle());
+            offsets_vk.push(buffer.offset());
+            sizes_vk.push(buffer.size());
+        }
+
+        let fns = self.device().fns();
+        (fns.ext_transform_feedback
+            .cmd_bind_transform_feedback_buffers_ext)(
+            self.handle(),
+            first_binding,
+            buffers_vk.len() as u32,
+            buffers_vk.as_ptr(),
+            offsets_vk.as_ptr(),
+            sizes_vk.as_ptr(),
+        );
+
+        self
+    }
+
+    pub unsafe fn begin_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_begin_transform_feedback(first_counter_buffer, counter_buffers)?;
+
+        Ok(self.begin_transform_feedback_unchecked(first_counter_buffer, counter_buffers))
+    }
+
+    fn validate_begin_transform_feedback(
+        &self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_features().transform_feedback {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "transform_feedback",
+                )])]),
+                vuids: &["VUID-vkCmdBeginTransformFeedbackEXT-transformFeedback-02366"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdBeginTransformFeedbackEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        self.validate_counter_buffers(
+            first_counter_buffer,
+            counter_buffers,
+            &[
+                "VUID-vkCmdBeginTransformFeedbackEXT-firstCounterBuffer-02368",
+                "VUID-vkCmdBeginTransformFeedbackEXT-firstCounterBuffer-02369",
+                "VUID-vkCmdBeginTransformFeedbackEXT-pCounterBuffers-02372",
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn begin_transform_feedback_unchecked(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) -> &mut Self {
+        let (counter_buffers_vk, counter_buffer_offsets_vk) = counter_buffers_vk(counter_buffers);
+
+        let fns = self.device().fns();
+        (fns.ext_transform_feedback.cmd_begin_transform_feedback_ext)(
+            self.handle(),
+            first_counter_buffer,
+            counter_buffers_vk.len() as u32,
+            counter_buffers_vk.as_ptr(),
+            counter_buffer_offsets_vk.as_ptr(),
+        );
+
+        self
+    }
+
+    pub unsafe fn end_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_end_transform_feedback(first_counter_buffer, counter_buffers)?;
+
+        Ok(self.end_transform_feedback_unchecked(first_counter_buffer, counter_buffers))
+    }
+
+    fn validate_end_transform_feedback(
+        &self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_features().transform_feedback {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "transform_feedback",
+                )])]),
+                vuids: &["VUID-vkCmdEndTransformFeedbackEXT-transformFeedback-02374"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdEndTransformFeedbackEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        self.validate_counter_buffers(
+            first_counter_buffer,
+            counter_buffers,
+            &[
+                "VUID-vkCmdEndTransformFeedbackEXT-firstCounterBuffer-02376",
+                "VUID-vkCmdEndTransformFeedbackEXT-firstCounterBuffer-02377",
+                "VUID-vkCmdEndTransformFeedbackEXT-pCounterBuffers-02380",
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn end_transform_feedback_unchecked(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) -> &mut Self {
+        let (counter_buffers_vk, counter_buffer_offsets_vk) = counter_buffers_vk(counter_buffers);
+
+        let fns = self.device().fns();
+        (fns.ext_transform_feedback.cmd_end_transform_feedback_ext)(
+            self.handle(),
+            first_counter_buffer,
+            counter_buffers_vk.len() as u32,
+            counter_buffers_vk.as_ptr(),
+            counter_buffer_offsets_vk.as_ptr(),
+        );
+
+        self
+    }
+
+    fn validate_counter_buffers(
+        &self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+        vuids: &'static [&'static str; 3],
+    ) -> Result<(), Box<ValidationError>> {
+        let device = self.device();
+        let max_transform_feedback_buffers = device
+            .physical_device()
+            .properties()
+            .max_transform_feedback_buffers
+            .unwrap_or_default();
+
+        if first_counter_buffer >= max_transform_feedback_buffers {
+            return Err(Box::new(ValidationError {
+                context: "first_counter_buffer".into(),
+                problem: "is not less than the `max_transform_feedback_buffers` limit".into(),
+                vuids: &vuids[0..1],
+                ..Default::default()
+            }));
+        }
+
+        if first_counter_buffer + counter_buffers.len() as u32 > max_transform_feedback_buffers {
+            return Err(Box::new(ValidationError {
+                problem: "`first_counter_buffer` + the length of `counter_buffers` is \
+                    greater than the `max_transform_feedback_buffers` limit"
+                    .into(),
+                vuids: &vuids[1..2],
+                ..Default::default()
+            }));
+        }
+
+        for (buffer_index, counter_buffer) in counter_buffers.iter().enumerate() {
+            let counter_buffer = match counter_buffer {
+                Some(counter_buffer) => counter_buffer,
+                None => continue,
+            };
+
+            assert_eq!(device, counter_buffer.device());
+
+            if !counter_buffer
+                .buffer()
+                .usage()
+                .intersects(BufferUsage::TRANSFORM_FEEDBACK_COUNTER_BUFFER)
+            {
+                return Err(Box::new(ValidationError {
+                    context: format!("counter_buffers[{}].usage()", buffer_index).into(),
+                    problem: "does not contain `BufferUsage::TRANSFORM_FEEDBACK_COUNTER_BUFFER`"
+                        .into(),
+                    vuids: &vuids[2..3],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn counter_buffers_vk(
+    counter_buffers: &[Option<Subbuffer<u32>>],
+) -> (SmallVec<[ash::vk::Buffer; 4]>, SmallVec<[DeviceSize; 4]>) {
+    counter_buffers
+        .iter()
+        .map(|counter_buffer| match counter_buffer {
+            Some(counter_buffer) => (counter_buffer.buffer().handle(), counter_buffer.offset()),
+            None => (ash::vk::Buffer::null(), 0),
+        })
+        .unzip()
+}
@@ -141,6 +141,8 @@ pub mod auto;
 mod commands;
 pub mod pool;
 pub mod sys;
+#[cfg(feature = "trace")]
+pub mod trace;
 mod traits;
 
 #[repr(C)]
@@ -162,6 +164,35 @@ pub struct DrawIndexedIndirectCommand {
     pub first_instance: u32,
 }
 
+/// The number of workgroups of a single mesh shading draw, used by
+/// [`draw_mesh_tasks_indirect`](AutoCommandBufferBuilder::draw_mesh_tasks_indirect).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
+pub struct DrawMeshTasksIndirectCommand {
+    pub group_count_x: u32,
+    pub group_count_y: u32,
+    pub group_count_z: u32,
+}
+
+/// The range of vertices of a single draw, used by
+/// [`draw_multi`](AutoCommandBufferBuilder::draw_multi).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
+pub struct MultiDrawInfo {
+    pub first_vertex: u32,
+    pub vertex_count: u32,
+}
+
+/// The range of indices of a single draw, used by
+/// [`draw_multi_indexed`](AutoCommandBufferBuilder::draw_multi_indexed).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
+pub struct MultiDrawIndexedInfo {
+    pub first_index: u32,
+    pub index_count: u32,
+    pub vertex_offset: i32,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
 pub struct DispatchIndirectCommand {
@@ -170,6 +201,16 @@ pub struct DispatchIndirectCommand {
     pub z: u32,
 }
 
+/// The dimensions of a single ray tracing operation, used by
+/// [`trace_rays_indirect`](AutoCommandBufferBuilder::trace_rays_indirect).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
+pub struct TraceRaysIndirectCommand {
+    pub width: u32,
+    pub height: u32,
+    pub depth: u32,
+}
+
 vulkan_enum! {
     #[non_exhaustive]
 
@@ -885,6 +926,8 @@ pub enum ResourceInCommand {
     AccelerationStructure { index: u32 },
     ColorAttachment { index: u32 },
     ColorResolveAttachment { index: u32 },
+    ConditionalRenderingBuffer,
+    CountBuffer,
     DepthStencilAttachment,
     DepthStencilResolveAttachment,
     DescriptorSet { set: u32, binding: u32, index: u32 },
@@ -900,7 +943,10 @@ pub enum ResourceInCommand {
     IndirectBuffer,
     ScratchData,
     SecondaryCommandBuffer { index: u32 },
+    ShaderBindingTable,
     Source,
+    TransformFeedbackBuffer { binding: u32 },
+    TransformFeedbackCounterBuffer { index: u32 },
     VertexBuffer { binding: u32 },
 }
 
@@ -0,0 +1,761 @@
+// Copyright (c) 2024 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Recording the transfer commands of a command buffer, so that they can be replayed elsewhere.
+//!
+//! Only transfer and clear commands, such as [`copy_buffer`] and [`clear_color_image`], can be
+//! replayed from a trace. The trace is therefore meant for reproducing problems with the way
+//! data is moved between buffers and images, not for capturing whole frames.
+//!
+//! When [`AutoCommandBufferBuilder::start_trace`] is called, the builder records every command
+//! that is added to it afterwards into a [`CommandTrace`], which can be retrieved with
+//! [`AutoCommandBufferBuilder::take_trace`]. The trace implements the `serde` traits, so it can
+//! be saved to a file, for example as part of a bug report, and loaded again elsewhere.
+//!
+//! Buffers and images are identified in the trace by a [`ResourceId`], and the trace stores the
+//! parameters they were created with, but not their contents. A trace can be replayed with
+//! [`CommandTrace::replay`], after creating new resources for it with
+//! [`CommandTrace::create_resources`] or providing existing ones with [`ReplayResources`].
+//!
+//! Transfer and clear commands are recorded with all of their parameters. Other commands, such
+//! as draw and dispatch commands, depend on objects that can't be recorded, such as pipelines and
+//! descriptor sets. They are recorded as [`TracedCommand::Other`], with only their name and the
+//! resources they access, so that the trace still shows every access to its resources. A trace
+//! that contains such a command can't be replayed.
+//!
+//! [`copy_buffer`]: AutoCommandBufferBuilder::copy_buffer
+//! [`clear_color_image`]: AutoCommandBufferBuilder::clear_color_image
+
+use super::{
+    allocator::CommandBufferAllocator,
+    auto::{Resource, ResourceUseRef2},
+    AutoCommandBufferBuilder, BlitImageInfo, BufferCopy, BufferImageCopy, ClearColorImageInfo,
+    ClearDepthStencilImageInfo, CopyBufferInfo, CopyBufferToImageInfo, CopyImageInfo,
+    CopyImageToBufferInfo, ImageBlit, ImageCopy, ImageResolve, ResolveImageInfo,
+};
+use crate::{
+    buffer::{AllocateBufferError, Buffer, BufferCreateInfo, BufferUsage, Subbuffer},
+    format::{ClearColorValue, ClearDepthStencilValue, Format},
+    image::{
+        sampler::Filter, AllocateImageError, Image, ImageCreateFlags, ImageCreateInfo, ImageLayout,
+        ImageSubresourceRange, ImageTiling, ImageType, ImageUsage, SampleCount,
+    },
+    memory::allocator::{AllocationCreateInfo, DeviceLayout, MemoryAllocator},
+    DeviceSize, Validated, ValidationError,
+};
+use ahash::HashMap;
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+};
+
+/// The commands that were recorded into a command buffer, and the resources they access.
+///
+/// Only the transfer and clear commands of a trace can be replayed.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CommandTrace {
+    /// The buffers and images that are accessed by the commands, indexed by [`ResourceId`].
+    pub resources: Vec<TracedResource>,
+
+    /// The commands, in the order that they were recorded.
+    pub commands: Vec<TracedCommand>,
+}
+
+impl CommandTrace {
+    /// Creates a new resource for each resource of the trace, with the same creation parameters
+    /// as the original.
+    ///
+    /// The new resources are allocated in device-local memory, and their contents are
+    /// undefined.
+    pub fn create_resources(
+        &self,
+        memory_allocator: Arc<dyn MemoryAllocator>,
+    ) -> Result<ReplayResources, ReplayError> {
+        let mut replay_resources = ReplayResources::new();
+
+        for (index, resource) in self.resources.iter().enumerate() {
+            let id = ResourceId(index as u32);
+
+            match *resource {
+                TracedResource::Buffer { size, usage } => {
+                    let buffer = Buffer::new(
+                        memory_allocator.clone(),
+                        BufferCreateInfo {
+                            usage,
+                            ..Default::default()
+                        },
+                        AllocationCreateInfo::default(),
+                        DeviceLayout::from_size_alignment(size, 1)
+                            .ok_or(ReplayError::ResourceMismatch(id))?,
+                    )
+                    .map_err(|err| ReplayError::CreateBuffer(id, err))?;
+                    replay_resources.set_buffer(id, buffer);
+                }
+                TracedResource::Image {
+                    flags,
+                    image_type,
+                    format,
+                    extent,
+                    array_layers,
+                    mip_levels,
+                    samples,
+                    tiling,
+                    usage,
+                } => {
+                    let image = Image::new(
+                        memory_allocator.clone(),
+                        ImageCreateInfo {
+                            flags,
+                            image_type,
+                            format,
+                            extent,
+                            array_layers,
+                            mip_levels,
+                            samples,
+                            tiling,
+                            usage,
+                            ..Default::default()
+                        },
+                        AllocationCreateInfo::default(),
+                    )
+                    .map_err(|err| ReplayError::CreateImage(id, err))?;
+                    replay_resources.set_image(id, image);
+                }
+            }
+        }
+
+        Ok(replay_resources)
+    }
+
+    /// Records the transfer and clear commands of the trace into `builder`.
+    ///
+    /// Each resource of the trace is replaced by the resource with the same ID in `resources`.
+    /// Returns [`ReplayError::UnsupportedCommand`] if the trace contains a
+    /// [`TracedCommand::Other`] command, such as a draw or dispatch command, and returns an error
+    /// if a command fails validation. The commands before the failing command will have been
+    /// recorded.
+    pub fn replay<L, A>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L, A>,
+        resources: &ReplayResources,
+    ) -> Result<(), ReplayError>
+    where
+        A: CommandBufferAllocator,
+    {
+        for (index, command) in self.commands.iter().enumerate() {
+            let result = match command {
+                TracedCommand::CopyBuffer {
+                    src_buffer,
+                    dst_buffer,
+                    regions,
+                } => builder.copy_buffer(CopyBufferInfo {
+                    regions: regions.iter().cloned().collect(),
+                    ..CopyBufferInfo::buffers(
+                        resources.subbuffer(src_buffer)?,
+                        resources.subbuffer(dst_buffer)?,
+                    )
+                }),
+                TracedCommand::CopyImage {
+                    src_image,
+                    src_image_layout,
+                    dst_image,
+                    dst_image_layout,
+                    regions,
+                } => builder.copy_image(CopyImageInfo {
+                    src_image_layout: *src_image_layout,
+                    dst_image_layout: *dst_image_layout,
+                    regions: regions.iter().cloned().collect(),
+                    ..CopyImageInfo::images(
+                        resources.image_or_err(*src_image)?,
+                        resources.image_or_err(*dst_image)?,
+                    )
+                }),
+                TracedCommand::CopyBufferToImage {
+                    src_buffer,
+                    dst_image,
+                    dst_image_layout,
+                    regions,
+                } => builder.copy_buffer_to_image(CopyBufferToImageInfo {
+                    dst_image_layout: *dst_image_layout,
+                    regions: regions.iter().cloned().collect(),
+                    ..CopyBufferToImageInfo::buffer_image(
+                        resources.subbuffer(src_buffer)?,
+                        resources.image_or_err(*dst_image)?,
+                    )
+                }),
+                TracedCommand::CopyImageToBuffer {
+                    src_image,
+                    src_image_layout,
+                    dst_buffer,
+                    regions,
+                } => builder.copy_image_to_buffer(CopyImageToBufferInfo {
+                    src_image_layout: *src_image_layout,
+                    regions: regions.iter().cloned().collect(),
+                    ..CopyImageToBufferInfo::image_buffer(
+                        resources.image_or_err(*src_image)?,
+                        resources.subbuffer(dst_buffer)?,
+                    )
+                }),
+                TracedCommand::BlitImage {
+                    src_image,
+                    src_image_layout,
+                    dst_image,
+                    dst_image_layout,
+                    regions,
+                    filter,
+                } => builder.blit_image(BlitImageInfo {
+                    src_image_layout: *src_image_layout,
+                    dst_image_layout: *dst_image_layout,
+                    regions: regions.iter().cloned().collect(),
+                    filter: *filter,
+                    ..BlitImageInfo::images(
+                        resources.image_or_err(*src_image)?,
+                        resources.image_or_err(*dst_image)?,
+                    )
+                }),
+                TracedCommand::ResolveImage {
+                    src_image,
+                    src_image_layout,
+                    dst_image,
+                    dst_image_layout,
+                    regions,
+                } => builder.resolve_image(ResolveImageInfo {
+                    src_image_layout: *src_image_layout,
+                    dst_image_layout: *dst_image_layout,
+                    regions: regions.iter().cloned().collect(),
+                    ..ResolveImageInfo::images(
+                        resources.image_or_err(*src_image)?,
+                        resources.image_or_err(*dst_image)?,
+                    )
+                }),
+                TracedCommand::ClearColorImage {
+                    image,
+                    image_layout,
+                    clear_value,
+                    regions,
+                } => builder.clear_color_image(ClearColorImageInfo {
+                    image_layout: *image_layout,
+                    clear_value: *clear_value,
+                    regions: regions.iter().cloned().collect(),
+                    ..ClearColorImageInfo::image(resources.image_or_err(*image)?)
+                }),
+                TracedCommand::ClearDepthStencilImage {
+                    image,
+                    image_layout,
+                    clear_value,
+                    regions,
+                } => builder.clear_depth_stencil_image(ClearDepthStencilImageInfo {
+                    image_layout: *image_layout,
+                    clear_value: *clear_value,
+                    regions: regions.iter().cloned().collect(),
+                    ..ClearDepthStencilImageInfo::image(resources.image_or_err(*image)?)
+                }),
+                TracedCommand::FillBuffer { dst_buffer, data } => {
+                    let subbuffer = resources.subbuffer(dst_buffer)?;
+
+                    if subbuffer.offset() % 4 != 0 || subbuffer.size() % 4 != 0 {
+                        return Err(ReplayError::ResourceMismatch(dst_buffer.resource));
+                    }
+
+                    builder.fill_buffer(subbuffer.reinterpret(), *data)
+                }
+                TracedCommand::UpdateBuffer { dst_buffer, data } => builder.update_buffer(
+                    resources.subbuffer(dst_buffer)?,
+                    data.clone().into_boxed_slice(),
+                ),
+                TracedCommand::Other { name, .. } => {
+                    return Err(ReplayError::UnsupportedCommand(name.clone()));
+                }
+            };
+
+            result.map_err(|err| ReplayError::RecordCommand(index, err))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Identifies a resource within a [`CommandTrace`]. The value is the index of the resource in
+/// [`CommandTrace::resources`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ResourceId(pub u32);
+
+/// A resource that is accessed by the commands of a [`CommandTrace`], with the parameters it was
+/// created with.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum TracedResource {
+    /// A buffer.
+    Buffer {
+        /// The size of the buffer in bytes.
+        size: DeviceSize,
+
+        /// The usage that the buffer was created with.
+        usage: BufferUsage,
+    },
+
+    /// An image.
+    Image {
+        /// The flags that the image was created with.
+        flags: ImageCreateFlags,
+
+        /// The type of the image.
+        image_type: ImageType,
+
+        /// The format of the image.
+        format: Format,
+
+        /// The extent of the first mip level of the image.
+        extent: [u32; 3],
+
+        /// The number of array layers of the image.
+        array_layers: u32,
+
+        /// The number of mip levels of the image.
+        mip_levels: u32,
+
+        /// The number of samples per texel of the image.
+        samples: SampleCount,
+
+        /// The tiling of the image.
+        tiling: ImageTiling,
+
+        /// The usage that the image was created with.
+        usage: ImageUsage,
+    },
+}
+
+/// A range of a buffer in a [`CommandTrace`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TracedBuffer {
+    /// The buffer.
+    pub resource: ResourceId,
+
+    /// The offset in bytes from the start of the buffer.
+    pub offset: DeviceSize,
+
+    /// The size in bytes of the range.
+    pub size: DeviceSize,
+}
+
+/// A command in a [`CommandTrace`].
+///
+/// The fields of each command correspond to the fields of the info struct, or the parameters, of
+/// the command with the same name in [`AutoCommandBufferBuilder`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
+#[allow(missing_docs)]
+pub enum TracedCommand {
+    CopyBuffer {
+        src_buffer: TracedBuffer,
+        dst_buffer: TracedBuffer,
+        regions: Vec<BufferCopy>,
+    },
+    CopyImage {
+        src_image: ResourceId,
+        src_image_layout: ImageLayout,
+        dst_image: ResourceId,
+        dst_image_layout: ImageLayout,
+        regions: Vec<ImageCopy>,
+    },
+    CopyBufferToImage {
+        src_buffer: TracedBuffer,
+        dst_image: ResourceId,
+        dst_image_layout: ImageLayout,
+        regions: Vec<BufferImageCopy>,
+    },
+    CopyImageToBuffer {
+        src_image: ResourceId,
+        src_image_layout: ImageLayout,
+        dst_buffer: TracedBuffer,
+        regions: Vec<BufferImageCopy>,
+    },
+    BlitImage {
+        src_image: ResourceId,
+        src_image_layout: ImageLayout,
+        dst_image: ResourceId,
+        dst_image_layout: ImageLayout,
+        regions: Vec<ImageBlit>,
+        filter: Filter,
+    },
+    ResolveImage {
+        src_image: ResourceId,
+        src_image_layout: ImageLayout,
+        dst_image: ResourceId,
+        dst_image_layout: ImageLayout,
+        regions: Vec<ImageResolve>,
+    },
+    ClearColorImage {
+        image: ResourceId,
+        image_layout: ImageLayout,
+        clear_value: ClearColorValue,
+        regions: Vec<ImageSubresourceRange>,
+    },
+    ClearDepthStencilImage {
+        image: ResourceId,
+        image_layout: ImageLayout,
+        clear_value: ClearDepthStencilValue,
+        regions: Vec<ImageSubresourceRange>,
+    },
+    FillBuffer {
+        dst_buffer: TracedBuffer,
+        data: u32,
+    },
+    UpdateBuffer {
+        dst_buffer: TracedBuffer,
+        data: Vec<u8>,
+    },
+    /// A command whose parameters can't be recorded, such as a draw or dispatch command.
+    Other {
+        /// The name of the command, such as `"dispatch"`.
+        name: String,
+
+        /// The resources that the command accesses, including the resources accessed through
+        /// descriptor sets and vertex or index buffers.
+        resources: Vec<ResourceId>,
+    },
+}
+
+/// The resources to use when replaying a [`CommandTrace`], keyed by their ID in the trace.
+#[derive(Clone, Debug, Default)]
+pub struct ReplayResources {
+    buffers: HashMap<ResourceId, Arc<Buffer>>,
+    images: HashMap<ResourceId, Arc<Image>>,
+}
+
+impl ReplayResources {
+    /// Returns an empty `ReplayResources`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the buffer to use in place of the resource `id`.
+    ///
+    /// The buffer must be at least as large as the original buffer.
+    #[inline]
+    pub fn set_buffer(&mut self, id: ResourceId, buffer: Arc<Buffer>) {
+        self.buffers.insert(id, buffer);
+    }
+
+    /// Sets the image to use in place of the resource `id`.
+    #[inline]
+    pub fn set_image(&mut self, id: ResourceId, image: Arc<Image>) {
+        self.images.insert(id, image);
+    }
+
+    /// Returns the buffer to use in place of the resource `id`, if any.
+    #[inline]
+    pub fn buffer(&self, id: ResourceId) -> Option<&Arc<Buffer>> {
+        self.buffers.get(&id)
+    }
+
+    /// Returns the image to use in place of the resource `id`, if any.
+    #[inline]
+    pub fn image(&self, id: ResourceId) -> Option<&Arc<Image>> {
+        self.images.get(&id)
+    }
+
+    fn subbuffer(&self, traced: &TracedBuffer) -> Result<Subbuffer<[u8]>, ReplayError> {
+        let &TracedBuffer {
+            resource,
+            offset,
+            size,
+        } = traced;
+        let buffer = self
+            .buffers
+            .get(&resource)
+            .ok_or(ReplayError::MissingResource(resource))?;
+
+        if size == 0
+            || offset
+                .checked_add(size)
+                .filter(|&end| end <= buffer.size())
+                .is_none()
+        {
+            return Err(ReplayError::ResourceMismatch(resource));
+        }
+
+        Ok(Subbuffer::new(buffer.clone()).slice(offset..offset + size))
+    }
+
+    fn image_or_err(&self, id: ResourceId) -> Result<Arc<Image>, ReplayError> {
+        self.images
+            .get(&id)
+            .cloned()
+            .ok_or(ReplayError::MissingResource(id))
+    }
+}
+
+/// Error that can happen when replaying a [`CommandTrace`].
+#[derive(Clone, Debug)]
+pub enum ReplayError {
+    /// Creating the buffer for a resource failed.
+    CreateBuffer(ResourceId, Validated<AllocateBufferError>),
+
+    /// Creating the image for a resource failed.
+    CreateImage(ResourceId, Validated<AllocateImageError>),
+
+    /// No replacement was provided for a resource that is accessed by a command.
+    MissingResource(ResourceId),
+
+    /// The replacement of a resource is too small or has the wrong alignment for a command.
+    ResourceMismatch(ResourceId),
+
+    /// The trace contains a command that can't be replayed.
+    UnsupportedCommand(String),
+
+    /// Recording the command with the given index failed.
+    RecordCommand(usize, Box<ValidationError>),
+}
+
+impl Error for ReplayError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::CreateBuffer(_, err) => Some(err),
+            Self::CreateImage(_, err) => Some(err),
+            Self::RecordCommand(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::CreateBuffer(id, _) => write!(f, "creating the buffer for {:?} failed", id),
+            Self::CreateImage(id, _) => write!(f, "creating the image for {:?} failed", id),
+            Self::MissingResource(id) => write!(f, "no resource was provided for {:?}", id),
+            Self::ResourceMismatch(id) => write!(
+                f,
+                "the resource provided for {:?} is not compatible with the commands",
+                id,
+            ),
+            Self::UnsupportedCommand(name) => {
+                write!(f, "the `{}` command can't be replayed", name)
+            }
+            Self::RecordCommand(index, _) => {
+                write!(f, "recording command {} of the trace failed", index)
+            }
+        }
+    }
+}
+
+/// The state of a trace that is being recorded by an `AutoCommandBufferBuilder`.
+#[derive(Debug, Default)]
+pub(in crate::command_buffer) struct TraceRecorder {
+    trace: CommandTrace,
+    buffers: HashMap<Arc<Buffer>, ResourceId>,
+    images: HashMap<Arc<Image>, ResourceId>,
+    // The detailed version of the command that is currently being added.
+    pending: Option<TracedCommand>,
+}
+
+impl TraceRecorder {
+    pub(in crate::command_buffer) fn into_trace(self) -> CommandTrace {
+        self.trace
+    }
+
+    pub(in crate::command_buffer) fn buffer(
+        &mut self,
+        subbuffer: &Subbuffer<impl ?Sized>,
+    ) -> TracedBuffer {
+        TracedBuffer {
+            resource: self.buffer_id(subbuffer.buffer()),
+            offset: subbuffer.offset(),
+            size: subbuffer.size(),
+        }
+    }
+
+    fn buffer_id(&mut self, buffer: &Arc<Buffer>) -> ResourceId {
+        let resources = &mut self.trace.resources;
+
+        *self.buffers.entry(buffer.clone()).or_insert_with(|| {
+            resources.push(TracedResource::Buffer {
+                size: buffer.size(),
+                usage: buffer.usage(),
+            });
+
+            ResourceId(resources.len() as u32 - 1)
+        })
+    }
+
+    pub(in crate::command_buffer) fn image(&mut self, image: &Arc<Image>) -> ResourceId {
+        let resources = &mut self.trace.resources;
+
+        *self.images.entry(image.clone()).or_insert_with(|| {
+            resources.push(TracedResource::Image {
+                flags: image.flags(),
+                image_type: image.image_type(),
+                format: image.format(),
+                extent: image.extent(),
+                array_layers: image.array_layers(),
+                mip_levels: image.mip_levels(),
+                samples: image.samples(),
+                tiling: image.tiling(),
+                usage: image.usage(),
+            });
+
+            ResourceId(resources.len() as u32 - 1)
+        })
+    }
+
+    /// Sets the detailed version of the command that is about to be added.
+    pub(in crate::command_buffer) fn set_pending(&mut self, command: TracedCommand) {
+        self.pending = Some(command);
+    }
+
+    /// Records a command that was added to the builder. If no detailed version of the command
+    /// was set, it is recorded as `TracedCommand::Other`.
+    pub(in crate::command_buffer) fn add_command<'a>(
+        &mut self,
+        name: &'static str,
+        used_resources: impl IntoIterator<Item = &'a (ResourceUseRef2, Resource)>,
+    ) {
+        let command = match self.pending.take() {
+            Some(command) => command,
+            None => {
+                let mut resources: SmallVec<[ResourceId; 4]> = SmallVec::new();
+
+                for (_, resource) in used_resources {
+                    let id = match resource {
+                        Resource::Buffer { buffer, .. } => self.buffer_id(buffer.buffer()),
+                        Resource::Image { image, .. } => self.image(image),
+                    };
+
+                    if !resources.contains(&id) {
+                        resources.push(id);
+                    }
+                }
+
+                TracedCommand::Other {
+                    name: name.to_owned(),
+                    resources: resources.into_vec(),
+                }
+            }
+        };
+
+        self.trace.commands.push(command);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommandTrace, ReplayError, TracedCommand, TracedResource};
+    use crate::{
+        buffer::{Buffer, BufferCreateInfo, BufferUsage},
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder,
+            CommandBufferUsage, CopyBufferInfo,
+        },
+        memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn trace_and_replay() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let command_buffer_allocator =
+            StandardCommandBufferAllocator::new(device, Default::default());
+
+        let buffers: Vec<_> = (0..2)
+            .map(|_| {
+                Buffer::new_slice::<u32>(
+                    memory_allocator.clone(),
+                    BufferCreateInfo {
+                        usage: BufferUsage::TRANSFER_SRC | BufferUsage::TRANSFER_DST,
+                        ..Default::default()
+                    },
+                    AllocationCreateInfo::default(),
+                    16,
+                )
+                .unwrap()
+            })
+            .collect();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &command_buffer_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        builder
+            .start_trace()
+            .fill_buffer(buffers[0].clone(), 7)
+            .unwrap()
+            .copy_buffer(CopyBufferInfo::buffers(
+                buffers[0].clone(),
+                buffers[1].clone(),
+            ))
+            .unwrap();
+        let trace = builder.take_trace().unwrap();
+
+        assert_eq!(trace.resources.len(), 2);
+        assert!(matches!(
+            trace.resources[0],
+            TracedResource::Buffer { size: 64, .. },
+        ));
+        assert!(matches!(
+            trace.commands[..],
+            [
+                TracedCommand::FillBuffer { data: 7, .. },
+                TracedCommand::CopyBuffer { .. },
+            ],
+        ));
+
+        let resources = trace.create_resources(memory_allocator).unwrap();
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &command_buffer_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        builder.start_trace();
+        trace.replay(&mut builder, &resources).unwrap();
+        let replayed = builder.take_trace().unwrap();
+
+        assert_eq!(replayed.resources, trace.resources);
+        assert_eq!(replayed.commands.len(), trace.commands.len());
+    }
+
+    #[test]
+    fn replay_rejects_other_commands() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let command_buffer_allocator =
+            StandardCommandBufferAllocator::new(device, Default::default());
+
+        let trace = CommandTrace {
+            resources: Vec::new(),
+            commands: vec![TracedCommand::Other {
+                name: "dispatch".to_owned(),
+                resources: Vec::new(),
+            }],
+        };
+        let resources = trace.create_resources(memory_allocator).unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &command_buffer_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        match trace.replay(&mut builder, &resources) {
+            Err(ReplayError::UnsupportedCommand(name)) if name == "dispatch" => {}
+            _ => panic!(),
+        }
+    }
+}
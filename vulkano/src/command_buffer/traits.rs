@@ -9,13 +9,14 @@
 
 use super::{
     CommandBufferInheritanceInfo, CommandBufferResourcesUsage, CommandBufferState,
-    CommandBufferUsage, SecondaryCommandBufferResourcesUsage, SemaphoreSubmitInfo, SubmitInfo,
+    CommandBufferUsage, SecondaryCommandBufferResourcesUsage, SemaphoreSubmitInfo,
 };
+#[cfg(feature = "swapchain")]
+use crate::swapchain::Swapchain;
 use crate::{
     buffer::Buffer,
     device::{Device, DeviceOwned, Queue},
     image::{Image, ImageLayout},
-    swapchain::Swapchain,
     sync::{
         future::{now, AccessCheckError, AccessError, GpuFuture, NowFuture, SubmitAnyBuilder},
         PipelineStages,
@@ -41,6 +42,9 @@ pub unsafe trait PrimaryCommandBufferAbstract:
     /// Returns the usage of this command buffer.
     fn usage(&self) -> CommandBufferUsage;
 
+    /// Returns the queue family index that this command buffer was created for.
+    fn queue_family_index(&self) -> u32;
+
     /// Executes this command buffer on a queue.
     ///
     /// This function returns an object that implements the [`GpuFuture`] trait. See the
@@ -147,6 +151,10 @@ where
         (**self).usage()
     }
 
+    fn queue_family_index(&self) -> u32 {
+        (**self).queue_family_index()
+    }
+
     fn state(&self) -> MutexGuard<'_, CommandBufferState> {
         (**self).state()
     }
@@ -235,31 +243,30 @@ where
     // You must make sure to not submit same command buffer multiple times.
     unsafe fn build_submission_impl(&self) -> Result<SubmitAnyBuilder, Validated<VulkanError>> {
         Ok(match self.previous.build_submission()? {
-            SubmitAnyBuilder::Empty => SubmitAnyBuilder::CommandBuffer(
-                SubmitInfo {
-                    command_buffers: vec![self.command_buffer.clone()],
-                    ..Default::default()
-                },
-                None,
-            ),
+            SubmitAnyBuilder::Empty => {
+                let mut submit_info = self.queue.with(|mut q| q.recycled_submit_info());
+                submit_info
+                    .command_buffers
+                    .push(self.command_buffer.clone());
+
+                SubmitAnyBuilder::CommandBuffer(submit_info, None)
+            }
             SubmitAnyBuilder::SemaphoresWait(semaphores) => {
-                SubmitAnyBuilder::CommandBuffer(
-                    SubmitInfo {
-                        wait_semaphores: semaphores
-                            .into_iter()
-                            .map(|semaphore| {
-                                SemaphoreSubmitInfo {
-                                    // TODO: correct stages ; hard
-                                    stages: PipelineStages::ALL_COMMANDS,
-                                    ..SemaphoreSubmitInfo::semaphore(semaphore)
-                                }
-                            })
-                            .collect(),
-                        command_buffers: vec![self.command_buffer.clone()],
-                        ..Default::default()
-                    },
-                    None,
-                )
+                let mut submit_info = self.queue.with(|mut q| q.recycled_submit_info());
+                submit_info
+                    .wait_semaphores
+                    .extend(semaphores.into_iter().map(|semaphore| {
+                        SemaphoreSubmitInfo {
+                            // TODO: correct stages ; hard
+                            stages: PipelineStages::ALL_COMMANDS,
+                            ..SemaphoreSubmitInfo::semaphore(semaphore)
+                        }
+                    }));
+                submit_info
+                    .command_buffers
+                    .push(self.command_buffer.clone());
+
+                SubmitAnyBuilder::CommandBuffer(submit_info, None)
             }
             SubmitAnyBuilder::CommandBuffer(mut submit_info, fence) => {
                 // FIXME: add pipeline barrier
@@ -268,7 +275,9 @@ where
                     .push(self.command_buffer.clone());
                 SubmitAnyBuilder::CommandBuffer(submit_info, fence)
             }
-            SubmitAnyBuilder::QueuePresent(_) | SubmitAnyBuilder::BindSparse(_, _) => {
+            #[cfg(feature = "swapchain")]
+            SubmitAnyBuilder::QueuePresent(_) => unimplemented!(), // TODO:
+            SubmitAnyBuilder::BindSparse(_, _) => {
                 unimplemented!() // TODO:
                                  /*present.submit();     // TODO: wrong
                                  let mut builder = SubmitCommandBufferBuilder::new();
@@ -414,6 +423,7 @@ where
         }
     }
 
+    #[cfg(feature = "swapchain")]
     #[inline]
     fn check_swapchain_image_acquired(
         &self,
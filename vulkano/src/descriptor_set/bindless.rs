@@ -0,0 +1,590 @@
+// Copyright (c) 2024 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! A managed heap of image descriptors, for bindless rendering.
+//!
+//! Bindless renderers put all their images into one large descriptor array, and select the image
+//! to use in the shader with an index that is passed in a push constant, a vertex attribute or a
+//! buffer. [`BindlessImageHeap`] manages such an array: image views are registered with it and
+//! get a stable `u32` index, and the descriptor set containing the array is updated in place.
+//!
+//! The descriptor set is created with the [`UPDATE_AFTER_BIND`], [`UPDATE_UNUSED_WHILE_PENDING`]
+//! and [`PARTIALLY_BOUND`] binding flags, so slots can be written while command buffers using
+//! the heap are still executing, and slots that are not registered don't need to be valid. This
+//! requires the corresponding `descriptor_binding_*` features to be enabled on the device.
+//!
+//! When an image view is unregistered, its slot is not reused right away, because command
+//! buffers that were submitted earlier may still access it. Instead, the slot becomes free again
+//! once [`end_frame`] has been called `frames_in_flight` times. The heap can't know when the
+//! device has finished executing those command buffers, so `end_frame` is unsafe: the caller
+//! must wait for them, for example by waiting on the fence of the frame that is being reused.
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use vulkano::{
+//! #     descriptor_set::bindless::{BindlessImageHeap, BindlessImageHeapCreateInfo},
+//! #     device::Device,
+//! #     image::view::ImageView,
+//! # };
+//! # fn example(device: Arc<Device>, image_view: Arc<ImageView>) {
+//! let mut heap = BindlessImageHeap::new(
+//!     device,
+//!     BindlessImageHeapCreateInfo {
+//!         capacity: 4096,
+//!         ..Default::default()
+//!     },
+//! )
+//! .unwrap();
+//!
+//! // Pass `index` to the shader, which samples `textures[index]`.
+//! let index = heap.register(image_view).unwrap();
+//!
+//! // Bind `heap.descriptor_set()` when drawing, with a pipeline layout that uses `heap.layout()`.
+//! let descriptor_set = heap.descriptor_set();
+//!
+//! heap.unregister(index).unwrap();
+//!
+//! // Wait for the command buffers of the frame `frames_in_flight` frames ago to finish, and then:
+//! unsafe { heap.end_frame() };
+//! # }
+//! ```
+//!
+//! [`UPDATE_AFTER_BIND`]: DescriptorBindingFlags::UPDATE_AFTER_BIND
+//! [`UPDATE_UNUSED_WHILE_PENDING`]: DescriptorBindingFlags::UPDATE_UNUSED_WHILE_PENDING
+//! [`PARTIALLY_BOUND`]: DescriptorBindingFlags::PARTIALLY_BOUND
+//! [`end_frame`]: BindlessImageHeap::end_frame
+
+use super::{
+    layout::{
+        DescriptorBindingFlags, DescriptorSetLayout, DescriptorSetLayoutBinding,
+        DescriptorSetLayoutCreateFlags, DescriptorSetLayoutCreateInfo, DescriptorType,
+    },
+    pool::{
+        DescriptorPool, DescriptorPoolAlloc, DescriptorPoolCreateFlags, DescriptorPoolCreateInfo,
+        DescriptorSetAllocateInfo,
+    },
+    sys::update_descriptor_set_unchecked,
+    DescriptorSet, DescriptorSetResources, WriteDescriptorSet,
+};
+use crate::{
+    device::{Device, DeviceOwned},
+    image::{sampler::Sampler, view::ImageView},
+    shader::ShaderStages,
+    Validated, ValidationError, VulkanError, VulkanObject,
+};
+use std::{
+    collections::VecDeque,
+    hash::{Hash, Hasher},
+    slice,
+    sync::Arc,
+};
+
+/// The binding number of the image array in the descriptor set layout of a heap.
+const BINDING: u32 = 0;
+
+/// A heap of image descriptors, with stable indices that can be used in shaders.
+///
+/// See the [module-level documentation](self) for more.
+pub struct BindlessImageHeap {
+    set: Arc<HeapSetAlloc>,
+    descriptor_type: DescriptorType,
+    capacity: u32,
+    frames_in_flight: u32,
+
+    resources: DescriptorSetResources,
+    snapshot: Option<Arc<BindlessImageHeapSet>>,
+    registered: Vec<bool>,
+    registered_count: u32,
+    next_unused_slot: u32,
+    free_slots: Vec<u32>,
+    released_slots: VecDeque<(u64, u32)>,
+    frame: u64,
+}
+
+struct HeapSetAlloc {
+    // Declared before `pool`, so that it's dropped first.
+    alloc: DescriptorPoolAlloc,
+    pool: DescriptorPool,
+}
+
+// This is needed because of the blanket impl of `Send` on `Arc<T>`, which requires that `T` is
+// `Send + Sync`. `DescriptorPool` is `!Sync`, but the pool is never accessed after the descriptor
+// set has been allocated from it.
+unsafe impl Send for HeapSetAlloc {}
+unsafe impl Sync for HeapSetAlloc {}
+
+impl BindlessImageHeap {
+    /// Creates a new `BindlessImageHeap`, with its own descriptor set layout and pool.
+    pub fn new(
+        device: Arc<Device>,
+        create_info: BindlessImageHeapCreateInfo,
+    ) -> Result<Self, Validated<VulkanError>> {
+        create_info
+            .validate(&device)
+            .map_err(|err| err.add_context("create_info"))?;
+
+        let BindlessImageHeapCreateInfo {
+            descriptor_type,
+            capacity,
+            stages,
+            frames_in_flight,
+            _ne: _,
+        } = create_info;
+
+        let layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                flags: DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL,
+                bindings: [(
+                    BINDING,
+                    DescriptorSetLayoutBinding {
+                        binding_flags: DescriptorBindingFlags::UPDATE_AFTER_BIND
+                            | DescriptorBindingFlags::UPDATE_UNUSED_WHILE_PENDING
+                            | DescriptorBindingFlags::PARTIALLY_BOUND,
+                        descriptor_count: capacity,
+                        stages,
+                        ..DescriptorSetLayoutBinding::descriptor_type(descriptor_type)
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )?;
+        let pool = DescriptorPool::new(
+            device,
+            DescriptorPoolCreateInfo {
+                flags: DescriptorPoolCreateFlags::UPDATE_AFTER_BIND,
+                max_sets: 1,
+                pool_sizes: [(descriptor_type, capacity)].into_iter().collect(),
+                ..Default::default()
+            },
+        )?;
+        let alloc = unsafe {
+            pool.allocate_descriptor_sets([DescriptorSetAllocateInfo::new(layout.clone())])?
+        }
+        .next()
+        .unwrap();
+
+        Ok(BindlessImageHeap {
+            set: Arc::new(HeapSetAlloc { alloc, pool }),
+            descriptor_type,
+            capacity,
+            frames_in_flight,
+
+            resources: DescriptorSetResources::new(&layout, 0),
+            snapshot: None,
+            registered: vec![false; capacity as usize],
+            registered_count: 0,
+            next_unused_slot: 0,
+            free_slots: Vec::new(),
+            released_slots: VecDeque::new(),
+            frame: 0,
+        })
+    }
+
+    /// Returns the descriptor set layout of the heap.
+    ///
+    /// The pipeline layouts of pipelines that access the heap must use this layout for the set
+    /// that the heap is bound to.
+    #[inline]
+    pub fn layout(&self) -> &Arc<DescriptorSetLayout> {
+        self.set.alloc.layout()
+    }
+
+    /// Returns the descriptor type of the image array.
+    #[inline]
+    pub fn descriptor_type(&self) -> DescriptorType {
+        self.descriptor_type
+    }
+
+    /// Returns the number of slots in the heap.
+    #[inline]
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Returns the number of image views that are currently registered.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        self.registered_count
+    }
+
+    /// Returns whether no image views are currently registered.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.registered_count == 0
+    }
+
+    /// Returns whether an image view is currently registered at `index`.
+    #[inline]
+    pub fn is_registered(&self, index: u32) -> bool {
+        self.registered
+            .get(index as usize)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Registers `image_view` with the heap, and returns the index of its slot.
+    ///
+    /// The descriptor type of the heap must not be [`DescriptorType::CombinedImageSampler`];
+    /// use [`register_with_sampler`](Self::register_with_sampler) for that.
+    pub fn register(&mut self, image_view: Arc<ImageView>) -> Result<u32, Box<ValidationError>> {
+        if self.descriptor_type == DescriptorType::CombinedImageSampler {
+            return Err(Box::new(ValidationError {
+                problem: "the descriptor type of the heap is \
+                    `DescriptorType::CombinedImageSampler`"
+                    .into(),
+                ..Default::default()
+            }));
+        }
+
+        self.register_write(|index| {
+            WriteDescriptorSet::image_view_array(BINDING, index, [image_view])
+        })
+    }
+
+    /// Registers `image_view` together with `sampler` with the heap, and returns the index of its
+    /// slot.
+    ///
+    /// The descriptor type of the heap must be [`DescriptorType::CombinedImageSampler`].
+    pub fn register_with_sampler(
+        &mut self,
+        image_view: Arc<ImageView>,
+        sampler: Arc<Sampler>,
+    ) -> Result<u32, Box<ValidationError>> {
+        if self.descriptor_type != DescriptorType::CombinedImageSampler {
+            return Err(Box::new(ValidationError {
+                problem: "the descriptor type of the heap is not \
+                    `DescriptorType::CombinedImageSampler`"
+                    .into(),
+                ..Default::default()
+            }));
+        }
+
+        self.register_write(|index| {
+            WriteDescriptorSet::image_view_sampler_array(BINDING, index, [(image_view, sampler)])
+        })
+    }
+
+    fn register_write(
+        &mut self,
+        write: impl FnOnce(u32) -> WriteDescriptorSet,
+    ) -> Result<u32, Box<ValidationError>> {
+        let index = match self.free_slots.last() {
+            Some(&index) => index,
+            None if self.next_unused_slot < self.capacity => self.next_unused_slot,
+            None => {
+                return Err(Box::new(ValidationError {
+                    problem: "all slots of the heap are registered or waiting to be released"
+                        .into(),
+                    ..Default::default()
+                }));
+            }
+        };
+
+        let write = write(index);
+        write.validate(self.layout(), 0)?;
+
+        unsafe {
+            update_descriptor_set_unchecked(&self.set.alloc, slice::from_ref(&write), &[]);
+        }
+
+        if self.free_slots.pop().is_none() {
+            self.next_unused_slot += 1;
+        }
+
+        self.resources.write(&write, self.set.alloc.layout());
+        self.registered[index as usize] = true;
+        self.registered_count += 1;
+        self.snapshot = None;
+
+        Ok(index)
+    }
+
+    /// Unregisters the image view at `index`.
+    ///
+    /// Descriptor sets returned by [`descriptor_set`](Self::descriptor_set) after this call no
+    /// longer keep the image view alive, and the automatic synchronization no longer tracks it.
+    /// The slot can be reused once [`end_frame`](Self::end_frame) has been called
+    /// `frames_in_flight` times.
+    ///
+    /// Shaders must not access the slot after this call, except in command buffers that were
+    /// recorded with an earlier descriptor set.
+    pub fn unregister(&mut self, index: u32) -> Result<(), Box<ValidationError>> {
+        if !self.is_registered(index) {
+            return Err(Box::new(ValidationError {
+                context: "index".into(),
+                problem: "no image view is registered at this index".into(),
+                ..Default::default()
+            }));
+        }
+
+        self.resources.invalidate(BINDING, index, 1);
+        self.registered[index as usize] = false;
+        self.registered_count -= 1;
+        self.released_slots.push_back((self.frame, index));
+        self.snapshot = None;
+
+        Ok(())
+    }
+
+    /// Marks the end of a frame, and makes the slots that were unregistered `frames_in_flight`
+    /// frames ago available again.
+    ///
+    /// # Safety
+    ///
+    /// - All command buffers that use a descriptor set of this heap, and that were submitted
+    ///   before the `frames_in_flight`th most recent call to `end_frame` (counting this call),
+    ///   must have completed execution. For example, with `frames_in_flight` equal to 1, all
+    ///   command buffers submitted before this call must have completed execution.
+    pub unsafe fn end_frame(&mut self) {
+        self.frame += 1;
+
+        while let Some(&(frame, index)) = self.released_slots.front() {
+            if frame + self.frames_in_flight as u64 > self.frame {
+                break;
+            }
+
+            self.released_slots.pop_front();
+            self.free_slots.push(index);
+        }
+    }
+
+    /// Returns a descriptor set containing the image views that are currently registered.
+    ///
+    /// All descriptor sets returned by this method refer to the same Vulkan descriptor set, but
+    /// each of them keeps the image views alive that were registered when it was returned. The
+    /// returned value is cached until the next call to `register` or `unregister`.
+    pub fn descriptor_set(&mut self) -> Arc<BindlessImageHeapSet> {
+        self.snapshot
+            .get_or_insert_with(|| {
+                Arc::new(BindlessImageHeapSet {
+                    set: self.set.clone(),
+                    resources: self.resources.clone(),
+                })
+            })
+            .clone()
+    }
+}
+
+unsafe impl DeviceOwned for BindlessImageHeap {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.set.alloc.device()
+    }
+}
+
+/// Parameters to create a new `BindlessImageHeap`.
+#[derive(Clone, Debug)]
+pub struct BindlessImageHeapCreateInfo {
+    /// The type of the descriptors in the heap.
+    ///
+    /// This must be [`DescriptorType::SampledImage`], [`DescriptorType::StorageImage`] or
+    /// [`DescriptorType::CombinedImageSampler`].
+    ///
+    /// The default value is [`DescriptorType::SampledImage`].
+    pub descriptor_type: DescriptorType,
+
+    /// The number of slots in the heap.
+    ///
+    /// The default value is 0, which must be overridden.
+    pub capacity: u32,
+
+    /// The shader stages that can access the heap.
+    ///
+    /// The default value is [`ShaderStages::all_graphics()`] plus [`ShaderStages::COMPUTE`].
+    pub stages: ShaderStages,
+
+    /// The number of calls to [`BindlessImageHeap::end_frame`] after which an unregistered slot
+    /// can be reused.
+    ///
+    /// This should be the maximum number of frames that can be in flight on the device at the
+    /// same time.
+    ///
+    /// The default value is 2.
+    pub frames_in_flight: u32,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for BindlessImageHeapCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            descriptor_type: DescriptorType::SampledImage,
+            capacity: 0,
+            stages: ShaderStages::all_graphics() | ShaderStages::COMPUTE,
+            frames_in_flight: 2,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl BindlessImageHeapCreateInfo {
+    pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            descriptor_type,
+            capacity,
+            stages,
+            frames_in_flight: _,
+            _ne: _,
+        } = self;
+
+        descriptor_type.validate_device(device).map_err(|err| {
+            err.add_context("descriptor_type")
+                .set_vuids(&["VUID-VkDescriptorSetLayoutBinding-descriptorType-parameter"])
+        })?;
+
+        if !matches!(
+            descriptor_type,
+            DescriptorType::SampledImage
+                | DescriptorType::StorageImage
+                | DescriptorType::CombinedImageSampler
+        ) {
+            return Err(Box::new(ValidationError {
+                context: "descriptor_type".into(),
+                problem: "is not `DescriptorType::SampledImage`, `DescriptorType::StorageImage` \
+                    or `DescriptorType::CombinedImageSampler`"
+                    .into(),
+                ..Default::default()
+            }));
+        }
+
+        if capacity == 0 {
+            return Err(Box::new(ValidationError {
+                context: "capacity".into(),
+                problem: "is zero".into(),
+                ..Default::default()
+            }));
+        }
+
+        stages.validate_device(device).map_err(|err| {
+            err.add_context("stages")
+                .set_vuids(&["VUID-VkDescriptorSetLayoutBinding-descriptorCount-00283"])
+        })?;
+
+        Ok(())
+    }
+}
+
+/// The descriptor set of a [`BindlessImageHeap`], as returned by
+/// [`BindlessImageHeap::descriptor_set`].
+pub struct BindlessImageHeapSet {
+    set: Arc<HeapSetAlloc>,
+    resources: DescriptorSetResources,
+}
+
+unsafe impl DescriptorSet for BindlessImageHeapSet {
+    #[inline]
+    fn alloc(&self) -> &DescriptorPoolAlloc {
+        &self.set.alloc
+    }
+
+    #[inline]
+    fn pool(&self) -> &DescriptorPool {
+        &self.set.pool
+    }
+
+    #[inline]
+    fn resources(&self) -> &DescriptorSetResources {
+        &self.resources
+    }
+}
+
+unsafe impl VulkanObject for BindlessImageHeapSet {
+    type Handle = ash::vk::DescriptorSet;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.set.alloc.handle()
+    }
+}
+
+unsafe impl DeviceOwned for BindlessImageHeapSet {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.set.alloc.device()
+    }
+}
+
+impl PartialEq for BindlessImageHeapSet {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.set.alloc == other.set.alloc
+    }
+}
+
+impl Eq for BindlessImageHeapSet {}
+
+impl Hash for BindlessImageHeapSet {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.set.alloc.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BindlessImageHeap, BindlessImageHeapCreateInfo};
+    use crate::{
+        format::Format,
+        image::{view::ImageView, Image, ImageCreateInfo, ImageType, ImageUsage},
+        memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn slot_recycling() {
+        let (device, _) = gfx_dev_and_queue!(
+            descriptor_binding_partially_bound,
+            descriptor_binding_sampled_image_update_after_bind,
+            descriptor_binding_update_unused_while_pending
+        );
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let image_view = ImageView::new_default(
+            Image::new(
+                memory_allocator,
+                ImageCreateInfo {
+                    image_type: ImageType::Dim2d,
+                    format: Format::R8G8B8A8_UNORM,
+                    extent: [4, 4, 1],
+                    usage: ImageUsage::SAMPLED,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let mut heap = BindlessImageHeap::new(
+            device,
+            BindlessImageHeapCreateInfo {
+                capacity: 2,
+                frames_in_flight: 1,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let first = heap.register(image_view.clone()).unwrap();
+        let second = heap.register(image_view.clone()).unwrap();
+        assert_ne!(first, second);
+        assert!(heap.register(image_view.clone()).is_err());
+
+        // The slot isn't reused until the frame has ended.
+        heap.unregister(first).unwrap();
+        assert!(heap.unregister(first).is_err());
+        assert!(heap.register(image_view.clone()).is_err());
+
+        unsafe { heap.end_frame() };
+        assert_eq!(heap.register(image_view).unwrap(), first);
+        assert_eq!(heap.len(), 2);
+    }
+}
@@ -109,6 +109,7 @@ use std::{
 };
 
 pub mod allocator;
+pub mod bindless;
 mod collection;
 pub mod layout;
 pub mod persistent;
@@ -268,6 +269,21 @@ impl DescriptorSetResources {
             .write(write, descriptor_type)
     }
 
+    /// Sets `descriptor_count` elements of `binding`, starting at `first_array_element`, to
+    /// `None`.
+    #[inline]
+    pub(crate) fn invalidate(
+        &mut self,
+        binding: u32,
+        first_array_element: u32,
+        descriptor_count: u32,
+    ) {
+        self.binding_resources
+            .get_mut(&binding)
+            .expect("descriptor invalidation has invalid binding number")
+            .invalidate(first_array_element, descriptor_count)
+    }
+
     #[inline]
     pub(crate) fn copy(&mut self, copy: &CopyDescriptorSet) {
         let src = copy
@@ -420,6 +436,44 @@ impl DescriptorBindingResources {
         }
     }
 
+    pub(crate) fn invalidate(&mut self, first: u32, count: u32) {
+        fn invalidate_resources<T>(resources: &mut [Option<T>], first: usize, count: usize) {
+            resources
+                .get_mut(first..first + count)
+                .expect("descriptor invalidation for binding out of bounds")
+                .iter_mut()
+                .for_each(|resource| *resource = None);
+        }
+
+        let first = first as usize;
+        let count = count as usize;
+
+        match self {
+            DescriptorBindingResources::None(resources) => {
+                invalidate_resources(resources, first, count)
+            }
+            DescriptorBindingResources::Buffer(resources) => {
+                invalidate_resources(resources, first, count)
+            }
+            DescriptorBindingResources::BufferView(resources) => {
+                invalidate_resources(resources, first, count)
+            }
+            DescriptorBindingResources::ImageView(resources) => {
+                invalidate_resources(resources, first, count)
+            }
+            DescriptorBindingResources::ImageViewSampler(resources) => {
+                invalidate_resources(resources, first, count)
+            }
+            DescriptorBindingResources::Sampler(resources) => {
+                invalidate_resources(resources, first, count)
+            }
+            DescriptorBindingResources::InlineUniformBlock => (),
+            DescriptorBindingResources::AccelerationStructure(resources) => {
+                invalidate_resources(resources, first, count)
+            }
+        }
+    }
+
     pub(crate) fn copy(
         &mut self,
         src: &DescriptorBindingResources,
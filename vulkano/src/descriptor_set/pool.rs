@@ -20,7 +20,14 @@ use crate::{
 };
 use ahash::HashMap;
 use smallvec::SmallVec;
-use std::{cell::Cell, marker::PhantomData, mem::MaybeUninit, num::NonZeroU64, ptr, sync::Arc};
+use std::{
+    cell::Cell,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    num::NonZeroU64,
+    ptr,
+    sync::{atomic::Ordering, Arc},
+};
 
 /// Pool that descriptors are allocated from.
 ///
@@ -36,6 +43,7 @@ pub struct DescriptorPool {
     max_sets: u32,
     pool_sizes: HashMap<DescriptorType, u32>,
     max_inline_uniform_block_bindings: u32,
+    allocated_sets: Cell<usize>,
 
     // Unimplement `Sync`, as Vulkan descriptor pools are not thread safe.
     _marker: PhantomData<Cell<ash::vk::DescriptorPool>>,
@@ -155,6 +163,7 @@ impl DescriptorPool {
             pool_sizes,
             max_inline_uniform_block_bindings,
 
+            allocated_sets: Cell::new(0),
             _marker: PhantomData,
         }
     }
@@ -325,6 +334,7 @@ impl DescriptorPool {
             })?;
 
             output.set_len(layouts_vk.len());
+            self.sets_allocated(output.len());
         }
 
         Ok(output
@@ -393,6 +403,8 @@ impl DescriptorPool {
             )
             .result()
             .map_err(VulkanError::from)?;
+
+            self.sets_freed(sets.len());
         }
 
         Ok(())
@@ -417,8 +429,26 @@ impl DescriptorPool {
         .result()
         .map_err(VulkanError::from)?;
 
+        self.sets_freed(self.allocated_sets.get());
+
         Ok(())
     }
+
+    fn sets_allocated(&self, count: usize) {
+        self.allocated_sets.set(self.allocated_sets.get() + count);
+        self.device
+            .counters
+            .descriptor_sets
+            .fetch_add(count, Ordering::Relaxed);
+    }
+
+    fn sets_freed(&self, count: usize) {
+        self.allocated_sets.set(self.allocated_sets.get() - count);
+        self.device
+            .counters
+            .descriptor_sets
+            .fetch_sub(count, Ordering::Relaxed);
+    }
 }
 
 impl Drop for DescriptorPool {
@@ -428,6 +458,8 @@ impl Drop for DescriptorPool {
             let fns = self.device.fns();
             (fns.v1_0.destroy_descriptor_pool)(self.device.handle(), self.handle, ptr::null());
         }
+
+        self.sets_freed(self.allocated_sets.get());
     }
 }
 
@@ -11,7 +11,7 @@
 
 use super::{
     allocator::{DescriptorSetAlloc, DescriptorSetAllocator, StandardDescriptorSetAlloc},
-    pool::DescriptorPool,
+    pool::{DescriptorPool, DescriptorPoolAlloc},
     CopyDescriptorSet,
 };
 use crate::{
@@ -124,94 +124,103 @@ where
         descriptor_writes: &[WriteDescriptorSet],
         descriptor_copies: &[CopyDescriptorSet],
     ) {
-        struct PerDescriptorWrite {
-            write_info: DescriptorWriteInfo,
-            acceleration_structures: ash::vk::WriteDescriptorSetAccelerationStructureKHR,
-            inline_uniform_block: ash::vk::WriteDescriptorSetInlineUniformBlock,
-        }
+        update_descriptor_set_unchecked(self.alloc.inner(), descriptor_writes, descriptor_copies);
+    }
+}
+
+/// Performs the descriptor writes and copies on `set`, without validating them.
+pub(crate) unsafe fn update_descriptor_set_unchecked(
+    set: &DescriptorPoolAlloc,
+    descriptor_writes: &[WriteDescriptorSet],
+    descriptor_copies: &[CopyDescriptorSet],
+) {
+    struct PerDescriptorWrite {
+        write_info: DescriptorWriteInfo,
+        acceleration_structures: ash::vk::WriteDescriptorSetAccelerationStructureKHR,
+        inline_uniform_block: ash::vk::WriteDescriptorSetInlineUniformBlock,
+    }
 
-        let mut writes_vk: SmallVec<[_; 8]> = SmallVec::with_capacity(descriptor_writes.len());
-        let mut per_writes_vk: SmallVec<[_; 8]> = SmallVec::with_capacity(descriptor_writes.len());
+    let mut writes_vk: SmallVec<[_; 8]> = SmallVec::with_capacity(descriptor_writes.len());
+    let mut per_writes_vk: SmallVec<[_; 8]> = SmallVec::with_capacity(descriptor_writes.len());
 
-        for write in descriptor_writes {
-            let layout_binding = &self.layout().bindings()[&write.binding()];
-            writes_vk.push(write.to_vulkan(self.handle(), layout_binding.descriptor_type));
-            per_writes_vk.push(PerDescriptorWrite {
-                write_info: write.to_vulkan_info(layout_binding.descriptor_type),
-                acceleration_structures: Default::default(),
-                inline_uniform_block: Default::default(),
-            });
-        }
+    for write in descriptor_writes {
+        let layout_binding = &set.layout().bindings()[&write.binding()];
+        writes_vk.push(write.to_vulkan(set.handle(), layout_binding.descriptor_type));
+        per_writes_vk.push(PerDescriptorWrite {
+            write_info: write.to_vulkan_info(layout_binding.descriptor_type),
+            acceleration_structures: Default::default(),
+            inline_uniform_block: Default::default(),
+        });
+    }
 
-        for (write_vk, per_write_vk) in writes_vk.iter_mut().zip(per_writes_vk.iter_mut()) {
-            match &mut per_write_vk.write_info {
-                DescriptorWriteInfo::Image(info) => {
-                    write_vk.descriptor_count = info.len() as u32;
-                    write_vk.p_image_info = info.as_ptr();
-                }
-                DescriptorWriteInfo::Buffer(info) => {
-                    write_vk.descriptor_count = info.len() as u32;
-                    write_vk.p_buffer_info = info.as_ptr();
-                }
-                DescriptorWriteInfo::BufferView(info) => {
-                    write_vk.descriptor_count = info.len() as u32;
-                    write_vk.p_texel_buffer_view = info.as_ptr();
-                }
-                DescriptorWriteInfo::InlineUniformBlock(data) => {
-                    write_vk.descriptor_count = data.len() as u32;
-                    write_vk.p_next = &per_write_vk.inline_uniform_block as *const _ as _;
-                    per_write_vk.inline_uniform_block.data_size = write_vk.descriptor_count;
-                    per_write_vk.inline_uniform_block.p_data = data.as_ptr() as *const _;
-                }
-                DescriptorWriteInfo::AccelerationStructure(info) => {
-                    write_vk.descriptor_count = info.len() as u32;
-                    write_vk.p_next = &per_write_vk.acceleration_structures as *const _ as _;
-                    per_write_vk
-                        .acceleration_structures
-                        .acceleration_structure_count = write_vk.descriptor_count;
-                    per_write_vk
-                        .acceleration_structures
-                        .p_acceleration_structures = info.as_ptr();
-                }
+    for (write_vk, per_write_vk) in writes_vk.iter_mut().zip(per_writes_vk.iter_mut()) {
+        match &mut per_write_vk.write_info {
+            DescriptorWriteInfo::Image(info) => {
+                write_vk.descriptor_count = info.len() as u32;
+                write_vk.p_image_info = info.as_ptr();
+            }
+            DescriptorWriteInfo::Buffer(info) => {
+                write_vk.descriptor_count = info.len() as u32;
+                write_vk.p_buffer_info = info.as_ptr();
+            }
+            DescriptorWriteInfo::BufferView(info) => {
+                write_vk.descriptor_count = info.len() as u32;
+                write_vk.p_texel_buffer_view = info.as_ptr();
+            }
+            DescriptorWriteInfo::InlineUniformBlock(data) => {
+                write_vk.descriptor_count = data.len() as u32;
+                write_vk.p_next = &per_write_vk.inline_uniform_block as *const _ as _;
+                per_write_vk.inline_uniform_block.data_size = write_vk.descriptor_count;
+                per_write_vk.inline_uniform_block.p_data = data.as_ptr() as *const _;
+            }
+            DescriptorWriteInfo::AccelerationStructure(info) => {
+                write_vk.descriptor_count = info.len() as u32;
+                write_vk.p_next = &per_write_vk.acceleration_structures as *const _ as _;
+                per_write_vk
+                    .acceleration_structures
+                    .acceleration_structure_count = write_vk.descriptor_count;
+                per_write_vk
+                    .acceleration_structures
+                    .p_acceleration_structures = info.as_ptr();
             }
-
-            debug_assert!(write_vk.descriptor_count != 0);
         }
 
-        let mut copies_vk: SmallVec<[_; 8]> = SmallVec::with_capacity(descriptor_copies.len());
+        debug_assert!(write_vk.descriptor_count != 0);
+    }
 
-        for copy in descriptor_copies {
-            let &CopyDescriptorSet {
-                ref src_set,
-                src_binding,
-                src_first_array_element,
-                dst_binding,
-                dst_first_array_element,
-                descriptor_count,
-                _ne: _,
-            } = copy;
+    let mut copies_vk: SmallVec<[_; 8]> = SmallVec::with_capacity(descriptor_copies.len());
 
-            copies_vk.push(ash::vk::CopyDescriptorSet {
-                src_set: src_set.handle(),
-                src_binding,
-                src_array_element: src_first_array_element,
-                dst_set: self.handle(),
-                dst_binding,
-                dst_array_element: dst_first_array_element,
-                descriptor_count,
-                ..Default::default()
-            });
-        }
+    for copy in descriptor_copies {
+        let &CopyDescriptorSet {
+            ref src_set,
+            src_binding,
+            src_first_array_element,
+            dst_binding,
+            dst_first_array_element,
+            descriptor_count,
+            _ne: _,
+        } = copy;
 
-        let fns = self.device().fns();
-        (fns.v1_0.update_descriptor_sets)(
-            self.device().handle(),
-            writes_vk.len() as u32,
-            writes_vk.as_ptr(),
-            copies_vk.len() as u32,
-            copies_vk.as_ptr(),
-        );
+        copies_vk.push(ash::vk::CopyDescriptorSet {
+            src_set: src_set.handle(),
+            src_binding,
+            src_array_element: src_first_array_element,
+            dst_set: set.handle(),
+            dst_binding,
+            dst_array_element: dst_first_array_element,
+            descriptor_count,
+            ..Default::default()
+        });
     }
+
+    let fns = set.device().fns();
+    (fns.v1_0.update_descriptor_sets)(
+        set.device().handle(),
+        writes_vk.len() as u32,
+        writes_vk.as_ptr(),
+        copies_vk.len() as u32,
+        copies_vk.as_ptr(),
+    );
 }
 
 unsafe impl<P> VulkanObject for UnsafeDescriptorSet<P>
@@ -101,12 +101,15 @@
 //!
 //! TODO: write
 
-use self::physical::PhysicalDevice;
 pub(crate) use self::properties::PropertiesFfi;
+use self::{physical::PhysicalDevice, statistics::DeviceCounters};
 pub use self::{
     properties::Properties,
     queue::{Queue, QueueFamilyProperties, QueueFlags, QueueGuard},
+    statistics::DeviceStatistics,
 };
+#[cfg(feature = "display")]
+use crate::display::{Display, DisplayPowerState};
 pub use crate::fns::DeviceFunctions;
 use crate::{
     acceleration_structure::{
@@ -117,7 +120,7 @@ use crate::{
         DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo, DescriptorSetLayoutSupport,
     },
     instance::{Instance, InstanceOwned, InstanceOwnedDebugWrapper},
-    macros::{impl_id_counter, vulkan_bitflags},
+    macros::{impl_id_counter, log_event, vulkan_bitflags},
     memory::ExternalMemoryHandleType,
     Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version, VulkanError,
     VulkanObject,
@@ -141,8 +144,10 @@ use std::{
 
 pub mod physical;
 pub mod private_data;
+pub mod profile;
 pub(crate) mod properties;
 mod queue;
+mod statistics;
 
 // Generated by build.rs
 include!(concat!(env!("OUT_DIR"), "/device_extensions.rs"));
@@ -168,6 +173,8 @@ pub struct Device {
     // This is required for validation in `memory::device_memory`, the count must only be modified
     // in that module.
     pub(crate) allocation_count: AtomicU32,
+    // Object and work counters, for `statistics`.
+    pub(crate) counters: DeviceCounters,
     fence_pool: Mutex<Vec<ash::vk::Fence>>,
     semaphore_pool: Mutex<Vec<ash::vk::Semaphore>>,
     event_pool: Mutex<Vec<ash::vk::Event>>,
@@ -393,10 +400,31 @@ impl Device {
                 output.as_mut_ptr(),
             )
             .result()
-            .map_err(VulkanError::from)?;
+            .map_err(|err| {
+                log_event!(
+                    error,
+                    "device",
+                    "failed to create a device on `{}`: {:?}",
+                    physical_device.properties().device_name,
+                    err,
+                );
+                VulkanError::from(err)
+            })?;
             output.assume_init()
         };
 
+        log_event!(
+            info,
+            "device",
+            "created a device on `{}` ({:?}, Vulkan {}); enabled extensions: {:?}; enabled \
+            features: {:?}",
+            physical_device.properties().device_name,
+            physical_device.properties().device_type,
+            physical_device.api_version(),
+            create_info.enabled_extensions,
+            create_info.enabled_features,
+        );
+
         Ok(Self::from_handle(physical_device, handle, create_info))
     }
 
@@ -478,6 +506,7 @@ impl Device {
             active_queue_family_indices,
 
             allocation_count: AtomicU32::new(0),
+            counters: DeviceCounters::default(),
             fence_pool: Mutex::new(Vec::new()),
             semaphore_pool: Mutex::new(Vec::new()),
             event_pool: Mutex::new(Vec::new()),
@@ -570,6 +599,19 @@ impl Device {
         self.allocation_count.load(Ordering::Acquire)
     }
 
+    /// Returns a snapshot of the number of objects that currently exist on the device, the
+    /// amount of memory allocated from each memory heap, and the work that has been submitted to
+    /// its queues.
+    #[inline]
+    pub fn statistics(&self) -> DeviceStatistics {
+        self.counters.snapshot(
+            self.physical_device()
+                .memory_properties()
+                .memory_heaps
+                .len(),
+        )
+    }
+
     pub(crate) fn fence_pool(&self) -> &Mutex<Vec<ash::vk::Fence>> {
         &self.fence_pool
     }
@@ -1042,6 +1084,77 @@ impl Device {
         })
     }
 
+    /// Sets the power state of a display.
+    ///
+    /// The [`ext_display_control`](DeviceExtensions::ext_display_control) extension must be
+    /// enabled on the device.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `display` does not belong to the physical device of `self`.
+    #[cfg(feature = "display")]
+    #[inline]
+    pub fn display_power_control(
+        &self,
+        display: &Display,
+        power_state: DisplayPowerState,
+    ) -> Result<(), Validated<VulkanError>> {
+        self.validate_display_power_control(display, power_state)?;
+
+        unsafe { Ok(self.display_power_control_unchecked(display, power_state)?) }
+    }
+
+    #[cfg(feature = "display")]
+    fn validate_display_power_control(
+        &self,
+        display: &Display,
+        power_state: DisplayPowerState,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.enabled_extensions().ext_display_control {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "ext_display_control",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkDisplayPowerControlEXT-commonparent
+        assert_eq!(self.physical_device(), display.physical_device());
+
+        power_state.validate_device(self).map_err(|err| {
+            err.add_context("power_state")
+                .set_vuids(&["VUID-VkDisplayPowerInfoEXT-powerState-parameter"])
+        })?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "display")]
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn display_power_control_unchecked(
+        &self,
+        display: &Display,
+        power_state: DisplayPowerState,
+    ) -> Result<(), VulkanError> {
+        let info_vk = ash::vk::DisplayPowerInfoEXT {
+            power_state: power_state.into(),
+            ..Default::default()
+        };
+
+        let fns = self.fns();
+        (fns.ext_display_control.display_power_control_ext)(
+            self.handle,
+            display.handle(),
+            &info_vk,
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        Ok(())
+    }
+
     /// Assigns a human-readable name to `object` for debugging purposes.
     ///
     /// If `object_name` is `None`, a previously set object name is removed.
@@ -1112,6 +1225,7 @@ impl Debug for Device {
             active_queue_family_indices,
 
             allocation_count,
+            counters: _,
             fence_pool: _,
             semaphore_pool: _,
             event_pool: _,
@@ -10,21 +10,17 @@
 use super::QueueFamilyProperties;
 use crate::{
     buffer::{ExternalBufferInfo, ExternalBufferProperties},
-    cache::{OnceCache, WeakArcOnceCache},
+    cache::OnceCache,
     device::{properties::Properties, DeviceExtensions, Features, FeaturesFfi, PropertiesFfi},
-    display::{Display, DisplayPlaneProperties, DisplayPlanePropertiesRaw, DisplayProperties},
-    format::{DrmFormatModifierProperties, Format, FormatProperties},
+    format::{DrmFormatModifierProperties, Format, FormatFeatures, FormatProperties},
     image::{
-        ImageDrmFormatModifierInfo, ImageFormatInfo, ImageFormatProperties, ImageUsage,
-        SparseImageFormatInfo, SparseImageFormatProperties,
+        ImageDrmFormatModifierInfo, ImageFormatInfo, ImageFormatProperties, ImageTiling,
+        SampleCount, SparseImageFormatInfo, SparseImageFormatProperties,
     },
     instance::{Instance, InstanceOwned},
     macros::{impl_id_counter, vulkan_bitflags, vulkan_enum},
     memory::{ExternalMemoryHandleType, MemoryProperties},
-    swapchain::{
-        ColorSpace, FullScreenExclusive, PresentMode, Surface, SurfaceApi, SurfaceCapabilities,
-        SurfaceInfo, SurfaceTransforms,
-    },
+    pipeline::graphics::multisample::MultisampleProperties,
     sync::{
         fence::{ExternalFenceInfo, ExternalFenceProperties},
         semaphore::{ExternalSemaphoreInfo, ExternalSemaphoreProperties},
@@ -33,10 +29,27 @@ use crate::{
     DebugWrapper, ExtensionProperties, Requires, RequiresAllOf, RequiresOneOf, Validated,
     ValidationError, Version, VulkanError, VulkanObject,
 };
+#[cfg(feature = "display")]
+use crate::{
+    cache::WeakArcOnceCache,
+    display::{
+        raw_drm_fd, Display, DisplayPlaneProperties, DisplayPlanePropertiesRaw, DisplayProperties,
+    },
+};
+#[cfg(feature = "swapchain")]
+use crate::{
+    image::ImageUsage,
+    swapchain::{
+        ColorSpace, FullScreenExclusive, PresentMode, Surface, SurfaceApi, SurfaceCapabilities,
+        SurfaceInfo, SurfaceTransforms,
+    },
+};
 use bytemuck::cast_slice;
+#[cfg(feature = "display")]
 use parking_lot::RwLock;
+#[cfg(feature = "display")]
+use std::{ffi::CStr, fs::File};
 use std::{
-    ffi::CStr,
     fmt::{Debug, Error as FmtError, Formatter},
     mem::MaybeUninit,
     num::NonZeroU64,
@@ -80,13 +93,16 @@ pub struct PhysicalDevice {
     queue_family_properties: Vec<QueueFamilyProperties>,
 
     // Data queried by the user at runtime, cached for faster lookups.
+    #[cfg(feature = "display")]
     display_properties: WeakArcOnceCache<ash::vk::DisplayKHR, Display>,
+    #[cfg(feature = "display")]
     display_plane_properties: RwLock<Vec<DisplayPlanePropertiesRaw>>,
     external_buffer_properties: OnceCache<ExternalBufferInfo, ExternalBufferProperties>,
     external_fence_properties: OnceCache<ExternalFenceInfo, ExternalFenceProperties>,
     external_semaphore_properties: OnceCache<ExternalSemaphoreInfo, ExternalSemaphoreProperties>,
     format_properties: OnceCache<Format, FormatProperties>,
     image_format_properties: OnceCache<ImageFormatInfo, Option<ImageFormatProperties>>,
+    multisample_properties: OnceCache<SampleCount, MultisampleProperties>,
     sparse_image_format_properties:
         OnceCache<SparseImageFormatInfo, Vec<SparseImageFormatProperties>>,
 }
@@ -147,13 +163,16 @@ impl PhysicalDevice {
             memory_properties,
             queue_family_properties,
 
+            #[cfg(feature = "display")]
             display_properties: WeakArcOnceCache::new(),
+            #[cfg(feature = "display")]
             display_plane_properties: RwLock::new(Vec::new()),
             external_buffer_properties: OnceCache::new(),
             external_fence_properties: OnceCache::new(),
             external_semaphore_properties: OnceCache::new(),
             format_properties: OnceCache::new(),
             image_format_properties: OnceCache::new(),
+            multisample_properties: OnceCache::new(),
             sparse_image_format_properties: OnceCache::new(),
         }))
     }
@@ -493,6 +512,7 @@ impl PhysicalDevice {
     }
 
     /// Returns the properties of displays attached to the physical device.
+    #[cfg(feature = "display")]
     #[inline]
     pub fn display_properties<'a>(
         self: &'a Arc<Self>,
@@ -502,6 +522,7 @@ impl PhysicalDevice {
         unsafe { Ok(self.display_properties_unchecked()?) }
     }
 
+    #[cfg(feature = "display")]
     fn validate_display_properties(&self) -> Result<(), Box<ValidationError>> {
         if !self.instance.enabled_extensions().khr_display {
             return Err(Box::new(ValidationError {
@@ -515,6 +536,7 @@ impl PhysicalDevice {
         Ok(())
     }
 
+    #[cfg(feature = "display")]
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
     pub unsafe fn display_properties_unchecked<'a>(
         self: &'a Arc<Self>,
@@ -656,6 +678,7 @@ impl PhysicalDevice {
     }
 
     /// Returns the properties of the display planes of the physical device.
+    #[cfg(feature = "display")]
     #[inline]
     pub fn display_plane_properties(
         self: &Arc<Self>,
@@ -665,6 +688,7 @@ impl PhysicalDevice {
         unsafe { Ok(self.display_plane_properties_unchecked()?) }
     }
 
+    #[cfg(feature = "display")]
     fn validate_display_plane_properties(&self) -> Result<(), Box<ValidationError>> {
         if !self.instance.enabled_extensions().khr_display {
             return Err(Box::new(ValidationError {
@@ -678,6 +702,7 @@ impl PhysicalDevice {
         Ok(())
     }
 
+    #[cfg(feature = "display")]
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
     pub unsafe fn display_plane_properties_unchecked(
         self: &Arc<Self>,
@@ -710,6 +735,7 @@ impl PhysicalDevice {
             .collect()
     }
 
+    #[cfg(feature = "display")]
     pub(crate) unsafe fn display_plane_properties_raw(
         &self,
     ) -> Result<Vec<DisplayPlanePropertiesRaw>, VulkanError> {
@@ -724,6 +750,7 @@ impl PhysicalDevice {
         self.get_display_plane_properties_raw()
     }
 
+    #[cfg(feature = "display")]
     unsafe fn get_display_plane_properties_raw(
         &self,
     ) -> Result<Vec<DisplayPlanePropertiesRaw>, VulkanError> {
@@ -829,6 +856,7 @@ impl PhysicalDevice {
     ///
     /// The index must be less than the number of elements returned by
     /// [`display_plane_properties`](Self::display_plane_properties).
+    #[cfg(feature = "display")]
     #[inline]
     pub fn display_plane_supported_displays(
         self: &Arc<Self>,
@@ -839,6 +867,7 @@ impl PhysicalDevice {
         unsafe { Ok(self.display_plane_supported_displays_unchecked(plane_index)?) }
     }
 
+    #[cfg(feature = "display")]
     fn validate_display_plane_supported_displays(
         &self,
         plane_index: u32,
@@ -876,6 +905,7 @@ impl PhysicalDevice {
         Ok(())
     }
 
+    #[cfg(feature = "display")]
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
     pub unsafe fn display_plane_supported_displays_unchecked(
         self: &Arc<Self>,
@@ -931,6 +961,150 @@ impl PhysicalDevice {
         Ok(displays)
     }
 
+    /// Returns the display that corresponds to an X11 RandR output, for use with
+    /// [`Display::acquire_xlib`].
+    ///
+    /// Returns `None` if the RandR output does not correspond to a display that is connected to
+    /// this physical device.
+    ///
+    /// The [`ext_acquire_xlib_display`](crate::instance::InstanceExtensions::ext_acquire_xlib_display)
+    /// extension must be enabled on the instance.
+    ///
+    /// # Safety
+    ///
+    /// - `dpy` must be a valid Xlib `Display` handle.
+    #[cfg(feature = "display")]
+    #[inline]
+    pub unsafe fn xlib_randr_output_display<D>(
+        self: &Arc<Self>,
+        dpy: *const D,
+        rr_output: ash::vk::RROutput,
+    ) -> Result<Option<Arc<Display>>, Validated<VulkanError>> {
+        self.validate_xlib_randr_output_display(dpy, rr_output)?;
+
+        Ok(self.xlib_randr_output_display_unchecked(dpy, rr_output)?)
+    }
+
+    #[cfg(feature = "display")]
+    fn validate_xlib_randr_output_display<D>(
+        &self,
+        _dpy: *const D,
+        _rr_output: ash::vk::RROutput,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.instance.enabled_extensions().ext_acquire_xlib_display {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::InstanceExtension(
+                    "ext_acquire_xlib_display",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkGetRandROutputDisplayEXT-dpy-parameter
+        // Can't validate, therefore unsafe
+
+        Ok(())
+    }
+
+    #[cfg(feature = "display")]
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn xlib_randr_output_display_unchecked<D>(
+        self: &Arc<Self>,
+        dpy: *const D,
+        rr_output: ash::vk::RROutput,
+    ) -> Result<Option<Arc<Display>>, VulkanError> {
+        let fns = self.instance.fns();
+        let mut output = MaybeUninit::uninit();
+        (fns.ext_acquire_xlib_display.get_rand_r_output_display_ext)(
+            self.handle,
+            dpy as *mut _,
+            rr_output,
+            output.as_mut_ptr(),
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        self.display_from_handle(output.assume_init())
+    }
+
+    /// Returns the display that corresponds to a DRM connector, for use with
+    /// [`Display::acquire_drm`].
+    ///
+    /// `drm_fd` must be a file descriptor of the DRM primary node that the connector belongs to.
+    /// Returns `None` if the connector does not correspond to a display that is connected to
+    /// this physical device.
+    ///
+    /// The [`ext_acquire_drm_display`](crate::instance::InstanceExtensions::ext_acquire_drm_display)
+    /// extension must be enabled on the instance.
+    #[cfg(feature = "display")]
+    #[inline]
+    pub fn drm_display(
+        self: &Arc<Self>,
+        drm_fd: &File,
+        connector_id: u32,
+    ) -> Result<Option<Arc<Display>>, Validated<VulkanError>> {
+        self.validate_drm_display(drm_fd, connector_id)?;
+
+        unsafe { Ok(self.drm_display_unchecked(drm_fd, connector_id)?) }
+    }
+
+    #[cfg(feature = "display")]
+    fn validate_drm_display(
+        &self,
+        _drm_fd: &File,
+        _connector_id: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.instance.enabled_extensions().ext_acquire_drm_display {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::InstanceExtension(
+                    "ext_acquire_drm_display",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "display")]
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn drm_display_unchecked(
+        self: &Arc<Self>,
+        drm_fd: &File,
+        connector_id: u32,
+    ) -> Result<Option<Arc<Display>>, VulkanError> {
+        let fns = self.instance.fns();
+        let mut output = MaybeUninit::uninit();
+        (fns.ext_acquire_drm_display.get_drm_display_ext)(
+            self.handle,
+            raw_drm_fd(drm_fd),
+            connector_id,
+            output.as_mut_ptr(),
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        self.display_from_handle(output.assume_init())
+    }
+
+    #[cfg(feature = "display")]
+    unsafe fn display_from_handle(
+        self: &Arc<Self>,
+        display_vk: ash::vk::DisplayKHR,
+    ) -> Result<Option<Arc<Display>>, VulkanError> {
+        if display_vk == ash::vk::DisplayKHR::null() {
+            return Ok(None);
+        }
+
+        if let Some(display) = self.display_properties.get(&display_vk) {
+            return Ok(Some(display));
+        }
+
+        self.display_properties_unchecked()?;
+
+        Ok(self.display_properties.get(&display_vk))
+    }
+
     /// Retrieves the external memory properties supported for buffers with a given configuration.
     ///
     /// Instance API version must be at least 1.1, or the [`khr_external_memory_capabilities`]
@@ -1263,6 +1437,66 @@ impl PhysicalDevice {
         Ok(())
     }
 
+    /// Returns the first format in `candidates` that supports all of `features` with the given
+    /// tiling on this physical device, or `None` if none of them do.
+    ///
+    /// Formats that are not supported by the physical device's API version or extensions are
+    /// skipped.
+    ///
+    /// [`ImageTiling::DrmFormatModifier`] checks the features that are supported by every DRM
+    /// format modifier.
+    pub fn find_supported_format(
+        &self,
+        candidates: impl IntoIterator<Item = Format>,
+        tiling: ImageTiling,
+        features: FormatFeatures,
+    ) -> Option<Format> {
+        candidates.into_iter().find(|&format| {
+            self.format_properties(format)
+                .is_ok_and(|format_properties| {
+                    let format_features = match tiling {
+                        ImageTiling::Optimal => format_properties.optimal_tiling_features,
+                        ImageTiling::Linear => format_properties.linear_tiling_features,
+                        ImageTiling::DrmFormatModifier => format_properties
+                            .drm_format_modifier_properties
+                            .iter()
+                            .map(|properties| properties.drm_format_modifier_tiling_features)
+                            .reduce(|a, b| a & b)
+                            .unwrap_or_default(),
+                    };
+
+                    format_features.contains(features)
+                })
+        })
+    }
+
+    /// Returns a format with a depth aspect, and a stencil aspect if `stencil` is `true`, that
+    /// can be used as an optimal-tiling depth/stencil attachment on this physical device.
+    ///
+    /// Formats with higher precision are preferred. Vulkan requires at least one of the candidate
+    /// formats to be supported in either case, so this normally returns `Some`.
+    pub fn depth_stencil_format(&self, stencil: bool) -> Option<Format> {
+        let candidates: &[Format] = if stencil {
+            &[
+                Format::D32_SFLOAT_S8_UINT,
+                Format::D24_UNORM_S8_UINT,
+                Format::D16_UNORM_S8_UINT,
+            ]
+        } else {
+            &[
+                Format::D32_SFLOAT,
+                Format::X8_D24_UNORM_PACK32,
+                Format::D16_UNORM,
+            ]
+        };
+
+        self.find_supported_format(
+            candidates.iter().copied(),
+            ImageTiling::Optimal,
+            FormatFeatures::DEPTH_STENCIL_ATTACHMENT,
+        )
+    }
+
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
     #[inline]
     pub unsafe fn format_properties_unchecked(&self, format: Format) -> FormatProperties {
@@ -1666,6 +1900,72 @@ impl PhysicalDevice {
             })
     }
 
+    /// Retrieves the properties of the physical device that depend on the number of samples
+    /// per pixel.
+    ///
+    /// The [`ext_sample_locations`](DeviceExtensions::ext_sample_locations) extension must be
+    /// supported by the physical device.
+    ///
+    /// The results of this function are cached, so that future calls with the same arguments
+    /// do not need to make a call to the Vulkan API again.
+    #[inline]
+    pub fn multisample_properties(
+        &self,
+        samples: SampleCount,
+    ) -> Result<MultisampleProperties, Box<ValidationError>> {
+        self.validate_multisample_properties(samples)?;
+
+        unsafe { Ok(self.multisample_properties_unchecked(samples)) }
+    }
+
+    fn validate_multisample_properties(
+        &self,
+        samples: SampleCount,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.supported_extensions().ext_sample_locations {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "ext_sample_locations",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        samples.validate_physical_device(self).map_err(|err| {
+            err.add_context("samples")
+                .set_vuids(&["VUID-vkGetPhysicalDeviceMultisamplePropertiesEXT-samples-parameter"])
+        })?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn multisample_properties_unchecked(
+        &self,
+        samples: SampleCount,
+    ) -> MultisampleProperties {
+        self.multisample_properties
+            .get_or_insert(samples, |&samples| {
+                let fns = self.instance.fns();
+                let mut multisample_properties_vk = ash::vk::MultisamplePropertiesEXT::default();
+
+                (fns.ext_sample_locations
+                    .get_physical_device_multisample_properties_ext)(
+                    self.handle,
+                    samples.into(),
+                    &mut multisample_properties_vk,
+                );
+
+                let ash::vk::Extent2D { width, height } =
+                    multisample_properties_vk.max_sample_location_grid_size;
+
+                MultisampleProperties {
+                    max_sample_location_grid_size: [width, height],
+                }
+            })
+    }
+
     /// Queries whether the physical device supports presenting to QNX Screen surfaces from queues
     /// of the given queue family.
     ///
@@ -1915,6 +2215,7 @@ impl PhysicalDevice {
     /// # Panics
     ///
     /// - Panics if the physical device and the surface don't belong to the same instance.
+    #[cfg(feature = "swapchain")]
     pub fn surface_capabilities(
         &self,
         surface: &Surface,
@@ -1925,6 +2226,7 @@ impl PhysicalDevice {
         unsafe { Ok(self.surface_capabilities_unchecked(surface, surface_info)?) }
     }
 
+    #[cfg(feature = "swapchain")]
     fn validate_surface_capabilities(
         &self,
         surface: &Surface,
@@ -2031,6 +2333,7 @@ impl PhysicalDevice {
         Ok(())
     }
 
+    #[cfg(feature = "swapchain")]
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
     pub unsafe fn surface_capabilities_unchecked(
         &self,
@@ -2165,6 +2468,26 @@ impl PhysicalDevice {
             .map_err(VulkanError::from)?;
         };
 
+        let mut capabilities2_ext_vk = None;
+
+        if self
+            .instance
+            .enabled_extensions()
+            .ext_display_surface_counter
+        {
+            let capabilities2_ext_vk =
+                capabilities2_ext_vk.insert(ash::vk::SurfaceCapabilities2EXT::default());
+
+            (fns.ext_display_surface_counter
+                .get_physical_device_surface_capabilities2_ext)(
+                self.handle(),
+                info_vk.surface,
+                capabilities2_ext_vk,
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+        }
+
         Ok(SurfaceCapabilities {
             min_image_count: capabilities_vk.surface_capabilities.min_image_count,
             max_image_count: (capabilities_vk.surface_capabilities.max_image_count != 0)
@@ -2277,6 +2600,9 @@ impl PhysicalDevice {
 
             full_screen_exclusive_supported: capabilities_full_screen_exclusive_vk
                 .map_or(false, |c| c.full_screen_exclusive_supported != 0),
+
+            supported_surface_counters: capabilities2_ext_vk
+                .map_or_else(Default::default, |c| c.supported_surface_counters.into()),
         })
     }
 
@@ -2289,6 +2615,7 @@ impl PhysicalDevice {
     /// # Panics
     ///
     /// - Panics if the physical device and the surface don't belong to the same instance.
+    #[cfg(feature = "swapchain")]
     pub fn surface_formats(
         &self,
         surface: &Surface,
@@ -2299,6 +2626,7 @@ impl PhysicalDevice {
         unsafe { Ok(self.surface_formats_unchecked(surface, surface_info)?) }
     }
 
+    #[cfg(feature = "swapchain")]
     fn validate_surface_formats(
         &self,
         surface: &Surface,
@@ -2433,6 +2761,7 @@ impl PhysicalDevice {
         Ok(())
     }
 
+    #[cfg(feature = "swapchain")]
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
     pub unsafe fn surface_formats_unchecked(
         &self,
@@ -2588,6 +2917,7 @@ impl PhysicalDevice {
     /// # Panics
     ///
     /// - Panics if the physical device and the surface don't belong to the same instance.
+    #[cfg(feature = "swapchain")]
     pub fn surface_present_modes(
         &self,
         surface: &Surface,
@@ -2598,6 +2928,7 @@ impl PhysicalDevice {
         unsafe { Ok(self.surface_present_modes_unchecked(surface, surface_info)?) }
     }
 
+    #[cfg(feature = "swapchain")]
     fn validate_surface_present_modes(
         &self,
         surface: &Surface,
@@ -2704,6 +3035,7 @@ impl PhysicalDevice {
         Ok(())
     }
 
+    #[cfg(feature = "swapchain")]
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
     pub unsafe fn surface_present_modes_unchecked(
         &self,
@@ -2836,6 +3168,7 @@ impl PhysicalDevice {
     ///
     /// The results of this function are cached, so that future calls with the same arguments
     /// do not need to make a call to the Vulkan API again.
+    #[cfg(feature = "swapchain")]
     #[inline]
     pub fn surface_support(
         &self,
@@ -2847,6 +3180,7 @@ impl PhysicalDevice {
         unsafe { Ok(self.surface_support_unchecked(queue_family_index, surface)?) }
     }
 
+    #[cfg(feature = "swapchain")]
     fn validate_surface_support(
         &self,
         queue_family_index: u32,
@@ -2874,6 +3208,7 @@ impl PhysicalDevice {
         Ok(())
     }
 
+    #[cfg(feature = "swapchain")]
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
     pub unsafe fn surface_support_unchecked(
         &self,
@@ -3267,13 +3602,16 @@ impl Debug for PhysicalDevice {
             memory_properties,
             queue_family_properties,
 
-            display_properties: _,
-            display_plane_properties: _,
+            #[cfg(feature = "display")]
+                display_properties: _,
+            #[cfg(feature = "display")]
+                display_plane_properties: _,
             external_buffer_properties: _,
             external_fence_properties: _,
             external_semaphore_properties: _,
             format_properties: _,
             image_format_properties: _,
+            multisample_properties: _,
             sparse_image_format_properties: _,
         } = self;
 
@@ -0,0 +1,496 @@
+// Copyright (c) 2024 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Sets of capabilities that a physical device can be checked against in one call.
+//!
+//! A [`Profile`] lists an API version, device extensions, features, limits and format features.
+//! [`Profile::unmet_requirements`] checks all of them against a physical device, and
+//! [`Profile::create_device`] creates a device with the extensions and features of the profile
+//! enabled.
+//!
+//! Vulkano provides the [Roadmap 2022] profile with [`Profile::roadmap_2022`]. Other profiles can
+//! be defined by filling in the fields of `Profile` directly.
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use vulkano::device::{physical::PhysicalDevice, profile::Profile};
+//! # fn example(physical_device: Arc<PhysicalDevice>) {
+//! let profile = Profile::roadmap_2022();
+//!
+//! for requirement in profile.unmet_requirements(&physical_device) {
+//!     println!("{}", requirement);
+//! }
+//! # }
+//! ```
+//!
+//! [Roadmap 2022]: https://registry.khronos.org/vulkan/specs/1.3-extensions/html/vkspec.html#roadmap-2022
+
+use super::{
+    physical::{PhysicalDevice, SubgroupFeatures},
+    Device, DeviceCreateInfo, DeviceExtensions, Features, Properties, Queue,
+};
+use crate::{
+    format::{Format, FormatFeatures},
+    shader::ShaderStages,
+    Validated, ValidationError, Version, VulkanError,
+};
+use std::{
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+};
+
+/// A set of capabilities that a physical device must support.
+///
+/// See the [module-level documentation](self) for more.
+#[derive(Clone, Debug)]
+pub struct Profile {
+    /// The name of the profile, used in error messages.
+    ///
+    /// The default value is empty.
+    pub name: String,
+
+    /// The minimum API version of the physical device.
+    ///
+    /// The default value is [`Version::V1_0`].
+    pub api_version: Version,
+
+    /// The device extensions that must be supported.
+    ///
+    /// The default value is [`DeviceExtensions::empty()`].
+    pub device_extensions: DeviceExtensions,
+
+    /// The features that must be supported.
+    ///
+    /// The default value is [`Features::empty()`].
+    pub features: Features,
+
+    /// The limits that must be met.
+    ///
+    /// The default value is empty.
+    pub limits: Vec<ProfileLimit>,
+
+    /// The formats that must be supported, and the format features that they must support with
+    /// optimal tiling.
+    ///
+    /// The default value is empty.
+    pub formats: Vec<(Format, FormatFeatures)>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for Profile {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            api_version: Version::V1_0,
+            device_extensions: DeviceExtensions::empty(),
+            features: Features::empty(),
+            limits: Vec::new(),
+            formats: Vec::new(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl Profile {
+    /// Returns the [Roadmap 2022] profile (`VP_KHR_roadmap_2022`).
+    ///
+    /// This profile describes the capabilities of mid-to-high-end devices from 2022 onwards,
+    /// including Vulkan 1.3, descriptor indexing, buffer device address and dynamic rendering.
+    ///
+    /// [Roadmap 2022]: https://registry.khronos.org/vulkan/specs/1.3-extensions/html/vkspec.html#roadmap-2022
+    pub fn roadmap_2022() -> Self {
+        Self {
+            name: "VP_KHR_roadmap_2022".to_owned(),
+            api_version: Version::V1_3,
+            device_extensions: DeviceExtensions {
+                khr_global_priority: true,
+                ..DeviceExtensions::empty()
+            },
+            features: Features {
+                // Vulkan 1.0
+                full_draw_index_uint32: true,
+                image_cube_array: true,
+                independent_blend: true,
+                sample_rate_shading: true,
+                draw_indirect_first_instance: true,
+                depth_clamp: true,
+                depth_bias_clamp: true,
+                sampler_anisotropy: true,
+                occlusion_query_precise: true,
+                fragment_stores_and_atomics: true,
+                shader_storage_image_extended_formats: true,
+                shader_uniform_buffer_array_dynamic_indexing: true,
+                shader_sampled_image_array_dynamic_indexing: true,
+                shader_storage_buffer_array_dynamic_indexing: true,
+                shader_storage_image_array_dynamic_indexing: true,
+                // Vulkan 1.1
+                sampler_ycbcr_conversion: true,
+                // Vulkan 1.2
+                sampler_mirror_clamp_to_edge: true,
+                descriptor_indexing: true,
+                shader_uniform_texel_buffer_array_dynamic_indexing: true,
+                shader_storage_texel_buffer_array_dynamic_indexing: true,
+                shader_uniform_buffer_array_non_uniform_indexing: true,
+                shader_sampled_image_array_non_uniform_indexing: true,
+                shader_storage_buffer_array_non_uniform_indexing: true,
+                shader_storage_image_array_non_uniform_indexing: true,
+                shader_uniform_texel_buffer_array_non_uniform_indexing: true,
+                shader_storage_texel_buffer_array_non_uniform_indexing: true,
+                descriptor_binding_sampled_image_update_after_bind: true,
+                descriptor_binding_storage_image_update_after_bind: true,
+                descriptor_binding_storage_buffer_update_after_bind: true,
+                descriptor_binding_uniform_texel_buffer_update_after_bind: true,
+                descriptor_binding_storage_texel_buffer_update_after_bind: true,
+                descriptor_binding_update_unused_while_pending: true,
+                descriptor_binding_partially_bound: true,
+                descriptor_binding_variable_descriptor_count: true,
+                runtime_descriptor_array: true,
+                scalar_block_layout: true,
+                imageless_framebuffer: true,
+                uniform_buffer_standard_layout: true,
+                shader_subgroup_extended_types: true,
+                separate_depth_stencil_layouts: true,
+                host_query_reset: true,
+                timeline_semaphore: true,
+                buffer_device_address: true,
+                vulkan_memory_model: true,
+                vulkan_memory_model_device_scope: true,
+                vulkan_memory_model_availability_visibility_chains: true,
+                subgroup_broadcast_dynamic_id: true,
+                // Vulkan 1.3
+                robust_image_access: true,
+                inline_uniform_block: true,
+                descriptor_binding_inline_uniform_block_update_after_bind: true,
+                pipeline_creation_cache_control: true,
+                private_data: true,
+                shader_demote_to_helper_invocation: true,
+                shader_terminate_invocation: true,
+                subgroup_size_control: true,
+                compute_full_subgroups: true,
+                synchronization2: true,
+                shader_zero_initialize_workgroup_memory: true,
+                dynamic_rendering: true,
+                shader_integer_dot_product: true,
+                maintenance4: true,
+                ..Features::empty()
+            },
+            limits: vec![
+                ProfileLimit::new("max_image_dimension1_d >= 8192", |p| {
+                    p.max_image_dimension1_d >= 8192
+                }),
+                ProfileLimit::new("max_image_dimension2_d >= 8192", |p| {
+                    p.max_image_dimension2_d >= 8192
+                }),
+                ProfileLimit::new("max_image_dimension_cube >= 8192", |p| {
+                    p.max_image_dimension_cube >= 8192
+                }),
+                ProfileLimit::new("max_image_array_layers >= 2048", |p| {
+                    p.max_image_array_layers >= 2048
+                }),
+                ProfileLimit::new("max_uniform_buffer_range >= 65536", |p| {
+                    p.max_uniform_buffer_range >= 65536
+                }),
+                ProfileLimit::new("buffer_image_granularity <= 4096", |p| {
+                    p.buffer_image_granularity.as_devicesize() <= 4096
+                }),
+                ProfileLimit::new("max_per_stage_descriptor_samplers >= 64", |p| {
+                    p.max_per_stage_descriptor_samplers >= 64
+                }),
+                ProfileLimit::new("max_per_stage_descriptor_uniform_buffers >= 15", |p| {
+                    p.max_per_stage_descriptor_uniform_buffers >= 15
+                }),
+                ProfileLimit::new("max_per_stage_descriptor_storage_buffers >= 30", |p| {
+                    p.max_per_stage_descriptor_storage_buffers >= 30
+                }),
+                ProfileLimit::new("max_per_stage_descriptor_sampled_images >= 200", |p| {
+                    p.max_per_stage_descriptor_sampled_images >= 200
+                }),
+                ProfileLimit::new("max_per_stage_descriptor_storage_images >= 16", |p| {
+                    p.max_per_stage_descriptor_storage_images >= 16
+                }),
+                ProfileLimit::new("max_per_stage_resources >= 200", |p| {
+                    p.max_per_stage_resources >= 200
+                }),
+                ProfileLimit::new("max_descriptor_set_samplers >= 576", |p| {
+                    p.max_descriptor_set_samplers >= 576
+                }),
+                ProfileLimit::new("max_descriptor_set_uniform_buffers >= 90", |p| {
+                    p.max_descriptor_set_uniform_buffers >= 90
+                }),
+                ProfileLimit::new("max_descriptor_set_storage_buffers >= 96", |p| {
+                    p.max_descriptor_set_storage_buffers >= 96
+                }),
+                ProfileLimit::new("max_descriptor_set_sampled_images >= 1800", |p| {
+                    p.max_descriptor_set_sampled_images >= 1800
+                }),
+                ProfileLimit::new("max_descriptor_set_storage_images >= 144", |p| {
+                    p.max_descriptor_set_storage_images >= 144
+                }),
+                ProfileLimit::new("max_fragment_combined_output_resources >= 16", |p| {
+                    p.max_fragment_combined_output_resources >= 16
+                }),
+                ProfileLimit::new("max_compute_work_group_invocations >= 256", |p| {
+                    p.max_compute_work_group_invocations >= 256
+                }),
+                ProfileLimit::new("max_compute_work_group_size >= [256, 256, 64]", |p| {
+                    let [x, y, z] = p.max_compute_work_group_size;
+                    x >= 256 && y >= 256 && z >= 64
+                }),
+                ProfileLimit::new("sub_texel_precision_bits >= 8", |p| {
+                    p.sub_texel_precision_bits >= 8
+                }),
+                ProfileLimit::new("mipmap_precision_bits >= 6", |p| {
+                    p.mipmap_precision_bits >= 6
+                }),
+                ProfileLimit::new("max_sampler_lod_bias >= 14.0", |p| {
+                    p.max_sampler_lod_bias >= 14.0
+                }),
+                ProfileLimit::new("point_size_granularity <= 0.125", |p| {
+                    p.point_size_granularity <= 0.125
+                }),
+                ProfileLimit::new("line_width_granularity <= 0.5", |p| {
+                    p.line_width_granularity <= 0.5
+                }),
+                ProfileLimit::new("standard_sample_locations", |p| p.standard_sample_locations),
+                ProfileLimit::new("max_color_attachments >= 7", |p| {
+                    p.max_color_attachments >= 7
+                }),
+                ProfileLimit::new("subgroup_size >= 4", |p| p.subgroup_size.unwrap_or(0) >= 4),
+                ProfileLimit::new(
+                    "subgroup_supported_stages contains COMPUTE | FRAGMENT",
+                    |p| {
+                        p.subgroup_supported_stages
+                            .unwrap_or_default()
+                            .contains(ShaderStages::COMPUTE | ShaderStages::FRAGMENT)
+                    },
+                ),
+                ProfileLimit::new(
+                    "subgroup_supported_operations contains BASIC | VOTE | ARITHMETIC | BALLOT \
+                    | SHUFFLE | SHUFFLE_RELATIVE | QUAD",
+                    |p| {
+                        p.subgroup_supported_operations
+                            .unwrap_or_default()
+                            .contains(
+                                SubgroupFeatures::BASIC
+                                    | SubgroupFeatures::VOTE
+                                    | SubgroupFeatures::ARITHMETIC
+                                    | SubgroupFeatures::BALLOT
+                                    | SubgroupFeatures::SHUFFLE
+                                    | SubgroupFeatures::SHUFFLE_RELATIVE
+                                    | SubgroupFeatures::QUAD,
+                            )
+                    },
+                ),
+                ProfileLimit::new("shader_signed_zero_inf_nan_preserve_float16", |p| {
+                    p.shader_signed_zero_inf_nan_preserve_float16
+                        .unwrap_or(false)
+                }),
+                ProfileLimit::new("shader_signed_zero_inf_nan_preserve_float32", |p| {
+                    p.shader_signed_zero_inf_nan_preserve_float32
+                        .unwrap_or(false)
+                }),
+                ProfileLimit::new(
+                    "max_per_stage_descriptor_update_after_bind_input_attachments >= 7",
+                    |p| {
+                        p.max_per_stage_descriptor_update_after_bind_input_attachments
+                            .unwrap_or(0)
+                            >= 7
+                    },
+                ),
+                ProfileLimit::new("max_inline_uniform_total_size >= 256", |p| {
+                    p.max_inline_uniform_total_size.unwrap_or(0) >= 256
+                }),
+            ],
+            formats: Vec::new(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
+    /// Returns the requirements of the profile that `physical_device` doesn't meet.
+    ///
+    /// The physical device supports the profile if the returned list is empty.
+    pub fn unmet_requirements(&self, physical_device: &PhysicalDevice) -> Vec<ProfileRequirement> {
+        let mut unmet = Vec::new();
+
+        if physical_device.api_version() < self.api_version {
+            unmet.push(ProfileRequirement::ApiVersion(self.api_version));
+        }
+
+        let missing_extensions = self
+            .device_extensions
+            .difference(physical_device.supported_extensions());
+
+        if missing_extensions != DeviceExtensions::empty() {
+            unmet.push(ProfileRequirement::DeviceExtensions(missing_extensions));
+        }
+
+        let missing_features = self
+            .features
+            .difference(physical_device.supported_features());
+
+        if missing_features != Features::empty() {
+            unmet.push(ProfileRequirement::Features(missing_features));
+        }
+
+        let properties = physical_device.properties();
+
+        for limit in &self.limits {
+            if !(limit.check)(properties) {
+                unmet.push(ProfileRequirement::Limit(limit.name));
+            }
+        }
+
+        for &(format, format_features) in &self.formats {
+            let supported_features = physical_device
+                .format_properties(format)
+                .map_or(FormatFeatures::empty(), |properties| {
+                    properties.optimal_tiling_features
+                });
+
+            if !supported_features.contains(format_features) {
+                unmet.push(ProfileRequirement::Format(
+                    format,
+                    format_features - supported_features,
+                ));
+            }
+        }
+
+        unmet
+    }
+
+    /// Returns whether `physical_device` meets all requirements of the profile.
+    #[inline]
+    pub fn is_supported_by(&self, physical_device: &PhysicalDevice) -> bool {
+        self.unmet_requirements(physical_device).is_empty()
+    }
+
+    /// Creates a device from `physical_device`, with the extensions and features of the profile
+    /// enabled in addition to those in `create_info`.
+    ///
+    /// Returns an error if `physical_device` doesn't support the profile.
+    pub fn create_device(
+        &self,
+        physical_device: Arc<PhysicalDevice>,
+        create_info: DeviceCreateInfo,
+    ) -> Result<(Arc<Device>, impl ExactSizeIterator<Item = Arc<Queue>>), Validated<VulkanError>>
+    {
+        let unmet = self.unmet_requirements(&physical_device);
+
+        if !unmet.is_empty() {
+            let unmet: Vec<_> = unmet.iter().map(ToString::to_string).collect();
+
+            return Err(Box::new(ValidationError {
+                context: "physical_device".into(),
+                problem: format!(
+                    "doesn't support the profile `{}`: {}",
+                    self.name,
+                    unmet.join(", "),
+                )
+                .into(),
+                ..Default::default()
+            })
+            .into());
+        }
+
+        Device::new(
+            physical_device,
+            DeviceCreateInfo {
+                enabled_extensions: create_info
+                    .enabled_extensions
+                    .union(&self.device_extensions),
+                enabled_features: create_info.enabled_features.union(&self.features),
+                ..create_info
+            },
+        )
+    }
+}
+
+/// A limit that a [`Profile`] requires.
+#[derive(Clone, Copy, Debug)]
+pub struct ProfileLimit {
+    /// A description of the limit, used when reporting that it isn't met.
+    pub name: &'static str,
+
+    /// Returns whether the limit is met by the given properties.
+    pub check: fn(&Properties) -> bool,
+}
+
+impl ProfileLimit {
+    /// Returns a `ProfileLimit` with the given description and check.
+    #[inline]
+    pub const fn new(name: &'static str, check: fn(&Properties) -> bool) -> Self {
+        Self { name, check }
+    }
+}
+
+/// A requirement of a [`Profile`] that a physical device doesn't meet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProfileRequirement {
+    /// The API version of the physical device is lower than this version.
+    ApiVersion(Version),
+
+    /// These device extensions are not supported.
+    DeviceExtensions(DeviceExtensions),
+
+    /// These features are not supported.
+    Features(Features),
+
+    /// The limit with this description is not met.
+    Limit(&'static str),
+
+    /// This format doesn't support these format features with optimal tiling.
+    Format(Format, FormatFeatures),
+}
+
+impl Display for ProfileRequirement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::ApiVersion(version) => write!(f, "API version {} is required", version),
+            Self::DeviceExtensions(extensions) => {
+                write!(f, "device extensions {:?} are not supported", extensions)
+            }
+            Self::Features(features) => write!(f, "features {:?} are not supported", features),
+            Self::Limit(name) => write!(f, "limit `{}` is not met", name),
+            Self::Format(format, format_features) => write!(
+                f,
+                "format {:?} doesn't support {:?} with optimal tiling",
+                format, format_features,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Profile, ProfileLimit, ProfileRequirement};
+    use crate::Version;
+
+    #[test]
+    fn unmet_requirements() {
+        let (device, _) = gfx_dev_and_queue!();
+        let physical_device = device.physical_device();
+
+        assert!(Profile::default().is_supported_by(physical_device));
+
+        let profile = Profile {
+            api_version: Version::major_minor(u32::MAX, 0),
+            limits: vec![ProfileLimit::new("impossible", |_| false)],
+            ..Default::default()
+        };
+        assert_eq!(
+            profile.unmet_requirements(physical_device),
+            [
+                ProfileRequirement::ApiVersion(Version::major_minor(u32::MAX, 0)),
+                ProfileRequirement::Limit("impossible"),
+            ],
+        );
+    }
+}
@@ -20,15 +20,20 @@ use crate::{
     memory::{
         BindSparseInfo, SparseBufferMemoryBind, SparseImageMemoryBind, SparseImageOpaqueMemoryBind,
     },
-    swapchain::{PresentInfo, SwapchainPresentInfo},
     sync::{
         fence::{Fence, FenceState},
-        future::{AccessCheckError, GpuFuture},
+        future::{now, AccessCheckError, GpuFuture},
         semaphore::SemaphoreState,
+        PipelineStages,
     },
     Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version, VulkanError,
     VulkanObject,
 };
+#[cfg(feature = "swapchain")]
+use crate::{
+    macros::log_event,
+    swapchain::{PresentInfo, SwapchainPresentInfo},
+};
 use ahash::HashMap;
 use parking_lot::{Mutex, MutexGuard};
 use smallvec::{smallvec, SmallVec};
@@ -184,6 +189,12 @@ impl<'a> QueueGuard<'a> {
         self.state.fence_signaled(fence)
     }
 
+    /// Returns an empty `SubmitInfo`, reusing the allocations of one from a finished submission
+    /// if there is one.
+    pub(crate) fn recycled_submit_info(&mut self) -> SubmitInfo {
+        self.state.recycled_submit_infos.pop().unwrap_or_default()
+    }
+
     /// Waits until all work on this queue has finished, then releases ownership of all resources
     /// that were in use by the queue.
     ///
@@ -513,6 +524,7 @@ impl<'a> QueueGuard<'a> {
         Ok(())
     }
 
+    #[cfg(feature = "swapchain")]
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
     #[inline]
     pub unsafe fn present_unchecked(
@@ -523,6 +535,7 @@ impl<'a> QueueGuard<'a> {
         self.present_unchecked_locked(&present_info, &mut states)
     }
 
+    #[cfg(feature = "swapchain")]
     unsafe fn present_unchecked_locked(
         &mut self,
         present_info: &PresentInfo,
@@ -682,8 +695,21 @@ impl<'a> QueueGuard<'a> {
 
         Ok(results.into_iter().map(|result| match result {
             ash::vk::Result::SUCCESS => Ok(false),
-            ash::vk::Result::SUBOPTIMAL_KHR => Ok(true),
-            err => Err(VulkanError::from(err)),
+            ash::vk::Result::SUBOPTIMAL_KHR => {
+                log_event!(debug, "swapchain", "presented to a suboptimal swapchain");
+                Ok(true)
+            }
+            err => {
+                if err == ash::vk::Result::ERROR_OUT_OF_DATE_KHR {
+                    log_event!(
+                        debug,
+                        "swapchain",
+                        "failed to present: the swapchain is out of date"
+                    );
+                }
+
+                Err(VulkanError::from(err))
+            }
         }))
     }
 
@@ -696,9 +722,37 @@ impl<'a> QueueGuard<'a> {
         queue: &Queue,
     ) -> Result<(), Validated<VulkanError>> {
         let submit_infos: SmallVec<[_; 4]> = smallvec![submit_info];
-        let mut states = States::from_submit_infos(&submit_infos);
+        let mut states = States::from_submit_infos(self.state.submit_states_len, &submit_infos);
+
+        let result = Self::check_submit_with_future(&submit_infos, &mut states, future, queue)
+            .and_then(|()| {
+                Ok(self.submit_unchecked_locked(
+                    &submit_infos,
+                    fence.as_ref().map(|fence| {
+                        let state = fence.state();
+                        (fence, state)
+                    }),
+                    &mut states,
+                )?)
+            });
+        self.state.submit_states_len = states.len();
+        drop(states);
+        result?;
+
+        self.state
+            .operations
+            .push_back((submit_infos.into(), fence));
 
-        for submit_info in &submit_infos {
+        Ok(())
+    }
+
+    fn check_submit_with_future(
+        submit_infos: &[SubmitInfo],
+        states: &mut States<'_>,
+        future: &dyn GpuFuture,
+        queue: &Queue,
+    ) -> Result<(), Validated<VulkanError>> {
+        for submit_info in submit_infos {
             for command_buffer in &submit_info.command_buffers {
                 let state = states
                     .command_buffers
@@ -842,14 +896,145 @@ impl<'a> QueueGuard<'a> {
             }
         }
 
-        Ok(self.submit_unchecked_locked(
+        Ok(())
+    }
+
+    /// Submits batches of command buffers to the queue with a single queue submission.
+    ///
+    /// Each element of `submit_infos` is a batch with its own semaphore wait and signal
+    /// operations. Submitting many command buffers in one call has less overhead than submitting
+    /// them one at a time through [`GpuFuture`]s. If `fence` is `Some`, it is signaled once all
+    /// batches have completed execution.
+    ///
+    /// The command buffers are checked against the resource accesses of previous submissions
+    /// that are still pending, in the same way as when they are executed through a future.
+    ///
+    /// # Safety
+    ///
+    /// - For each element of `wait_semaphores`, the semaphore must have a signal operation that
+    ///   has been submitted previously, or is part of an earlier batch of `submit_infos`.
+    /// - The elements of `signal_semaphores` must not have a signal operation that is still
+    ///   pending.
+    /// - If `fence` is `Some`, it must be unsignaled and not in use by another queue operation.
+    /// - Accesses to resources by the command buffers must be synchronized with other queue
+    ///   operations, such as those of earlier batches of `submit_infos` or of other queues, with
+    ///   the semaphores of `submit_infos` or in other ways.
+    pub unsafe fn submit(
+        &mut self,
+        submit_infos: impl IntoIterator<Item = SubmitInfo>,
+        fence: Option<Arc<Fence>>,
+    ) -> Result<(), Validated<VulkanError>> {
+        let submit_infos: SmallVec<[_; 4]> = submit_infos.into_iter().collect();
+        self.validate_submit(&submit_infos, fence.as_ref())?;
+
+        let mut states = States::from_submit_infos(self.state.submit_states_len, &submit_infos);
+
+        let result = Self::check_submit_with_future(
             &submit_infos,
-            fence.as_ref().map(|fence| {
-                let state = fence.state();
-                (fence, state)
-            }),
             &mut states,
-        )?)
+            &now(self.queue.device.clone()),
+            self.queue,
+        )
+        .and_then(|()| {
+            Ok(self.submit_unchecked_locked(
+                &submit_infos,
+                fence.as_ref().map(|fence| {
+                    let state = fence.state();
+                    (fence, state)
+                }),
+                &mut states,
+            )?)
+        });
+        self.state.submit_states_len = states.len();
+        drop(states);
+        result?;
+
+        self.state
+            .operations
+            .push_back((submit_infos.into(), fence));
+
+        Ok(())
+    }
+
+    fn validate_submit(
+        &self,
+        submit_infos: &[SubmitInfo],
+        fence: Option<&Arc<Fence>>,
+    ) -> Result<(), Box<ValidationError>> {
+        let device = self.queue.device();
+
+        if let Some(fence) = fence {
+            // VUID-vkQueueSubmit2-commonparent
+            assert_eq!(device, fence.device());
+        }
+
+        for (submit_info_index, submit_info) in submit_infos.iter().enumerate() {
+            let &SubmitInfo {
+                ref wait_semaphores,
+                ref command_buffers,
+                ref signal_semaphores,
+                _ne: _,
+            } = submit_info;
+
+            for (semaphore_index, semaphore_submit_info) in
+                wait_semaphores.iter().chain(signal_semaphores).enumerate()
+            {
+                let &SemaphoreSubmitInfo {
+                    ref semaphore,
+                    stages,
+                    _ne: _,
+                } = semaphore_submit_info;
+
+                // VUID-VkSubmitInfo2-commonparent
+                assert_eq!(device, semaphore.device());
+
+                if stages != PipelineStages::ALL_COMMANDS
+                    && !device.enabled_features().synchronization2
+                {
+                    let context = if semaphore_index < wait_semaphores.len() {
+                        format!(
+                            "submit_infos[{}].wait_semaphores[{}].stages",
+                            submit_info_index, semaphore_index
+                        )
+                    } else {
+                        format!(
+                            "submit_infos[{}].signal_semaphores[{}].stages",
+                            submit_info_index,
+                            semaphore_index - wait_semaphores.len()
+                        )
+                    };
+
+                    return Err(Box::new(ValidationError {
+                        context: context.into(),
+                        problem: "is not `PipelineStages::ALL_COMMANDS`".into(),
+                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                            "synchronization2",
+                        )])]),
+                        ..Default::default()
+                    }));
+                }
+            }
+
+            for (command_buffer_index, command_buffer) in command_buffers.iter().enumerate() {
+                // VUID-VkCommandBufferSubmitInfo-commandBuffer-parameter
+                assert_eq!(device, command_buffer.device());
+
+                if command_buffer.queue_family_index() != self.queue.queue_family_index {
+                    return Err(Box::new(ValidationError {
+                        context: format!(
+                            "submit_infos[{}].command_buffers[{}]",
+                            submit_info_index, command_buffer_index
+                        )
+                        .into(),
+                        problem: "was not allocated for the queue family of the queue".into(),
+                        vuids: &["VUID-vkQueueSubmit2-commandBuffer-03880"],
+                        ..Default::default()
+                    }));
+                }
+            }
+        }
+
+        Ok(())
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
@@ -859,16 +1044,25 @@ impl<'a> QueueGuard<'a> {
         fence: Option<Arc<Fence>>,
     ) -> Result<(), VulkanError> {
         let submit_infos: SmallVec<[_; 4]> = submit_infos.into_iter().collect();
-        let mut states = States::from_submit_infos(&submit_infos);
+        let mut states = States::from_submit_infos(self.state.submit_states_len, &submit_infos);
 
-        self.submit_unchecked_locked(
+        let result = self.submit_unchecked_locked(
             &submit_infos,
             fence.as_ref().map(|fence| {
                 let state = fence.state();
                 (fence, state)
             }),
             &mut states,
-        )
+        );
+        self.state.submit_states_len = states.len();
+        drop(states);
+        result?;
+
+        self.state
+            .operations
+            .push_back((submit_infos.into(), fence));
+
+        Ok(())
     }
 
     unsafe fn submit_unchecked_locked(
@@ -1174,14 +1368,21 @@ impl<'a> QueueGuard<'a> {
             }
         }
 
-        let fence = fence.map(|(fence, mut state)| {
+        if let Some((_, mut state)) = fence {
             state.add_queue_signal(self.queue);
-            fence.clone()
-        });
+        }
 
-        self.state
-            .operations
-            .push_back((submit_infos.clone().into(), fence));
+        let counters = &self.queue.device.counters;
+        counters
+            .queue_submissions
+            .fetch_add(submit_infos.len() as u64, Ordering::Relaxed);
+        counters.command_buffers_in_flight.fetch_add(
+            submit_infos
+                .iter()
+                .map(|submit_info| submit_info.command_buffers.len())
+                .sum(),
+            Ordering::Relaxed,
+        );
 
         Ok(())
     }
@@ -1355,6 +1556,14 @@ impl<'a> QueueGuard<'a> {
 #[derive(Debug, Default)]
 struct QueueState {
     operations: VecDeque<(QueueOperation, Option<Arc<Fence>>)>,
+
+    // Submit infos of finished operations, which are cleared and reused by new submissions so
+    // that their allocations aren't freed and allocated again for every submission.
+    recycled_submit_infos: Vec<SubmitInfo>,
+
+    // The number of objects of each kind that the last submission locked. The next submission
+    // allocates its maps with this capacity, so that they rarely need to grow.
+    submit_states_len: StatesLen,
 }
 
 impl QueueState {
@@ -1368,7 +1577,7 @@ impl QueueState {
             // Since we now know that the queue is finished with all work,
             // we can safely release all resources.
             for (operation, _) in take(&mut self.operations) {
-                operation.set_finished();
+                operation.set_finished(&mut self.recycled_submit_infos);
             }
 
             Ok(())
@@ -1391,7 +1600,7 @@ impl QueueState {
             // Remove all operations up to this index, and perform cleanup if needed.
             for (operation, fence) in self.operations.drain(..index + 1) {
                 unsafe {
-                    operation.set_finished();
+                    operation.set_finished(&mut self.recycled_submit_infos);
 
                     if let Some(fence) = fence {
                         fence.state().set_signal_finished();
@@ -1405,12 +1614,13 @@ impl QueueState {
 #[derive(Debug)]
 enum QueueOperation {
     BindSparse(SmallVec<[BindSparseInfo; 4]>),
+    #[cfg(feature = "swapchain")]
     Present(PresentInfo),
     Submit(SmallVec<[SubmitInfo; 4]>),
 }
 
 impl QueueOperation {
-    unsafe fn set_finished(self) {
+    unsafe fn set_finished(self, recycled_submit_infos: &mut Vec<SubmitInfo>) {
         match self {
             QueueOperation::BindSparse(bind_infos) => {
                 for bind_info in bind_infos {
@@ -1425,25 +1635,26 @@ impl QueueOperation {
 
                 // TODO: Do we need to unlock buffers and images here?
             }
+            #[cfg(feature = "swapchain")]
             QueueOperation::Present(present_info) => {
                 for semaphore in present_info.wait_semaphores {
                     semaphore.state().set_wait_finished();
                 }
             }
             QueueOperation::Submit(submit_infos) => {
-                for submit_info in submit_infos {
-                    for semaphore_submit_info in submit_info.wait_semaphores {
+                for mut submit_info in submit_infos {
+                    for semaphore_submit_info in submit_info.wait_semaphores.drain(..) {
                         semaphore_submit_info.semaphore.state().set_wait_finished();
                     }
 
-                    for semaphore_submit_info in submit_info.signal_semaphores {
+                    for semaphore_submit_info in submit_info.signal_semaphores.drain(..) {
                         semaphore_submit_info
                             .semaphore
                             .state()
                             .set_signal_finished();
                     }
 
-                    for command_buffer in submit_info.command_buffers {
+                    for command_buffer in submit_info.command_buffers.drain(..) {
                         let resource_usage = command_buffer.resources_usage();
 
                         for usage in &resource_usage.buffers {
@@ -1471,6 +1682,15 @@ impl QueueOperation {
                         }
 
                         command_buffer.state().set_submit_finished();
+                        command_buffer
+                            .device()
+                            .counters
+                            .command_buffers_in_flight
+                            .fetch_sub(1, Ordering::Relaxed);
+                    }
+
+                    if recycled_submit_infos.len() < MAX_RECYCLED_SUBMIT_INFOS {
+                        recycled_submit_infos.push(submit_info);
                     }
                 }
             }
@@ -1478,6 +1698,10 @@ impl QueueOperation {
     }
 }
 
+// The maximum number of submit infos that a queue keeps for reuse. Enough for a few frames in
+// flight, without holding on to the allocations of an unusually large burst of submissions.
+const MAX_RECYCLED_SUBMIT_INFOS: usize = 16;
+
 impl From<SmallVec<[BindSparseInfo; 4]>> for QueueOperation {
     #[inline]
     fn from(val: SmallVec<[BindSparseInfo; 4]>) -> Self {
@@ -1485,6 +1709,7 @@ impl From<SmallVec<[BindSparseInfo; 4]>> for QueueOperation {
     }
 }
 
+#[cfg(feature = "swapchain")]
 impl From<PresentInfo> for QueueOperation {
     #[inline]
     fn from(val: PresentInfo) -> Self {
@@ -1510,6 +1735,15 @@ struct States<'a> {
 }
 
 impl<'a> States<'a> {
+    fn len(&self) -> StatesLen {
+        StatesLen {
+            buffers: self.buffers.len(),
+            command_buffers: self.command_buffers.len(),
+            images: self.images.len(),
+            semaphores: self.semaphores.len(),
+        }
+    }
+
     fn from_bind_infos(bind_infos: &'a [BindSparseInfo]) -> Self {
         let mut buffers = HashMap::default();
         let mut images = HashMap::default();
@@ -1565,6 +1799,7 @@ impl<'a> States<'a> {
         }
     }
 
+    #[cfg(feature = "swapchain")]
     fn from_present_info(present_info: &'a PresentInfo) -> Self {
         let mut semaphores = HashMap::default();
 
@@ -1588,11 +1823,14 @@ impl<'a> States<'a> {
         }
     }
 
-    fn from_submit_infos(submit_infos: &'a [SubmitInfo]) -> Self {
-        let mut buffers = HashMap::default();
-        let mut command_buffers = HashMap::default();
-        let mut images = HashMap::default();
-        let mut semaphores = HashMap::default();
+    /// Locks the states of all objects used by `submit_infos`, allocating the maps with the
+    /// capacities given by `len`.
+    fn from_submit_infos(len: StatesLen, submit_infos: &'a [SubmitInfo]) -> Self {
+        let mut buffers = HashMap::with_capacity_and_hasher(len.buffers, Default::default());
+        let mut command_buffers =
+            HashMap::with_capacity_and_hasher(len.command_buffers, Default::default());
+        let mut images = HashMap::with_capacity_and_hasher(len.images, Default::default());
+        let mut semaphores = HashMap::with_capacity_and_hasher(len.semaphores, Default::default());
 
         for submit_info in submit_infos {
             let SubmitInfo {
@@ -1653,6 +1891,15 @@ impl<'a> States<'a> {
     }
 }
 
+// The number of objects of each kind in a `States`.
+#[derive(Clone, Copy, Debug, Default)]
+struct StatesLen {
+    buffers: usize,
+    command_buffers: usize,
+    images: usize,
+    semaphores: usize,
+}
+
 /// Properties of a queue family in a physical device.
 #[derive(Clone, Debug)]
 #[non_exhaustive]
@@ -1737,7 +1984,13 @@ vulkan_bitflags! {
 
 #[cfg(test)]
 mod tests {
-    use crate::sync::fence::Fence;
+    use crate::{
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder,
+            CommandBufferUsage, SubmitInfo,
+        },
+        sync::fence::Fence,
+    };
     use std::{sync::Arc, time::Duration};
 
     #[test]
@@ -1765,4 +2018,58 @@ mod tests {
             assert!(fence.is_signaled().unwrap());
         }
     }
+
+    #[test]
+    fn batched_submit() {
+        unsafe {
+            let (device, queue) = gfx_dev_and_queue!();
+
+            let cb_allocator =
+                StandardCommandBufferAllocator::new(device.clone(), Default::default());
+            let command_buffers: Vec<_> = (0..2)
+                .map(|_| {
+                    AutoCommandBufferBuilder::primary(
+                        &cb_allocator,
+                        queue.queue_family_index(),
+                        CommandBufferUsage::OneTimeSubmit,
+                    )
+                    .unwrap()
+                    .build()
+                    .unwrap()
+                })
+                .collect();
+
+            let fence = Arc::new(Fence::new(device, Default::default()).unwrap());
+            let submit_infos = command_buffers.iter().map(|command_buffer| SubmitInfo {
+                command_buffers: vec![command_buffer.clone() as _],
+                ..Default::default()
+            });
+
+            queue
+                .with(|mut q| q.submit(submit_infos.clone(), Some(fence.clone())))
+                .unwrap();
+
+            fence.wait(Some(Duration::from_secs(5))).unwrap();
+            queue.with(|mut q| q.wait_idle()).unwrap();
+
+            // One-time-submit command buffers can't be submitted again.
+            assert!(queue.with(|mut q| q.submit(submit_infos, None)).is_err());
+        }
+    }
+
+    #[test]
+    fn recycle_submit_infos() {
+        let (_device, queue) = gfx_dev_and_queue!();
+
+        queue.with(|mut q| {
+            unsafe { q.submit_unchecked([Default::default()], None) }.unwrap();
+            assert!(q.state.recycled_submit_infos.is_empty());
+
+            q.wait_idle().unwrap();
+            assert_eq!(q.state.recycled_submit_infos.len(), 1);
+
+            let _submit_info = q.recycled_submit_info();
+            assert!(q.state.recycled_submit_infos.is_empty());
+        });
+    }
 }
@@ -0,0 +1,103 @@
+// Copyright (c) 2024 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Counters of the objects that exist on a device and the work submitted to it.
+//!
+//! A snapshot of the counters can be retrieved with [`Device::statistics`]. The counters are
+//! updated by the constructors and destructors of the objects themselves, so objects that are
+//! created from a raw handle are counted as well.
+//!
+//! [`Device::statistics`]: super::Device::statistics
+
+use crate::DeviceSize;
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+
+/// The live counters of a device.
+#[derive(Debug, Default)]
+pub(crate) struct DeviceCounters {
+    pub(crate) buffers: AtomicUsize,
+    pub(crate) images: AtomicUsize,
+    pub(crate) pipelines: AtomicUsize,
+    pub(crate) descriptor_sets: AtomicUsize,
+    pub(crate) command_buffers_in_flight: AtomicUsize,
+    pub(crate) queue_submissions: AtomicU64,
+    /// The number of samplers that were created with a custom border color, which is limited by
+    /// the `max_custom_border_color_samplers` device property.
+    pub(crate) custom_border_color_samplers: AtomicU32,
+    memory_allocated: [AtomicU64; ash::vk::MAX_MEMORY_HEAPS],
+}
+
+impl DeviceCounters {
+    #[inline]
+    pub(crate) fn memory_allocated(&self, heap_index: u32, size: DeviceSize) {
+        self.memory_allocated[heap_index as usize].fetch_add(size, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn memory_freed(&self, heap_index: u32, size: DeviceSize) {
+        self.memory_allocated[heap_index as usize].fetch_sub(size, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self, memory_heap_count: usize) -> DeviceStatistics {
+        DeviceStatistics {
+            buffers: self.buffers.load(Ordering::Relaxed),
+            images: self.images.load(Ordering::Relaxed),
+            pipelines: self.pipelines.load(Ordering::Relaxed),
+            descriptor_sets: self.descriptor_sets.load(Ordering::Relaxed),
+            memory_allocated_per_heap: self.memory_allocated[..memory_heap_count]
+                .iter()
+                .map(|counter| counter.load(Ordering::Relaxed))
+                .collect(),
+            command_buffers_in_flight: self.command_buffers_in_flight.load(Ordering::Relaxed),
+            queue_submissions: self.queue_submissions.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A snapshot of the objects that exist on a device and the work submitted to it, returned by
+/// [`Device::statistics`].
+///
+/// The counters are read one by one without any synchronization between them, so if other
+/// threads are creating or destroying objects at the same time, the snapshot may not correspond
+/// to a single point in time.
+///
+/// [`Device::statistics`]: super::Device::statistics
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DeviceStatistics {
+    /// The number of buffer objects that currently exist.
+    pub buffers: usize,
+
+    /// The number of image objects that currently exist, including swapchain images.
+    pub images: usize,
+
+    /// The number of compute, graphics and ray tracing pipelines that currently exist.
+    pub pipelines: usize,
+
+    /// The number of descriptor sets that are currently allocated from descriptor pools.
+    pub descriptor_sets: usize,
+
+    /// For each memory heap of the physical device, the number of bytes of device memory that is
+    /// currently allocated from it.
+    pub memory_allocated_per_heap: Vec<DeviceSize>,
+
+    /// The number of command buffers that have been submitted to a queue, and that vulkano does
+    /// not yet know to have finished executing.
+    ///
+    /// Vulkano only finds out that a submission has finished when the fence that it signals is
+    /// waited on, or when the queue is waited on to become idle, so this value can be higher than
+    /// the number of command buffers that are actually executing.
+    pub command_buffers_in_flight: usize,
+
+    /// The total number of batches that have been submitted to queues of the device.
+    ///
+    /// To get the number of submissions per frame, take the difference between snapshots of two
+    /// consecutive frames.
+    pub queue_submissions: u64,
+}
@@ -35,11 +35,12 @@ use crate::{
     cache::{OnceCache, WeakArcOnceCache},
     device::physical::PhysicalDevice,
     instance::{Instance, InstanceOwned, InstanceOwnedDebugWrapper},
-    macros::vulkan_bitflags_enum,
+    macros::{vulkan_bitflags_enum, vulkan_enum},
     swapchain::SurfaceTransforms,
-    Validated, ValidationError, VulkanError, VulkanObject,
+    Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, VulkanError, VulkanObject,
 };
 use std::{
+    fs::File,
     hash::{Hash, Hasher},
     mem::MaybeUninit,
     ptr,
@@ -259,6 +260,157 @@ impl Display {
                 .collect())
         }
     }
+
+    /// Acquires exclusive control of the display from an X11 server, so that it can be rendered
+    /// to directly without going through the window system.
+    ///
+    /// The [`ext_acquire_xlib_display`](crate::instance::InstanceExtensions::ext_acquire_xlib_display)
+    /// extension must be enabled on the instance.
+    ///
+    /// # Safety
+    ///
+    /// - `dpy` must be a valid Xlib `Display` handle, that is connected to the X11 server that
+    ///   currently controls the display.
+    #[inline]
+    pub unsafe fn acquire_xlib<D>(&self, dpy: *const D) -> Result<(), Validated<VulkanError>> {
+        self.validate_acquire_xlib(dpy)?;
+
+        Ok(self.acquire_xlib_unchecked(dpy)?)
+    }
+
+    fn validate_acquire_xlib<D>(&self, _dpy: *const D) -> Result<(), Box<ValidationError>> {
+        if !self
+            .instance()
+            .enabled_extensions()
+            .ext_acquire_xlib_display
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::InstanceExtension(
+                    "ext_acquire_xlib_display",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkAcquireXlibDisplayEXT-dpy-parameter
+        // Can't validate, therefore unsafe
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn acquire_xlib_unchecked<D>(&self, dpy: *const D) -> Result<(), VulkanError> {
+        let fns = self.instance().fns();
+        (fns.ext_acquire_xlib_display.acquire_xlib_display_ext)(
+            self.physical_device.handle(),
+            dpy as *mut _,
+            self.handle,
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        Ok(())
+    }
+
+    /// Acquires exclusive control of the display from a DRM master, so that it can be rendered
+    /// to directly without going through the window system.
+    ///
+    /// `drm_fd` must be a file descriptor of the DRM primary node that currently controls the
+    /// display, with DRM master permissions. It is not consumed, and may be closed after the
+    /// display has been acquired.
+    ///
+    /// The [`ext_acquire_drm_display`](crate::instance::InstanceExtensions::ext_acquire_drm_display)
+    /// extension must be enabled on the instance.
+    #[inline]
+    pub fn acquire_drm(&self, drm_fd: &File) -> Result<(), Validated<VulkanError>> {
+        self.validate_acquire_drm(drm_fd)?;
+
+        unsafe { Ok(self.acquire_drm_unchecked(drm_fd)?) }
+    }
+
+    fn validate_acquire_drm(&self, _drm_fd: &File) -> Result<(), Box<ValidationError>> {
+        if !self.instance().enabled_extensions().ext_acquire_drm_display {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::InstanceExtension(
+                    "ext_acquire_drm_display",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn acquire_drm_unchecked(&self, drm_fd: &File) -> Result<(), VulkanError> {
+        let fns = self.instance().fns();
+        (fns.ext_acquire_drm_display.acquire_drm_display_ext)(
+            self.physical_device.handle(),
+            raw_drm_fd(drm_fd),
+            self.handle,
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        Ok(())
+    }
+
+    /// Releases control of a display that was previously acquired with
+    /// [`acquire_xlib`](Self::acquire_xlib) or [`acquire_drm`](Self::acquire_drm), returning it
+    /// to the window system.
+    ///
+    /// The [`ext_direct_mode_display`](crate::instance::InstanceExtensions::ext_direct_mode_display)
+    /// extension must be enabled on the instance.
+    #[inline]
+    pub fn release(&self) -> Result<(), Validated<VulkanError>> {
+        self.validate_release()?;
+
+        unsafe { Ok(self.release_unchecked()?) }
+    }
+
+    fn validate_release(&self) -> Result<(), Box<ValidationError>> {
+        if !self.instance().enabled_extensions().ext_direct_mode_display {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::InstanceExtension(
+                    "ext_direct_mode_display",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn release_unchecked(&self) -> Result<(), VulkanError> {
+        let fns = self.instance().fns();
+        (fns.ext_direct_mode_display.release_display_ext)(
+            self.physical_device.handle(),
+            self.handle,
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        Ok(())
+    }
+}
+
+/// Returns the raw file descriptor of a DRM node, for passing to Vulkan.
+pub(crate) fn raw_drm_fd(drm_fd: &File) -> i32 {
+    #[cfg(unix)]
+    {
+        use std::os::fd::AsRawFd;
+        drm_fd.as_raw_fd()
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = drm_fd;
+        -1
+    }
 }
 
 unsafe impl VulkanObject for Display {
@@ -744,3 +896,46 @@ vulkan_bitflags_enum! {
     /// but treat the other components as having already been multiplied by the alpha component.
     PER_PIXEL_PREMULTIPLIED, PerPixelPremultiplied = PER_PIXEL_PREMULTIPLIED,
 }
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The power state of a display, set with [`Device::display_power_control`].
+    ///
+    /// [`Device::display_power_control`]: crate::device::Device::display_power_control
+    DisplayPowerState = DisplayPowerStateEXT(i32);
+
+    /// The display is powered down.
+    Off = OFF,
+
+    /// The display is put into a low power mode, from which it may be able to resume more quickly
+    /// than from `Off`.
+    Suspend = SUSPEND,
+
+    /// The display is powered on.
+    On = ON,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// An event on a display that a fence can be signaled by, with [`Fence::from_display_event`].
+    ///
+    /// [`Fence::from_display_event`]: crate::sync::fence::Fence::from_display_event
+    DisplayEventType = DisplayEventTypeEXT(i32);
+
+    /// The first pixel of a new frame is scanned out of the display.
+    FirstPixelOut = FIRST_PIXEL_OUT,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// An event on a device that a fence can be signaled by, with [`Fence::from_device_event`].
+    ///
+    /// [`Fence::from_device_event`]: crate::sync::fence::Fence::from_device_event
+    DeviceEventType = DeviceEventTypeEXT(i32);
+
+    /// A display is plugged into or unplugged from the physical device.
+    DisplayHotplug = DISPLAY_HOTPLUG,
+}
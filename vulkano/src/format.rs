@@ -92,6 +92,15 @@
 //! plane as an individual *aspect* of the image. A single-plane aspect of a multi-planar image
 //! behaves as a regular image, and even has its own format, which can be queried with the `plane`
 //! method on a format.
+//!
+//! ## Half-precision floating-point formats
+//!
+//! Formats with 16-bit `SFLOAT` components, such as `R16G16B16A16_SFLOAT`, store each component as
+//! an IEEE 754 half-precision float. The [`f16`] type of the [`half`](crate::half) crate, which is
+//! re-exported by Vulkano, has the same representation, so buffers of `f16` or arrays of `f16` can
+//! be used to upload data to images of these formats, or to read it back, without dealing with raw
+//! bit patterns. `f16` can also be used for vertex members with 16-bit float formats, and can be
+//! converted into a [`ClearValue`] or [`ClearColorValue`].
 
 use crate::{
     device::{physical::PhysicalDevice, Device},
@@ -100,6 +109,7 @@ use crate::{
     shader::spirv::ImageFormat,
     DeviceSize, Requires, RequiresAllOf, RequiresOneOf, ValidationError,
 };
+use half::f16;
 
 // Generated by build.rs
 include!(concat!(env!("OUT_DIR"), "/formats.rs"));
@@ -115,6 +125,29 @@ impl Format {
         physical_device.format_properties(self).unwrap()
     }
 
+    /// Returns the number of components (R, G, B, A, or depth and stencil) that are present in
+    /// the format.
+    #[inline]
+    pub fn component_count(self) -> u32 {
+        self.components()
+            .into_iter()
+            .filter(|&bits| bits != 0)
+            .count() as u32
+    }
+
+    /// Returns whether the format has a depth aspect, a stencil aspect, or both.
+    #[inline]
+    pub fn is_depth_stencil(self) -> bool {
+        self.aspects()
+            .intersects(ImageAspects::DEPTH | ImageAspects::STENCIL)
+    }
+
+    /// Returns whether the color components of the format are stored in the sRGB color space.
+    #[inline]
+    pub fn is_srgb(self) -> bool {
+        self.numeric_format_color() == Some(NumericFormat::SRGB)
+    }
+
     /// Returns whether the format can be used with a storage image, without specifying
     /// the format in the shader, if the
     /// [`shader_storage_image_read_without_format`](crate::device::Features::shader_storage_image_read_without_format)
@@ -551,6 +584,39 @@ impl From<[f32; 4]> for ClearValue {
     }
 }
 
+impl From<[f16; 1]> for ClearValue {
+    #[inline]
+    fn from(val: [f16; 1]) -> Self {
+        Self::Float([val[0].to_f32(), 0.0, 0.0, 1.0])
+    }
+}
+
+impl From<[f16; 2]> for ClearValue {
+    #[inline]
+    fn from(val: [f16; 2]) -> Self {
+        Self::Float([val[0].to_f32(), val[1].to_f32(), 0.0, 1.0])
+    }
+}
+
+impl From<[f16; 3]> for ClearValue {
+    #[inline]
+    fn from(val: [f16; 3]) -> Self {
+        Self::Float([val[0].to_f32(), val[1].to_f32(), val[2].to_f32(), 1.0])
+    }
+}
+
+impl From<[f16; 4]> for ClearValue {
+    #[inline]
+    fn from(val: [f16; 4]) -> Self {
+        Self::Float([
+            val[0].to_f32(),
+            val[1].to_f32(),
+            val[2].to_f32(),
+            val[3].to_f32(),
+        ])
+    }
+}
+
 impl From<[u32; 1]> for ClearValue {
     #[inline]
     fn from(val: [u32; 1]) -> Self {
@@ -640,6 +706,7 @@ pub(crate) enum ClearValueType {
 
 /// A value that will be used to clear a color image.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ClearColorValue {
     /// Value for formats with a numeric type that is not `SINT` or `UINT`.
     Float([f32; 4]),
@@ -699,6 +766,39 @@ impl From<[f32; 4]> for ClearColorValue {
     }
 }
 
+impl From<[f16; 1]> for ClearColorValue {
+    #[inline]
+    fn from(val: [f16; 1]) -> Self {
+        Self::Float([val[0].to_f32(), 0.0, 0.0, 1.0])
+    }
+}
+
+impl From<[f16; 2]> for ClearColorValue {
+    #[inline]
+    fn from(val: [f16; 2]) -> Self {
+        Self::Float([val[0].to_f32(), val[1].to_f32(), 0.0, 1.0])
+    }
+}
+
+impl From<[f16; 3]> for ClearColorValue {
+    #[inline]
+    fn from(val: [f16; 3]) -> Self {
+        Self::Float([val[0].to_f32(), val[1].to_f32(), val[2].to_f32(), 1.0])
+    }
+}
+
+impl From<[f16; 4]> for ClearColorValue {
+    #[inline]
+    fn from(val: [f16; 4]) -> Self {
+        Self::Float([
+            val[0].to_f32(),
+            val[1].to_f32(),
+            val[2].to_f32(),
+            val[3].to_f32(),
+        ])
+    }
+}
+
 impl From<[i32; 1]> for ClearColorValue {
     #[inline]
     fn from(val: [i32; 1]) -> Self {
@@ -757,6 +857,7 @@ impl From<[u32; 4]> for ClearColorValue {
 
 /// A value that will be used to clear a depth/stencil image.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ClearDepthStencilValue {
     /// Value for the depth component.
     pub depth: f32,
@@ -182,12 +182,13 @@ vulkan_enum! {
         RequiresAllOf([DeviceExtension(ext_fragment_density_map)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// For an image used as a fragment shading rate attachment in a framebuffer. Images that are
+    /// transitioned into this layout must have the `fragment_shading_rate_attachment` usage
+    /// enabled.
     FragmentShadingRateAttachmentOptimal = FRAGMENT_SHADING_RATE_ATTACHMENT_OPTIMAL_KHR
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(khr_fragment_shading_rate)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -249,7 +250,8 @@ impl ImageLayout {
                 | ImageLayout::DepthReadOnlyOptimal
                 | ImageLayout::StencilAttachmentOptimal
                 | ImageLayout::StencilReadOnlyOptimal
-                | ImageLayout::PresentSrc => false,
+                | ImageLayout::PresentSrc
+                | ImageLayout::FragmentShadingRateAttachmentOptimal => false,
             },
             ImageAspect::Depth => match self {
                 ImageLayout::General
@@ -268,7 +270,8 @@ impl ImageLayout {
                 | ImageLayout::DepthReadOnlyOptimal
                 | ImageLayout::StencilAttachmentOptimal
                 | ImageLayout::StencilReadOnlyOptimal
-                | ImageLayout::PresentSrc => false,
+                | ImageLayout::PresentSrc
+                | ImageLayout::FragmentShadingRateAttachmentOptimal => false,
             },
             ImageAspect::Stencil => match self {
                 ImageLayout::General
@@ -287,7 +290,8 @@ impl ImageLayout {
                 | ImageLayout::DepthAttachmentOptimal
                 | ImageLayout::DepthReadOnlyOptimal
                 | ImageLayout::StencilReadOnlyOptimal
-                | ImageLayout::PresentSrc => false,
+                | ImageLayout::PresentSrc
+                | ImageLayout::FragmentShadingRateAttachmentOptimal => false,
             },
             ImageAspect::Metadata
             | ImageAspect::MemoryPlane0
@@ -63,6 +63,8 @@
 
 pub use self::{aspect::*, layout::*, sys::ImageCreateInfo, usage::*};
 use self::{sys::RawImage, view::ImageViewType};
+#[cfg(feature = "swapchain")]
+use crate::swapchain::Swapchain;
 use crate::{
     device::{physical::PhysicalDevice, Device, DeviceOwned},
     format::{Format, FormatFeatures},
@@ -73,7 +75,6 @@ use crate::{
         ExternalMemoryProperties, MemoryRequirements, ResourceMemory,
     },
     range_map::RangeMap,
-    swapchain::Swapchain,
     sync::{future::AccessError, AccessConflict, CurrentAccess, Sharing},
     DeviceSize, Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version,
     VulkanError, VulkanObject,
@@ -136,6 +137,7 @@ pub enum ImageMemory {
     Sparse(Vec<SparseImageMemoryRequirements>),
 
     /// The image is backed by memory owned by a [`Swapchain`].
+    #[cfg(feature = "swapchain")]
     Swapchain {
         swapchain: Arc<Swapchain>,
         image_index: u32,
@@ -201,6 +203,7 @@ impl Image {
         }
     }
 
+    #[cfg(feature = "swapchain")]
     pub(crate) unsafe fn from_swapchain(
         handle: ash::vk::Image,
         swapchain: Arc<Swapchain>,
@@ -504,11 +507,17 @@ impl Image {
         self.state.lock()
     }
 
-    pub(crate) fn initial_layout_requirement(&self) -> ImageLayout {
+    /// Returns the layout that the image must be in when a command buffer that uses it starts
+    /// executing, once its layout has been initialized.
+    #[inline]
+    pub fn initial_layout_requirement(&self) -> ImageLayout {
         self.layout
     }
 
-    pub(crate) fn final_layout_requirement(&self) -> ImageLayout {
+    /// Returns the layout that a primary command buffer must leave the image in when it
+    /// finishes executing.
+    #[inline]
+    pub fn final_layout_requirement(&self) -> ImageLayout {
         self.layout
     }
 
@@ -517,6 +526,7 @@ impl Image {
             ImageMemory::Normal(..) | ImageMemory::Sparse(..) => {
                 self.is_layout_initialized.store(true, Ordering::Release);
             }
+            #[cfg(feature = "swapchain")]
             ImageMemory::Swapchain {
                 swapchain,
                 image_index,
@@ -531,6 +541,7 @@ impl Image {
             ImageMemory::Normal(..) | ImageMemory::Sparse(..) => {
                 self.is_layout_initialized.load(Ordering::Acquire)
             }
+            #[cfg(feature = "swapchain")]
             ImageMemory::Swapchain {
                 swapchain,
                 image_index,
@@ -1350,6 +1361,7 @@ pub fn mip_level_extent(extent: [u32; 3], level: u32) -> Option<[u32; 3]> {
 /// One or more subresources of an image, spanning a single mip level, that should be accessed by a
 /// command.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImageSubresourceLayers {
     /// Selects the aspects that will be included.
     ///
@@ -1488,6 +1500,7 @@ impl From<&ImageSubresourceLayers> for ash::vk::ImageSubresourceLayers {
 
 /// One or more subresources of an image that should be accessed by a command.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImageSubresourceRange {
     /// Selects the aspects that will be included.
     ///
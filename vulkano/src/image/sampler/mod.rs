@@ -49,17 +49,23 @@ pub mod ycbcr;
 use self::ycbcr::SamplerYcbcrConversion;
 use crate::{
     device::{Device, DeviceOwned, DeviceOwnedDebugWrapper},
-    format::{FormatFeatures, NumericType},
+    format::{ClearColorValue, Format, FormatFeatures, NumericType},
     image::{
         view::{ImageView, ImageViewType},
         ImageAspects,
     },
     instance::InstanceOwnedDebugWrapper,
-    macros::{impl_id_counter, vulkan_enum},
+    macros::{impl_id_counter, vulkan_bitflags, vulkan_enum},
     pipeline::graphics::depth_stencil::CompareOp,
     Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, VulkanError, VulkanObject,
 };
-use std::{mem::MaybeUninit, num::NonZeroU64, ops::RangeInclusive, ptr, sync::Arc};
+use std::{
+    mem::MaybeUninit,
+    num::NonZeroU64,
+    ops::RangeInclusive,
+    ptr,
+    sync::{atomic::Ordering, Arc},
+};
 
 /// Describes how to retrieve data from a sampled image within a shader.
 ///
@@ -96,9 +102,12 @@ pub struct Sampler {
     device: InstanceOwnedDebugWrapper<Arc<Device>>,
     id: NonZeroU64,
 
+    flags: SamplerCreateFlags,
     address_mode: [SamplerAddressMode; 3],
     anisotropy: Option<f32>,
     border_color: Option<BorderColor>,
+    custom_border_color: ClearColorValue,
+    custom_border_color_format: Option<Format>,
     compare: Option<CompareOp>,
     lod: RangeInclusive<f32>,
     mag_filter: Filter,
@@ -108,6 +117,8 @@ pub struct Sampler {
     reduction_mode: SamplerReductionMode,
     sampler_ycbcr_conversion: Option<DeviceOwnedDebugWrapper<Arc<SamplerYcbcrConversion>>>,
     unnormalized_coordinates: bool,
+
+    has_custom_border_color: bool,
 }
 
 impl Sampler {
@@ -130,6 +141,34 @@ impl Sampler {
             .validate(device)
             .map_err(|err| err.add_context("create_info"))?;
 
+        if create_info.border_color.is_custom() {
+            if let Some(max_custom_border_color_samplers) = device
+                .physical_device()
+                .properties()
+                .max_custom_border_color_samplers
+            {
+                // This can race with other threads creating samplers, in which case the limit
+                // may be exceeded anyway.
+                if device
+                    .counters
+                    .custom_border_color_samplers
+                    .load(Ordering::Relaxed)
+                    >= max_custom_border_color_samplers
+                {
+                    return Err(Box::new(ValidationError {
+                        context: "create_info.border_color".into(),
+                        problem: "is `BorderColor::FloatCustom` or `BorderColor::IntCustom`, but \
+                            the number of samplers with a custom border color that currently \
+                            exist on the device is already equal to the \
+                            `max_custom_border_color_samplers` limit"
+                            .into(),
+                        vuids: &["VUID-VkSamplerCreateInfo-None-04012"],
+                        ..Default::default()
+                    }));
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -139,6 +178,7 @@ impl Sampler {
         create_info: SamplerCreateInfo,
     ) -> Result<Arc<Sampler>, VulkanError> {
         let &SamplerCreateInfo {
+            flags,
             mag_filter,
             min_filter,
             mipmap_mode,
@@ -148,6 +188,8 @@ impl Sampler {
             compare,
             ref lod,
             border_color,
+            custom_border_color,
+            custom_border_color_format,
             unnormalized_coordinates,
             reduction_mode,
             ref sampler_ycbcr_conversion,
@@ -167,7 +209,7 @@ impl Sampler {
         };
 
         let mut create_info_vk = ash::vk::SamplerCreateInfo {
-            flags: ash::vk::SamplerCreateFlags::empty(),
+            flags: flags.into(),
             mag_filter: mag_filter.into(),
             min_filter: min_filter.into(),
             mipmap_mode: mipmap_mode.into(),
@@ -185,9 +227,24 @@ impl Sampler {
             unnormalized_coordinates: unnormalized_coordinates as ash::vk::Bool32,
             ..Default::default()
         };
+        let mut sampler_custom_border_color_create_info_vk = None;
         let mut sampler_reduction_mode_create_info_vk = None;
         let mut sampler_ycbcr_conversion_info_vk = None;
 
+        if border_color.is_custom() {
+            let next = sampler_custom_border_color_create_info_vk.insert(
+                ash::vk::SamplerCustomBorderColorCreateInfoEXT {
+                    custom_border_color: custom_border_color.into(),
+                    format: custom_border_color_format
+                        .map_or(ash::vk::Format::UNDEFINED, Into::into),
+                    ..Default::default()
+                },
+            );
+
+            next.p_next = create_info_vk.p_next;
+            create_info_vk.p_next = next as *const _ as *const _;
+        }
+
         if reduction_mode != SamplerReductionMode::WeightedAverage {
             let next = sampler_reduction_mode_create_info_vk.insert(
                 ash::vk::SamplerReductionModeCreateInfo {
@@ -241,6 +298,7 @@ impl Sampler {
         create_info: SamplerCreateInfo,
     ) -> Arc<Sampler> {
         let SamplerCreateInfo {
+            flags,
             mag_filter,
             min_filter,
             mipmap_mode,
@@ -250,22 +308,36 @@ impl Sampler {
             compare,
             lod,
             border_color,
+            custom_border_color,
+            custom_border_color_format,
             unnormalized_coordinates,
             reduction_mode,
             sampler_ycbcr_conversion,
             _ne: _,
         } = create_info;
 
+        let has_custom_border_color = border_color.is_custom();
+
+        if has_custom_border_color {
+            device
+                .counters
+                .custom_border_color_samplers
+                .fetch_add(1, Ordering::Relaxed);
+        }
+
         Arc::new(Sampler {
             handle,
             device: InstanceOwnedDebugWrapper(device),
             id: Self::next_id(),
+            flags,
             address_mode,
             anisotropy,
             border_color: address_mode
                 .into_iter()
                 .any(|mode| mode == SamplerAddressMode::ClampToBorder)
                 .then_some(border_color),
+            custom_border_color,
+            custom_border_color_format,
             compare,
             lod,
             mag_filter,
@@ -275,6 +347,8 @@ impl Sampler {
             reduction_mode,
             sampler_ycbcr_conversion: sampler_ycbcr_conversion.map(DeviceOwnedDebugWrapper),
             unnormalized_coordinates,
+
+            has_custom_border_color,
         })
     }
 
@@ -416,7 +490,8 @@ impl Sampler {
             match border_color {
                 BorderColor::IntTransparentBlack
                 | BorderColor::IntOpaqueBlack
-                | BorderColor::IntOpaqueWhite => {
+                | BorderColor::IntOpaqueWhite
+                | BorderColor::IntCustom => {
                     // The sampler borderColor is an integer type and the image view
                     // format is not one of the VkFormat integer types or a stencil
                     // component of a depth/stencil format.
@@ -431,7 +506,8 @@ impl Sampler {
                 }
                 BorderColor::FloatTransparentBlack
                 | BorderColor::FloatOpaqueBlack
-                | BorderColor::FloatOpaqueWhite => {
+                | BorderColor::FloatOpaqueWhite
+                | BorderColor::FloatCustom => {
                     // The sampler borderColor is a float type and the image view
                     // format is not one of the VkFormat float types or a depth
                     // component of a depth/stencil format.
@@ -504,6 +580,12 @@ impl Sampler {
         Ok(())
     }
 
+    /// Returns the flags that the sampler was created with.
+    #[inline]
+    pub fn flags(&self) -> SamplerCreateFlags {
+        self.flags
+    }
+
     /// Returns the address modes for the u, v and w coordinates.
     #[inline]
     pub fn address_mode(&self) -> [SamplerAddressMode; 3] {
@@ -522,6 +604,23 @@ impl Sampler {
         self.border_color
     }
 
+    /// Returns the custom border color value, if the border color is
+    /// [`FloatCustom`](BorderColor::FloatCustom) or [`IntCustom`](BorderColor::IntCustom).
+    #[inline]
+    pub fn custom_border_color(&self) -> Option<ClearColorValue> {
+        matches!(
+            self.border_color,
+            Some(BorderColor::FloatCustom | BorderColor::IntCustom)
+        )
+        .then_some(self.custom_border_color)
+    }
+
+    /// Returns the format of the custom border color, if one was specified.
+    #[inline]
+    pub fn custom_border_color_format(&self) -> Option<Format> {
+        self.custom_border_color_format
+    }
+
     /// Returns the compare operation if the sampler is a compare-mode sampler.
     #[inline]
     pub fn compare(&self) -> Option<CompareOp> {
@@ -584,6 +683,13 @@ impl Drop for Sampler {
             let fns = self.device.fns();
             (fns.v1_0.destroy_sampler)(self.device.handle(), self.handle, ptr::null());
         }
+
+        if self.has_custom_border_color {
+            self.device
+                .counters
+                .custom_border_color_samplers
+                .fetch_sub(1, Ordering::Relaxed);
+        }
     }
 }
 
@@ -608,6 +714,11 @@ impl_id_counter!(Sampler);
 /// Parameters to create a new `Sampler`.
 #[derive(Clone, Debug)]
 pub struct SamplerCreateInfo {
+    /// Additional properties of the sampler.
+    ///
+    /// The default value is empty.
+    pub flags: SamplerCreateFlags,
+
     /// How the sampled value of a single mipmap should be calculated,
     /// when magnification is applied (LOD <= 0.0).
     ///
@@ -694,6 +805,25 @@ pub struct SamplerCreateInfo {
     /// The default value is [`FloatTransparentBlack`](BorderColor::FloatTransparentBlack).
     pub border_color: BorderColor,
 
+    /// The color value to use for the border, if `border_color` is
+    /// [`FloatCustom`](BorderColor::FloatCustom) or [`IntCustom`](BorderColor::IntCustom).
+    ///
+    /// With `FloatCustom`, this must be [`ClearColorValue::Float`], and with `IntCustom`, it must
+    /// be [`ClearColorValue::Int`] or [`ClearColorValue::Uint`].
+    ///
+    /// The default value is `ClearColorValue::Float([0.0; 4])`.
+    pub custom_border_color: ClearColorValue,
+
+    /// The format of the image views that the sampler will be used with, if `border_color` is
+    /// [`FloatCustom`](BorderColor::FloatCustom) or [`IntCustom`](BorderColor::IntCustom).
+    ///
+    /// If set to `None`, the
+    /// [`custom_border_color_without_format`](crate::device::Features::custom_border_color_without_format)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is `None`.
+    pub custom_border_color_format: Option<Format>,
+
     /// Whether unnormalized texture coordinates are enabled.
     ///
     /// When a sampler is set to use unnormalized coordinates as input, the texture coordinates are
@@ -750,6 +880,7 @@ impl Default for SamplerCreateInfo {
     #[inline]
     fn default() -> Self {
         Self {
+            flags: SamplerCreateFlags::empty(),
             mag_filter: Filter::Nearest,
             min_filter: Filter::Nearest,
             mipmap_mode: SamplerMipmapMode::Nearest,
@@ -759,6 +890,8 @@ impl Default for SamplerCreateInfo {
             compare: None,
             lod: 0.0..=0.0,
             border_color: BorderColor::FloatTransparentBlack,
+            custom_border_color: ClearColorValue::Float([0.0; 4]),
+            custom_border_color_format: None,
             unnormalized_coordinates: false,
             reduction_mode: SamplerReductionMode::WeightedAverage,
             sampler_ycbcr_conversion: None,
@@ -797,6 +930,7 @@ impl SamplerCreateInfo {
 
     pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
         let &Self {
+            flags,
             mag_filter,
             min_filter,
             mipmap_mode,
@@ -806,6 +940,8 @@ impl SamplerCreateInfo {
             compare,
             ref lod,
             border_color,
+            custom_border_color,
+            custom_border_color_format,
             unnormalized_coordinates,
             reduction_mode,
             ref sampler_ycbcr_conversion,
@@ -814,6 +950,11 @@ impl SamplerCreateInfo {
 
         let properties = device.physical_device().properties();
 
+        flags.validate_device(device).map_err(|err| {
+            err.add_context("flags")
+                .set_vuids(&["VUID-VkSamplerCreateInfo-flags-parameter"])
+        })?;
+
         mag_filter.validate_device(device).map_err(|err| {
             err.add_context("mag_filter")
                 .set_vuids(&["VUID-VkSamplerCreateInfo-magFilter-parameter"])
@@ -847,6 +988,69 @@ impl SamplerCreateInfo {
             })?;
         }
 
+        if matches!(
+            border_color,
+            BorderColor::FloatCustom | BorderColor::IntCustom
+        ) {
+            if !device.enabled_features().custom_border_colors {
+                return Err(Box::new(ValidationError {
+                    context: "border_color".into(),
+                    problem: "is `BorderColor::FloatCustom` or `BorderColor::IntCustom`".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "custom_border_colors",
+                    )])]),
+                    vuids: &[
+                        "VUID-VkSamplerCustomBorderColorCreateInfoEXT-customBorderColors-04085",
+                    ],
+                }));
+            }
+
+            match (border_color, custom_border_color) {
+                (BorderColor::FloatCustom, ClearColorValue::Float(_))
+                | (BorderColor::IntCustom, ClearColorValue::Int(_) | ClearColorValue::Uint(_)) => {}
+                _ => {
+                    return Err(Box::new(ValidationError {
+                        problem: "the numeric type of `custom_border_color` does not match \
+                            `border_color`"
+                            .into(),
+                        ..Default::default()
+                    }));
+                }
+            }
+
+            if let Some(format) = custom_border_color_format {
+                format.validate_device(device).map_err(|err| {
+                    err.add_context("custom_border_color_format").set_vuids(&[
+                        "VUID-VkSamplerCustomBorderColorCreateInfoEXT-format-parameter",
+                    ])
+                })?;
+            } else if !device.enabled_features().custom_border_color_without_format {
+                return Err(Box::new(ValidationError {
+                    problem: "`border_color` is `BorderColor::FloatCustom` or \
+                        `BorderColor::IntCustom`, and `custom_border_color_format` is `None`"
+                        .into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "custom_border_color_without_format",
+                    )])]),
+                    vuids: &["VUID-VkSamplerCustomBorderColorCreateInfoEXT-format-04014"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if flags.intersects(SamplerCreateFlags::NON_SEAMLESS_CUBE_MAP)
+            && !device.enabled_features().non_seamless_cube_map
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `SamplerCreateFlags::NON_SEAMLESS_CUBE_MAP`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "non_seamless_cube_map",
+                )])]),
+                vuids: &["VUID-VkSamplerCreateInfo-nonSeamlessCubeMap-06788"],
+            }));
+        }
+
         reduction_mode.validate_device(device).map_err(|err| {
             err.add_context("reduction_mode")
                 .set_vuids(&["VUID-VkSamplerReductionModeCreateInfo-reductionMode-parameter"])
@@ -881,7 +1085,7 @@ impl SamplerCreateInfo {
 
         if mip_lod_bias.abs() > properties.max_sampler_lod_bias {
             return Err(Box::new(ValidationError {
-                context: "lod".into(),
+                context: "mip_lod_bias".into(),
                 problem: "the absolute value is greater than the `max_sampler_lod_bias` limit"
                     .into(),
                 vuids: &["VUID-VkSamplerCreateInfo-mipLodBias-01069"],
@@ -987,7 +1191,7 @@ impl SamplerCreateInfo {
             if *lod != (0.0..=0.0) {
                 return Err(Box::new(ValidationError {
                     problem: "`unnormalized_coordinates` is `true`, but \
-                        `lod` is not `0.0..=1.0`"
+                        `lod` is not `0.0..=0.0`"
                         .into(),
                     vuids: &["VUID-VkSamplerCreateInfo-unnormalizedCoordinates-01074"],
                     ..Default::default()
@@ -1022,7 +1226,7 @@ impl SamplerCreateInfo {
             if compare.is_some() {
                 return Err(Box::new(ValidationError {
                     problem: "`unnormalized_coordinates` is `true`, but `compare` is `Some`".into(),
-                    vuids: &["VUID-VkSamplerCreateInfo-unnormalizedCoordinates-0107"],
+                    vuids: &["VUID-VkSamplerCreateInfo-unnormalizedCoordinates-01077"],
                     ..Default::default()
                 }));
             }
@@ -1302,6 +1506,37 @@ impl Default for ComponentSwizzle {
     }
 }
 
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags that control how a sampler is created.
+    SamplerCreateFlags = SamplerCreateFlags(u32);
+
+    /* TODO: enable
+    // TODO: document
+    SUBSAMPLED = SUBSAMPLED_EXT
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_fragment_density_map)]),
+    ]),*/
+
+    /* TODO: enable
+    // TODO: document
+    SUBSAMPLED_COARSE_RECONSTRUCTION = SUBSAMPLED_COARSE_RECONSTRUCTION_EXT
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_fragment_density_map)]),
+    ]),*/
+
+    /// Cube map images sampled with this sampler are sampled as if each face were a separate
+    /// image, instead of filtering across the edges between faces.
+    ///
+    /// The [`non_seamless_cube_map`](crate::device::Features::non_seamless_cube_map) feature
+    /// must be enabled on the device.
+    NON_SEAMLESS_CUBE_MAP = NON_SEAMLESS_CUBE_MAP_EXT
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_non_seamless_cube_map)]),
+    ]),
+}
+
 vulkan_enum! {
     #[non_exhaustive]
 
@@ -1417,19 +1652,33 @@ vulkan_enum! {
     /// The value `(1, 1, 1, 1)`. Can only be used with integer images.
     IntOpaqueWhite = INT_OPAQUE_WHITE,
 
-    /* TODO: enable
-    // TODO: document
-    FloatCustom = VK_BORDER_COLOR_FLOAT_CUSTOM_EXT
+    /// The value of [`SamplerCreateInfo::custom_border_color`]. Can only be used with
+    /// floating-point images.
+    ///
+    /// The [`custom_border_colors`](crate::device::Features::custom_border_colors) feature must
+    /// be enabled on the device.
+    FloatCustom = FLOAT_CUSTOM_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_custom_border_color)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// The value of [`SamplerCreateInfo::custom_border_color`]. Can only be used with integer
+    /// images.
+    ///
+    /// The [`custom_border_colors`](crate::device::Features::custom_border_colors) feature must
+    /// be enabled on the device.
     IntCustom = INT_CUSTOM_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_custom_border_color)]),
-    ]),*/
+    ]),
+}
+
+impl BorderColor {
+    /// Returns whether `self` is one of the custom border colors.
+    #[inline]
+    pub(crate) fn is_custom(self) -> bool {
+        matches!(self, BorderColor::FloatCustom | BorderColor::IntCustom)
+    }
 }
 
 vulkan_enum! {
@@ -1463,11 +1712,13 @@ vulkan_enum! {
 #[cfg(test)]
 mod tests {
     use crate::{
+        format::ClearColorValue,
         image::sampler::{
-            Filter, Sampler, SamplerAddressMode, SamplerCreateInfo, SamplerReductionMode,
+            BorderColor, Filter, Sampler, SamplerAddressMode, SamplerCreateInfo,
+            SamplerReductionMode,
         },
         pipeline::graphics::depth_stencil::CompareOp,
-        Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError,
+        Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version,
     };
 
     #[test]
@@ -1682,6 +1933,20 @@ mod tests {
                         ..
                     }
                 ) => {}
+            // Before Vulkan 1.2, the address mode itself requires the extension.
+            Err(Validated::ValidationError(err))
+                if matches!(
+                    *err,
+                    ValidationError {
+                        requires_one_of: RequiresOneOf([
+                            RequiresAllOf([Requires::APIVersion(Version::V1_2)]),
+                            RequiresAllOf([Requires::DeviceExtension(
+                                "khr_sampler_mirror_clamp_to_edge"
+                            )],)
+                        ],),
+                        ..
+                    }
+                ) => {}
             _ => panic!(),
         }
     }
@@ -1715,4 +1980,33 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn custom_border_color_extension() {
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        let r = Sampler::new(
+            device,
+            SamplerCreateInfo {
+                address_mode: [SamplerAddressMode::ClampToBorder; 3],
+                border_color: BorderColor::FloatCustom,
+                custom_border_color: ClearColorValue::Float([1.0, 0.0, 1.0, 1.0]),
+                ..Default::default()
+            },
+        );
+
+        match r {
+            Err(Validated::ValidationError(err))
+                if matches!(
+                    *err,
+                    ValidationError {
+                        requires_one_of: RequiresOneOf([RequiresAllOf([
+                            Requires::DeviceExtension("ext_custom_border_color")
+                        ])]),
+                        ..
+                    }
+                ) => {}
+            _ => panic!(),
+        }
+    }
 }
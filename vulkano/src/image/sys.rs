@@ -42,7 +42,12 @@ use crate::{
     VulkanObject,
 };
 use smallvec::{smallvec, SmallVec};
-use std::{mem::MaybeUninit, num::NonZeroU64, ptr, sync::Arc};
+use std::{
+    mem::MaybeUninit,
+    num::NonZeroU64,
+    ptr,
+    sync::{atomic::Ordering, Arc},
+};
 
 /// A raw image, with no memory backing it.
 ///
@@ -355,6 +360,8 @@ impl RawImage {
             smallvec![]
         };
 
+        device.counters.images.fetch_add(1, Ordering::Relaxed);
+
         Ok(RawImage {
             handle,
             device: InstanceOwnedDebugWrapper(device),
@@ -1629,6 +1636,8 @@ impl RawImage {
 impl Drop for RawImage {
     #[inline]
     fn drop(&mut self) {
+        self.device.counters.images.fetch_sub(1, Ordering::Relaxed);
+
         if !self.needs_destruction {
             return;
         }
@@ -2260,6 +2269,28 @@ impl ImageCreateInfo {
             }));
         }
 
+        if usage.intersects(ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT) {
+            if image_type != ImageType::Dim2d {
+                return Err(Box::new(ValidationError {
+                    problem: "`usage` contains `ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT`, \
+                        but `image_type` is not `ImageType::Dim2d`"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-imageType-02082"],
+                    ..Default::default()
+                }));
+            }
+
+            if samples != SampleCount::Sample1 {
+                return Err(Box::new(ValidationError {
+                    problem: "`usage` contains `ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT`, \
+                        but `samples` is not `SampleCount::Sample1`"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-samples-02083"],
+                    ..Default::default()
+                }));
+            }
+        }
+
         if usage.intersects(ImageUsage::TRANSIENT_ATTACHMENT) {
             if !usage.intersects(
                 ImageUsage::COLOR_ATTACHMENT
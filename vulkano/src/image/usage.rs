@@ -77,12 +77,11 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(ext_fragment_density_map)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The image can be used as a fragment shading rate attachment in a render pass/framebuffer.
     FRAGMENT_SHADING_RATE_ATTACHMENT = FRAGMENT_SHADING_RATE_ATTACHMENT_KHR
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(khr_fragment_shading_rate)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
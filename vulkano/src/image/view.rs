@@ -287,13 +287,15 @@ impl ImageView {
                 | ImageUsage::COLOR_ATTACHMENT
                 | ImageUsage::DEPTH_STENCIL_ATTACHMENT
                 | ImageUsage::INPUT_ATTACHMENT
-                | ImageUsage::TRANSIENT_ATTACHMENT,
+                | ImageUsage::TRANSIENT_ATTACHMENT
+                | ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT,
         ) {
             return Err(Box::new(ValidationError {
                 context: "image.usage()".into(),
                 problem: "does not contain one of `ImageUsage::SAMPLED`, `ImageUsage::STORAGE`, \
                     `ImageUsage::COLOR_ATTACHMENT`, `ImageUsage::DEPTH_STENCIL_ATTACHMENT`, \
-                    `ImageUsage::INPUT_ATTACHMENT` or `ImageUsage::TRANSIENT_ATTACHMENT`"
+                    `ImageUsage::INPUT_ATTACHMENT`, `ImageUsage::TRANSIENT_ATTACHMENT` or \
+                    `ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT`"
                     .into(),
                 vuids: &["VUID-VkImageViewCreateInfo-image-04441"],
                 ..Default::default()
@@ -378,6 +380,21 @@ impl ImageView {
             }));
         }
 
+        if usage.intersects(ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT)
+            && !format_features.intersects(FormatFeatures::FRAGMENT_SHADING_RATE_ATTACHMENT)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`create_info.usage` or the implicit default usage \
+                    (calculated from `image` and `create_info.subresource_range.aspects`) \
+                    contains `ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT`, but \
+                    the format features of `create_info.format` do not contain \
+                    `FormatFeatures::FRAGMENT_SHADING_RATE_ATTACHMENT`"
+                    .into(),
+                vuids: &["VUID-VkImageViewCreateInfo-usage-04550"],
+                ..Default::default()
+            }));
+        }
+
         /* Check flags requirements */
 
         if format != image.format() {
@@ -99,8 +99,27 @@
 //! | Feature              | Description                                                    |
 //! |----------------------|----------------------------------------------------------------|
 //! | `macros`             | Include reexports from [`vulkano-macros`]. Enabled by default. |
+//! | `swapchain`          | The `swapchain` module and presentation. Enabled by default.   |
+//! | `display`            | The `display` module. Enables `swapchain`. Enabled by default. |
 //! | `document_unchecked` | Include `_unchecked` functions in the generated documentation. |
 //! | `serde`              | Enables (de)serialization of certain types using [`serde`].    |
+//! | `log`                | Emits diagnostic records through the [`log`] crate.            |
+//! | `null_driver`        | Provides a Vulkan implementation without a GPU, for testing.   |
+//! | `trace`              | Transfer command trace recording and replay. Enables `serde`.  |
+//!
+//! # Logging
+//!
+//! With the `log` feature enabled, Vulkano reports noteworthy events to whichever logger the
+//! application has installed. Each category of events uses its own log target, so that they can
+//! be filtered individually at runtime through the logger's configuration (for example
+//! `RUST_LOG=vulkano::swapchain=debug` with `env_logger`):
+//!
+//! | Target               | Events                                                         |
+//! |----------------------|----------------------------------------------------------------|
+//! | `vulkano::device`    | Device creation, with the enabled extensions and features.     |
+//! | `vulkano::memory`    | Failed device memory allocations.                              |
+//! | `vulkano::pipeline`  | Compute and graphics pipeline creation.                        |
+//! | `vulkano::swapchain` | Swapchain (re)creation, out-of-date and suboptimal results.    |
 //!
 //! [`VulkanLibrary`]: crate::VulkanLibrary
 //! [`Instance`]: crate::instance::Instance
@@ -125,6 +144,7 @@
 //! [`Framebuffer`]: crate::render_pass::Framebuffer
 //! [`vulkano-macros`]: vulkano_macros
 //! [`serde`]: https://crates.io/crates/serde
+//! [`log`]: https://crates.io/crates/log
 
 //#![warn(missing_docs)]        // TODO: activate
 #![warn(
@@ -178,6 +198,7 @@ pub mod command_buffer;
 pub mod deferred;
 pub mod descriptor_set;
 pub mod device;
+#[cfg(feature = "display")]
 pub mod display;
 pub mod format;
 mod version;
@@ -196,6 +217,7 @@ pub mod query;
 mod range_map;
 pub mod range_set;
 pub mod shader;
+#[cfg(feature = "swapchain")]
 pub mod swapchain;
 pub mod sync;
 
@@ -700,3 +722,10 @@ impl Display for Requires {
 /// syntax from being used.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)] // add traits as needed
 pub struct NonExhaustive(pub(crate) ());
+
+impl NonExhaustive {
+    #[cfg(feature = "serde")]
+    pub(crate) const fn new() -> Self {
+        Self(())
+    }
+}
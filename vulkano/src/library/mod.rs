@@ -17,6 +17,10 @@
 //!
 //! Once you have a type that implements `Loader`, you can create a `VulkanLibrary`
 //! from it and use this `VulkanLibrary` struct to build an `Instance`.
+//!
+//! With the `null_driver` feature, the `null` module provides a loader for a Vulkan
+//! implementation that doesn't need a GPU, which can be used to test code on machines without
+//! Vulkan support.
 
 pub use crate::fns::EntryFunctions;
 use crate::{
@@ -35,6 +39,9 @@ use std::{
     sync::Arc,
 };
 
+#[cfg(feature = "null_driver")]
+pub mod null;
+
 /// A loaded library containing a valid Vulkan implementation.
 #[derive(Debug)]
 pub struct VulkanLibrary {
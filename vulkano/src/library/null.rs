@@ -0,0 +1,2031 @@
+// Copyright (c) 2024 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! A Vulkan implementation that doesn't need a GPU or a driver.
+//!
+//! [`NullLoader`] implements the core Vulkan 1.0 entry points in Rust, so that code using vulkano
+//! can be tested on machines that have no Vulkan implementation installed, such as most CI
+//! runners. All of vulkano's validation is performed as usual, and resources, memory allocations
+//! and command buffers behave as they would on a real device, but commands recorded into command
+//! buffers are never executed. Instead, the null implementation keeps a list of the commands that
+//! were recorded, which can be retrieved with [`recorded_commands`].
+//!
+//! The null implementation exposes one physical device, which supports Vulkan 1.0 without any
+//! extensions. It supports all core features apart from the sparse ones, and every format that
+//! has no multi-planar or YCbCr layout. It has two queue families: the first supports graphics,
+//! compute and transfer operations, the second only compute and transfer operations. Memory that
+//! is host-visible is backed by host memory, so it can be written to and read from as usual.
+//!
+//! Submitted work completes immediately: fences that are passed to a queue submission are
+//! signaled when the submission returns. Queries always return zero. Entry points that are not
+//! implemented, including all extension entry points, are reported as not present, so calling
+//! them will panic.
+//!
+//! ```
+//! use vulkano::{
+//!     device::{Device, DeviceCreateInfo, QueueCreateInfo},
+//!     instance::{Instance, InstanceCreateInfo},
+//!     library::{null::NullLoader, VulkanLibrary},
+//! };
+//!
+//! let library = VulkanLibrary::with_loader(NullLoader::new()).unwrap();
+//! let instance = Instance::new(library, InstanceCreateInfo::default()).unwrap();
+//! let physical_device = instance.enumerate_physical_devices().unwrap().next().unwrap();
+//! let (device, mut queues) = Device::new(
+//!     physical_device,
+//!     DeviceCreateInfo {
+//!         queue_create_infos: vec![QueueCreateInfo::default()],
+//!         ..Default::default()
+//!     },
+//! )
+//! .unwrap();
+//! let queue = queues.next().unwrap();
+//! ```
+
+use super::Loader;
+use crate::{format::Format, VulkanObject};
+use ahash::HashMap;
+use ash::vk::{self, Handle};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::{
+    ffi::{c_void, CStr},
+    mem::{size_of, transmute},
+    os::raw::c_char,
+    ptr, slice,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Implementation of `Loader` that provides a Vulkan implementation without a GPU or driver.
+///
+/// See the [module-level documentation](self) for what the implementation supports.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NullLoader {
+    _private: (),
+}
+
+impl NullLoader {
+    /// Returns a new `NullLoader`.
+    #[inline]
+    pub fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+unsafe impl Loader for NullLoader {
+    #[inline]
+    unsafe fn get_instance_proc_addr(
+        &self,
+        instance: vk::Instance,
+        name: *const c_char,
+    ) -> vk::PFN_vkVoidFunction {
+        get_instance_proc_addr(instance, name)
+    }
+}
+
+/// Returns the names of the commands that were recorded into `command_buffer` since recording
+/// was last begun, such as `"vkCmdCopyBuffer"`.
+///
+/// The commands that vulkano records on its own, such as pipeline barriers inserted by the
+/// automatic synchronization, are included. If `command_buffer` was not allocated from a device
+/// that uses [`NullLoader`], an empty list is returned.
+pub fn recorded_commands(
+    command_buffer: &(impl VulkanObject<Handle = vk::CommandBuffer> + ?Sized),
+) -> Vec<&'static str> {
+    STATE
+        .lock()
+        .command_buffers
+        .get(&command_buffer.handle().as_raw())
+        .map(|state| state.commands.clone())
+        .unwrap_or_default()
+}
+
+const API_VERSION: u32 = vk::make_api_version(0, 1, 0, 0);
+const PHYSICAL_DEVICE: u64 = 1;
+const QUEUE_FAMILIES: [(vk::QueueFlags, u32); 2] = [
+    (
+        vk::QueueFlags::from_raw(
+            vk::QueueFlags::GRAPHICS.as_raw()
+                | vk::QueueFlags::COMPUTE.as_raw()
+                | vk::QueueFlags::TRANSFER.as_raw(),
+        ),
+        4,
+    ),
+    (
+        vk::QueueFlags::from_raw(
+            vk::QueueFlags::COMPUTE.as_raw() | vk::QueueFlags::TRANSFER.as_raw(),
+        ),
+        4,
+    ),
+];
+const HEAP_SIZE: vk::DeviceSize = 2 << 30;
+const BUFFER_ALIGNMENT: vk::DeviceSize = 256;
+const IMAGE_ALIGNMENT: vk::DeviceSize = 4096;
+// The largest block size of any format, used to make image memory requirements large enough.
+const MAX_TEXEL_SIZE: vk::DeviceSize = 32;
+
+static NEXT_HANDLE: AtomicU64 = AtomicU64::new(PHYSICAL_DEVICE + 1);
+static STATE: Lazy<Mutex<State>> = Lazy::new(Default::default);
+
+/// The objects that need to be remembered between calls, keyed by their raw handle.
+#[derive(Default)]
+struct State {
+    memories: HashMap<u64, Memory>,
+    buffers: HashMap<u64, vk::DeviceSize>,
+    images: HashMap<u64, ImageState>,
+    fences: HashMap<u64, bool>,
+    events: HashMap<u64, bool>,
+    command_pools: HashMap<u64, Vec<u64>>,
+    command_buffers: HashMap<u64, CommandBufferState>,
+}
+
+struct Memory {
+    size: vk::DeviceSize,
+    // Only allocated for host-visible memory types.
+    data: Option<Box<[u8]>>,
+}
+
+struct ImageState {
+    extent: vk::Extent3D,
+    size: vk::DeviceSize,
+}
+
+#[derive(Default)]
+struct CommandBufferState {
+    commands: Vec<&'static str>,
+}
+
+fn new_handle<T: Handle>() -> T {
+    T::from_raw(NEXT_HANDLE.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Writes `items` to a Vulkan output array, following the two-call idiom.
+unsafe fn write_array<T: Copy>(items: &[T], p_count: *mut u32, p_items: *mut T) -> vk::Result {
+    if p_items.is_null() {
+        *p_count = items.len() as u32;
+        return vk::Result::SUCCESS;
+    }
+
+    let count = (*p_count as usize).min(items.len());
+    ptr::copy_nonoverlapping(items.as_ptr(), p_items, count);
+    *p_count = count as u32;
+
+    if count < items.len() {
+        vk::Result::INCOMPLETE
+    } else {
+        vk::Result::SUCCESS
+    }
+}
+
+macro_rules! proc_addrs {
+    ($name:expr, { $($vk_name:literal => $f:ident: $pfn:ident,)* }) => {
+        match $name {
+            $(
+                $vk_name => Some(transmute::<vk::$pfn, unsafe extern "system" fn()>($f)),
+            )*
+            _ => None,
+        }
+    };
+}
+
+unsafe extern "system" fn get_instance_proc_addr(
+    instance: vk::Instance,
+    p_name: *const c_char,
+) -> vk::PFN_vkVoidFunction {
+    let name = CStr::from_ptr(p_name).to_bytes();
+
+    let global = proc_addrs!(name, {
+        b"vkGetInstanceProcAddr" => get_instance_proc_addr: PFN_vkGetInstanceProcAddr,
+        b"vkCreateInstance" => create_instance: PFN_vkCreateInstance,
+        b"vkEnumerateInstanceExtensionProperties" =>
+            enumerate_instance_extension_properties: PFN_vkEnumerateInstanceExtensionProperties,
+        b"vkEnumerateInstanceLayerProperties" =>
+            enumerate_instance_layer_properties: PFN_vkEnumerateInstanceLayerProperties,
+    });
+
+    if global.is_some() || instance == vk::Instance::null() {
+        return global;
+    }
+
+    proc_addrs!(name, {
+        b"vkDestroyInstance" => destroy_instance: PFN_vkDestroyInstance,
+        b"vkEnumeratePhysicalDevices" => enumerate_physical_devices: PFN_vkEnumeratePhysicalDevices,
+        b"vkGetPhysicalDeviceFeatures" =>
+            get_physical_device_features: PFN_vkGetPhysicalDeviceFeatures,
+        b"vkGetPhysicalDeviceProperties" =>
+            get_physical_device_properties: PFN_vkGetPhysicalDeviceProperties,
+        b"vkGetPhysicalDeviceMemoryProperties" =>
+            get_physical_device_memory_properties: PFN_vkGetPhysicalDeviceMemoryProperties,
+        b"vkGetPhysicalDeviceQueueFamilyProperties" =>
+            get_physical_device_queue_family_properties:
+                PFN_vkGetPhysicalDeviceQueueFamilyProperties,
+        b"vkGetPhysicalDeviceFormatProperties" =>
+            get_physical_device_format_properties: PFN_vkGetPhysicalDeviceFormatProperties,
+        b"vkGetPhysicalDeviceImageFormatProperties" =>
+            get_physical_device_image_format_properties:
+                PFN_vkGetPhysicalDeviceImageFormatProperties,
+        b"vkGetPhysicalDeviceSparseImageFormatProperties" =>
+            get_physical_device_sparse_image_format_properties:
+                PFN_vkGetPhysicalDeviceSparseImageFormatProperties,
+        b"vkEnumerateDeviceExtensionProperties" =>
+            enumerate_device_extension_properties: PFN_vkEnumerateDeviceExtensionProperties,
+        b"vkEnumerateDeviceLayerProperties" =>
+            enumerate_device_layer_properties: PFN_vkEnumerateDeviceLayerProperties,
+        b"vkCreateDevice" => create_device: PFN_vkCreateDevice,
+        b"vkGetDeviceProcAddr" => get_device_proc_addr: PFN_vkGetDeviceProcAddr,
+    })
+    .or_else(|| device_proc_addr(name))
+}
+
+unsafe extern "system" fn get_device_proc_addr(
+    _device: vk::Device,
+    p_name: *const c_char,
+) -> vk::PFN_vkVoidFunction {
+    device_proc_addr(CStr::from_ptr(p_name).to_bytes())
+}
+
+unsafe fn device_proc_addr(name: &[u8]) -> vk::PFN_vkVoidFunction {
+    proc_addrs!(name, {
+        b"vkGetDeviceProcAddr" => get_device_proc_addr: PFN_vkGetDeviceProcAddr,
+        b"vkDestroyDevice" => destroy_device: PFN_vkDestroyDevice,
+        b"vkGetDeviceQueue" => get_device_queue: PFN_vkGetDeviceQueue,
+        b"vkQueueSubmit" => queue_submit: PFN_vkQueueSubmit,
+        b"vkQueueWaitIdle" => queue_wait_idle: PFN_vkQueueWaitIdle,
+        b"vkDeviceWaitIdle" => device_wait_idle: PFN_vkDeviceWaitIdle,
+        b"vkAllocateMemory" => allocate_memory: PFN_vkAllocateMemory,
+        b"vkFreeMemory" => free_memory: PFN_vkFreeMemory,
+        b"vkMapMemory" => map_memory: PFN_vkMapMemory,
+        b"vkUnmapMemory" => unmap_memory: PFN_vkUnmapMemory,
+        b"vkFlushMappedMemoryRanges" => flush_mapped_memory_ranges: PFN_vkFlushMappedMemoryRanges,
+        b"vkInvalidateMappedMemoryRanges" =>
+            flush_mapped_memory_ranges: PFN_vkInvalidateMappedMemoryRanges,
+        b"vkGetDeviceMemoryCommitment" =>
+            get_device_memory_commitment: PFN_vkGetDeviceMemoryCommitment,
+        b"vkBindBufferMemory" => bind_buffer_memory: PFN_vkBindBufferMemory,
+        b"vkBindImageMemory" => bind_image_memory: PFN_vkBindImageMemory,
+        b"vkGetBufferMemoryRequirements" =>
+            get_buffer_memory_requirements: PFN_vkGetBufferMemoryRequirements,
+        b"vkGetImageMemoryRequirements" =>
+            get_image_memory_requirements: PFN_vkGetImageMemoryRequirements,
+        b"vkGetImageSubresourceLayout" =>
+            get_image_subresource_layout: PFN_vkGetImageSubresourceLayout,
+        b"vkCreateFence" => create_fence: PFN_vkCreateFence,
+        b"vkDestroyFence" => destroy_fence: PFN_vkDestroyFence,
+        b"vkResetFences" => reset_fences: PFN_vkResetFences,
+        b"vkGetFenceStatus" => get_fence_status: PFN_vkGetFenceStatus,
+        b"vkWaitForFences" => wait_for_fences: PFN_vkWaitForFences,
+        b"vkCreateSemaphore" => create_semaphore: PFN_vkCreateSemaphore,
+        b"vkDestroySemaphore" => destroy_semaphore: PFN_vkDestroySemaphore,
+        b"vkCreateEvent" => create_event: PFN_vkCreateEvent,
+        b"vkDestroyEvent" => destroy_event: PFN_vkDestroyEvent,
+        b"vkGetEventStatus" => get_event_status: PFN_vkGetEventStatus,
+        b"vkSetEvent" => set_event: PFN_vkSetEvent,
+        b"vkResetEvent" => reset_event: PFN_vkResetEvent,
+        b"vkCreateQueryPool" => create_query_pool: PFN_vkCreateQueryPool,
+        b"vkDestroyQueryPool" => destroy_query_pool: PFN_vkDestroyQueryPool,
+        b"vkGetQueryPoolResults" => get_query_pool_results: PFN_vkGetQueryPoolResults,
+        b"vkCreateBuffer" => create_buffer: PFN_vkCreateBuffer,
+        b"vkDestroyBuffer" => destroy_buffer: PFN_vkDestroyBuffer,
+        b"vkCreateBufferView" => create_buffer_view: PFN_vkCreateBufferView,
+        b"vkDestroyBufferView" => destroy_buffer_view: PFN_vkDestroyBufferView,
+        b"vkCreateImage" => create_image: PFN_vkCreateImage,
+        b"vkDestroyImage" => destroy_image: PFN_vkDestroyImage,
+        b"vkCreateImageView" => create_image_view: PFN_vkCreateImageView,
+        b"vkDestroyImageView" => destroy_image_view: PFN_vkDestroyImageView,
+        b"vkCreateShaderModule" => create_shader_module: PFN_vkCreateShaderModule,
+        b"vkDestroyShaderModule" => destroy_shader_module: PFN_vkDestroyShaderModule,
+        b"vkCreatePipelineCache" => create_pipeline_cache: PFN_vkCreatePipelineCache,
+        b"vkDestroyPipelineCache" => destroy_pipeline_cache: PFN_vkDestroyPipelineCache,
+        b"vkGetPipelineCacheData" => get_pipeline_cache_data: PFN_vkGetPipelineCacheData,
+        b"vkMergePipelineCaches" => merge_pipeline_caches: PFN_vkMergePipelineCaches,
+        b"vkCreateGraphicsPipelines" => create_graphics_pipelines: PFN_vkCreateGraphicsPipelines,
+        b"vkCreateComputePipelines" => create_compute_pipelines: PFN_vkCreateComputePipelines,
+        b"vkDestroyPipeline" => destroy_pipeline: PFN_vkDestroyPipeline,
+        b"vkCreatePipelineLayout" => create_pipeline_layout: PFN_vkCreatePipelineLayout,
+        b"vkDestroyPipelineLayout" => destroy_pipeline_layout: PFN_vkDestroyPipelineLayout,
+        b"vkCreateSampler" => create_sampler: PFN_vkCreateSampler,
+        b"vkDestroySampler" => destroy_sampler: PFN_vkDestroySampler,
+        b"vkCreateDescriptorSetLayout" =>
+            create_descriptor_set_layout: PFN_vkCreateDescriptorSetLayout,
+        b"vkDestroyDescriptorSetLayout" =>
+            destroy_descriptor_set_layout: PFN_vkDestroyDescriptorSetLayout,
+        b"vkCreateDescriptorPool" => create_descriptor_pool: PFN_vkCreateDescriptorPool,
+        b"vkDestroyDescriptorPool" => destroy_descriptor_pool: PFN_vkDestroyDescriptorPool,
+        b"vkResetDescriptorPool" => reset_descriptor_pool: PFN_vkResetDescriptorPool,
+        b"vkAllocateDescriptorSets" => allocate_descriptor_sets: PFN_vkAllocateDescriptorSets,
+        b"vkFreeDescriptorSets" => free_descriptor_sets: PFN_vkFreeDescriptorSets,
+        b"vkUpdateDescriptorSets" => update_descriptor_sets: PFN_vkUpdateDescriptorSets,
+        b"vkCreateFramebuffer" => create_framebuffer: PFN_vkCreateFramebuffer,
+        b"vkDestroyFramebuffer" => destroy_framebuffer: PFN_vkDestroyFramebuffer,
+        b"vkCreateRenderPass" => create_render_pass: PFN_vkCreateRenderPass,
+        b"vkDestroyRenderPass" => destroy_render_pass: PFN_vkDestroyRenderPass,
+        b"vkGetRenderAreaGranularity" =>
+            get_render_area_granularity: PFN_vkGetRenderAreaGranularity,
+        b"vkCreateCommandPool" => create_command_pool: PFN_vkCreateCommandPool,
+        b"vkDestroyCommandPool" => destroy_command_pool: PFN_vkDestroyCommandPool,
+        b"vkResetCommandPool" => reset_command_pool: PFN_vkResetCommandPool,
+        b"vkAllocateCommandBuffers" => allocate_command_buffers: PFN_vkAllocateCommandBuffers,
+        b"vkFreeCommandBuffers" => free_command_buffers: PFN_vkFreeCommandBuffers,
+        b"vkBeginCommandBuffer" => begin_command_buffer: PFN_vkBeginCommandBuffer,
+        b"vkEndCommandBuffer" => end_command_buffer: PFN_vkEndCommandBuffer,
+        b"vkResetCommandBuffer" => reset_command_buffer: PFN_vkResetCommandBuffer,
+    })
+    .or_else(|| command_proc_addr(name))
+}
+
+/*
+    Instance and physical device
+*/
+
+unsafe extern "system" fn create_instance(
+    p_create_info: *const vk::InstanceCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_instance: *mut vk::Instance,
+) -> vk::Result {
+    let create_info = &*p_create_info;
+
+    if create_info.enabled_layer_count != 0 {
+        return vk::Result::ERROR_LAYER_NOT_PRESENT;
+    }
+
+    if create_info.enabled_extension_count != 0 {
+        return vk::Result::ERROR_EXTENSION_NOT_PRESENT;
+    }
+
+    *p_instance = new_handle();
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_instance(
+    _instance: vk::Instance,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+}
+
+unsafe extern "system" fn enumerate_instance_extension_properties(
+    _p_layer_name: *const c_char,
+    p_property_count: *mut u32,
+    p_properties: *mut vk::ExtensionProperties,
+) -> vk::Result {
+    write_array(&[], p_property_count, p_properties)
+}
+
+unsafe extern "system" fn enumerate_instance_layer_properties(
+    p_property_count: *mut u32,
+    p_properties: *mut vk::LayerProperties,
+) -> vk::Result {
+    write_array(&[], p_property_count, p_properties)
+}
+
+unsafe extern "system" fn enumerate_physical_devices(
+    _instance: vk::Instance,
+    p_physical_device_count: *mut u32,
+    p_physical_devices: *mut vk::PhysicalDevice,
+) -> vk::Result {
+    write_array(
+        &[vk::PhysicalDevice::from_raw(PHYSICAL_DEVICE)],
+        p_physical_device_count,
+        p_physical_devices,
+    )
+}
+
+unsafe extern "system" fn get_physical_device_features(
+    _physical_device: vk::PhysicalDevice,
+    p_features: *mut vk::PhysicalDeviceFeatures,
+) {
+    const BOOL_COUNT: usize = size_of::<vk::PhysicalDeviceFeatures>() / size_of::<vk::Bool32>();
+
+    let mut features =
+        transmute::<[vk::Bool32; BOOL_COUNT], vk::PhysicalDeviceFeatures>([vk::TRUE; BOOL_COUNT]);
+    features.sparse_binding = vk::FALSE;
+    features.sparse_residency_buffer = vk::FALSE;
+    features.sparse_residency_image2_d = vk::FALSE;
+    features.sparse_residency_image3_d = vk::FALSE;
+    features.sparse_residency2_samples = vk::FALSE;
+    features.sparse_residency4_samples = vk::FALSE;
+    features.sparse_residency8_samples = vk::FALSE;
+    features.sparse_residency16_samples = vk::FALSE;
+    features.sparse_residency_aliased = vk::FALSE;
+
+    *p_features = features;
+}
+
+unsafe extern "system" fn get_physical_device_properties(
+    _physical_device: vk::PhysicalDevice,
+    p_properties: *mut vk::PhysicalDeviceProperties,
+) {
+    let sample_counts = vk::SampleCountFlags::TYPE_1
+        | vk::SampleCountFlags::TYPE_2
+        | vk::SampleCountFlags::TYPE_4
+        | vk::SampleCountFlags::TYPE_8;
+
+    let limits = vk::PhysicalDeviceLimits {
+        max_image_dimension1_d: 16384,
+        max_image_dimension2_d: 16384,
+        max_image_dimension3_d: 2048,
+        max_image_dimension_cube: 16384,
+        max_image_array_layers: 2048,
+        max_texel_buffer_elements: 1 << 27,
+        max_uniform_buffer_range: 1 << 16,
+        max_storage_buffer_range: 1 << 30,
+        max_push_constants_size: 256,
+        max_memory_allocation_count: 4096,
+        max_sampler_allocation_count: 4000,
+        buffer_image_granularity: 1,
+        sparse_address_space_size: 0,
+        max_bound_descriptor_sets: 8,
+        max_per_stage_descriptor_samplers: 1 << 20,
+        max_per_stage_descriptor_uniform_buffers: 1 << 20,
+        max_per_stage_descriptor_storage_buffers: 1 << 20,
+        max_per_stage_descriptor_sampled_images: 1 << 20,
+        max_per_stage_descriptor_storage_images: 1 << 20,
+        max_per_stage_descriptor_input_attachments: 1 << 20,
+        max_per_stage_resources: 1 << 22,
+        max_descriptor_set_samplers: 1 << 20,
+        max_descriptor_set_uniform_buffers: 1 << 20,
+        max_descriptor_set_uniform_buffers_dynamic: 16,
+        max_descriptor_set_storage_buffers: 1 << 20,
+        max_descriptor_set_storage_buffers_dynamic: 16,
+        max_descriptor_set_sampled_images: 1 << 20,
+        max_descriptor_set_storage_images: 1 << 20,
+        max_descriptor_set_input_attachments: 1 << 20,
+        max_vertex_input_attributes: 32,
+        max_vertex_input_bindings: 32,
+        max_vertex_input_attribute_offset: 2047,
+        max_vertex_input_binding_stride: 2048,
+        max_vertex_output_components: 128,
+        max_tessellation_generation_level: 64,
+        max_tessellation_patch_size: 32,
+        max_tessellation_control_per_vertex_input_components: 128,
+        max_tessellation_control_per_vertex_output_components: 128,
+        max_tessellation_control_per_patch_output_components: 120,
+        max_tessellation_control_total_output_components: 4096,
+        max_tessellation_evaluation_input_components: 128,
+        max_tessellation_evaluation_output_components: 128,
+        max_geometry_shader_invocations: 32,
+        max_geometry_input_components: 64,
+        max_geometry_output_components: 128,
+        max_geometry_output_vertices: 256,
+        max_geometry_total_output_components: 1024,
+        max_fragment_input_components: 128,
+        max_fragment_output_attachments: 8,
+        max_fragment_dual_src_attachments: 1,
+        max_fragment_combined_output_resources: 1 << 20,
+        max_compute_shared_memory_size: 1 << 15,
+        max_compute_work_group_count: [65535; 3],
+        max_compute_work_group_invocations: 1024,
+        max_compute_work_group_size: [1024, 1024, 64],
+        sub_pixel_precision_bits: 8,
+        sub_texel_precision_bits: 8,
+        mipmap_precision_bits: 8,
+        max_draw_indexed_index_value: u32::MAX,
+        max_draw_indirect_count: u32::MAX,
+        max_sampler_lod_bias: 16.0,
+        max_sampler_anisotropy: 16.0,
+        max_viewports: 16,
+        max_viewport_dimensions: [16384; 2],
+        viewport_bounds_range: [-32768.0, 32767.0],
+        viewport_sub_pixel_bits: 8,
+        min_memory_map_alignment: 64,
+        min_texel_buffer_offset_alignment: 16,
+        min_uniform_buffer_offset_alignment: 256,
+        min_storage_buffer_offset_alignment: 16,
+        min_texel_offset: -8,
+        max_texel_offset: 7,
+        min_texel_gather_offset: -32,
+        max_texel_gather_offset: 31,
+        min_interpolation_offset: -0.5,
+        max_interpolation_offset: 0.4375,
+        sub_pixel_interpolation_offset_bits: 4,
+        max_framebuffer_width: 16384,
+        max_framebuffer_height: 16384,
+        max_framebuffer_layers: 2048,
+        framebuffer_color_sample_counts: sample_counts,
+        framebuffer_depth_sample_counts: sample_counts,
+        framebuffer_stencil_sample_counts: sample_counts,
+        framebuffer_no_attachments_sample_counts: sample_counts,
+        max_color_attachments: 8,
+        sampled_image_color_sample_counts: sample_counts,
+        sampled_image_integer_sample_counts: sample_counts,
+        sampled_image_depth_sample_counts: sample_counts,
+        sampled_image_stencil_sample_counts: sample_counts,
+        storage_image_sample_counts: sample_counts,
+        max_sample_mask_words: 1,
+        timestamp_compute_and_graphics: vk::TRUE,
+        timestamp_period: 1.0,
+        max_clip_distances: 8,
+        max_cull_distances: 8,
+        max_combined_clip_and_cull_distances: 8,
+        discrete_queue_priorities: 2,
+        point_size_range: [1.0, 64.0],
+        line_width_range: [1.0, 8.0],
+        point_size_granularity: 0.125,
+        line_width_granularity: 0.125,
+        strict_lines: vk::FALSE,
+        standard_sample_locations: vk::TRUE,
+        optimal_buffer_copy_offset_alignment: 1,
+        optimal_buffer_copy_row_pitch_alignment: 1,
+        non_coherent_atom_size: 64,
+    };
+
+    let mut properties = vk::PhysicalDeviceProperties {
+        api_version: API_VERSION,
+        driver_version: 1,
+        vendor_id: 0,
+        device_id: 0,
+        device_type: vk::PhysicalDeviceType::CPU,
+        device_name: [0; vk::MAX_PHYSICAL_DEVICE_NAME_SIZE],
+        pipeline_cache_uuid: [0; vk::UUID_SIZE],
+        limits,
+        sparse_properties: vk::PhysicalDeviceSparseProperties::default(),
+    };
+
+    for (dst, &src) in properties
+        .device_name
+        .iter_mut()
+        .zip(b"vulkano null device".iter())
+    {
+        *dst = src as c_char;
+    }
+
+    *p_properties = properties;
+}
+
+unsafe extern "system" fn get_physical_device_memory_properties(
+    _physical_device: vk::PhysicalDevice,
+    p_memory_properties: *mut vk::PhysicalDeviceMemoryProperties,
+) {
+    let mut properties = vk::PhysicalDeviceMemoryProperties {
+        memory_type_count: 3,
+        memory_heap_count: 2,
+        ..Default::default()
+    };
+    properties.memory_types[0] = vk::MemoryType {
+        property_flags: vk::MemoryPropertyFlags::DEVICE_LOCAL,
+        heap_index: 0,
+    };
+    properties.memory_types[1] = vk::MemoryType {
+        property_flags: vk::MemoryPropertyFlags::HOST_VISIBLE
+            | vk::MemoryPropertyFlags::HOST_COHERENT,
+        heap_index: 1,
+    };
+    properties.memory_types[2] = vk::MemoryType {
+        property_flags: vk::MemoryPropertyFlags::HOST_VISIBLE
+            | vk::MemoryPropertyFlags::HOST_COHERENT
+            | vk::MemoryPropertyFlags::HOST_CACHED,
+        heap_index: 1,
+    };
+    properties.memory_heaps[0] = vk::MemoryHeap {
+        size: HEAP_SIZE,
+        flags: vk::MemoryHeapFlags::DEVICE_LOCAL,
+    };
+    properties.memory_heaps[1] = vk::MemoryHeap {
+        size: HEAP_SIZE,
+        flags: vk::MemoryHeapFlags::empty(),
+    };
+
+    *p_memory_properties = properties;
+}
+
+unsafe extern "system" fn get_physical_device_queue_family_properties(
+    _physical_device: vk::PhysicalDevice,
+    p_queue_family_property_count: *mut u32,
+    p_queue_family_properties: *mut vk::QueueFamilyProperties,
+) {
+    let properties = QUEUE_FAMILIES.map(|(queue_flags, queue_count)| vk::QueueFamilyProperties {
+        queue_flags,
+        queue_count,
+        timestamp_valid_bits: 64,
+        min_image_transfer_granularity: vk::Extent3D {
+            width: 1,
+            height: 1,
+            depth: 1,
+        },
+    });
+
+    let _ = write_array(
+        &properties,
+        p_queue_family_property_count,
+        p_queue_family_properties,
+    );
+}
+
+fn format_properties(format: vk::Format) -> vk::FormatProperties {
+    let format = match Format::try_from(format) {
+        Ok(format) if format.ycbcr_chroma_sampling().is_none() && format.planes().is_empty() => {
+            format
+        }
+        _ => return vk::FormatProperties::default(),
+    };
+
+    let transfer = vk::FormatFeatureFlags::TRANSFER_SRC | vk::FormatFeatureFlags::TRANSFER_DST;
+
+    if format.compression().is_some() {
+        vk::FormatProperties {
+            optimal_tiling_features: transfer
+                | vk::FormatFeatureFlags::SAMPLED_IMAGE
+                | vk::FormatFeatureFlags::SAMPLED_IMAGE_FILTER_LINEAR
+                | vk::FormatFeatureFlags::BLIT_SRC,
+            ..Default::default()
+        }
+    } else if format.is_depth_stencil() {
+        vk::FormatProperties {
+            optimal_tiling_features: transfer
+                | vk::FormatFeatureFlags::SAMPLED_IMAGE
+                | vk::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT
+                | vk::FormatFeatureFlags::BLIT_SRC,
+            ..Default::default()
+        }
+    } else {
+        let image_features = transfer
+            | vk::FormatFeatureFlags::SAMPLED_IMAGE
+            | vk::FormatFeatureFlags::SAMPLED_IMAGE_FILTER_LINEAR
+            | vk::FormatFeatureFlags::STORAGE_IMAGE
+            | vk::FormatFeatureFlags::COLOR_ATTACHMENT
+            | vk::FormatFeatureFlags::COLOR_ATTACHMENT_BLEND
+            | vk::FormatFeatureFlags::BLIT_SRC
+            | vk::FormatFeatureFlags::BLIT_DST;
+        // Like most real devices, don't support texel buffers with 64-bit components.
+        let buffer_features = if format.components().contains(&64) {
+            vk::FormatFeatureFlags::VERTEX_BUFFER
+        } else {
+            vk::FormatFeatureFlags::VERTEX_BUFFER
+                | vk::FormatFeatureFlags::UNIFORM_TEXEL_BUFFER
+                | vk::FormatFeatureFlags::STORAGE_TEXEL_BUFFER
+        };
+        let atomic_features = if matches!(format, Format::R32_UINT | Format::R32_SINT) {
+            vk::FormatFeatureFlags::STORAGE_IMAGE_ATOMIC
+                | vk::FormatFeatureFlags::STORAGE_TEXEL_BUFFER_ATOMIC
+        } else {
+            vk::FormatFeatureFlags::empty()
+        };
+
+        vk::FormatProperties {
+            linear_tiling_features: image_features | atomic_features,
+            optimal_tiling_features: image_features | atomic_features,
+            buffer_features: buffer_features | atomic_features,
+        }
+    }
+}
+
+unsafe extern "system" fn get_physical_device_format_properties(
+    _physical_device: vk::PhysicalDevice,
+    format: vk::Format,
+    p_format_properties: *mut vk::FormatProperties,
+) {
+    *p_format_properties = format_properties(format);
+}
+
+unsafe extern "system" fn get_physical_device_image_format_properties(
+    _physical_device: vk::PhysicalDevice,
+    format: vk::Format,
+    ty: vk::ImageType,
+    tiling: vk::ImageTiling,
+    usage: vk::ImageUsageFlags,
+    _flags: vk::ImageCreateFlags,
+    p_image_format_properties: *mut vk::ImageFormatProperties,
+) -> vk::Result {
+    let format_properties = format_properties(format);
+    let features = match tiling {
+        vk::ImageTiling::LINEAR => format_properties.linear_tiling_features,
+        _ => format_properties.optimal_tiling_features,
+    };
+
+    if features.is_empty() {
+        return vk::Result::ERROR_FORMAT_NOT_SUPPORTED;
+    }
+
+    // Each usage needs the corresponding format feature.
+    let attachment_features =
+        vk::FormatFeatureFlags::COLOR_ATTACHMENT | vk::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT;
+
+    for (usage_flag, required_features) in [
+        (
+            vk::ImageUsageFlags::TRANSFER_SRC,
+            vk::FormatFeatureFlags::TRANSFER_SRC,
+        ),
+        (
+            vk::ImageUsageFlags::TRANSFER_DST,
+            vk::FormatFeatureFlags::TRANSFER_DST,
+        ),
+        (
+            vk::ImageUsageFlags::SAMPLED,
+            vk::FormatFeatureFlags::SAMPLED_IMAGE,
+        ),
+        (
+            vk::ImageUsageFlags::STORAGE,
+            vk::FormatFeatureFlags::STORAGE_IMAGE,
+        ),
+        (
+            vk::ImageUsageFlags::COLOR_ATTACHMENT,
+            vk::FormatFeatureFlags::COLOR_ATTACHMENT,
+        ),
+        (
+            vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT,
+            vk::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT,
+        ),
+        (vk::ImageUsageFlags::INPUT_ATTACHMENT, attachment_features),
+        (
+            vk::ImageUsageFlags::TRANSIENT_ATTACHMENT,
+            attachment_features,
+        ),
+    ] {
+        if usage.contains(usage_flag) && !features.intersects(required_features) {
+            return vk::Result::ERROR_FORMAT_NOT_SUPPORTED;
+        }
+    }
+
+    let max_extent = match ty {
+        vk::ImageType::TYPE_1D => vk::Extent3D {
+            width: 16384,
+            height: 1,
+            depth: 1,
+        },
+        vk::ImageType::TYPE_2D => vk::Extent3D {
+            width: 16384,
+            height: 16384,
+            depth: 1,
+        },
+        _ => vk::Extent3D {
+            width: 2048,
+            height: 2048,
+            depth: 2048,
+        },
+    };
+
+    *p_image_format_properties = vk::ImageFormatProperties {
+        max_extent,
+        max_mip_levels: 15,
+        max_array_layers: if ty == vk::ImageType::TYPE_3D {
+            1
+        } else {
+            2048
+        },
+        sample_counts: if tiling == vk::ImageTiling::OPTIMAL && ty == vk::ImageType::TYPE_2D {
+            vk::SampleCountFlags::TYPE_1
+                | vk::SampleCountFlags::TYPE_2
+                | vk::SampleCountFlags::TYPE_4
+                | vk::SampleCountFlags::TYPE_8
+        } else {
+            vk::SampleCountFlags::TYPE_1
+        },
+        max_resource_size: HEAP_SIZE,
+    };
+
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn get_physical_device_sparse_image_format_properties(
+    _physical_device: vk::PhysicalDevice,
+    _format: vk::Format,
+    _ty: vk::ImageType,
+    _samples: vk::SampleCountFlags,
+    _usage: vk::ImageUsageFlags,
+    _tiling: vk::ImageTiling,
+    p_property_count: *mut u32,
+    p_properties: *mut vk::SparseImageFormatProperties,
+) {
+    let _ = write_array(&[], p_property_count, p_properties);
+}
+
+unsafe extern "system" fn enumerate_device_extension_properties(
+    _physical_device: vk::PhysicalDevice,
+    _p_layer_name: *const c_char,
+    p_property_count: *mut u32,
+    p_properties: *mut vk::ExtensionProperties,
+) -> vk::Result {
+    write_array(&[], p_property_count, p_properties)
+}
+
+unsafe extern "system" fn enumerate_device_layer_properties(
+    _physical_device: vk::PhysicalDevice,
+    p_property_count: *mut u32,
+    p_properties: *mut vk::LayerProperties,
+) -> vk::Result {
+    write_array(&[], p_property_count, p_properties)
+}
+
+/*
+    Device and queues
+*/
+
+unsafe extern "system" fn create_device(
+    _physical_device: vk::PhysicalDevice,
+    p_create_info: *const vk::DeviceCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_device: *mut vk::Device,
+) -> vk::Result {
+    if (*p_create_info).enabled_extension_count != 0 {
+        return vk::Result::ERROR_EXTENSION_NOT_PRESENT;
+    }
+
+    *p_device = new_handle();
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_device(
+    _device: vk::Device,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+}
+
+unsafe extern "system" fn get_device_queue(
+    _device: vk::Device,
+    _queue_family_index: u32,
+    _queue_index: u32,
+    p_queue: *mut vk::Queue,
+) {
+    *p_queue = new_handle();
+}
+
+unsafe extern "system" fn queue_submit(
+    _queue: vk::Queue,
+    _submit_count: u32,
+    _p_submits: *const vk::SubmitInfo,
+    fence: vk::Fence,
+) -> vk::Result {
+    // The submitted work is never executed, so it is complete as soon as it is submitted.
+    if fence != vk::Fence::null() {
+        STATE.lock().fences.insert(fence.as_raw(), true);
+    }
+
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn queue_wait_idle(_queue: vk::Queue) -> vk::Result {
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn device_wait_idle(_device: vk::Device) -> vk::Result {
+    vk::Result::SUCCESS
+}
+
+/*
+    Memory
+*/
+
+unsafe extern "system" fn allocate_memory(
+    _device: vk::Device,
+    p_allocate_info: *const vk::MemoryAllocateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_memory: *mut vk::DeviceMemory,
+) -> vk::Result {
+    let allocate_info = &*p_allocate_info;
+    let size = allocate_info.allocation_size;
+    let data = if allocate_info.memory_type_index == 0 {
+        None
+    } else {
+        match usize::try_from(size) {
+            Ok(size) => Some(vec![0; size].into_boxed_slice()),
+            Err(_) => return vk::Result::ERROR_OUT_OF_HOST_MEMORY,
+        }
+    };
+
+    let memory: vk::DeviceMemory = new_handle();
+    STATE
+        .lock()
+        .memories
+        .insert(memory.as_raw(), Memory { size, data });
+    *p_memory = memory;
+
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn free_memory(
+    _device: vk::Device,
+    memory: vk::DeviceMemory,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+    STATE.lock().memories.remove(&memory.as_raw());
+}
+
+unsafe extern "system" fn map_memory(
+    _device: vk::Device,
+    memory: vk::DeviceMemory,
+    offset: vk::DeviceSize,
+    _size: vk::DeviceSize,
+    _flags: vk::MemoryMapFlags,
+    pp_data: *mut *mut c_void,
+) -> vk::Result {
+    let mut state = STATE.lock();
+
+    match state
+        .memories
+        .get_mut(&memory.as_raw())
+        .and_then(|memory| memory.data.as_mut())
+    {
+        Some(data) => {
+            *pp_data = data.as_mut_ptr().add(offset as usize).cast();
+            vk::Result::SUCCESS
+        }
+        None => vk::Result::ERROR_MEMORY_MAP_FAILED,
+    }
+}
+
+unsafe extern "system" fn unmap_memory(_device: vk::Device, _memory: vk::DeviceMemory) {}
+
+unsafe extern "system" fn flush_mapped_memory_ranges(
+    _device: vk::Device,
+    _memory_range_count: u32,
+    _p_memory_ranges: *const vk::MappedMemoryRange,
+) -> vk::Result {
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn get_device_memory_commitment(
+    _device: vk::Device,
+    memory: vk::DeviceMemory,
+    p_committed_memory_in_bytes: *mut vk::DeviceSize,
+) {
+    *p_committed_memory_in_bytes = STATE
+        .lock()
+        .memories
+        .get(&memory.as_raw())
+        .map_or(0, |memory| memory.size);
+}
+
+unsafe extern "system" fn bind_buffer_memory(
+    _device: vk::Device,
+    _buffer: vk::Buffer,
+    _memory: vk::DeviceMemory,
+    _memory_offset: vk::DeviceSize,
+) -> vk::Result {
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn bind_image_memory(
+    _device: vk::Device,
+    _image: vk::Image,
+    _memory: vk::DeviceMemory,
+    _memory_offset: vk::DeviceSize,
+) -> vk::Result {
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn get_buffer_memory_requirements(
+    _device: vk::Device,
+    buffer: vk::Buffer,
+    p_memory_requirements: *mut vk::MemoryRequirements,
+) {
+    let size = STATE
+        .lock()
+        .buffers
+        .get(&buffer.as_raw())
+        .copied()
+        .unwrap_or(0);
+
+    *p_memory_requirements = vk::MemoryRequirements {
+        size: size.next_multiple_of(BUFFER_ALIGNMENT),
+        alignment: BUFFER_ALIGNMENT,
+        memory_type_bits: 0b111,
+    };
+}
+
+unsafe extern "system" fn get_image_memory_requirements(
+    _device: vk::Device,
+    image: vk::Image,
+    p_memory_requirements: *mut vk::MemoryRequirements,
+) {
+    let size = STATE
+        .lock()
+        .images
+        .get(&image.as_raw())
+        .map_or(0, |image| image.size);
+
+    *p_memory_requirements = vk::MemoryRequirements {
+        size: size.next_multiple_of(IMAGE_ALIGNMENT),
+        alignment: IMAGE_ALIGNMENT,
+        memory_type_bits: 0b111,
+    };
+}
+
+unsafe extern "system" fn get_image_subresource_layout(
+    _device: vk::Device,
+    image: vk::Image,
+    _p_subresource: *const vk::ImageSubresource,
+    p_layout: *mut vk::SubresourceLayout,
+) {
+    let state = STATE.lock();
+    let (extent, size) = state
+        .images
+        .get(&image.as_raw())
+        .map_or((vk::Extent3D::default(), 0), |image| {
+            (image.extent, image.size)
+        });
+    let row_pitch = extent.width as vk::DeviceSize * MAX_TEXEL_SIZE;
+    let array_pitch = row_pitch * extent.height as vk::DeviceSize;
+
+    *p_layout = vk::SubresourceLayout {
+        offset: 0,
+        size,
+        row_pitch,
+        array_pitch,
+        depth_pitch: array_pitch,
+    };
+}
+
+/*
+    Synchronization
+*/
+
+unsafe extern "system" fn create_fence(
+    _device: vk::Device,
+    p_create_info: *const vk::FenceCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_fence: *mut vk::Fence,
+) -> vk::Result {
+    let signaled = (*p_create_info)
+        .flags
+        .contains(vk::FenceCreateFlags::SIGNALED);
+    let fence: vk::Fence = new_handle();
+    STATE.lock().fences.insert(fence.as_raw(), signaled);
+    *p_fence = fence;
+
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_fence(
+    _device: vk::Device,
+    fence: vk::Fence,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+    STATE.lock().fences.remove(&fence.as_raw());
+}
+
+unsafe extern "system" fn reset_fences(
+    _device: vk::Device,
+    fence_count: u32,
+    p_fences: *const vk::Fence,
+) -> vk::Result {
+    let mut state = STATE.lock();
+
+    for fence in slice::from_raw_parts(p_fences, fence_count as usize) {
+        state.fences.insert(fence.as_raw(), false);
+    }
+
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn get_fence_status(_device: vk::Device, fence: vk::Fence) -> vk::Result {
+    if STATE.lock().fences.get(&fence.as_raw()) == Some(&true) {
+        vk::Result::SUCCESS
+    } else {
+        vk::Result::NOT_READY
+    }
+}
+
+unsafe extern "system" fn wait_for_fences(
+    _device: vk::Device,
+    fence_count: u32,
+    p_fences: *const vk::Fence,
+    wait_all: vk::Bool32,
+    _timeout: u64,
+) -> vk::Result {
+    let state = STATE.lock();
+    let mut signaled = slice::from_raw_parts(p_fences, fence_count as usize)
+        .iter()
+        .map(|fence| state.fences.get(&fence.as_raw()) == Some(&true));
+
+    // Nothing is executing, so a fence that is not signaled now will never become signaled.
+    let done = if wait_all == vk::TRUE {
+        signaled.all(|signaled| signaled)
+    } else {
+        signaled.any(|signaled| signaled)
+    };
+
+    if done {
+        vk::Result::SUCCESS
+    } else {
+        vk::Result::TIMEOUT
+    }
+}
+
+unsafe extern "system" fn create_semaphore(
+    _device: vk::Device,
+    _p_create_info: *const vk::SemaphoreCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_semaphore: *mut vk::Semaphore,
+) -> vk::Result {
+    *p_semaphore = new_handle();
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_semaphore(
+    _device: vk::Device,
+    _semaphore: vk::Semaphore,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+}
+
+unsafe extern "system" fn create_event(
+    _device: vk::Device,
+    _p_create_info: *const vk::EventCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_event: *mut vk::Event,
+) -> vk::Result {
+    let event: vk::Event = new_handle();
+    STATE.lock().events.insert(event.as_raw(), false);
+    *p_event = event;
+
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_event(
+    _device: vk::Device,
+    event: vk::Event,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+    STATE.lock().events.remove(&event.as_raw());
+}
+
+unsafe extern "system" fn get_event_status(_device: vk::Device, event: vk::Event) -> vk::Result {
+    if STATE.lock().events.get(&event.as_raw()) == Some(&true) {
+        vk::Result::EVENT_SET
+    } else {
+        vk::Result::EVENT_RESET
+    }
+}
+
+unsafe extern "system" fn set_event(_device: vk::Device, event: vk::Event) -> vk::Result {
+    STATE.lock().events.insert(event.as_raw(), true);
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn reset_event(_device: vk::Device, event: vk::Event) -> vk::Result {
+    STATE.lock().events.insert(event.as_raw(), false);
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn create_query_pool(
+    _device: vk::Device,
+    _p_create_info: *const vk::QueryPoolCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_query_pool: *mut vk::QueryPool,
+) -> vk::Result {
+    *p_query_pool = new_handle();
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_query_pool(
+    _device: vk::Device,
+    _query_pool: vk::QueryPool,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+}
+
+unsafe extern "system" fn get_query_pool_results(
+    _device: vk::Device,
+    _query_pool: vk::QueryPool,
+    _first_query: u32,
+    _query_count: u32,
+    data_size: usize,
+    p_data: *mut c_void,
+    _stride: vk::DeviceSize,
+    _flags: vk::QueryResultFlags,
+) -> vk::Result {
+    ptr::write_bytes(p_data.cast::<u8>(), 0, data_size);
+    vk::Result::SUCCESS
+}
+
+/*
+    Resources
+*/
+
+unsafe extern "system" fn create_buffer(
+    _device: vk::Device,
+    p_create_info: *const vk::BufferCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_buffer: *mut vk::Buffer,
+) -> vk::Result {
+    let buffer: vk::Buffer = new_handle();
+    STATE
+        .lock()
+        .buffers
+        .insert(buffer.as_raw(), (*p_create_info).size);
+    *p_buffer = buffer;
+
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_buffer(
+    _device: vk::Device,
+    buffer: vk::Buffer,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+    STATE.lock().buffers.remove(&buffer.as_raw());
+}
+
+unsafe extern "system" fn create_buffer_view(
+    _device: vk::Device,
+    _p_create_info: *const vk::BufferViewCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_view: *mut vk::BufferView,
+) -> vk::Result {
+    *p_view = new_handle();
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_buffer_view(
+    _device: vk::Device,
+    _buffer_view: vk::BufferView,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+}
+
+unsafe extern "system" fn create_image(
+    _device: vk::Device,
+    p_create_info: *const vk::ImageCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_image: *mut vk::Image,
+) -> vk::Result {
+    let create_info = &*p_create_info;
+    let extent = create_info.extent;
+    let texels_per_layer: vk::DeviceSize = (0..create_info.mip_levels)
+        .map(|level| {
+            (extent.width >> level).max(1) as vk::DeviceSize
+                * (extent.height >> level).max(1) as vk::DeviceSize
+                * (extent.depth >> level).max(1) as vk::DeviceSize
+        })
+        .sum();
+    let size = texels_per_layer
+        * create_info.array_layers as vk::DeviceSize
+        * create_info.samples.as_raw() as vk::DeviceSize
+        * MAX_TEXEL_SIZE;
+
+    let image: vk::Image = new_handle();
+    STATE
+        .lock()
+        .images
+        .insert(image.as_raw(), ImageState { extent, size });
+    *p_image = image;
+
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_image(
+    _device: vk::Device,
+    image: vk::Image,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+    STATE.lock().images.remove(&image.as_raw());
+}
+
+unsafe extern "system" fn create_image_view(
+    _device: vk::Device,
+    _p_create_info: *const vk::ImageViewCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_view: *mut vk::ImageView,
+) -> vk::Result {
+    *p_view = new_handle();
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_image_view(
+    _device: vk::Device,
+    _image_view: vk::ImageView,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+}
+
+unsafe extern "system" fn create_sampler(
+    _device: vk::Device,
+    _p_create_info: *const vk::SamplerCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_sampler: *mut vk::Sampler,
+) -> vk::Result {
+    *p_sampler = new_handle();
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_sampler(
+    _device: vk::Device,
+    _sampler: vk::Sampler,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+}
+
+/*
+    Pipelines and descriptor sets
+*/
+
+unsafe extern "system" fn create_shader_module(
+    _device: vk::Device,
+    _p_create_info: *const vk::ShaderModuleCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_shader_module: *mut vk::ShaderModule,
+) -> vk::Result {
+    *p_shader_module = new_handle();
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_shader_module(
+    _device: vk::Device,
+    _shader_module: vk::ShaderModule,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+}
+
+unsafe extern "system" fn create_pipeline_cache(
+    _device: vk::Device,
+    _p_create_info: *const vk::PipelineCacheCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_pipeline_cache: *mut vk::PipelineCache,
+) -> vk::Result {
+    *p_pipeline_cache = new_handle();
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_pipeline_cache(
+    _device: vk::Device,
+    _pipeline_cache: vk::PipelineCache,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+}
+
+unsafe extern "system" fn get_pipeline_cache_data(
+    _device: vk::Device,
+    _pipeline_cache: vk::PipelineCache,
+    p_data_size: *mut usize,
+    _p_data: *mut c_void,
+) -> vk::Result {
+    *p_data_size = 0;
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn merge_pipeline_caches(
+    _device: vk::Device,
+    _dst_cache: vk::PipelineCache,
+    _src_cache_count: u32,
+    _p_src_caches: *const vk::PipelineCache,
+) -> vk::Result {
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn create_graphics_pipelines(
+    _device: vk::Device,
+    _pipeline_cache: vk::PipelineCache,
+    create_info_count: u32,
+    _p_create_infos: *const vk::GraphicsPipelineCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_pipelines: *mut vk::Pipeline,
+) -> vk::Result {
+    for pipeline in slice::from_raw_parts_mut(p_pipelines, create_info_count as usize) {
+        *pipeline = new_handle();
+    }
+
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn create_compute_pipelines(
+    _device: vk::Device,
+    _pipeline_cache: vk::PipelineCache,
+    create_info_count: u32,
+    _p_create_infos: *const vk::ComputePipelineCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_pipelines: *mut vk::Pipeline,
+) -> vk::Result {
+    for pipeline in slice::from_raw_parts_mut(p_pipelines, create_info_count as usize) {
+        *pipeline = new_handle();
+    }
+
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_pipeline(
+    _device: vk::Device,
+    _pipeline: vk::Pipeline,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+}
+
+unsafe extern "system" fn create_pipeline_layout(
+    _device: vk::Device,
+    _p_create_info: *const vk::PipelineLayoutCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_pipeline_layout: *mut vk::PipelineLayout,
+) -> vk::Result {
+    *p_pipeline_layout = new_handle();
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_pipeline_layout(
+    _device: vk::Device,
+    _pipeline_layout: vk::PipelineLayout,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+}
+
+unsafe extern "system" fn create_descriptor_set_layout(
+    _device: vk::Device,
+    _p_create_info: *const vk::DescriptorSetLayoutCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_set_layout: *mut vk::DescriptorSetLayout,
+) -> vk::Result {
+    *p_set_layout = new_handle();
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_descriptor_set_layout(
+    _device: vk::Device,
+    _descriptor_set_layout: vk::DescriptorSetLayout,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+}
+
+unsafe extern "system" fn create_descriptor_pool(
+    _device: vk::Device,
+    _p_create_info: *const vk::DescriptorPoolCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_descriptor_pool: *mut vk::DescriptorPool,
+) -> vk::Result {
+    *p_descriptor_pool = new_handle();
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_descriptor_pool(
+    _device: vk::Device,
+    _descriptor_pool: vk::DescriptorPool,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+}
+
+unsafe extern "system" fn reset_descriptor_pool(
+    _device: vk::Device,
+    _descriptor_pool: vk::DescriptorPool,
+    _flags: vk::DescriptorPoolResetFlags,
+) -> vk::Result {
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn allocate_descriptor_sets(
+    _device: vk::Device,
+    p_allocate_info: *const vk::DescriptorSetAllocateInfo,
+    p_descriptor_sets: *mut vk::DescriptorSet,
+) -> vk::Result {
+    let count = (*p_allocate_info).descriptor_set_count as usize;
+
+    for set in slice::from_raw_parts_mut(p_descriptor_sets, count) {
+        *set = new_handle();
+    }
+
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn free_descriptor_sets(
+    _device: vk::Device,
+    _descriptor_pool: vk::DescriptorPool,
+    _descriptor_set_count: u32,
+    _p_descriptor_sets: *const vk::DescriptorSet,
+) -> vk::Result {
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn update_descriptor_sets(
+    _device: vk::Device,
+    _descriptor_write_count: u32,
+    _p_descriptor_writes: *const vk::WriteDescriptorSet,
+    _descriptor_copy_count: u32,
+    _p_descriptor_copies: *const vk::CopyDescriptorSet,
+) {
+}
+
+/*
+    Render passes
+*/
+
+unsafe extern "system" fn create_framebuffer(
+    _device: vk::Device,
+    _p_create_info: *const vk::FramebufferCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_framebuffer: *mut vk::Framebuffer,
+) -> vk::Result {
+    *p_framebuffer = new_handle();
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_framebuffer(
+    _device: vk::Device,
+    _framebuffer: vk::Framebuffer,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+}
+
+unsafe extern "system" fn create_render_pass(
+    _device: vk::Device,
+    _p_create_info: *const vk::RenderPassCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_render_pass: *mut vk::RenderPass,
+) -> vk::Result {
+    *p_render_pass = new_handle();
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_render_pass(
+    _device: vk::Device,
+    _render_pass: vk::RenderPass,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+}
+
+unsafe extern "system" fn get_render_area_granularity(
+    _device: vk::Device,
+    _render_pass: vk::RenderPass,
+    p_granularity: *mut vk::Extent2D,
+) {
+    *p_granularity = vk::Extent2D {
+        width: 1,
+        height: 1,
+    };
+}
+
+/*
+    Command buffers
+*/
+
+unsafe extern "system" fn create_command_pool(
+    _device: vk::Device,
+    _p_create_info: *const vk::CommandPoolCreateInfo,
+    _p_allocator: *const vk::AllocationCallbacks,
+    p_command_pool: *mut vk::CommandPool,
+) -> vk::Result {
+    let command_pool: vk::CommandPool = new_handle();
+    STATE
+        .lock()
+        .command_pools
+        .insert(command_pool.as_raw(), Vec::new());
+    *p_command_pool = command_pool;
+
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn destroy_command_pool(
+    _device: vk::Device,
+    command_pool: vk::CommandPool,
+    _p_allocator: *const vk::AllocationCallbacks,
+) {
+    let mut state = STATE.lock();
+
+    for command_buffer in state
+        .command_pools
+        .remove(&command_pool.as_raw())
+        .unwrap_or_default()
+    {
+        state.command_buffers.remove(&command_buffer);
+    }
+}
+
+unsafe extern "system" fn reset_command_pool(
+    _device: vk::Device,
+    command_pool: vk::CommandPool,
+    _flags: vk::CommandPoolResetFlags,
+) -> vk::Result {
+    let mut state = STATE.lock();
+    let State {
+        command_pools,
+        command_buffers,
+        ..
+    } = &mut *state;
+
+    for command_buffer in command_pools
+        .get(&command_pool.as_raw())
+        .into_iter()
+        .flatten()
+    {
+        if let Some(command_buffer) = command_buffers.get_mut(command_buffer) {
+            command_buffer.commands.clear();
+        }
+    }
+
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn allocate_command_buffers(
+    _device: vk::Device,
+    p_allocate_info: *const vk::CommandBufferAllocateInfo,
+    p_command_buffers: *mut vk::CommandBuffer,
+) -> vk::Result {
+    let allocate_info = &*p_allocate_info;
+    let mut state = STATE.lock();
+
+    for command_buffer in slice::from_raw_parts_mut(
+        p_command_buffers,
+        allocate_info.command_buffer_count as usize,
+    ) {
+        *command_buffer = new_handle::<vk::CommandBuffer>();
+        state
+            .command_buffers
+            .insert(command_buffer.as_raw(), CommandBufferState::default());
+        state
+            .command_pools
+            .entry(allocate_info.command_pool.as_raw())
+            .or_default()
+            .push(command_buffer.as_raw());
+    }
+
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn free_command_buffers(
+    _device: vk::Device,
+    command_pool: vk::CommandPool,
+    command_buffer_count: u32,
+    p_command_buffers: *const vk::CommandBuffer,
+) {
+    let mut state = STATE.lock();
+    let freed = slice::from_raw_parts(p_command_buffers, command_buffer_count as usize);
+
+    for command_buffer in freed {
+        state.command_buffers.remove(&command_buffer.as_raw());
+    }
+
+    if let Some(command_buffers) = state.command_pools.get_mut(&command_pool.as_raw()) {
+        command_buffers.retain(|&handle| !freed.iter().any(|freed| freed.as_raw() == handle));
+    }
+}
+
+unsafe extern "system" fn begin_command_buffer(
+    command_buffer: vk::CommandBuffer,
+    _p_begin_info: *const vk::CommandBufferBeginInfo,
+) -> vk::Result {
+    if let Some(command_buffer) = STATE
+        .lock()
+        .command_buffers
+        .get_mut(&command_buffer.as_raw())
+    {
+        command_buffer.commands.clear();
+    }
+
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn end_command_buffer(_command_buffer: vk::CommandBuffer) -> vk::Result {
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn reset_command_buffer(
+    command_buffer: vk::CommandBuffer,
+    _flags: vk::CommandBufferResetFlags,
+) -> vk::Result {
+    if let Some(command_buffer) = STATE
+        .lock()
+        .command_buffers
+        .get_mut(&command_buffer.as_raw())
+    {
+        command_buffer.commands.clear();
+    }
+
+    vk::Result::SUCCESS
+}
+
+fn record_command(command_buffer: vk::CommandBuffer, name: &'static str) {
+    if let Some(command_buffer) = STATE
+        .lock()
+        .command_buffers
+        .get_mut(&command_buffer.as_raw())
+    {
+        command_buffer.commands.push(name);
+    }
+}
+
+/// Defines an entry point for each command, which records the name of the command, and
+/// `command_proc_addr` to look them up.
+macro_rules! commands {
+    ($($f:ident: $pfn:ident = $vk_name:literal ($($ty:ty),* $(,)?);)*) => {
+        $(
+            unsafe extern "system" fn $f(command_buffer: vk::CommandBuffer, $(_: $ty),*) {
+                record_command(command_buffer, $vk_name);
+            }
+        )*
+
+        unsafe fn command_proc_addr(name: &[u8]) -> vk::PFN_vkVoidFunction {
+            $(
+                if name == $vk_name.as_bytes() {
+                    return Some(transmute::<vk::$pfn, unsafe extern "system" fn()>($f));
+                }
+            )*
+
+            None
+        }
+    };
+}
+
+commands! {
+    cmd_bind_pipeline: PFN_vkCmdBindPipeline = "vkCmdBindPipeline"(
+        vk::PipelineBindPoint,
+        vk::Pipeline,
+    );
+    cmd_set_viewport: PFN_vkCmdSetViewport = "vkCmdSetViewport"(u32, u32, *const vk::Viewport);
+    cmd_set_scissor: PFN_vkCmdSetScissor = "vkCmdSetScissor"(u32, u32, *const vk::Rect2D);
+    cmd_set_line_width: PFN_vkCmdSetLineWidth = "vkCmdSetLineWidth"(f32);
+    cmd_set_depth_bias: PFN_vkCmdSetDepthBias = "vkCmdSetDepthBias"(f32, f32, f32);
+    cmd_set_blend_constants: PFN_vkCmdSetBlendConstants = "vkCmdSetBlendConstants"(
+        *const [f32; 4],
+    );
+    cmd_set_depth_bounds: PFN_vkCmdSetDepthBounds = "vkCmdSetDepthBounds"(f32, f32);
+    cmd_set_stencil_compare_mask: PFN_vkCmdSetStencilCompareMask = "vkCmdSetStencilCompareMask"(
+        vk::StencilFaceFlags,
+        u32,
+    );
+    cmd_set_stencil_write_mask: PFN_vkCmdSetStencilWriteMask = "vkCmdSetStencilWriteMask"(
+        vk::StencilFaceFlags,
+        u32,
+    );
+    cmd_set_stencil_reference: PFN_vkCmdSetStencilReference = "vkCmdSetStencilReference"(
+        vk::StencilFaceFlags,
+        u32,
+    );
+    cmd_bind_descriptor_sets: PFN_vkCmdBindDescriptorSets = "vkCmdBindDescriptorSets"(
+        vk::PipelineBindPoint,
+        vk::PipelineLayout,
+        u32,
+        u32,
+        *const vk::DescriptorSet,
+        u32,
+        *const u32,
+    );
+    cmd_bind_index_buffer: PFN_vkCmdBindIndexBuffer = "vkCmdBindIndexBuffer"(
+        vk::Buffer,
+        vk::DeviceSize,
+        vk::IndexType,
+    );
+    cmd_bind_vertex_buffers: PFN_vkCmdBindVertexBuffers = "vkCmdBindVertexBuffers"(
+        u32,
+        u32,
+        *const vk::Buffer,
+        *const vk::DeviceSize,
+    );
+    cmd_draw: PFN_vkCmdDraw = "vkCmdDraw"(u32, u32, u32, u32);
+    cmd_draw_indexed: PFN_vkCmdDrawIndexed = "vkCmdDrawIndexed"(u32, u32, u32, i32, u32);
+    cmd_draw_indirect: PFN_vkCmdDrawIndirect = "vkCmdDrawIndirect"(
+        vk::Buffer,
+        vk::DeviceSize,
+        u32,
+        u32,
+    );
+    cmd_draw_indexed_indirect: PFN_vkCmdDrawIndexedIndirect = "vkCmdDrawIndexedIndirect"(
+        vk::Buffer,
+        vk::DeviceSize,
+        u32,
+        u32,
+    );
+    cmd_dispatch: PFN_vkCmdDispatch = "vkCmdDispatch"(u32, u32, u32);
+    cmd_dispatch_indirect: PFN_vkCmdDispatchIndirect = "vkCmdDispatchIndirect"(
+        vk::Buffer,
+        vk::DeviceSize,
+    );
+    cmd_copy_buffer: PFN_vkCmdCopyBuffer = "vkCmdCopyBuffer"(
+        vk::Buffer,
+        vk::Buffer,
+        u32,
+        *const vk::BufferCopy,
+    );
+    cmd_copy_image: PFN_vkCmdCopyImage = "vkCmdCopyImage"(
+        vk::Image,
+        vk::ImageLayout,
+        vk::Image,
+        vk::ImageLayout,
+        u32,
+        *const vk::ImageCopy,
+    );
+    cmd_blit_image: PFN_vkCmdBlitImage = "vkCmdBlitImage"(
+        vk::Image,
+        vk::ImageLayout,
+        vk::Image,
+        vk::ImageLayout,
+        u32,
+        *const vk::ImageBlit,
+        vk::Filter,
+    );
+    cmd_copy_buffer_to_image: PFN_vkCmdCopyBufferToImage = "vkCmdCopyBufferToImage"(
+        vk::Buffer,
+        vk::Image,
+        vk::ImageLayout,
+        u32,
+        *const vk::BufferImageCopy,
+    );
+    cmd_copy_image_to_buffer: PFN_vkCmdCopyImageToBuffer = "vkCmdCopyImageToBuffer"(
+        vk::Image,
+        vk::ImageLayout,
+        vk::Buffer,
+        u32,
+        *const vk::BufferImageCopy,
+    );
+    cmd_update_buffer: PFN_vkCmdUpdateBuffer = "vkCmdUpdateBuffer"(
+        vk::Buffer,
+        vk::DeviceSize,
+        vk::DeviceSize,
+        *const c_void,
+    );
+    cmd_fill_buffer: PFN_vkCmdFillBuffer = "vkCmdFillBuffer"(
+        vk::Buffer,
+        vk::DeviceSize,
+        vk::DeviceSize,
+        u32,
+    );
+    cmd_clear_color_image: PFN_vkCmdClearColorImage = "vkCmdClearColorImage"(
+        vk::Image,
+        vk::ImageLayout,
+        *const vk::ClearColorValue,
+        u32,
+        *const vk::ImageSubresourceRange,
+    );
+    cmd_clear_depth_stencil_image: PFN_vkCmdClearDepthStencilImage =
+        "vkCmdClearDepthStencilImage"(
+            vk::Image,
+            vk::ImageLayout,
+            *const vk::ClearDepthStencilValue,
+            u32,
+            *const vk::ImageSubresourceRange,
+        );
+    cmd_clear_attachments: PFN_vkCmdClearAttachments = "vkCmdClearAttachments"(
+        u32,
+        *const vk::ClearAttachment,
+        u32,
+        *const vk::ClearRect,
+    );
+    cmd_resolve_image: PFN_vkCmdResolveImage = "vkCmdResolveImage"(
+        vk::Image,
+        vk::ImageLayout,
+        vk::Image,
+        vk::ImageLayout,
+        u32,
+        *const vk::ImageResolve,
+    );
+    cmd_set_event: PFN_vkCmdSetEvent = "vkCmdSetEvent"(vk::Event, vk::PipelineStageFlags);
+    cmd_reset_event: PFN_vkCmdResetEvent = "vkCmdResetEvent"(vk::Event, vk::PipelineStageFlags);
+    cmd_wait_events: PFN_vkCmdWaitEvents = "vkCmdWaitEvents"(
+        u32,
+        *const vk::Event,
+        vk::PipelineStageFlags,
+        vk::PipelineStageFlags,
+        u32,
+        *const vk::MemoryBarrier,
+        u32,
+        *const vk::BufferMemoryBarrier,
+        u32,
+        *const vk::ImageMemoryBarrier,
+    );
+    cmd_pipeline_barrier: PFN_vkCmdPipelineBarrier = "vkCmdPipelineBarrier"(
+        vk::PipelineStageFlags,
+        vk::PipelineStageFlags,
+        vk::DependencyFlags,
+        u32,
+        *const vk::MemoryBarrier,
+        u32,
+        *const vk::BufferMemoryBarrier,
+        u32,
+        *const vk::ImageMemoryBarrier,
+    );
+    cmd_begin_query: PFN_vkCmdBeginQuery = "vkCmdBeginQuery"(
+        vk::QueryPool,
+        u32,
+        vk::QueryControlFlags,
+    );
+    cmd_end_query: PFN_vkCmdEndQuery = "vkCmdEndQuery"(vk::QueryPool, u32);
+    cmd_reset_query_pool: PFN_vkCmdResetQueryPool = "vkCmdResetQueryPool"(vk::QueryPool, u32, u32);
+    cmd_write_timestamp: PFN_vkCmdWriteTimestamp = "vkCmdWriteTimestamp"(
+        vk::PipelineStageFlags,
+        vk::QueryPool,
+        u32,
+    );
+    cmd_copy_query_pool_results: PFN_vkCmdCopyQueryPoolResults = "vkCmdCopyQueryPoolResults"(
+        vk::QueryPool,
+        u32,
+        u32,
+        vk::Buffer,
+        vk::DeviceSize,
+        vk::DeviceSize,
+        vk::QueryResultFlags,
+    );
+    cmd_push_constants: PFN_vkCmdPushConstants = "vkCmdPushConstants"(
+        vk::PipelineLayout,
+        vk::ShaderStageFlags,
+        u32,
+        u32,
+        *const c_void,
+    );
+    cmd_begin_render_pass: PFN_vkCmdBeginRenderPass = "vkCmdBeginRenderPass"(
+        *const vk::RenderPassBeginInfo,
+        vk::SubpassContents,
+    );
+    cmd_next_subpass: PFN_vkCmdNextSubpass = "vkCmdNextSubpass"(vk::SubpassContents);
+    cmd_end_render_pass: PFN_vkCmdEndRenderPass = "vkCmdEndRenderPass"();
+    cmd_execute_commands: PFN_vkCmdExecuteCommands = "vkCmdExecuteCommands"(
+        u32,
+        *const vk::CommandBuffer,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{recorded_commands, NullLoader};
+    use crate::{
+        buffer::{Buffer, BufferCreateInfo, BufferUsage},
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder,
+            CommandBufferUsage, CopyBufferInfo,
+        },
+        device::{Device, DeviceCreateInfo, QueueCreateInfo},
+        instance::{Instance, InstanceCreateInfo},
+        library::VulkanLibrary,
+        memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
+        sync::{now, GpuFuture},
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn record_and_submit() {
+        let library = VulkanLibrary::with_loader(NullLoader::new()).unwrap();
+        let instance = Instance::new(library, InstanceCreateInfo::default()).unwrap();
+        let physical_device = instance
+            .enumerate_physical_devices()
+            .unwrap()
+            .next()
+            .unwrap();
+        let (device, mut queues) = Device::new(
+            physical_device,
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo::default()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let queue = queues.next().unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let command_buffer_allocator =
+            StandardCommandBufferAllocator::new(device.clone(), Default::default());
+
+        let source = Buffer::from_iter(
+            memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_SRC,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            [1u32, 2, 3, 4],
+        )
+        .unwrap();
+        assert_eq!(&*source.read().unwrap(), &[1, 2, 3, 4]);
+
+        let destination = Buffer::new_slice::<u32>(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+            4,
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &command_buffer_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // Validation is still performed.
+        assert!(builder
+            .copy_buffer(CopyBufferInfo::buffers(destination.clone(), source.clone()))
+            .is_err());
+
+        builder
+            .copy_buffer(CopyBufferInfo::buffers(source, destination.clone()))
+            .unwrap()
+            .fill_buffer(destination, 0)
+            .unwrap();
+        let command_buffer = builder.build().unwrap();
+
+        assert_eq!(
+            recorded_commands(&*command_buffer),
+            [
+                "vkCmdPipelineBarrier",
+                "vkCmdCopyBuffer",
+                "vkCmdPipelineBarrier",
+                "vkCmdFillBuffer",
+            ],
+        );
+
+        now(device.clone())
+            .then_execute(queue, command_buffer)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        assert_eq!(device.statistics().command_buffers_in_flight, 0);
+    }
+}
@@ -21,8 +21,23 @@ macro_rules! vulkan_bitflags {
     } => {
         $(#[doc = $ty_doc])*
         #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize))]
         pub struct $ty($repr);
 
+        // Deserialized manually, so that unknown bits are rejected like they are masked out when
+        // converting from the raw Vulkan type.
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $ty {
+            #[inline]
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                crate::macros::deserialize_flags(deserializer, stringify!($ty), Self::all_raw())
+                    .map(Self)
+            }
+        }
+
         impl $ty {
             $(
                 $(#[doc = $flag_doc])*
@@ -249,8 +264,23 @@ macro_rules! vulkan_bitflags {
     } => {
         $(#[doc = $ty_doc])*
         #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize))]
         pub struct $ty($repr);
 
+        // Deserialized manually, so that unknown bits are rejected like they are masked out when
+        // converting from the raw Vulkan type.
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $ty {
+            #[inline]
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                crate::macros::deserialize_flags(deserializer, stringify!($ty), Self::all_raw())
+                    .map(Self)
+            }
+        }
+
         impl $ty {
             $(
                 $(#[doc = $flag_doc])*
@@ -585,6 +615,7 @@ macro_rules! vulkan_enum {
     } => {
         $(#[doc = $ty_doc])*
         #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         #[repr($repr)]
         pub enum $ty {
             $(
@@ -650,6 +681,7 @@ macro_rules! vulkan_enum {
     } => {
         $(#[doc = $ty_doc])*
         #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         #[non_exhaustive]
         #[repr($repr)]
         pub enum $ty {
@@ -1005,6 +1037,24 @@ macro_rules! impl_id_counter {
     };
 }
 
+/// Emits a log record with the `vulkano::<category>` target, if the `log` feature is enabled.
+/// Otherwise the arguments are type-checked but never evaluated.
+macro_rules! log_event {
+    ($level:ident, $category:literal, $($arg:tt)+) => {
+        #[cfg(feature = "log")]
+        {
+            ::log::$level!(target: concat!("vulkano::", $category), $($arg)+);
+        }
+
+        #[cfg(not(feature = "log"))]
+        {
+            if false {
+                let _ = format_args!($($arg)+);
+            }
+        }
+    };
+}
+
 // TODO: Replace with the `?` operator once its constness is stabilized.
 macro_rules! try_opt {
     ($e:expr) => {
@@ -1016,4 +1066,78 @@ macro_rules! try_opt {
     };
 }
 
-pub(crate) use {impl_id_counter, try_opt, vulkan_bitflags, vulkan_bitflags_enum, vulkan_enum};
+/// Deserializes the raw value of a flags type named `name` in the same way as the derived
+/// implementation would for a tuple struct, but returns an error if any bits outside of `all`
+/// are set.
+#[cfg(feature = "serde")]
+pub(crate) fn deserialize_flags<'de, D, R>(
+    deserializer: D,
+    name: &'static str,
+    all: R,
+) -> Result<R, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    R: serde::Deserialize<'de> + Copy + PartialEq + std::ops::BitAnd<Output = R>,
+{
+    use serde::de::{Deserializer, Error, SeqAccess, Visitor};
+    use std::{fmt::Formatter, marker::PhantomData};
+
+    struct FlagsVisitor<R>(&'static str, PhantomData<R>);
+
+    impl<'de, R> Visitor<'de> for FlagsVisitor<R>
+    where
+        R: serde::Deserialize<'de>,
+    {
+        type Value = R;
+
+        fn expecting(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(f, "tuple struct {}", self.0)
+        }
+
+        fn visit_newtype_struct<D>(self, deserializer: D) -> Result<R, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            R::deserialize(deserializer)
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<R, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            seq.next_element()?
+                .ok_or_else(|| A::Error::invalid_length(0, &self))
+        }
+    }
+
+    let raw =
+        deserializer.deserialize_newtype_struct(name, FlagsVisitor::<R>(name, PhantomData))?;
+
+    if raw & all != raw {
+        return Err(D::Error::custom(format_args!(
+            "the value contains bits that are not valid for `{}`",
+            name,
+        )));
+    }
+
+    Ok(raw)
+}
+
+pub(crate) use {
+    impl_id_counter, log_event, try_opt, vulkan_bitflags, vulkan_bitflags_enum, vulkan_enum,
+};
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use crate::buffer::BufferUsage;
+
+    #[test]
+    fn deserialize_flags() {
+        let usage = BufferUsage::TRANSFER_SRC | BufferUsage::VERTEX_BUFFER;
+        let json = serde_json::to_string(&usage).unwrap();
+        assert_eq!(serde_json::from_str::<BufferUsage>(&json).unwrap(), usage);
+
+        // Bits that don't belong to any flag are rejected.
+        assert!(serde_json::from_str::<BufferUsage>(&u32::MAX.to_string()).is_err());
+    }
+}
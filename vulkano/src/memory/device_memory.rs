@@ -11,7 +11,7 @@ use super::{DedicatedAllocation, DedicatedTo, DeviceAlignment};
 use crate::{
     device::{Device, DeviceOwned},
     instance::InstanceOwnedDebugWrapper,
-    macros::{impl_id_counter, vulkan_bitflags, vulkan_bitflags_enum},
+    macros::{impl_id_counter, log_event, vulkan_bitflags, vulkan_bitflags_enum},
     memory::{is_aligned, MemoryPropertyFlags},
     DeviceSize, Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version,
     VulkanError, VulkanObject,
@@ -275,7 +275,18 @@ impl DeviceMemory {
             .fetch_update(Ordering::Acquire, Ordering::Relaxed, move |count| {
                 (count < max_allocations).then_some(count + 1)
             })
-            .map_err(|_| VulkanError::TooManyObjects)?;
+            .map_err(|_| {
+                log_event!(
+                    warn,
+                    "memory",
+                    "failed to allocate {} bytes from memory type {}: the \
+                    `max_memory_allocation_count` limit of {} has been reached",
+                    allocation_size,
+                    memory_type_index,
+                    max_allocations,
+                );
+                VulkanError::TooManyObjects
+            })?;
 
         let handle = {
             let fns = device.fns();
@@ -289,6 +300,14 @@ impl DeviceMemory {
             .result()
             .map_err(|e| {
                 device.allocation_count.fetch_sub(1, Ordering::Release);
+                log_event!(
+                    warn,
+                    "memory",
+                    "failed to allocate {} bytes from memory type {}: {:?}",
+                    allocation_size,
+                    memory_type_index,
+                    e,
+                );
                 VulkanError::from(e)
             })?;
 
@@ -302,6 +321,11 @@ impl DeviceMemory {
             .property_flags
             .intersects(MemoryPropertyFlags::HOST_COHERENT);
 
+        device.counters.memory_allocated(
+            Self::heap_index(&device, memory_type_index),
+            allocation_size,
+        );
+
         Ok(DeviceMemory {
             handle,
             device: InstanceOwnedDebugWrapper(device),
@@ -348,6 +372,11 @@ impl DeviceMemory {
             .property_flags
             .intersects(MemoryPropertyFlags::HOST_COHERENT);
 
+        device.counters.memory_allocated(
+            Self::heap_index(&device, memory_type_index),
+            allocation_size,
+        );
+
         DeviceMemory {
             handle,
             device: InstanceOwnedDebugWrapper(device),
@@ -366,6 +395,11 @@ impl DeviceMemory {
         }
     }
 
+    fn heap_index(device: &Device, memory_type_index: u32) -> u32 {
+        device.physical_device().memory_properties().memory_types[memory_type_index as usize]
+            .heap_index
+    }
+
     /// Returns the index of the memory type that this memory was allocated from.
     #[inline]
     pub fn memory_type_index(&self) -> u32 {
@@ -855,6 +889,11 @@ impl Drop for DeviceMemory {
             (fns.v1_0.free_memory)(self.device.handle(), self.handle, ptr::null());
             self.device.allocation_count.fetch_sub(1, Ordering::Release);
         }
+
+        self.device.counters.memory_freed(
+            Self::heap_index(&self.device, self.memory_type_index),
+            self.allocation_size,
+        );
     }
 }
 
@@ -2141,7 +2180,10 @@ unsafe impl Sync for MappedDeviceMemory {}
 #[cfg(test)]
 mod tests {
     use super::MemoryAllocateInfo;
-    use crate::memory::{DeviceMemory, MemoryPropertyFlags};
+    use crate::{
+        device::Device,
+        memory::{DeviceMemory, MemoryPropertyFlags},
+    };
 
     #[test]
     fn create() {
@@ -2270,4 +2312,27 @@ mod tests {
         }
         assert_eq!(device.allocation_count(), 1);
     }
+
+    #[test]
+    fn statistics_memory_allocated() {
+        let (device, _) = gfx_dev_and_queue!();
+        let heap_index = device.physical_device().memory_properties().memory_types[0].heap_index;
+        let allocated =
+            |device: &Device| device.statistics().memory_allocated_per_heap[heap_index as usize];
+
+        assert_eq!(allocated(&device), 0);
+        {
+            let _mem = DeviceMemory::allocate(
+                device.clone(),
+                MemoryAllocateInfo {
+                    allocation_size: 256,
+                    memory_type_index: 0,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            assert_eq!(allocated(&device), 256);
+        }
+        assert_eq!(allocated(&device), 0);
+    }
 }
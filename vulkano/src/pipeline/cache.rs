@@ -20,6 +20,8 @@
 //! Once that is done, you can extract the data from the cache and store it. See the documentation
 //! of [`get_data`](crate::pipeline::cache::PipelineCache::get_data) for example of how to store the data
 //! on the disk, and [`new`](crate::pipeline::cache::PipelineCache::new) for how to reload it.
+//! [`with_data`](crate::pipeline::cache::PipelineCache::with_data) can be used instead of `new`
+//! to discard data that was created by a different device or driver version.
 
 use crate::{
     device::{Device, DeviceOwned},
@@ -148,6 +150,69 @@ impl PipelineCache {
         Ok(Self::from_handle(device, handle, create_info))
     }
 
+    /// Builds a new pipeline cache from data that was previously retrieved with
+    /// [`get_data`](PipelineCache::get_data), possibly in an earlier run of the application.
+    ///
+    /// The header of `data` is checked with
+    /// [`is_data_compatible`](PipelineCache::is_data_compatible) first. If it was not created by
+    /// a device with the same vendor, device ID and pipeline cache UUID, for example because the
+    /// driver was updated since, then the data is discarded and an empty cache is created.
+    ///
+    /// # Safety
+    ///
+    /// - The data after the header must be valid data that was previously retrieved using
+    ///   `get_data`.
+    #[inline]
+    pub unsafe fn with_data(
+        device: Arc<Device>,
+        mut data: Vec<u8>,
+    ) -> Result<Arc<PipelineCache>, Validated<VulkanError>> {
+        if !Self::is_data_compatible(&device, &data) {
+            data.clear();
+        }
+
+        Self::new(
+            device,
+            PipelineCacheCreateInfo {
+                initial_data: data,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Returns whether the header of `data`, which was previously retrieved with
+    /// [`get_data`](PipelineCache::get_data), matches `device`.
+    ///
+    /// This checks the header version, and that the vendor ID, device ID and pipeline cache UUID
+    /// in the header are equal to those in the properties of the physical device. Only the header
+    /// is checked, not the data that follows it.
+    pub fn is_data_compatible(device: &Device, data: &[u8]) -> bool {
+        // The header is stored as `VkPipelineCacheHeaderVersionOne`, with each field in
+        // little-endian byte order.
+        const HEADER_SIZE: usize = 16 + ash::vk::UUID_SIZE;
+
+        if data.len() < HEADER_SIZE {
+            return false;
+        }
+
+        let read_u32 =
+            |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+        let header_size = read_u32(0);
+        let header_version = read_u32(4);
+        let vendor_id = read_u32(8);
+        let device_id = read_u32(12);
+        let pipeline_cache_uuid = &data[16..HEADER_SIZE];
+
+        let properties = device.physical_device().properties();
+
+        header_size as usize >= HEADER_SIZE
+            && header_size as usize <= data.len()
+            && header_version == ash::vk::PipelineCacheHeaderVersion::ONE.as_raw() as u32
+            && vendor_id == properties.vendor_id
+            && device_id == properties.device_id
+            && pipeline_cache_uuid == properties.pipeline_cache_uuid
+    }
+
     /// Creates a new `PipelineCache` from a raw object handle.
     ///
     /// # Safety
@@ -400,6 +465,27 @@ mod tests {
         shader::{ShaderModule, ShaderModuleCreateInfo},
     };
 
+    #[test]
+    fn with_data_discards_incompatible() {
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        let properties = device.physical_device().properties();
+        let mut data = Vec::new();
+        data.extend(32u32.to_le_bytes());
+        data.extend(1u32.to_le_bytes());
+        data.extend(properties.vendor_id.to_le_bytes());
+        data.extend(properties.device_id.to_le_bytes());
+        data.extend(properties.pipeline_cache_uuid);
+        assert!(PipelineCache::is_data_compatible(&device, &data));
+
+        data[12] ^= 1;
+        assert!(!PipelineCache::is_data_compatible(&device, &data));
+        assert!(!PipelineCache::is_data_compatible(&device, &data[..16]));
+
+        // The mismatched data is dropped instead of being passed to the implementation.
+        unsafe { PipelineCache::with_data(device, data).unwrap() };
+    }
+
     #[test]
     fn merge_self_forbidden() {
         let (device, _queue) = gfx_dev_and_queue!();
@@ -22,17 +22,30 @@
 //! any descriptor sets and/or push constants that the pipeline needs, and then issuing a `dispatch`
 //! command on the command buffer.
 
-use super::{PipelineCreateFlags, PipelineShaderStageCreateInfo};
+use super::{
+    executable::{
+        self, PipelineExecutableInternalRepresentation, PipelineExecutableProperties,
+        PipelineExecutableStatistic,
+    },
+    PipelineCreateFlags, PipelineCreationFeedback, PipelineShaderStageCreateInfo,
+};
 use crate::{
     device::{Device, DeviceOwned, DeviceOwnedDebugWrapper},
     instance::InstanceOwnedDebugWrapper,
-    macros::impl_id_counter,
+    macros::{impl_id_counter, log_event},
     pipeline::{cache::PipelineCache, layout::PipelineLayout, Pipeline, PipelineBindPoint},
     shader::{spirv::ExecutionModel, DescriptorBindingRequirements, ShaderStage},
     Validated, ValidationError, VulkanError, VulkanObject,
 };
 use ahash::HashMap;
-use std::{ffi::CString, fmt::Debug, mem::MaybeUninit, num::NonZeroU64, ptr, sync::Arc};
+use std::{
+    ffi::CString,
+    fmt::Debug,
+    mem::MaybeUninit,
+    num::NonZeroU64,
+    ptr,
+    sync::{atomic::Ordering, Arc},
+};
 
 /// A pipeline object that describes to the Vulkan implementation how it should perform compute
 /// operations.
@@ -53,6 +66,9 @@ pub struct ComputePipeline {
 
     descriptor_binding_requirements: HashMap<(u32, u32), DescriptorBindingRequirements>,
     num_used_descriptor_sets: u32,
+
+    creation_feedback: Option<PipelineCreationFeedback>,
+    stage_creation_feedback: Option<PipelineCreationFeedback>,
 }
 
 impl ComputePipeline {
@@ -167,7 +183,7 @@ impl ComputePipeline {
             };
         }
 
-        let create_infos_vk = ash::vk::ComputePipelineCreateInfo {
+        let mut create_infos_vk = ash::vk::ComputePipelineCreateInfo {
             flags: flags.into(),
             stage: stage_vk,
             layout: layout.handle(),
@@ -178,6 +194,23 @@ impl ComputePipeline {
             ..Default::default()
         };
 
+        let mut creation_feedback_vk = ash::vk::PipelineCreationFeedback::default();
+        let mut stage_creation_feedback_vk = ash::vk::PipelineCreationFeedback::default();
+        let mut creation_feedback_create_info_vk = None;
+
+        if PipelineCreationFeedback::is_supported(&device) {
+            let info = creation_feedback_create_info_vk.insert(
+                ash::vk::PipelineCreationFeedbackCreateInfo {
+                    p_pipeline_creation_feedback: &mut creation_feedback_vk,
+                    pipeline_stage_creation_feedback_count: 1,
+                    p_pipeline_stage_creation_feedbacks: &mut stage_creation_feedback_vk,
+                    ..Default::default()
+                },
+            );
+            info.p_next = create_infos_vk.p_next;
+            create_infos_vk.p_next = info as *const _ as *const _;
+        }
+
         let handle = {
             let fns = device.fns();
             let mut output = MaybeUninit::uninit();
@@ -190,11 +223,33 @@ impl ComputePipeline {
                 output.as_mut_ptr(),
             )
             .result()
-            .map_err(VulkanError::from)?;
+            .map_err(|err| {
+                log_event!(
+                    warn,
+                    "pipeline",
+                    "failed to create a compute pipeline: {:?}",
+                    err
+                );
+                VulkanError::from(err)
+            })?;
             output.assume_init()
         };
 
-        Ok(Self::from_handle(device, handle, create_info))
+        log_event!(
+            debug,
+            "pipeline",
+            "created compute pipeline 0x{:x} (cached: {})",
+            ash::vk::Handle::as_raw(handle),
+            cache.is_some(),
+        );
+
+        Ok(Self::from_handle_with_creation_feedback(
+            device,
+            handle,
+            create_info,
+            PipelineCreationFeedback::from_vk(&creation_feedback_vk),
+            PipelineCreationFeedback::from_vk(&stage_creation_feedback_vk),
+        ))
     }
 
     /// Creates a new `ComputePipeline` from a raw object handle.
@@ -208,6 +263,16 @@ impl ComputePipeline {
         device: Arc<Device>,
         handle: ash::vk::Pipeline,
         create_info: ComputePipelineCreateInfo,
+    ) -> Arc<ComputePipeline> {
+        Self::from_handle_with_creation_feedback(device, handle, create_info, None, None)
+    }
+
+    unsafe fn from_handle_with_creation_feedback(
+        device: Arc<Device>,
+        handle: ash::vk::Pipeline,
+        create_info: ComputePipelineCreateInfo,
+        creation_feedback: Option<PipelineCreationFeedback>,
+        stage_creation_feedback: Option<PipelineCreationFeedback>,
     ) -> Arc<ComputePipeline> {
         let ComputePipelineCreateInfo {
             flags,
@@ -231,6 +296,8 @@ impl ComputePipeline {
             .map(|x| x + 1)
             .unwrap_or(0);
 
+        device.counters.pipelines.fetch_add(1, Ordering::Relaxed);
+
         Arc::new(ComputePipeline {
             handle,
             device: InstanceOwnedDebugWrapper(device),
@@ -241,6 +308,9 @@ impl ComputePipeline {
 
             descriptor_binding_requirements,
             num_used_descriptor_sets,
+
+            creation_feedback,
+            stage_creation_feedback,
         })
     }
 
@@ -255,6 +325,65 @@ impl ComputePipeline {
     pub fn flags(&self) -> PipelineCreateFlags {
         self.flags
     }
+
+    /// Returns the properties of the executables that the implementation compiled the pipeline
+    /// into.
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device.
+    #[inline]
+    pub fn executable_properties(
+        &self,
+    ) -> Result<Vec<PipelineExecutableProperties>, Validated<VulkanError>> {
+        executable::executable_properties(&self.device, self.handle)
+    }
+
+    /// Returns statistics about the executable with index `executable_index`, as returned by
+    /// [`executable_properties`](Self::executable_properties).
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device, and the pipeline must have been created with
+    /// [`PipelineCreateFlags::CAPTURE_STATISTICS`].
+    #[inline]
+    pub fn executable_statistics(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableStatistic>, Validated<VulkanError>> {
+        executable::executable_statistics(&self.device, self.handle, self.flags, executable_index)
+    }
+
+    /// Returns the internal representations of the executable with index `executable_index`,
+    /// as returned by [`executable_properties`](Self::executable_properties).
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device, and the pipeline must have been created with
+    /// [`PipelineCreateFlags::CAPTURE_INTERNAL_REPRESENTATIONS`].
+    #[inline]
+    pub fn executable_internal_representations(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableInternalRepresentation>, Validated<VulkanError>> {
+        executable::executable_internal_representations(
+            &self.device,
+            self.handle,
+            self.flags,
+            executable_index,
+        )
+    }
+
+    /// Returns the feedback that the implementation provided about the creation of the
+    /// pipeline as a whole, or `None` if it was not provided.
+    #[inline]
+    pub fn creation_feedback(&self) -> Option<&PipelineCreationFeedback> {
+        self.creation_feedback.as_ref()
+    }
+
+    /// Returns the feedback that the implementation provided about the creation of the compute
+    /// shader stage, or `None` if it was not provided.
+    #[inline]
+    pub fn stage_creation_feedback(&self) -> Option<&PipelineCreationFeedback> {
+        self.stage_creation_feedback.as_ref()
+    }
 }
 
 impl Pipeline for ComputePipeline {
@@ -306,6 +435,11 @@ impl Drop for ComputePipeline {
             let fns = self.device.fns();
             (fns.v1_0.destroy_pipeline)(self.device.handle(), self.handle, ptr::null());
         }
+
+        self.device
+            .counters
+            .pipelines
+            .fetch_sub(1, Ordering::Relaxed);
     }
 }
 
@@ -366,6 +500,15 @@ impl ComputePipelineCreateInfo {
                 .set_vuids(&["VUID-VkComputePipelineCreateInfo-flags-parameter"])
         })?;
 
+        if flags.intersects(PipelineCreateFlags::LIBRARY) {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `PipelineCreateFlags::LIBRARY`".into(),
+                vuids: &["VUID-VkComputePipelineCreateInfo-flags-03364"],
+                ..Default::default()
+            }));
+        }
+
         stage
             .validate(device)
             .map_err(|err| err.add_context("stage"))?;
@@ -480,6 +623,7 @@ mod tests {
         // constant to a buffer. The buffer content is then checked for the right value.
 
         let (device, queue) = gfx_dev_and_queue!();
+        skip_if_null_device!(device);
 
         let cs = unsafe {
             /*
@@ -509,6 +653,8 @@ mod tests {
             ];
             let module =
                 ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            assert_eq!(module.specialization_constant_id("VALUE"), Some(83));
+            assert_eq!(module.specialization_constant_id("write"), None);
             module
                 .specialize([(83, 0x12345678i32.into())].into_iter().collect())
                 .unwrap()
@@ -533,6 +679,10 @@ mod tests {
             .unwrap()
         };
 
+        // The `pipeline_executable_info` feature is not enabled.
+        assert!(pipeline.executable_properties().is_err());
+        assert!(pipeline.executable_statistics(0).is_err());
+
         let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
         let data_buffer = Buffer::from_data(
             memory_allocator,
@@ -0,0 +1,455 @@
+// Copyright (c) 2024 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Information about the executables that the implementation compiled a pipeline into.
+//!
+//! When a pipeline is created, the implementation compiles its shader stages into one or more
+//! executables. With the [`pipeline_executable_info`] feature enabled, these executables can be
+//! inspected after the pipeline has been created, which is useful when tuning shaders:
+//!
+//! - [`executable_properties`] lists the executables, and which shader stages each contains.
+//! - [`executable_statistics`] returns implementation-defined statistics about an executable,
+//!   such as the number of registers used or the number of spills. The pipeline must have been
+//!   created with [`PipelineCreateFlags::CAPTURE_STATISTICS`].
+//! - [`executable_internal_representations`] returns implementation-defined internal
+//!   representations of an executable, such as the final machine code. The pipeline must have
+//!   been created with [`PipelineCreateFlags::CAPTURE_INTERNAL_REPRESENTATIONS`].
+//!
+//! [`pipeline_executable_info`]: crate::device::Features::pipeline_executable_info
+//! [`executable_properties`]: crate::pipeline::ComputePipeline::executable_properties
+//! [`executable_statistics`]: crate::pipeline::ComputePipeline::executable_statistics
+//! [`executable_internal_representations`]: crate::pipeline::ComputePipeline::executable_internal_representations
+
+use super::PipelineCreateFlags;
+use crate::{
+    device::Device, shader::ShaderStages, Requires, RequiresAllOf, RequiresOneOf, Validated,
+    ValidationError, VulkanError, VulkanObject,
+};
+use std::{ffi::CStr, os::raw::c_char, ptr};
+
+/// The properties of an executable of a pipeline.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PipelineExecutableProperties {
+    /// The shader stages that were compiled into the executable.
+    pub stages: ShaderStages,
+
+    /// A short human-readable name of the executable.
+    pub name: String,
+
+    /// A human-readable description of the executable.
+    pub description: String,
+
+    /// The subgroup size that the executable was compiled with, or 0 if the executable does not
+    /// contain compute-like shader stages.
+    pub subgroup_size: u32,
+}
+
+impl From<&ash::vk::PipelineExecutablePropertiesKHR> for PipelineExecutableProperties {
+    #[inline]
+    fn from(val: &ash::vk::PipelineExecutablePropertiesKHR) -> Self {
+        Self {
+            stages: val.stages.into(),
+            name: string_from_vk(&val.name),
+            description: string_from_vk(&val.description),
+            subgroup_size: val.subgroup_size,
+        }
+    }
+}
+
+/// A statistic about an executable of a pipeline.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PipelineExecutableStatistic {
+    /// A short human-readable name of the statistic.
+    pub name: String,
+
+    /// A human-readable description of the statistic.
+    pub description: String,
+
+    /// The value of the statistic.
+    pub value: PipelineExecutableStatisticValue,
+}
+
+impl From<&ash::vk::PipelineExecutableStatisticKHR> for PipelineExecutableStatistic {
+    #[inline]
+    fn from(val: &ash::vk::PipelineExecutableStatisticKHR) -> Self {
+        let value = unsafe {
+            match val.format {
+                ash::vk::PipelineExecutableStatisticFormatKHR::BOOL32 => {
+                    PipelineExecutableStatisticValue::Bool(val.value.b32 != ash::vk::FALSE)
+                }
+                ash::vk::PipelineExecutableStatisticFormatKHR::INT64 => {
+                    PipelineExecutableStatisticValue::Int(val.value.i64)
+                }
+                ash::vk::PipelineExecutableStatisticFormatKHR::UINT64 => {
+                    PipelineExecutableStatisticValue::Uint(val.value.u64)
+                }
+                ash::vk::PipelineExecutableStatisticFormatKHR::FLOAT64 => {
+                    PipelineExecutableStatisticValue::Float(val.value.f64)
+                }
+                _ => unreachable!(),
+            }
+        };
+
+        Self {
+            name: string_from_vk(&val.name),
+            description: string_from_vk(&val.description),
+            value,
+        }
+    }
+}
+
+/// The value of a [`PipelineExecutableStatistic`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PipelineExecutableStatisticValue {
+    /// A boolean value.
+    Bool(bool),
+
+    /// A signed integer value.
+    Int(i64),
+
+    /// An unsigned integer value.
+    Uint(u64),
+
+    /// A floating-point value.
+    Float(f64),
+}
+
+/// An internal representation of an executable of a pipeline.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PipelineExecutableInternalRepresentation {
+    /// A short human-readable name of the internal representation.
+    pub name: String,
+
+    /// A human-readable description of the internal representation.
+    pub description: String,
+
+    /// Whether `data` contains text. If `true`, `data` is UTF-8 without a null terminator.
+    /// Otherwise, it contains opaque binary data.
+    pub is_text: bool,
+
+    /// The data of the internal representation.
+    pub data: Vec<u8>,
+}
+
+pub(crate) fn executable_properties(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+) -> Result<Vec<PipelineExecutableProperties>, Validated<VulkanError>> {
+    validate_executable_properties(device)?;
+
+    unsafe { Ok(executable_properties_unchecked(device, pipeline)?) }
+}
+
+fn validate_executable_properties(device: &Device) -> Result<(), Box<ValidationError>> {
+    if !device.enabled_features().pipeline_executable_info {
+        return Err(Box::new(ValidationError {
+            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                "pipeline_executable_info",
+            )])]),
+            vuids: &["VUID-vkGetPipelineExecutablePropertiesKHR-pipelineExecutableInfo-03270"],
+            ..Default::default()
+        }));
+    }
+
+    Ok(())
+}
+
+pub(crate) unsafe fn executable_properties_unchecked(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+) -> Result<Vec<PipelineExecutableProperties>, VulkanError> {
+    let fns = device.fns();
+    let pipeline_info_vk = ash::vk::PipelineInfoKHR {
+        pipeline,
+        ..Default::default()
+    };
+
+    loop {
+        let mut count = 0;
+        (fns.khr_pipeline_executable_properties
+            .get_pipeline_executable_properties_khr)(
+            device.handle(),
+            &pipeline_info_vk,
+            &mut count,
+            ptr::null_mut(),
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        let mut properties_vk =
+            vec![ash::vk::PipelineExecutablePropertiesKHR::default(); count as usize];
+        let result = (fns
+            .khr_pipeline_executable_properties
+            .get_pipeline_executable_properties_khr)(
+            device.handle(),
+            &pipeline_info_vk,
+            &mut count,
+            properties_vk.as_mut_ptr(),
+        );
+
+        match result {
+            ash::vk::Result::SUCCESS => {
+                properties_vk.set_len(count as usize);
+                return Ok(properties_vk.iter().map(Into::into).collect());
+            }
+            ash::vk::Result::INCOMPLETE => (),
+            err => return Err(VulkanError::from(err)),
+        }
+    }
+}
+
+pub(crate) fn executable_statistics(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+    flags: PipelineCreateFlags,
+    executable_index: u32,
+) -> Result<Vec<PipelineExecutableStatistic>, Validated<VulkanError>> {
+    validate_executable_statistics(device, flags)?;
+    validate_executable_index(device, pipeline, executable_index)?;
+
+    unsafe {
+        Ok(executable_statistics_unchecked(
+            device,
+            pipeline,
+            executable_index,
+        )?)
+    }
+}
+
+fn validate_executable_statistics(
+    device: &Device,
+    flags: PipelineCreateFlags,
+) -> Result<(), Box<ValidationError>> {
+    if !device.enabled_features().pipeline_executable_info {
+        return Err(Box::new(ValidationError {
+            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                "pipeline_executable_info",
+            )])]),
+            vuids: &["VUID-vkGetPipelineExecutableStatisticsKHR-pipelineExecutableInfo-03272"],
+            ..Default::default()
+        }));
+    }
+
+    if !flags.intersects(PipelineCreateFlags::CAPTURE_STATISTICS) {
+        return Err(Box::new(ValidationError {
+            context: "pipeline.flags()".into(),
+            problem: "does not contain `PipelineCreateFlags::CAPTURE_STATISTICS`".into(),
+            vuids: &["VUID-vkGetPipelineExecutableStatisticsKHR-pipeline-03274"],
+            ..Default::default()
+        }));
+    }
+
+    Ok(())
+}
+
+pub(crate) unsafe fn executable_statistics_unchecked(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+    executable_index: u32,
+) -> Result<Vec<PipelineExecutableStatistic>, VulkanError> {
+    let fns = device.fns();
+    let executable_info_vk = ash::vk::PipelineExecutableInfoKHR {
+        pipeline,
+        executable_index,
+        ..Default::default()
+    };
+
+    loop {
+        let mut count = 0;
+        (fns.khr_pipeline_executable_properties
+            .get_pipeline_executable_statistics_khr)(
+            device.handle(),
+            &executable_info_vk,
+            &mut count,
+            ptr::null_mut(),
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        let mut statistics_vk =
+            vec![ash::vk::PipelineExecutableStatisticKHR::default(); count as usize];
+        let result = (fns
+            .khr_pipeline_executable_properties
+            .get_pipeline_executable_statistics_khr)(
+            device.handle(),
+            &executable_info_vk,
+            &mut count,
+            statistics_vk.as_mut_ptr(),
+        );
+
+        match result {
+            ash::vk::Result::SUCCESS => {
+                statistics_vk.set_len(count as usize);
+                return Ok(statistics_vk.iter().map(Into::into).collect());
+            }
+            ash::vk::Result::INCOMPLETE => (),
+            err => return Err(VulkanError::from(err)),
+        }
+    }
+}
+
+pub(crate) fn executable_internal_representations(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+    flags: PipelineCreateFlags,
+    executable_index: u32,
+) -> Result<Vec<PipelineExecutableInternalRepresentation>, Validated<VulkanError>> {
+    validate_executable_internal_representations(device, flags)?;
+    validate_executable_index(device, pipeline, executable_index)?;
+
+    unsafe {
+        Ok(executable_internal_representations_unchecked(
+            device,
+            pipeline,
+            executable_index,
+        )?)
+    }
+}
+
+fn validate_executable_internal_representations(
+    device: &Device,
+    flags: PipelineCreateFlags,
+) -> Result<(), Box<ValidationError>> {
+    if !device.enabled_features().pipeline_executable_info {
+        return Err(Box::new(ValidationError {
+            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                "pipeline_executable_info",
+            )])]),
+            vuids: &[
+                "VUID-vkGetPipelineExecutableInternalRepresentationsKHR-pipelineExecutableInfo-03276",
+            ],
+            ..Default::default()
+        }));
+    }
+
+    if !flags.intersects(PipelineCreateFlags::CAPTURE_INTERNAL_REPRESENTATIONS) {
+        return Err(Box::new(ValidationError {
+            context: "pipeline.flags()".into(),
+            problem: "does not contain `PipelineCreateFlags::CAPTURE_INTERNAL_REPRESENTATIONS`"
+                .into(),
+            vuids: &["VUID-vkGetPipelineExecutableInternalRepresentationsKHR-pipeline-03278"],
+            ..Default::default()
+        }));
+    }
+
+    Ok(())
+}
+
+pub(crate) unsafe fn executable_internal_representations_unchecked(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+    executable_index: u32,
+) -> Result<Vec<PipelineExecutableInternalRepresentation>, VulkanError> {
+    let fns = device.fns();
+    let executable_info_vk = ash::vk::PipelineExecutableInfoKHR {
+        pipeline,
+        executable_index,
+        ..Default::default()
+    };
+
+    loop {
+        let mut count = 0;
+        (fns.khr_pipeline_executable_properties
+            .get_pipeline_executable_internal_representations_khr)(
+            device.handle(),
+            &executable_info_vk,
+            &mut count,
+            ptr::null_mut(),
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        // First retrieve the size of the data of each representation.
+        let mut representations_vk =
+            vec![ash::vk::PipelineExecutableInternalRepresentationKHR::default(); count as usize];
+        let result = (fns
+            .khr_pipeline_executable_properties
+            .get_pipeline_executable_internal_representations_khr)(
+            device.handle(),
+            &executable_info_vk,
+            &mut count,
+            representations_vk.as_mut_ptr(),
+        );
+
+        match result {
+            ash::vk::Result::SUCCESS | ash::vk::Result::INCOMPLETE => (),
+            err => return Err(VulkanError::from(err)),
+        }
+
+        // Then retrieve the data itself.
+        representations_vk.truncate(count as usize);
+        let mut data: Vec<Vec<u8>> = representations_vk
+            .iter()
+            .map(|representation_vk| vec![0; representation_vk.data_size])
+            .collect();
+
+        for (representation_vk, data) in representations_vk.iter_mut().zip(&mut data) {
+            representation_vk.p_data = data.as_mut_ptr() as *mut _;
+        }
+
+        let result = (fns
+            .khr_pipeline_executable_properties
+            .get_pipeline_executable_internal_representations_khr)(
+            device.handle(),
+            &executable_info_vk,
+            &mut count,
+            representations_vk.as_mut_ptr(),
+        );
+
+        match result {
+            ash::vk::Result::SUCCESS => {
+                return Ok(representations_vk
+                    .iter()
+                    .zip(data)
+                    .map(|(representation_vk, mut data)| {
+                        data.truncate(representation_vk.data_size);
+
+                        PipelineExecutableInternalRepresentation {
+                            name: string_from_vk(&representation_vk.name),
+                            description: string_from_vk(&representation_vk.description),
+                            is_text: representation_vk.is_text != ash::vk::FALSE,
+                            data,
+                        }
+                    })
+                    .collect());
+            }
+            ash::vk::Result::INCOMPLETE => (),
+            err => return Err(VulkanError::from(err)),
+        }
+    }
+}
+
+fn validate_executable_index(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+    executable_index: u32,
+) -> Result<(), Validated<VulkanError>> {
+    let executable_count = unsafe { executable_properties_unchecked(device, pipeline)? }.len();
+
+    if executable_index as usize >= executable_count {
+        return Err(Box::new(ValidationError {
+            context: "executable_index".into(),
+            problem: "is not less than the number of executables of the pipeline".into(),
+            vuids: &["VUID-VkPipelineExecutableInfoKHR-executableIndex-03275"],
+            ..Default::default()
+        })
+        .into());
+    }
+
+    Ok(())
+}
+
+fn string_from_vk(val: &[c_char]) -> String {
+    unsafe { CStr::from_ptr(val.as_ptr()) }
+        .to_string_lossy()
+        .into_owned()
+}
@@ -62,6 +62,15 @@ pub struct ColorBlendState {
     /// The default value is `[0.0; 4]`.
     pub blend_constants: [f32; 4],
 
+    /// Additional parameters for attachments that use an advanced blend operation.
+    ///
+    /// If set to anything other than the default, the
+    /// [`ext_blend_operation_advanced`](crate::device::DeviceExtensions::ext_blend_operation_advanced)
+    /// extension must be enabled on the device.
+    ///
+    /// The default value is [`AdvancedBlendState::default()`].
+    pub advanced_blend: AdvancedBlendState,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -74,6 +83,7 @@ impl Default for ColorBlendState {
             logic_op: None,
             attachments: Vec::new(),
             blend_constants: [0.0; 4],
+            advanced_blend: AdvancedBlendState::default(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -104,6 +114,7 @@ impl ColorBlendState {
                 .map(|_| ColorBlendAttachmentState::default())
                 .collect(),
             blend_constants: [0.0; 4],
+            advanced_blend: AdvancedBlendState::default(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -170,14 +181,33 @@ impl ColorBlendState {
             logic_op,
             ref attachments,
             blend_constants: _,
+            ref advanced_blend,
             _ne: _,
         } = self;
 
+        let properties = device.physical_device().properties();
+
         flags.validate_device(device).map_err(|err| {
             err.add_context("flags")
                 .set_vuids(&["VUID-VkPipelineColorBlendStateCreateInfo-flags-parameter"])
         })?;
 
+        if flags.intersects(ColorBlendStateFlags::RASTERIZATION_ORDER_ATTACHMENT_ACCESS)
+            && !device
+                .enabled_features()
+                .rasterization_order_color_attachment_access
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `ColorBlendStateFlags::RASTERIZATION_ORDER_ATTACHMENT_ACCESS`"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "rasterization_order_color_attachment_access",
+                )])]),
+                vuids: &["VUID-VkPipelineColorBlendStateCreateInfo-rasterizationOrderColorAttachmentAccess-06465"],
+            }));
+        }
+
         if let Some(logic_op) = logic_op {
             if !device.enabled_features().logic_op {
                 return Err(Box::new(ValidationError {
@@ -225,23 +255,220 @@ impl ColorBlendState {
             }
         }
 
+        let mut advanced_blend_ops = attachments
+            .iter()
+            .filter_map(|state| state.blend)
+            .map(|blend| blend.color_blend_op)
+            .filter(|color_blend_op| color_blend_op.is_advanced());
+
+        if let Some(first_advanced_blend_op) = advanced_blend_ops.next() {
+            if attachments.len() as u32 > properties.advanced_blend_max_color_attachments.unwrap() {
+                return Err(Box::new(ValidationError {
+                    problem: "an element of `attachments` uses an advanced blend operation, and \
+                        `attachments.len()` exceeds the `advanced_blend_max_color_attachments` \
+                        limit"
+                        .into(),
+                    vuids: &["VUID-VkPipelineColorBlendAttachmentState-colorBlendOp-01410"],
+                    ..Default::default()
+                }));
+            }
+
+            if !properties.advanced_blend_independent_blend.unwrap()
+                && attachments.iter().any(|state| {
+                    state.blend.map(|blend| blend.color_blend_op) != Some(first_advanced_blend_op)
+                })
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "an element of `attachments` uses an advanced blend operation, and \
+                        the `advanced_blend_independent_blend` device property is `false`, but \
+                        not all elements of `attachments` use the same blend operation"
+                        .into(),
+                    vuids: &["VUID-VkPipelineColorBlendAttachmentState-advancedBlendIndependentBlend-01407"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        advanced_blend
+            .validate(device)
+            .map_err(|err| err.add_context("advanced_blend"))?;
+
         Ok(())
     }
 }
 
+/// Additional parameters for attachments that use an advanced blend operation.
+///
+/// Advanced blend operations read the existing value of the attachment. If the
+/// [`advanced_blend_coherent_operations`](crate::device::Features::advanced_blend_coherent_operations)
+/// feature is not enabled on the device, then the reads are not automatically ordered with writes
+/// from previous draw calls that overlap the same pixels. In that case, a pipeline barrier with
+/// [`AccessFlags::COLOR_ATTACHMENT_READ_NONCOHERENT`] must be recorded between such draw calls.
+///
+/// [`AccessFlags::COLOR_ATTACHMENT_READ_NONCOHERENT`]: crate::sync::AccessFlags::COLOR_ATTACHMENT_READ_NONCOHERENT
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdvancedBlendState {
+    /// Whether the source color is considered to be premultiplied by the source alpha.
+    ///
+    /// If set to `false`, the
+    /// [`advanced_blend_non_premultiplied_src_color`](crate::device::Properties::advanced_blend_non_premultiplied_src_color)
+    /// device property must be `true`.
+    ///
+    /// The default value is `true`.
+    pub src_premultiplied: bool,
+
+    /// Whether the destination color is considered to be premultiplied by the destination alpha.
+    ///
+    /// If set to `false`, the
+    /// [`advanced_blend_non_premultiplied_dst_color`](crate::device::Properties::advanced_blend_non_premultiplied_dst_color)
+    /// device property must be `true`.
+    ///
+    /// The default value is `true`.
+    pub dst_premultiplied: bool,
+
+    /// How the coverage of the source and destination are assumed to overlap.
+    ///
+    /// If set to anything other than [`BlendOverlap::Uncorrelated`], the
+    /// [`advanced_blend_correlated_overlap`](crate::device::Properties::advanced_blend_correlated_overlap)
+    /// device property must be `true`.
+    ///
+    /// The default value is [`BlendOverlap::Uncorrelated`].
+    pub blend_overlap: BlendOverlap,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for AdvancedBlendState {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            src_premultiplied: true,
+            dst_premultiplied: true,
+            blend_overlap: BlendOverlap::Uncorrelated,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl AdvancedBlendState {
+    pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            src_premultiplied,
+            dst_premultiplied,
+            blend_overlap,
+            _ne: _,
+        } = self;
+
+        if *self == Self::default() {
+            return Ok(());
+        }
+
+        if !device.enabled_extensions().ext_blend_operation_advanced {
+            return Err(Box::new(ValidationError {
+                problem: "is not the default value".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "ext_blend_operation_advanced",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        let properties = device.physical_device().properties();
+
+        if !src_premultiplied
+            && !properties
+                .advanced_blend_non_premultiplied_src_color
+                .unwrap()
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`src_premultiplied` is `false`, but the \
+                    `advanced_blend_non_premultiplied_src_color` device property is `false`"
+                    .into(),
+                vuids: &[
+                    "VUID-VkPipelineColorBlendAdvancedStateCreateInfoEXT-srcPremultiplied-01424",
+                ],
+                ..Default::default()
+            }));
+        }
+
+        if !dst_premultiplied
+            && !properties
+                .advanced_blend_non_premultiplied_dst_color
+                .unwrap()
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`dst_premultiplied` is `false`, but the \
+                    `advanced_blend_non_premultiplied_dst_color` device property is `false`"
+                    .into(),
+                vuids: &[
+                    "VUID-VkPipelineColorBlendAdvancedStateCreateInfoEXT-dstPremultiplied-01425",
+                ],
+                ..Default::default()
+            }));
+        }
+
+        blend_overlap.validate_device(device).map_err(|err| {
+            err.add_context("blend_overlap").set_vuids(&[
+                "VUID-VkPipelineColorBlendAdvancedStateCreateInfoEXT-blendOverlap-parameter",
+            ])
+        })?;
+
+        if blend_overlap != BlendOverlap::Uncorrelated
+            && !properties.advanced_blend_correlated_overlap.unwrap()
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`blend_overlap` is not `BlendOverlap::Uncorrelated`, but the \
+                    `advanced_blend_correlated_overlap` device property is `false`"
+                    .into(),
+                vuids: &["VUID-VkPipelineColorBlendAdvancedStateCreateInfoEXT-blendOverlap-01426"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// How the coverage of the source and destination of an advanced blend operation are assumed
+    /// to overlap within a pixel.
+    BlendOverlap = BlendOverlapEXT(i32);
+
+    /// The coverage of the source and destination is uncorrelated.
+    Uncorrelated = UNCORRELATED,
+
+    /// The coverage of the source and destination is disjoint, as far as possible.
+    Disjoint = DISJOINT,
+
+    /// The coverage of the source and destination is conjoint, as far as possible.
+    Conjoint = CONJOINT,
+}
+
 vulkan_bitflags! {
     #[non_exhaustive]
 
     /// Flags specifying additional properties of the color blend state.
     ColorBlendStateFlags = PipelineColorBlendStateCreateFlags(u32);
 
-    /* TODO: enable
-    // TODO: document
+    /// Reads from the color attachments, for example as input attachments, are synchronized in
+    /// rasterization order with writes from earlier fragments.
+    ///
+    /// The pipeline must be created for a subpass whose flags include
+    /// [`SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS`]. It can not be
+    /// used with dynamic rendering.
+    ///
+    /// The
+    /// [`rasterization_order_color_attachment_access`](crate::device::Features::rasterization_order_color_attachment_access)
+    /// feature must be enabled on the device.
+    ///
+    /// [`SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS`]: crate::render_pass::SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS
     RASTERIZATION_ORDER_ATTACHMENT_ACCESS = RASTERIZATION_ORDER_ATTACHMENT_ACCESS_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_rasterization_order_attachment_access)]),
         RequiresAllOf([DeviceExtension(arm_rasterization_order_attachment_access)]),
-    ]), */
+    ]),
 }
 
 vulkan_enum! {
@@ -526,13 +753,7 @@ impl AttachmentBlend {
         })?;
 
         if !device.enabled_features().dual_src_blend {
-            if matches!(
-                src_color_blend_factor,
-                BlendFactor::Src1Color
-                    | BlendFactor::OneMinusSrc1Color
-                    | BlendFactor::Src1Alpha
-                    | BlendFactor::OneMinusSrc1Alpha
-            ) {
+            if src_color_blend_factor.is_dual_source() {
                 return Err(Box::new(ValidationError {
                     context: "src_color_blend_factor".into(),
                     problem: "is `BlendFactor::Src1*`".into(),
@@ -543,13 +764,7 @@ impl AttachmentBlend {
                 }));
             }
 
-            if matches!(
-                dst_color_blend_factor,
-                BlendFactor::Src1Color
-                    | BlendFactor::OneMinusSrc1Color
-                    | BlendFactor::Src1Alpha
-                    | BlendFactor::OneMinusSrc1Alpha
-            ) {
+            if dst_color_blend_factor.is_dual_source() {
                 return Err(Box::new(ValidationError {
                     context: "dst_color_blend_factor".into(),
                     problem: "is `BlendFactor::Src1*`".into(),
@@ -560,13 +775,7 @@ impl AttachmentBlend {
                 }));
             }
 
-            if matches!(
-                src_alpha_blend_factor,
-                BlendFactor::Src1Color
-                    | BlendFactor::OneMinusSrc1Color
-                    | BlendFactor::Src1Alpha
-                    | BlendFactor::OneMinusSrc1Alpha
-            ) {
+            if src_alpha_blend_factor.is_dual_source() {
                 return Err(Box::new(ValidationError {
                     context: "src_alpha_blend_factor".into(),
                     problem: "is `BlendFactor::Src1*`".into(),
@@ -577,13 +786,7 @@ impl AttachmentBlend {
                 }));
             }
 
-            if matches!(
-                dst_alpha_blend_factor,
-                BlendFactor::Src1Color
-                    | BlendFactor::OneMinusSrc1Color
-                    | BlendFactor::Src1Alpha
-                    | BlendFactor::OneMinusSrc1Alpha
-            ) {
+            if dst_alpha_blend_factor.is_dual_source() {
                 return Err(Box::new(ValidationError {
                     context: "dst_alpha_blend_factor".into(),
                     problem: "is `BlendFactor::Src1*`".into(),
@@ -595,6 +798,37 @@ impl AttachmentBlend {
             }
         }
 
+        if color_blend_op.is_advanced() || alpha_blend_op.is_advanced() {
+            if color_blend_op != alpha_blend_op {
+                return Err(Box::new(ValidationError {
+                    problem: "`color_blend_op` or `alpha_blend_op` is an advanced blend \
+                        operation, but they are not equal"
+                        .into(),
+                    vuids: &["VUID-VkPipelineColorBlendAttachmentState-colorBlendOp-01406"],
+                    ..Default::default()
+                }));
+            }
+
+            if !device
+                .physical_device()
+                .properties()
+                .advanced_blend_all_operations
+                .unwrap()
+                && !color_blend_op.is_advanced_basic()
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "`color_blend_op` is an advanced blend operation that is only \
+                        supported if the `advanced_blend_all_operations` device property is \
+                        `true`, but it is `false`"
+                        .into(),
+                    vuids: &[
+                        "VUID-VkPipelineColorBlendAttachmentState-advancedBlendAllOperations-01409",
+                    ],
+                    ..Default::default()
+                }));
+            }
+        }
+
         if device.enabled_extensions().khr_portability_subset
             && !device.enabled_features().constant_alpha_color_blend_factors
         {
@@ -734,6 +968,21 @@ vulkan_enum! {
     OneMinusSrc1Alpha = ONE_MINUS_SRC1_ALPHA,
 }
 
+impl BlendFactor {
+    /// Returns whether `self` takes the second source value, `source1`, as an input.
+    ///
+    /// The second source value is the fragment shader output that has the same location as the
+    /// first, but with an `Index` decoration of 1. If the fragment shader does not write such an
+    /// output, the value of `source1` is undefined, but this is not an error.
+    #[inline]
+    pub fn is_dual_source(self) -> bool {
+        matches!(
+            self,
+            Self::Src1Color | Self::OneMinusSrc1Color | Self::Src1Alpha | Self::OneMinusSrc1Alpha
+        )
+    }
+}
+
 vulkan_enum! {
     #[non_exhaustive]
 
@@ -756,327 +1005,341 @@ vulkan_enum! {
     /// `max(source, destination)`.
     Max = MAX,
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the result is zero.
     Zero = ZERO_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the source replaces the destination (Porter-Duff *source*).
     Src = SRC_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the destination is kept (Porter-Duff *destination*).
     Dst = DST_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the source is placed over the destination (Porter-Duff
+    /// *source over*).
     SrcOver = SRC_OVER_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the destination is placed over the source (Porter-Duff
+    /// *destination over*).
     DstOver = DST_OVER_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the part of the source inside the destination replaces the
+    /// destination (Porter-Duff *source in*).
     SrcIn = SRC_IN_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the part of the destination inside the source replaces the
+    /// destination (Porter-Duff *destination in*).
     DstIn = DST_IN_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the part of the source outside the destination replaces the
+    /// destination (Porter-Duff *source out*).
     SrcOut = SRC_OUT_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the part of the destination outside the source replaces the
+    /// destination (Porter-Duff *destination out*).
     DstOut = DST_OUT_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the part of the source inside the destination is placed over the
+    /// destination (Porter-Duff *source atop*).
     SrcAtop = SRC_ATOP_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the part of the destination inside the source is placed over the
+    /// source (Porter-Duff *destination atop*).
     DstAtop = DST_ATOP_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the non-overlapping parts of the source and destination are
+    /// combined (Porter-Duff *xor*).
     Xor = XOR_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the source and destination colors are multiplied.
     Multiply = MULTIPLY_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the inverses of the source and destination colors are multiplied,
+    /// and the result is inverted.
     Screen = SCREEN_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: `Multiply` or `Screen`, depending on the destination color.
     Overlay = OVERLAY_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the minimum of the source and destination colors.
     Darken = DARKEN_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the maximum of the source and destination colors.
     Lighten = LIGHTEN_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the destination color is brightened to reflect the source color.
     Colordodge = COLORDODGE_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the destination color is darkened to reflect the source color.
     Colorburn = COLORBURN_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: `Multiply` or `Screen`, depending on the source color.
     Hardlight = HARDLIGHT_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the destination color is darkened or lightened, depending on the
+    /// source color.
     Softlight = SOFTLIGHT_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the absolute difference of the source and destination colors.
     Difference = DIFFERENCE_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: similar to `Difference`, but with lower contrast.
     Exclusion = EXCLUSION_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the destination color is inverted.
     Invert = INVERT_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the destination color is inverted, scaled by the source color.
     InvertRgb = INVERT_RGB_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the sum of the source and destination colors.
     Lineardodge = LINEARDODGE_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the sum of the source and destination colors, minus one.
     Linearburn = LINEARBURN_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: `Colorburn` or `Colordodge`, depending on the source color.
     Vividlight = VIVIDLIGHT_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: `Linearburn` or `Lineardodge`, depending on the source color.
     Linearlight = LINEARLIGHT_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: `Darken` or `Lighten`, depending on the source color.
     Pinlight = PINLIGHT_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: each component is set to zero or one, depending on the sum of
+    /// the source and destination.
     Hardmix = HARDMIX_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the hue of the source with the saturation and luminosity of the
+    /// destination.
     HslHue = HSL_HUE_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the saturation of the source with the hue and luminosity of the
+    /// destination.
     HslSaturation = HSL_SATURATION_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the hue and saturation of the source with the luminosity of the
+    /// destination.
     HslColor = HSL_COLOR_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the luminosity of the source with the hue and saturation of the
+    /// destination.
     HslLuminosity = HSL_LUMINOSITY_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the sum of the premultiplied source and destination.
     Plus = PLUS_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the sum of the premultiplied source and destination, clamped to
+    /// one.
     PlusClamped = PLUS_CLAMPED_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the sum of the premultiplied source and destination, with the
+    /// color clamped to the resulting alpha.
     PlusClampedAlpha = PLUS_CLAMPED_ALPHA_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the sum of the premultiplied source and destination, minus one,
+    /// clamped to zero.
     PlusDarker = PLUS_DARKER_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the premultiplied source subtracted from the destination.
     Minus = MINUS_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the premultiplied source subtracted from the destination, clamped
+    /// to zero.
     MinusClamped = MINUS_CLAMPED_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the contrast of the destination is increased or decreased, based
+    /// on the source.
     Contrast = CONTRAST_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the destination color is inverted, as defined by OpenVG.
     InvertOvg = INVERT_OVG_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the red component of the source with the green and blue
+    /// components of the destination.
     Red = RED_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the green component of the source with the red and blue
+    /// components of the destination.
     Green = GREEN_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Advanced blend operation: the blue component of the source with the red and green
+    /// components of the destination.
     Blue = BLUE_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_blend_operation_advanced)]),
-    ]),*/
+    ]),
+}
+
+impl BlendOp {
+    /// Returns whether `self` is one of the advanced blend operations, that are provided by the
+    /// [`ext_blend_operation_advanced`](crate::device::DeviceExtensions::ext_blend_operation_advanced)
+    /// extension.
+    #[inline]
+    pub fn is_advanced(self) -> bool {
+        !matches!(
+            self,
+            Self::Add | Self::Subtract | Self::ReverseSubtract | Self::Min | Self::Max
+        )
+    }
+
+    /// Returns whether `self` is an advanced blend operation that is supported even if the
+    /// `advanced_blend_all_operations` device property is `false`.
+    fn is_advanced_basic(self) -> bool {
+        matches!(
+            self,
+            Self::Multiply
+                | Self::Screen
+                | Self::Overlay
+                | Self::Darken
+                | Self::Lighten
+                | Self::Colordodge
+                | Self::Colorburn
+                | Self::Hardlight
+                | Self::Softlight
+                | Self::Difference
+                | Self::Exclusion
+                | Self::HslHue
+                | Self::HslSaturation
+                | Self::HslColor
+                | Self::HslLuminosity
+        )
+    }
 }
 
 vulkan_bitflags! {
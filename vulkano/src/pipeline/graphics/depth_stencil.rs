@@ -112,6 +112,40 @@ impl DepthStencilState {
                 .set_vuids(&["VUID-VkPipelineDepthStencilStateCreateInfo-flags-parameter"])
         })?;
 
+        if flags.intersects(DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS)
+            && !device
+                .enabled_features()
+                .rasterization_order_depth_attachment_access
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains \
+                    `DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS`"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "rasterization_order_depth_attachment_access",
+                )])]),
+                vuids: &["VUID-VkPipelineDepthStencilStateCreateInfo-rasterizationOrderDepthAttachmentAccess-06463"],
+            }));
+        }
+
+        if flags.intersects(DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS)
+            && !device
+                .enabled_features()
+                .rasterization_order_stencil_attachment_access
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains \
+                    `DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS`"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "rasterization_order_stencil_attachment_access",
+                )])]),
+                vuids: &["VUID-VkPipelineDepthStencilStateCreateInfo-rasterizationOrderStencilAttachmentAccess-06464"],
+            }));
+        }
+
         if let Some(depth_state) = depth {
             depth_state
                 .validate(device)
@@ -173,21 +207,41 @@ vulkan_bitflags! {
     /// Flags specifying additional properties of the depth/stencil state.
     DepthStencilStateFlags = PipelineDepthStencilStateCreateFlags(u32);
 
-    /* TODO: enable
-    // TODO: document
+    /// Reads from the depth aspect of the depth/stencil attachment are synchronized in
+    /// rasterization order with writes from earlier fragments.
+    ///
+    /// The pipeline must be created for a subpass whose flags include
+    /// [`SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS`]. It can not be
+    /// used with dynamic rendering.
+    ///
+    /// The
+    /// [`rasterization_order_depth_attachment_access`](crate::device::Features::rasterization_order_depth_attachment_access)
+    /// feature must be enabled on the device.
+    ///
+    /// [`SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS`]: crate::render_pass::SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS
     RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS = RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_rasterization_order_attachment_access)]),
         RequiresAllOf([DeviceExtension(arm_rasterization_order_attachment_access)]),
-    ]), */
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Reads from the stencil aspect of the depth/stencil attachment are synchronized in
+    /// rasterization order with writes from earlier fragments.
+    ///
+    /// The pipeline must be created for a subpass whose flags include
+    /// [`SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS`]. It can not be
+    /// used with dynamic rendering.
+    ///
+    /// The
+    /// [`rasterization_order_stencil_attachment_access`](crate::device::Features::rasterization_order_stencil_attachment_access)
+    /// feature must be enabled on the device.
+    ///
+    /// [`SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS`]: crate::render_pass::SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS
     RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS = RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_rasterization_order_attachment_access)]),
         RequiresAllOf([DeviceExtension(arm_rasterization_order_attachment_access)]),
-    ]), */
+    ]),
 }
 
 /// The state in a graphics pipeline describing how the depth test should behave when enabled.
@@ -20,7 +20,11 @@ use crate::{
 pub struct DiscardRectangleState {
     /// Sets whether the discard rectangle test operates inclusively or exclusively.
     ///
+    /// When [`DynamicState::DiscardRectangleMode`] is used, this value is ignored.
+    ///
     /// The default value is [`DiscardRectangleMode::Exclusive`].
+    ///
+    /// [`DynamicState::DiscardRectangleMode`]: crate::pipeline::DynamicState::DiscardRectangleMode
     pub mode: DiscardRectangleMode,
 
     /// Specifies the discard rectangles.
@@ -0,0 +1,260 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Configures the number of pixels that a single fragment shader invocation covers.
+//!
+//! Normally, the fragment shader is invoked once for every pixel covered by a primitive (or for
+//! every sample, when sample shading is used). With a coarser *fragment shading rate*, a single
+//! invocation provides the color for a whole block of pixels, such as 2×2 or 4×2. This lowers
+//! the shading cost in regions of the image where the detail is not needed, for example in the
+//! periphery of a VR lens.
+//!
+//! The shading rate can come from three sources, which are combined in order:
+//!
+//! 1. The *pipeline* rate, set with [`FragmentShadingRateState::fragment_size`], or dynamically.
+//! 2. The *primitive* rate, written by the last pre-rasterization shader stage to the
+//!    `PrimitiveShadingRateKHR` built-in.
+//! 3. The *attachment* rate, read from the
+//!    [fragment shading rate attachment] of the subpass.
+//!
+//! The pipeline and primitive rates are combined with the first combiner operation, and the
+//! result of that is combined with the attachment rate using the second operation.
+//!
+//! [fragment shading rate attachment]: crate::render_pass::SubpassDescription::fragment_shading_rate_attachment
+
+use crate::{
+    device::Device, macros::vulkan_enum, Requires, RequiresAllOf, RequiresOneOf, ValidationError,
+};
+
+/// The state in a graphics pipeline describing the fragment shading rate.
+#[derive(Clone, Debug)]
+pub struct FragmentShadingRateState {
+    /// The size of the block of pixels that a single fragment shader invocation covers, before
+    /// the combiner operations are applied.
+    ///
+    /// Each dimension must be 1, 2 or 4. If this is not `[1, 1]`, then the
+    /// [`pipeline_fragment_shading_rate`](crate::device::Features::pipeline_fragment_shading_rate)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is `[1, 1]`.
+    pub fragment_size: [u32; 2],
+
+    /// The operations that are used to combine the pipeline, primitive and attachment fragment
+    /// shading rates.
+    ///
+    /// The first element combines the pipeline rate with the primitive rate. If it is not
+    /// `Keep`, then the
+    /// [`primitive_fragment_shading_rate`](crate::device::Features::primitive_fragment_shading_rate)
+    /// feature must be enabled on the device.
+    ///
+    /// The second element combines the result of that with the attachment rate. If it is not
+    /// `Keep`, then the
+    /// [`attachment_fragment_shading_rate`](crate::device::Features::attachment_fragment_shading_rate)
+    /// feature must be enabled on the device.
+    ///
+    /// If the
+    /// [`fragment_shading_rate_non_trivial_combiner_ops`](crate::device::Properties::fragment_shading_rate_non_trivial_combiner_ops)
+    /// device property is `false`, then both elements must be `Keep` or `Replace`.
+    ///
+    /// The default value is `[FragmentShadingRateCombinerOp::Keep; 2]`.
+    pub combiner_ops: [FragmentShadingRateCombinerOp; 2],
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for FragmentShadingRateState {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            fragment_size: [1, 1],
+            combiner_ops: [FragmentShadingRateCombinerOp::Keep; 2],
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl FragmentShadingRateState {
+    pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            fragment_size,
+            combiner_ops,
+            _ne: _,
+        } = self;
+
+        validate_fragment_size(
+            device,
+            fragment_size,
+            &[
+                "VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04494",
+                "VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04496",
+                "VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04498",
+            ],
+            &[
+                "VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04495",
+                "VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04497",
+                "VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04499",
+            ],
+            &["VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04500"],
+        )?;
+
+        validate_combiner_ops(
+            device,
+            combiner_ops,
+            [
+                &["VUID-VkGraphicsPipelineCreateInfo-pDynamicState-06567"],
+                &["VUID-VkGraphicsPipelineCreateInfo-pDynamicState-06568"],
+            ],
+            &["VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04501"],
+            &["VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04502"],
+            &["VUID-VkGraphicsPipelineCreateInfo-fragmentShadingRateNonTrivialCombinerOps-04506"],
+        )?;
+
+        Ok(())
+    }
+}
+
+pub(crate) fn validate_fragment_size(
+    device: &Device,
+    fragment_size: [u32; 2],
+    width_vuids: &'static [&'static str],
+    height_vuids: &'static [&'static str],
+    feature_vuids: &'static [&'static str],
+) -> Result<(), Box<ValidationError>> {
+    if !matches!(fragment_size[0], 1 | 2 | 4) {
+        return Err(Box::new(ValidationError {
+            context: "fragment_size[0]".into(),
+            problem: "is not 1, 2 or 4".into(),
+            vuids: width_vuids,
+            ..Default::default()
+        }));
+    }
+
+    if !matches!(fragment_size[1], 1 | 2 | 4) {
+        return Err(Box::new(ValidationError {
+            context: "fragment_size[1]".into(),
+            problem: "is not 1, 2 or 4".into(),
+            vuids: height_vuids,
+            ..Default::default()
+        }));
+    }
+
+    if fragment_size != [1, 1] && !device.enabled_features().pipeline_fragment_shading_rate {
+        return Err(Box::new(ValidationError {
+            context: "fragment_size".into(),
+            problem: "is not `[1, 1]`".into(),
+            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                "pipeline_fragment_shading_rate",
+            )])]),
+            vuids: feature_vuids,
+        }));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn validate_combiner_ops(
+    device: &Device,
+    combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    parameter_vuids: [&'static [&'static str]; 2],
+    primitive_vuids: &'static [&'static str],
+    attachment_vuids: &'static [&'static str],
+    non_trivial_vuids: &'static [&'static str],
+) -> Result<(), Box<ValidationError>> {
+    for (index, combiner_op) in combiner_ops.into_iter().enumerate() {
+        combiner_op.validate_device(device).map_err(|err| {
+            err.add_context(format!("combiner_ops[{}]", index))
+                .set_vuids(parameter_vuids[index])
+        })?;
+    }
+
+    if combiner_ops[0] != FragmentShadingRateCombinerOp::Keep
+        && !device.enabled_features().primitive_fragment_shading_rate
+    {
+        return Err(Box::new(ValidationError {
+            context: "combiner_ops[0]".into(),
+            problem: "is not `FragmentShadingRateCombinerOp::Keep`".into(),
+            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                "primitive_fragment_shading_rate",
+            )])]),
+            vuids: primitive_vuids,
+        }));
+    }
+
+    if combiner_ops[1] != FragmentShadingRateCombinerOp::Keep
+        && !device.enabled_features().attachment_fragment_shading_rate
+    {
+        return Err(Box::new(ValidationError {
+            context: "combiner_ops[1]".into(),
+            problem: "is not `FragmentShadingRateCombinerOp::Keep`".into(),
+            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                "attachment_fragment_shading_rate",
+            )])]),
+            vuids: attachment_vuids,
+        }));
+    }
+
+    if !device
+        .physical_device()
+        .properties()
+        .fragment_shading_rate_non_trivial_combiner_ops
+        .unwrap_or(false)
+    {
+        for (index, combiner_op) in combiner_ops.into_iter().enumerate() {
+            if !matches!(
+                combiner_op,
+                FragmentShadingRateCombinerOp::Keep | FragmentShadingRateCombinerOp::Replace
+            ) {
+                return Err(Box::new(ValidationError {
+                    context: format!("combiner_ops[{}]", index).into(),
+                    problem: "is not `FragmentShadingRateCombinerOp::Keep` or \
+                        `FragmentShadingRateCombinerOp::Replace`, but the \
+                        `fragment_shading_rate_non_trivial_combiner_ops` property is `false`"
+                        .into(),
+                    vuids: non_trivial_vuids,
+                    ..Default::default()
+                }));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// How two fragment shading rates are combined into one.
+    ///
+    /// In the descriptions below, `A` is the rate that results from the previous step, and `B`
+    /// is the rate that is being combined with it.
+    FragmentShadingRateCombinerOp = FragmentShadingRateCombinerOpKHR(i32);
+
+    /// The result is `A`; `B` is ignored.
+    Keep = KEEP,
+
+    /// The result is `B`; `A` is ignored.
+    Replace = REPLACE,
+
+    /// The result is the component-wise minimum of `A` and `B`.
+    Min = MIN,
+
+    /// The result is the component-wise maximum of `A` and `B`.
+    Max = MAX,
+
+    /// The result is the component-wise product of `A` and `B`.
+    Mul = MUL,
+}
+
+impl Default for FragmentShadingRateCombinerOp {
+    /// Returns `FragmentShadingRateCombinerOp::Keep`.
+    #[inline]
+    fn default() -> Self {
+        Self::Keep
+    }
+}
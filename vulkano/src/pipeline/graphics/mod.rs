@@ -57,9 +57,10 @@
 //! command.
 
 use self::{
-    color_blend::ColorBlendState,
-    depth_stencil::{DepthState, DepthStencilState},
+    color_blend::{ColorBlendState, ColorBlendStateFlags},
+    depth_stencil::{DepthState, DepthStencilState, DepthStencilStateFlags},
     discard_rectangle::DiscardRectangleState,
+    fragment_shading_rate::FragmentShadingRateState,
     input_assembly::{InputAssemblyState, PrimitiveTopology},
     multisample::MultisampleState,
     rasterization::RasterizationState,
@@ -69,7 +70,12 @@ use self::{
     viewport::ViewportState,
 };
 use super::{
-    cache::PipelineCache, DynamicState, Pipeline, PipelineBindPoint, PipelineCreateFlags,
+    cache::PipelineCache,
+    executable::{
+        self, PipelineExecutableInternalRepresentation, PipelineExecutableProperties,
+        PipelineExecutableStatistic,
+    },
+    DynamicState, Pipeline, PipelineBindPoint, PipelineCreateFlags, PipelineCreationFeedback,
     PipelineLayout, PipelineShaderStageCreateInfo,
 };
 use crate::{
@@ -77,15 +83,18 @@ use crate::{
     format::FormatFeatures,
     image::{ImageAspect, ImageAspects},
     instance::InstanceOwnedDebugWrapper,
-    macros::impl_id_counter,
+    macros::{impl_id_counter, log_event, vulkan_bitflags},
     pipeline::graphics::{
-        color_blend::ColorBlendAttachmentState,
+        color_blend::{
+            AdvancedBlendState, AttachmentBlend, BlendFactor, ColorBlendAttachmentState,
+        },
         depth_stencil::{StencilOpState, StencilState},
-        rasterization::{CullMode, DepthBiasState},
+        rasterization::{ConservativeRasterizationMode, CullMode, DepthBiasState},
         subpass::PipelineRenderingCreateInfo,
         tessellation::TessellationDomainOrigin,
         vertex_input::VertexInputRate,
     },
+    render_pass::SubpassDescriptionFlags,
     shader::{
         spirv::{ExecutionMode, ExecutionModel, Instruction},
         DescriptorBindingRequirements, ShaderStage, ShaderStages,
@@ -93,15 +102,21 @@ use crate::{
     Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, VulkanError, VulkanObject,
 };
 use ahash::{HashMap, HashSet};
-use smallvec::SmallVec;
+use smallvec::{smallvec, SmallVec};
 use std::{
-    collections::hash_map::Entry, ffi::CString, fmt::Debug, mem::MaybeUninit, num::NonZeroU64, ptr,
-    sync::Arc,
+    collections::hash_map::Entry,
+    ffi::CString,
+    fmt::Debug,
+    mem::MaybeUninit,
+    num::NonZeroU64,
+    ptr,
+    sync::{atomic::Ordering, Arc},
 };
 
 pub mod color_blend;
 pub mod depth_stencil;
 pub mod discard_rectangle;
+pub mod fragment_shading_rate;
 pub mod input_assembly;
 pub mod multisample;
 pub mod rasterization;
@@ -129,21 +144,26 @@ pub struct GraphicsPipeline {
     num_used_descriptor_sets: u32,
     fragment_tests_stages: Option<FragmentTestsStages>,
 
-    vertex_input_state: VertexInputState,
-    input_assembly_state: InputAssemblyState,
+    vertex_input_state: Option<VertexInputState>,
+    input_assembly_state: Option<InputAssemblyState>,
     tessellation_state: Option<TessellationState>,
     viewport_state: Option<ViewportState>,
-    rasterization_state: RasterizationState,
+    rasterization_state: Option<RasterizationState>,
     multisample_state: Option<MultisampleState>,
     depth_stencil_state: Option<DepthStencilState>,
     color_blend_state: Option<ColorBlendState>,
     dynamic_state: HashSet<DynamicState>,
     layout: DeviceOwnedDebugWrapper<Arc<PipelineLayout>>,
-    subpass: PipelineSubpassType,
+    subpass: Option<PipelineSubpassType>,
+    library_flags: GraphicsPipelineLibraryFlags,
 
     discard_rectangle_state: Option<DiscardRectangleState>,
+    fragment_shading_rate_state: Option<FragmentShadingRateState>,
 
     fixed_state: HashSet<DynamicState>,
+
+    creation_feedback: Option<PipelineCreationFeedback>,
+    stage_creation_feedback: HashMap<ShaderStage, PipelineCreationFeedback>,
 }
 
 impl GraphicsPipeline {
@@ -194,7 +214,11 @@ impl GraphicsPipeline {
             ref subpass,
             ref base_pipeline,
 
+            library_flags,
+            ref libraries,
+
             ref discard_rectangle_state,
+            ref fragment_shading_rate_state,
             _ne: _,
         } = &create_info;
 
@@ -422,41 +446,60 @@ impl GraphicsPipeline {
         let mut viewport_state_vk = None;
         let mut viewports_vk: SmallVec<[_; 2]> = SmallVec::new();
         let mut scissors_vk: SmallVec<[_; 2]> = SmallVec::new();
+        let mut viewport_depth_clip_control_state_vk = None;
 
         if let Some(viewport_state) = viewport_state {
-            let ViewportState {
-                viewports,
-                scissors,
+            let &ViewportState {
+                ref viewports,
+                ref scissors,
+                depth_clip_negative_one_to_one,
                 _ne: _,
             } = viewport_state;
 
             viewports_vk.extend(viewports.iter().map(Into::into));
             scissors_vk.extend(scissors.iter().map(Into::into));
 
-            let _ = viewport_state_vk.insert(ash::vk::PipelineViewportStateCreateInfo {
-                flags: ash::vk::PipelineViewportStateCreateFlags::empty(),
-                viewport_count: viewports_vk.len() as u32,
-                p_viewports: if viewports_vk.is_empty() {
-                    ptr::null()
-                } else {
-                    viewports_vk.as_ptr()
-                },
-                scissor_count: scissors_vk.len() as u32,
-                p_scissors: if scissors_vk.is_empty() {
-                    ptr::null()
-                } else {
-                    scissors_vk.as_ptr()
-                },
-                ..Default::default()
-            });
+            let viewport_state =
+                viewport_state_vk.insert(ash::vk::PipelineViewportStateCreateInfo {
+                    flags: ash::vk::PipelineViewportStateCreateFlags::empty(),
+                    viewport_count: viewports_vk.len() as u32,
+                    p_viewports: if viewports_vk.is_empty() {
+                        ptr::null()
+                    } else {
+                        viewports_vk.as_ptr()
+                    },
+                    scissor_count: scissors_vk.len() as u32,
+                    p_scissors: if scissors_vk.is_empty() {
+                        ptr::null()
+                    } else {
+                        scissors_vk.as_ptr()
+                    },
+                    ..Default::default()
+                });
+
+            if depth_clip_negative_one_to_one {
+                let next = viewport_depth_clip_control_state_vk.insert(
+                    ash::vk::PipelineViewportDepthClipControlCreateInfoEXT {
+                        negative_one_to_one: ash::vk::TRUE,
+                        ..Default::default()
+                    },
+                );
+                viewport_state.p_next = next as *const _ as *const _;
+            }
         }
 
         let mut rasterization_state_vk = None;
         let mut rasterization_line_state_vk = None;
+        let mut rasterization_depth_clip_state_vk = None;
+        let mut rasterization_provoking_vertex_state_vk = None;
+        let mut rasterization_order_state_vk = None;
+        let mut rasterization_stream_state_vk = None;
+        let mut rasterization_conservative_state_vk = None;
 
         if let Some(rasterization_state) = rasterization_state {
             let &RasterizationState {
                 depth_clamp_enable,
+                depth_clip_enable,
                 rasterizer_discard_enable,
                 polygon_mode,
                 cull_mode,
@@ -465,6 +508,11 @@ impl GraphicsPipeline {
                 line_width,
                 line_rasterization_mode,
                 line_stipple,
+                provoking_vertex_mode,
+                rasterization_order,
+                rasterization_stream,
+                conservative_rasterization_mode,
+                extra_primitive_overestimation_size,
                 _ne: _,
             } = rasterization_state;
 
@@ -519,9 +567,71 @@ impl GraphicsPipeline {
                     },
                 ) as *const _ as *const _;
             }
+
+            if let Some(depth_clip_enable) = depth_clip_enable {
+                let next = rasterization_depth_clip_state_vk.insert(
+                    ash::vk::PipelineRasterizationDepthClipStateCreateInfoEXT {
+                        flags: ash::vk::PipelineRasterizationDepthClipStateCreateFlagsEXT::empty(),
+                        depth_clip_enable: depth_clip_enable as ash::vk::Bool32,
+                        p_next: rasterization_state.p_next,
+                        ..Default::default()
+                    },
+                );
+                rasterization_state.p_next = next as *const _ as *const _;
+            }
+
+            if device.enabled_extensions().ext_provoking_vertex {
+                let next = rasterization_provoking_vertex_state_vk.insert(
+                    ash::vk::PipelineRasterizationProvokingVertexStateCreateInfoEXT {
+                        provoking_vertex_mode: provoking_vertex_mode.into(),
+                        p_next: rasterization_state.p_next,
+                        ..Default::default()
+                    },
+                );
+                rasterization_state.p_next = next as *const _ as *const _;
+            }
+
+            if device.enabled_extensions().amd_rasterization_order {
+                let next = rasterization_order_state_vk.insert(
+                    ash::vk::PipelineRasterizationStateRasterizationOrderAMD {
+                        rasterization_order: rasterization_order.into(),
+                        p_next: rasterization_state.p_next,
+                        ..Default::default()
+                    },
+                );
+                rasterization_state.p_next = next as *const _ as *const _;
+            }
+
+            if rasterization_stream != 0 {
+                let next = rasterization_stream_state_vk.insert(
+                    ash::vk::PipelineRasterizationStateStreamCreateInfoEXT {
+                        flags: ash::vk::PipelineRasterizationStateStreamCreateFlagsEXT::empty(),
+                        rasterization_stream,
+                        p_next: rasterization_state.p_next,
+                        ..Default::default()
+                    },
+                );
+                rasterization_state.p_next = next as *const _ as *const _;
+            }
+
+            if device.enabled_extensions().ext_conservative_rasterization {
+                let next = rasterization_conservative_state_vk.insert(
+                    ash::vk::PipelineRasterizationConservativeStateCreateInfoEXT {
+                        flags: ash::vk::PipelineRasterizationConservativeStateCreateFlagsEXT::empty(
+                        ),
+                        conservative_rasterization_mode: conservative_rasterization_mode.into(),
+                        extra_primitive_overestimation_size,
+                        p_next: rasterization_state.p_next,
+                        ..Default::default()
+                    },
+                );
+                rasterization_state.p_next = next as *const _ as *const _;
+            }
         }
 
         let mut multisample_state_vk = None;
+        let mut multisample_sample_locations_state_vk = None;
+        let sample_locations_vk;
 
         if let Some(multisample_state) = multisample_state {
             let &MultisampleState {
@@ -530,6 +640,7 @@ impl GraphicsPipeline {
                 ref sample_mask,
                 alpha_to_coverage_enable,
                 alpha_to_one_enable,
+                ref sample_locations,
                 _ne: _,
             } = multisample_state;
 
@@ -540,16 +651,29 @@ impl GraphicsPipeline {
                     (ash::vk::FALSE, 0.0)
                 };
 
-            let _ = multisample_state_vk.insert(ash::vk::PipelineMultisampleStateCreateInfo {
-                flags: ash::vk::PipelineMultisampleStateCreateFlags::empty(),
-                rasterization_samples: rasterization_samples.into(),
-                sample_shading_enable,
-                min_sample_shading,
-                p_sample_mask: sample_mask as _,
-                alpha_to_coverage_enable: alpha_to_coverage_enable as ash::vk::Bool32,
-                alpha_to_one_enable: alpha_to_one_enable as ash::vk::Bool32,
-                ..Default::default()
-            });
+            let multisample_state =
+                multisample_state_vk.insert(ash::vk::PipelineMultisampleStateCreateInfo {
+                    flags: ash::vk::PipelineMultisampleStateCreateFlags::empty(),
+                    rasterization_samples: rasterization_samples.into(),
+                    sample_shading_enable,
+                    min_sample_shading,
+                    p_sample_mask: sample_mask as _,
+                    alpha_to_coverage_enable: alpha_to_coverage_enable as ash::vk::Bool32,
+                    alpha_to_one_enable: alpha_to_one_enable as ash::vk::Bool32,
+                    ..Default::default()
+                });
+
+            if let Some(sample_locations) = sample_locations {
+                sample_locations_vk = sample_locations.to_vk_sample_locations();
+
+                multisample_state.p_next = multisample_sample_locations_state_vk.insert(
+                    ash::vk::PipelineSampleLocationsStateCreateInfoEXT {
+                        sample_locations_enable: ash::vk::TRUE,
+                        sample_locations_info: sample_locations.to_vk(&sample_locations_vk),
+                        ..Default::default()
+                    },
+                ) as *const _ as *const _;
+            }
         }
 
         let mut depth_stencil_state_vk = None;
@@ -632,6 +756,7 @@ impl GraphicsPipeline {
         let mut color_blend_attachments_vk: SmallVec<[_; 4]> = SmallVec::new();
         let mut color_write_vk = None;
         let mut color_write_enables_vk: SmallVec<[_; 4]> = SmallVec::new();
+        let mut color_blend_advanced_vk = None;
 
         if let Some(color_blend_state) = color_blend_state {
             let &ColorBlendState {
@@ -639,6 +764,7 @@ impl GraphicsPipeline {
                 logic_op,
                 ref attachments,
                 blend_constants,
+                ref advanced_blend,
                 _ne: _,
             } = color_blend_state;
 
@@ -700,6 +826,26 @@ impl GraphicsPipeline {
                         ..Default::default()
                     }) as *const _ as *const _;
             }
+
+            if device.enabled_extensions().ext_blend_operation_advanced {
+                let &AdvancedBlendState {
+                    src_premultiplied,
+                    dst_premultiplied,
+                    blend_overlap,
+                    _ne: _,
+                } = advanced_blend;
+
+                let next = color_blend_advanced_vk.insert(
+                    ash::vk::PipelineColorBlendAdvancedStateCreateInfoEXT {
+                        src_premultiplied: src_premultiplied as ash::vk::Bool32,
+                        dst_premultiplied: dst_premultiplied as ash::vk::Bool32,
+                        blend_overlap: blend_overlap.into(),
+                        p_next: color_blend_state_vk.p_next,
+                        ..Default::default()
+                    },
+                );
+                color_blend_state_vk.p_next = next as *const _ as *const _;
+            }
         }
 
         let dynamic_state_list_vk: SmallVec<[_; 4]> =
@@ -712,18 +858,17 @@ impl GraphicsPipeline {
                 ..Default::default()
             });
 
-        let render_pass = subpass.as_ref().unwrap();
         let mut render_pass_vk = ash::vk::RenderPass::null();
         let mut subpass_vk = 0;
         let mut color_attachment_formats_vk: SmallVec<[_; 4]> = SmallVec::new();
         let mut rendering_create_info_vk = None;
 
-        match render_pass {
-            PipelineSubpassType::BeginRenderPass(subpass) => {
+        match subpass {
+            Some(PipelineSubpassType::BeginRenderPass(subpass)) => {
                 render_pass_vk = subpass.render_pass().handle();
                 subpass_vk = subpass.index();
             }
-            PipelineSubpassType::BeginRendering(rendering_info) => {
+            Some(PipelineSubpassType::BeginRendering(rendering_info)) => {
                 let &PipelineRenderingCreateInfo {
                     view_mask,
                     ref color_attachment_formats,
@@ -749,6 +894,27 @@ impl GraphicsPipeline {
                     ..Default::default()
                 });
             }
+            None => (),
+        }
+
+        let mut library_create_info_vk = None;
+
+        if !library_flags.is_empty() {
+            let _ = library_create_info_vk.insert(ash::vk::GraphicsPipelineLibraryCreateInfoEXT {
+                flags: library_flags.into(),
+                ..Default::default()
+            });
+        }
+
+        let libraries_vk: SmallVec<[_; 4]> = libraries.iter().map(VulkanObject::handle).collect();
+        let mut pipeline_library_create_info_vk = None;
+
+        if !libraries_vk.is_empty() {
+            let _ = pipeline_library_create_info_vk.insert(ash::vk::PipelineLibraryCreateInfoKHR {
+                library_count: libraries_vk.len() as u32,
+                p_libraries: libraries_vk.as_ptr(),
+                ..Default::default()
+            });
         }
 
         let mut discard_rectangle_state_vk = None;
@@ -774,6 +940,27 @@ impl GraphicsPipeline {
             );
         }
 
+        let mut fragment_shading_rate_state_vk = None;
+
+        if let Some(fragment_shading_rate_state) = fragment_shading_rate_state {
+            let &FragmentShadingRateState {
+                fragment_size,
+                combiner_ops,
+                _ne: _,
+            } = fragment_shading_rate_state;
+
+            let _ = fragment_shading_rate_state_vk.insert(
+                ash::vk::PipelineFragmentShadingRateStateCreateInfoKHR {
+                    fragment_size: ash::vk::Extent2D {
+                        width: fragment_size[0],
+                        height: fragment_size[1],
+                    },
+                    combiner_ops: combiner_ops.map(Into::into),
+                    ..Default::default()
+                },
+            );
+        }
+
         /*
             Create
         */
@@ -833,11 +1020,45 @@ impl GraphicsPipeline {
             create_info_vk.p_next = info as *const _ as *const _;
         }
 
+        if let Some(info) = fragment_shading_rate_state_vk.as_mut() {
+            info.p_next = create_info_vk.p_next;
+            create_info_vk.p_next = info as *const _ as *const _;
+        }
+
         if let Some(info) = rendering_create_info_vk.as_mut() {
             info.p_next = create_info_vk.p_next;
             create_info_vk.p_next = info as *const _ as *const _;
         }
 
+        if let Some(info) = library_create_info_vk.as_mut() {
+            info.p_next = create_info_vk.p_next as *mut _;
+            create_info_vk.p_next = info as *const _ as *const _;
+        }
+
+        if let Some(info) = pipeline_library_create_info_vk.as_mut() {
+            info.p_next = create_info_vk.p_next;
+            create_info_vk.p_next = info as *const _ as *const _;
+        }
+
+        let mut creation_feedback_vk = ash::vk::PipelineCreationFeedback::default();
+        let mut stage_creation_feedbacks_vk: SmallVec<[_; 5]> =
+            smallvec![ash::vk::PipelineCreationFeedback::default(); stages_vk.len()];
+        let mut creation_feedback_create_info_vk = None;
+
+        if PipelineCreationFeedback::is_supported(&device) {
+            let info = creation_feedback_create_info_vk.insert(
+                ash::vk::PipelineCreationFeedbackCreateInfo {
+                    p_pipeline_creation_feedback: &mut creation_feedback_vk,
+                    pipeline_stage_creation_feedback_count: stage_creation_feedbacks_vk.len()
+                        as u32,
+                    p_pipeline_stage_creation_feedbacks: stage_creation_feedbacks_vk.as_mut_ptr(),
+                    ..Default::default()
+                },
+            );
+            info.p_next = create_info_vk.p_next;
+            create_info_vk.p_next = info as *const _ as *const _;
+        }
+
         let cache_handle = match cache.as_ref() {
             Some(cache) => cache.handle(),
             None => ash::vk::PipelineCache::null(),
@@ -855,7 +1076,15 @@ impl GraphicsPipeline {
                 output.as_mut_ptr(),
             )
             .result()
-            .map_err(VulkanError::from)?;
+            .map_err(|err| {
+                log_event!(
+                    warn,
+                    "pipeline",
+                    "failed to create a graphics pipeline: {:?}",
+                    err
+                );
+                VulkanError::from(err)
+            })?;
 
             output.assume_init()
         };
@@ -867,7 +1096,31 @@ impl GraphicsPipeline {
             panic!("vkCreateGraphicsPipelines provided a NULL handle");
         }
 
-        Ok(Self::from_handle(device, handle, create_info))
+        log_event!(
+            debug,
+            "pipeline",
+            "created graphics pipeline 0x{:x} (cached: {})",
+            ash::vk::Handle::as_raw(handle),
+            cache.is_some(),
+        );
+
+        let creation_feedback = PipelineCreationFeedback::from_vk(&creation_feedback_vk);
+        let stage_creation_feedback = stages
+            .iter()
+            .zip(&stage_creation_feedbacks_vk)
+            .filter_map(|(stage, feedback_vk)| {
+                let stage = ShaderStage::from(stage.entry_point.info().execution_model);
+                PipelineCreationFeedback::from_vk(feedback_vk).map(|feedback| (stage, feedback))
+            })
+            .collect();
+
+        Ok(Self::from_handle_with_creation_feedback(
+            device,
+            handle,
+            create_info,
+            creation_feedback,
+            stage_creation_feedback,
+        ))
     }
 
     /// Creates a new `GraphicsPipeline` from a raw object handle.
@@ -882,25 +1135,47 @@ impl GraphicsPipeline {
         handle: ash::vk::Pipeline,
         create_info: GraphicsPipelineCreateInfo,
     ) -> Arc<Self> {
+        Self::from_handle_with_creation_feedback(
+            device,
+            handle,
+            create_info,
+            None,
+            HashMap::default(),
+        )
+    }
+
+    unsafe fn from_handle_with_creation_feedback(
+        device: Arc<Device>,
+        handle: ash::vk::Pipeline,
+        create_info: GraphicsPipelineCreateInfo,
+        creation_feedback: Option<PipelineCreationFeedback>,
+        stage_creation_feedback: HashMap<ShaderStage, PipelineCreationFeedback>,
+    ) -> Arc<Self> {
+        let mut library_flags = create_info.own_library_flags();
+
         let GraphicsPipelineCreateInfo {
             flags,
             stages,
 
-            vertex_input_state,
-            input_assembly_state,
-            tessellation_state,
-            viewport_state,
-            rasterization_state,
-            multisample_state,
-            depth_stencil_state,
-            color_blend_state,
-            dynamic_state,
+            mut vertex_input_state,
+            mut input_assembly_state,
+            mut tessellation_state,
+            mut viewport_state,
+            mut rasterization_state,
+            mut multisample_state,
+            mut depth_stencil_state,
+            mut color_blend_state,
+            mut dynamic_state,
 
             layout,
-            subpass,
+            mut subpass,
             base_pipeline: _,
 
-            discard_rectangle_state,
+            library_flags: _,
+            libraries,
+
+            mut discard_rectangle_state,
+            mut fragment_shading_rate_state,
 
             _ne: _,
         } = create_info;
@@ -954,6 +1229,46 @@ impl GraphicsPipeline {
             }
         }
 
+        // Take the subsets of state that were not provided by the create info from the libraries.
+        for library in &libraries {
+            library_flags |= library.library_flags;
+            shaders.extend(
+                library
+                    .shaders
+                    .iter()
+                    .map(|(&stage, &shader)| (stage, shader)),
+            );
+            fragment_tests_stages = fragment_tests_stages.or(library.fragment_tests_stages);
+            dynamic_state.extend(library.dynamic_state.iter().copied());
+
+            for (&loc, reqs) in &library.descriptor_binding_requirements {
+                match descriptor_binding_requirements.entry(loc) {
+                    Entry::Occupied(entry) => {
+                        entry.into_mut().merge(reqs).expect("Could not produce an intersection of the shader descriptor requirements");
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(reqs.clone());
+                    }
+                }
+            }
+
+            vertex_input_state = vertex_input_state.or_else(|| library.vertex_input_state.clone());
+            input_assembly_state = input_assembly_state.or(library.input_assembly_state);
+            tessellation_state = tessellation_state.or(library.tessellation_state);
+            viewport_state = viewport_state.or_else(|| library.viewport_state.clone());
+            rasterization_state =
+                rasterization_state.or_else(|| library.rasterization_state.clone());
+            multisample_state = multisample_state.or_else(|| library.multisample_state.clone());
+            depth_stencil_state =
+                depth_stencil_state.or_else(|| library.depth_stencil_state.clone());
+            color_blend_state = color_blend_state.or_else(|| library.color_blend_state.clone());
+            subpass = subpass.or_else(|| library.subpass.clone());
+            discard_rectangle_state =
+                discard_rectangle_state.or_else(|| library.discard_rectangle_state.clone());
+            fragment_shading_rate_state =
+                fragment_shading_rate_state.or_else(|| library.fragment_shading_rate_state.clone());
+        }
+
         let num_used_descriptor_sets = descriptor_binding_requirements
             .keys()
             .map(|loc| loc.0)
@@ -963,6 +1278,10 @@ impl GraphicsPipeline {
 
         let mut fixed_state: HashSet<DynamicState> = Default::default();
 
+        if vertex_input_state.is_some() {
+            fixed_state.extend([DynamicState::VertexInputBindingStride]);
+        }
+
         if input_assembly_state.is_some() {
             fixed_state.extend([
                 DynamicState::PrimitiveTopology,
@@ -985,6 +1304,8 @@ impl GraphicsPipeline {
 
         if rasterization_state.is_some() {
             fixed_state.extend([
+                DynamicState::DepthClampEnable,
+                DynamicState::DepthClipEnable,
                 DynamicState::RasterizerDiscardEnable,
                 DynamicState::CullMode,
                 DynamicState::FrontFace,
@@ -992,6 +1313,14 @@ impl GraphicsPipeline {
                 DynamicState::DepthBias,
                 DynamicState::LineWidth,
                 DynamicState::LineStipple,
+                DynamicState::ProvokingVertexMode,
+            ]);
+        }
+
+        if multisample_state.is_some() {
+            fixed_state.extend([
+                DynamicState::SampleLocationsEnable,
+                DynamicState::SampleLocations,
             ]);
         }
 
@@ -1019,11 +1348,21 @@ impl GraphicsPipeline {
         }
 
         if discard_rectangle_state.is_some() {
-            fixed_state.extend([DynamicState::DiscardRectangle]);
+            fixed_state.extend([
+                DynamicState::DiscardRectangle,
+                DynamicState::DiscardRectangleEnable,
+                DynamicState::DiscardRectangleMode,
+            ]);
+        }
+
+        if fragment_shading_rate_state.is_some() {
+            fixed_state.insert(DynamicState::FragmentShadingRate);
         }
 
         fixed_state.retain(|state| !dynamic_state.contains(state));
 
+        device.counters.pipelines.fetch_add(1, Ordering::Relaxed);
+
         Arc::new(Self {
             handle,
             device: InstanceOwnedDebugWrapper(device),
@@ -1035,21 +1374,26 @@ impl GraphicsPipeline {
             num_used_descriptor_sets,
             fragment_tests_stages,
 
-            vertex_input_state: vertex_input_state.unwrap(), // Can be None if there's a mesh shader, but we don't support that yet
-            input_assembly_state: input_assembly_state.unwrap(), // Can be None if there's a mesh shader, but we don't support that yet
+            vertex_input_state,
+            input_assembly_state,
             tessellation_state,
             viewport_state,
-            rasterization_state: rasterization_state.unwrap(), // Can be None for pipeline libraries, but we don't support that yet
+            rasterization_state,
             multisample_state,
             depth_stencil_state,
             color_blend_state,
             dynamic_state,
             layout: DeviceOwnedDebugWrapper(layout),
-            subpass: subpass.unwrap(),
+            subpass,
+            library_flags,
 
             discard_rectangle_state,
+            fragment_shading_rate_state,
 
             fixed_state,
+
+            creation_feedback,
+            stage_creation_feedback,
         })
     }
 
@@ -1065,6 +1409,67 @@ impl GraphicsPipeline {
         self.flags
     }
 
+    /// Returns the properties of the executables that the implementation compiled the pipeline
+    /// into.
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device.
+    #[inline]
+    pub fn executable_properties(
+        &self,
+    ) -> Result<Vec<PipelineExecutableProperties>, Validated<VulkanError>> {
+        executable::executable_properties(&self.device, self.handle)
+    }
+
+    /// Returns statistics about the executable with index `executable_index`, as returned by
+    /// [`executable_properties`](Self::executable_properties).
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device, and the pipeline must have been created with
+    /// [`PipelineCreateFlags::CAPTURE_STATISTICS`].
+    #[inline]
+    pub fn executable_statistics(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableStatistic>, Validated<VulkanError>> {
+        executable::executable_statistics(&self.device, self.handle, self.flags, executable_index)
+    }
+
+    /// Returns the internal representations of the executable with index `executable_index`,
+    /// as returned by [`executable_properties`](Self::executable_properties).
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device, and the pipeline must have been created with
+    /// [`PipelineCreateFlags::CAPTURE_INTERNAL_REPRESENTATIONS`].
+    #[inline]
+    pub fn executable_internal_representations(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableInternalRepresentation>, Validated<VulkanError>> {
+        executable::executable_internal_representations(
+            &self.device,
+            self.handle,
+            self.flags,
+            executable_index,
+        )
+    }
+
+    /// Returns the feedback that the implementation provided about the creation of the
+    /// pipeline as a whole, or `None` if it was not provided.
+    #[inline]
+    pub fn creation_feedback(&self) -> Option<&PipelineCreationFeedback> {
+        self.creation_feedback.as_ref()
+    }
+
+    /// Returns the feedback that the implementation provided about the creation of each shader
+    /// stage of the pipeline.
+    ///
+    /// Stages for which the implementation did not provide feedback are not included.
+    #[inline]
+    pub fn stage_creation_feedback(&self) -> &HashMap<ShaderStage, PipelineCreationFeedback> {
+        &self.stage_creation_feedback
+    }
+
     /// Returns information about a particular shader.
     ///
     /// `None` is returned if the pipeline does not contain this shader.
@@ -1078,15 +1483,21 @@ impl GraphicsPipeline {
     }
 
     /// Returns the vertex input state used to create this pipeline.
+    ///
+    /// This is `None` if the pipeline is a library that does not contain the vertex input
+    /// interface.
     #[inline]
-    pub fn vertex_input_state(&self) -> &VertexInputState {
-        &self.vertex_input_state
+    pub fn vertex_input_state(&self) -> Option<&VertexInputState> {
+        self.vertex_input_state.as_ref()
     }
 
     /// Returns the input assembly state used to create this pipeline.
+    ///
+    /// This is `None` if the pipeline is a library that does not contain the vertex input
+    /// interface.
     #[inline]
-    pub fn input_assembly_state(&self) -> &InputAssemblyState {
-        &self.input_assembly_state
+    pub fn input_assembly_state(&self) -> Option<&InputAssemblyState> {
+        self.input_assembly_state.as_ref()
     }
 
     /// Returns the tessellation state used to create this pipeline.
@@ -1102,9 +1513,12 @@ impl GraphicsPipeline {
     }
 
     /// Returns the rasterization state used to create this pipeline.
+    ///
+    /// This is `None` if the pipeline is a library that does not contain the pre-rasterization
+    /// shaders.
     #[inline]
-    pub fn rasterization_state(&self) -> &RasterizationState {
-        &self.rasterization_state
+    pub fn rasterization_state(&self) -> Option<&RasterizationState> {
+        self.rasterization_state.as_ref()
     }
 
     /// Returns the multisample state used to create this pipeline.
@@ -1126,9 +1540,21 @@ impl GraphicsPipeline {
     }
 
     /// Returns the subpass this graphics pipeline is rendering to.
+    ///
+    /// This is `None` if the pipeline is a library that contains only the vertex input
+    /// interface.
     #[inline]
-    pub fn subpass(&self) -> &PipelineSubpassType {
-        &self.subpass
+    pub fn subpass(&self) -> Option<&PipelineSubpassType> {
+        self.subpass.as_ref()
+    }
+
+    /// Returns the subsets of graphics pipeline state that the pipeline contains, either
+    /// directly or through the libraries that were linked into it.
+    ///
+    /// For a pipeline that is not a library, this contains all subsets.
+    #[inline]
+    pub fn library_flags(&self) -> GraphicsPipelineLibraryFlags {
+        self.library_flags
     }
 
     /// Returns the dynamic states of the pipeline.
@@ -1143,6 +1569,12 @@ impl GraphicsPipeline {
         self.discard_rectangle_state.as_ref()
     }
 
+    /// Returns the fragment shading rate state used to create this pipeline.
+    #[inline]
+    pub fn fragment_shading_rate_state(&self) -> Option<&FragmentShadingRateState> {
+        self.fragment_shading_rate_state.as_ref()
+    }
+
     /// If the pipeline has a fragment shader, returns the fragment tests stages used.
     #[inline]
     pub fn fragment_tests_stages(&self) -> Option<FragmentTestsStages> {
@@ -1203,6 +1635,11 @@ impl Drop for GraphicsPipeline {
             let fns = self.device.fns();
             (fns.v1_0.destroy_pipeline)(self.device.handle(), self.handle, ptr::null());
         }
+
+        self.device
+            .counters
+            .pipelines
+            .fetch_sub(1, Ordering::Relaxed);
     }
 }
 
@@ -1315,6 +1752,36 @@ pub struct GraphicsPipelineCreateInfo {
     /// The default value is `None`.
     pub base_pipeline: Option<Arc<GraphicsPipeline>>,
 
+    /// The subsets of the graphics pipeline state that are provided by this create info, if it
+    /// is used to create a pipeline library or to link pipeline libraries.
+    ///
+    /// If `flags` contains [`PipelineCreateFlags::LIBRARY`] or `libraries` is not empty, then
+    /// only the states that belong to the subsets in `library_flags` are used, and the states
+    /// of all other subsets must be `None`. Otherwise, `library_flags` must be either empty or
+    /// contain all subsets, and a complete pipeline is created.
+    ///
+    /// If this is not empty, the
+    /// [`graphics_pipeline_library`](crate::device::Features::graphics_pipeline_library)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is empty.
+    pub library_flags: GraphicsPipelineLibraryFlags,
+
+    /// Pipeline libraries to link into the pipeline.
+    ///
+    /// Each library must have been created with [`PipelineCreateFlags::LIBRARY`], and the
+    /// subsets of state that each library provides must not overlap with `library_flags`, or
+    /// with those of the other libraries. If `flags` does not contain
+    /// [`PipelineCreateFlags::LIBRARY`], then together with `library_flags`, the libraries
+    /// must provide all the subsets of state that are needed for a complete pipeline.
+    ///
+    /// If this is not empty, the
+    /// [`graphics_pipeline_library`](crate::device::Features::graphics_pipeline_library)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is empty.
+    pub libraries: Vec<Arc<GraphicsPipeline>>,
+
     /// The discard rectangle state.
     ///
     /// This state is always used if it is provided.
@@ -1322,6 +1789,14 @@ pub struct GraphicsPipelineCreateInfo {
     /// The default value is `None`.
     pub discard_rectangle_state: Option<DiscardRectangleState>,
 
+    /// The fragment shading rate state.
+    ///
+    /// If this is `None`, then the pipeline fragment shading rate is `[1, 1]` and both combiner
+    /// operations are `Keep`, unless the state is set dynamically.
+    ///
+    /// The default value is `None`.
+    pub fragment_shading_rate_state: Option<FragmentShadingRateState>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -1347,11 +1822,25 @@ impl GraphicsPipelineCreateInfo {
             subpass: None,
             base_pipeline: None,
 
+            library_flags: GraphicsPipelineLibraryFlags::empty(),
+            libraries: Vec::new(),
+
             discard_rectangle_state: None,
+            fragment_shading_rate_state: None,
             _ne: crate::NonExhaustive(()),
         }
     }
 
+    /// Returns the subsets of state that are provided by the create info itself, rather than
+    /// by `libraries`.
+    fn own_library_flags(&self) -> GraphicsPipelineLibraryFlags {
+        if self.flags.intersects(PipelineCreateFlags::LIBRARY) || !self.libraries.is_empty() {
+            self.library_flags
+        } else {
+            GraphicsPipelineLibraryFlags::COMPLETE
+        }
+    }
+
     pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
         let &Self {
             flags,
@@ -1371,7 +1860,11 @@ impl GraphicsPipelineCreateInfo {
             ref subpass,
             ref base_pipeline,
 
+            library_flags,
+            ref libraries,
+
             ref discard_rectangle_state,
+            ref fragment_shading_rate_state,
             _ne: _,
         } = self;
 
@@ -1380,6 +1873,109 @@ impl GraphicsPipelineCreateInfo {
                 .set_vuids(&["VUID-VkGraphicsPipelineCreateInfo-flags-parameter"])
         })?;
 
+        library_flags.validate_device(device).map_err(|err| {
+            err.add_context("library_flags")
+                .set_vuids(&["VUID-VkGraphicsPipelineLibraryCreateInfoEXT-flags-parameter"])
+        })?;
+
+        if flags.intersects(PipelineCreateFlags::LIBRARY)
+            && !device.enabled_features().graphics_pipeline_library
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `PipelineCreateFlags::LIBRARY`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "graphics_pipeline_library",
+                )])]),
+                vuids: &["VUID-VkGraphicsPipelineCreateInfo-graphicsPipelineLibrary-06606"],
+            }));
+        }
+
+        if !library_flags.is_empty() && !device.enabled_features().graphics_pipeline_library {
+            return Err(Box::new(ValidationError {
+                context: "library_flags".into(),
+                problem: "is not empty".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "graphics_pipeline_library",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        if !libraries.is_empty() && !device.enabled_features().graphics_pipeline_library {
+            return Err(Box::new(ValidationError {
+                context: "libraries".into(),
+                problem: "is not empty".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "graphics_pipeline_library",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        let own_library_flags = self.own_library_flags();
+
+        if !flags.intersects(PipelineCreateFlags::LIBRARY)
+            && libraries.is_empty()
+            && !library_flags.is_empty()
+            && library_flags != GraphicsPipelineLibraryFlags::COMPLETE
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`flags` does not contain `PipelineCreateFlags::LIBRARY`, and \
+                    `libraries` is empty, but `library_flags` is neither empty nor contains \
+                    all subsets of state"
+                    .into(),
+                ..Default::default()
+            }));
+        }
+
+        let mut libraries_library_flags = GraphicsPipelineLibraryFlags::empty();
+
+        for (library_index, library) in libraries.iter().enumerate() {
+            // VUID-VkPipelineLibraryCreateInfoKHR-pLibraries-parameter
+            assert_eq!(device, library.device().as_ref());
+
+            if !library.flags().intersects(PipelineCreateFlags::LIBRARY) {
+                return Err(Box::new(ValidationError {
+                    context: format!("libraries[{}].flags()", library_index).into(),
+                    problem: "does not contain `PipelineCreateFlags::LIBRARY`".into(),
+                    vuids: &["VUID-VkPipelineLibraryCreateInfoKHR-pLibraries-03381"],
+                    ..Default::default()
+                }));
+            }
+
+            if library
+                .library_flags()
+                .intersects(own_library_flags | libraries_library_flags)
+            {
+                return Err(Box::new(ValidationError {
+                    context: format!("libraries[{}].library_flags()", library_index).into(),
+                    problem: "contains subsets of state that are also provided by \
+                        `library_flags`, or by another element of `libraries`"
+                        .into(),
+                    ..Default::default()
+                }));
+            }
+
+            libraries_library_flags |= library.library_flags();
+
+            let library_layout = library.layout();
+            let num_sets = layout.set_layouts().len() as u32;
+
+            if library_layout.set_layouts().len() as u32 != num_sets
+                || !layout.is_compatible_with(library_layout, num_sets)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`layout` is not compatible with `libraries[{}].layout()`",
+                        library_index
+                    )
+                    .into(),
+                    ..Default::default()
+                }));
+            }
+        }
+
         if flags.intersects(PipelineCreateFlags::DERIVATIVE) {
             let base_pipeline = base_pipeline.as_ref().ok_or_else(|| {
                 Box::new(ValidationError {
@@ -1420,6 +2016,8 @@ impl GraphicsPipelineCreateInfo {
         let mut tessellation_control_stage = None;
         let mut tessellation_evaluation_stage = None;
         let mut geometry_stage = None;
+        let mut task_stage = None;
+        let mut mesh_stage = None;
         let mut fragment_stage = None;
 
         for (stage_index, stage) in stages.iter().enumerate() {
@@ -1443,7 +2041,7 @@ impl GraphicsPipelineCreateInfo {
 
             const PRIMITIVE_SHADING_STAGES: ShaderStages = ShaderStages::VERTEX
                 .union(ShaderStages::TESSELLATION_CONTROL)
-                .union(ShaderStages::TESSELLATION_CONTROL)
+                .union(ShaderStages::TESSELLATION_EVALUATION)
                 .union(ShaderStages::GEOMETRY);
             const MESH_SHADING_STAGES: ShaderStages = ShaderStages::MESH.union(ShaderStages::TASK);
 
@@ -1466,6 +2064,8 @@ impl GraphicsPipelineCreateInfo {
                 ShaderStage::TessellationControl => &mut tessellation_control_stage,
                 ShaderStage::TessellationEvaluation => &mut tessellation_evaluation_stage,
                 ShaderStage::Geometry => &mut geometry_stage,
+                ShaderStage::Task => &mut task_stage,
+                ShaderStage::Mesh => &mut mesh_stage,
                 ShaderStage::Fragment => &mut fragment_stage,
                 _ => {
                     return Err(Box::new(ValidationError {
@@ -1485,7 +2085,8 @@ impl GraphicsPipelineCreateInfo {
             Validate needed/unused state
         */
 
-        let need_pre_rasterization_shader_state = true;
+        let need_pre_rasterization_shader_state =
+            own_library_flags.intersects(GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS);
 
         // Check this first because everything else depends on it.
         match (
@@ -1515,32 +2116,73 @@ impl GraphicsPipelineCreateInfo {
             _ => (),
         }
 
-        let need_vertex_input_state = need_pre_rasterization_shader_state
-            && stages.iter().any(|stage| {
+        // If the pre-rasterization shader state is provided by a library, the rasterizer discard
+        // state of that library applies.
+        let rasterizer_discard_enable = if need_pre_rasterization_shader_state {
+            let rasterization_state = rasterization_state.as_ref().unwrap();
+            rasterization_state.rasterizer_discard_enable
+                && !dynamic_state.contains(&DynamicState::RasterizerDiscardEnable)
+        } else {
+            libraries.iter().any(|library| {
                 matches!(
-                    stage.entry_point.info().execution_model,
-                    ExecutionModel::Vertex
-                )
-            });
-        let need_fragment_shader_state = need_pre_rasterization_shader_state
-            && (!rasterization_state
-                .as_ref()
-                .unwrap()
-                .rasterizer_discard_enable
-                || dynamic_state.contains(&DynamicState::RasterizerDiscardEnable));
-        let need_fragment_output_state = need_pre_rasterization_shader_state
-            && (!rasterization_state
-                .as_ref()
-                .unwrap()
-                .rasterizer_discard_enable
-                || dynamic_state.contains(&DynamicState::RasterizerDiscardEnable));
+                    library.rasterization_state(),
+                    Some(rasterization_state) if rasterization_state.rasterizer_discard_enable
+                ) && !library
+                    .dynamic_state()
+                    .contains(&DynamicState::RasterizerDiscardEnable)
+            })
+        };
+
+        // Mesh shading pipelines have no vertex input state, even if it is requested.
+        let is_mesh_shading = mesh_stage.is_some()
+            || libraries
+                .iter()
+                .any(|library| library.shader(ShaderStage::Mesh).is_some());
+
+        let need_vertex_input_state = own_library_flags
+            .intersects(GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE)
+            && !is_mesh_shading;
+        let need_fragment_shader_state = own_library_flags
+            .intersects(GraphicsPipelineLibraryFlags::FRAGMENT_SHADER)
+            && !rasterizer_discard_enable;
+        let need_fragment_output_state = own_library_flags
+            .intersects(GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE)
+            && !rasterizer_discard_enable;
+
+        if !flags.intersects(PipelineCreateFlags::LIBRARY) {
+            let mut required_library_flags =
+                GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS;
+
+            if !is_mesh_shading {
+                required_library_flags |= GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE;
+            }
+
+            if !rasterizer_discard_enable {
+                required_library_flags |= GraphicsPipelineLibraryFlags::FRAGMENT_SHADER
+                    | GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE;
+            }
+
+            if !(own_library_flags | libraries_library_flags).contains(required_library_flags) {
+                return Err(Box::new(ValidationError {
+                    problem: "`flags` does not contain `PipelineCreateFlags::LIBRARY`, but \
+                        `library_flags` and the library flags of the elements of `libraries` \
+                        together do not contain all subsets of state that are needed for a \
+                        complete pipeline"
+                        .into(),
+                    ..Default::default()
+                }));
+            }
+        }
 
-        match (vertex_stage.is_some(), need_pre_rasterization_shader_state) {
+        match (
+            vertex_stage.is_some() || mesh_stage.is_some(),
+            need_pre_rasterization_shader_state,
+        ) {
             (true, false) => {
                 return Err(Box::new(ValidationError {
                     problem: "the pipeline is not being created with \
                         pre-rasterization shader state, but `stages` contains a \
-                        `ShaderStage::Vertex` stage"
+                        `ShaderStage::Vertex` or `ShaderStage::Mesh` stage"
                         .into(),
                     vuids: &["VUID-VkGraphicsPipelineCreateInfo-pStages-06895"],
                     ..Default::default()
@@ -1550,7 +2192,7 @@ impl GraphicsPipelineCreateInfo {
                 return Err(Box::new(ValidationError {
                     problem: "the pipeline is being created with \
                         pre-rasterization shader state, but `stages` does not contain a \
-                        `ShaderStage::Vertex` stage"
+                        `ShaderStage::Vertex` or `ShaderStage::Mesh` stage"
                         .into(),
                     vuids: &["VUID-VkGraphicsPipelineCreateInfo-stage-02096"],
                     ..Default::default()
@@ -1637,12 +2279,23 @@ impl GraphicsPipelineCreateInfo {
             _ => (),
         }
 
+        if task_stage.is_some() && !need_pre_rasterization_shader_state {
+            return Err(Box::new(ValidationError {
+                problem: "the pipeline is not being created with \
+                    pre-rasterization shader state, but `stages` contains a \
+                    `ShaderStage::Task` stage"
+                    .into(),
+                vuids: &["VUID-VkGraphicsPipelineCreateInfo-pStages-06895"],
+                ..Default::default()
+            }));
+        }
+
         match (fragment_stage.is_some(), need_fragment_shader_state) {
             (true, false) => {
                 return Err(Box::new(ValidationError {
                     problem: "the pipeline is not being created with \
                         fragment shader state, but `stages` contains a \
-                        `ShaderStage::Geometry` stage"
+                        `ShaderStage::Fragment` stage"
                         .into(),
                     vuids: &["VUID-VkGraphicsPipelineCreateInfo-pStages-06894"],
                     ..Default::default()
@@ -1731,12 +2384,7 @@ impl GraphicsPipelineCreateInfo {
 
         match (
             viewport_state.is_some(),
-            need_pre_rasterization_shader_state
-                && (!rasterization_state
-                    .as_ref()
-                    .unwrap()
-                    .rasterizer_discard_enable
-                    || dynamic_state.contains(&DynamicState::RasterizerDiscardEnable)),
+            need_pre_rasterization_shader_state && !rasterizer_discard_enable,
         ) {
             (true, false) => {
                 return Err(Box::new(ValidationError {
@@ -1766,10 +2414,15 @@ impl GraphicsPipelineCreateInfo {
         }
 
         match (multisample_state.is_some(), need_fragment_output_state) {
-            (true, false) => {
+            (true, false)
+                if !own_library_flags.intersects(
+                    GraphicsPipelineLibraryFlags::FRAGMENT_SHADER
+                        | GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE,
+                ) =>
+            {
                 return Err(Box::new(ValidationError {
                     problem: "the pipeline is not being created with \
-                        fragment output state, but \
+                        fragment shader or fragment output state, but \
                         `multisample_state` is `Some`"
                         .into(),
                     ..Default::default()
@@ -1790,19 +2443,20 @@ impl GraphicsPipelineCreateInfo {
 
         match (
             depth_stencil_state.is_some(),
-            !need_fragment_output_state
-                || match subpass {
+            need_fragment_shader_state
+                && match subpass {
                     Some(PipelineSubpassType::BeginRenderPass(subpass)) => {
                         subpass.subpass_desc().depth_stencil_attachment.is_some()
                     }
                     Some(PipelineSubpassType::BeginRendering(rendering_info)) => {
-                        rendering_info.depth_attachment_format.is_some()
+                        !need_fragment_output_state
+                            || rendering_info.depth_attachment_format.is_some()
                             || rendering_info.stencil_attachment_format.is_some()
                     }
                     None => false,
                 },
         ) {
-            (true, false) => {
+            (true, false) if need_fragment_shader_state && need_fragment_output_state => {
                 return Err(Box::new(ValidationError {
                     problem: "the pipeline is being created with \
                         fragment output state, and \
@@ -1812,11 +2466,23 @@ impl GraphicsPipelineCreateInfo {
                     ..Default::default()
                 }));
             }
-            (false, true) => {
+            (true, false)
+                if !own_library_flags.intersects(GraphicsPipelineLibraryFlags::FRAGMENT_SHADER) =>
+            {
                 return Err(Box::new(ValidationError {
                     problem: "the pipeline is not being created with \
-                        fragment output state, or \
-                        `subpass` has a depth/stencil attachment, but \
+                        fragment shader state, but \
+                        `depth_stencil_state` is `Some`"
+                        .into(),
+                    ..Default::default()
+                }));
+            }
+            (false, true) => {
+                return Err(Box::new(ValidationError {
+                    problem: "the pipeline is being created with \
+                        fragment shader state, and \
+                        `subpass` has a depth/stencil attachment or the pipeline is not being \
+                        created with fragment output state, but \
                         `depth_stencil_state` is `None`"
                         .into(),
                     vuids: &[
@@ -1916,6 +2582,18 @@ impl GraphicsPipelineCreateInfo {
             _ => (),
         }
 
+        if fragment_shading_rate_state.is_some()
+            && !(need_pre_rasterization_shader_state || need_fragment_shader_state)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the pipeline is not being created with \
+                    pre-rasterization or fragment shader state, but \
+                    `fragment_shading_rate_state` is `Some`"
+                    .into(),
+                ..Default::default()
+            }));
+        }
+
         /*
             Validate shader stages individually
         */
@@ -1956,6 +2634,7 @@ impl GraphicsPipelineCreateInfo {
             tessellation_control_stage,
             tessellation_evaluation_stage,
             geometry_stage,
+            mesh_stage,
             fragment_stage,
         ]
         .into_iter()
@@ -2023,6 +2702,7 @@ impl GraphicsPipelineCreateInfo {
             let ViewportState {
                 ref viewports,
                 ref scissors,
+                depth_clip_negative_one_to_one: _,
                 _ne: _,
             } = viewport_state;
 
@@ -2101,6 +2781,7 @@ impl GraphicsPipelineCreateInfo {
 
             let &RasterizationState {
                 depth_clamp_enable: _,
+                depth_clip_enable: _,
                 rasterizer_discard_enable: _,
                 polygon_mode: _,
                 cull_mode: _,
@@ -2109,6 +2790,11 @@ impl GraphicsPipelineCreateInfo {
                 line_width,
                 line_rasterization_mode: _,
                 line_stipple,
+                provoking_vertex_mode: _,
+                rasterization_order: _,
+                rasterization_stream: _,
+                conservative_rasterization_mode: _,
+                extra_primitive_overestimation_size: _,
                 _ne: _,
             } = rasterization_state;
 
@@ -2180,6 +2866,35 @@ impl GraphicsPipelineCreateInfo {
                 .validate(device)
                 .map_err(|err| err.add_context("multisample_state"))?;
 
+            if let Some(sample_locations) = &multisample_state.sample_locations {
+                if !dynamic_state.contains(&DynamicState::SampleLocations) {
+                    if sample_locations.sample_locations_per_pixel
+                        != multisample_state.rasterization_samples
+                    {
+                        return Err(Box::new(ValidationError {
+                            problem: "`multisample_state.sample_locations` is `Some`, and \
+                                `dynamic_state` does not contain \
+                                `DynamicState::SampleLocations`, but \
+                                `multisample_state.sample_locations.sample_locations_per_pixel` \
+                                does not equal `multisample_state.rasterization_samples`"
+                                .into(),
+                            vuids: &["VUID-VkGraphicsPipelineCreateInfo-pDynamicStates-01523"],
+                            ..Default::default()
+                        }));
+                    }
+
+                    sample_locations
+                        .validate_grid_size(device, multisample_state.rasterization_samples)
+                        .map_err(|err| {
+                            err.add_context("multisample_state.sample_locations")
+                                .set_vuids(&[
+                                    "VUID-VkGraphicsPipelineCreateInfo-pDynamicStates-01521",
+                                    "VUID-VkGraphicsPipelineCreateInfo-pDynamicStates-01522",
+                                ])
+                        })?;
+                }
+            }
+
             // TODO:
             // VUID-VkGraphicsPipelineCreateInfo-lineRasterizationMode-02766
         }
@@ -2354,11 +3069,53 @@ impl GraphicsPipelineCreateInfo {
                 .map_err(|err| err.add_context("discard_rectangle_state"))?;
         }
 
+        if let Some(fragment_shading_rate_state) = fragment_shading_rate_state {
+            if !device.enabled_extensions().khr_fragment_shading_rate {
+                return Err(Box::new(ValidationError {
+                    context: "fragment_shading_rate_state".into(),
+                    problem: "is `Some`".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                        "khr_fragment_shading_rate",
+                    )])]),
+                    ..Default::default()
+                }));
+            }
+
+            if !dynamic_state.contains(&DynamicState::FragmentShadingRate) {
+                fragment_shading_rate_state
+                    .validate(device)
+                    .map_err(|err| err.add_context("fragment_shading_rate_state"))?;
+            }
+        }
+
         for dynamic_state in dynamic_state.iter().copied() {
             dynamic_state.validate_device(device).map_err(|err| {
                 err.add_context("dynamic_state")
                     .set_vuids(&["VUID-VkPipelineDynamicStateCreateInfo-pDynamicStates-parameter"])
             })?;
+
+            if is_mesh_shading
+                && matches!(
+                    dynamic_state,
+                    DynamicState::PrimitiveTopology
+                        | DynamicState::PrimitiveRestartEnable
+                        | DynamicState::VertexInputBindingStride
+                )
+            {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`stages` contains a `ShaderStage::Mesh` stage, but \
+                        `dynamic_state` contains `DynamicState::{:?}`",
+                        dynamic_state
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkGraphicsPipelineCreateInfo-pDynamicStates-07065",
+                        "VUID-VkGraphicsPipelineCreateInfo-pDynamicStates-07066",
+                    ],
+                    ..Default::default()
+                }));
+            }
         }
 
         /*
@@ -2416,14 +3173,82 @@ impl GraphicsPipelineCreateInfo {
             }
         }
 
-        if let (Some(_), Some(_)) = (tessellation_control_stage, tessellation_evaluation_stage) {
-            // FIXME: must check that the control shader and evaluation shader are compatible
+        if let (Some(tessellation_control_stage), Some(tessellation_evaluation_stage)) =
+            (tessellation_control_stage, tessellation_evaluation_stage)
+        {
+            // Returns the subdivision type and the output patch size that are declared by the
+            // execution modes of the shader.
+            let tessellation_modes = |stage: &PipelineShaderStageCreateInfo| {
+                let spirv = stage.entry_point.module().spirv();
+                let entry_point_function = spirv.function(stage.entry_point.id());
+                let mut subdivision = None;
+                let mut output_vertices = None;
 
-            // TODO:
-            // VUID-VkGraphicsPipelineCreateInfo-pStages-00732
-            // VUID-VkGraphicsPipelineCreateInfo-pStages-00733
-            // VUID-VkGraphicsPipelineCreateInfo-pStages-00734
-            // VUID-VkGraphicsPipelineCreateInfo-pStages-00735
+                for instruction in entry_point_function.iter_execution_mode() {
+                    if let Instruction::ExecutionMode { mode, .. } = *instruction {
+                        match mode {
+                            ExecutionMode::Triangles
+                            | ExecutionMode::Quads
+                            | ExecutionMode::Isolines => subdivision = Some(mode),
+                            ExecutionMode::OutputVertices { vertex_count } => {
+                                output_vertices = Some(vertex_count)
+                            }
+                            _ => (),
+                        }
+                    }
+                }
+
+                (subdivision, output_vertices)
+            };
+
+            let (control_subdivision, control_output_vertices) =
+                tessellation_modes(tessellation_control_stage);
+            let (evaluation_subdivision, evaluation_output_vertices) =
+                tessellation_modes(tessellation_evaluation_stage);
+
+            match (control_subdivision, evaluation_subdivision) {
+                (None, None) => {
+                    return Err(Box::new(ValidationError {
+                        problem: "neither the tessellation control shader nor the tessellation \
+                            evaluation shader specifies a tessellation subdivision type"
+                            .into(),
+                        vuids: &["VUID-VkGraphicsPipelineCreateInfo-pStages-00732"],
+                        ..Default::default()
+                    }));
+                }
+                (Some(control), Some(evaluation)) if control != evaluation => {
+                    return Err(Box::new(ValidationError {
+                        problem: "the tessellation control shader and the tessellation \
+                            evaluation shader specify different tessellation subdivision types"
+                            .into(),
+                        vuids: &["VUID-VkGraphicsPipelineCreateInfo-pStages-00733"],
+                        ..Default::default()
+                    }));
+                }
+                _ => (),
+            }
+
+            match (control_output_vertices, evaluation_output_vertices) {
+                (None, None) => {
+                    return Err(Box::new(ValidationError {
+                        problem: "neither the tessellation control shader nor the tessellation \
+                            evaluation shader specifies an output patch size"
+                            .into(),
+                        vuids: &["VUID-VkGraphicsPipelineCreateInfo-pStages-00734"],
+                        ..Default::default()
+                    }));
+                }
+                (Some(control), Some(evaluation)) if control != evaluation => {
+                    return Err(Box::new(ValidationError {
+                        problem: "the tessellation control shader and the tessellation \
+                            evaluation shader specify different output patch sizes"
+                            .into(),
+                        vuids: &["VUID-VkGraphicsPipelineCreateInfo-pStages-00735"],
+                        ..Default::default()
+                    }));
+                }
+                _ => (),
+            }
         }
 
         if let (Some(_), Some(_)) = (tessellation_evaluation_stage, geometry_stage) {
@@ -2499,6 +3324,57 @@ impl GraphicsPipelineCreateInfo {
             // VUID-VkGraphicsPipelineCreateInfo-renderPass-06061
         }
 
+        if let (Some(fragment_stage), Some(color_blend_state)) = (fragment_stage, color_blend_state)
+        {
+            let uses_dual_source = color_blend_state
+                .attachments
+                .iter()
+                .filter_map(|attachment| attachment.blend.as_ref())
+                .any(|blend| {
+                    let &AttachmentBlend {
+                        src_color_blend_factor,
+                        dst_color_blend_factor,
+                        color_blend_op: _,
+                        src_alpha_blend_factor,
+                        dst_alpha_blend_factor,
+                        alpha_blend_op: _,
+                    } = blend;
+
+                    [
+                        src_color_blend_factor,
+                        dst_color_blend_factor,
+                        src_alpha_blend_factor,
+                        dst_alpha_blend_factor,
+                    ]
+                    .into_iter()
+                    .any(BlendFactor::is_dual_source)
+                });
+
+            if uses_dual_source {
+                let output_interface = &fragment_stage.entry_point.info().output_interface;
+
+                let max_fragment_dual_src_attachments = device
+                    .physical_device()
+                    .properties()
+                    .max_fragment_dual_src_attachments;
+
+                if output_interface.elements().iter().any(|element| {
+                    element.location + element.ty.num_locations()
+                        > max_fragment_dual_src_attachments
+                }) {
+                    return Err(Box::new(ValidationError {
+                        problem: "`color_blend_state.attachments` contains a blend factor that \
+                            uses the second source value, but the fragment shader writes to an \
+                            output location that is not less than the \
+                            `max_fragment_dual_src_attachments` limit"
+                            .into(),
+                        vuids: &["VUID-RuntimeSpirv-Fragment-06427"],
+                        ..Default::default()
+                    }));
+                }
+            }
+        }
+
         if let (Some(input_assembly_state), Some(_)) = (input_assembly_state, tessellation_state) {
             if input_assembly_state.topology != PrimitiveTopology::PatchList {
                 return Err(Box::new(ValidationError {
@@ -2511,6 +3387,38 @@ impl GraphicsPipelineCreateInfo {
             }
         }
 
+        if let (None, Some(input_assembly_state), Some(rasterization_state)) =
+            (geometry_stage, input_assembly_state, rasterization_state)
+        {
+            if rasterization_state.conservative_rasterization_mode
+                != ConservativeRasterizationMode::Disabled
+                && matches!(
+                    input_assembly_state.topology,
+                    PrimitiveTopology::PointList
+                        | PrimitiveTopology::LineList
+                        | PrimitiveTopology::LineStrip
+                )
+                && !device
+                    .physical_device()
+                    .properties()
+                    .conservative_point_and_line_rasterization
+                    .unwrap_or(false)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "`stages` does not contain a geometry shader, \
+                        `input_assembly_state.topology` is a point or line topology, and \
+                        `rasterization_state.conservative_rasterization_mode` is not \
+                        `ConservativeRasterizationMode::Disabled`, but the \
+                        `conservative_point_and_line_rasterization` property is `false`"
+                        .into(),
+                    vuids: &[
+                        "VUID-VkGraphicsPipelineCreateInfo-conservativePointAndLineRasterization-08892",
+                    ],
+                    ..Default::default()
+                }));
+            }
+        }
+
         if let (Some(rasterization_state), Some(depth_stencil_state)) =
             (rasterization_state, depth_stencil_state)
         {
@@ -2566,6 +3474,56 @@ impl GraphicsPipelineCreateInfo {
         }
 
         if let (Some(depth_stencil_state), Some(subpass)) = (depth_stencil_state, subpass) {
+            for (state_flag, subpass_flag, render_pass_vuids, rendering_vuids) in [
+                (
+                    DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS,
+                    SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS,
+                    &["VUID-VkGraphicsPipelineCreateInfo-renderPass-06467"],
+                    &["VUID-VkGraphicsPipelineCreateInfo-flags-06483"],
+                ),
+                (
+                    DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS,
+                    SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS,
+                    &["VUID-VkGraphicsPipelineCreateInfo-renderPass-06468"],
+                    &["VUID-VkGraphicsPipelineCreateInfo-flags-06483"],
+                ),
+            ] {
+                if !depth_stencil_state.flags.intersects(state_flag) {
+                    continue;
+                }
+
+                match subpass {
+                    PipelineSubpassType::BeginRenderPass(subpass) => {
+                        if !subpass.subpass_desc().flags.intersects(subpass_flag) {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "`depth_stencil_state.flags` contains \
+                                    `DepthStencilStateFlags::{:?}`, but the flags of \
+                                    `subpass` do not contain `SubpassDescriptionFlags::{0:?}`",
+                                    state_flag,
+                                )
+                                .into(),
+                                vuids: render_pass_vuids,
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                    PipelineSubpassType::BeginRendering(_) => {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "`depth_stencil_state.flags` contains \
+                                `DepthStencilStateFlags::{:?}`, but `subpass` is \
+                                `PipelineSubpassType::BeginRendering`",
+                                state_flag,
+                            )
+                            .into(),
+                            vuids: rendering_vuids,
+                            ..Default::default()
+                        }));
+                    }
+                }
+            }
+
             if let Some(depth_state) = &depth_stencil_state.depth {
                 let has_depth_attachment = match subpass {
                     PipelineSubpassType::BeginRenderPass(subpass) => subpass
@@ -2664,6 +3622,40 @@ impl GraphicsPipelineCreateInfo {
         }
 
         if let (Some(color_blend_state), Some(subpass)) = (color_blend_state, subpass) {
+            if color_blend_state
+                .flags
+                .intersects(ColorBlendStateFlags::RASTERIZATION_ORDER_ATTACHMENT_ACCESS)
+            {
+                match subpass {
+                    PipelineSubpassType::BeginRenderPass(subpass) => {
+                        if !subpass.subpass_desc().flags.intersects(
+                            SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS,
+                        ) {
+                            return Err(Box::new(ValidationError {
+                                problem: "`color_blend_state.flags` contains \
+                                    `ColorBlendStateFlags::RASTERIZATION_ORDER_ATTACHMENT_ACCESS`, \
+                                    but the flags of `subpass` do not contain \
+                                    `SubpassDescriptionFlags::\
+                                    RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS`"
+                                    .into(),
+                                vuids: &["VUID-VkGraphicsPipelineCreateInfo-renderPass-06466"],
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                    PipelineSubpassType::BeginRendering(_) => {
+                        return Err(Box::new(ValidationError {
+                            problem: "`color_blend_state.flags` contains \
+                                `ColorBlendStateFlags::RASTERIZATION_ORDER_ATTACHMENT_ACCESS`, \
+                                but `subpass` is `PipelineSubpassType::BeginRendering`"
+                                .into(),
+                            vuids: &["VUID-VkGraphicsPipelineCreateInfo-flags-06482"],
+                            ..Default::default()
+                        }));
+                    }
+                }
+            }
+
             let color_attachment_count = match subpass {
                 PipelineSubpassType::BeginRenderPass(subpass) => {
                     subpass.subpass_desc().color_attachments.len()
@@ -2734,6 +3726,50 @@ impl GraphicsPipelineCreateInfo {
     }
 }
 
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Subsets of the state of a graphics pipeline, that can be created separately as pipeline
+    /// libraries and then linked together.
+    GraphicsPipelineLibraryFlags = GraphicsPipelineLibraryFlagsEXT(u32);
+
+    /// The vertex input interface: `vertex_input_state` and `input_assembly_state`.
+    VERTEX_INPUT_INTERFACE = VERTEX_INPUT_INTERFACE
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_graphics_pipeline_library)]),
+    ]),
+
+    /// The pre-rasterization shaders: the vertex, tessellation and geometry shader stages,
+    /// `tessellation_state`, `viewport_state`, `rasterization_state` and
+    /// `discard_rectangle_state` and `fragment_shading_rate_state`, as well as `subpass`.
+    PRE_RASTERIZATION_SHADERS = PRE_RASTERIZATION_SHADERS
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_graphics_pipeline_library)]),
+    ]),
+
+    /// The fragment shader: the fragment shader stage, `depth_stencil_state`,
+    /// `fragment_shading_rate_state` and optionally `multisample_state`, as well as `subpass`.
+    FRAGMENT_SHADER = FRAGMENT_SHADER
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_graphics_pipeline_library)]),
+    ]),
+
+    /// The fragment output interface: `color_blend_state` and `multisample_state`, as well as
+    /// `subpass`.
+    FRAGMENT_OUTPUT_INTERFACE = FRAGMENT_OUTPUT_INTERFACE
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_graphics_pipeline_library)]),
+    ]),
+}
+
+impl GraphicsPipelineLibraryFlags {
+    /// All subsets of state, which together make up a complete pipeline.
+    const COMPLETE: Self = Self::VERTEX_INPUT_INTERFACE
+        .union(Self::PRE_RASTERIZATION_SHADERS)
+        .union(Self::FRAGMENT_SHADER)
+        .union(Self::FRAGMENT_OUTPUT_INTERFACE);
+}
+
 /// The input primitive type that is expected by a geometry shader.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 enum GeometryShaderInput {
@@ -13,11 +13,12 @@
 use crate::{
     device::Device, image::SampleCount, Requires, RequiresAllOf, RequiresOneOf, ValidationError,
 };
+use smallvec::SmallVec;
 
 // TODO: handle some weird behaviors with non-floating-point targets
 
 /// State of the multisampling.
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct MultisampleState {
     /// The number of rasterization samples to take per pixel. The GPU will pick this many different
     /// locations within each pixel and assign to each of these locations a different depth value.
@@ -64,6 +65,25 @@ pub struct MultisampleState {
     /// The default value is `false`.
     pub alpha_to_one_enable: bool,
 
+    /// Custom locations of the samples within each pixel, replacing the standard sample
+    /// locations.
+    ///
+    /// When [`DynamicState::SampleLocationsEnable`] is used, whether custom sample locations are
+    /// enabled is set dynamically, and the value of this field is ignored.
+    /// When [`DynamicState::SampleLocations`] is used, the contained locations are ignored and
+    /// must be set dynamically, but custom sample locations are still only enabled if this is
+    /// `Some`.
+    ///
+    /// If set to `Some`, the
+    /// [`ext_sample_locations`](crate::device::DeviceExtensions::ext_sample_locations)
+    /// extension must be enabled on the device.
+    ///
+    /// The default value is `None`.
+    ///
+    /// [`DynamicState::SampleLocationsEnable`]: crate::pipeline::DynamicState::SampleLocationsEnable
+    /// [`DynamicState::SampleLocations`]: crate::pipeline::DynamicState::SampleLocations
+    pub sample_locations: Option<SampleLocationsInfo>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -76,6 +96,7 @@ impl Default for MultisampleState {
             sample_mask: [u32::MAX; 2],
             alpha_to_coverage_enable: false,
             alpha_to_one_enable: false,
+            sample_locations: None,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -96,6 +117,7 @@ impl MultisampleState {
             sample_mask: _,
             alpha_to_coverage_enable: _,
             alpha_to_one_enable,
+            ref sample_locations,
             _ne: _,
         } = self;
 
@@ -140,6 +162,189 @@ impl MultisampleState {
             }));
         }
 
+        if let Some(sample_locations) = sample_locations {
+            if !device.enabled_extensions().ext_sample_locations {
+                return Err(Box::new(ValidationError {
+                    context: "sample_locations".into(),
+                    problem: "is `Some`".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                        "ext_sample_locations",
+                    )])]),
+                    ..Default::default()
+                }));
+            }
+
+            sample_locations
+                .validate(device)
+                .map_err(|err| err.add_context("sample_locations"))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A set of custom sample locations.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SampleLocationsInfo {
+    /// The number of samples per pixel that the sample locations are specified for.
+    ///
+    /// The default value is [`SampleCount::Sample1`].
+    pub sample_locations_per_pixel: SampleCount,
+
+    /// The width and height in pixels of the grid of pixels that the sample locations are
+    /// specified for. The sample locations are repeated across the framebuffer in this grid.
+    ///
+    /// The default value is `[1; 2]`.
+    pub sample_location_grid_size: [u32; 2],
+
+    /// The locations of the samples, as `[x, y]` coordinates within the pixel, where `[0.0, 0.0]`
+    /// is the top left corner. The locations are given for each sample of each pixel of the grid,
+    /// in sample-major, then x-major, then y-major order. The coordinates are clamped to the
+    /// [`sample_location_coordinate_range`] device property.
+    ///
+    /// The length must be equal to `sample_locations_per_pixel` multiplied by the width and
+    /// height of `sample_location_grid_size`.
+    ///
+    /// The default value is `[[0.5, 0.5]]`.
+    ///
+    /// [`sample_location_coordinate_range`]: crate::device::Properties::sample_location_coordinate_range
+    pub sample_locations: Vec<[f32; 2]>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for SampleLocationsInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            sample_locations_per_pixel: SampleCount::Sample1,
+            sample_location_grid_size: [1; 2],
+            sample_locations: vec![[0.5; 2]],
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl SampleLocationsInfo {
+    pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            sample_locations_per_pixel,
+            sample_location_grid_size,
+            ref sample_locations,
+            _ne: _,
+        } = self;
+
+        let properties = device.physical_device().properties();
+
+        sample_locations_per_pixel
+            .validate_device(device)
+            .map_err(|err| {
+                err.add_context("sample_locations_per_pixel")
+                    .set_vuids(&["VUID-VkSampleLocationsInfoEXT-sampleLocationsPerPixel-parameter"])
+            })?;
+
+        if !properties
+            .sample_location_sample_counts
+            .unwrap_or_default()
+            .contains_enum(sample_locations_per_pixel)
+        {
+            return Err(Box::new(ValidationError {
+                context: "sample_locations_per_pixel".into(),
+                problem: "is not one of the values in the `sample_location_sample_counts` \
+                    device property"
+                    .into(),
+                vuids: &["VUID-VkSampleLocationsInfoEXT-sampleLocationsPerPixel-01526"],
+                ..Default::default()
+            }));
+        }
+
+        if sample_locations.len() as u64
+            != sample_locations_per_pixel as u64
+                * sample_location_grid_size[0] as u64
+                * sample_location_grid_size[1] as u64
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the length of `sample_locations` does not equal \
+                    `sample_locations_per_pixel` multiplied by the width and height of \
+                    `sample_location_grid_size`"
+                    .into(),
+                vuids: &["VUID-VkSampleLocationsInfoEXT-sampleLocationsCount-01527"],
+                ..Default::default()
+            }));
+        }
+
         Ok(())
     }
+
+    /// Validates that the grid size is compatible with `samples`, as required when the sample
+    /// locations are used for rasterization.
+    pub(crate) fn validate_grid_size(
+        &self,
+        device: &Device,
+        samples: SampleCount,
+    ) -> Result<(), Box<ValidationError>> {
+        let max_sample_location_grid_size = unsafe {
+            device
+                .physical_device()
+                .multisample_properties_unchecked(samples)
+        }
+        .max_sample_location_grid_size;
+
+        if max_sample_location_grid_size[0] % self.sample_location_grid_size[0].max(1) != 0 {
+            return Err(Box::new(ValidationError {
+                problem: "`sample_location_grid_size[0]` does not evenly divide the \
+                    `max_sample_location_grid_size[0]` multisample property for the \
+                    number of samples"
+                    .into(),
+                ..Default::default()
+            }));
+        }
+
+        if max_sample_location_grid_size[1] % self.sample_location_grid_size[1].max(1) != 0 {
+            return Err(Box::new(ValidationError {
+                problem: "`sample_location_grid_size[1]` does not evenly divide the \
+                    `max_sample_location_grid_size[1]` multisample property for the \
+                    number of samples"
+                    .into(),
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn to_vk(
+        &self,
+        sample_locations_vk: &[ash::vk::SampleLocationEXT],
+    ) -> ash::vk::SampleLocationsInfoEXT {
+        let [width, height] = self.sample_location_grid_size;
+
+        ash::vk::SampleLocationsInfoEXT {
+            sample_locations_per_pixel: self.sample_locations_per_pixel.into(),
+            sample_location_grid_size: ash::vk::Extent2D { width, height },
+            sample_locations_count: sample_locations_vk.len() as u32,
+            p_sample_locations: sample_locations_vk.as_ptr(),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn to_vk_sample_locations(&self) -> SmallVec<[ash::vk::SampleLocationEXT; 16]> {
+        self.sample_locations
+            .iter()
+            .map(|&[x, y]| ash::vk::SampleLocationEXT { x, y })
+            .collect()
+    }
+}
+
+/// Properties of a physical device for a specific number of samples.
+///
+/// Returned by [`PhysicalDevice::multisample_properties`].
+///
+/// [`PhysicalDevice::multisample_properties`]: crate::device::physical::PhysicalDevice::multisample_properties
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct MultisampleProperties {
+    /// The maximum size of the pixel grid that custom sample locations can be specified for.
+    /// The `sample_location_grid_size` of a [`SampleLocationsInfo`] must evenly divide this.
+    pub max_sample_location_grid_size: [u32; 2],
 }
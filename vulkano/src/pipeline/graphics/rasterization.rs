@@ -25,6 +25,20 @@ pub struct RasterizationState {
     /// The default value is `false`.
     pub depth_clamp_enable: bool,
 
+    /// Explicitly sets whether fragments whose depth is outside of the range [0.0, 1.0] should be
+    /// discarded, independently of `depth_clamp_enable`.
+    ///
+    /// If set to `None`, depth clipping is enabled if and only if `depth_clamp_enable` is false.
+    /// Setting this to `Some` and `depth_clamp_enable` to `true` allows depth values to be
+    /// clamped without discarding fragments, or the reverse.
+    ///
+    /// If set to `Some`, the
+    /// [`depth_clip_enable`](crate::device::Features::depth_clip_enable) feature must be
+    /// enabled on the device.
+    ///
+    /// The default value is `None`.
+    pub depth_clip_enable: Option<bool>,
+
     /// If true, all the fragments will be discarded, and the fragment shader will not be run. This
     /// is usually used when your vertex shader has some side effects and you don't need to run the
     /// fragment shader.
@@ -87,6 +101,59 @@ pub struct RasterizationState {
     /// The default value is `None`.
     pub line_stipple: Option<LineStipple>,
 
+    /// The vertex of each primitive that provides the value of flat-shaded (non-interpolated)
+    /// vertex attributes.
+    ///
+    /// If this is not set to `FirstVertex`, the
+    /// [`ext_provoking_vertex`](crate::device::DeviceExtensions::ext_provoking_vertex)
+    /// extension must be enabled on the device.
+    ///
+    /// The default value is [`ProvokingVertexMode::FirstVertex`].
+    pub provoking_vertex_mode: ProvokingVertexMode,
+
+    /// The order in which primitives are rasterized.
+    ///
+    /// If this is not set to `Strict`, the
+    /// [`amd_rasterization_order`](crate::device::DeviceExtensions::amd_rasterization_order)
+    /// extension must be enabled on the device.
+    ///
+    /// The default value is [`RasterizationOrder::Strict`].
+    pub rasterization_order: RasterizationOrder,
+
+    /// The vertex stream that is rasterized, when the pipeline writes vertices to several streams
+    /// for transform feedback.
+    ///
+    /// If this is not set to `0`, the
+    /// [`geometry_streams`](crate::device::Features::geometry_streams) feature must be enabled
+    /// on the device, and the
+    /// [`transform_feedback_rasterization_stream_select`](crate::device::Properties::transform_feedback_rasterization_stream_select)
+    /// device property must be `true`.
+    ///
+    /// The default value is `0`.
+    pub rasterization_stream: u32,
+
+    /// The conservative rasterization mode to use.
+    ///
+    /// If this is not set to `Disabled`, the
+    /// [`ext_conservative_rasterization`](crate::device::DeviceExtensions::ext_conservative_rasterization)
+    /// extension must be enabled on the device.
+    ///
+    /// The default value is [`ConservativeRasterizationMode::Disabled`].
+    pub conservative_rasterization_mode: ConservativeRasterizationMode,
+
+    /// The extra size in pixels to increase the generating primitive during conservative
+    /// rasterization, when `conservative_rasterization_mode` is
+    /// [`ConservativeRasterizationMode::Overestimate`]. This is in addition to the base
+    /// [`primitive_overestimation_size`](crate::device::Properties::primitive_overestimation_size)
+    /// of the implementation.
+    ///
+    /// The value must be between 0.0 and the
+    /// [`max_extra_primitive_overestimation_size`](crate::device::Properties::max_extra_primitive_overestimation_size)
+    /// device property.
+    ///
+    /// The default value is `0.0`.
+    pub extra_primitive_overestimation_size: f32,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -95,6 +162,7 @@ impl Default for RasterizationState {
     fn default() -> Self {
         Self {
             depth_clamp_enable: false,
+            depth_clip_enable: None,
             rasterizer_discard_enable: false,
             polygon_mode: Default::default(),
             cull_mode: Default::default(),
@@ -103,6 +171,11 @@ impl Default for RasterizationState {
             line_width: 1.0,
             line_rasterization_mode: Default::default(),
             line_stipple: None,
+            provoking_vertex_mode: Default::default(),
+            rasterization_order: Default::default(),
+            rasterization_stream: 0,
+            conservative_rasterization_mode: Default::default(),
+            extra_primitive_overestimation_size: 0.0,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -145,6 +218,7 @@ impl RasterizationState {
     pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
         let &Self {
             depth_clamp_enable,
+            depth_clip_enable,
             rasterizer_discard_enable,
             polygon_mode,
             cull_mode,
@@ -153,6 +227,11 @@ impl RasterizationState {
             line_width: _,
             line_rasterization_mode,
             ref line_stipple,
+            provoking_vertex_mode,
+            rasterization_order,
+            rasterization_stream,
+            conservative_rasterization_mode,
+            extra_primitive_overestimation_size,
             _ne: _,
         } = self;
 
@@ -178,6 +257,17 @@ impl RasterizationState {
             }));
         }
 
+        if depth_clip_enable.is_some() && !device.enabled_features().depth_clip_enable {
+            return Err(Box::new(ValidationError {
+                context: "depth_clip_enable".into(),
+                problem: "is `Some`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "depth_clip_enable",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
         if polygon_mode != PolygonMode::Fill && !device.enabled_features().fill_mode_non_solid {
             return Err(Box::new(ValidationError {
                 context: "polygon_mode".into(),
@@ -217,6 +307,82 @@ impl RasterizationState {
                 .set_vuids(&["VUID-VkPipelineRasterizationStateCreateInfo-frontFace-parameter"])
         })?;
 
+        provoking_vertex_mode.validate_device(device).map_err(|err| {
+            err.add_context("provoking_vertex_mode").set_vuids(&[
+                "VUID-VkPipelineRasterizationProvokingVertexStateCreateInfoEXT-provokingVertexMode-parameter",
+            ])
+        })?;
+
+        if provoking_vertex_mode == ProvokingVertexMode::LastVertex
+            && !device.enabled_features().provoking_vertex_last
+        {
+            return Err(Box::new(ValidationError {
+                context: "provoking_vertex_mode".into(),
+                problem: "is `ProvokingVertexMode::LastVertex`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "provoking_vertex_last",
+                )])]),
+                vuids: &["VUID-VkPipelineRasterizationProvokingVertexStateCreateInfoEXT-provokingVertexMode-04883"],
+            }));
+        }
+
+        rasterization_order.validate_device(device).map_err(|err| {
+            err.add_context("rasterization_order").set_vuids(&[
+                "VUID-VkPipelineRasterizationStateRasterizationOrderAMD-rasterizationOrder-parameter",
+            ])
+        })?;
+
+        if rasterization_stream != 0 {
+            if !device.enabled_extensions().ext_transform_feedback {
+                return Err(Box::new(ValidationError {
+                    context: "rasterization_stream".into(),
+                    problem: "is not 0".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                        "ext_transform_feedback",
+                    )])]),
+                    ..Default::default()
+                }));
+            }
+
+            if !device.enabled_features().geometry_streams {
+                return Err(Box::new(ValidationError {
+                    context: "rasterization_stream".into(),
+                    problem: "is not 0".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "geometry_streams",
+                    )])]),
+                    vuids: &["VUID-VkPipelineRasterizationStateStreamCreateInfoEXT-geometryStreams-02324"],
+                }));
+            }
+
+            if rasterization_stream
+                >= properties
+                    .max_transform_feedback_streams
+                    .unwrap_or_default()
+            {
+                return Err(Box::new(ValidationError {
+                    context: "rasterization_stream".into(),
+                    problem: "is not less than the `max_transform_feedback_streams` limit".into(),
+                    vuids: &["VUID-VkPipelineRasterizationStateStreamCreateInfoEXT-rasterizationStream-02325"],
+                    ..Default::default()
+                }));
+            }
+
+            if !properties
+                .transform_feedback_rasterization_stream_select
+                .unwrap_or_default()
+            {
+                return Err(Box::new(ValidationError {
+                    context: "rasterization_stream".into(),
+                    problem: "is not 0, but the `transform_feedback_rasterization_stream_select` \
+                        property is `false`"
+                        .into(),
+                    vuids: &["VUID-VkPipelineRasterizationStateStreamCreateInfoEXT-rasterizationStream-02326"],
+                    ..Default::default()
+                }));
+            }
+        }
+
         if line_rasterization_mode != LineRasterizationMode::Default {
             if !device.enabled_extensions().ext_line_rasterization {
                 return Err(Box::new(ValidationError {
@@ -353,6 +519,54 @@ impl RasterizationState {
             }
         }
 
+        conservative_rasterization_mode
+            .validate_device(device)
+            .map_err(|err| {
+                err.add_context("conservative_rasterization_mode").set_vuids(&[
+                    "VUID-VkPipelineRasterizationConservativeStateCreateInfoEXT-conservativeRasterizationMode-parameter",
+                ])
+            })?;
+
+        if conservative_rasterization_mode == ConservativeRasterizationMode::Underestimate
+            && !properties.primitive_underestimation.unwrap_or(false)
+        {
+            return Err(Box::new(ValidationError {
+                context: "conservative_rasterization_mode".into(),
+                problem: "is `ConservativeRasterizationMode::Underestimate`, but the \
+                    `primitive_underestimation` property is `false`"
+                    .into(),
+                ..Default::default()
+            }));
+        }
+
+        if extra_primitive_overestimation_size != 0.0 {
+            if !device.enabled_extensions().ext_conservative_rasterization {
+                return Err(Box::new(ValidationError {
+                    context: "extra_primitive_overestimation_size".into(),
+                    problem: "is not 0.0".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                        "ext_conservative_rasterization",
+                    )])]),
+                    ..Default::default()
+                }));
+            }
+
+            if !(0.0..=properties
+                .max_extra_primitive_overestimation_size
+                .unwrap_or(0.0))
+                .contains(&extra_primitive_overestimation_size)
+            {
+                return Err(Box::new(ValidationError {
+                    context: "extra_primitive_overestimation_size".into(),
+                    problem: "is not between 0.0 and the \
+                        `max_extra_primitive_overestimation_size` limit"
+                        .into(),
+                    vuids: &["VUID-VkPipelineRasterizationConservativeStateCreateInfoEXT-extraPrimitiveOverestimationSize-01769"],
+                    ..Default::default()
+                }));
+            }
+        }
+
         Ok(())
     }
 }
@@ -526,6 +740,101 @@ impl Default for LineRasterizationMode {
     }
 }
 
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The vertex of a primitive that provides the values of flat-shaded vertex attributes.
+    ProvokingVertexMode = ProvokingVertexModeEXT(i32);
+
+    /// The first vertex of each primitive is the provoking vertex. This is the Vulkan default.
+    FirstVertex = FIRST_VERTEX,
+
+    /// The last vertex of each primitive is the provoking vertex. This matches the default
+    /// convention of OpenGL.
+    ///
+    /// The [`provoking_vertex_last`](crate::device::Features::provoking_vertex_last) feature must
+    /// be enabled on the device.
+    LastVertex = LAST_VERTEX
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_provoking_vertex)]),
+    ]),
+}
+
+impl Default for ProvokingVertexMode {
+    /// Returns `ProvokingVertexMode::FirstVertex`.
+    #[inline]
+    fn default() -> Self {
+        Self::FirstVertex
+    }
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The order in which primitives are rasterized within a subpass.
+    RasterizationOrder = RasterizationOrderAMD(i32);
+
+    /// Primitives are rasterized in the order that they are submitted, as required by the
+    /// Vulkan specification. This is the Vulkan default.
+    Strict = STRICT,
+
+    /// Primitives may be rasterized in any order. Results that depend on the order of
+    /// rasterization, such as blending or depth testing with a non-strict comparison, may differ
+    /// from `Strict`, but this can improve performance.
+    Relaxed = RELAXED
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(amd_rasterization_order)]),
+    ]),
+}
+
+impl Default for RasterizationOrder {
+    /// Returns `RasterizationOrder::Strict`.
+    #[inline]
+    fn default() -> Self {
+        Self::Strict
+    }
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The mode of conservative rasterization, which changes how the rasterizer determines which
+    /// pixels a primitive covers.
+    ConservativeRasterizationMode = ConservativeRasterizationModeEXT(i32);
+
+    /// Conservative rasterization is disabled. A pixel is covered if its sample locations are
+    /// covered by the primitive. This is the Vulkan default.
+    Disabled = DISABLED,
+
+    /// A fragment is generated for every pixel that the primitive touches at all. The primitive
+    /// is enlarged by the
+    /// [`primitive_overestimation_size`](crate::device::Properties::primitive_overestimation_size)
+    /// property plus
+    /// [`RasterizationState::extra_primitive_overestimation_size`] when determining coverage.
+    Overestimate = OVERESTIMATE
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_conservative_rasterization)]),
+    ]),
+
+    /// A fragment is generated only for pixels that are fully covered by the primitive.
+    ///
+    /// The
+    /// [`primitive_underestimation`](crate::device::Properties::primitive_underestimation)
+    /// device property must be `true`.
+    Underestimate = UNDERESTIMATE
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_conservative_rasterization)]),
+    ]),
+}
+
+impl Default for ConservativeRasterizationMode {
+    /// Returns `ConservativeRasterizationMode::Disabled`.
+    #[inline]
+    fn default() -> Self {
+        Self::Disabled
+    }
+}
+
 /// The parameters of a stippled line.
 #[derive(Clone, Copy, Debug)]
 pub struct LineStipple {
@@ -8,6 +8,7 @@
 // according to those terms.
 
 use crate::format::Format;
+use half::f16;
 
 /// Implements the `Vertex` trait on a struct.
 ///
@@ -123,6 +124,7 @@ impl_vertex_member!(i16, R16_SINT);
 impl_vertex_member!(u16, R16_UINT);
 impl_vertex_member!(i32, R32_SINT);
 impl_vertex_member!(u32, R32_UINT);
+impl_vertex_member!(f16, R16_SFLOAT);
 impl_vertex_member!(f32, R32_SFLOAT);
 impl_vertex_member!(f64, R64_SFLOAT);
 impl_vertex_member!([i8; 2], R8G8_SINT);
@@ -131,6 +133,7 @@ impl_vertex_member!([i16; 2], R16G16_SINT);
 impl_vertex_member!([u16; 2], R16G16_UINT);
 impl_vertex_member!([i32; 2], R32G32_SINT);
 impl_vertex_member!([u32; 2], R32G32_UINT);
+impl_vertex_member!([f16; 2], R16G16_SFLOAT);
 impl_vertex_member!([f32; 2], R32G32_SFLOAT);
 impl_vertex_member!([f64; 2], R64G64_SFLOAT);
 impl_vertex_member!([i8; 3], R8G8B8_SINT);
@@ -139,6 +142,7 @@ impl_vertex_member!([i16; 3], R16G16B16_SINT);
 impl_vertex_member!([u16; 3], R16G16B16_UINT);
 impl_vertex_member!([i32; 3], R32G32B32_SINT);
 impl_vertex_member!([u32; 3], R32G32B32_UINT);
+impl_vertex_member!([f16; 3], R16G16B16_SFLOAT);
 impl_vertex_member!([f32; 3], R32G32B32_SFLOAT);
 impl_vertex_member!([f64; 3], R64G64B64_SFLOAT);
 impl_vertex_member!([i8; 4], R8G8B8A8_SINT);
@@ -147,6 +151,7 @@ impl_vertex_member!([i16; 4], R16G16B16A16_SINT);
 impl_vertex_member!([u16; 4], R16G16B16A16_UINT);
 impl_vertex_member!([i32; 4], R32G32B32A32_SINT);
 impl_vertex_member!([u32; 4], R32G32B32A32_UINT);
+impl_vertex_member!([f16; 4], R16G16B16A16_SFLOAT);
 impl_vertex_member!([f32; 4], R32G32B32A32_SFLOAT);
 impl_vertex_member!([f64; 4], R64G64B64A64_SFLOAT);
 impl_vertex_member!([f32; 9], R32G32B32_SFLOAT);
@@ -197,4 +202,28 @@ mod tests {
         assert_eq!(scalar.offset, 16 * 5);
         assert_eq!(scalar.num_elements, 1);
     }
+
+    #[test]
+    #[allow(deprecated)]
+    fn impl_vertex_f16() {
+        use half::f16;
+
+        #[repr(C)]
+        #[derive(Clone, Copy, Debug, Default, Zeroable, Pod)]
+        struct TestVertex {
+            position: [f16; 4],
+            weight: f16,
+            _padding: f16,
+        }
+        impl_vertex!(TestVertex, position, weight);
+
+        let info = TestVertex::per_vertex();
+        let position = info.members.get("position").unwrap();
+        let weight = info.members.get("weight").unwrap();
+        assert_eq!(position.format, Format::R16G16B16A16_SFLOAT);
+        assert_eq!(position.offset, 0);
+        assert_eq!(weight.format, Format::R16_SFLOAT);
+        assert_eq!(weight.offset, 8);
+        assert_eq!(info.stride, 12);
+    }
 }
@@ -317,6 +317,7 @@ impl Default for VertexInputState {
 
 /// Describes a single vertex buffer binding.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VertexInputBindingDescription {
     /// The number of bytes from the start of one element in the vertex buffer to the start of the
     /// next element. This can be simply the size of the data in each element, but larger strides
@@ -413,6 +414,7 @@ impl VertexInputBindingDescription {
 
 /// Describes a single vertex buffer attribute mapping.
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VertexInputAttributeDescription {
     /// The vertex buffer binding number that this attribute should take its data from.
     pub binding: u32,
@@ -486,6 +488,7 @@ impl VertexInputAttributeDescription {
 
 /// How the vertex source should be unrolled.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VertexInputRate {
     /// Each element of the source corresponds to a vertex.
     Vertex,
@@ -33,6 +33,22 @@
 //! If you don't use a geometry shader or use a geometry shader where don't set which viewport to
 //! use, then the first viewport and scissor box will be used.
 //!
+//! Using more than one viewport requires the
+//! [`multi_viewport`](crate::device::Features::multi_viewport) feature to be enabled on the
+//! device, and the number of viewports can't exceed the
+//! [`max_viewports`](crate::device::Properties::max_viewports) limit.
+//!
+//! The viewport index can also be written from a vertex or tessellation evaluation shader,
+//! without needing a geometry shader. This requires the
+//! [`shader_output_viewport_index`](crate::device::Features::shader_output_viewport_index)
+//! feature, or the
+//! [`ext_shader_viewport_index_layer`](crate::device::DeviceExtensions::ext_shader_viewport_index_layer)
+//! extension. This is useful for rendering to several viewports in a single pass, for example
+//! both eyes of a stereo image, or all cascades of a shadow map atlas.
+//!
+//! The viewports and scissors can also be set dynamically, including their number, with
+//! [`DynamicState::ViewportWithCount`] and [`DynamicState::ScissorWithCount`].
+//!
 //! # Dynamic and fixed
 //!
 //! Vulkan allows four different setups:
@@ -47,6 +63,8 @@
 //!
 //! In all cases the number of viewports and scissor boxes must be the same.
 //!
+//! [`DynamicState::ViewportWithCount`]: crate::pipeline::DynamicState::ViewportWithCount
+//! [`DynamicState::ScissorWithCount`]: crate::pipeline::DynamicState::ScissorWithCount
 
 use crate::{device::Device, Requires, RequiresAllOf, RequiresOneOf, ValidationError, Version};
 use smallvec::{smallvec, SmallVec};
@@ -91,6 +109,17 @@ pub struct ViewportState {
     /// [`DynamicState::ScissorWithCount`]: crate::pipeline::DynamicState::ScissorWithCount
     pub scissors: SmallVec<[Scissor; 1]>,
 
+    /// Whether the Z coordinate of vertices in clip space ranges from `-1.0` to `1.0`, as in
+    /// OpenGL, rather than from `0.0` to `1.0`, which is the Vulkan default.
+    ///
+    /// Setting this to `true` lets you use projection matrices written for OpenGL without
+    /// having to remap the depth range. The
+    /// [`depth_clip_control`](crate::device::Features::depth_clip_control) feature must then be
+    /// enabled on the device.
+    ///
+    /// The default value is `false`.
+    pub depth_clip_negative_one_to_one: bool,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -100,6 +129,7 @@ impl Default for ViewportState {
         Self {
             viewports: smallvec![Viewport::default()],
             scissors: smallvec![Scissor::default()],
+            depth_clip_negative_one_to_one: false,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -113,6 +143,7 @@ impl ViewportState {
         Self {
             viewports: SmallVec::new(),
             scissors: SmallVec::new(),
+            depth_clip_negative_one_to_one: false,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -126,6 +157,7 @@ impl ViewportState {
         Self {
             viewports,
             scissors,
+            depth_clip_negative_one_to_one: false,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -139,14 +171,16 @@ impl ViewportState {
         Self {
             viewports,
             scissors,
+            depth_clip_negative_one_to_one: false,
             _ne: crate::NonExhaustive(()),
         }
     }
 
     pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
-        let Self {
-            viewports,
-            scissors,
+        let &Self {
+            ref viewports,
+            ref scissors,
+            depth_clip_negative_one_to_one,
             _ne: _,
         } = self;
 
@@ -231,6 +265,30 @@ impl ViewportState {
             }));
         }
 
+        if depth_clip_negative_one_to_one {
+            if !device.enabled_extensions().ext_depth_clip_control {
+                return Err(Box::new(ValidationError {
+                    context: "depth_clip_negative_one_to_one".into(),
+                    problem: "is `true`".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                        "ext_depth_clip_control",
+                    )])]),
+                    ..Default::default()
+                }));
+            }
+
+            if !device.enabled_features().depth_clip_control {
+                return Err(Box::new(ValidationError {
+                    context: "depth_clip_negative_one_to_one".into(),
+                    problem: "is `true`".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "depth_clip_control",
+                    )])]),
+                    vuids: &["VUID-VkPipelineViewportDepthClipControlCreateInfoEXT-negativeOneToOne-06470"],
+                }));
+            }
+        }
+
         Ok(())
     }
 }
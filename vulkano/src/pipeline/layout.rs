@@ -80,7 +80,7 @@ use smallvec::SmallVec;
 use std::{
     array,
     cmp::max,
-    collections::hash_map::Entry,
+    collections::{btree_map, hash_map::Entry},
     error::Error,
     fmt::{Display, Formatter, Write},
     mem::MaybeUninit,
@@ -318,6 +318,36 @@ impl PipelineLayout {
             })
     }
 
+    /// Returns whether `self` is compatible with `other` for the set with number `set_num`.
+    ///
+    /// Two pipeline layouts are compatible for set N if they were created with identical push
+    /// constant ranges, and if their descriptor set layouts are compatible for every set from 0
+    /// up to and including N. When binding a pipeline, descriptor sets that were bound with a
+    /// layout that is compatible for their set number remain bound, and do not need to be bound
+    /// again.
+    pub fn is_compatible_for_set(&self, other: &PipelineLayout, set_num: u32) -> bool {
+        let num_sets = set_num as usize + 1;
+
+        if num_sets > self.set_layouts.len() || num_sets > other.set_layouts.len() {
+            return false;
+        }
+
+        if self == other {
+            return true;
+        }
+
+        if self.push_constant_ranges != other.push_constant_ranges {
+            return false;
+        }
+
+        self.set_layouts[..num_sets]
+            .iter()
+            .zip(&other.set_layouts[..num_sets])
+            .all(|(self_set_layout, other_set_layout)| {
+                self_set_layout.is_compatible_with(other_set_layout)
+            })
+    }
+
     /// Makes sure that `self` is a superset of the provided descriptor set layouts and push
     /// constant ranges. Returns an `Err` if this is not the case.
     pub(crate) fn ensure_compatible_with_shader<'a>(
@@ -1145,6 +1175,150 @@ impl PipelineDescriptorSetLayoutCreateInfo {
         }
     }
 
+    /// Creates a new `PipelineDescriptorSetLayoutCreateInfo` with the same flags, descriptor set
+    /// layouts and push constant ranges as `layout`.
+    ///
+    /// This can be combined with [`union`](Self::union) to extend an existing pipeline layout.
+    pub fn from_layout(layout: &PipelineLayout) -> Self {
+        Self {
+            flags: layout.flags(),
+            set_layouts: layout
+                .set_layouts()
+                .iter()
+                .map(|set_layout| DescriptorSetLayoutCreateInfo {
+                    flags: set_layout.flags(),
+                    bindings: set_layout.bindings().clone(),
+                    ..Default::default()
+                })
+                .collect(),
+            push_constant_ranges: layout.push_constant_ranges().to_vec(),
+        }
+    }
+
+    /// Creates a new `PipelineDescriptorSetLayoutCreateInfo` from the union of `create_infos`.
+    ///
+    /// This can be used to create a single pipeline layout for several pipelines, for example
+    /// when they share a per-frame descriptor set. The pipelines will then be compatible for the
+    /// shared sets, so that those sets only need to be bound once.
+    ///
+    /// The create infos are combined as follows:
+    /// - The flags of the pipeline layout are the union of all the flags.
+    /// - Bindings with the same set and binding number must be identical in every create info,
+    ///   except for `stages`, which are combined. A descriptor set layout with bindings must have
+    ///   the same flags in every create info that provides bindings for that set.
+    /// - For each shader stage, the resulting push constant range covers the ranges of every
+    ///   create info that includes that stage.
+    ///
+    /// Returns an error if two bindings with the same set and binding number are not compatible,
+    /// or if the flags of a descriptor set layout differ.
+    pub fn union<'a>(
+        create_infos: impl IntoIterator<Item = &'a Self>,
+    ) -> Result<Self, Box<ValidationError>> {
+        let mut flags = PipelineLayoutCreateFlags::empty();
+        let mut set_layouts: Vec<DescriptorSetLayoutCreateInfo> = Vec::new();
+        let mut stage_ranges: Vec<(ShaderStage, u32, u32)> = Vec::new();
+
+        for (index, create_info) in create_infos.into_iter().enumerate() {
+            flags |= create_info.flags;
+
+            if set_layouts.len() < create_info.set_layouts.len() {
+                set_layouts.resize(create_info.set_layouts.len(), Default::default());
+            }
+
+            for (set_num, set_layout) in create_info.set_layouts.iter().enumerate() {
+                if set_layout.bindings.is_empty() {
+                    continue;
+                }
+
+                let merged_set_layout = &mut set_layouts[set_num];
+
+                if merged_set_layout.bindings.is_empty() {
+                    merged_set_layout.flags = set_layout.flags;
+                } else if merged_set_layout.flags != set_layout.flags {
+                    return Err(Box::new(ValidationError {
+                        context: format!("create_infos[{}].set_layouts[{}].flags", index, set_num)
+                            .into(),
+                        problem: "is not equal to the flags of the same set in a previous \
+                            element of `create_infos`"
+                            .into(),
+                        ..Default::default()
+                    }));
+                }
+
+                for (&binding_num, binding) in &set_layout.bindings {
+                    match merged_set_layout.bindings.entry(binding_num) {
+                        btree_map::Entry::Occupied(entry) => {
+                            let merged_binding = entry.into_mut();
+                            let is_compatible = DescriptorSetLayoutBinding {
+                                stages: merged_binding.stages,
+                                ..binding.clone()
+                            } == *merged_binding;
+
+                            if !is_compatible {
+                                return Err(Box::new(ValidationError {
+                                    context: format!(
+                                        "create_infos[{}].set_layouts[{}].bindings[{}]",
+                                        index, set_num, binding_num
+                                    )
+                                    .into(),
+                                    problem: "is not identical, apart from `stages`, to the \
+                                        binding with the same set and binding number in a \
+                                        previous element of `create_infos`"
+                                        .into(),
+                                    ..Default::default()
+                                }));
+                            }
+
+                            merged_binding.stages |= binding.stages;
+                        }
+                        btree_map::Entry::Vacant(entry) => {
+                            entry.insert(binding.clone());
+                        }
+                    }
+                }
+            }
+
+            for range in &create_info.push_constant_ranges {
+                let range_end = range.offset + range.size;
+
+                for stage in range.stages {
+                    if let Some((_, offset, end)) = stage_ranges
+                        .iter_mut()
+                        .find(|(existing_stage, _, _)| *existing_stage == stage)
+                    {
+                        *offset = (*offset).min(range.offset);
+                        *end = (*end).max(range_end);
+                    } else {
+                        stage_ranges.push((stage, range.offset, range_end));
+                    }
+                }
+            }
+        }
+
+        // Stages that ended up with the same range share a single push constant range.
+        let mut push_constant_ranges: Vec<PushConstantRange> = Vec::new();
+
+        for (stage, offset, end) in stage_ranges {
+            if let Some(existing_range) = push_constant_ranges.iter_mut().find(|existing_range| {
+                existing_range.offset == offset && existing_range.size == end - offset
+            }) {
+                existing_range.stages |= stage.into();
+            } else {
+                push_constant_ranges.push(PushConstantRange {
+                    stages: stage.into(),
+                    offset,
+                    size: end - offset,
+                });
+            }
+        }
+
+        Ok(Self {
+            flags,
+            set_layouts,
+            push_constant_ranges,
+        })
+    }
+
     /// Converts the `PipelineDescriptorSetLayoutCreateInfo` into a `PipelineLayoutCreateInfo` by
     /// creating the descriptor set layout objects.
     pub fn into_pipeline_layout_create_info(
@@ -1206,7 +1380,13 @@ mod tests {
 
     use super::PipelineLayout;
     use crate::{
-        pipeline::layout::{PipelineLayoutCreateInfo, PushConstantRange},
+        descriptor_set::layout::{
+            DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo, DescriptorType,
+        },
+        pipeline::layout::{
+            PipelineDescriptorSetLayoutCreateInfo, PipelineLayoutCreateFlags,
+            PipelineLayoutCreateInfo, PushConstantRange,
+        },
         shader::ShaderStages,
     };
 
@@ -1375,6 +1555,77 @@ mod tests {
             assert_eq!(layout.push_constant_ranges_disjoint.as_slice(), expected);
         }
     }
+
+    #[test]
+    fn union() {
+        let create_info =
+            |descriptor_type, stages, push_constant_range| PipelineDescriptorSetLayoutCreateInfo {
+                flags: PipelineLayoutCreateFlags::empty(),
+                set_layouts: vec![DescriptorSetLayoutCreateInfo {
+                    bindings: [(
+                        0,
+                        DescriptorSetLayoutBinding {
+                            stages,
+                            ..DescriptorSetLayoutBinding::descriptor_type(descriptor_type)
+                        },
+                    )]
+                    .into(),
+                    ..Default::default()
+                }],
+                push_constant_ranges: vec![push_constant_range],
+            };
+
+        let vertex = create_info(
+            DescriptorType::UniformBuffer,
+            ShaderStages::VERTEX,
+            PushConstantRange {
+                stages: ShaderStages::VERTEX,
+                offset: 0,
+                size: 16,
+            },
+        );
+        let fragment = create_info(
+            DescriptorType::UniformBuffer,
+            ShaderStages::FRAGMENT,
+            PushConstantRange {
+                stages: ShaderStages::FRAGMENT | ShaderStages::VERTEX,
+                offset: 8,
+                size: 16,
+            },
+        );
+
+        let union = PipelineDescriptorSetLayoutCreateInfo::union([&vertex, &fragment]).unwrap();
+        assert_eq!(
+            union.set_layouts[0].bindings[&0].stages,
+            ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+        );
+        assert_eq!(
+            union.push_constant_ranges,
+            [
+                PushConstantRange {
+                    stages: ShaderStages::VERTEX,
+                    offset: 0,
+                    size: 24,
+                },
+                PushConstantRange {
+                    stages: ShaderStages::FRAGMENT,
+                    offset: 8,
+                    size: 16,
+                },
+            ],
+        );
+
+        let storage = create_info(
+            DescriptorType::StorageBuffer,
+            ShaderStages::FRAGMENT,
+            PushConstantRange {
+                stages: ShaderStages::FRAGMENT,
+                offset: 0,
+                size: 4,
+            },
+        );
+        assert!(PipelineDescriptorSetLayoutCreateInfo::union([&vertex, &storage]).is_err());
+    }
 }
 
 /* TODO: restore
@@ -17,7 +17,10 @@
 //! the CPU). Consequently it is a CPU-intensive operation that should be performed at
 //! initialization or during a loading screen.
 
-pub use self::{compute::ComputePipeline, graphics::GraphicsPipeline, layout::PipelineLayout};
+pub use self::{
+    compute::ComputePipeline, graphics::GraphicsPipeline, layout::PipelineLayout,
+    ray_tracing::RayTracingPipeline,
+};
 use crate::{
     device::{Device, DeviceOwned},
     macros::{vulkan_bitflags, vulkan_enum},
@@ -25,15 +28,17 @@ use crate::{
         spirv::{BuiltIn, Decoration, ExecutionMode, Id, Instruction},
         DescriptorBindingRequirements, EntryPoint, ShaderStage,
     },
-    Requires, RequiresAllOf, RequiresOneOf, ValidationError,
+    Requires, RequiresAllOf, RequiresOneOf, ValidationError, Version,
 };
 use ahash::HashMap;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 pub mod cache;
 pub mod compute;
+pub mod executable;
 pub mod graphics;
 pub mod layout;
+pub mod ray_tracing;
 
 /// A trait for operations shared between pipeline types.
 pub trait Pipeline: DeviceOwned {
@@ -70,13 +75,12 @@ vulkan_enum! {
     // TODO: document
     Graphics = GRAPHICS,
 
-    /* TODO: enable
     // TODO: document
     RayTracing = RAY_TRACING_KHR
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(khr_ray_tracing_pipeline)]),
         RequiresAllOf([DeviceExtension(nv_ray_tracing)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -109,13 +113,14 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(khr_device_group)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// For compute pipelines, the pipeline can be used with
+    /// [`dispatch_base`](crate::command_buffer::AutoCommandBufferBuilder::dispatch_base)
+    /// with a nonzero base workgroup.
     DISPATCH_BASE = DISPATCH_BASE
     RequiresOneOf([
         RequiresAllOf([APIVersion(V1_1)]),
         RequiresAllOf([DeviceExtension(khr_device_group)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -202,18 +207,20 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(nv_ray_tracing)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// Statistics about the executables of the pipeline will be captured, so that they can be
+    /// retrieved with `executable_statistics` after the pipeline has been created.
     CAPTURE_STATISTICS = CAPTURE_STATISTICS_KHR
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(khr_pipeline_executable_properties)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    CAPTURE_INTERNAL_REPRESENTATIONS = CAPTURE_INTERNAL_REPRESENTATIONS_KHR{
-        device_extensions: [khr_pipeline_executable_properties],
-    },*/
+    /// Internal representations of the executables of the pipeline will be captured, so that
+    /// they can be retrieved with `executable_internal_representations` after the pipeline has
+    /// been created.
+    CAPTURE_INTERNAL_REPRESENTATIONS = CAPTURE_INTERNAL_REPRESENTATIONS_KHR
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(khr_pipeline_executable_properties)]),
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -221,12 +228,15 @@ vulkan_bitflags! {
         device_extensions: [nv_device_generated_commands],
     },*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The pipeline is a pipeline library, which can not be bound or used directly, but can be
+    /// linked into other pipelines.
+    ///
+    /// For graphics pipelines, see
+    /// [`GraphicsPipelineCreateInfo::library_flags`](crate::pipeline::graphics::GraphicsPipelineCreateInfo::library_flags).
     LIBRARY = LIBRARY_KHR
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(khr_pipeline_library)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -235,19 +245,21 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(ext_descriptor_buffer)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// For graphics pipeline libraries, the implementation will retain the information that is
+    /// needed to perform link time optimization when the library is linked into a pipeline with
+    /// [`LINK_TIME_OPTIMIZATION`](Self::LINK_TIME_OPTIMIZATION).
     RETAIN_LINK_TIME_OPTIMIZATION_INFO = RETAIN_LINK_TIME_OPTIMIZATION_INFO_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_graphics_pipeline_library)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// When linking graphics pipeline libraries, the implementation will perform link time
+    /// optimization. This is slower than a plain link, but produces a pipeline that performs
+    /// as well as one that was created without libraries.
     LINK_TIME_OPTIMIZATION = LINK_TIME_OPTIMIZATION_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_graphics_pipeline_library)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -324,6 +336,9 @@ pub struct PipelineShaderStageCreateInfo {
     /// For compute shaders, `max_compute_workgroup_subgroups * required_subgroup_size` must be
     /// greater than or equal to `workgroup_size.x * workgroup_size.y * workgroup_size.z`.
     ///
+    /// If this is `Some`, then `flags` must not contain
+    /// [`PipelineShaderStageCreateFlags::ALLOW_VARYING_SUBGROUP_SIZE`].
+    ///
     /// The default value is None.
     pub required_subgroup_size: Option<u32>,
 
@@ -832,7 +847,98 @@ impl PipelineShaderStageCreateInfo {
 
         let workgroup_size = workgroup_size.unwrap();
 
+        if flags.intersects(PipelineShaderStageCreateFlags::ALLOW_VARYING_SUBGROUP_SIZE)
+            && !device.enabled_features().subgroup_size_control
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `PipelineShaderStageCreateFlags::ALLOW_VARYING_SUBGROUP_SIZE`"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "subgroup_size_control",
+                )])]),
+                vuids: &["VUID-VkPipelineShaderStageCreateInfo-flags-02784"],
+            }));
+        }
+
+        if flags.intersects(PipelineShaderStageCreateFlags::REQUIRE_FULL_SUBGROUPS) {
+            if !device.enabled_features().compute_full_subgroups {
+                return Err(Box::new(ValidationError {
+                    context: "flags".into(),
+                    problem: "contains `PipelineShaderStageCreateFlags::REQUIRE_FULL_SUBGROUPS`"
+                        .into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "compute_full_subgroups",
+                    )])]),
+                    vuids: &["VUID-VkPipelineShaderStageCreateInfo-flags-02785"],
+                }));
+            }
+
+            if !matches!(
+                stage_enum,
+                ShaderStage::Compute | ShaderStage::Mesh | ShaderStage::Task
+            ) {
+                return Err(Box::new(ValidationError {
+                    problem: "`flags` contains \
+                        `PipelineShaderStageCreateFlags::REQUIRE_FULL_SUBGROUPS`, but \
+                        `entry_point` is not a compute, task or mesh shader"
+                        .into(),
+                    vuids: &["VUID-VkPipelineShaderStageCreateInfo-flags-08988"],
+                    ..Default::default()
+                }));
+            }
+
+            if let Some(required_subgroup_size) = required_subgroup_size {
+                if local_size[0] % required_subgroup_size != 0 {
+                    return Err(Box::new(ValidationError {
+                        problem: "`flags` contains \
+                            `PipelineShaderStageCreateFlags::REQUIRE_FULL_SUBGROUPS`, and \
+                            `required_subgroup_size` is `Some`, but the `local_size_x` of \
+                            `entry_point` is not a multiple of `required_subgroup_size`"
+                            .into(),
+                        vuids: &["VUID-VkPipelineShaderStageCreateInfo-pNext-02757"],
+                        ..Default::default()
+                    }));
+                }
+            } else if flags.intersects(PipelineShaderStageCreateFlags::ALLOW_VARYING_SUBGROUP_SIZE)
+            {
+                if local_size[0] % properties.max_subgroup_size.unwrap_or(1) != 0 {
+                    return Err(Box::new(ValidationError {
+                        problem: "`flags` contains both \
+                            `PipelineShaderStageCreateFlags::REQUIRE_FULL_SUBGROUPS` and \
+                            `PipelineShaderStageCreateFlags::ALLOW_VARYING_SUBGROUP_SIZE`, but \
+                            the `local_size_x` of `entry_point` is not a multiple of the \
+                            `max_subgroup_size` device property"
+                            .into(),
+                        vuids: &["VUID-VkPipelineShaderStageCreateInfo-flags-02758"],
+                        ..Default::default()
+                    }));
+                }
+            } else if local_size[0] % properties.subgroup_size.unwrap_or(1) != 0 {
+                return Err(Box::new(ValidationError {
+                    problem: "`flags` contains \
+                        `PipelineShaderStageCreateFlags::REQUIRE_FULL_SUBGROUPS`, but not \
+                        `PipelineShaderStageCreateFlags::ALLOW_VARYING_SUBGROUP_SIZE`, and \
+                        `required_subgroup_size` is `None`, but the `local_size_x` of \
+                        `entry_point` is not a multiple of the `subgroup_size` device property"
+                        .into(),
+                    vuids: &["VUID-VkPipelineShaderStageCreateInfo-flags-02759"],
+                    ..Default::default()
+                }));
+            }
+        }
+
         if let Some(required_subgroup_size) = required_subgroup_size {
+            if flags.intersects(PipelineShaderStageCreateFlags::ALLOW_VARYING_SUBGROUP_SIZE) {
+                return Err(Box::new(ValidationError {
+                    problem: "`required_subgroup_size` is `Some`, but `flags` contains \
+                        `PipelineShaderStageCreateFlags::ALLOW_VARYING_SUBGROUP_SIZE`"
+                        .into(),
+                    vuids: &["VUID-VkPipelineShaderStageCreateInfo-pNext-02754"],
+                    ..Default::default()
+                }));
+            }
+
             if !device.enabled_features().subgroup_size_control {
                 return Err(Box::new(ValidationError {
                     context: "required_subgroup_size".into(),
@@ -923,23 +1029,84 @@ vulkan_bitflags! {
     /// Flags specifying additional properties of a pipeline shader stage.
     PipelineShaderStageCreateFlags = PipelineShaderStageCreateFlags(u32);
 
-    /* TODO: enable
-    // TODO: document
+    /// The `SubgroupSize` built-in of the shader may vary between invocations, and can have any
+    /// value between the
+    /// [`min_subgroup_size`](crate::device::Properties::min_subgroup_size) and
+    /// [`max_subgroup_size`](crate::device::Properties::max_subgroup_size) device properties.
+    ///
+    /// If this flag is not set, and no required subgroup size is given, then the subgroup size is
+    /// the value of the [`subgroup_size`](crate::device::Properties::subgroup_size) device
+    /// property.
+    ///
+    /// The [`subgroup_size_control`](crate::device::Features::subgroup_size_control) feature must
+    /// be enabled on the device.
     ALLOW_VARYING_SUBGROUP_SIZE = ALLOW_VARYING_SUBGROUP_SIZE
     RequiresOneOf([
         RequiresAllOf([APIVersion(V1_3)]),
         RequiresAllOf([DeviceExtension(ext_subgroup_size_control)]),
     ]),
-    */
 
-    /* TODO: enable
-    // TODO: document
+    /// All subgroups in the local workgroup of the shader are fully populated, with all
+    /// invocations active.
+    ///
+    /// This can only be used with compute, task and mesh shaders. The `local_size_x` of the
+    /// shader must be a multiple of the subgroup size that will be used.
+    ///
+    /// The [`compute_full_subgroups`](crate::device::Features::compute_full_subgroups) feature
+    /// must be enabled on the device.
     REQUIRE_FULL_SUBGROUPS = REQUIRE_FULL_SUBGROUPS
     RequiresOneOf([
         RequiresAllOf([APIVersion(V1_3)]),
         RequiresAllOf([DeviceExtension(ext_subgroup_size_control)]),
     ]),
-    */
+}
+
+/// Feedback from the Vulkan implementation about the creation of a pipeline, or of one of its
+/// shader stages.
+///
+/// Creation feedback is only available if the device API version is at least 1.3, or the
+/// [`ext_pipeline_creation_feedback`] extension is enabled on the device.
+///
+/// [`ext_pipeline_creation_feedback`]: crate::device::DeviceExtensions::ext_pipeline_creation_feedback
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PipelineCreationFeedback {
+    /// Additional information about the creation.
+    pub flags: PipelineCreationFeedbackFlags,
+
+    /// The time that was spent creating the pipeline or shader stage.
+    pub duration: Duration,
+}
+
+impl PipelineCreationFeedback {
+    pub(crate) fn is_supported(device: &Device) -> bool {
+        device.api_version() >= Version::V1_3
+            || device.enabled_extensions().ext_pipeline_creation_feedback
+    }
+
+    /// Returns `None` if the implementation did not provide the feedback.
+    pub(crate) fn from_vk(val: &ash::vk::PipelineCreationFeedback) -> Option<Self> {
+        val.flags
+            .contains(ash::vk::PipelineCreationFeedbackFlags::VALID)
+            .then(|| Self {
+                flags: val.flags.into(),
+                duration: Duration::from_nanos(val.duration),
+            })
+    }
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags that provide information about the creation of a pipeline or shader stage.
+    PipelineCreationFeedbackFlags = PipelineCreationFeedbackFlags(u32);
+
+    /// The pipeline or shader stage was found in the pipeline cache that was provided when
+    /// creating the pipeline, so it did not need to be compiled.
+    APPLICATION_PIPELINE_CACHE_HIT = APPLICATION_PIPELINE_CACHE_HIT,
+
+    /// The base pipeline, that was provided when creating a derivative pipeline, was used to
+    /// speed up the creation.
+    BASE_PIPELINE_ACCELERATION = BASE_PIPELINE_ACCELERATION,
 }
 
 vulkan_enum! {
@@ -1081,13 +1248,16 @@ vulkan_enum! {
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state)]),
     ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// The `stride` value of the bindings in
+    /// [`VertexInputState::bindings`](crate::pipeline::graphics::vertex_input::VertexInputState::bindings).
+    ///
+    /// Set with
+    /// [`bind_vertex_buffers_with_strides`](crate::command_buffer::AutoCommandBufferBuilder::bind_vertex_buffers_with_strides).
     VertexInputBindingStride = VERTEX_INPUT_BINDING_STRIDE
     RequiresOneOf([
         RequiresAllOf([APIVersion(V1_3)]),
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state)]),
-    ]),*/
+    ]),
 
     /// The `Option` variant of
     /// [`DepthStencilState::depth`](crate::pipeline::graphics::depth_stencil::DepthStencilState::depth).
@@ -1206,12 +1376,37 @@ vulkan_enum! {
         RequiresAllOf([DeviceExtension(ext_discard_rectangles)]),
     ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Whether the discard rectangle test is enabled. If this state is not dynamic, the test is
+    /// enabled when
+    /// [`DiscardRectangleState::rectangles`](crate::pipeline::graphics::discard_rectangle::DiscardRectangleState::rectangles)
+    /// is not empty.
+    ///
+    /// Set with
+    /// [`set_discard_rectangle_enable`](crate::command_buffer::AutoCommandBufferBuilder::set_discard_rectangle_enable).
+    DiscardRectangleEnable = DISCARD_RECTANGLE_ENABLE_EXT
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_discard_rectangles)]),
+    ]),
+
+    /// The value of
+    /// [`DiscardRectangleState::mode`](crate::pipeline::graphics::discard_rectangle::DiscardRectangleState::mode).
+    ///
+    /// Set with
+    /// [`set_discard_rectangle_mode`](crate::command_buffer::AutoCommandBufferBuilder::set_discard_rectangle_mode).
+    DiscardRectangleMode = DISCARD_RECTANGLE_MODE_EXT
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_discard_rectangles)]),
+    ]),
+
+    /// The value of
+    /// [`MultisampleState::sample_locations`](crate::pipeline::graphics::multisample::MultisampleState::sample_locations).
+    ///
+    /// Set with
+    /// [`set_sample_locations`](crate::command_buffer::AutoCommandBufferBuilder::set_sample_locations).
     SampleLocations = SAMPLE_LOCATIONS_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_sample_locations)]),
-    ]), */
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -1241,12 +1436,15 @@ vulkan_enum! {
         RequiresAllOf([DeviceExtension(nv_scissor_exclusive)]),
     ]), */
 
-    /* TODO: enable
-    // TODO: document
+    /// The value of
+    /// [`FragmentShadingRateState`](crate::pipeline::graphics::fragment_shading_rate::FragmentShadingRateState).
+    ///
+    /// Set with
+    /// [`set_fragment_shading_rate`](crate::command_buffer::AutoCommandBufferBuilder::set_fragment_shading_rate).
     FragmentShadingRate = FRAGMENT_SHADING_RATE_KHR
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(khr_fragment_shading_rate)]),
-    ]), */
+    ]),
 
     /// The value of
     /// [`RasterizationState::line_stipple`](crate::pipeline::graphics::rasterization::RasterizationState::line_stipple).
@@ -1303,12 +1501,15 @@ vulkan_enum! {
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
     ]), */
 
-    /* TODO: enable
-    // TODO: document
+    /// The value of
+    /// [`RasterizationState::depth_clamp_enable`](crate::pipeline::graphics::rasterization::RasterizationState::depth_clamp_enable).
+    ///
+    /// Set with
+    /// [`set_depth_clamp_enable`](crate::command_buffer::AutoCommandBufferBuilder::set_depth_clamp_enable).
     DepthClampEnable = DEPTH_CLAMP_ENABLE_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
-    ]), */
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -1394,19 +1595,26 @@ vulkan_enum! {
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
     ]), */
 
-    /* TODO: enable
-    // TODO: document
+    /// The value of
+    /// [`RasterizationState::depth_clip_enable`](crate::pipeline::graphics::rasterization::RasterizationState::depth_clip_enable).
+    ///
+    /// Set with
+    /// [`set_depth_clip_enable`](crate::command_buffer::AutoCommandBufferBuilder::set_depth_clip_enable).
     DepthClipEnable = DEPTH_CLIP_ENABLE_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
-    ]), */
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Whether
+    /// [`MultisampleState::sample_locations`](crate::pipeline::graphics::multisample::MultisampleState::sample_locations)
+    /// is `Some`.
+    ///
+    /// Set with
+    /// [`set_sample_locations_enable`](crate::command_buffer::AutoCommandBufferBuilder::set_sample_locations_enable).
     SampleLocationsEnable = SAMPLE_LOCATIONS_ENABLE_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
-    ]), */
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -1415,12 +1623,15 @@ vulkan_enum! {
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
     ]), */
 
-    /* TODO: enable
-    // TODO: document
+    /// The value of
+    /// [`RasterizationState::provoking_vertex_mode`](crate::pipeline::graphics::rasterization::RasterizationState::provoking_vertex_mode).
+    ///
+    /// Set with
+    /// [`set_provoking_vertex_mode`](crate::command_buffer::AutoCommandBufferBuilder::set_provoking_vertex_mode).
     ProvokingVertexMode = PROVOKING_VERTEX_MODE_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
-    ]), */
+    ]),
 
     /* TODO: enable
     // TODO: document
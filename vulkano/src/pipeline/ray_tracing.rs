@@ -0,0 +1,1258 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! A pipeline that traces rays through a scene of acceleration structures.
+//!
+//! A ray tracing pipeline consists of a collection of shaders of the ray tracing stages, grouped
+//! into *shader groups*. A ray generation shader starts the work by tracing rays. Each ray that
+//! hits geometry invokes the hit group belonging to that geometry, which consists of a closest
+//! hit shader, an any hit shader and, for procedural geometry, an intersection shader. Rays that
+//! don't hit any geometry invoke a miss shader. Callable shaders can be invoked from the other
+//! shaders directly.
+//!
+//! Which shader group is used for each of these purposes is determined by the *shader binding
+//! table*, a buffer that contains the opaque handles of the shader groups in a layout that the
+//! implementation prescribes. The [`ShaderBindingTable`] type creates such a buffer for all the
+//! shader groups of a pipeline. Once created, you can execute a ray tracing pipeline by binding
+//! it in a command buffer, binding any descriptor sets and/or push constants that the pipeline
+//! needs, and then issuing a `trace_rays` command with the shader binding table.
+
+use super::{PipelineCreateFlags, PipelineShaderStageCreateInfo};
+use crate::{
+    buffer::{AllocateBufferError, Buffer, BufferCreateInfo, BufferUsage, Subbuffer},
+    device::{Device, DeviceOwned, DeviceOwnedDebugWrapper},
+    instance::InstanceOwnedDebugWrapper,
+    macros::{impl_id_counter, log_event},
+    memory::{
+        allocator::{
+            align_up, AllocationCreateInfo, MemoryAllocator, MemoryAllocatorError, MemoryTypeFilter,
+        },
+        DeviceAlignment,
+    },
+    pipeline::{cache::PipelineCache, layout::PipelineLayout, Pipeline, PipelineBindPoint},
+    shader::{DescriptorBindingRequirements, ShaderStage},
+    DeviceSize, Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, VulkanError,
+    VulkanObject,
+};
+use ahash::HashMap;
+use smallvec::SmallVec;
+use std::{
+    collections::hash_map::Entry,
+    ffi::CString,
+    fmt::Debug,
+    mem::MaybeUninit,
+    num::NonZeroU64,
+    ptr,
+    sync::{atomic::Ordering, Arc},
+};
+
+/// A pipeline object that describes to the Vulkan implementation how it should perform ray
+/// tracing operations.
+#[derive(Debug)]
+pub struct RayTracingPipeline {
+    handle: ash::vk::Pipeline,
+    device: InstanceOwnedDebugWrapper<Arc<Device>>,
+    id: NonZeroU64,
+
+    flags: PipelineCreateFlags,
+    stages: SmallVec<[ShaderStage; 5]>,
+    groups: SmallVec<[RayTracingShaderGroupCreateInfo; 5]>,
+    max_pipeline_ray_recursion_depth: u32,
+    layout: DeviceOwnedDebugWrapper<Arc<PipelineLayout>>,
+
+    descriptor_binding_requirements: HashMap<(u32, u32), DescriptorBindingRequirements>,
+    num_used_descriptor_sets: u32,
+}
+
+impl RayTracingPipeline {
+    /// Creates a new `RayTracingPipeline`.
+    #[inline]
+    pub fn new(
+        device: Arc<Device>,
+        cache: Option<Arc<PipelineCache>>,
+        create_info: RayTracingPipelineCreateInfo,
+    ) -> Result<Arc<RayTracingPipeline>, Validated<VulkanError>> {
+        Self::validate_new(&device, cache.as_ref().map(AsRef::as_ref), &create_info)?;
+
+        unsafe { Ok(Self::new_unchecked(device, cache, create_info)?) }
+    }
+
+    fn validate_new(
+        device: &Device,
+        cache: Option<&PipelineCache>,
+        create_info: &RayTracingPipelineCreateInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        if !device.enabled_features().ray_tracing_pipeline {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "ray_tracing_pipeline",
+                )])]),
+                vuids: &["VUID-vkCreateRayTracingPipelinesKHR-rayTracingPipeline-03586"],
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkCreateRayTracingPipelinesKHR-pipelineCache-parent
+        if let Some(cache) = &cache {
+            assert_eq!(device, cache.device().as_ref());
+        }
+        create_info
+            .validate(device)
+            .map_err(|err| err.add_context("create_info"))?;
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn new_unchecked(
+        device: Arc<Device>,
+        cache: Option<Arc<PipelineCache>>,
+        create_info: RayTracingPipelineCreateInfo,
+    ) -> Result<Arc<RayTracingPipeline>, VulkanError> {
+        let &RayTracingPipelineCreateInfo {
+            flags,
+            ref stages,
+            ref groups,
+            max_pipeline_ray_recursion_depth,
+            ref layout,
+            ref base_pipeline,
+            _ne: _,
+        } = &create_info;
+
+        struct PerPipelineShaderStageCreateInfo {
+            name_vk: CString,
+            specialization_info_vk: ash::vk::SpecializationInfo,
+            specialization_map_entries_vk: Vec<ash::vk::SpecializationMapEntry>,
+            specialization_data_vk: Vec<u8>,
+            required_subgroup_size_create_info:
+                Option<ash::vk::PipelineShaderStageRequiredSubgroupSizeCreateInfo>,
+        }
+
+        let (mut stages_vk, mut per_stage_vk): (SmallVec<[_; 5]>, SmallVec<[_; 5]>) = stages
+            .iter()
+            .map(|stage| {
+                let &PipelineShaderStageCreateInfo {
+                    flags,
+                    ref entry_point,
+                    ref required_subgroup_size,
+                    _ne: _,
+                } = stage;
+
+                let entry_point_info = entry_point.info();
+                let stage = ShaderStage::from(entry_point_info.execution_model);
+
+                let mut specialization_data_vk: Vec<u8> = Vec::new();
+                let specialization_map_entries_vk: Vec<_> = entry_point
+                    .module()
+                    .specialization_info()
+                    .iter()
+                    .map(|(&constant_id, value)| {
+                        let data = value.as_bytes();
+                        let offset = specialization_data_vk.len() as u32;
+                        let size = data.len();
+                        specialization_data_vk.extend(data);
+
+                        ash::vk::SpecializationMapEntry {
+                            constant_id,
+                            offset,
+                            size,
+                        }
+                    })
+                    .collect();
+                let required_subgroup_size_create_info =
+                    required_subgroup_size.map(|required_subgroup_size| {
+                        ash::vk::PipelineShaderStageRequiredSubgroupSizeCreateInfo {
+                            required_subgroup_size,
+                            ..Default::default()
+                        }
+                    });
+                (
+                    ash::vk::PipelineShaderStageCreateInfo {
+                        flags: flags.into(),
+                        stage: stage.into(),
+                        module: entry_point.module().handle(),
+                        p_name: ptr::null(),
+                        p_specialization_info: ptr::null(),
+                        ..Default::default()
+                    },
+                    PerPipelineShaderStageCreateInfo {
+                        name_vk: CString::new(entry_point_info.name.as_str()).unwrap(),
+                        specialization_info_vk: ash::vk::SpecializationInfo {
+                            map_entry_count: specialization_map_entries_vk.len() as u32,
+                            p_map_entries: ptr::null(),
+                            data_size: specialization_data_vk.len(),
+                            p_data: ptr::null(),
+                        },
+                        specialization_map_entries_vk,
+                        specialization_data_vk,
+                        required_subgroup_size_create_info,
+                    },
+                )
+            })
+            .unzip();
+
+        for (
+            stage_vk,
+            PerPipelineShaderStageCreateInfo {
+                name_vk,
+                specialization_info_vk,
+                specialization_map_entries_vk,
+                specialization_data_vk,
+                required_subgroup_size_create_info,
+            },
+        ) in (stages_vk.iter_mut()).zip(per_stage_vk.iter_mut())
+        {
+            *stage_vk = ash::vk::PipelineShaderStageCreateInfo {
+                p_next: required_subgroup_size_create_info.as_ref().map_or(
+                    ptr::null(),
+                    |required_subgroup_size_create_info| {
+                        required_subgroup_size_create_info as *const _ as _
+                    },
+                ),
+                p_name: name_vk.as_ptr(),
+                p_specialization_info: if specialization_info_vk.data_size == 0 {
+                    ptr::null()
+                } else {
+                    specialization_info_vk
+                },
+                ..*stage_vk
+            };
+
+            *specialization_info_vk = ash::vk::SpecializationInfo {
+                p_map_entries: specialization_map_entries_vk.as_ptr(),
+                p_data: specialization_data_vk.as_ptr() as _,
+                ..*specialization_info_vk
+            };
+        }
+
+        let groups_vk: SmallVec<[_; 5]> = groups
+            .iter()
+            .map(RayTracingShaderGroupCreateInfo::to_vk)
+            .collect();
+
+        let create_infos_vk = ash::vk::RayTracingPipelineCreateInfoKHR {
+            flags: flags.into(),
+            stage_count: stages_vk.len() as u32,
+            p_stages: stages_vk.as_ptr(),
+            group_count: groups_vk.len() as u32,
+            p_groups: groups_vk.as_ptr(),
+            max_pipeline_ray_recursion_depth,
+            p_library_info: ptr::null(),
+            p_library_interface: ptr::null(),
+            p_dynamic_state: ptr::null(),
+            layout: layout.handle(),
+            base_pipeline_handle: base_pipeline
+                .as_ref()
+                .map_or(ash::vk::Pipeline::null(), VulkanObject::handle),
+            base_pipeline_index: -1,
+            ..Default::default()
+        };
+
+        let handle = {
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            (fns.khr_ray_tracing_pipeline
+                .create_ray_tracing_pipelines_khr)(
+                device.handle(),
+                ash::vk::DeferredOperationKHR::null(),
+                cache.as_ref().map_or_else(Default::default, |c| c.handle()),
+                1,
+                &create_infos_vk,
+                ptr::null(),
+                output.as_mut_ptr(),
+            )
+            .result()
+            .map_err(|err| {
+                log_event!(
+                    warn,
+                    "pipeline",
+                    "failed to create a ray tracing pipeline: {:?}",
+                    err
+                );
+                VulkanError::from(err)
+            })?;
+            output.assume_init()
+        };
+
+        log_event!(
+            debug,
+            "pipeline",
+            "created ray tracing pipeline 0x{:x} (cached: {})",
+            ash::vk::Handle::as_raw(handle),
+            cache.is_some(),
+        );
+
+        Ok(Self::from_handle(device, handle, create_info))
+    }
+
+    /// Creates a new `RayTracingPipeline` from a raw object handle.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid Vulkan object handle created from `device`.
+    /// - `create_info` must match the info used to create the object.
+    #[inline]
+    pub unsafe fn from_handle(
+        device: Arc<Device>,
+        handle: ash::vk::Pipeline,
+        create_info: RayTracingPipelineCreateInfo,
+    ) -> Arc<RayTracingPipeline> {
+        let RayTracingPipelineCreateInfo {
+            flags,
+            stages,
+            groups,
+            max_pipeline_ray_recursion_depth,
+            layout,
+            base_pipeline: _,
+            _ne: _,
+        } = create_info;
+
+        let mut descriptor_binding_requirements: HashMap<
+            (u32, u32),
+            DescriptorBindingRequirements,
+        > = HashMap::default();
+
+        for stage in &stages {
+            for (&loc, reqs) in &stage.entry_point.info().descriptor_binding_requirements {
+                match descriptor_binding_requirements.entry(loc) {
+                    Entry::Occupied(entry) => {
+                        entry.into_mut().merge(reqs).expect(
+                            "Could not produce an intersection of the shader descriptor \
+                            requirements",
+                        );
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(reqs.clone());
+                    }
+                }
+            }
+        }
+
+        let num_used_descriptor_sets = descriptor_binding_requirements
+            .keys()
+            .map(|loc| loc.0)
+            .max()
+            .map(|x| x + 1)
+            .unwrap_or(0);
+
+        let stages = stages
+            .iter()
+            .map(|stage| ShaderStage::from(stage.entry_point.info().execution_model))
+            .collect();
+
+        device.counters.pipelines.fetch_add(1, Ordering::Relaxed);
+
+        Arc::new(RayTracingPipeline {
+            handle,
+            device: InstanceOwnedDebugWrapper(device),
+            id: Self::next_id(),
+
+            flags,
+            stages,
+            groups,
+            max_pipeline_ray_recursion_depth,
+            layout: DeviceOwnedDebugWrapper(layout),
+
+            descriptor_binding_requirements,
+            num_used_descriptor_sets,
+        })
+    }
+
+    /// Returns the `Device` that the pipeline was created with.
+    #[inline]
+    pub fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+
+    /// Returns the flags that the pipeline was created with.
+    #[inline]
+    pub fn flags(&self) -> PipelineCreateFlags {
+        self.flags
+    }
+
+    /// Returns the shader groups that the pipeline was created with.
+    #[inline]
+    pub fn groups(&self) -> &[RayTracingShaderGroupCreateInfo] {
+        &self.groups
+    }
+
+    /// Returns the maximum recursion depth that the pipeline was created with.
+    #[inline]
+    pub fn max_pipeline_ray_recursion_depth(&self) -> u32 {
+        self.max_pipeline_ray_recursion_depth
+    }
+
+    /// Returns the opaque handles of `group_count` shader groups, starting with `first_group`.
+    ///
+    /// These are the values that must be stored in a shader binding table for the shader groups.
+    /// You only need this if you want to lay out the shader binding table yourself; otherwise,
+    /// [`ShaderBindingTable`] does this for you.
+    pub fn group_handles(
+        &self,
+        first_group: u32,
+        group_count: u32,
+    ) -> Result<ShaderGroupHandlesData, Validated<VulkanError>> {
+        self.validate_group_handles(first_group, group_count)?;
+
+        unsafe { Ok(self.group_handles_unchecked(first_group, group_count)?) }
+    }
+
+    fn validate_group_handles(
+        &self,
+        first_group: u32,
+        group_count: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        if first_group >= self.groups.len() as u32 {
+            return Err(Box::new(ValidationError {
+                context: "first_group".into(),
+                problem: "is not less than the number of shader groups in the pipeline".into(),
+                vuids: &["VUID-vkGetRayTracingShaderGroupHandlesKHR-firstGroup-04050"],
+                ..Default::default()
+            }));
+        }
+
+        if first_group as u64 + group_count as u64 > self.groups.len() as u64 {
+            return Err(Box::new(ValidationError {
+                problem: "`first_group + group_count` is greater than the number of shader \
+                    groups in the pipeline"
+                    .into(),
+                vuids: &["VUID-vkGetRayTracingShaderGroupHandlesKHR-firstGroup-02419"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn group_handles_unchecked(
+        &self,
+        first_group: u32,
+        group_count: u32,
+    ) -> Result<ShaderGroupHandlesData, VulkanError> {
+        let handle_size = self
+            .device
+            .physical_device()
+            .properties()
+            .shader_group_handle_size
+            .unwrap();
+        let mut data = vec![0u8; (handle_size * group_count) as usize];
+
+        let fns = self.device.fns();
+        (fns.khr_ray_tracing_pipeline
+            .get_ray_tracing_shader_group_handles_khr)(
+            self.device.handle(),
+            self.handle,
+            first_group,
+            group_count,
+            data.len(),
+            data.as_mut_ptr() as *mut _,
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        Ok(ShaderGroupHandlesData { data, handle_size })
+    }
+}
+
+impl Pipeline for RayTracingPipeline {
+    #[inline]
+    fn bind_point(&self) -> PipelineBindPoint {
+        PipelineBindPoint::RayTracing
+    }
+
+    #[inline]
+    fn layout(&self) -> &Arc<PipelineLayout> {
+        &self.layout
+    }
+
+    #[inline]
+    fn num_used_descriptor_sets(&self) -> u32 {
+        self.num_used_descriptor_sets
+    }
+
+    #[inline]
+    fn descriptor_binding_requirements(
+        &self,
+    ) -> &HashMap<(u32, u32), DescriptorBindingRequirements> {
+        &self.descriptor_binding_requirements
+    }
+}
+
+impl_id_counter!(RayTracingPipeline);
+
+unsafe impl VulkanObject for RayTracingPipeline {
+    type Handle = ash::vk::Pipeline;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.handle
+    }
+}
+
+unsafe impl DeviceOwned for RayTracingPipeline {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.device()
+    }
+}
+
+impl Drop for RayTracingPipeline {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let fns = self.device.fns();
+            (fns.v1_0.destroy_pipeline)(self.device.handle(), self.handle, ptr::null());
+        }
+
+        self.device
+            .counters
+            .pipelines
+            .fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Parameters to create a new `RayTracingPipeline`.
+#[derive(Clone, Debug)]
+pub struct RayTracingPipelineCreateInfo {
+    /// Additional properties of the pipeline.
+    ///
+    /// The default value is empty.
+    pub flags: PipelineCreateFlags,
+
+    /// The shader stages to use.
+    ///
+    /// There must be at least one `ShaderStage::Raygen` stage. The stages are referred to by
+    /// their index in `groups`.
+    ///
+    /// The default value is empty.
+    pub stages: SmallVec<[PipelineShaderStageCreateInfo; 5]>,
+
+    /// The shader groups to create from `stages`.
+    ///
+    /// The shader binding table refers to the groups by their index in this list.
+    ///
+    /// The default value is empty.
+    pub groups: SmallVec<[RayTracingShaderGroupCreateInfo; 5]>,
+
+    /// The maximum depth of recursive `traceRayEXT` calls that shaders of the pipeline will
+    /// make. A value of 1 means that only the ray generation shader traces rays.
+    ///
+    /// The value must not be greater than the
+    /// [`max_ray_recursion_depth`](crate::device::Properties::max_ray_recursion_depth) limit.
+    ///
+    /// The default value is 1.
+    pub max_pipeline_ray_recursion_depth: u32,
+
+    /// The pipeline layout to use.
+    ///
+    /// There is no default value.
+    pub layout: Arc<PipelineLayout>,
+
+    /// The pipeline to use as a base when creating this pipeline.
+    ///
+    /// If this is `Some`, then `flags` must contain [`PipelineCreateFlags::DERIVATIVE`],
+    /// and the `flags` of the provided pipeline must contain
+    /// [`PipelineCreateFlags::ALLOW_DERIVATIVES`].
+    ///
+    /// The default value is `None`.
+    pub base_pipeline: Option<Arc<RayTracingPipeline>>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl RayTracingPipelineCreateInfo {
+    /// Returns a `RayTracingPipelineCreateInfo` with the specified `layout`.
+    #[inline]
+    pub fn layout(layout: Arc<PipelineLayout>) -> Self {
+        Self {
+            flags: PipelineCreateFlags::empty(),
+            stages: SmallVec::new(),
+            groups: SmallVec::new(),
+            max_pipeline_ray_recursion_depth: 1,
+            layout,
+            base_pipeline: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
+    pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            flags,
+            ref stages,
+            ref groups,
+            max_pipeline_ray_recursion_depth,
+            ref layout,
+            ref base_pipeline,
+            _ne: _,
+        } = self;
+
+        flags.validate_device(device).map_err(|err| {
+            err.add_context("flags")
+                .set_vuids(&["VUID-VkRayTracingPipelineCreateInfoKHR-flags-parameter"])
+        })?;
+
+        if flags.intersects(PipelineCreateFlags::LIBRARY) {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `PipelineCreateFlags::LIBRARY`, but ray tracing pipeline \
+                    libraries are not supported"
+                    .into(),
+                vuids: &["VUID-VkRayTracingPipelineCreateInfoKHR-flags-03465"],
+                ..Default::default()
+            }));
+        }
+
+        if flags.intersects(PipelineCreateFlags::DERIVATIVE) {
+            let base_pipeline = base_pipeline.as_ref().ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "`flags` contains `PipelineCreateFlags::DERIVATIVE`, but \
+                        `base_pipeline` is `None`"
+                        .into(),
+                    vuids: &["VUID-VkRayTracingPipelineCreateInfoKHR-flags-07984"],
+                    ..Default::default()
+                })
+            })?;
+
+            if !base_pipeline
+                .flags()
+                .intersects(PipelineCreateFlags::ALLOW_DERIVATIVES)
+            {
+                return Err(Box::new(ValidationError {
+                    context: "base_pipeline.flags()".into(),
+                    problem: "does not contain `PipelineCreateFlags::ALLOW_DERIVATIVES`".into(),
+                    vuids: &["VUID-vkCreateRayTracingPipelinesKHR-flags-03416"],
+                    ..Default::default()
+                }));
+            }
+        } else if base_pipeline.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "`flags` does not contain `PipelineCreateFlags::DERIVATIVE`, but \
+                    `base_pipeline` is `Some`"
+                    .into(),
+                ..Default::default()
+            }));
+        }
+
+        let mut stage_enums: SmallVec<[ShaderStage; 5]> = SmallVec::new();
+
+        for (stage_index, stage) in stages.iter().enumerate() {
+            stage
+                .validate(device)
+                .map_err(|err| err.add_context(format!("stages[{}]", stage_index)))?;
+
+            let entry_point_info = stage.entry_point.info();
+            let stage_enum = ShaderStage::from(entry_point_info.execution_model);
+
+            if !matches!(
+                stage_enum,
+                ShaderStage::Raygen
+                    | ShaderStage::AnyHit
+                    | ShaderStage::ClosestHit
+                    | ShaderStage::Miss
+                    | ShaderStage::Intersection
+                    | ShaderStage::Callable
+            ) {
+                return Err(Box::new(ValidationError {
+                    context: format!("stages[{}]", stage_index).into(),
+                    problem: "is not a ray tracing shader stage".into(),
+                    vuids: &["VUID-VkRayTracingPipelineCreateInfoKHR-stage-06899"],
+                    ..Default::default()
+                }));
+            }
+
+            layout
+                .ensure_compatible_with_shader(
+                    entry_point_info
+                        .descriptor_binding_requirements
+                        .iter()
+                        .map(|(k, v)| (*k, v)),
+                    entry_point_info.push_constant_requirements.as_ref(),
+                )
+                .map_err(|err| {
+                    Box::new(ValidationError {
+                        context: format!("stages[{}].entry_point", stage_index).into(),
+                        vuids: &["VUID-VkRayTracingPipelineCreateInfoKHR-layout-03427"],
+                        ..ValidationError::from_error(err)
+                    })
+                })?;
+
+            stage_enums.push(stage_enum);
+        }
+
+        if !stage_enums.contains(&ShaderStage::Raygen) {
+            return Err(Box::new(ValidationError {
+                context: "stages".into(),
+                problem: "does not contain a `ShaderStage::Raygen` stage".into(),
+                vuids: &["VUID-VkRayTracingPipelineCreateInfoKHR-stage-03425"],
+                ..Default::default()
+            }));
+        }
+
+        if groups.is_empty() {
+            return Err(Box::new(ValidationError {
+                context: "groups".into(),
+                problem: "is empty".into(),
+                ..Default::default()
+            }));
+        }
+
+        for (group_index, group) in groups.iter().enumerate() {
+            group
+                .validate(&stage_enums)
+                .map_err(|err| err.add_context(format!("groups[{}]", group_index)))?;
+        }
+
+        let properties = device.physical_device().properties();
+
+        if max_pipeline_ray_recursion_depth > properties.max_ray_recursion_depth.unwrap_or(0) {
+            return Err(Box::new(ValidationError {
+                context: "max_pipeline_ray_recursion_depth".into(),
+                problem: "is greater than the `max_ray_recursion_depth` limit".into(),
+                vuids: &[
+                    "VUID-VkRayTracingPipelineCreateInfoKHR-maxPipelineRayRecursionDepth-03589",
+                ],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+}
+
+/// A group of shaders in a ray tracing pipeline, that is referred to by a single entry in the
+/// shader binding table.
+///
+/// The shaders are given as indices into the `stages` of [`RayTracingPipelineCreateInfo`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RayTracingShaderGroupCreateInfo {
+    /// A group consisting of a single ray generation, miss or callable shader.
+    General { general_shader: u32 },
+
+    /// A hit group for triangle geometry, consisting of an optional closest hit shader and an
+    /// optional any hit shader.
+    TrianglesHit {
+        closest_hit_shader: Option<u32>,
+        any_hit_shader: Option<u32>,
+    },
+
+    /// A hit group for procedural (AABB) geometry, consisting of an intersection shader, and an
+    /// optional closest hit shader and an optional any hit shader.
+    ProceduralHit {
+        closest_hit_shader: Option<u32>,
+        any_hit_shader: Option<u32>,
+        intersection_shader: u32,
+    },
+}
+
+impl RayTracingShaderGroupCreateInfo {
+    pub(crate) fn validate(&self, stages: &[ShaderStage]) -> Result<(), Box<ValidationError>> {
+        let check_stage = |index: u32,
+                           field: &'static str,
+                           expected: ShaderStage,
+                           vuids: &'static [&'static str]|
+         -> Result<(), Box<ValidationError>> {
+            match stages.get(index as usize) {
+                Some(&stage) if stage == expected => Ok(()),
+                _ => Err(Box::new(ValidationError {
+                    context: field.into(),
+                    problem: format!(
+                        "is not the index of a `ShaderStage::{:?}` element of `stages`",
+                        expected
+                    )
+                    .into(),
+                    vuids,
+                    ..Default::default()
+                })),
+            }
+        };
+
+        match *self {
+            Self::General { general_shader } => {
+                if !matches!(
+                    stages.get(general_shader as usize),
+                    Some(ShaderStage::Raygen | ShaderStage::Miss | ShaderStage::Callable)
+                ) {
+                    return Err(Box::new(ValidationError {
+                        context: "general_shader".into(),
+                        problem: "is not the index of a `ShaderStage::Raygen`, \
+                            `ShaderStage::Miss` or `ShaderStage::Callable` element of `stages`"
+                            .into(),
+                        vuids: &["VUID-VkRayTracingShaderGroupCreateInfoKHR-type-03474"],
+                        ..Default::default()
+                    }));
+                }
+            }
+            Self::TrianglesHit {
+                closest_hit_shader,
+                any_hit_shader,
+            } => {
+                if let Some(closest_hit_shader) = closest_hit_shader {
+                    check_stage(
+                        closest_hit_shader,
+                        "closest_hit_shader",
+                        ShaderStage::ClosestHit,
+                        &["VUID-VkRayTracingShaderGroupCreateInfoKHR-closestHitShader-03477"],
+                    )?;
+                }
+
+                if let Some(any_hit_shader) = any_hit_shader {
+                    check_stage(
+                        any_hit_shader,
+                        "any_hit_shader",
+                        ShaderStage::AnyHit,
+                        &["VUID-VkRayTracingShaderGroupCreateInfoKHR-anyHitShader-03479"],
+                    )?;
+                }
+            }
+            Self::ProceduralHit {
+                closest_hit_shader,
+                any_hit_shader,
+                intersection_shader,
+            } => {
+                if let Some(closest_hit_shader) = closest_hit_shader {
+                    check_stage(
+                        closest_hit_shader,
+                        "closest_hit_shader",
+                        ShaderStage::ClosestHit,
+                        &["VUID-VkRayTracingShaderGroupCreateInfoKHR-closestHitShader-03477"],
+                    )?;
+                }
+
+                if let Some(any_hit_shader) = any_hit_shader {
+                    check_stage(
+                        any_hit_shader,
+                        "any_hit_shader",
+                        ShaderStage::AnyHit,
+                        &["VUID-VkRayTracingShaderGroupCreateInfoKHR-anyHitShader-03479"],
+                    )?;
+                }
+
+                check_stage(
+                    intersection_shader,
+                    "intersection_shader",
+                    ShaderStage::Intersection,
+                    &["VUID-VkRayTracingShaderGroupCreateInfoKHR-type-03475"],
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn to_vk(&self) -> ash::vk::RayTracingShaderGroupCreateInfoKHR {
+        const UNUSED: u32 = ash::vk::SHADER_UNUSED_KHR;
+
+        let (ty, general_shader, closest_hit_shader, any_hit_shader, intersection_shader) =
+            match *self {
+                Self::General { general_shader } => (
+                    ash::vk::RayTracingShaderGroupTypeKHR::GENERAL,
+                    general_shader,
+                    UNUSED,
+                    UNUSED,
+                    UNUSED,
+                ),
+                Self::TrianglesHit {
+                    closest_hit_shader,
+                    any_hit_shader,
+                } => (
+                    ash::vk::RayTracingShaderGroupTypeKHR::TRIANGLES_HIT_GROUP,
+                    UNUSED,
+                    closest_hit_shader.unwrap_or(UNUSED),
+                    any_hit_shader.unwrap_or(UNUSED),
+                    UNUSED,
+                ),
+                Self::ProceduralHit {
+                    closest_hit_shader,
+                    any_hit_shader,
+                    intersection_shader,
+                } => (
+                    ash::vk::RayTracingShaderGroupTypeKHR::PROCEDURAL_HIT_GROUP,
+                    UNUSED,
+                    closest_hit_shader.unwrap_or(UNUSED),
+                    any_hit_shader.unwrap_or(UNUSED),
+                    intersection_shader,
+                ),
+            };
+
+        ash::vk::RayTracingShaderGroupCreateInfoKHR {
+            ty,
+            general_shader,
+            closest_hit_shader,
+            any_hit_shader,
+            intersection_shader,
+            p_shader_group_capture_replay_handle: ptr::null(),
+            ..Default::default()
+        }
+    }
+}
+
+/// The opaque handles of shader groups, as returned by
+/// [`RayTracingPipeline::group_handles`].
+#[derive(Clone, Debug)]
+pub struct ShaderGroupHandlesData {
+    data: Vec<u8>,
+    handle_size: u32,
+}
+
+impl ShaderGroupHandlesData {
+    /// Returns the raw data of all handles, concatenated.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the size in bytes of each handle.
+    #[inline]
+    pub fn handle_size(&self) -> u32 {
+        self.handle_size
+    }
+
+    /// Returns an iterator over the handles.
+    #[inline]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &[u8]> {
+        self.data.chunks_exact(self.handle_size as usize)
+    }
+}
+
+/// A region of a shader binding table, as it is passed to the `trace_rays` commands.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StridedDeviceAddressRegion {
+    /// The device address of the start of the region, or 0 if the region is empty.
+    pub device_address: DeviceSize,
+
+    /// The number of bytes between consecutive shader group handles in the region.
+    pub stride: DeviceSize,
+
+    /// The size in bytes of the region.
+    pub size: DeviceSize,
+}
+
+impl StridedDeviceAddressRegion {
+    pub(crate) fn to_vk(self) -> ash::vk::StridedDeviceAddressRegionKHR {
+        ash::vk::StridedDeviceAddressRegionKHR {
+            device_address: self.device_address,
+            stride: self.stride,
+            size: self.size,
+        }
+    }
+}
+
+/// The regions of a shader binding table for each kind of shader group.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ShaderBindingTableAddresses {
+    /// The region containing the ray generation shader group. This must contain exactly one
+    /// handle, so `size` must equal `stride`.
+    pub raygen: StridedDeviceAddressRegion,
+
+    /// The region containing the miss shader groups.
+    pub miss: StridedDeviceAddressRegion,
+
+    /// The region containing the hit groups.
+    pub hit: StridedDeviceAddressRegion,
+
+    /// The region containing the callable shader groups.
+    pub callable: StridedDeviceAddressRegion,
+}
+
+/// A buffer containing the shader group handles of a ray tracing pipeline, laid out as a shader
+/// binding table.
+///
+/// The general shader groups are sorted into the raygen, miss and callable regions according to
+/// the stage of their shader, and the hit groups go into the hit region, each keeping the
+/// relative order that they have in [`RayTracingPipeline::groups`]. Within a region, the
+/// shader group that a ray uses is then selected by its index in this order. If the pipeline has
+/// more than one ray generation group, the raygen region refers to the first one.
+///
+/// The handles are aligned and padded according to the `shader_group_handle_alignment` and
+/// `shader_group_base_alignment` properties of the physical device.
+///
+/// The handles are only meaningful to the pipeline they were taken from, so the shader binding
+/// table keeps that pipeline, and can only be used for tracing rays while it is bound.
+#[derive(Clone, Debug)]
+pub struct ShaderBindingTable {
+    pipeline: Arc<RayTracingPipeline>,
+    buffer: Subbuffer<[u8]>,
+    addresses: ShaderBindingTableAddresses,
+}
+
+/// The placement of the regions of a shader binding table within its buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct ShaderBindingTableLayout {
+    raygen_stride: DeviceSize,
+    stride: DeviceSize,
+    raygen_offset: DeviceSize,
+    miss_offset: DeviceSize,
+    hit_offset: DeviceSize,
+    callable_offset: DeviceSize,
+    total_size: DeviceSize,
+}
+
+impl ShaderBindingTableLayout {
+    /// Computes the layout for the given number of raygen, miss, hit and callable groups.
+    fn new(
+        handle_size: DeviceSize,
+        handle_alignment: DeviceAlignment,
+        base_alignment: DeviceAlignment,
+        [raygen_count, miss_count, hit_count, callable_count]: [usize; 4],
+    ) -> Self {
+        // The raygen region must have a size equal to its stride, and every region must start
+        // at a multiple of the base alignment.
+        let raygen_stride = align_up(handle_size, base_alignment);
+        let stride = align_up(handle_size, handle_alignment);
+        let region_size = |count: usize| align_up(count as DeviceSize * stride, base_alignment);
+
+        let raygen_offset = 0;
+        let miss_offset = raygen_offset + raygen_stride * raygen_count as DeviceSize;
+        let hit_offset = miss_offset + region_size(miss_count);
+        let callable_offset = hit_offset + region_size(hit_count);
+        let total_size = callable_offset + region_size(callable_count);
+
+        ShaderBindingTableLayout {
+            raygen_stride,
+            stride,
+            raygen_offset,
+            miss_offset,
+            hit_offset,
+            callable_offset,
+            total_size,
+        }
+    }
+}
+
+impl ShaderBindingTable {
+    /// Creates a new `ShaderBindingTable` for all shader groups of `pipeline`.
+    ///
+    /// The [`buffer_device_address`](crate::device::Features::buffer_device_address) feature
+    /// must be enabled on the device.
+    pub fn new(
+        allocator: Arc<dyn MemoryAllocator>,
+        pipeline: Arc<RayTracingPipeline>,
+    ) -> Result<Self, Validated<VulkanError>> {
+        let device = pipeline.device();
+
+        if !device.enabled_features().buffer_device_address {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "buffer_device_address",
+                )])]),
+                ..Default::default()
+            })
+            .into());
+        }
+
+        let mut raygen_groups: SmallVec<[u32; 1]> = SmallVec::new();
+        let mut miss_groups: SmallVec<[u32; 4]> = SmallVec::new();
+        let mut hit_groups: SmallVec<[u32; 4]> = SmallVec::new();
+        let mut callable_groups: SmallVec<[u32; 4]> = SmallVec::new();
+
+        for (group_index, group) in pipeline.groups.iter().enumerate() {
+            let group_index = group_index as u32;
+
+            match *group {
+                RayTracingShaderGroupCreateInfo::General { general_shader } => {
+                    match pipeline.stages[general_shader as usize] {
+                        ShaderStage::Raygen => raygen_groups.push(group_index),
+                        ShaderStage::Miss => miss_groups.push(group_index),
+                        ShaderStage::Callable => callable_groups.push(group_index),
+                        _ => unreachable!(),
+                    }
+                }
+                RayTracingShaderGroupCreateInfo::TrianglesHit { .. }
+                | RayTracingShaderGroupCreateInfo::ProceduralHit { .. } => {
+                    hit_groups.push(group_index)
+                }
+            }
+        }
+
+        let properties = device.physical_device().properties();
+        let handle_size = properties.shader_group_handle_size.unwrap() as DeviceSize;
+        let max_shader_group_stride = properties.max_shader_group_stride.unwrap() as DeviceSize;
+        let handle_alignment =
+            DeviceAlignment::new(properties.shader_group_handle_alignment.unwrap() as DeviceSize)
+                .unwrap();
+        let base_alignment =
+            DeviceAlignment::new(properties.shader_group_base_alignment.unwrap() as DeviceSize)
+                .unwrap();
+
+        let ShaderBindingTableLayout {
+            raygen_stride,
+            stride,
+            raygen_offset,
+            miss_offset,
+            hit_offset,
+            callable_offset,
+            total_size,
+        } = ShaderBindingTableLayout::new(
+            handle_size,
+            handle_alignment,
+            base_alignment,
+            [
+                raygen_groups.len(),
+                miss_groups.len(),
+                hit_groups.len(),
+                callable_groups.len(),
+            ],
+        );
+
+        if stride > max_shader_group_stride {
+            return Err(Box::new(ValidationError {
+                context: "pipeline".into(),
+                problem: "the `shader_group_handle_size` property, aligned to the \
+                    `shader_group_handle_alignment` property, is greater than the \
+                    `max_shader_group_stride` limit"
+                    .into(),
+                vuids: &[
+                    "VUID-vkCmdTraceRaysKHR-stride-03687",
+                    "VUID-vkCmdTraceRaysKHR-stride-03691",
+                    "VUID-vkCmdTraceRaysKHR-stride-03695",
+                ],
+                ..Default::default()
+            })
+            .into());
+        }
+
+        // The buffer may not be aligned to the base alignment, so allocate enough to align the
+        // start manually.
+        let buffer = Buffer::new_slice::<u8>(
+            allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::SHADER_BINDING_TABLE | BufferUsage::SHADER_DEVICE_ADDRESS,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            total_size + base_alignment.as_devicesize(),
+        )
+        .map_err(allocate_buffer_error_to_vulkan)?;
+
+        let buffer_address = buffer.device_address().unwrap().get();
+        let start_offset = align_up(buffer_address, base_alignment) - buffer_address;
+        let buffer = buffer.slice(start_offset..start_offset + total_size);
+        let base_address = buffer_address + start_offset;
+
+        let handles = pipeline.group_handles(0, pipeline.groups.len() as u32)?;
+
+        {
+            let mut data = buffer.write().unwrap();
+            let mut write_region = |offset: DeviceSize, stride: DeviceSize, groups: &[u32]| {
+                for (index, &group_index) in groups.iter().enumerate() {
+                    let start = (offset + index as DeviceSize * stride) as usize;
+                    let handle_start = (group_index as DeviceSize * handle_size) as usize;
+                    data[start..start + handle_size as usize].copy_from_slice(
+                        &handles.data()[handle_start..handle_start + handle_size as usize],
+                    );
+                }
+            };
+
+            write_region(raygen_offset, raygen_stride, &raygen_groups);
+            write_region(miss_offset, stride, &miss_groups);
+            write_region(hit_offset, stride, &hit_groups);
+            write_region(callable_offset, stride, &callable_groups);
+        }
+
+        let region = |offset: DeviceSize, stride: DeviceSize, count: usize| {
+            if count == 0 {
+                StridedDeviceAddressRegion::default()
+            } else {
+                StridedDeviceAddressRegion {
+                    device_address: base_address + offset,
+                    stride,
+                    size: count as DeviceSize * stride,
+                }
+            }
+        };
+
+        Ok(ShaderBindingTable {
+            pipeline,
+            buffer,
+            addresses: ShaderBindingTableAddresses {
+                raygen: region(raygen_offset, raygen_stride, raygen_groups.len().min(1)),
+                miss: region(miss_offset, stride, miss_groups.len()),
+                hit: region(hit_offset, stride, hit_groups.len()),
+                callable: region(callable_offset, stride, callable_groups.len()),
+            },
+        })
+    }
+
+    /// Returns the pipeline that the shader group handles were taken from.
+    #[inline]
+    pub fn pipeline(&self) -> &Arc<RayTracingPipeline> {
+        &self.pipeline
+    }
+
+    /// Returns the buffer containing the shader binding table.
+    #[inline]
+    pub fn buffer(&self) -> &Subbuffer<[u8]> {
+        &self.buffer
+    }
+
+    /// Returns the addresses of the regions of the shader binding table.
+    #[inline]
+    pub fn addresses(&self) -> &ShaderBindingTableAddresses {
+        &self.addresses
+    }
+}
+
+fn allocate_buffer_error_to_vulkan(err: Validated<AllocateBufferError>) -> Validated<VulkanError> {
+    match err {
+        Validated::Error(
+            AllocateBufferError::CreateBuffer(err) | AllocateBufferError::BindMemory(err),
+        ) => Validated::Error(err),
+        Validated::Error(AllocateBufferError::AllocateMemory(
+            MemoryAllocatorError::AllocateDeviceMemory(err),
+        )) => err,
+        Validated::Error(AllocateBufferError::AllocateMemory(_)) => {
+            Validated::Error(VulkanError::OutOfDeviceMemory)
+        }
+        Validated::ValidationError(err) => Validated::ValidationError(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ShaderBindingTableLayout;
+    use crate::memory::DeviceAlignment;
+
+    #[test]
+    fn shader_binding_table_layout() {
+        let alignment = |value| DeviceAlignment::new(value).unwrap();
+
+        // Handles are padded to the handle alignment, raygen records to the base alignment, and
+        // every region starts at a multiple of the base alignment.
+        assert_eq!(
+            ShaderBindingTableLayout::new(32, alignment(64), alignment(256), [2, 3, 1, 0]),
+            ShaderBindingTableLayout {
+                raygen_stride: 256,
+                stride: 64,
+                raygen_offset: 0,
+                miss_offset: 512,
+                hit_offset: 768,
+                callable_offset: 1024,
+                total_size: 1024,
+            },
+        );
+
+        // A region that exactly fills a multiple of the base alignment isn't padded further.
+        assert_eq!(
+            ShaderBindingTableLayout::new(48, alignment(32), alignment(64), [1, 2, 5, 3]),
+            ShaderBindingTableLayout {
+                raygen_stride: 64,
+                stride: 64,
+                raygen_offset: 0,
+                miss_offset: 64,
+                hit_offset: 192,
+                callable_offset: 512,
+                total_size: 704,
+            },
+        );
+    }
+}
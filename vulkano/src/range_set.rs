@@ -27,7 +27,7 @@ impl<T: Ord + Copy> RangeSet<T> {
     pub fn contains(&self, elements: Range<T>) -> bool {
         self.0
             .iter()
-            .any(|range| range.start <= elements.end && range.end >= elements.end)
+            .any(|range| range.start <= elements.start && range.end >= elements.end)
     }
 
     /// Removes all ranges from the set.
@@ -66,3 +66,22 @@ impl<T: Ord + Copy> RangeSet<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::RangeSet;
+
+    #[test]
+    fn contains() {
+        let mut set = RangeSet::new();
+        set.insert(4..8);
+
+        assert!(set.contains(4..8));
+        assert!(set.contains(5..7));
+        assert!(!set.contains(2..6));
+        assert!(!set.contains(6..10));
+        // Starts before the stored range, but ends within it.
+        assert!(!set.contains(0..8));
+        assert!(!set.contains(8..12));
+    }
+}
@@ -102,6 +102,8 @@ impl RenderPass {
             depth_stencil_resolve_attachment_vk: ash::vk::AttachmentReference2,
             per_depth_stencil_resolve_attachment_vk: PerAttachmentReferenceVk,
             depth_stencil_resolve_vk: Option<ash::vk::SubpassDescriptionDepthStencilResolve>,
+            fragment_shading_rate_attachment_vk: ash::vk::AttachmentReference2,
+            fragment_shading_rate_vk: Option<ash::vk::FragmentShadingRateAttachmentInfoKHR>,
         }
 
         #[derive(Default)]
@@ -123,6 +125,8 @@ impl RenderPass {
                         ref depth_stencil_resolve_attachment,
                         depth_resolve_mode,
                         stencil_resolve_mode,
+                        ref fragment_shading_rate_attachment,
+                        fragment_shading_rate_attachment_texel_size,
                         ref preserve_attachments,
                         _ne: _,
                     } = subpass;
@@ -304,6 +308,41 @@ impl RenderPass {
                             ..Default::default()
                         });
 
+                    let fragment_shading_rate_attachment_vk =
+                        if let Some(fragment_shading_rate_attachment) =
+                            fragment_shading_rate_attachment
+                        {
+                            let &AttachmentReference {
+                                attachment,
+                                layout,
+                                stencil_layout: _,
+                                aspects: _,
+                                _ne: _,
+                            } = fragment_shading_rate_attachment;
+
+                            ash::vk::AttachmentReference2 {
+                                attachment,
+                                layout: layout.into(),
+                                ..Default::default()
+                            }
+                        } else {
+                            ash::vk::AttachmentReference2 {
+                                attachment: ash::vk::ATTACHMENT_UNUSED,
+                                ..Default::default()
+                            }
+                        };
+
+                    let fragment_shading_rate_vk = fragment_shading_rate_attachment
+                        .is_some()
+                        .then_some(ash::vk::FragmentShadingRateAttachmentInfoKHR {
+                            p_fragment_shading_rate_attachment: ptr::null(),
+                            shading_rate_attachment_texel_size: ash::vk::Extent2D {
+                                width: fragment_shading_rate_attachment_texel_size[0],
+                                height: fragment_shading_rate_attachment_texel_size[1],
+                            },
+                            ..Default::default()
+                        });
+
                     (
                         ash::vk::SubpassDescription2 {
                             flags: flags.into(),
@@ -333,6 +372,8 @@ impl RenderPass {
                             depth_stencil_resolve_attachment_vk,
                             per_depth_stencil_resolve_attachment_vk,
                             depth_stencil_resolve_vk,
+                            fragment_shading_rate_attachment_vk,
+                            fragment_shading_rate_vk,
                         },
                     )
                 })
@@ -349,6 +390,8 @@ impl RenderPass {
                 depth_stencil_resolve_attachment_vk,
                 per_depth_stencil_resolve_attachment_vk,
                 depth_stencil_resolve_vk,
+                fragment_shading_rate_attachment_vk,
+                fragment_shading_rate_vk,
             } = per_subpass_vk;
 
             for (input_attachment_vk, per_input_attachment_vk) in input_attachments_vk
@@ -415,6 +458,16 @@ impl RenderPass {
                 depth_stencil_resolve_vk.p_next = subpass_vk.p_next;
                 subpass_vk.p_next = depth_stencil_resolve_vk as *const _ as *const _;
             }
+
+            if let Some(fragment_shading_rate_vk) = fragment_shading_rate_vk {
+                *fragment_shading_rate_vk = ash::vk::FragmentShadingRateAttachmentInfoKHR {
+                    p_fragment_shading_rate_attachment: fragment_shading_rate_attachment_vk,
+                    ..*fragment_shading_rate_vk
+                };
+
+                fragment_shading_rate_vk.p_next = subpass_vk.p_next;
+                subpass_vk.p_next = fragment_shading_rate_vk as *const _ as *const _;
+            }
         }
 
         struct PerSubpassDependencyVk {
@@ -593,6 +646,8 @@ impl RenderPass {
                     depth_stencil_resolve_attachment: _,
                     depth_resolve_mode: _,
                     stencil_resolve_mode: _,
+                    fragment_shading_rate_attachment: _,
+                    fragment_shading_rate_attachment_texel_size: _,
                     ref preserve_attachments,
                     _ne: _,
                 } = subpass;
@@ -154,6 +154,24 @@ impl Framebuffer {
                 }));
             }
 
+            if attachment_use.fragment_shading_rate_attachment
+                && !image_view
+                    .usage()
+                    .intersects(ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`render_pass` uses `create_info.attachments[{}]` as \
+                        a fragment shading rate attachment, but it was not created with the \
+                        `ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT` usage",
+                        index,
+                    )
+                    .into(),
+                    vuids: &["VUID-VkFramebufferCreateInfo-flags-04548"],
+                    ..Default::default()
+                }));
+            }
+
             if image_view.format() != attachment_desc.format {
                 return Err(Box::new(ValidationError {
                     problem: format!(
@@ -235,6 +253,58 @@ impl Framebuffer {
                 }
             }
 
+            if attachment_use.fragment_shading_rate_attachment {
+                let texel_size = render_pass
+                    .subpasses()
+                    .iter()
+                    .filter(|subpass_desc| {
+                        subpass_desc
+                            .fragment_shading_rate_attachment
+                            .as_ref()
+                            .is_some_and(|atch_ref| atch_ref.attachment as usize == index)
+                    })
+                    .fold([1, 1], |texel_size, subpass_desc| {
+                        let subpass_texel_size =
+                            subpass_desc.fragment_shading_rate_attachment_texel_size;
+                        [
+                            texel_size[0].max(subpass_texel_size[0]),
+                            texel_size[1].max(subpass_texel_size[1]),
+                        ]
+                    });
+
+                if image_view_extent[0] < extent[0].div_ceil(texel_size[0]) {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`render_pass` uses `create_info.attachments[{}]` as a fragment \
+                            shading rate attachment, but its width is less than \
+                            `create_info.extent[0]` divided by the largest \
+                            `fragment_shading_rate_attachment_texel_size[0]` that it is used \
+                            with, rounded up",
+                            index,
+                        )
+                        .into(),
+                        vuids: &["VUID-VkFramebufferCreateInfo-flags-04539"],
+                        ..Default::default()
+                    }));
+                }
+
+                if image_view_extent[1] < extent[1].div_ceil(texel_size[1]) {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`render_pass` uses `create_info.attachments[{}]` as a fragment \
+                            shading rate attachment, but its height is less than \
+                            `create_info.extent[1]` divided by the largest \
+                            `fragment_shading_rate_attachment_texel_size[1]` that it is used \
+                            with, rounded up",
+                            index,
+                        )
+                        .into(),
+                        vuids: &["VUID-VkFramebufferCreateInfo-flags-04540"],
+                        ..Default::default()
+                    }));
+                }
+            }
+
             if render_pass.views_used() != 0 && layers != 1 {
                 return Err(Box::new(ValidationError {
                     problem: "`render_pass` has multiview enabled, but \
@@ -494,7 +564,18 @@ impl FramebufferCreateInfo {
                 u32::MAX
             };
 
-            for image_view in attachments.iter() {
+            for (image_view, attachment_use) in attachments.iter().zip(&render_pass.attachment_use)
+            {
+                // A fragment shading rate attachment is smaller than the framebuffer, so it
+                // doesn't constrain the extent.
+                if attachment_use.fragment_shading_rate_attachment
+                    && !(attachment_use.color_attachment
+                        || attachment_use.depth_stencil_attachment
+                        || attachment_use.input_attachment)
+                {
+                    continue;
+                }
+
                 let image_view_extent = image_view.image().extent();
                 let image_view_array_layers =
                     image_view.subresource_range().array_layers.len() as u32;
@@ -231,6 +231,8 @@ impl RenderPass {
                 ref depth_stencil_resolve_attachment,
                 depth_resolve_mode: _,
                 stencil_resolve_mode: _,
+                ref fragment_shading_rate_attachment,
+                fragment_shading_rate_attachment_texel_size: _,
                 preserve_attachments: _,
                 _ne: _,
             } = subpass_desc;
@@ -258,6 +260,11 @@ impl RenderPass {
                 attachment_use[input_attachment.attachment as usize].input_attachment = true;
             }
 
+            if let Some(fragment_shading_rate_attachment) = fragment_shading_rate_attachment {
+                attachment_use[fragment_shading_rate_attachment.attachment as usize]
+                    .fragment_shading_rate_attachment = true;
+            }
+
             views_used = max(views_used, u32::BITS - view_mask.leading_zeros());
         }
 
@@ -502,6 +509,9 @@ impl RenderPass {
                     depth_stencil_resolve_attachment: depth_stencil_resolve_attachment1,
                     depth_resolve_mode: depth_resolve_mode1,
                     stencil_resolve_mode: stencil_resolve_mode1,
+                    fragment_shading_rate_attachment: fragment_shading_rate_attachment1,
+                    fragment_shading_rate_attachment_texel_size:
+                        fragment_shading_rate_attachment_texel_size1,
                     preserve_attachments: _,
                     _ne: _,
                 } = subpass1;
@@ -515,6 +525,9 @@ impl RenderPass {
                     depth_stencil_resolve_attachment: depth_stencil_resolve_attachment2,
                     depth_resolve_mode: depth_resolve_mode2,
                     stencil_resolve_mode: stencil_resolve_mode2,
+                    fragment_shading_rate_attachment: fragment_shading_rate_attachment2,
+                    fragment_shading_rate_attachment_texel_size:
+                        fragment_shading_rate_attachment_texel_size2,
                     preserve_attachments: _,
                     _ne: _,
                 } = subpass2;
@@ -580,6 +593,20 @@ impl RenderPass {
                     return false;
                 }
 
+                if !are_atch_refs_compatible(
+                    fragment_shading_rate_attachment1.as_ref(),
+                    fragment_shading_rate_attachment2.as_ref(),
+                ) {
+                    return false;
+                }
+
+                if fragment_shading_rate_attachment1.is_some()
+                    && fragment_shading_rate_attachment_texel_size1
+                        != fragment_shading_rate_attachment_texel_size2
+                {
+                    return false;
+                }
+
                 if view_mask1 != view_mask2 {
                     return false;
                 }
@@ -899,6 +926,8 @@ impl RenderPassCreateInfo {
                 ref depth_stencil_resolve_attachment,
                 depth_resolve_mode: _,
                 stencil_resolve_mode: _,
+                ref fragment_shading_rate_attachment,
+                fragment_shading_rate_attachment_texel_size: _,
                 ref preserve_attachments,
                 _ne: _,
             } = subpass_desc;
@@ -1480,6 +1509,40 @@ impl RenderPassCreateInfo {
                 }
             }
 
+            if let Some(fragment_shading_rate_attachment) = fragment_shading_rate_attachment {
+                let attachment = fragment_shading_rate_attachment.attachment;
+
+                if attachment as usize >= attachments.len() {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`subpasses[{}].fragment_shading_rate_attachment.attachment` \
+                            is not less than the length of `attachments`",
+                            subpass_index
+                        )
+                        .into(),
+                        // vuids?
+                        ..Default::default()
+                    }));
+                }
+
+                if !attachment_potential_format_features[attachment as usize]
+                    .intersects(FormatFeatures::FRAGMENT_SHADING_RATE_ATTACHMENT)
+                {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "attachment {0} is used in \
+                            `subpasses[{1}].fragment_shading_rate_attachment`, \
+                            but the potential format features of `attachments[{0}].format` \
+                            do not include `FormatFeatures::FRAGMENT_SHADING_RATE_ATTACHMENT`",
+                            attachment, subpass_index
+                        )
+                        .into(),
+                        // vuids?
+                        ..Default::default()
+                    }));
+                }
+            }
+
             for (ref_index, &atch) in preserve_attachments.iter().enumerate() {
                 if atch as usize >= attachments.len() {
                     return Err(Box::new(ValidationError {
@@ -2295,6 +2358,33 @@ pub struct SubpassDescription {
     /// The default value is `None`.
     pub stencil_resolve_mode: Option<ResolveMode>,
 
+    /// The single attachment of the render pass that is to be used as fragment shading rate
+    /// attachment in this subpass.
+    ///
+    /// Each texel of the attachment provides the fragment shading rate for a region of
+    /// `fragment_shading_rate_attachment_texel_size` pixels of the framebuffer. The referenced
+    /// attachment must not be used as any other attachment type in the subpass, and its layout
+    /// must be [`ImageLayout::General`] or [`ImageLayout::FragmentShadingRateAttachmentOptimal`].
+    ///
+    /// If this is `Some`, then the
+    /// [`attachment_fragment_shading_rate`](crate::device::Features::attachment_fragment_shading_rate)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is `None`.
+    pub fragment_shading_rate_attachment: Option<AttachmentReference>,
+
+    /// The size in pixels of the framebuffer region that each texel of
+    /// `fragment_shading_rate_attachment` applies to.
+    ///
+    /// Each dimension must be a power of two, and within the range given by the
+    /// [`min_fragment_shading_rate_attachment_texel_size`](crate::device::Properties::min_fragment_shading_rate_attachment_texel_size)
+    /// and
+    /// [`max_fragment_shading_rate_attachment_texel_size`](crate::device::Properties::max_fragment_shading_rate_attachment_texel_size)
+    /// device properties. This is ignored if `fragment_shading_rate_attachment` is `None`.
+    ///
+    /// The default value is `[16, 16]`.
+    pub fragment_shading_rate_attachment_texel_size: [u32; 2],
+
     /// The indices of attachments of the render pass that will be preserved during this subpass.
     ///
     /// The referenced attachments must not be used as any other attachment type in the subpass.
@@ -2317,6 +2407,8 @@ impl Default for SubpassDescription {
             depth_stencil_resolve_attachment: None,
             depth_resolve_mode: None,
             stencil_resolve_mode: None,
+            fragment_shading_rate_attachment: None,
+            fragment_shading_rate_attachment_texel_size: [16, 16],
             input_attachments: Vec::new(),
             preserve_attachments: Vec::new(),
             _ne: crate::NonExhaustive(()),
@@ -2338,6 +2430,8 @@ impl SubpassDescription {
             ref depth_stencil_resolve_attachment,
             depth_resolve_mode,
             stencil_resolve_mode,
+            ref fragment_shading_rate_attachment,
+            fragment_shading_rate_attachment_texel_size,
             ref preserve_attachments,
             _ne: _,
         } = self;
@@ -2347,6 +2441,60 @@ impl SubpassDescription {
                 .set_vuids(&["VUID-VkSubpassDescription2-flags-parameter"])
         })?;
 
+        if flags.intersects(SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS)
+            && !device
+                .enabled_features()
+                .rasterization_order_color_attachment_access
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains \
+                    `SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS`"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "rasterization_order_color_attachment_access",
+                )])]),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
+        if flags.intersects(SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS)
+            && !device
+                .enabled_features()
+                .rasterization_order_depth_attachment_access
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains \
+                    `SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS`"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "rasterization_order_depth_attachment_access",
+                )])]),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
+        if flags.intersects(SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS)
+            && !device
+                .enabled_features()
+                .rasterization_order_stencil_attachment_access
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains \
+                    `SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS`"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "rasterization_order_stencil_attachment_access",
+                )])]),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
         if color_attachments.len() as u32 > properties.max_color_attachments {
             return Err(Box::new(ValidationError {
                 context: "color_attachments".into(),
@@ -2979,6 +3127,161 @@ impl SubpassDescription {
             }
         }
 
+        if let Some(fragment_shading_rate_attachment) = fragment_shading_rate_attachment {
+            if !device.enabled_features().attachment_fragment_shading_rate {
+                return Err(Box::new(ValidationError {
+                    context: "fragment_shading_rate_attachment".into(),
+                    problem: "is `Some`".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "attachment_fragment_shading_rate",
+                    )])]),
+                    // vuids?
+                    ..Default::default()
+                }));
+            }
+
+            fragment_shading_rate_attachment
+                .validate(device)
+                .map_err(|err| err.add_context("fragment_shading_rate_attachment"))?;
+
+            let &AttachmentReference {
+                attachment,
+                layout,
+                stencil_layout: _,
+                aspects: _,
+                _ne: _,
+            } = fragment_shading_rate_attachment;
+
+            if !matches!(
+                layout,
+                ImageLayout::General | ImageLayout::FragmentShadingRateAttachmentOptimal
+            ) {
+                return Err(Box::new(ValidationError {
+                    context: "fragment_shading_rate_attachment.layout".into(),
+                    problem: "is not `ImageLayout::General` or \
+                        `ImageLayout::FragmentShadingRateAttachmentOptimal`"
+                        .into(),
+                    vuids: &["VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04524"],
+                    ..Default::default()
+                }));
+            }
+
+            if (input_attachments.iter().flatten())
+                .chain(color_attachments.iter().flatten())
+                .chain(color_resolve_attachments.iter().flatten())
+                .chain(depth_stencil_attachment.iter())
+                .chain(depth_stencil_resolve_attachment.iter())
+                .any(|atch_ref| atch_ref.attachment == attachment)
+                || preserve_attachments.contains(&attachment)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "`fragment_shading_rate_attachment.attachment` also occurs in \
+                        another attachment of the subpass"
+                        .into(),
+                    // vuids?
+                    ..Default::default()
+                }));
+            }
+
+            let [texel_width, texel_height] = fragment_shading_rate_attachment_texel_size;
+            let min_texel_size = properties
+                .min_fragment_shading_rate_attachment_texel_size
+                .unwrap_or_default();
+            let max_texel_size = properties
+                .max_fragment_shading_rate_attachment_texel_size
+                .unwrap_or_default();
+
+            if !texel_width.is_power_of_two() {
+                return Err(Box::new(ValidationError {
+                    context: "fragment_shading_rate_attachment_texel_size[0]".into(),
+                    problem: "is not a power of two".into(),
+                    vuids: &["VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04525"],
+                    ..Default::default()
+                }));
+            }
+
+            if texel_width > max_texel_size[0] {
+                return Err(Box::new(ValidationError {
+                    context: "fragment_shading_rate_attachment_texel_size[0]".into(),
+                    problem: "is greater than the \
+                        `max_fragment_shading_rate_attachment_texel_size[0]` limit"
+                        .into(),
+                    vuids: &["VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04526"],
+                    ..Default::default()
+                }));
+            }
+
+            if texel_width < min_texel_size[0] {
+                return Err(Box::new(ValidationError {
+                    context: "fragment_shading_rate_attachment_texel_size[0]".into(),
+                    problem: "is less than the \
+                        `min_fragment_shading_rate_attachment_texel_size[0]` limit"
+                        .into(),
+                    vuids: &["VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04527"],
+                    ..Default::default()
+                }));
+            }
+
+            if !texel_height.is_power_of_two() {
+                return Err(Box::new(ValidationError {
+                    context: "fragment_shading_rate_attachment_texel_size[1]".into(),
+                    problem: "is not a power of two".into(),
+                    vuids: &["VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04528"],
+                    ..Default::default()
+                }));
+            }
+
+            if texel_height > max_texel_size[1] {
+                return Err(Box::new(ValidationError {
+                    context: "fragment_shading_rate_attachment_texel_size[1]".into(),
+                    problem: "is greater than the \
+                        `max_fragment_shading_rate_attachment_texel_size[1]` limit"
+                        .into(),
+                    vuids: &["VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04529"],
+                    ..Default::default()
+                }));
+            }
+
+            if texel_height < min_texel_size[1] {
+                return Err(Box::new(ValidationError {
+                    context: "fragment_shading_rate_attachment_texel_size[1]".into(),
+                    problem: "is less than the \
+                        `min_fragment_shading_rate_attachment_texel_size[1]` limit"
+                        .into(),
+                    vuids: &["VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04530"],
+                    ..Default::default()
+                }));
+            }
+
+            let max_aspect_ratio = properties
+                .max_fragment_shading_rate_attachment_texel_size_aspect_ratio
+                .unwrap_or_default();
+
+            if texel_width / texel_height > max_aspect_ratio {
+                return Err(Box::new(ValidationError {
+                    problem: "the quotient of `fragment_shading_rate_attachment_texel_size[0]` \
+                        divided by `fragment_shading_rate_attachment_texel_size[1]` is greater \
+                        than the `max_fragment_shading_rate_attachment_texel_size_aspect_ratio` \
+                        limit"
+                        .into(),
+                    vuids: &["VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04531"],
+                    ..Default::default()
+                }));
+            }
+
+            if texel_height / texel_width > max_aspect_ratio {
+                return Err(Box::new(ValidationError {
+                    problem: "the quotient of `fragment_shading_rate_attachment_texel_size[1]` \
+                        divided by `fragment_shading_rate_attachment_texel_size[0]` is greater \
+                        than the `max_fragment_shading_rate_attachment_texel_size_aspect_ratio` \
+                        limit"
+                        .into(),
+                    vuids: &["VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04532"],
+                    ..Default::default()
+                }));
+            }
+        }
+
         if !device.enabled_features().multiview && view_mask != 0 {
             return Err(Box::new(ValidationError {
                 context: "view_mask".into(),
@@ -2990,7 +3293,7 @@ impl SubpassDescription {
 
         let highest_view_index = u32::BITS - view_mask.leading_zeros();
 
-        if highest_view_index >= properties.max_multiview_view_count.unwrap_or(0) {
+        if highest_view_index > properties.max_multiview_view_count.unwrap_or(0) {
             return Err(Box::new(ValidationError {
                 context: "view_mask".into(),
                 problem: "the highest enabled view index is not less than the \
@@ -3035,23 +3338,58 @@ vulkan_bitflags! {
         device_extensions: [qcom_render_pass_shader_resolve],
     }, */
 
-    /* TODO: enable
-    // TODO: document
-    RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS = RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS_EXT {
-        device_extensions: [ext_rasterization_order_attachment_access, arm_rasterization_order_attachment_access],
-    }, */
+    /// Reads from the color attachments of the subpass, for example as input attachments, are
+    /// synchronized in rasterization order with writes from earlier fragments. This allows
+    /// reading the results of earlier fragments in the same subpass without a pipeline barrier,
+    /// for example for programmable blending.
+    ///
+    /// Pipelines used in the subpass can then use
+    /// [`ColorBlendStateFlags::RASTERIZATION_ORDER_ATTACHMENT_ACCESS`].
+    ///
+    /// The
+    /// [`rasterization_order_color_attachment_access`](crate::device::Features::rasterization_order_color_attachment_access)
+    /// feature must be enabled on the device.
+    ///
+    /// [`ColorBlendStateFlags::RASTERIZATION_ORDER_ATTACHMENT_ACCESS`]: crate::pipeline::graphics::color_blend::ColorBlendStateFlags::RASTERIZATION_ORDER_ATTACHMENT_ACCESS
+    RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS = RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS_EXT
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_rasterization_order_attachment_access)]),
+        RequiresAllOf([DeviceExtension(arm_rasterization_order_attachment_access)]),
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS = RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS_EXT {
-        device_extensions: [ext_rasterization_order_attachment_access, arm_rasterization_order_attachment_access],
-    }, */
+    /// Like `RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS`, but for the depth aspect of the
+    /// depth/stencil attachment.
+    ///
+    /// Pipelines used in the subpass can then use
+    /// [`DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS`].
+    ///
+    /// The
+    /// [`rasterization_order_depth_attachment_access`](crate::device::Features::rasterization_order_depth_attachment_access)
+    /// feature must be enabled on the device.
+    ///
+    /// [`DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS`]: crate::pipeline::graphics::depth_stencil::DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS
+    RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS = RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS_EXT
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_rasterization_order_attachment_access)]),
+        RequiresAllOf([DeviceExtension(arm_rasterization_order_attachment_access)]),
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS = RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS_EXT {
-        device_extensions: [ext_rasterization_order_attachment_access, arm_rasterization_order_attachment_access],
-    }, */
+    /// Like `RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS`, but for the stencil aspect of the
+    /// depth/stencil attachment.
+    ///
+    /// Pipelines used in the subpass can then use
+    /// [`DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS`].
+    ///
+    /// The
+    /// [`rasterization_order_stencil_attachment_access`](crate::device::Features::rasterization_order_stencil_attachment_access)
+    /// feature must be enabled on the device.
+    ///
+    /// [`DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS`]: crate::pipeline::graphics::depth_stencil::DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS
+    RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS = RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS_EXT
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_rasterization_order_attachment_access)]),
+        RequiresAllOf([DeviceExtension(arm_rasterization_order_attachment_access)]),
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -3651,6 +3989,7 @@ pub(crate) struct AttachmentUse {
     pub(crate) color_attachment: bool,
     pub(crate) depth_stencil_attachment: bool,
     pub(crate) input_attachment: bool,
+    pub(crate) fragment_shading_rate_attachment: bool,
 }
 
 #[cfg(test)]
@@ -81,6 +81,8 @@
 //!
 //! Each alignment type is a subset of the ones above it, so if something adheres to the extended
 //! alignment rules, it also follows the rules for the base and scalar alignments.
+//! When a shader module is created, the offsets and strides of its blocks are checked against
+//! these rules, and an error is returned if a block requires a feature that is not enabled.
 //!
 //! In all three of these alignment rules, a primitive/scalar value with a size of N bytes has an
 //! alignment of N, meaning that it must have an offset that is a multiple of its size,
@@ -131,7 +133,10 @@
 //! [`scalar_block_layout`]: crate::device::Features::scalar_block_layout
 //! [`uniform_buffer_standard_layout`]: crate::device::Features::uniform_buffer_standard_layout
 
-use self::spirv::{Id, Instruction};
+use self::{
+    reflect::BlockLayout,
+    spirv::{Decoration, Id, Instruction, StorageClass},
+};
 use crate::{
     descriptor_set::layout::DescriptorType,
     device::{Device, DeviceOwned},
@@ -159,6 +164,7 @@ use std::{
     sync::Arc,
 };
 
+pub mod object;
 pub mod reflect;
 pub mod spirv;
 
@@ -172,8 +178,10 @@ pub struct ShaderModule {
     device: InstanceOwnedDebugWrapper<Arc<Device>>,
     id: NonZeroU64,
 
+    code: Vec<u32>,
     spirv: Spirv,
     specialization_constants: HashMap<u32, SpecializationConstant>,
+    specialization_constant_names: HashMap<String, u32>,
 }
 
 impl ShaderModule {
@@ -278,16 +286,19 @@ impl ShaderModule {
         create_info: ShaderModuleCreateInfo<'_>,
         spirv: Spirv,
     ) -> Arc<ShaderModule> {
-        let ShaderModuleCreateInfo { code: _, _ne: _ } = create_info;
+        let ShaderModuleCreateInfo { code, _ne: _ } = create_info;
         let specialization_constants = reflect::specialization_constants(&spirv);
+        let specialization_constant_names = reflect::specialization_constant_names(&spirv);
 
         Arc::new(ShaderModule {
             handle,
             device: InstanceOwnedDebugWrapper(device),
             id: Self::next_id(),
 
+            code: code.to_vec(),
             spirv,
             specialization_constants,
+            specialization_constant_names,
         })
     }
 
@@ -325,6 +336,12 @@ impl ShaderModule {
         Self::new(device, ShaderModuleCreateInfo::new(&words))
     }
 
+    /// Returns the SPIR-V code that the module was created from.
+    #[inline]
+    pub(crate) fn code(&self) -> &[u32] {
+        &self.code
+    }
+
     /// Returns the specialization constants that are defined in the module,
     /// along with their default values.
     ///
@@ -335,6 +352,16 @@ impl ShaderModule {
         &self.specialization_constants
     }
 
+    /// Returns the `constant_id` of the specialization constant with the given name, or `None`
+    /// if there is no such constant.
+    ///
+    /// The names are read from the debug information in the SPIR-V code, so this returns `None`
+    /// for every name if the debug information was stripped from the module.
+    #[inline]
+    pub fn specialization_constant_id(&self, name: &str) -> Option<u32> {
+        self.specialization_constant_names.get(name).copied()
+    }
+
     /// Applies the specialization constants to the shader module,
     /// and returns a specialized version of the module.
     ///
@@ -556,6 +583,8 @@ impl<'a> ShaderModuleCreateInfo<'a> {
             validate_spirv_extension(device, extension).map_err(|err| err.add_context("code"))?;
         }
 
+        validate_block_layouts(device, spirv).map_err(|err| err.add_context("code"))?;
+
         // VUID-VkShaderModuleCreateInfo-pCode-08736
         // VUID-VkShaderModuleCreateInfo-pCode-08737
         // VUID-VkShaderModuleCreateInfo-pCode-08738
@@ -565,6 +594,110 @@ impl<'a> ShaderModuleCreateInfo<'a> {
     }
 }
 
+/// Checks that the members of the uniform buffer, storage buffer and push constant blocks in
+/// `spirv` are laid out according to the rules that are allowed by the enabled features.
+fn validate_block_layouts(device: &Device, spirv: &Spirv) -> Result<(), Box<ValidationError>> {
+    let relaxed = device.api_version() >= Version::V1_1
+        || device.enabled_extensions().khr_relaxed_block_layout;
+    let scalar_block_layout = device.enabled_features().scalar_block_layout;
+    let uniform_buffer_standard_layout = device.enabled_features().uniform_buffer_standard_layout;
+
+    for instruction in spirv.iter_global() {
+        let (result_id, result_type_id, storage_class) = match *instruction {
+            Instruction::Variable {
+                result_id,
+                result_type_id,
+                storage_class,
+                ..
+            } => (result_id, result_type_id, storage_class),
+            _ => continue,
+        };
+
+        if !matches!(
+            storage_class,
+            StorageClass::Uniform | StorageClass::StorageBuffer | StorageClass::PushConstant
+        ) {
+            continue;
+        }
+
+        let mut block_type = match *spirv.id(result_type_id).instruction() {
+            Instruction::TypePointer { ty, .. } => ty,
+            _ => continue,
+        };
+
+        // Arrays of descriptors.
+        while let Instruction::TypeArray { element_type, .. }
+        | Instruction::TypeRuntimeArray { element_type, .. } =
+            *spirv.id(block_type).instruction()
+        {
+            block_type = element_type;
+        }
+
+        let is_uniform_buffer = storage_class == StorageClass::Uniform
+            && spirv.id(block_type).iter_decoration().any(|instruction| {
+                matches!(
+                    instruction,
+                    Instruction::Decorate {
+                        decoration: Decoration::Block,
+                        ..
+                    }
+                )
+            });
+
+        match reflect::block_layout(spirv, block_type, relaxed) {
+            None => {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "the members of the block of variable {} are not aligned according to \
+                        any of the block layout rules",
+                        result_id
+                    )
+                    .into(),
+                    vuids: &["VUID-VkShaderModuleCreateInfo-pCode-08737"],
+                    ..Default::default()
+                }));
+            }
+            Some(BlockLayout::Scalar) if !scalar_block_layout => {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "the members of the block of variable {} are aligned according to the \
+                        scalar block layout rules",
+                        result_id
+                    )
+                    .into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "scalar_block_layout",
+                    )])]),
+                    vuids: &["VUID-VkShaderModuleCreateInfo-pCode-08737"],
+                    ..Default::default()
+                }));
+            }
+            Some(BlockLayout::Base)
+                if is_uniform_buffer
+                    && !(uniform_buffer_standard_layout || scalar_block_layout) =>
+            {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "variable {} is a uniform buffer, and the members of its block are \
+                        aligned according to the base (std430) block layout rules",
+                        result_id
+                    )
+                    .into(),
+                    requires_one_of: RequiresOneOf(&[
+                        RequiresAllOf(&[Requires::Feature("uniform_buffer_standard_layout")]),
+                        RequiresAllOf(&[Requires::Feature("scalar_block_layout")]),
+                    ]),
+                    vuids: &["VUID-VkShaderModuleCreateInfo-pCode-08737"],
+                    ..Default::default()
+                }));
+            }
+            Some(_) => (),
+        }
+    }
+
+    Ok(())
+}
+
 /// The value to provide for a specialization constant, when creating a pipeline.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum SpecializationConstant {
@@ -0,0 +1,559 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Shaders that can be bound directly, without creating a pipeline.
+//!
+//! Normally, shaders are combined with fixed-function state into a [pipeline] object, and a new
+//! pipeline must be created for every combination of shaders and non-dynamic state that is used.
+//! A *shader object* instead wraps a single shader stage. Shader objects for different stages
+//! are bound individually with the [`bind_shaders`] command, and all state that would otherwise
+//! be part of the pipeline is set with the dynamic state commands. This avoids having to create
+//! large numbers of pipelines, for example in editors that expose many combinations of state.
+//!
+//! Shader objects require the
+//! [`shader_object`](crate::device::Features::shader_object) feature to be enabled on the device.
+//!
+//! When graphics shader objects are bound, every graphics stage that is supported by the
+//! enabled features must be bound, either to a shader object or to `None`, before drawing. The
+//! dynamic state that is needed by the bound stages must also be set, including state that has
+//! no pipeline equivalent yet, such as [`set_vertex_input`] and [`set_polygon_mode`]. Draw
+//! commands with shader objects must be recorded inside a render pass instance begun with
+//! [`begin_rendering`].
+//!
+//! [pipeline]: crate::pipeline
+//! [`bind_shaders`]: crate::command_buffer::AutoCommandBufferBuilder::bind_shaders
+//! [`set_vertex_input`]: crate::command_buffer::AutoCommandBufferBuilder::set_vertex_input
+//! [`set_polygon_mode`]: crate::command_buffer::AutoCommandBufferBuilder::set_polygon_mode
+//! [`begin_rendering`]: crate::command_buffer::AutoCommandBufferBuilder::begin_rendering
+
+use crate::{
+    device::{Device, DeviceOwned},
+    instance::InstanceOwnedDebugWrapper,
+    macros::{impl_id_counter, vulkan_bitflags},
+    pipeline::{PipelineLayout, PipelineShaderStageCreateInfo},
+    shader::{EntryPoint, ShaderStage, ShaderStages},
+    Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, VulkanError, VulkanObject,
+};
+use smallvec::SmallVec;
+use std::{
+    ffi::CString,
+    mem::{size_of_val, MaybeUninit},
+    num::NonZeroU64,
+    ptr,
+    sync::Arc,
+};
+
+/// A single shader stage that can be bound without a pipeline.
+#[derive(Debug)]
+pub struct ShaderObject {
+    handle: ash::vk::ShaderEXT,
+    device: InstanceOwnedDebugWrapper<Arc<Device>>,
+    id: NonZeroU64,
+
+    flags: ShaderCreateFlags,
+    entry_point: EntryPoint,
+    next_stages: ShaderStages,
+    layout: Arc<PipelineLayout>,
+}
+
+impl ShaderObject {
+    /// Creates a new `ShaderObject`.
+    #[inline]
+    pub fn new(
+        device: Arc<Device>,
+        create_info: ShaderCreateInfo,
+    ) -> Result<Arc<ShaderObject>, Validated<VulkanError>> {
+        Self::validate_new(&device, &create_info)?;
+
+        unsafe { Ok(Self::new_unchecked(device, create_info)?) }
+    }
+
+    fn validate_new(
+        device: &Device,
+        create_info: &ShaderCreateInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        if !device.enabled_features().shader_object {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "shader_object",
+                )])]),
+                vuids: &["VUID-vkCreateShadersEXT-None-08400"],
+                ..Default::default()
+            }));
+        }
+
+        create_info
+            .validate(device)
+            .map_err(|err| err.add_context("create_info"))?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn new_unchecked(
+        device: Arc<Device>,
+        create_info: ShaderCreateInfo,
+    ) -> Result<Arc<ShaderObject>, VulkanError> {
+        let &ShaderCreateInfo {
+            flags,
+            ref entry_point,
+            next_stages,
+            ref layout,
+            _ne: _,
+        } = &create_info;
+
+        let entry_point_info = entry_point.info();
+        let code = entry_point.module().base_module().code();
+        let name_vk = CString::new(entry_point_info.name.as_str()).unwrap();
+
+        let mut specialization_data_vk: Vec<u8> = Vec::new();
+        let specialization_map_entries_vk: Vec<_> = entry_point
+            .module()
+            .specialization_info()
+            .iter()
+            .map(|(&constant_id, value)| {
+                let data = value.as_bytes();
+                let offset = specialization_data_vk.len() as u32;
+                let size = data.len();
+                specialization_data_vk.extend(data);
+
+                ash::vk::SpecializationMapEntry {
+                    constant_id,
+                    offset,
+                    size,
+                }
+            })
+            .collect();
+
+        let specialization_info_vk = ash::vk::SpecializationInfo {
+            map_entry_count: specialization_map_entries_vk.len() as u32,
+            p_map_entries: specialization_map_entries_vk.as_ptr(),
+            data_size: specialization_data_vk.len(),
+            p_data: specialization_data_vk.as_ptr() as *const _,
+        };
+
+        let set_layouts_vk: SmallVec<[_; 4]> =
+            layout.set_layouts().iter().map(|l| l.handle()).collect();
+        let push_constant_ranges_vk: SmallVec<[_; 4]> = layout
+            .push_constant_ranges()
+            .iter()
+            .map(|range| ash::vk::PushConstantRange {
+                stage_flags: range.stages.into(),
+                offset: range.offset,
+                size: range.size,
+            })
+            .collect();
+
+        let create_info_vk = ash::vk::ShaderCreateInfoEXT {
+            flags: flags.into(),
+            stage: ShaderStage::from(entry_point_info.execution_model).into(),
+            next_stage: next_stages.into(),
+            code_type: ash::vk::ShaderCodeTypeEXT::SPIRV,
+            code_size: size_of_val(code),
+            p_code: code.as_ptr() as *const _,
+            p_name: name_vk.as_ptr(),
+            set_layout_count: set_layouts_vk.len() as u32,
+            p_set_layouts: set_layouts_vk.as_ptr(),
+            push_constant_range_count: push_constant_ranges_vk.len() as u32,
+            p_push_constant_ranges: push_constant_ranges_vk.as_ptr(),
+            p_specialization_info: if specialization_info_vk.data_size == 0 {
+                ptr::null()
+            } else {
+                &specialization_info_vk
+            },
+            ..Default::default()
+        };
+
+        let handle = {
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            (fns.ext_shader_object.create_shaders_ext)(
+                device.handle(),
+                1,
+                &create_info_vk,
+                ptr::null(),
+                output.as_mut_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+            output.assume_init()
+        };
+
+        Ok(Self::from_handle(device, handle, create_info))
+    }
+
+    /// Creates a new `ShaderObject` from a raw object handle.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid Vulkan object handle created from `device`.
+    /// - `create_info` must match the info used to create the object.
+    #[inline]
+    pub unsafe fn from_handle(
+        device: Arc<Device>,
+        handle: ash::vk::ShaderEXT,
+        create_info: ShaderCreateInfo,
+    ) -> Arc<ShaderObject> {
+        let ShaderCreateInfo {
+            flags,
+            entry_point,
+            next_stages,
+            layout,
+            _ne: _,
+        } = create_info;
+
+        Arc::new(ShaderObject {
+            handle,
+            device: InstanceOwnedDebugWrapper(device),
+            id: Self::next_id(),
+
+            flags,
+            entry_point,
+            next_stages,
+            layout,
+        })
+    }
+
+    /// Returns the flags that the shader object was created with.
+    #[inline]
+    pub fn flags(&self) -> ShaderCreateFlags {
+        self.flags
+    }
+
+    /// Returns the shader entry point that the shader object was created from.
+    #[inline]
+    pub fn entry_point(&self) -> &EntryPoint {
+        &self.entry_point
+    }
+
+    /// Returns the shader stage of the shader object.
+    #[inline]
+    pub fn stage(&self) -> ShaderStage {
+        ShaderStage::from(self.entry_point.info().execution_model)
+    }
+
+    /// Returns the stages that may follow this shader object.
+    #[inline]
+    pub fn next_stages(&self) -> ShaderStages {
+        self.next_stages
+    }
+
+    /// Returns the pipeline layout that the shader object was created with.
+    #[inline]
+    pub fn layout(&self) -> &Arc<PipelineLayout> {
+        &self.layout
+    }
+}
+
+impl Drop for ShaderObject {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let fns = self.device.fns();
+            (fns.ext_shader_object.destroy_shader_ext)(
+                self.device.handle(),
+                self.handle,
+                ptr::null(),
+            );
+        }
+    }
+}
+
+unsafe impl VulkanObject for ShaderObject {
+    type Handle = ash::vk::ShaderEXT;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.handle
+    }
+}
+
+unsafe impl DeviceOwned for ShaderObject {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+impl_id_counter!(ShaderObject);
+
+/// Parameters to create a new `ShaderObject`.
+#[derive(Clone, Debug)]
+pub struct ShaderCreateInfo {
+    /// Additional properties of the shader object.
+    ///
+    /// The default value is empty.
+    pub flags: ShaderCreateFlags,
+
+    /// The shader entry point that the shader object is created from, which includes any
+    /// specialization constants.
+    ///
+    /// There is no default value.
+    pub entry_point: EntryPoint,
+
+    /// The shader stages that may be bound after this one.
+    ///
+    /// This may only contain stages that can follow the stage of `entry_point` in a graphics
+    /// pipeline. For fragment and compute shaders, it must be empty.
+    ///
+    /// The default value is empty.
+    pub next_stages: ShaderStages,
+
+    /// The pipeline layout whose descriptor set layouts and push constant ranges are used by the
+    /// shader.
+    ///
+    /// The layout must be compatible with the resources that `entry_point` uses. When descriptor
+    /// sets are bound or push constants are set for the shader object, a pipeline layout
+    /// that is compatible with this one must be used.
+    ///
+    /// There is no default value.
+    pub layout: Arc<PipelineLayout>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl ShaderCreateInfo {
+    /// Returns a `ShaderCreateInfo` with the specified `entry_point` and `layout`.
+    #[inline]
+    pub fn new(entry_point: EntryPoint, layout: Arc<PipelineLayout>) -> Self {
+        Self {
+            flags: ShaderCreateFlags::empty(),
+            entry_point,
+            next_stages: ShaderStages::empty(),
+            layout,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
+    pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            flags,
+            ref entry_point,
+            next_stages,
+            ref layout,
+            _ne: _,
+        } = self;
+
+        flags.validate_device(device).map_err(|err| {
+            err.add_context("flags")
+                .set_vuids(&["VUID-VkShaderCreateInfoEXT-flags-parameter"])
+        })?;
+
+        next_stages.validate_device(device).map_err(|err| {
+            err.add_context("next_stages")
+                .set_vuids(&["VUID-VkShaderCreateInfoEXT-nextStage-parameter"])
+        })?;
+
+        // VUID-vkCreateShadersEXT-device-parameter
+        assert_eq!(device, layout.device().as_ref());
+
+        // The requirements on the shader itself are the same as for a pipeline shader stage.
+        PipelineShaderStageCreateInfo::new(entry_point.clone()).validate(device)?;
+
+        let entry_point_info = entry_point.info();
+        let stage = ShaderStage::from(entry_point_info.execution_model);
+
+        let allowed_next_stages = match stage {
+            ShaderStage::Vertex => {
+                ShaderStages::TESSELLATION_CONTROL | ShaderStages::GEOMETRY | ShaderStages::FRAGMENT
+            }
+            ShaderStage::TessellationControl => ShaderStages::TESSELLATION_EVALUATION,
+            ShaderStage::TessellationEvaluation => ShaderStages::GEOMETRY | ShaderStages::FRAGMENT,
+            ShaderStage::Geometry => ShaderStages::FRAGMENT,
+            ShaderStage::Fragment | ShaderStage::Compute => ShaderStages::empty(),
+            ShaderStage::Task => ShaderStages::MESH,
+            ShaderStage::Mesh => ShaderStages::FRAGMENT,
+            _ => {
+                return Err(Box::new(ValidationError {
+                    context: "entry_point".into(),
+                    problem: "is not a vertex, tessellation, geometry, fragment, compute, task or \
+                        mesh shader"
+                        .into(),
+                    vuids: &["VUID-VkShaderCreateInfoEXT-stage-parameter"],
+                    ..Default::default()
+                }));
+            }
+        };
+
+        if flags.intersects(ShaderCreateFlags::DISPATCH_BASE) && stage != ShaderStage::Compute {
+            return Err(Box::new(ValidationError {
+                problem: "`flags` contains `ShaderCreateFlags::DISPATCH_BASE`, but `entry_point` \
+                    is not a compute shader"
+                    .into(),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
+        if !allowed_next_stages.contains(next_stages) {
+            return Err(Box::new(ValidationError {
+                problem: "`next_stages` contains a stage that cannot follow the stage of \
+                    `entry_point`"
+                    .into(),
+                vuids: &[
+                    "VUID-VkShaderCreateInfoEXT-nextStage-08428",
+                    "VUID-VkShaderCreateInfoEXT-nextStage-08429",
+                    "VUID-VkShaderCreateInfoEXT-nextStage-08430",
+                    "VUID-VkShaderCreateInfoEXT-nextStage-08431",
+                    "VUID-VkShaderCreateInfoEXT-nextStage-08432",
+                    "VUID-VkShaderCreateInfoEXT-nextStage-08433",
+                    "VUID-VkShaderCreateInfoEXT-nextStage-08434",
+                ],
+                ..Default::default()
+            }));
+        }
+
+        layout
+            .ensure_compatible_with_shader(
+                entry_point_info
+                    .descriptor_binding_requirements
+                    .iter()
+                    .map(|(k, v)| (*k, v)),
+                entry_point_info.push_constant_requirements.as_ref(),
+            )
+            .map_err(|err| {
+                Box::new(ValidationError {
+                    context: "entry_point".into(),
+                    // vuids?
+                    ..ValidationError::from_error(err)
+                })
+            })?;
+
+        Ok(())
+    }
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags specifying additional properties of a shader object.
+    ShaderCreateFlags = ShaderCreateFlagsEXT(u32);
+
+    /* TODO: enable
+    // TODO: document
+    LINK_STAGE = LINK_STAGE, */
+
+    /* TODO: enable
+    // TODO: document
+    ALLOW_VARYING_SUBGROUP_SIZE = ALLOW_VARYING_SUBGROUP_SIZE, */
+
+    /* TODO: enable
+    // TODO: document
+    REQUIRE_FULL_SUBGROUPS = REQUIRE_FULL_SUBGROUPS, */
+
+    /* TODO: enable
+    // TODO: document
+    NO_TASK_SHADER = NO_TASK_SHADER, */
+
+    /// The shader object can be used with
+    /// [`dispatch_base`](crate::command_buffer::AutoCommandBufferBuilder::dispatch_base) with a
+    /// nonzero base workgroup. Only compute shaders can be created with this flag.
+    DISPATCH_BASE = DISPATCH_BASE,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ShaderCreateInfo, ShaderObject};
+    use crate::{
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator,
+            sys::{CommandBufferBeginInfo, UnsafeCommandBufferBuilder},
+            CommandBufferLevel,
+        },
+        pipeline::{
+            layout::PipelineDescriptorSetLayoutCreateInfo, PipelineLayout,
+            PipelineShaderStageCreateInfo,
+        },
+        shader::{ShaderModule, ShaderModuleCreateInfo, ShaderStage, ShaderStages},
+        Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError,
+    };
+
+    #[test]
+    fn create_info_validation() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+            layout(constant_id = 83) const int VALUE = 0xdeadbeef;
+
+            layout(set = 0, binding = 0) buffer Output {
+                int write;
+            } write;
+
+            void main() {
+                write.write = VALUE;
+            }
+            */
+            const MODULE: [u32; 120] = [
+                119734787, 65536, 524289, 14, 0, 131089, 1, 393227, 1, 1280527431, 1685353262,
+                808793134, 0, 196622, 0, 1, 327695, 5, 4, 1852399981, 0, 393232, 4, 17, 1, 1, 1,
+                196611, 2, 450, 262149, 4, 1852399981, 0, 262149, 7, 1886680399, 29813, 327686, 7,
+                0, 1953067639, 101, 262149, 9, 1953067639, 101, 262149, 11, 1431060822, 69, 327752,
+                7, 0, 35, 0, 196679, 7, 3, 262215, 9, 34, 0, 262215, 9, 33, 0, 262215, 11, 1, 83,
+                131091, 2, 196641, 3, 2, 262165, 6, 32, 1, 196638, 7, 6, 262176, 8, 2, 7, 262203,
+                8, 9, 2, 262187, 6, 10, 0, 262194, 6, 11, 3735928559, 262176, 12, 2, 6, 327734, 2,
+                4, 0, 3, 131320, 5, 327745, 12, 13, 9, 10, 196670, 13, 11, 65789, 65592,
+            ];
+            ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE))
+                .unwrap()
+                .entry_point("main")
+                .unwrap()
+        };
+        let layout = PipelineLayout::new(
+            device.clone(),
+            PipelineDescriptorSetLayoutCreateInfo::from_stages([
+                &PipelineShaderStageCreateInfo::new(cs.clone()),
+            ])
+            .into_pipeline_layout_create_info(device.clone())
+            .unwrap(),
+        )
+        .unwrap();
+
+        let create_info = ShaderCreateInfo::new(cs.clone(), layout.clone());
+        assert!(create_info.validate(&device).is_ok());
+
+        // The `shader_object` feature is not enabled.
+        match ShaderObject::new(device.clone(), create_info) {
+            Err(Validated::ValidationError(err))
+                if matches!(
+                    *err,
+                    ValidationError {
+                        requires_one_of: RequiresOneOf([RequiresAllOf([Requires::Feature(
+                            "shader_object"
+                        )])]),
+                        ..
+                    }
+                ) => {}
+            _ => panic!(),
+        }
+
+        // No stage can follow a compute shader.
+        let create_info = ShaderCreateInfo {
+            next_stages: ShaderStages::FRAGMENT,
+            ..ShaderCreateInfo::new(cs, layout)
+        };
+        assert!(create_info.validate(&device).is_err());
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = unsafe {
+            UnsafeCommandBufferBuilder::new(
+                &cb_allocator,
+                queue.queue_family_index(),
+                CommandBufferLevel::Primary,
+                CommandBufferBeginInfo::default(),
+            )
+            .unwrap()
+        };
+        assert!(unsafe { builder.bind_shaders(&[(ShaderStage::Compute, None)]) }.is_err());
+    }
+}
@@ -66,7 +66,12 @@ pub fn entry_points(spirv: &Spirv) -> impl Iterator<Item = (Id, EntryPointInfo)>
             spirv,
             interface,
             StorageClass::Output,
-            matches!(execution_model, ExecutionModel::TessellationControl),
+            matches!(
+                execution_model,
+                ExecutionModel::TessellationControl
+                    | ExecutionModel::MeshEXT
+                    | ExecutionModel::MeshNV
+            ),
         );
 
         Some((
@@ -881,21 +886,7 @@ fn push_constant_requirements(spirv: &Spirv, stage: ShaderStage) -> Option<PushC
 
 /// Extracts the `SpecializationConstant` map from `spirv`.
 pub(super) fn specialization_constants(spirv: &Spirv) -> HashMap<u32, SpecializationConstant> {
-    let get_constant_id = |result_id| {
-        spirv
-            .id(result_id)
-            .iter_decoration()
-            .find_map(|instruction| match *instruction {
-                Instruction::Decorate {
-                    decoration:
-                        Decoration::SpecId {
-                            specialization_constant_id,
-                        },
-                    ..
-                } => Some(specialization_constant_id),
-                _ => None,
-            })
-    };
+    let get_constant_id = |result_id| specialization_constant_id(spirv, result_id);
 
     spirv
         .iter_global()
@@ -964,6 +955,49 @@ pub(super) fn specialization_constants(spirv: &Spirv) -> HashMap<u32, Specializa
         .collect()
 }
 
+/// Returns a map from the names of the specialization constants in `spirv` to their
+/// `constant_id`. Constants without a name in the debug information are left out.
+pub(super) fn specialization_constant_names(spirv: &Spirv) -> HashMap<String, u32> {
+    spirv
+        .iter_global()
+        .filter_map(|instruction| match *instruction {
+            Instruction::SpecConstantFalse { result_id, .. }
+            | Instruction::SpecConstantTrue { result_id, .. }
+            | Instruction::SpecConstant { result_id, .. } => {
+                let constant_id = specialization_constant_id(spirv, result_id)?;
+                let name =
+                    spirv
+                        .id(result_id)
+                        .iter_name()
+                        .find_map(|instruction| match *instruction {
+                            Instruction::Name { ref name, .. } => Some(name.clone()),
+                            _ => None,
+                        })?;
+
+                Some((name, constant_id))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the `constant_id` of the specialization constant with the given result id.
+fn specialization_constant_id(spirv: &Spirv, result_id: Id) -> Option<u32> {
+    spirv
+        .id(result_id)
+        .iter_decoration()
+        .find_map(|instruction| match *instruction {
+            Instruction::Decorate {
+                decoration:
+                    Decoration::SpecId {
+                        specialization_constant_id,
+                    },
+                ..
+            } => Some(specialization_constant_id),
+            _ => None,
+        })
+}
+
 /// Extracts the `ShaderInterface` with the given storage class from `spirv`.
 fn shader_interface(
     spirv: &Spirv,
@@ -1333,3 +1367,216 @@ fn is_builtin(spirv: &Spirv, id: Id) -> bool {
         _ => false,
     }
 }
+
+/// The layout rules that the members of a block are laid out with.
+///
+/// The variants are ordered from least to most strict; a block that satisfies the rules of one
+/// layout also satisfies the rules of all the layouts before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum BlockLayout {
+    /// The scalar alignment rules, which require the `scalar_block_layout` feature.
+    Scalar,
+    /// The base alignment rules, also known as std430.
+    Base,
+    /// The extended alignment rules, also known as std140.
+    Extended,
+}
+
+/// Returns the strictest layout whose offset and stride rules are satisfied by the members of the
+/// struct type `id`, or `None` if the members don't even satisfy the scalar alignment rules.
+///
+/// If `relaxed` is true, vectors in the base and extended layouts only need to be aligned to
+/// their component type, as long as they don't improperly straddle a 16-byte boundary.
+pub(crate) fn block_layout(spirv: &Spirv, id: Id, relaxed: bool) -> Option<BlockLayout> {
+    [
+        BlockLayout::Extended,
+        BlockLayout::Base,
+        BlockLayout::Scalar,
+    ]
+    .into_iter()
+    .find(|&layout| struct_follows_layout(spirv, id, layout, relaxed))
+}
+
+fn struct_follows_layout(spirv: &Spirv, id: Id, layout: BlockLayout, relaxed: bool) -> bool {
+    let id_info = spirv.id(id);
+    let member_types = match id_info.instruction() {
+        Instruction::TypeStruct { member_types, .. } => member_types,
+        _ => return true,
+    };
+
+    member_types
+        .iter()
+        .zip(id_info.iter_members())
+        .all(|(&member_type, member_info)| {
+            let mut offset = None;
+            let mut row_major = false;
+
+            for instruction in member_info.iter_decoration() {
+                match *instruction {
+                    Instruction::MemberDecorate {
+                        decoration: Decoration::Offset { byte_offset },
+                        ..
+                    } => offset = Some(byte_offset as DeviceSize),
+                    Instruction::MemberDecorate {
+                        decoration: Decoration::RowMajor,
+                        ..
+                    } => row_major = true,
+                    _ => (),
+                }
+            }
+
+            // Members without an offset, such as built-ins, have no layout to check.
+            let offset = match offset {
+                Some(offset) => offset,
+                None => return true,
+            };
+
+            let offset_follows_layout = match *spirv.id(member_type).instruction() {
+                Instruction::TypeVector {
+                    component_type,
+                    component_count,
+                    ..
+                } if relaxed && layout != BlockLayout::Scalar => {
+                    let component_alignment =
+                        type_alignment(spirv, component_type, BlockLayout::Scalar, false);
+                    let size = component_alignment * component_count as DeviceSize;
+                    let improperly_straddles = if size <= 16 {
+                        offset / 16 != (offset + size - 1) / 16
+                    } else {
+                        offset % 16 != 0
+                    };
+
+                    offset % component_alignment == 0 && !improperly_straddles
+                }
+                _ => offset % type_alignment(spirv, member_type, layout, row_major) == 0,
+            };
+
+            offset_follows_layout
+                && type_follows_layout(spirv, member_type, layout, relaxed, row_major)
+        })
+}
+
+/// Checks the strides of `id` and of all the types it contains.
+fn type_follows_layout(
+    spirv: &Spirv,
+    id: Id,
+    layout: BlockLayout,
+    relaxed: bool,
+    row_major: bool,
+) -> bool {
+    let id_info = spirv.id(id);
+
+    match *id_info.instruction() {
+        Instruction::TypeMatrix { .. } => {
+            let alignment = type_alignment(spirv, id, layout, row_major);
+
+            id_info
+                .iter_decoration()
+                .all(|instruction| match *instruction {
+                    Instruction::Decorate {
+                        decoration: Decoration::MatrixStride { matrix_stride },
+                        ..
+                    } => (matrix_stride as DeviceSize).is_multiple_of(alignment),
+                    _ => true,
+                })
+        }
+        Instruction::TypeArray { element_type, .. }
+        | Instruction::TypeRuntimeArray { element_type, .. } => {
+            let alignment = type_alignment(spirv, id, layout, row_major);
+
+            id_info
+                .iter_decoration()
+                .all(|instruction| match *instruction {
+                    Instruction::Decorate {
+                        decoration: Decoration::ArrayStride { array_stride },
+                        ..
+                    } => (array_stride as DeviceSize).is_multiple_of(alignment),
+                    _ => true,
+                })
+                && type_follows_layout(spirv, element_type, layout, relaxed, row_major)
+        }
+        Instruction::TypeStruct { .. } => struct_follows_layout(spirv, id, layout, relaxed),
+        _ => true,
+    }
+}
+
+/// Returns the alignment of the type `id` under the rules of `layout`.
+fn type_alignment(spirv: &Spirv, id: Id, layout: BlockLayout, row_major: bool) -> DeviceSize {
+    match *spirv.id(id).instruction() {
+        Instruction::TypeInt { width, .. } | Instruction::TypeFloat { width, .. } => {
+            width as DeviceSize / 8
+        }
+        Instruction::TypePointer { .. } => 8,
+        Instruction::TypeVector {
+            component_type,
+            component_count,
+            ..
+        } => {
+            let component_alignment = type_alignment(spirv, component_type, layout, false);
+
+            match (layout, component_count) {
+                (BlockLayout::Scalar, _) => component_alignment,
+                (_, 2) => component_alignment * 2,
+                _ => component_alignment * 4,
+            }
+        }
+        Instruction::TypeMatrix {
+            column_type,
+            column_count,
+            ..
+        } => {
+            if row_major && layout != BlockLayout::Scalar {
+                // A row-major matrix is aligned like a vector with one component per column.
+                let component_type = match *spirv.id(column_type).instruction() {
+                    Instruction::TypeVector { component_type, .. } => component_type,
+                    _ => unreachable!(),
+                };
+                let component_alignment = type_alignment(spirv, component_type, layout, false);
+
+                match column_count {
+                    2 => component_alignment * 2,
+                    _ => component_alignment * 4,
+                }
+            } else {
+                type_alignment(spirv, column_type, layout, false)
+            }
+        }
+        Instruction::TypeArray { element_type, .. }
+        | Instruction::TypeRuntimeArray { element_type, .. } => {
+            let alignment = type_alignment(spirv, element_type, layout, row_major);
+
+            match layout {
+                BlockLayout::Extended => alignment.max(16),
+                _ => alignment,
+            }
+        }
+        Instruction::TypeStruct {
+            ref member_types, ..
+        } => {
+            let alignment = member_types
+                .iter()
+                .zip(spirv.id(id).iter_members())
+                .map(|(&member_type, member_info)| {
+                    let row_major = member_info.iter_decoration().any(|instruction| {
+                        matches!(
+                            instruction,
+                            Instruction::MemberDecorate {
+                                decoration: Decoration::RowMajor,
+                                ..
+                            }
+                        )
+                    });
+
+                    type_alignment(spirv, member_type, layout, row_major)
+                })
+                .max()
+                .unwrap_or(1);
+
+            match layout {
+                BlockLayout::Extended => alignment.max(16),
+                _ => alignment,
+            }
+        }
+        _ => 1,
+    }
+}
@@ -12,6 +12,7 @@ use crate::{
     buffer::Buffer,
     device::{Device, DeviceOwned, Queue},
     image::{Image, ImageLayout},
+    macros::log_event,
     sync::{
         fence::Fence,
         future::{AccessCheckError, AccessError, GpuFuture, SubmitAnyBuilder},
@@ -131,9 +132,24 @@ pub unsafe fn acquire_next_image_raw(
 
     let suboptimal = match result {
         ash::vk::Result::SUCCESS => false,
-        ash::vk::Result::SUBOPTIMAL_KHR => true,
+        ash::vk::Result::SUBOPTIMAL_KHR => {
+            log_event!(
+                debug,
+                "swapchain",
+                "acquired an image from a suboptimal swapchain"
+            );
+            true
+        }
         ash::vk::Result::NOT_READY => return Err(VulkanError::NotReady.into()),
         ash::vk::Result::TIMEOUT => return Err(VulkanError::Timeout.into()),
+        ash::vk::Result::ERROR_OUT_OF_DATE_KHR => {
+            log_event!(
+                debug,
+                "swapchain",
+                "failed to acquire an image: the swapchain is out of date"
+            );
+            return Err(VulkanError::OutOfDate.into());
+        }
         err => return Err(VulkanError::from(err).into()),
     };
 
@@ -330,7 +330,7 @@ use crate::{
     format::Format,
     image::{Image, ImageCreateFlags, ImageFormatInfo, ImageTiling, ImageType, ImageUsage},
     instance::InstanceOwnedDebugWrapper,
-    macros::{impl_id_counter, vulkan_bitflags, vulkan_bitflags_enum, vulkan_enum},
+    macros::{impl_id_counter, log_event, vulkan_bitflags, vulkan_bitflags_enum, vulkan_enum},
     sync::Sharing,
     Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version, VulkanError,
     VulkanObject,
@@ -374,6 +374,7 @@ pub struct Swapchain {
     present_gravity: Option<[PresentGravity; 2]>,
     full_screen_exclusive: FullScreenExclusive,
     win32_monitor: Option<Win32Monitor>,
+    surface_counters: SurfaceCounters,
 
     prev_present_id: AtomicU64,
 
@@ -429,6 +430,15 @@ impl Swapchain {
         surface: Arc<Surface>,
         create_info: SwapchainCreateInfo,
     ) -> Result<(Arc<Swapchain>, Vec<Arc<Image>>), VulkanError> {
+        log_event!(
+            debug,
+            "swapchain",
+            "creating a swapchain with extent {:?}, format {:?} and present mode {:?}",
+            create_info.image_extent,
+            create_info.image_format,
+            create_info.present_mode,
+        );
+
         let (handle, image_handles) =
             Self::new_inner_unchecked(&device, &surface, &create_info, None)?;
 
@@ -490,6 +500,15 @@ impl Swapchain {
         // even if the call to `vkCreateSwapchainKHR` below fails.
         *self.is_retired.lock() = true;
 
+        log_event!(
+            debug,
+            "swapchain",
+            "recreating a swapchain with extent {:?}, format {:?} and present mode {:?}",
+            create_info.image_extent,
+            create_info.image_format,
+            create_info.present_mode,
+        );
+
         let (handle, image_handles) = unsafe {
             Self::new_inner_unchecked(&self.device, &self.surface, &create_info, Some(self))?
         };
@@ -549,6 +568,7 @@ impl Swapchain {
             present_gravity,
             full_screen_exclusive,
             win32_monitor,
+            surface_counters,
             _ne: _,
         } = create_info;
 
@@ -960,6 +980,19 @@ impl Swapchain {
             */
         }
 
+        if !surface_capabilities
+            .supported_surface_counters
+            .contains(surface_counters)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`create_info.surface_counters` contains flags that are not set in \
+                    the `supported_surface_counters` value of the capabilities of `surface`"
+                    .into(),
+                vuids: &["VUID-VkSwapchainCounterCreateInfoEXT-surfaceCounters-01244"],
+                ..Default::default()
+            }));
+        }
+
         if surface.api() == SurfaceApi::Win32
             && full_screen_exclusive == FullScreenExclusive::ApplicationControlled
         {
@@ -1015,6 +1048,7 @@ impl Swapchain {
             present_gravity,
             full_screen_exclusive,
             win32_monitor,
+            surface_counters,
             _ne: _,
         } = create_info;
 
@@ -1057,6 +1091,7 @@ impl Swapchain {
         let mut present_modes_info_vk = None;
         let present_modes_vk: SmallVec<[ash::vk::PresentModeKHR; PresentMode::COUNT]>;
         let mut present_scaling_info_vk = None;
+        let mut counter_info_vk = None;
 
         if !image_view_formats.is_empty() {
             format_list_view_formats_vk = image_view_formats
@@ -1126,6 +1161,16 @@ impl Swapchain {
             create_info_vk.p_next = next as *const _ as *const _;
         }
 
+        if !surface_counters.is_empty() {
+            let next = counter_info_vk.insert(ash::vk::SwapchainCounterCreateInfoEXT {
+                surface_counters: surface_counters.into(),
+                ..Default::default()
+            });
+
+            next.p_next = create_info_vk.p_next as *mut _;
+            create_info_vk.p_next = next as *const _ as *const _;
+        }
+
         let fns = device.fns();
 
         let handle = {
@@ -1208,6 +1253,7 @@ impl Swapchain {
             present_gravity,
             full_screen_exclusive,
             win32_monitor,
+            surface_counters,
             _ne: _,
         } = create_info;
 
@@ -1235,6 +1281,7 @@ impl Swapchain {
             present_gravity,
             full_screen_exclusive,
             win32_monitor,
+            surface_counters,
 
             prev_present_id: Default::default(),
             full_screen_exclusive_held: AtomicBool::new(false),
@@ -1286,6 +1333,7 @@ impl Swapchain {
             present_gravity: self.present_gravity,
             full_screen_exclusive: self.full_screen_exclusive,
             win32_monitor: self.win32_monitor,
+            surface_counters: self.surface_counters,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -1537,6 +1585,64 @@ impl Swapchain {
         }
     }
 
+    /// Returns the surface counters that were enabled when creating the swapchain.
+    #[inline]
+    pub fn surface_counters(&self) -> SurfaceCounters {
+        self.surface_counters
+    }
+
+    /// Returns the current value of a surface counter of the swapchain.
+    ///
+    /// The counter must have been enabled in
+    /// [`SwapchainCreateInfo::surface_counters`] when creating the swapchain, and at least one
+    /// present operation on the swapchain must have been processed by the presentation engine.
+    #[inline]
+    pub fn counter_value(&self, counter: SurfaceCounter) -> Result<u64, Validated<VulkanError>> {
+        self.validate_counter_value(counter)?;
+
+        unsafe { Ok(self.counter_value_unchecked(counter)?) }
+    }
+
+    fn validate_counter_value(&self, counter: SurfaceCounter) -> Result<(), Box<ValidationError>> {
+        counter.validate_device(&self.device).map_err(|err| {
+            err.add_context("counter")
+                .set_vuids(&["VUID-vkGetSwapchainCounterEXT-counter-parameter"])
+        })?;
+
+        if !self.surface_counters.contains_enum(counter) {
+            return Err(Box::new(ValidationError {
+                problem: "`self.surface_counters()` does not contain `counter`".into(),
+                vuids: &["VUID-vkGetSwapchainCounterEXT-counter-01246"],
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkGetSwapchainCounterEXT-swapchain-01245
+        // Not checked, the presentation engine processes presents asynchronously.
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn counter_value_unchecked(
+        &self,
+        counter: SurfaceCounter,
+    ) -> Result<u64, VulkanError> {
+        let fns = self.device.fns();
+        let mut output = MaybeUninit::uninit();
+        (fns.ext_display_control.get_swapchain_counter_ext)(
+            self.device.handle(),
+            self.handle,
+            counter.into(),
+            output.as_mut_ptr(),
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        Ok(output.assume_init())
+    }
+
     // This method is necessary to allow `SwapchainImage`s to signal when they have been
     // transitioned out of their initial `undefined` image layout.
     //
@@ -1740,6 +1846,18 @@ pub struct SwapchainCreateInfo {
     /// The default value is `None`.
     pub win32_monitor: Option<Win32Monitor>,
 
+    /// The surface counters to enable for the swapchain, which can then be queried with
+    /// [`Swapchain::counter_value`].
+    ///
+    /// If not empty, then the
+    /// [`ext_display_control`](crate::device::DeviceExtensions::ext_display_control)
+    /// extension must be enabled on the device, and the counters must be present in the
+    /// [`supported_surface_counters`](SurfaceCapabilities::supported_surface_counters) value of
+    /// the capabilities of the surface.
+    ///
+    /// The default value is empty.
+    pub surface_counters: SurfaceCounters,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -1765,6 +1883,7 @@ impl Default for SwapchainCreateInfo {
             present_gravity: None,
             full_screen_exclusive: FullScreenExclusive::Default,
             win32_monitor: None,
+            surface_counters: SurfaceCounters::empty(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -1791,6 +1910,7 @@ impl SwapchainCreateInfo {
             present_gravity,
             full_screen_exclusive,
             win32_monitor: _,
+            surface_counters,
             _ne: _,
         } = self;
 
@@ -2096,6 +2216,24 @@ impl SwapchainCreateInfo {
                 })?;
         }
 
+        if !surface_counters.is_empty() {
+            if !device.enabled_extensions().ext_display_control {
+                return Err(Box::new(ValidationError {
+                    context: "surface_counters".into(),
+                    problem: "is not empty".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                        "ext_display_control",
+                    )])]),
+                    ..Default::default()
+                }));
+            }
+
+            surface_counters.validate_device(device).map_err(|err| {
+                err.add_context("surface_counters")
+                    .set_vuids(&["VUID-VkSwapchainCounterCreateInfoEXT-surfaceCounters-parameter"])
+            })?;
+        }
+
         Ok(())
     }
 }
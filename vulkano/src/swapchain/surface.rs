@@ -12,10 +12,11 @@
                       // so that it'll be compatible w/egui
 
 use super::{FullScreenExclusive, PresentGravityFlags, PresentScalingFlags, Win32Monitor};
+#[cfg(feature = "display")]
+use crate::display::{DisplayMode, DisplayPlaneAlpha};
 use crate::{
     cache::OnceCache,
     device::physical::PhysicalDevice,
-    display::{DisplayMode, DisplayPlaneAlpha},
     format::Format,
     image::ImageUsage,
     instance::{Instance, InstanceExtensions, InstanceOwned},
@@ -23,7 +24,6 @@ use crate::{
     DebugWrapper, Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, VulkanError,
     VulkanObject,
 };
-
 #[cfg(any(target_os = "macos", target_os = "ios"))]
 use objc::{class, msg_send, runtime::Object, sel, sel_impl};
 use raw_window_handle::{
@@ -251,6 +251,7 @@ impl Surface {
     }
 
     /// Creates a `Surface` from a `DisplayMode` and display plane.
+    #[cfg(feature = "display")]
     #[inline]
     pub fn from_display_plane(
         display_mode: Arc<DisplayMode>,
@@ -266,6 +267,7 @@ impl Surface {
         }
     }
 
+    #[cfg(feature = "display")]
     fn validate_from_display_plane(
         display_mode: &DisplayMode,
         create_info: &DisplaySurfaceCreateInfo,
@@ -378,6 +380,7 @@ impl Surface {
         Ok(())
     }
 
+    #[cfg(feature = "display")]
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
     pub unsafe fn from_display_plane_unchecked(
         display_mode: Arc<DisplayMode>,
@@ -1569,6 +1572,7 @@ unsafe fn get_metal_layer_macos(ns_view: *mut std::ffi::c_void) -> *mut Object {
 }
 
 /// Parameters to create a surface from a display mode and plane.
+#[cfg(feature = "display")]
 #[derive(Clone, Debug)]
 pub struct DisplaySurfaceCreateInfo {
     /// The index of the display plane in which the surface will appear.
@@ -1605,6 +1609,7 @@ pub struct DisplaySurfaceCreateInfo {
     pub _ne: crate::NonExhaustive,
 }
 
+#[cfg(feature = "display")]
 impl Default for DisplaySurfaceCreateInfo {
     #[inline]
     fn default() -> Self {
@@ -1620,6 +1625,7 @@ impl Default for DisplaySurfaceCreateInfo {
     }
 }
 
+#[cfg(feature = "display")]
 impl DisplaySurfaceCreateInfo {
     pub(crate) fn validate(
         &self,
@@ -1960,6 +1966,23 @@ vulkan_bitflags_enum! {
     INHERIT, Inherit = INHERIT,
 }
 
+vulkan_bitflags_enum! {
+    #[non_exhaustive]
+
+    /// A set of [`SurfaceCounter`] values.
+    SurfaceCounters,
+
+    /// A counter that the presentation engine keeps for a surface, and that can be queried for a
+    /// swapchain.
+    SurfaceCounter,
+
+    = SurfaceCounterFlagsEXT(u32);
+
+    /// The number of vertical blanking periods that have occurred on the display that the surface
+    /// is presented to.
+    VBLANK, Vblank = VBLANK,
+}
+
 vulkan_enum! {
     #[non_exhaustive]
 
@@ -2359,6 +2382,13 @@ pub struct SurfaceCapabilities {
 
     /// Whether full-screen exclusivity is supported.
     pub full_screen_exclusive_supported: bool,
+
+    /// The counters that can be enabled for a swapchain created from the surface.
+    ///
+    /// This is always empty unless the
+    /// [`ext_display_surface_counter`](crate::instance::InstanceExtensions::ext_display_surface_counter)
+    /// extension is enabled on the instance.
+    pub supported_surface_counters: SurfaceCounters,
 }
 
 #[cfg(test)]
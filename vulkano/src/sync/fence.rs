@@ -10,6 +10,8 @@
 //! A fence provides synchronization between the device and the host, or between an external source
 //! and the host.
 
+#[cfg(feature = "display")]
+use crate::display::{DeviceEventType, Display, DisplayEventType};
 use crate::{
     device::{physical::PhysicalDevice, Device, DeviceOwned, Queue},
     instance::InstanceOwnedDebugWrapper,
@@ -191,6 +193,172 @@ impl Fence {
         Ok(fence)
     }
 
+    /// Creates a new `Fence` that will be signaled when `event_type` occurs on `device`.
+    ///
+    /// The [`ext_display_control`](crate::device::DeviceExtensions::ext_display_control)
+    /// extension must be enabled on the device.
+    #[cfg(feature = "display")]
+    #[inline]
+    pub fn from_device_event(
+        device: Arc<Device>,
+        event_type: DeviceEventType,
+    ) -> Result<Fence, Validated<VulkanError>> {
+        Self::validate_from_device_event(&device, event_type)?;
+
+        unsafe { Ok(Self::from_device_event_unchecked(device, event_type)?) }
+    }
+
+    #[cfg(feature = "display")]
+    fn validate_from_device_event(
+        device: &Device,
+        event_type: DeviceEventType,
+    ) -> Result<(), Box<ValidationError>> {
+        if !device.enabled_extensions().ext_display_control {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "ext_display_control",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        event_type.validate_device(device).map_err(|err| {
+            err.add_context("event_type")
+                .set_vuids(&["VUID-VkDeviceEventInfoEXT-deviceEvent-parameter"])
+        })?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "display")]
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn from_device_event_unchecked(
+        device: Arc<Device>,
+        event_type: DeviceEventType,
+    ) -> Result<Fence, VulkanError> {
+        let info_vk = ash::vk::DeviceEventInfoEXT {
+            device_event: event_type.into(),
+            ..Default::default()
+        };
+
+        let handle = {
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            (fns.ext_display_control.register_device_event_ext)(
+                device.handle(),
+                &info_vk,
+                ptr::null(),
+                output.as_mut_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+
+            output.assume_init()
+        };
+
+        Ok(Self::from_event_handle(device, handle))
+    }
+
+    /// Creates a new `Fence` that will be signaled when `event_type` occurs on `display`.
+    ///
+    /// The [`ext_display_control`](crate::device::DeviceExtensions::ext_display_control)
+    /// extension must be enabled on the device.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `display` does not belong to the physical device of `device`.
+    #[cfg(feature = "display")]
+    #[inline]
+    pub fn from_display_event(
+        device: Arc<Device>,
+        display: &Display,
+        event_type: DisplayEventType,
+    ) -> Result<Fence, Validated<VulkanError>> {
+        Self::validate_from_display_event(&device, display, event_type)?;
+
+        unsafe {
+            Ok(Self::from_display_event_unchecked(
+                device, display, event_type,
+            )?)
+        }
+    }
+
+    #[cfg(feature = "display")]
+    fn validate_from_display_event(
+        device: &Device,
+        display: &Display,
+        event_type: DisplayEventType,
+    ) -> Result<(), Box<ValidationError>> {
+        if !device.enabled_extensions().ext_display_control {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "ext_display_control",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkRegisterDisplayEventEXT-commonparent
+        assert_eq!(device.physical_device(), display.physical_device());
+
+        event_type.validate_device(device).map_err(|err| {
+            err.add_context("event_type")
+                .set_vuids(&["VUID-VkDisplayEventInfoEXT-displayEvent-parameter"])
+        })?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "display")]
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn from_display_event_unchecked(
+        device: Arc<Device>,
+        display: &Display,
+        event_type: DisplayEventType,
+    ) -> Result<Fence, VulkanError> {
+        let info_vk = ash::vk::DisplayEventInfoEXT {
+            display_event: event_type.into(),
+            ..Default::default()
+        };
+
+        let handle = {
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            (fns.ext_display_control.register_display_event_ext)(
+                device.handle(),
+                display.handle(),
+                &info_vk,
+                ptr::null(),
+                output.as_mut_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+
+            output.assume_init()
+        };
+
+        Ok(Self::from_event_handle(device, handle))
+    }
+
+    #[cfg(feature = "display")]
+    unsafe fn from_event_handle(device: Arc<Device>, handle: ash::vk::Fence) -> Fence {
+        Fence {
+            handle,
+            device: InstanceOwnedDebugWrapper(device),
+            id: Self::next_id(),
+
+            flags: FenceCreateFlags::empty(),
+            export_handle_types: ExternalFenceHandleTypes::empty(),
+
+            must_put_in_pool: false,
+            // The fence is signaled by the implementation, so its status must always be queried.
+            state: Mutex::new(FenceState {
+                current_import: Some(ImportType::Event),
+                ..Default::default()
+            }),
+        }
+    }
+
     /// Creates a new `Fence` from a raw object handle.
     ///
     /// # Safety
@@ -619,6 +787,7 @@ impl Fence {
 
         if let Some(imported_handle_type) = state.current_import {
             match imported_handle_type {
+                #[cfg(feature = "swapchain")]
                 ImportType::SwapchainAcquire => {
                     return Err(Box::new(ValidationError {
                         problem: "the fence currently has an imported payload from a \
@@ -628,6 +797,10 @@ impl Fence {
                         ..Default::default()
                     }));
                 }
+                // Fences created from events can't have export handle types, so this was
+                // already checked above.
+                #[cfg(feature = "display")]
+                ImportType::Event => (),
                 ImportType::ExternalFence(imported_handle_type) => {
                     let external_fence_properties = unsafe {
                         self.device
@@ -786,6 +959,7 @@ impl Fence {
 
         if let Some(imported_handle_type) = state.current_import {
             match imported_handle_type {
+                #[cfg(feature = "swapchain")]
                 ImportType::SwapchainAcquire => {
                     return Err(Box::new(ValidationError {
                         problem: "the fence currently has an imported payload from a \
@@ -795,6 +969,10 @@ impl Fence {
                         ..Default::default()
                     }));
                 }
+                // Fences created from events can't have export handle types, so this was
+                // already checked above.
+                #[cfg(feature = "display")]
+                ImportType::Event => (),
                 ImportType::ExternalFence(imported_handle_type) => {
                     let external_fence_properties = unsafe {
                         self.device
@@ -1625,6 +1803,7 @@ impl FenceState {
         }
     }
 
+    #[cfg(feature = "swapchain")]
     #[inline]
     pub(crate) unsafe fn import_swapchain_acquire(&mut self) {
         debug_assert!(!self.is_in_queue());
@@ -1634,7 +1813,10 @@ impl FenceState {
 
 #[derive(Clone, Copy, Debug)]
 enum ImportType {
+    #[cfg(feature = "swapchain")]
     SwapchainAcquire,
+    #[cfg(feature = "display")]
+    Event,
     ExternalFence(ExternalFenceHandleType),
 }
 
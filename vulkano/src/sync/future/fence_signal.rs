@@ -10,17 +10,14 @@
 use super::{AccessCheckError, GpuFuture};
 use crate::{
     buffer::Buffer,
-    command_buffer::{SemaphoreSubmitInfo, SubmitInfo},
+    command_buffer::SemaphoreSubmitInfo,
     device::{Device, DeviceOwned, Queue, QueueFlags},
     image::{Image, ImageLayout},
-    swapchain::Swapchain,
-    sync::{
-        fence::Fence,
-        future::{AccessError, SubmitAnyBuilder},
-        PipelineStages,
-    },
-    DeviceSize, Validated, ValidationError, VulkanError,
+    sync::{fence::Fence, future::SubmitAnyBuilder, PipelineStages},
+    DeviceSize, Validated, VulkanError,
 };
+#[cfg(feature = "swapchain")]
+use crate::{swapchain::Swapchain, sync::future::AccessError, ValidationError};
 use parking_lot::{Mutex, MutexGuard};
 use std::{
     future::Future,
@@ -236,6 +233,7 @@ where
             // in which case `result` will contain `Err(OutcomeErr::Partial)`, or total failure
             // in which case `result` will contain `Err(OutcomeErr::Full)`.
             enum OutcomeErr<E> {
+                #[cfg_attr(not(feature = "swapchain"), allow(dead_code))]
                 Partial(E),
                 Full(E),
             }
@@ -254,22 +252,18 @@ where
 
                     queue
                         .with(|mut q| {
-                            q.submit_unchecked(
-                                [SubmitInfo {
-                                    wait_semaphores: semaphores
-                                        .into_iter()
-                                        .map(|semaphore| {
-                                            SemaphoreSubmitInfo {
-                                                // TODO: correct stages ; hard
-                                                stages: PipelineStages::ALL_COMMANDS,
-                                                ..SemaphoreSubmitInfo::semaphore(semaphore)
-                                            }
-                                        })
-                                        .collect(),
-                                    ..Default::default()
-                                }],
-                                None,
-                            )
+                            let mut submit_info = q.recycled_submit_info();
+                            submit_info
+                                .wait_semaphores
+                                .extend(semaphores.into_iter().map(|semaphore| {
+                                    SemaphoreSubmitInfo {
+                                        // TODO: correct stages ; hard
+                                        stages: PipelineStages::ALL_COMMANDS,
+                                        ..SemaphoreSubmitInfo::semaphore(semaphore)
+                                    }
+                                }));
+
+                            q.submit_unchecked([submit_info], None)
                         })
                         .map_err(|err| OutcomeErr::Full(err.into()))
                 }
@@ -306,6 +300,7 @@ where
                         .with(|mut q| q.bind_sparse_unchecked(bind_infos, Some(new_fence.clone())))
                         .map_err(|err| OutcomeErr::Full(err.into()))
                 }
+                #[cfg(feature = "swapchain")]
                 SubmitAnyBuilder::QueuePresent(present_info) => {
                     if partially_flushed {
                         queue
@@ -510,6 +505,7 @@ where
         }
     }
 
+    #[cfg(feature = "swapchain")]
     #[inline]
     fn check_swapchain_image_acquired(
         &self,
@@ -623,6 +619,7 @@ where
         (**self).check_image_access(image, range, exclusive, expected_layout, queue)
     }
 
+    #[cfg(feature = "swapchain")]
     #[inline]
     fn check_swapchain_image_acquired(
         &self,
@@ -8,11 +8,12 @@
 // according to those terms.
 
 use super::{AccessCheckError, GpuFuture, SubmitAnyBuilder};
+#[cfg(feature = "swapchain")]
+use crate::swapchain::Swapchain;
 use crate::{
     buffer::Buffer,
     device::{Device, DeviceOwned, Queue},
     image::{Image, ImageLayout},
-    swapchain::Swapchain,
     DeviceSize, Validated, VulkanError, VulkanObject,
 };
 use std::{ops::Range, sync::Arc};
@@ -93,10 +94,12 @@ where
                 self.first.flush()?;
                 SubmitAnyBuilder::SemaphoresWait(b)
             }
+            #[cfg(feature = "swapchain")]
             (SubmitAnyBuilder::SemaphoresWait(a), SubmitAnyBuilder::QueuePresent(_)) => {
                 self.second.flush()?;
                 SubmitAnyBuilder::SemaphoresWait(a)
             }
+            #[cfg(feature = "swapchain")]
             (SubmitAnyBuilder::QueuePresent(_), SubmitAnyBuilder::SemaphoresWait(b)) => {
                 self.first.flush()?;
                 SubmitAnyBuilder::SemaphoresWait(b)
@@ -130,20 +133,25 @@ where
 
                 SubmitAnyBuilder::CommandBuffer(submit_info_a, fence_a.or(fence_b))
             }
+            #[cfg(feature = "swapchain")]
             (SubmitAnyBuilder::QueuePresent(_), SubmitAnyBuilder::QueuePresent(_)) => {
                 self.first.flush()?;
                 self.second.flush()?;
                 SubmitAnyBuilder::Empty
             }
+            #[cfg(feature = "swapchain")]
             (SubmitAnyBuilder::CommandBuffer(_, _), SubmitAnyBuilder::QueuePresent(_)) => {
                 unimplemented!()
             }
+            #[cfg(feature = "swapchain")]
             (SubmitAnyBuilder::QueuePresent(_), SubmitAnyBuilder::CommandBuffer(_, _)) => {
                 unimplemented!()
             }
+            #[cfg(feature = "swapchain")]
             (SubmitAnyBuilder::BindSparse(_, _), SubmitAnyBuilder::QueuePresent(_)) => {
                 unimplemented!()
             }
+            #[cfg(feature = "swapchain")]
             (SubmitAnyBuilder::QueuePresent(_), SubmitAnyBuilder::BindSparse(_, _)) => {
                 unimplemented!()
             }
@@ -261,6 +269,7 @@ where
         }
     }
 
+    #[cfg(feature = "swapchain")]
     #[inline]
     fn check_swapchain_image_acquired(
         &self,
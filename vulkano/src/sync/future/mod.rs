@@ -97,6 +97,8 @@ pub use self::{
     semaphore_signal::SemaphoreSignalFuture,
 };
 use super::{fence::Fence, semaphore::Semaphore};
+#[cfg(feature = "swapchain")]
+use crate::swapchain::{self, PresentFuture, PresentInfo, Swapchain, SwapchainPresentInfo};
 use crate::{
     buffer::Buffer,
     command_buffer::{
@@ -105,7 +107,6 @@ use crate::{
     device::{DeviceOwned, Queue},
     image::{Image, ImageLayout},
     memory::BindSparseInfo,
-    swapchain::{self, PresentFuture, PresentInfo, Swapchain, SwapchainPresentInfo},
     DeviceSize, Validated, VulkanError,
 };
 use smallvec::SmallVec;
@@ -223,6 +224,7 @@ pub unsafe trait GpuFuture: DeviceOwned {
     ///
     /// > **Note**: Setting `before` to `true` should skip checking the current future and always
     /// > forward the call to the future before.
+    #[cfg(feature = "swapchain")]
     fn check_swapchain_image_acquired(
         &self,
         swapchain: &Swapchain,
@@ -340,6 +342,7 @@ pub unsafe trait GpuFuture: DeviceOwned {
     /// otherwise an error will occur when flushing.
     ///
     /// > **Note**: This is just a shortcut for the `Swapchain::present()` function.
+    #[cfg(feature = "swapchain")]
     #[inline]
     fn then_swapchain_present(
         self,
@@ -447,6 +450,7 @@ where
         (**self).check_image_access(image, range, exclusive, expected_layout, queue)
     }
 
+    #[cfg(feature = "swapchain")]
     #[inline]
     fn check_swapchain_image_acquired(
         &self,
@@ -464,6 +468,7 @@ pub enum SubmitAnyBuilder {
     Empty,
     SemaphoresWait(SmallVec<[Arc<Semaphore>; 8]>),
     CommandBuffer(SubmitInfo, Option<Arc<Fence>>),
+    #[cfg(feature = "swapchain")]
     QueuePresent(PresentInfo),
     BindSparse(SmallVec<[BindSparseInfo; 1]>, Option<Arc<Fence>>),
 }
@@ -8,11 +8,12 @@
 // according to those terms.
 
 use super::{AccessCheckError, GpuFuture, SubmitAnyBuilder};
+#[cfg(feature = "swapchain")]
+use crate::swapchain::Swapchain;
 use crate::{
     buffer::Buffer,
     device::{Device, DeviceOwned, Queue},
     image::{Image, ImageLayout},
-    swapchain::Swapchain,
     DeviceSize, Validated, VulkanError,
 };
 use std::{ops::Range, sync::Arc};
@@ -78,6 +79,7 @@ unsafe impl GpuFuture for NowFuture {
         Err(AccessCheckError::Unknown)
     }
 
+    #[cfg(feature = "swapchain")]
     #[inline]
     fn check_swapchain_image_acquired(
         &self,
@@ -10,12 +10,15 @@
 use super::{AccessCheckError, GpuFuture, SubmitAnyBuilder};
 use crate::{
     buffer::Buffer,
-    command_buffer::{SemaphoreSubmitInfo, SubmitInfo},
+    command_buffer::SemaphoreSubmitInfo,
     device::{Device, DeviceOwned, Queue},
     image::{Image, ImageLayout},
-    swapchain::Swapchain,
-    sync::{future::AccessError, semaphore::Semaphore, PipelineStages},
-    DeviceSize, Validated, ValidationError, VulkanError,
+    sync::{semaphore::Semaphore, PipelineStages},
+    DeviceSize, Validated, VulkanError,
+};
+#[cfg(feature = "swapchain")]
+use crate::{
+    command_buffer::SubmitInfo, swapchain::Swapchain, sync::future::AccessError, ValidationError,
 };
 use parking_lot::Mutex;
 use smallvec::smallvec;
@@ -90,38 +93,31 @@ where
             match self.previous.build_submission()? {
                 SubmitAnyBuilder::Empty => {
                     queue.with(|mut q| {
-                        q.submit_unchecked(
-                            [SubmitInfo {
-                                signal_semaphores: vec![SemaphoreSubmitInfo::semaphore(
-                                    self.semaphore.clone(),
-                                )],
-                                ..Default::default()
-                            }],
-                            None,
-                        )
+                        let mut submit_info = q.recycled_submit_info();
+                        submit_info
+                            .signal_semaphores
+                            .push(SemaphoreSubmitInfo::semaphore(self.semaphore.clone()));
+
+                        q.submit_unchecked([submit_info], None)
                     })?;
                 }
                 SubmitAnyBuilder::SemaphoresWait(semaphores) => {
                     queue.with(|mut q| {
-                        q.submit_unchecked(
-                            [SubmitInfo {
-                                wait_semaphores: semaphores
-                                    .into_iter()
-                                    .map(|semaphore| {
-                                        SemaphoreSubmitInfo {
-                                            // TODO: correct stages ; hard
-                                            stages: PipelineStages::ALL_COMMANDS,
-                                            ..SemaphoreSubmitInfo::semaphore(semaphore)
-                                        }
-                                    })
-                                    .collect(),
-                                signal_semaphores: vec![SemaphoreSubmitInfo::semaphore(
-                                    self.semaphore.clone(),
-                                )],
-                                ..Default::default()
-                            }],
-                            None,
-                        )
+                        let mut submit_info = q.recycled_submit_info();
+                        submit_info
+                            .wait_semaphores
+                            .extend(semaphores.into_iter().map(|semaphore| {
+                                SemaphoreSubmitInfo {
+                                    // TODO: correct stages ; hard
+                                    stages: PipelineStages::ALL_COMMANDS,
+                                    ..SemaphoreSubmitInfo::semaphore(semaphore)
+                                }
+                            }));
+                        submit_info
+                            .signal_semaphores
+                            .push(SemaphoreSubmitInfo::semaphore(self.semaphore.clone()));
+
+                        q.submit_unchecked([submit_info], None)
                     })?;
                 }
                 SubmitAnyBuilder::CommandBuffer(mut submit_info, fence) => {
@@ -141,6 +137,7 @@ where
                                      builder.add_signal_semaphore(&self.semaphore);
                                      builder.submit(&queue)?;*/
                 }
+                #[cfg(feature = "swapchain")]
                 SubmitAnyBuilder::QueuePresent(present_info) => {
                     for swapchain_info in &present_info.swapchain_infos {
                         if swapchain_info.present_id.map_or(false, |present_id| {
@@ -236,6 +233,7 @@ where
             .check_image_access(image, range, exclusive, expected_layout, queue)
     }
 
+    #[cfg(feature = "swapchain")]
     #[inline]
     fn check_swapchain_image_acquired(
         &self,
@@ -550,6 +550,23 @@ vulkan_bitflags! {
             self.intersects(Self::WRITES)
         }
 
+        /// Returns whether `self` includes all accesses of `other`, taking into account that
+        /// `MEMORY_READ` and `MEMORY_WRITE` include all read and write accesses respectively.
+        pub(crate) fn contains_accesses(self, other: Self) -> bool {
+            let this = self.expand();
+            let mut other = other.expand();
+
+            if this.intersects(AccessFlags::MEMORY_READ) {
+                other = other.intersection(Self::WRITES);
+            }
+
+            if this.intersects(AccessFlags::MEMORY_WRITE) {
+                other = other.difference(Self::WRITES);
+            }
+
+            this.contains(other)
+        }
+
         /// Returns whether `self` contains stages that are only available in
         /// `VkAccessFlagBits2`.
         pub(crate) fn contains_flags2(self) -> bool {
@@ -597,7 +614,6 @@ vulkan_bitflags! {
         ///
         /// This may set flags that are not supported by the device, so this is for internal use
         /// only and should not be passed on to Vulkan.
-        #[allow(dead_code)] // TODO: use this function
         pub(crate) fn expand(mut self) -> Self {
             if self.intersects(AccessFlags::SHADER_READ) {
                 self -= AccessFlags::SHADER_READ;
@@ -1406,6 +1422,7 @@ pipeline_stage_access! {
     OpticalFlow_OpticalFlowWrite, OPTICAL_FLOW, OPTICAL_FLOW_WRITE;
     MicromapBuild_MicromapRead, MICROMAP_BUILD, MICROMAP_READ;
     MicromapBuild_MicromapWrite, MICROMAP_BUILD, MICROMAP_WRITE;
+    AllCommands_MemoryWrite, ALL_COMMANDS, MEMORY_WRITE;
 }
 
 impl PipelineStageAccess {
@@ -1710,7 +1727,8 @@ impl PipelineStageAccessFlags {
                 | PipelineStageAccessFlags::AccelerationStructureCopy_AccelerationStructureWrite
                 | PipelineStageAccessFlags::AccelerationStructureCopy_TransferWrite
                 | PipelineStageAccessFlags::OpticalFlow_OpticalFlowWrite
-                | PipelineStageAccessFlags::MicromapBuild_MicromapWrite,
+                | PipelineStageAccessFlags::MicromapBuild_MicromapWrite
+                | PipelineStageAccessFlags::AllCommands_MemoryWrite,
         )
     }
 }
@@ -3923,6 +3941,22 @@ impl ImageMemoryBarrier {
                         }));
                     }
                 }
+                ImageLayout::FragmentShadingRateAttachmentOptimal => {
+                    if !image
+                        .usage()
+                        .intersects(ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT)
+                    {
+                        return Err(Box::new(ValidationError {
+                            problem: "`old_layout` is \
+                                `ImageLayout::FragmentShadingRateAttachmentOptimal`, but \
+                                `image.usage()` does not contain \
+                                `ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT`"
+                                .into(),
+                            vuids: &["VUID-VkImageMemoryBarrier2-oldLayout-02088"],
+                            ..Default::default()
+                        }));
+                    }
+                }
                 ImageLayout::Undefined | ImageLayout::General | ImageLayout::PresentSrc => (),
             }
 
@@ -4038,6 +4072,22 @@ impl ImageMemoryBarrier {
                         }));
                     }
                 }
+                ImageLayout::FragmentShadingRateAttachmentOptimal => {
+                    if !image
+                        .usage()
+                        .intersects(ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT)
+                    {
+                        return Err(Box::new(ValidationError {
+                            problem: "`new_layout` is \
+                                `ImageLayout::FragmentShadingRateAttachmentOptimal`, but \
+                                `image.usage()` does not contain \
+                                `ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT`"
+                                .into(),
+                            vuids: &["VUID-VkImageMemoryBarrier2-oldLayout-02088"],
+                            ..Default::default()
+                        }));
+                    }
+                }
                 ImageLayout::General | ImageLayout::PresentSrc => (),
             }
 
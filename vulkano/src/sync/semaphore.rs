@@ -232,6 +232,7 @@ impl Semaphore {
 
         if let Some(imported_handle_type) = state.current_import {
             match imported_handle_type {
+                #[cfg(feature = "swapchain")]
                 ImportType::SwapchainAcquire => {
                     return Err(Box::new(ValidationError {
                         problem: "the semaphore currently has an imported payload from a \
@@ -422,6 +423,7 @@ impl Semaphore {
 
         if let Some(imported_handle_type) = state.current_import {
             match imported_handle_type {
+                #[cfg(feature = "swapchain")]
                 ImportType::SwapchainAcquire => {
                     return Err(Box::new(ValidationError {
                         problem: "the semaphore currently has an imported payload from a \
@@ -568,6 +570,7 @@ impl Semaphore {
 
         if let Some(imported_handle_type) = state.current_import {
             match imported_handle_type {
+                #[cfg(feature = "swapchain")]
                 ImportType::SwapchainAcquire => {
                     return Err(Box::new(ValidationError {
                         problem: "the semaphore currently has an imported payload from a \
@@ -1602,6 +1605,7 @@ impl SemaphoreState {
         }
     }
 
+    #[cfg(feature = "swapchain")]
     #[inline]
     pub(crate) unsafe fn swapchain_acquire(&mut self) {
         self.pending_signal = Some(SignalType::SwapchainAcquire);
@@ -1613,11 +1617,13 @@ impl SemaphoreState {
 enum SignalType {
     #[allow(dead_code)] // to keep my build system from yelling at me
     Queue(Weak<Queue>),
+    #[cfg(feature = "swapchain")]
     SwapchainAcquire,
 }
 
 #[derive(Clone, Copy, Debug)]
 enum ImportType {
+    #[cfg(feature = "swapchain")]
     SwapchainAcquire,
     ExternalSemaphore(ExternalSemaphoreHandleType),
 }
@@ -14,8 +14,13 @@ macro_rules! instance {
     () => {{
         use crate::{instance::Instance, VulkanLibrary};
 
+        // Without a Vulkan implementation on the system, run the tests against the null driver
+        // if it is available.
         let library = match VulkanLibrary::new() {
             Ok(x) => x,
+            #[cfg(feature = "null_driver")]
+            Err(_) => VulkanLibrary::with_loader(crate::library::null::NullLoader::new()).unwrap(),
+            #[cfg(not(feature = "null_driver"))]
             Err(_) => return,
         };
 
@@ -90,6 +95,26 @@ macro_rules! gfx_dev_and_queue {
     });
 }
 
+/// Returns if `device` is the device of the null driver, which never executes commands. Tests that
+/// read back the results of commands must use this.
+macro_rules! skip_if_null_device {
+    ($device:expr) => {
+        if $device.physical_device().properties().device_name == "vulkano null device" {
+            return;
+        }
+    };
+}
+
+/// Returns if `device` is not the device of the null driver. Tests that inspect the commands
+/// recorded by the null driver must use this, because other devices don't record them.
+macro_rules! skip_unless_null_device {
+    ($device:expr) => {
+        if $device.physical_device().properties().device_name != "vulkano null device" {
+            return;
+        }
+    };
+}
+
 macro_rules! assert_should_panic {
     ($msg:expr, $code:block) => {{
         let res = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| $code));